@@ -0,0 +1,166 @@
+//! Conversions between JSON values and ABI values of function parameters, used to encode and
+//! decode nested structs and arrays with the ABI of a contract.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue, Specifier};
+use alloy_json_abi::{Function, JsonAbi, Param};
+use alloy_primitives::hex;
+use eyre::{Result, WrapErr};
+use serde_json::{Map, Value};
+
+/// Finds a function of the ABI by name, or by signature if the name is overloaded.
+pub(crate) fn find_function<'a>(abi: &'a JsonAbi, function: &str) -> Result<&'a Function> {
+    if function.contains('(') {
+        let signature: String = function.chars().filter(|c| !c.is_whitespace()).collect();
+        return abi
+            .functions()
+            .find(|f| f.signature() == signature)
+            .ok_or_else(|| eyre::eyre!("function `{function}` not found in the ABI"));
+    }
+
+    match abi.function(function).map(Vec::as_slice) {
+        Some([function]) => Ok(function),
+        Some(functions) => {
+            let signatures = functions.iter().map(|f| f.signature()).collect::<Vec<_>>();
+            eyre::bail!(
+                "function `{function}` is overloaded, pass one of its signatures instead: {}",
+                signatures.join(", ")
+            )
+        }
+        None => eyre::bail!("function `{function}` not found in the ABI"),
+    }
+}
+
+/// Converts JSON arguments into values of the parameters.
+///
+/// The arguments are either an array in the order of the parameters or an object keyed by their
+/// names, structs likewise.
+pub(crate) fn values_from_json(params: &[Param], args: &Value) -> Result<Vec<DynSolValue>> {
+    let args = fields(params, args).wrap_err("invalid arguments")?;
+    params
+        .iter()
+        .zip(args)
+        .map(|(param, arg)| {
+            let ty = param.resolve()?;
+            value_from_json(&ty, &param.components, arg)
+                .wrap_err_with(|| format!("invalid value for `{}`", param_name(param)))
+        })
+        .collect()
+}
+
+/// Converts values of the parameters into JSON, as an object keyed by the parameter names if they
+/// are all named, or as an array otherwise.
+pub(crate) fn values_to_json(params: &[Param], values: &[DynSolValue]) -> Value {
+    if params.len() == values.len() && params.iter().all(|param| !param.name.is_empty()) {
+        Value::Object(
+            params
+                .iter()
+                .zip(values)
+                .map(|(param, value)| (param.name.clone(), value_to_json(value, &param.components)))
+                .collect(),
+        )
+    } else {
+        let components = params.iter().map(|param| param.components.as_slice());
+        Value::Array(
+            values
+                .iter()
+                .zip(components.chain(std::iter::repeat(&[][..])))
+                .map(|(value, components)| value_to_json(value, components))
+                .collect(),
+        )
+    }
+}
+
+/// Converts a JSON value into a value of type `ty`, `components` are the struct fields of `ty` or
+/// of its elements.
+fn value_from_json(ty: &DynSolType, components: &[Param], value: &Value) -> Result<DynSolValue> {
+    match ty {
+        DynSolType::Array(inner) => {
+            let items = value.as_array().ok_or_else(|| eyre::eyre!("expected an array"))?;
+            let items = items
+                .iter()
+                .map(|item| value_from_json(inner, components, item))
+                .collect::<Result<_>>()?;
+            Ok(DynSolValue::Array(items))
+        }
+        DynSolType::FixedArray(inner, len) => {
+            let items = value.as_array().ok_or_else(|| eyre::eyre!("expected an array"))?;
+            eyre::ensure!(items.len() == *len, "expected {len} elements, got {}", items.len());
+            let items = items
+                .iter()
+                .map(|item| value_from_json(inner, components, item))
+                .collect::<Result<_>>()?;
+            Ok(DynSolValue::FixedArray(items))
+        }
+        DynSolType::Tuple(types) | DynSolType::CustomStruct { tuple: types, .. } => {
+            let values = fields(components, value)?;
+            let values = types
+                .iter()
+                .zip(components)
+                .zip(values)
+                .map(|((ty, component), value)| {
+                    value_from_json(ty, &component.components, value)
+                        .wrap_err_with(|| format!("invalid value for `{}`", param_name(component)))
+                })
+                .collect::<Result<_>>()?;
+            Ok(DynSolValue::Tuple(values))
+        }
+        _ => {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                _ => eyre::bail!("expected a value of type `{ty}`, got `{value}`"),
+            };
+            Ok(ty.coerce_str(&value)?)
+        }
+    }
+}
+
+/// Converts a value into JSON, structs become objects keyed by the names of their fields.
+fn value_to_json(value: &DynSolValue, components: &[Param]) -> Value {
+    match value {
+        DynSolValue::Tuple(values) | DynSolValue::CustomStruct { tuple: values, .. } => {
+            values_to_json(components, values)
+        }
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            Value::Array(values.iter().map(|value| value_to_json(value, components)).collect())
+        }
+        DynSolValue::Bool(b) => Value::Bool(*b),
+        DynSolValue::Int(i, _) => Value::String(i.to_string()),
+        DynSolValue::Uint(u, _) => Value::String(u.to_string()),
+        DynSolValue::Address(address) => Value::String(address.to_checksum(None)),
+        DynSolValue::Function(function) => Value::String(hex::encode_prefixed(function)),
+        DynSolValue::FixedBytes(word, size) => Value::String(hex::encode_prefixed(&word[..*size])),
+        DynSolValue::Bytes(bytes) => Value::String(hex::encode_prefixed(bytes)),
+        DynSolValue::String(s) => Value::String(s.clone()),
+    }
+}
+
+/// Returns the JSON values of the parameters, from an array or an object keyed by their names.
+fn fields<'a>(params: &[Param], value: &'a Value) -> Result<Vec<&'a Value>> {
+    match value {
+        Value::Array(values) => {
+            eyre::ensure!(
+                values.len() == params.len(),
+                "expected {} values, got {}",
+                params.len(),
+                values.len()
+            );
+            Ok(values.iter().collect())
+        }
+        Value::Object(map) => params.iter().map(|param| field(map, param)).collect(),
+        _ => eyre::bail!("expected an array or an object, got `{value}`"),
+    }
+}
+
+fn field<'a>(map: &'a Map<String, Value>, param: &Param) -> Result<&'a Value> {
+    map.get(&param.name).ok_or_else(|| eyre::eyre!("missing value for `{}`", param_name(param)))
+}
+
+fn param_name(param: &Param) -> &str {
+    if param.name.is_empty() {
+        &param.ty
+    } else {
+        &param.name
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
+    cmd::{interface::load_abi_from_file, proof},
     opts::{Cast as CastArgs, CastSubcommand, ToBaseArgs},
+    signatures::{self, lookup_signatures},
+    storage_layout,
     traces::identifier::SignaturesIdentifier,
     Cast, SimpleCast,
 };
@@ -17,14 +20,13 @@
     fmt::{format_tokens, format_tokens_raw, format_uint_exp},
     fs,
     selectors::{
-        decode_calldata, decode_event_topic, decode_function_selector, decode_selectors,
-        import_selectors, parse_signatures, pretty_calldata, ParsedSignatures, SelectorImportData,
-        SelectorType,
+        decode_selectors, import_selectors, parse_signatures, pretty_calldata, ParsedSignatures,
+        SelectorImportData, SelectorType,
     },
     shell, stdin,
 };
 use foundry_config::Config;
-use std::time::Instant;
+use std::{path::Path, time::Instant};
 
 /// Run the `cast` command-line interface.
 pub fn run() -> Result<()> {
@@ -179,12 +181,23 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
         }
 
         // ABI encoding & decoding
-        CastSubcommand::DecodeAbi { sig, calldata, input } => {
+        CastSubcommand::DecodeAbi { sig, calldata, input, abi, function } => {
+            if let (Some(abi), Some(function)) = (abi, function) {
+                let (abi, _) = load_abi_from_file(&abi, None)?.remove(0);
+                let decoded = SimpleCast::abi_decode_json(&abi, &function, &sig, input)?;
+                sh_println!("{}", serde_json::to_string_pretty(&decoded)?)?;
+                return Ok(());
+            }
+            let calldata = calldata.expect("required without --abi");
             let tokens = SimpleCast::abi_decode(&sig, &calldata, input)?;
             print_tokens(&tokens);
         }
-        CastSubcommand::AbiEncode { sig, packed, args } => {
-            if !packed {
+        CastSubcommand::AbiEncode { sig, packed, args, abi, function } => {
+            if let (Some(abi), Some(function)) = (abi, function) {
+                let (abi, _) = load_abi_from_file(&abi, None)?.remove(0);
+                let args: serde_json::Value = fs::read_json_file(Path::new(&sig))?;
+                sh_println!("{}", SimpleCast::abi_encode_json(&abi, &function, &args)?)?;
+            } else if !packed {
                 sh_println!("{}", SimpleCast::abi_encode(&sig, &args)?)?
             } else {
                 sh_println!("{}", SimpleCast::abi_encode_packed(&sig, &args)?)?
@@ -338,7 +351,8 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
             };
             sh_println!("{number}")?
         }
-        CastSubcommand::Chain { rpc } => {
+        CastSubcommand::Chain { command: Some(command), .. } => command.run().await?,
+        CastSubcommand::Chain { command: None, rpc } => {
             let config = rpc.load_config()?;
             let provider = utils::get_provider(&config)?;
             sh_println!("{}", Cast::new(provider).chain().await?)?
@@ -406,8 +420,22 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
             let provider = utils::get_provider(&config)?;
             sh_println!("{}", Cast::new(provider).gas_price().await?)?;
         }
-        CastSubcommand::Index { key_type, key, slot_number } => {
-            sh_println!("{}", SimpleCast::index(&key_type, &key, &slot_number)?)?;
+        CastSubcommand::Index { args, ty } => {
+            let slot = match ty {
+                Some(ty) => {
+                    let (slot, keys) = args.split_last().expect("at least two arguments");
+                    storage_layout::nested_slot(&ty, keys, slot)?.to_string()
+                }
+                None => {
+                    let [key_type, key, slot_number] = &args[..] else {
+                        eyre::bail!(
+                            "expected the key type, the key and the storage slot of the mapping"
+                        )
+                    };
+                    SimpleCast::index(key_type, key, slot_number)?
+                }
+            };
+            sh_println!("{slot}")?;
         }
         CastSubcommand::IndexErc7201 { id, formula_id } => {
             eyre::ensure!(formula_id == "erc7201", "unsupported formula ID: {formula_id}");
@@ -444,22 +472,36 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
             let who = who.resolve(&provider).await?;
             sh_println!("{}", Cast::new(provider).storage_root(who, slots, block).await?)?;
         }
-        CastSubcommand::Proof { address, slots, rpc, block } => {
+        CastSubcommand::Proof { address, slots, rpc, block, verify } => {
             let config = rpc.load_config()?;
             let provider = utils::get_provider(&config)?;
             let address = address.resolve(&provider).await?;
-            let value = provider
-                .get_proof(address, slots.into_iter().collect())
-                .block_id(block.unwrap_or_default())
-                .await?;
-            sh_println!("{}", serde_json::to_string(&value)?)?;
+            if verify {
+                // pin the block, so that the proof is made against the fetched state root
+                let (number, state_root) =
+                    proof::state_root_at(&provider, block.unwrap_or_default()).await?;
+                let value = provider
+                    .get_proof(address, slots.into_iter().collect())
+                    .block_id(number.into())
+                    .await?;
+                proof::print_checks(&proof::verify_account_proof(&value, state_root))?;
+            } else {
+                let value = provider
+                    .get_proof(address, slots.into_iter().collect())
+                    .block_id(block.unwrap_or_default())
+                    .await?;
+                sh_println!("{}", serde_json::to_string(&value)?)?;
+            }
         }
+        CastSubcommand::VerifyProof(cmd) => cmd.run().await?,
         CastSubcommand::Rpc(cmd) => cmd.run().await?,
         CastSubcommand::Storage(cmd) => cmd.run().await?,
 
         // Calls & transactions
         CastSubcommand::Call(cmd) => cmd.run().await?,
+        CastSubcommand::Multicall(cmd) => cmd.run().await?,
         CastSubcommand::Estimate(cmd) => cmd.run().await?,
+        CastSubcommand::EstimateL1Fee(cmd) => cmd.run().await?,
         CastSubcommand::MakeTx(cmd) => cmd.run().await?,
         CastSubcommand::PublishTx { raw_tx, cast_async, rpc } => {
             let config = rpc.load_config()?;
@@ -486,6 +528,7 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
             )?
         }
         CastSubcommand::Run(cmd) => cmd.run().await?,
+        CastSubcommand::Explain(cmd) => cmd.run().await?,
         CastSubcommand::SendTx(cmd) => cmd.run().await?,
         CastSubcommand::Tx { tx_hash, field, raw, rpc } => {
             let config = rpc.load_config()?;
@@ -498,9 +541,14 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
         }
 
         // 4Byte
-        CastSubcommand::FourByte { selector } => {
+        CastSubcommand::FourByte { selector, sync, offline } => {
+            if sync {
+                let bundle = signatures::sync_bundle().await?;
+                sh_println!("Synced {} selectors into the local signature bundle", bundle.len())?;
+                return Ok(());
+            }
             let selector = stdin::unwrap_line(selector)?;
-            let sigs = decode_function_selector(&selector).await?;
+            let sigs = lookup_signatures(SelectorType::Function, &selector, offline).await?;
             if sigs.is_empty() {
                 eyre::bail!("No matching function signatures found for selector `{selector}`");
             }
@@ -509,11 +557,11 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
             }
         }
 
-        CastSubcommand::FourByteCalldata { calldata } => {
+        CastSubcommand::FourByteCalldata { calldata, offline } => {
             let calldata = stdin::unwrap_line(calldata)?;
 
             if calldata.len() == 10 {
-                let sigs = decode_function_selector(&calldata).await?;
+                let sigs = lookup_signatures(SelectorType::Function, &calldata, offline).await?;
                 if sigs.is_empty() {
                     eyre::bail!("No matching function signatures found for calldata `{calldata}`");
                 }
@@ -523,7 +571,7 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
                 return Ok(());
             }
 
-            let sigs = decode_calldata(&calldata).await?;
+            let sigs = signatures::lookup_calldata_signatures(&calldata, offline).await?;
             sigs.iter().enumerate().for_each(|(i, sig)| {
                 let _ = sh_println!("{}) \"{sig}\"", i + 1);
             });
@@ -541,9 +589,9 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
             print_tokens(&tokens);
         }
 
-        CastSubcommand::FourByteEvent { topic } => {
+        CastSubcommand::FourByteEvent { topic, offline } => {
             let topic = stdin::unwrap_line(topic)?;
-            let sigs = decode_event_topic(&topic).await?;
+            let sigs = lookup_signatures(SelectorType::Event, &topic, offline).await?;
             if sigs.is_empty() {
                 eyre::bail!("No matching event signatures found for topic `{topic}`");
             }
@@ -597,6 +645,7 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
             }
             sh_println!("{address}")?
         }
+        CastSubcommand::Ens { command } => command.run().await?,
 
         // Misc
         CastSubcommand::Keccak { data } => {
@@ -696,15 +745,21 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
         CastSubcommand::Logs(cmd) => cmd.run().await?,
         CastSubcommand::DecodeTransaction { tx } => {
             let tx = stdin::unwrap_line(tx)?;
-            let tx = SimpleCast::decode_raw_transaction(&tx)?;
+            let tx = SimpleCast::decode_raw_transaction_json(&tx)?;
 
             sh_println!("{}", serde_json::to_string_pretty(&tx)?)?
         }
+        CastSubcommand::BuildTransaction { tx } => {
+            let tx = stdin::unwrap(tx, false)?;
+            sh_println!("{}", SimpleCast::build_raw_transaction(&tx)?)?
+        }
         CastSubcommand::DecodeEof { eof } => {
             let eof = stdin::unwrap_line(eof)?;
             sh_println!("{}", SimpleCast::decode_eof(&eof)?)?
         }
         CastSubcommand::TxPool { command } => command.run().await?,
+        CastSubcommand::Blob { command } => command.run()?,
+        CastSubcommand::Bundle { command } => command.run().await?,
     };
 
     /// Prints slice of tokens using [`format_tokens`] or [`format_tokens_raw`] depending whether
@@ -0,0 +1,279 @@
+use alloy_consensus::{
+    utils::WholeFe, Blob, BlobTransactionSidecar, SidecarBuilder, SidecarCoder, SimpleCoder,
+};
+use alloy_eips::eip4844::{builder::PartialSidecar, BYTES_PER_BLOB, FIELD_ELEMENT_BYTES_USIZE};
+use alloy_primitives::{hex, Bytes, B256};
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use foundry_common::{fs, shell, stdin};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// CLI arguments for `cast blob`.
+#[derive(Clone, Debug, Parser)]
+pub enum BlobSubcommands {
+    /// Encode data into blobs, and commit to them.
+    ///
+    /// Prints the blob sidecar as JSON: the blobs, their KZG commitments and proofs, and their
+    /// versioned hashes. It can be sent with `cast send --blob --blob-file`.
+    #[command(visible_alias = "e")]
+    Encode {
+        /// The file with the data to encode. Reads the data from stdin if omitted.
+        file: Option<PathBuf>,
+
+        /// The file to write the sidecar to, instead of printing it.
+        #[arg(long, short)]
+        out: Option<PathBuf>,
+    },
+
+    /// Decode the data of blobs encoded with `cast blob encode`.
+    #[command(visible_alias = "d")]
+    Decode {
+        /// The blobs, as a sidecar JSON, a JSON array of blobs or hex, or a path to a file with
+        /// them. Reads the blobs from stdin if omitted.
+        blobs: Option<String>,
+
+        /// The file to write the raw data to, instead of printing it as hex.
+        #[arg(long, short)]
+        out: Option<PathBuf>,
+    },
+
+    /// Compute the KZG commitments and versioned hashes of blobs.
+    #[command(visible_alias = "c")]
+    Commitment {
+        /// The blobs, as a sidecar JSON, a JSON array of blobs or hex, or a path to a file with
+        /// them. Reads the blobs from stdin if omitted.
+        blobs: Option<String>,
+    },
+
+    /// Compute the KZG proofs of blobs.
+    #[command(visible_alias = "p")]
+    Proof {
+        /// The blobs, as a sidecar JSON, a JSON array of blobs or hex, or a path to a file with
+        /// them. Reads the blobs from stdin if omitted.
+        blobs: Option<String>,
+    },
+}
+
+/// A blob sidecar, with the versioned hashes of its blobs.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SidecarOutput<'a> {
+    #[serde(flatten)]
+    sidecar: &'a BlobTransactionSidecar,
+    versioned_hashes: Vec<B256>,
+}
+
+/// The commitment of a blob, and its proof.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlobCommitment {
+    versioned_hash: B256,
+    commitment: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof: Option<Bytes>,
+}
+
+impl BlobSubcommands {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Self::Encode { file, out } => {
+                let data = match &file {
+                    Some(path) => fs::read(path)?,
+                    None => stdin::read_bytes(false)?,
+                };
+                eyre::ensure!(!data.is_empty(), "no data to encode");
+                let sidecar = SidecarBuilder::<SimpleCoder>::from_slice(&data).build()?;
+                let output = SidecarOutput {
+                    sidecar: &sidecar,
+                    versioned_hashes: sidecar.versioned_hashes().collect(),
+                };
+                let json = serde_json::to_string_pretty(&output)?;
+                match out {
+                    Some(path) => fs::write(path, json)?,
+                    None => sh_println!("{json}")?,
+                }
+            }
+            Self::Decode { blobs, out } => {
+                let blobs = read_blobs(blobs)?;
+                let data = SimpleCoder::default()
+                    .decode_all(&blobs)
+                    .ok_or_else(|| {
+                        eyre::eyre!("the blobs are not encoded with `cast blob encode`")
+                    })?
+                    .concat();
+                match out {
+                    Some(path) => fs::write(path, data)?,
+                    None => sh_println!("{}", hex::encode_prefixed(data))?,
+                }
+            }
+            Self::Commitment { blobs } => {
+                print_commitments(&sidecar_from_blobs(&read_blobs(blobs)?)?, false)?
+            }
+            Self::Proof { blobs } => {
+                print_commitments(&sidecar_from_blobs(&read_blobs(blobs)?)?, true)?
+            }
+        }
+        Ok(())
+    }
+}
+
+fn print_commitments(sidecar: &BlobTransactionSidecar, proofs: bool) -> Result<()> {
+    let commitments = sidecar
+        .commitments
+        .iter()
+        .zip(&sidecar.proofs)
+        .zip(sidecar.versioned_hashes())
+        .map(|((commitment, proof), versioned_hash)| BlobCommitment {
+            versioned_hash,
+            commitment: commitment.to_vec().into(),
+            proof: proofs.then(|| proof.to_vec().into()),
+        })
+        .collect::<Vec<_>>();
+
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string_pretty(&commitments)?)?;
+        return Ok(());
+    }
+    for commitment in commitments {
+        match commitment.proof {
+            Some(proof) => sh_println!("{proof}")?,
+            None => sh_println!("{} {}", commitment.commitment, commitment.versioned_hash)?,
+        }
+    }
+    Ok(())
+}
+
+/// Reads blobs given as a sidecar JSON, a JSON array of blobs or hex, either directly, from a
+/// file, or from stdin.
+pub fn read_blobs(input: Option<String>) -> Result<Vec<Blob>> {
+    let input = match input {
+        Some(input) if Path::new(&input).is_file() => fs::read(input)?,
+        Some(input) => input.into_bytes(),
+        None => stdin::read_bytes(false)?,
+    };
+    parse_blobs(&input)
+}
+
+/// Parses blobs from a sidecar JSON, a JSON array of blobs, hex or raw bytes.
+fn parse_blobs(input: &[u8]) -> Result<Vec<Blob>> {
+    let text = std::str::from_utf8(input).map(str::trim).unwrap_or_default();
+    let blobs = if text.starts_with('{') || text.starts_with('[') {
+        let value: Value = serde_json::from_str(text).wrap_err("invalid blobs JSON")?;
+        let blobs = value.get("blobs").unwrap_or(&value);
+        let blobs = blobs
+            .as_array()
+            .ok_or_else(|| eyre::eyre!("expected an array of blobs or a sidecar"))?;
+        blobs
+            .iter()
+            .map(|blob| {
+                let blob = blob.as_str().ok_or_else(|| eyre::eyre!("expected a hex blob"))?;
+                blob_from_slice(&hex::decode(blob)?)
+            })
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        let data = match hex::decode(text) {
+            Ok(data) if !data.is_empty() => data,
+            _ => input.to_vec(),
+        };
+        eyre::ensure!(
+            !data.is_empty() && data.len() % BYTES_PER_BLOB == 0,
+            "expected blobs of {BYTES_PER_BLOB} bytes, got {} bytes",
+            data.len()
+        );
+        data.chunks(BYTES_PER_BLOB).map(blob_from_slice).collect::<Result<Vec<_>>>()?
+    };
+    eyre::ensure!(!blobs.is_empty(), "no blobs");
+
+    for (i, blob) in blobs.iter().enumerate() {
+        if let Some(fe) =
+            blob.chunks(FIELD_ELEMENT_BYTES_USIZE).position(|fe| WholeFe::new(fe).is_none())
+        {
+            eyre::bail!("blob {i} has an invalid field element at index {fe}");
+        }
+    }
+    Ok(blobs)
+}
+
+fn blob_from_slice(blob: &[u8]) -> Result<Blob> {
+    Blob::try_from(blob)
+        .map_err(|_| eyre::eyre!("expected a blob of {BYTES_PER_BLOB} bytes, got {}", blob.len()))
+}
+
+/// Computes the KZG commitments and proofs of the blobs, whose field elements must be valid.
+pub fn sidecar_from_blobs(blobs: &[Blob]) -> Result<BlobTransactionSidecar> {
+    let mut builder = SidecarBuilder::from_coder_and_capacity(RawBlobCoder, blobs.len());
+    for blob in blobs {
+        builder.ingest(blob.as_slice());
+    }
+    Ok(builder.build()?)
+}
+
+/// Reads the blob sidecar of a transaction from a file.
+///
+/// The file is either a sidecar or blobs as written by `cast blob encode`, whose commitments are
+/// recomputed, or raw data which is encoded into blobs.
+pub fn read_blob_file(path: &Path) -> Result<BlobTransactionSidecar> {
+    let content = fs::read(path)?;
+    let is_json = std::str::from_utf8(&content)
+        .is_ok_and(|text| text.trim_start().starts_with('{') || text.trim_start().starts_with('['));
+    if is_json {
+        return sidecar_from_blobs(&parse_blobs(&content)?);
+    }
+    Ok(SidecarBuilder::<SimpleCoder>::from_slice(&content).build()?)
+}
+
+/// Copies whole blobs into the sidecar, to commit to blobs which are already encoded.
+#[derive(Clone, Copy, Debug, Default)]
+struct RawBlobCoder;
+
+impl SidecarCoder for RawBlobCoder {
+    fn required_fe(&self, data: &[u8]) -> usize {
+        data.len().div_ceil(FIELD_ELEMENT_BYTES_USIZE)
+    }
+
+    fn code(&mut self, builder: &mut PartialSidecar, data: &[u8]) {
+        for fe in data.chunks(FIELD_ELEMENT_BYTES_USIZE) {
+            builder.ingest_valid_fe(WholeFe::new(fe).expect("field elements are validated"));
+        }
+    }
+
+    fn finish(self, _builder: &mut PartialSidecar) {}
+
+    fn decode_all(&mut self, blobs: &[Blob]) -> Option<Vec<Vec<u8>>> {
+        Some(blobs.iter().map(|blob| blob.to_vec()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_and_decodes_blobs() {
+        let data = b"hello blobs".repeat(20_000);
+        let sidecar = SidecarBuilder::<SimpleCoder>::from_slice(&data).build().unwrap();
+        assert_eq!(sidecar.blobs.len(), 2);
+
+        let output = SidecarOutput {
+            sidecar: &sidecar,
+            versioned_hashes: sidecar.versioned_hashes().collect(),
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        let blobs = parse_blobs(json.as_bytes()).unwrap();
+        assert_eq!(blobs, sidecar.blobs);
+        assert_eq!(SimpleCoder::default().decode_all(&blobs).unwrap().concat(), data);
+
+        // the commitments of already encoded blobs match
+        assert_eq!(sidecar_from_blobs(&blobs).unwrap(), sidecar);
+
+        let hex = hex::encode(blobs[0].as_slice());
+        assert_eq!(parse_blobs(hex.as_bytes()).unwrap(), blobs[..1]);
+        assert!(parse_blobs(b"0x1234").is_err());
+
+        let mut invalid = blobs[0];
+        invalid[0] = 0xff;
+        assert!(parse_blobs(hex::encode(invalid.as_slice()).as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,472 @@
+use crate::{
+    cmd::send::{build_batch, BatchTx},
+    tx,
+};
+use alloy_network::{eip2718::Encodable2718, EthereumWallet, TransactionBuilder};
+use alloy_primitives::{
+    hex, keccak256,
+    utils::{format_units, Unit},
+    Address, Bytes, B256, U256, U64,
+};
+use alloy_provider::Provider;
+use alloy_signer::Signer;
+use alloy_signer_local::PrivateKeySigner;
+use clap::Parser;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Cell, Table};
+use eyre::{Result, WrapErr};
+use foundry_cli::{
+    opts::{EthereumOpts, TransactionOpts},
+    utils::{self, LoadConfig},
+};
+use foundry_common::shell;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+
+/// The Flashbots relay.
+const FLASHBOTS_RELAY_URL: &str = "https://relay.flashbots.net";
+
+/// CLI arguments for `cast bundle`.
+#[derive(Debug, Parser)]
+pub enum BundleSubcommands {
+    /// Simulate a bundle on top of the latest block with `eth_callBundle`, or `mev_simBundle`
+    /// with `--mev-share`.
+    ///
+    /// Prints the result and the coinbase payment of each transaction.
+    #[command(visible_alias = "sim")]
+    Simulate {
+        #[command(flatten)]
+        args: BundleArgs,
+    },
+    /// Send a bundle to the relay with `eth_sendBundle`, or `mev_sendBundle` with `--mev-share`.
+    Send {
+        #[command(flatten)]
+        args: BundleArgs,
+
+        /// The number of consecutive blocks to target, starting from the target block.
+        #[arg(long, default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+        blocks: u64,
+    },
+}
+
+#[derive(Debug, Parser)]
+pub struct BundleArgs {
+    /// The transactions of the bundle, in order.
+    ///
+    /// Either signed raw transactions, or calls as `TO [SIG] [ARGS...]` which are signed by the
+    /// wallet with sequential nonces.
+    #[arg(required = true, value_name = "TX")]
+    txs: Vec<String>,
+
+    /// The URL of the relay.
+    #[arg(long, env = "BUNDLE_RELAY_URL", default_value = FLASHBOTS_RELAY_URL)]
+    relay: String,
+
+    /// Use the MEV-Share bundle API of the relay.
+    #[arg(long)]
+    mev_share: bool,
+
+    /// The block to target, defaults to the next block.
+    #[arg(long)]
+    target_block: Option<u64>,
+
+    /// The private key authenticating the requests to the relay with the `X-Flashbots-Signature`
+    /// header, defaults to a random key.
+    ///
+    /// It identifies the searcher to the relay, and doesn't need to hold funds.
+    #[arg(long, env = "FLASHBOTS_AUTH_KEY", value_name = "KEY")]
+    auth_key: Option<String>,
+
+    #[command(flatten)]
+    tx: TransactionOpts,
+
+    #[command(flatten)]
+    eth: EthereumOpts,
+}
+
+impl BundleSubcommands {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Self::Simulate { args } => {
+                let (relay, txs, block) = args.prepare().await?;
+                if relay.mev_share {
+                    let sim: SimBundleResponse = relay
+                        .request("mev_simBundle", mev_share_bundle(&txs, block, block))
+                        .await?;
+                    print_sim_bundle(&sim)
+                } else {
+                    let params = json!({
+                        "txs": txs,
+                        "blockNumber": U64::from(block),
+                        "stateBlockNumber": "latest",
+                    });
+                    let call: CallBundleResponse = relay.request("eth_callBundle", params).await?;
+                    print_call_bundle(&call)
+                }
+            }
+            Self::Send { args, blocks } => {
+                let (relay, txs, block) = args.prepare().await?;
+                let last_block = block + blocks - 1;
+                let mut sent = Vec::new();
+                if relay.mev_share {
+                    let bundle = mev_share_bundle(&txs, block, last_block);
+                    let response: SendBundleResponse =
+                        relay.request("mev_sendBundle", bundle).await?;
+                    sent.push(SentBundle { block, bundle_hash: response.bundle_hash });
+                } else {
+                    for block in block..=last_block {
+                        let params = json!({ "txs": txs, "blockNumber": U64::from(block) });
+                        let response: SendBundleResponse =
+                            relay.request("eth_sendBundle", params).await?;
+                        sent.push(SentBundle { block, bundle_hash: response.bundle_hash });
+                    }
+                }
+
+                if shell::is_json() {
+                    sh_println!("{}", serde_json::to_string_pretty(&sent)?)?;
+                } else if relay.mev_share {
+                    sh_println!(
+                        "bundle {} sent for blocks {block} to {last_block}",
+                        sent[0].bundle_hash
+                    )?;
+                } else {
+                    for SentBundle { block, bundle_hash } in &sent {
+                        sh_println!("bundle {bundle_hash} sent for block {block}")?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl BundleArgs {
+    /// Signs the transactions of the bundle and resolves the target block.
+    async fn prepare(self) -> Result<(Relay, Vec<Bytes>, u64)> {
+        let Self { txs, relay, mev_share, target_block, auth_key, tx, eth } = self;
+
+        let config = eth.load_config()?;
+        let provider = utils::get_provider(&config)?;
+
+        let txs = txs.iter().map(|tx| tx.parse()).collect::<Result<Vec<BundleTx>>>()?;
+        let calls = txs
+            .iter()
+            .filter_map(|tx| match tx {
+                BundleTx::Call(call) => Some(call.clone()),
+                BundleTx::Raw(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let mut signed_calls = Vec::with_capacity(calls.len());
+        if !calls.is_empty() {
+            let signer = eth.wallet.signer().await?;
+            let from = signer.address();
+            tx::validate_from_address(eth.wallet.from, from)?;
+
+            let requests = build_batch(&provider, &config, tx, &calls, from, Some(&signer)).await?;
+            let wallet = EthereumWallet::from(signer);
+            for request in requests {
+                let signed = request.build(&wallet).await?;
+                signed_calls.push(Bytes::from(signed.encoded_2718()));
+            }
+        }
+        let mut signed_calls = signed_calls.into_iter();
+        let txs = txs
+            .into_iter()
+            .map(|tx| match tx {
+                BundleTx::Raw(raw) => raw,
+                BundleTx::Call(_) => signed_calls.next().expect("signed all calls"),
+            })
+            .collect();
+
+        let block = match target_block {
+            Some(block) => block,
+            None => provider.get_block_number().await? + 1,
+        };
+
+        let signer = match auth_key {
+            Some(key) => key.parse().wrap_err("invalid auth key")?,
+            None => PrivateKeySigner::random_with(&mut rand::thread_rng()),
+        };
+        Ok((Relay { url: relay, signer, mev_share }, txs, block))
+    }
+}
+
+/// A transaction of a bundle.
+#[derive(Clone, Debug)]
+enum BundleTx {
+    /// A signed raw transaction.
+    Raw(Bytes),
+    /// A call to sign.
+    Call(BatchTx),
+}
+
+impl FromStr for BundleTx {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // anything longer than an address is a raw transaction
+        if let Ok(raw) = hex::decode(s.trim()) {
+            if raw.len() > 20 {
+                return Ok(Self::Raw(raw.into()));
+            }
+        }
+        s.parse().map(Self::Call).wrap_err_with(|| format!("invalid bundle transaction `{s}`"))
+    }
+}
+
+/// A relay accepting bundles.
+struct Relay {
+    url: String,
+    /// The signer of the `X-Flashbots-Signature` header.
+    signer: PrivateKeySigner,
+    mev_share: bool,
+}
+
+impl Relay {
+    /// Sends a JSON-RPC request to the relay, authenticated with the `X-Flashbots-Signature`
+    /// header.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T> {
+        let body =
+            json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": [params] }).to_string();
+        let response = reqwest::Client::new()
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .header("X-Flashbots-Signature", self.signature(&body).await?)
+            .body(body)
+            .send()
+            .await
+            .wrap_err_with(|| format!("failed to reach the relay at {}", self.url))?
+            .text()
+            .await?;
+        let response: serde_json::Value = serde_json::from_str(&response)
+            .wrap_err_with(|| format!("invalid response from the relay: {response}"))?;
+        if let Some(error) = response.get("error") {
+            let message = error.get("message").and_then(|message| message.as_str());
+            eyre::bail!("relay error: {}", message.map_or_else(|| error.to_string(), String::from));
+        }
+        serde_json::from_value(response["result"].clone())
+            .wrap_err_with(|| format!("unexpected {method} result: {}", response["result"]))
+    }
+
+    /// Returns the `X-Flashbots-Signature` header of a request body, the address of the signer
+    /// and its signature of the hex encoded hash of the body.
+    async fn signature(&self, body: &str) -> Result<String> {
+        let hash = keccak256(body).to_string();
+        let signature = self.signer.sign_message(hash.as_bytes()).await?;
+        Ok(format!("{}:{}", self.signer.address(), hex::encode_prefixed(signature.as_bytes())))
+    }
+}
+
+/// Returns the parameters of a MEV-Share bundle, which can't revert.
+fn mev_share_bundle(txs: &[Bytes], block: u64, max_block: u64) -> serde_json::Value {
+    json!({
+        "version": "v0.1",
+        "inclusion": { "block": U64::from(block), "maxBlock": U64::from(max_block) },
+        "body": txs.iter().map(|tx| json!({ "tx": tx, "canRevert": false })).collect::<Vec<_>>(),
+    })
+}
+
+/// The result of `eth_callBundle`, with values in wei as decimal strings.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallBundleResponse {
+    bundle_hash: B256,
+    #[serde(default)]
+    bundle_gas_price: Option<String>,
+    coinbase_diff: String,
+    eth_sent_to_coinbase: String,
+    gas_fees: String,
+    results: Vec<CallBundleTxResult>,
+    state_block_number: u64,
+    total_gas_used: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallBundleTxResult {
+    tx_hash: B256,
+    from_address: Address,
+    #[serde(default)]
+    to_address: Option<Address>,
+    gas_used: u64,
+    gas_price: String,
+    coinbase_diff: String,
+    eth_sent_to_coinbase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    revert: Option<String>,
+}
+
+/// The result of `mev_simBundle`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimBundleResponse {
+    success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    state_block: U64,
+    mev_gas_price: U256,
+    profit: U256,
+    refundable_value: U256,
+    gas_used: U64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SendBundleResponse {
+    bundle_hash: B256,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SentBundle {
+    block: u64,
+    bundle_hash: B256,
+}
+
+fn print_call_bundle(call: &CallBundleResponse) -> Result<()> {
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string_pretty(call)?)?;
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.apply_modifier(UTF8_ROUND_CORNERS);
+    table.set_header(vec![
+        Cell::new("Tx Hash"),
+        Cell::new("From"),
+        Cell::new("To"),
+        Cell::new("Gas Used"),
+        Cell::new("Gas Price (gwei)"),
+        Cell::new("Coinbase Payment (ETH)"),
+        Cell::new("Status"),
+    ]);
+    for result in &call.results {
+        let status = match (&result.error, &result.revert) {
+            (_, Some(revert)) => format!("reverted: {revert}"),
+            (Some(error), None) => format!("failed: {error}"),
+            (None, None) => "success".to_string(),
+        };
+        table.add_row([
+            result.tx_hash.to_string(),
+            result.from_address.to_string(),
+            result.to_address.map(|to| to.to_string()).unwrap_or_default(),
+            result.gas_used.to_string(),
+            format_wei(&result.gas_price, Unit::GWEI),
+            format_wei(&result.eth_sent_to_coinbase, Unit::ETHER),
+            status,
+        ]);
+    }
+    sh_println!("\n{table}\n")?;
+
+    sh_println!("bundle hash: {}", call.bundle_hash)?;
+    sh_println!("state block: {}", call.state_block_number)?;
+    sh_println!("total gas used: {}", call.total_gas_used)?;
+    if let Some(gas_price) = &call.bundle_gas_price {
+        sh_println!("bundle gas price: {} gwei", format_wei(gas_price, Unit::GWEI))?;
+    }
+    sh_println!("coinbase payment: {} ETH", format_wei(&call.eth_sent_to_coinbase, Unit::ETHER))?;
+    sh_println!("coinbase diff: {} ETH", format_wei(&call.coinbase_diff, Unit::ETHER))?;
+    Ok(())
+}
+
+fn print_sim_bundle(sim: &SimBundleResponse) -> Result<()> {
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string_pretty(sim)?)?;
+        return Ok(());
+    }
+    match &sim.error {
+        Some(error) => sh_println!("success: {} ({error})", sim.success)?,
+        None => sh_println!("success: {}", sim.success)?,
+    }
+    sh_println!("state block: {}", sim.state_block)?;
+    sh_println!("gas used: {}", sim.gas_used)?;
+    sh_println!("mev gas price: {} gwei", format_units(sim.mev_gas_price, Unit::GWEI)?)?;
+    sh_println!("coinbase profit: {} ETH", format_units(sim.profit, Unit::ETHER)?)?;
+    sh_println!("refundable value: {} ETH", format_units(sim.refundable_value, Unit::ETHER)?)?;
+    Ok(())
+}
+
+/// Formats a value in wei as a decimal string in the unit, or returns it as is if it's invalid.
+fn format_wei(value: &str, unit: Unit) -> String {
+    U256::from_str(value)
+        .ok()
+        .and_then(|value| format_units(value, unit).ok())
+        .unwrap_or_else(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bundle_txs() {
+        let raw = format!("0x02{}", "00".repeat(40));
+        assert!(matches!(raw.parse::<BundleTx>().unwrap(), BundleTx::Raw(_)));
+
+        let call = "0x0000000000000000000000000000000000001234 transfer(address,uint256) 0x0000000000000000000000000000000000000001 1"
+            .parse::<BundleTx>()
+            .unwrap();
+        let BundleTx::Call(call) = call else { panic!("expected a call") };
+        assert_eq!(call.to, "0x0000000000000000000000000000000000001234");
+        assert_eq!(call.sig.as_deref(), Some("transfer(address,uint256)"));
+        assert_eq!(call.args.len(), 2);
+
+        let transfer = "0x0000000000000000000000000000000000001234".parse::<BundleTx>().unwrap();
+        assert!(matches!(transfer, BundleTx::Call(_)));
+    }
+
+    #[tokio::test]
+    async fn signs_relay_requests() {
+        let relay = Relay {
+            url: FLASHBOTS_RELAY_URL.to_string(),
+            signer: "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+                .parse()
+                .unwrap(),
+            mev_share: false,
+        };
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"eth_sendBundle","params":[]}"#;
+        let header = relay.signature(body).await.unwrap();
+        let (address, signature) = header.split_once(':').unwrap();
+        assert_eq!(address, "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+        let signature = alloy_primitives::PrimitiveSignature::from_str(signature).unwrap();
+        let hash = keccak256(body).to_string();
+        let recovered = signature.recover_address_from_msg(hash.as_bytes()).unwrap();
+        assert_eq!(recovered.to_string(), address);
+    }
+
+    #[test]
+    fn decodes_call_bundle_results() {
+        let call: CallBundleResponse = serde_json::from_value(json!({
+            "bundleGasPrice": "476190476193",
+            "bundleHash": "0x73b1e258c7a42fd0230b2fd05529c5d4b6fcb66c227783f8bece8aeacdd1db2e",
+            "coinbaseDiff": "20000000000126000",
+            "ethSentToCoinbase": "20000000000000000",
+            "gasFees": "126000",
+            "results": [{
+                "coinbaseDiff": "10000000000063000",
+                "ethSentToCoinbase": "10000000000000000",
+                "fromAddress": "0x02A727155aeF8609c9f7F2179b2a1f560B39F5A0",
+                "gasFees": "63000",
+                "gasPrice": "476190476193",
+                "gasUsed": 21000,
+                "toAddress": "0x73625f59CAdc5009Cb458B751b3E7b6b48C06f2C",
+                "txHash": "0x669b4704a7d993a946cdd6e2f95233f308ce0c4649d2e04944e8299efcaa098a",
+                "value": "0x"
+            }],
+            "stateBlockNumber": 5221585,
+            "totalGasUsed": 42000
+        }))
+        .unwrap();
+        assert_eq!(call.results.len(), 1);
+        assert_eq!(call.results[0].gas_used, 21000);
+        assert_eq!(format_wei(&call.eth_sent_to_coinbase, Unit::ETHER), "0.020000000000000000");
+        assert_eq!(format_wei("invalid", Unit::ETHER), "invalid");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    state_override::StateOverrideArgs,
     traces::TraceKind,
     tx::{CastTxBuilder, SenderKind},
     Cast,
@@ -89,7 +90,13 @@ pub struct CallArgs {
     #[command(flatten)]
     eth: EthereumOpts,
 
+    #[command(flatten)]
+    state_override: StateOverrideArgs,
+
     /// Use current project artifacts for trace decoding.
+    ///
+    /// Contracts that already exist are identified by matching their code against the deployed
+    /// bytecode of the artifacts.
     #[arg(long, visible_alias = "la")]
     pub with_local_artifacts: bool,
 }
@@ -139,6 +146,7 @@ pub async fn run(self) -> Result<()> {
             labels,
             data,
             with_local_artifacts,
+            state_override,
             ..
         } = self;
 
@@ -200,6 +208,7 @@ pub async fn run(self) -> Result<()> {
                 .with_state_changes(shell::verbosity() > 4);
             let mut executor =
                 TracingExecutor::new(env, fork, evm_version, trace_mode, odyssey, create2_deployer);
+            state_override.apply(&mut executor)?;
 
             let value = tx.value.unwrap_or_default();
             let input = tx.inner.input.into_input().unwrap_or_default();
@@ -214,7 +223,8 @@ pub async fn run(self) -> Result<()> {
                     executor.transact_raw(from, to, input, value)?,
                     TraceKind::Execution,
                 ),
-            };
+            }
+            .with_runtime_codes(&executor);
 
             handle_traces(
                 trace,
@@ -230,7 +240,12 @@ pub async fn run(self) -> Result<()> {
             return Ok(());
         }
 
-        sh_println!("{}", Cast::new(provider).call(&tx, func.as_ref(), block).await?)?;
+        sh_println!(
+            "{}",
+            Cast::new(provider)
+                .call(&tx, func.as_ref(), block, state_override.state_override()?)
+                .await?
+        )?;
 
         Ok(())
     }
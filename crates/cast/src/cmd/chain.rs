@@ -0,0 +1,65 @@
+use alloy_provider::Provider;
+use clap::Parser;
+use eyre::Result;
+use foundry_cli::{
+    opts::RpcOpts,
+    utils::{self, find_chain, find_chain_by_id, LoadConfig},
+};
+use foundry_common::shell;
+
+/// CLI arguments for `cast chain`.
+#[derive(Debug, Parser, Clone)]
+pub enum ChainSubcommands {
+    /// Show the details of a chain from the bundled chain registry: its RPC URLs, explorers,
+    /// native currency and supported EIPs.
+    Info {
+        /// The chain name or ID, defaults to the chain of the RPC endpoint.
+        chain: Option<String>,
+
+        #[command(flatten)]
+        rpc: RpcOpts,
+    },
+}
+
+impl ChainSubcommands {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Self::Info { chain, rpc } => {
+                let info = match chain {
+                    Some(chain) => find_chain(&chain)
+                        .ok_or_else(|| eyre::eyre!("chain `{chain}` not found in the registry"))?,
+                    None => {
+                        let provider = utils::get_provider(&rpc.load_config()?)?;
+                        let id = provider.get_chain_id().await?;
+                        find_chain_by_id(id)
+                            .ok_or_else(|| eyre::eyre!("chain {id} not found in the registry"))?
+                    }
+                };
+
+                if shell::is_json() {
+                    sh_println!("{}", serde_json::to_string_pretty(info)?)?;
+                    return Ok(());
+                }
+                let currency = &info.native_currency;
+                sh_println!("name: {}", info.name)?;
+                sh_println!("chain id: {}", info.chain_id)?;
+                sh_println!("short name: {}", info.short_name)?;
+                sh_println!(
+                    "currency: {} ({}, {} decimals)",
+                    currency.name,
+                    currency.symbol,
+                    currency.decimals
+                )?;
+                sh_println!("EIP-1559: {}", info.supports("1559"))?;
+                sh_println!("EIP-4844: {}", info.supports("4844"))?;
+                for url in &info.rpc {
+                    sh_println!("rpc: {url}")?;
+                }
+                for explorer in &info.explorers {
+                    sh_println!("explorer: {} ({})", explorer.url, explorer.name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use alloy_network::TransactionBuilder;
+use alloy_primitives::{Address, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::TransactionRequest;
+use alloy_serde::WithOtherFields;
+use alloy_sol_types::{sol, SolCall};
+use clap::Parser;
+use eyre::Result;
+use foundry_cli::{
+    opts::RpcOpts,
+    utils::{self, LoadConfig},
+};
+use foundry_common::{
+    ens::{decode_contenthash, ProviderEnsExt},
+    provider::RetryProvider,
+};
+
+sol! {
+    interface IERC721Metadata {
+        function tokenURI(uint256 tokenId) external view returns (string);
+    }
+
+    interface IERC1155MetadataURI {
+        function uri(uint256 id) external view returns (string);
+    }
+}
+
+/// CLI arguments for `cast ens`.
+#[derive(Debug, Parser, Clone)]
+pub enum EnsSubcommands {
+    /// Get a text record of an ENS name, e.g. `url`, `email` or `com.github`.
+    Text {
+        /// The ENS name.
+        name: String,
+
+        /// The key of the record.
+        key: String,
+
+        #[command(flatten)]
+        rpc: RpcOpts,
+    },
+    /// Get the avatar of an ENS name.
+    ///
+    /// NFT avatars, like `eip155:1/erc721:0x.../1`, are resolved to the URI of the token metadata
+    /// if the token is on the same chain.
+    Avatar {
+        /// The ENS name.
+        name: String,
+
+        #[command(flatten)]
+        rpc: RpcOpts,
+    },
+    /// Get the content hash of an ENS name, decoded as a URI like `ipfs://Qm...`.
+    #[command(visible_alias = "ch")]
+    Contenthash {
+        /// The ENS name.
+        name: String,
+
+        /// Print the raw content hash.
+        #[arg(long)]
+        raw: bool,
+
+        #[command(flatten)]
+        rpc: RpcOpts,
+    },
+}
+
+impl EnsSubcommands {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Self::Text { name, key, rpc } => {
+                let provider = utils::get_provider(&rpc.load_config()?)?;
+                let text = provider.get_text(&name, &key).await?;
+                eyre::ensure!(!text.is_empty(), "no `{key}` record set for {name}");
+                sh_println!("{text}")?;
+            }
+            Self::Avatar { name, rpc } => {
+                let provider = utils::get_provider(&rpc.load_config()?)?;
+                let avatar = provider.get_text(&name, "avatar").await?;
+                eyre::ensure!(!avatar.is_empty(), "no avatar set for {name}");
+                sh_println!("{avatar}")?;
+                if let Some(nft) = NftAvatar::parse(&avatar) {
+                    if nft.chain_id == provider.get_chain_id().await? {
+                        sh_println!("token URI: {}", nft.token_uri(&provider).await?)?;
+                    }
+                }
+            }
+            Self::Contenthash { name, raw, rpc } => {
+                let provider = utils::get_provider(&rpc.load_config()?)?;
+                let hash = provider.get_contenthash(&name).await?;
+                eyre::ensure!(!hash.is_empty(), "no content hash set for {name}");
+                match decode_contenthash(&hash) {
+                    Some(uri) if !raw => sh_println!("{uri}")?,
+                    _ => sh_println!("{hash}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An NFT avatar as specified in [ENSIP-12](https://docs.ens.domains/ensip/12), e.g.
+/// `eip155:1/erc721:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/2430`.
+#[derive(Debug, PartialEq, Eq)]
+struct NftAvatar {
+    chain_id: u64,
+    erc1155: bool,
+    contract: Address,
+    token_id: U256,
+}
+
+impl NftAvatar {
+    fn parse(avatar: &str) -> Option<Self> {
+        let (chain, rest) = avatar.strip_prefix("eip155:")?.split_once('/')?;
+        let (standard, rest) = rest.split_once(':')?;
+        let (contract, token_id) = rest.split_once('/')?;
+        let erc1155 = match standard.to_lowercase().as_str() {
+            "erc721" => false,
+            "erc1155" => true,
+            _ => return None,
+        };
+        Some(Self {
+            chain_id: chain.parse().ok()?,
+            erc1155,
+            contract: contract.parse().ok()?,
+            token_id: token_id.parse().ok()?,
+        })
+    }
+
+    /// Returns the metadata URI of the token, with the `{id}` of ERC-1155 URIs substituted.
+    async fn token_uri(&self, provider: &RetryProvider) -> Result<String> {
+        let input = if self.erc1155 {
+            IERC1155MetadataURI::uriCall { id: self.token_id }.abi_encode()
+        } else {
+            IERC721Metadata::tokenURICall { tokenId: self.token_id }.abi_encode()
+        };
+        let tx = TransactionRequest::default().with_to(self.contract).with_input(input);
+        let output = provider.call(WithOtherFields::new(tx)).await?;
+        if self.erc1155 {
+            let uri = IERC1155MetadataURI::uriCall::abi_decode_returns(&output, false)?._0;
+            Ok(uri.replace("{id}", &format!("{:064x}", self.token_id)))
+        } else {
+            Ok(IERC721Metadata::tokenURICall::abi_decode_returns(&output, false)?._0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn parses_nft_avatars() {
+        assert_eq!(
+            NftAvatar::parse("eip155:1/erc721:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/2430"),
+            Some(NftAvatar {
+                chain_id: 1,
+                erc1155: false,
+                contract: address!("b47e3cd837dDF8e4c57F05d70Ab865de6e193BBB"),
+                token_id: U256::from(2430),
+            })
+        );
+        assert!(NftAvatar::parse(
+            "eip155:1/erc1155:0x495f947276749ce646f68ac8c248420045cb7b5e/8112316025873927737505937898915153732580103913704334048512380490797008551937"
+        )
+        .is_some_and(|nft| nft.erc1155));
+        assert_eq!(NftAvatar::parse("https://example.com/avatar.png"), None);
+        assert_eq!(
+            NftAvatar::parse("eip155:1/erc20:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/1"),
+            None
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::tx::{CastTxBuilder, SenderKind};
+use crate::tx::{estimate_access_list, CastTxBuilder, SenderKind};
 use alloy_primitives::U256;
 use alloy_provider::Provider;
 use alloy_rpc_types::BlockId;
@@ -8,7 +8,7 @@
     opts::{EthereumOpts, TransactionOpts},
     utils::{self, parse_ether_value, LoadConfig},
 };
-use foundry_common::ens::NameOrAddress;
+use foundry_common::{ens::NameOrAddress, shell};
 use std::str::FromStr;
 
 /// CLI arguments for `cast estimate`.
@@ -30,6 +30,11 @@ pub struct EstimateArgs {
     #[arg(long, short = 'B')]
     block: Option<BlockId>,
 
+    /// Create the access list of the transaction, and compare the gas estimates without and with
+    /// it applied.
+    #[arg(long, conflicts_with = "access_list")]
+    with_access_list: bool,
+
     #[command(subcommand)]
     command: Option<EstimateSubcommands>,
 
@@ -66,7 +71,7 @@ pub enum EstimateSubcommands {
 
 impl EstimateArgs {
     pub async fn run(self) -> Result<()> {
-        let Self { to, mut sig, mut args, mut tx, block, eth, command } = self;
+        let Self { to, mut sig, mut args, mut tx, block, with_access_list, eth, command } = self;
 
         let config = eth.load_config()?;
         let provider = utils::get_provider(&config)?;
@@ -98,6 +103,26 @@ pub async fn run(self) -> Result<()> {
             .build_raw(sender)
             .await?;
 
+        if with_access_list {
+            let estimate = estimate_access_list(&provider, &tx, block.unwrap_or_default()).await?;
+            if shell::is_json() {
+                sh_println!("{}", serde_json::to_string_pretty(&estimate)?)?;
+                return Ok(());
+            }
+            sh_println!("gas without access list: {}", estimate.gas)?;
+            sh_println!("gas with access list: {}", estimate.gas_with_access_list)?;
+            let delta = estimate.delta();
+            if delta > 0 {
+                sh_println!("the access list saves {delta} gas")?;
+            } else if delta < 0 {
+                sh_println!("the access list costs {} more gas", -delta)?;
+            } else {
+                sh_println!("the access list does not change the gas")?;
+            }
+            sh_println!("access list: {}", serde_json::to_string(&estimate.access_list)?)?;
+            return Ok(());
+        }
+
         let gas = provider.estimate_gas(tx).block(block.unwrap_or_default()).await?;
         sh_println!("{gas}")?;
         Ok(())
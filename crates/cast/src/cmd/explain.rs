@@ -0,0 +1,365 @@
+use crate::{cmd::run::RunArgs, IERC20};
+use alloy_network::TransactionResponse;
+use alloy_primitives::{
+    utils::{format_units, Unit},
+    Address, TxHash, U256,
+};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockId;
+use alloy_sol_types::SolEvent;
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use foundry_cli::{
+    opts::{EtherscanOpts, RpcOpts},
+    utils,
+};
+use foundry_common::{
+    compile::ProjectCompiler, contracts::get_contract_name, ens::ProviderEnsExt,
+    provider::RetryProvider, ContractsByArtifact,
+};
+use foundry_config::Config;
+use foundry_evm::traces::{
+    decode_trace_arena,
+    identifier::{SignaturesIdentifier, TraceIdentifiers},
+    CallKind, CallTrace, CallTraceDecoder, CallTraceDecoderBuilder,
+};
+use std::collections::HashMap;
+
+/// CLI arguments for `cast explain`.
+#[derive(Clone, Debug, Parser)]
+pub struct ExplainArgs {
+    /// The transaction hash.
+    tx_hash: String,
+
+    /// Executes the transaction only with the state from the previous block.
+    ///
+    /// May result in different calls than the live execution, the events and token transfers are
+    /// always read from the receipt.
+    #[arg(long)]
+    quick: bool,
+
+    /// Label addresses in the explanation.
+    ///
+    /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:vitalik.eth
+    #[arg(long, short)]
+    label: Vec<String>,
+
+    /// Use current project artifacts for decoding.
+    #[arg(long, visible_alias = "la")]
+    with_local_artifacts: bool,
+
+    #[command(flatten)]
+    etherscan: EtherscanOpts,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+impl ExplainArgs {
+    /// Replays the transaction to collect its calls, decodes them and the events of its receipt
+    /// with the ABIs from Etherscan and the local project, and prints what the transaction did.
+    pub async fn run(self) -> Result<()> {
+        let Self { tx_hash, quick, label, with_local_artifacts, etherscan, rpc } = self;
+        let tx_hash: TxHash = tx_hash.parse().wrap_err("invalid tx hash")?;
+
+        let run = RunArgs {
+            tx_hash: tx_hash.to_string(),
+            debug: false,
+            decode_internal: false,
+            trace_printer: false,
+            quick,
+            label: label.clone(),
+            etherscan,
+            rpc,
+            state_override: Default::default(),
+            evm_version: None,
+            compute_units_per_second: None,
+            no_rate_limit: false,
+            odyssey: false,
+            with_local_artifacts,
+            disable_block_gas_limit: false,
+        };
+        let (result, config, chain) = run.replay().await?;
+
+        let provider = utils::get_provider(&config)?;
+        let tx = provider
+            .get_transaction_by_hash(tx_hash)
+            .await?
+            .ok_or_else(|| eyre::eyre!("tx not found: {tx_hash:?}"))?;
+        let receipt = provider
+            .get_transaction_receipt(tx_hash)
+            .await?
+            .ok_or_else(|| eyre::eyre!("receipt not found: {tx_hash:?}"))?;
+
+        let known_contracts = if with_local_artifacts {
+            sh_println!("Compiling project to generate artifacts")?;
+            let project = config.project()?;
+            let output = ProjectCompiler::new().compile(&project)?;
+            Some(ContractsByArtifact::new(
+                output.artifact_ids().map(|(id, artifact)| (id, artifact.clone().into())),
+            ))
+        } else {
+            None
+        };
+
+        let mut traces = result.traces.unwrap_or_default();
+
+        // Primary ENS names of the accounts, overridden by the explicit labels.
+        let ens_names = if config.offline {
+            Default::default()
+        } else {
+            let addresses = traces
+                .iter()
+                .flat_map(|(_, arena)| arena.arena.nodes())
+                .flat_map(|node| [node.trace.caller, node.trace.address])
+                .chain([tx.from()])
+                .collect();
+            provider.lookup_addresses(addresses).await.unwrap_or_default()
+        };
+        let labels = label.iter().filter_map(|label| {
+            let (address, label) = label.split_once(':')?;
+            Some((address.parse().ok()?, label.to_string()))
+        });
+        let mut builder = CallTraceDecoderBuilder::new()
+            .with_labels(ens_names.into_iter().chain(labels).chain(config.labels.clone()))
+            .with_signature_identifier(SignaturesIdentifier::new(
+                Config::foundry_cache_dir(),
+                config.offline,
+            )?);
+        let mut identifier = TraceIdentifiers::new().with_etherscan(&config, chain)?;
+        if let Some(contracts) = &known_contracts {
+            builder = builder.with_known_contracts(contracts);
+            identifier = identifier.with_local(contracts).with_runtime_codes(&result.runtime_codes);
+        }
+        let mut decoder = builder.build();
+
+        for (_, arena) in &mut traces {
+            decoder.identify(&arena.arena, &mut identifier);
+            decode_trace_arena(&mut arena.arena, &decoder).await?;
+        }
+        let nodes =
+            traces.iter().flat_map(|(_, arena)| arena.arena.nodes().iter()).collect::<Vec<_>>();
+
+        // Summary of the transaction.
+        let block = receipt.block_number.unwrap_or_default();
+        if receipt.status() {
+            sh_println!("Transaction {tx_hash} succeeded in block {block}.")?;
+        } else {
+            sh_println!("Transaction {tx_hash} reverted in block {block}.")?;
+        }
+
+        let from = name(&decoder, tx.from());
+        match nodes.first() {
+            Some(node) if node.trace.kind.is_any_create() => {
+                sh_println!("{from} deployed {}.", name(&decoder, node.trace.address))?;
+            }
+            Some(node) if node.trace.data.is_empty() => {
+                sh_println!(
+                    "{from} sent {} ETH to {}.",
+                    format_amount(node.trace.value, Unit::ETHER.get()),
+                    name(&decoder, node.trace.address)
+                )?;
+            }
+            Some(node) => {
+                let mut sentence = format!(
+                    "{from} called {} on {}",
+                    describe_call(&node.trace),
+                    name(&decoder, node.trace.address)
+                );
+                if !node.trace.value.is_zero() {
+                    sentence.push_str(&format!(
+                        ", sending {} ETH",
+                        format_amount(node.trace.value, Unit::ETHER.get())
+                    ));
+                }
+                sh_println!("{sentence}.")?;
+            }
+            None => {}
+        }
+
+        if !receipt.status() {
+            if let Some(reason) =
+                nodes.first().and_then(|node| node.trace.decoded.return_data.clone())
+            {
+                sh_println!("It reverted with: {reason}.")?;
+            }
+        }
+
+        let fee = U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price);
+        sh_println!(
+            "It used {} gas at {} gwei, paying {} ETH.",
+            receipt.gas_used,
+            format_amount(U256::from(receipt.effective_gas_price), Unit::GWEI.get()),
+            format_amount(fee, Unit::ETHER.get())
+        )?;
+
+        // Calls made by the transaction, state changing calls only.
+        let calls = nodes
+            .iter()
+            .skip(1)
+            .filter(|node| {
+                node.trace.kind != CallKind::StaticCall &&
+                    node.trace.decoded.label.as_deref() != Some("PRECOMPILES")
+            })
+            .map(|node| {
+                let mut line = format!(
+                    "{}{}.{}",
+                    "  ".repeat(node.trace.depth),
+                    name(&decoder, node.trace.address),
+                    describe_call(&node.trace)
+                );
+                if node.trace.kind == CallKind::DelegateCall {
+                    line.push_str(" (delegatecall)");
+                }
+                if !node.trace.value.is_zero() {
+                    line.push_str(&format!(
+                        " with {} ETH",
+                        format_amount(node.trace.value, Unit::ETHER.get())
+                    ));
+                }
+                if !node.trace.success {
+                    line.push_str(" (reverted)");
+                }
+                line
+            })
+            .collect::<Vec<_>>();
+        print_section("Calls", &calls)?;
+
+        // Ether moved by the calls of the transaction.
+        let ether_transfers = nodes
+            .iter()
+            .filter(|node| node.trace.success && !node.trace.value.is_zero())
+            .map(|node| {
+                format!(
+                    "  {} ETH from {} to {}",
+                    format_amount(node.trace.value, Unit::ETHER.get()),
+                    name(&decoder, node.trace.caller),
+                    name(&decoder, node.trace.address)
+                )
+            })
+            .collect::<Vec<_>>();
+        print_section("Ether transfers", &ether_transfers)?;
+
+        // ERC20 transfers and all the events emitted, as recorded in the receipt.
+        let logs = receipt.inner.inner.logs();
+        let block_id = BlockId::number(block);
+        let mut tokens = HashMap::new();
+        let mut token_transfers = Vec::new();
+        let mut events = Vec::with_capacity(logs.len());
+        for log in logs {
+            let emitter = log.address();
+            if let Ok(transfer) = IERC20::Transfer::decode_log_data(log.data(), true) {
+                if !tokens.contains_key(&emitter) {
+                    let token = token_info(&provider, emitter, block_id).await;
+                    tokens.insert(emitter, token);
+                }
+                let (amount, symbol) = match &tokens[&emitter] {
+                    Some((symbol, decimals)) => {
+                        (format_amount(transfer.value, *decimals), symbol.clone())
+                    }
+                    None => (transfer.value.to_string(), name(&decoder, emitter)),
+                };
+                token_transfers.push(format!(
+                    "  {amount} {symbol} from {} to {}",
+                    name(&decoder, transfer.from),
+                    name(&decoder, transfer.to)
+                ));
+            }
+
+            let decoded = decoder.decode_event(log.data()).await;
+            let event = match (decoded.name, decoded.params) {
+                (Some(event), Some(params)) => format!(
+                    "{event}({})",
+                    params
+                        .iter()
+                        .map(|(name, value)| format!("{name}: {value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                _ => format!(
+                    "unknown event {}",
+                    log.topics().first().map(|topic| topic.to_string()).unwrap_or_default()
+                ),
+            };
+            events.push(format!("  {}.{event}", name(&decoder, emitter)));
+        }
+        print_section("Token transfers", &token_transfers)?;
+        print_section("Events", &events)?;
+
+        Ok(())
+    }
+}
+
+/// Prints the lines of a section of the explanation, if there are any.
+fn print_section(title: &str, lines: &[String]) -> Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    sh_println!("\n{title}:")?;
+    for line in lines {
+        sh_println!("{line}")?;
+    }
+    Ok(())
+}
+
+/// Returns the label or the name of the contract at `address`, or the address itself if it's
+/// unknown.
+fn name(decoder: &CallTraceDecoder, address: Address) -> String {
+    let name = decoder
+        .labels
+        .get(&address)
+        .map(String::as_str)
+        .or_else(|| decoder.contracts.get(&address).map(|id| get_contract_name(id)));
+    match name {
+        Some(name) => format!("{name} ({address})"),
+        None => address.to_string(),
+    }
+}
+
+/// Returns the decoded function call of a trace, e.g. `transfer(0x..., 100)`.
+fn describe_call(trace: &CallTrace) -> String {
+    if let Some(call_data) = &trace.decoded.call_data {
+        let function = call_data.signature.split('(').next().unwrap_or_default();
+        return format!("{function}({})", call_data.args.join(", "));
+    }
+    match trace.data.get(..4) {
+        Some(selector) => format!("{}(...)", alloy_primitives::hex::encode_prefixed(selector)),
+        None if trace.data.is_empty() => "receive()".to_string(),
+        None => "fallback()".to_string(),
+    }
+}
+
+/// Fetches the symbol and the decimals of an ERC20 token at the given block.
+async fn token_info(
+    provider: &RetryProvider,
+    token: Address,
+    block: BlockId,
+) -> Option<(String, u8)> {
+    let erc20 = IERC20::new(token, provider);
+    let symbol = erc20.symbol().block(block).call().await.ok()?._0;
+    let decimals = erc20.decimals().block(block).call().await.ok()?._0;
+    Some((symbol, decimals))
+}
+
+/// Formats an amount of a token with the given decimals, without trailing zeros.
+fn format_amount(value: U256, decimals: u8) -> String {
+    let Ok(formatted) = format_units(value, decimals) else { return value.to_string() };
+    if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_amounts() {
+        assert_eq!(format_amount(U256::from(1_500_000u64), 6), "1.5");
+        assert_eq!(format_amount(U256::from(10).pow(U256::from(18)), 18), "1");
+        assert_eq!(format_amount(U256::ZERO, 18), "0");
+        assert_eq!(format_amount(U256::from(42), 0), "42");
+    }
+}
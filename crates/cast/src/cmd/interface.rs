@@ -1,4 +1,4 @@
-use alloy_json_abi::{ContractObject, JsonAbi};
+use alloy_json_abi::{ContractObject, InternalType, JsonAbi, Param};
 use alloy_primitives::Address;
 use clap::Parser;
 use eyre::{Context, Result};
@@ -12,6 +12,7 @@
 use itertools::Itertools;
 use serde_json::Value;
 use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -20,14 +21,14 @@
 #[derive(Clone, Debug, Parser)]
 pub struct InterfaceArgs {
     /// The target contract, which can be one of:
-    /// - A file path to an ABI JSON file.
+    /// - An ABI JSON, or a file path to an ABI JSON file.
     /// - A contract identifier in the form `<path>:<contractname>` or just `<contractname>`.
     /// - An Ethereum address, for which the ABI will be fetched from Etherscan.
     contract: String,
 
     /// The name to use for the generated interface.
     ///
-    /// Only relevant when retrieving the ABI from a file.
+    /// Defaults to the name of the contract, or `Interface` for ABI files.
     #[arg(long, short)]
     name: Option<String>,
 
@@ -54,14 +55,17 @@ impl InterfaceArgs {
     pub async fn run(self) -> Result<()> {
         let Self { contract, name, pragma, output: output_location, etherscan } = self;
 
-        // Determine if the target contract is an ABI file, a local contract or an Ethereum address.
-        let abis = if Path::new(&contract).is_file() &&
+        // Determine if the target contract is an ABI, an ABI file, a local contract or an Ethereum
+        // address.
+        let abis = if contract.trim_start().starts_with(['[', '{']) {
+            vec![(parse_abi(&contract, "the ABI")?, "Interface".to_string())]
+        } else if Path::new(&contract).is_file() &&
             fs::read_to_string(&contract)
                 .ok()
                 .and_then(|content| serde_json::from_str::<Value>(&content).ok())
                 .is_some()
         {
-            load_abi_from_file(&contract, name)?
+            load_abi_from_file(&contract, None)?
         } else {
             match Address::from_str(&contract) {
                 Ok(address) => fetch_abi_from_etherscan(address, &etherscan).await?,
@@ -70,7 +74,7 @@ pub async fn run(self) -> Result<()> {
         };
 
         // Retrieve interfaces from the array of ABIs.
-        let interfaces = get_interfaces(abis)?;
+        let interfaces = get_interfaces(abis, name.as_deref())?;
 
         // Print result or write to file.
         let res = if shell::is_json() {
@@ -108,12 +112,17 @@ struct InterfaceSource {
 /// Load the ABI from a file.
 pub fn load_abi_from_file(path: &str, name: Option<String>) -> Result<Vec<(JsonAbi, String)>> {
     let file = std::fs::read_to_string(path).wrap_err("unable to read abi file")?;
-    let obj: ContractObject = serde_json::from_str(&file)?;
-    let abi = obj.abi.ok_or_else(|| eyre::eyre!("could not find ABI in file {path}"))?;
+    let abi = parse_abi(&file, &format!("file {path}"))?;
     let name = name.unwrap_or_else(|| "Interface".to_owned());
     Ok(vec![(abi, name)])
 }
 
+/// Parses an ABI, or the ABI of a contract object.
+fn parse_abi(content: &str, source: &str) -> Result<JsonAbi> {
+    let obj: ContractObject = serde_json::from_str(content)?;
+    obj.abi.ok_or_else(|| eyre::eyre!("could not find ABI in {source}"))
+}
+
 /// Load the ABI from the artifact of a locally compiled contract.
 fn load_abi_from_artifact(path_or_contract: &str) -> Result<Vec<(JsonAbi, String)>> {
     let config = load_config()?;
@@ -150,10 +159,19 @@ pub async fn fetch_abi_from_etherscan(
 }
 
 /// Converts a vector of tuples containing the ABI and contract name into a vector of
-/// `InterfaceSource` objects.
-fn get_interfaces(abis: Vec<(JsonAbi, String)>) -> Result<Vec<InterfaceSource>> {
+/// `InterfaceSource` objects, named `name` if set.
+fn get_interfaces(
+    abis: Vec<(JsonAbi, String)>,
+    name: Option<&str>,
+) -> Result<Vec<InterfaceSource>> {
+    let mut names = BTreeSet::new();
     abis.into_iter()
-        .map(|(contract_abi, name)| {
+        .map(|(mut contract_abi, contract_name)| {
+            let name = unique_name(name.unwrap_or(&contract_name), &names);
+            names.insert(name.clone());
+            let json_abi = serde_json::to_string_pretty(&contract_abi)?;
+            prepare_abi(&mut contract_abi, &contract_name, &name);
+
             let source = match foundry_cli::utils::abi_to_solidity(&contract_abi, &name) {
                 Ok(generated_source) => generated_source,
                 Err(e) => {
@@ -161,7 +179,205 @@ fn get_interfaces(abis: Vec<(JsonAbi, String)>) -> Result<Vec<InterfaceSource>>
                     contract_abi.to_sol(&name, None)
                 }
             };
-            Ok(InterfaceSource { json_abi: serde_json::to_string_pretty(&contract_abi)?, source })
+            Ok(InterfaceSource { json_abi, source })
         })
         .collect()
 }
+
+/// Prepares the ABI of the contract `original` to be printed as the interface `name`, so that the
+/// interface compiles:
+/// - the types declared in the original contract are declared in the interface;
+/// - tuples without internal types, as in the ABIs of old compilers, are declared as structs;
+/// - the types and libraries whose names collide with the functions, events or errors of the
+///   interface are renamed.
+fn prepare_abi(abi: &mut JsonAbi, original: &str, name: &str) {
+    if original != name {
+        visit_params(abi, &mut |_, _, internal_type, _| {
+            if let Some((contract, _)) = user_type(internal_type) {
+                if contract.as_deref() == Some(original) {
+                    *contract = Some(name.to_string());
+                }
+            }
+        });
+    }
+
+    let items = abi
+        .functions
+        .keys()
+        .chain(abi.events.keys())
+        .chain(abi.errors.keys())
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    let mut types = BTreeSet::new();
+    let mut libraries = BTreeSet::new();
+    visit_params(abi, &mut |_, _, internal_type, _| {
+        if let Some((contract, ty)) = user_type(internal_type) {
+            match contract {
+                Some(contract) if contract.as_str() != name => libraries.insert(contract.clone()),
+                _ => types.insert(base_type(ty).to_string()),
+            };
+        }
+    });
+
+    // name the structs of tuples after their parameters, reusing the names of identical structs
+    let mut taken: BTreeSet<String> =
+        items.iter().chain(&types).chain(&libraries).cloned().collect();
+    let mut structs = HashMap::new();
+    visit_params(abi, &mut |param_name, ty, internal_type, components| {
+        let Some(suffix) = ty.strip_prefix("tuple") else { return };
+        let is_anonymous = match internal_type {
+            None => true,
+            Some(InternalType::Other { ty, .. }) => ty.starts_with("tuple"),
+            _ => false,
+        };
+        if !is_anonymous {
+            return;
+        }
+        let key = serde_json::to_string(components).unwrap_or_default();
+        let struct_name = structs
+            .entry(key)
+            .or_insert_with(|| {
+                let struct_name = unique_name(&struct_name_of(param_name), &taken);
+                taken.insert(struct_name.clone());
+                struct_name
+            })
+            .clone();
+        *internal_type =
+            Some(InternalType::Struct { contract: None, ty: format!("{struct_name}{suffix}") });
+    });
+
+    // types shadow the items of the interface, and libraries the types of the interface
+    let mut renames = BTreeMap::new();
+    for ty in types.iter().filter(|ty| items.contains(*ty)) {
+        let renamed = unique_name(ty, &taken);
+        taken.insert(renamed.clone());
+        renames.insert(ty.clone(), renamed);
+    }
+    let mut library_renames = BTreeMap::new();
+    for library in libraries.iter().filter(|lib| items.contains(*lib) || types.contains(*lib)) {
+        let renamed = unique_name(library, &taken);
+        taken.insert(renamed.clone());
+        library_renames.insert(library.clone(), renamed);
+    }
+    if renames.is_empty() && library_renames.is_empty() {
+        return;
+    }
+    visit_params(abi, &mut |_, _, internal_type, _| {
+        let Some((contract, ty)) = user_type(internal_type) else { return };
+        match contract {
+            Some(library) if library.as_str() != name => {
+                if let Some(renamed) = library_renames.get(library.as_str()) {
+                    *library = renamed.clone();
+                }
+            }
+            _ => {
+                if let Some(renamed) = renames.get(base_type(ty)) {
+                    *ty = format!("{renamed}{}", &ty[base_type(ty).len()..]);
+                }
+            }
+        }
+    });
+}
+
+/// Calls `f` with the name, type, internal type and components of every parameter of the ABI,
+/// components first.
+fn visit_params(
+    abi: &mut JsonAbi,
+    f: &mut impl FnMut(&str, &str, &mut Option<InternalType>, &[Param]),
+) {
+    fn visit(
+        params: &mut [Param],
+        f: &mut impl FnMut(&str, &str, &mut Option<InternalType>, &[Param]),
+    ) {
+        for param in params {
+            visit(&mut param.components, f);
+            f(&param.name, &param.ty, &mut param.internal_type, &param.components);
+        }
+    }
+
+    if let Some(constructor) = &mut abi.constructor {
+        visit(&mut constructor.inputs, f);
+    }
+    for function in abi.functions.values_mut().flatten() {
+        visit(&mut function.inputs, f);
+        visit(&mut function.outputs, f);
+    }
+    for error in abi.errors.values_mut().flatten() {
+        visit(&mut error.inputs, f);
+    }
+    for event in abi.events.values_mut().flatten() {
+        for param in &mut event.inputs {
+            visit(&mut param.components, f);
+            f(&param.name, &param.ty, &mut param.internal_type, &param.components);
+        }
+    }
+}
+
+/// Returns the contract and the name of a user-defined type.
+fn user_type(
+    internal_type: &mut Option<InternalType>,
+) -> Option<(&mut Option<String>, &mut String)> {
+    match internal_type.as_mut()? {
+        InternalType::Struct { contract, ty } |
+        InternalType::Enum { contract, ty } |
+        InternalType::Other { contract, ty } => Some((contract, ty)),
+        InternalType::AddressPayable(_) | InternalType::Contract(_) => None,
+    }
+}
+
+/// Returns the type without its array dimensions.
+fn base_type(ty: &str) -> &str {
+    ty.split('[').next().unwrap_or(ty)
+}
+
+/// Returns the name of the struct of a tuple parameter, e.g. `Order` for `_order`.
+fn struct_name_of(param_name: &str) -> String {
+    let mut chars = param_name.trim_matches('_').chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() => {
+            first.to_ascii_uppercase().to_string() + chars.as_str()
+        }
+        _ => "Struct".to_string(),
+    }
+}
+
+/// Returns `name`, suffixed with a number if it's already taken.
+fn unique_name(name: &str, taken: &BTreeSet<String>) -> String {
+    (0..)
+        .map(|i| if i == 0 { name.to_string() } else { format!("{name}_{i}") })
+        .find(|name| !taken.contains(name))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepares_compilable_interfaces() {
+        let position = r#"{"name":"position","type":"tuple","internalType":"struct Vault.Position","components":[{"name":"id","type":"uint256","internalType":"uint256"}]}"#;
+        let order = r#"[{"name":"maker","type":"address"},{"name":"amount","type":"uint256"}]"#;
+        let abi = format!(
+            r#"[
+                {{"type":"function","name":"submit","inputs":[{{"name":"_order","type":"tuple","components":{order}}}],"outputs":[{{"name":"orders","type":"tuple[]","components":{order}}}],"stateMutability":"nonpayable"}},
+                {{"type":"function","name":"open","inputs":[{position}],"outputs":[],"stateMutability":"nonpayable"}},
+                {{"type":"event","name":"Position","inputs":[{{"name":"id","type":"uint256","indexed":false}}],"anonymous":false}}
+            ]"#
+        );
+        let mut abi = parse_abi(&abi, "the ABI").unwrap();
+        prepare_abi(&mut abi, "Vault", "IVault");
+        let source = abi.to_sol("IVault", None);
+
+        assert!(!source.contains("library Vault"), "{source}");
+        assert!(source.contains("struct Order {"), "{source}");
+        assert!(source.contains("struct Position_1 {"), "{source}");
+        assert!(source.contains("event Position(uint256 id);"), "{source}");
+        assert!(source.contains("function open(Position_1 memory position) external;"), "{source}");
+        assert!(
+            source.contains(
+                "function submit(Order memory _order) external returns (Order[] memory orders);"
+            ),
+            "{source}"
+        );
+    }
+}
@@ -0,0 +1,442 @@
+use crate::{
+    cmd::estimate::EstimateSubcommands,
+    tx::{CastTxBuilder, SenderKind},
+};
+use alloy_chains::Chain;
+use alloy_network::TransactionBuilder;
+use alloy_primitives::{address, hex, utils::format_ether, Address, TxKind, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockId, TransactionRequest};
+use alloy_serde::WithOtherFields;
+use alloy_sol_types::{sol, SolCall};
+use clap::{Parser, ValueEnum};
+use eyre::{Result, WrapErr};
+use foundry_cli::{
+    opts::{EthereumOpts, TransactionOpts},
+    utils::{self, LoadConfig},
+};
+use foundry_common::{ens::NameOrAddress, provider::RetryProvider, shell};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// The address of the `GasPriceOracle` predeploy of OP stack chains.
+const GAS_PRICE_ORACLE_ADDRESS: Address = address!("420000000000000000000000000000000000000F");
+
+/// The address of the `NodeInterface` virtual contract of Arbitrum chains.
+const NODE_INTERFACE_ADDRESS: Address = address!("00000000000000000000000000000000000000C8");
+
+/// The size of the signature the `GasPriceOracle` adds to unsigned transactions.
+const SIGNATURE_SIZE: u64 = 68;
+
+/// The parameters of the Fjord linear regression of the size of transactions after compression,
+/// scaled by 1e6.
+const FJORD_COST_INTERCEPT: i64 = -42_585_600;
+const FJORD_COST_FASTLZ_COEF: i64 = 836_500;
+const FJORD_MIN_TRANSACTION_SIZE: i64 = 100;
+
+sol! {
+    interface IGasPriceOracle {
+        function isEcotone() external view returns (bool);
+        function isFjord() external view returns (bool);
+        function l1BaseFee() external view returns (uint256);
+        function blobBaseFee() external view returns (uint256);
+        function baseFeeScalar() external view returns (uint32);
+        function blobBaseFeeScalar() external view returns (uint32);
+    }
+
+    interface INodeInterface {
+        function gasEstimateL1Component(address to, bool contractCreation, bytes calldata data) external payable returns (uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate);
+    }
+}
+
+/// CLI arguments for `cast estimate-l1-fee`.
+#[derive(Debug, Parser)]
+pub struct EstimateL1FeeArgs {
+    /// The destination of the transaction.
+    #[arg(value_parser = NameOrAddress::from_str)]
+    to: Option<NameOrAddress>,
+
+    /// The signature of the function to call.
+    sig: Option<String>,
+
+    /// The arguments of the function to call.
+    args: Vec<String>,
+
+    /// The L2 stack of the chain, detected from the chain if omitted.
+    #[arg(long, value_enum)]
+    stack: Option<L2Stack>,
+
+    /// The block height to query at.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending.
+    #[arg(long, short = 'B')]
+    block: Option<BlockId>,
+
+    #[command(subcommand)]
+    command: Option<EstimateSubcommands>,
+
+    #[command(flatten)]
+    tx: TransactionOpts,
+
+    #[command(flatten)]
+    eth: EthereumOpts,
+}
+
+/// An L2 stack with its own L1 data fee formula.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum L2Stack {
+    /// OP stack chains, e.g. Optimism and Base.
+    Op,
+    /// Arbitrum chains.
+    Arbitrum,
+}
+
+/// The L1 data fee of a transaction, with the values it's computed from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct L1FeeEstimate {
+    /// The L1 data fee, in wei.
+    l1_fee: U256,
+    /// The L1 base fee, or its estimate on Arbitrum.
+    l1_base_fee: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blob_base_fee: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_fee_scalar: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blob_base_fee_scalar: Option<u32>,
+    /// The size of the serialized transaction, with its signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    /// The estimated size of the transaction after compression, since Fjord.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compressed_size: Option<u64>,
+    /// The L1 gas of the transaction data, the L2 gas paying for it on Arbitrum.
+    l1_gas: u64,
+}
+
+impl EstimateL1FeeArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { to, mut sig, mut args, stack, block, command, mut tx, eth } = self;
+
+        let config = eth.load_config()?;
+        let provider = utils::get_provider(&config)?;
+        let sender = SenderKind::from_wallet_opts(eth.wallet).await?;
+        let block = block.unwrap_or_default();
+
+        let code = if let Some(EstimateSubcommands::Create {
+            code,
+            sig: create_sig,
+            args: create_args,
+            value,
+        }) = command
+        {
+            sig = create_sig;
+            args = create_args;
+            if let Some(value) = value {
+                tx.value = Some(value);
+            }
+            Some(code)
+        } else {
+            None
+        };
+
+        let stack = match stack {
+            Some(stack) => stack,
+            None => detect_stack(&provider, block).await?,
+        };
+
+        let builder = CastTxBuilder::new(&provider, tx, &config)
+            .await?
+            .with_to(to)
+            .await?
+            .with_code_sig_and_args(code, sig, args)
+            .await?;
+
+        let estimate = match stack {
+            L2Stack::Op => {
+                let unsigned = builder.build_unsigned_raw(sender.address()).await?;
+                op_l1_fee(&provider, &hex::decode(unsigned)?, block).await?
+            }
+            L2Stack::Arbitrum => {
+                let (tx, _) = builder.build_raw(sender).await?;
+                arbitrum_l1_fee(&provider, &tx, block).await?
+            }
+        };
+
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string_pretty(&estimate)?)?;
+            return Ok(());
+        }
+        sh_println!("L1 fee: {} wei ({} ETH)", estimate.l1_fee, format_ether(estimate.l1_fee))?;
+        sh_println!("L1 gas: {}", estimate.l1_gas)?;
+        sh_println!("L1 base fee: {}", estimate.l1_base_fee)?;
+        if let Some(blob_base_fee) = estimate.blob_base_fee {
+            sh_println!("Blob base fee: {blob_base_fee}")?;
+        }
+        if let Some(scalar) = estimate.base_fee_scalar {
+            sh_println!("Base fee scalar: {scalar}")?;
+        }
+        if let Some(scalar) = estimate.blob_base_fee_scalar {
+            sh_println!("Blob base fee scalar: {scalar}")?;
+        }
+        if let Some(size) = estimate.size {
+            sh_println!("Size: {size} bytes")?;
+        }
+        if let Some(size) = estimate.compressed_size {
+            sh_println!("Estimated compressed size: {size} bytes")?;
+        }
+        Ok(())
+    }
+}
+
+/// Detects the L2 stack of the chain, from its `GasPriceOracle` predeploy or its chain ID.
+async fn detect_stack(provider: &RetryProvider, block: BlockId) -> Result<L2Stack> {
+    if !provider.get_code_at(GAS_PRICE_ORACLE_ADDRESS).block_id(block).await?.is_empty() {
+        return Ok(L2Stack::Op);
+    }
+    let chain = Chain::from(provider.get_chain_id().await?);
+    if chain.named().is_some_and(|chain| chain.is_arbitrum()) {
+        return Ok(L2Stack::Arbitrum);
+    }
+    eyre::bail!("could not detect the L2 stack of chain {chain}, use `--stack` to specify it")
+}
+
+async fn call<C: SolCall>(
+    provider: &RetryProvider,
+    to: Address,
+    call: C,
+    block: BlockId,
+) -> Result<C::Return> {
+    let tx = TransactionRequest::default().with_to(to).with_input(call.abi_encode());
+    let output = provider.call(WithOtherFields::new(tx)).block(block).await?;
+    Ok(C::abi_decode_returns(&output, false)?)
+}
+
+/// Computes the L1 data fee of an unsigned transaction on an OP stack chain, with the fee
+/// parameters of its `GasPriceOracle`.
+async fn op_l1_fee(provider: &RetryProvider, tx: &[u8], block: BlockId) -> Result<L1FeeEstimate> {
+    let oracle = GAS_PRICE_ORACLE_ADDRESS;
+    let is_ecotone = call(provider, oracle, IGasPriceOracle::isEcotoneCall {}, block)
+        .await
+        .wrap_err("failed to query the GasPriceOracle")?
+        ._0;
+    eyre::ensure!(is_ecotone, "L1 fees are only supported since the Ecotone upgrade");
+    let is_fjord = call(provider, oracle, IGasPriceOracle::isFjordCall {}, block).await?._0;
+
+    let params = OpFeeParams {
+        l1_base_fee: call(provider, oracle, IGasPriceOracle::l1BaseFeeCall {}, block).await?._0,
+        blob_base_fee: call(provider, oracle, IGasPriceOracle::blobBaseFeeCall {}, block).await?._0,
+        base_fee_scalar: call(provider, oracle, IGasPriceOracle::baseFeeScalarCall {}, block)
+            .await?
+            ._0,
+        blob_base_fee_scalar: call(
+            provider,
+            oracle,
+            IGasPriceOracle::blobBaseFeeScalarCall {},
+            block,
+        )
+        .await?
+        ._0,
+    };
+    Ok(if is_fjord { params.fjord_l1_fee(tx) } else { params.ecotone_l1_fee(tx) })
+}
+
+/// The L1 fee parameters of an OP stack chain.
+#[derive(Clone, Copy, Debug)]
+struct OpFeeParams {
+    l1_base_fee: U256,
+    blob_base_fee: U256,
+    base_fee_scalar: u32,
+    blob_base_fee_scalar: u32,
+}
+
+impl OpFeeParams {
+    /// Returns the weighted L1 gas price, scaled by 16e6.
+    fn scaled_gas_price(&self) -> U256 {
+        U256::from(self.base_fee_scalar) * U256::from(16) * self.l1_base_fee +
+            U256::from(self.blob_base_fee_scalar) * self.blob_base_fee
+    }
+
+    fn estimate(&self, tx: &[u8]) -> L1FeeEstimate {
+        L1FeeEstimate {
+            l1_base_fee: self.l1_base_fee,
+            blob_base_fee: Some(self.blob_base_fee),
+            base_fee_scalar: Some(self.base_fee_scalar),
+            blob_base_fee_scalar: Some(self.blob_base_fee_scalar),
+            size: Some(tx.len() as u64 + SIGNATURE_SIZE),
+            ..Default::default()
+        }
+    }
+
+    /// Computes the L1 fee of an unsigned transaction with the Ecotone formula, from the gas of
+    /// its calldata.
+    fn ecotone_l1_fee(&self, tx: &[u8]) -> L1FeeEstimate {
+        let zeros = tx.iter().filter(|byte| **byte == 0).count() as u64;
+        let l1_gas = zeros * 4 + (tx.len() as u64 - zeros + SIGNATURE_SIZE) * 16;
+        let l1_fee = U256::from(l1_gas) * self.scaled_gas_price() / U256::from(16_000_000);
+        L1FeeEstimate { l1_fee, l1_gas, ..self.estimate(tx) }
+    }
+
+    /// Computes the L1 fee of an unsigned transaction with the Fjord formula, from the estimated
+    /// size of the transaction after compression.
+    fn fjord_l1_fee(&self, tx: &[u8]) -> L1FeeEstimate {
+        let fastlz_size = flz_compress_len(tx) as i64 + SIGNATURE_SIZE as i64;
+        let scaled_size = (FJORD_COST_INTERCEPT + FJORD_COST_FASTLZ_COEF * fastlz_size)
+            .max(FJORD_MIN_TRANSACTION_SIZE * 1_000_000) as u64;
+        let l1_fee =
+            U256::from(scaled_size) * self.scaled_gas_price() / U256::from(1_000_000_000_000u64);
+        L1FeeEstimate {
+            l1_fee,
+            l1_gas: scaled_size * 16 / 1_000_000,
+            compressed_size: Some(scaled_size / 1_000_000),
+            ..self.estimate(tx)
+        }
+    }
+}
+
+/// Computes the L1 data fee of a transaction on an Arbitrum chain, with the `NodeInterface`.
+async fn arbitrum_l1_fee(
+    provider: &RetryProvider,
+    tx: &WithOtherFields<TransactionRequest>,
+    block: BlockId,
+) -> Result<L1FeeEstimate> {
+    let (to, contract_creation) = match tx.to {
+        Some(TxKind::Call(to)) => (to, false),
+        _ => (Address::ZERO, true),
+    };
+    let data = tx.input.input().cloned().unwrap_or_default();
+    let estimate = call(
+        provider,
+        NODE_INTERFACE_ADDRESS,
+        INodeInterface::gasEstimateL1ComponentCall {
+            to,
+            contractCreation: contract_creation,
+            data,
+        },
+        block,
+    )
+    .await
+    .wrap_err("failed to query the NodeInterface")?;
+    Ok(L1FeeEstimate {
+        l1_fee: U256::from(estimate.gasEstimateForL1) * estimate.baseFee,
+        l1_base_fee: estimate.l1BaseFeeEstimate,
+        l1_gas: estimate.gasEstimateForL1,
+        ..Default::default()
+    })
+}
+
+/// Returns the length of the data compressed with FastLZ, as computed by the `GasPriceOracle`
+/// since Fjord.
+fn flz_compress_len(data: &[u8]) -> u64 {
+    let len = data.len() as u32;
+    let mut size = 0u64;
+    let mut table = vec![0u32; 8192];
+
+    let u24 = |i: u32| {
+        let i = i as usize;
+        data[i] as u32 | (data[i + 1] as u32) << 8 | (data[i + 2] as u32) << 16
+    };
+    let hash = |value: u32| (2_654_435_769u32.wrapping_mul(value) >> 19) & 0x1fff;
+    let literals = |size: &mut u64, run: u32| {
+        *size += 0x21 * (run / 0x20) as u64;
+        let rest = run % 0x20;
+        if rest != 0 {
+            *size += rest as u64 + 1;
+        }
+    };
+
+    let mut anchor = 0u32;
+    let limit = len.saturating_sub(13);
+    let mut ip = anchor + 2;
+    while ip < limit {
+        let mut reference;
+        loop {
+            let value = u24(ip);
+            let h = hash(value) as usize;
+            reference = table[h];
+            table[h] = ip;
+            let distance = ip - reference;
+            if ip >= limit {
+                break;
+            }
+            ip += 1;
+            if distance <= 0x1fff && value == u24(reference) {
+                break;
+            }
+        }
+        if ip >= limit {
+            break;
+        }
+        ip -= 1;
+        if ip > anchor {
+            literals(&mut size, ip - anchor);
+        }
+
+        // the length of the match, which ends at the first differing byte
+        let (p, q) = (reference + 3, ip + 3);
+        let mut end = limit + 9 - q;
+        let mut length = 0;
+        while length < end {
+            if data[(p + length) as usize] != data[(q + length) as usize] {
+                end = 0;
+            }
+            length += 1;
+        }
+
+        let encoded = length - 1;
+        size += 3 * (encoded / 262) as u64;
+        size += if encoded % 262 >= 6 { 3 } else { 2 };
+
+        ip += length;
+        for _ in 0..2 {
+            table[hash(u24(ip)) as usize] = ip;
+            ip += 1;
+        }
+        anchor = ip;
+    }
+    literals(&mut size, len - anchor);
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compresses_calldata() {
+        assert_eq!(flz_compress_len(&[]), 0);
+        // short data is copied as literals, with a header byte for each run of 32 bytes
+        assert_eq!(flz_compress_len(&[1; 10]), 11);
+        assert_eq!(flz_compress_len(&(0..64).collect::<Vec<u8>>()), 66);
+
+        let repeated = [0xab; 1000];
+        assert!(flz_compress_len(&repeated) < 50);
+        let random = alloy_primitives::keccak256("foundry").repeat(4);
+        assert!(flz_compress_len(&random) < random.len() as u64);
+    }
+
+    #[test]
+    fn computes_op_l1_fees() {
+        let params = OpFeeParams {
+            l1_base_fee: U256::from(10_000_000_000u64),
+            blob_base_fee: U256::from(1),
+            base_fee_scalar: 1368,
+            blob_base_fee_scalar: 810_949,
+        };
+        let tx = [[0u8; 10].as_slice(), &[1; 100]].concat();
+
+        let ecotone = params.ecotone_l1_fee(&tx);
+        assert_eq!(ecotone.l1_gas, 10 * 4 + 168 * 16);
+        assert_eq!(ecotone.size, Some(178));
+        // (1368 * 16 * 1e10 + 810949) * 2728 / 16e6
+        assert_eq!(ecotone.l1_fee, U256::from(37_319_040_138u64));
+
+        let fjord = params.fjord_l1_fee(&tx);
+        // small transactions are charged the minimum size
+        assert_eq!(fjord.compressed_size, Some(100));
+        assert_eq!(fjord.l1_gas, 1600);
+        // 100e6 * (1368 * 16 * 1e10 + 810949) / 1e12
+        assert_eq!(fjord.l1_fee, U256::from(21_888_000_081u64));
+    }
+}
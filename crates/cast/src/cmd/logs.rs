@@ -1,15 +1,29 @@
-use crate::Cast;
-use alloy_dyn_abi::{DynSolType, DynSolValue, Specifier};
+use crate::{cmd::interface::load_abi_from_file, Cast};
+use alloy_dyn_abi::{DynSolType, DynSolValue, EventExt, Specifier};
 use alloy_json_abi::Event;
 use alloy_network::AnyNetwork;
-use alloy_primitives::{hex::FromHex, Address, B256};
-use alloy_rpc_types::{BlockId, BlockNumberOrTag, Filter, FilterBlockOption, FilterSet, Topic};
-use clap::Parser;
+use alloy_primitives::{hex::FromHex, map::HashMap, Address, Bytes, LogData, B256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{
+    BlockId, BlockNumberOrTag, Filter, FilterBlockOption, FilterSet, Log, Topic,
+};
+use clap::{Parser, ValueEnum};
 use eyre::Result;
 use foundry_cli::{opts::EthereumOpts, utils, utils::LoadConfig};
-use foundry_common::ens::NameOrAddress;
+use foundry_common::{
+    abi::{get_event, get_indexed_event},
+    ens::NameOrAddress,
+    fmt::format_token_raw,
+    shell,
+};
+use futures::StreamExt;
 use itertools::Itertools;
-use std::{io, str::FromStr};
+use serde::Serialize;
+use std::{collections::VecDeque, io, str::FromStr, time::Duration};
+use tokio::signal::ctrl_c;
+
+/// The number of followed blocks whose hashes are kept to detect reorgs.
+const MAX_FOLLOWED_BLOCKS: usize = 128;
 
 /// CLI arguments for `cast logs`.
 #[derive(Debug, Parser)]
@@ -48,14 +62,55 @@ pub struct LogsArgs {
     #[arg(long)]
     subscribe: bool,
 
+    /// Follow new logs until interrupted or TO_BLOCK is reached, decoding them with the event
+    /// signature or `--abi`.
+    ///
+    /// New blocks are awaited with `eth_subscribe` on websocket endpoints, and polled otherwise.
+    /// Starts after the latest block unless FROM_BLOCK is set. The logs of blocks removed by a
+    /// reorg are replayed from the new chain.
+    #[arg(long, conflicts_with = "subscribe")]
+    follow: bool,
+
+    /// Path to the ABI or artifact of the contracts, to decode their events.
+    #[arg(long, value_name = "PATH", requires = "follow")]
+    abi: Option<String>,
+
+    /// The output format of the followed logs, `json` prints one log per line.
+    ///
+    /// Defaults to `json` with `--json`, and `table` otherwise.
+    #[arg(long, value_enum, requires = "follow")]
+    format: Option<LogFormat>,
+
+    /// The interval between polls of new blocks with `--follow`, in seconds.
+    #[arg(long, value_name = "SECONDS", default_value = "2")]
+    poll_interval: u64,
+
     #[command(flatten)]
     eth: EthereumOpts,
 }
 
+/// The output format of followed logs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    Table,
+    Json,
+}
+
 impl LogsArgs {
     pub async fn run(self) -> Result<()> {
-        let Self { from_block, to_block, address, sig_or_topic, topics_or_args, subscribe, eth } =
-            self;
+        let Self {
+            from_block,
+            to_block,
+            address,
+            sig_or_topic,
+            topics_or_args,
+            subscribe,
+            follow,
+            abi,
+            format,
+            poll_interval,
+            eth,
+        } = self;
 
         let config = eth.load_config()?;
         let provider = utils::get_provider(&config)?;
@@ -67,6 +122,41 @@ pub async fn run(self) -> Result<()> {
             None => None,
         };
 
+        if follow {
+            let mut decoder = LogDecoder::default();
+            if let Some(event) = sig_or_topic.as_deref().and_then(|sig| get_event(sig).ok()) {
+                decoder.add(event);
+            }
+            if let Some(abi) = abi {
+                let (abi, _) = load_abi_from_file(&abi, None)?.remove(0);
+                abi.events().cloned().for_each(|event| decoder.add(event));
+            }
+            let follower = Follower {
+                from_block: block_number(&provider, cast.convert_block_number(from_block).await?)
+                    .await?,
+                to_block: block_number(&provider, cast.convert_block_number(to_block).await?)
+                    .await?,
+                decoder,
+                format: format.unwrap_or(if shell::is_json() {
+                    LogFormat::Json
+                } else {
+                    LogFormat::Table
+                }),
+                poll_interval: Duration::from_secs(poll_interval),
+            };
+            let filter = build_filter(None, None, address, sig_or_topic, topics_or_args)?;
+
+            let url = utils::get_rpc_url(&config)?;
+            if url.starts_with("ws") {
+                // see the `--subscribe` transport below
+                let provider = alloy_provider::ProviderBuilder::<_, _, AnyNetwork>::default()
+                    .connect(url.as_ref())
+                    .await?;
+                return follower.follow(&provider, filter, true).await;
+            }
+            return follower.follow(&provider, filter, false).await;
+        }
+
         let from_block =
             cast.convert_block_number(Some(from_block.unwrap_or_else(BlockId::earliest))).await?;
         let to_block =
@@ -83,7 +173,7 @@ pub async fn run(self) -> Result<()> {
         // FIXME: this is a hotfix for <https://github.com/foundry-rs/foundry/issues/7682>
         //  currently the alloy `eth_subscribe` impl does not work with all transports, so we use
         // the builtin transport here for now
-        let url = config.get_rpc_url_or_localhost_http()?;
+        let url = utils::get_rpc_url(&config)?;
         let provider = alloy_provider::ProviderBuilder::<_, _, AnyNetwork>::default()
             .connect(url.as_ref())
             .await?;
@@ -95,6 +185,231 @@ pub async fn run(self) -> Result<()> {
     }
 }
 
+/// Returns the number of a block, resolving block tags.
+async fn block_number<P: Provider<AnyNetwork>>(
+    provider: &P,
+    block: Option<BlockNumberOrTag>,
+) -> Result<Option<u64>> {
+    Ok(match block {
+        Some(BlockNumberOrTag::Number(number)) => Some(number),
+        Some(tag) => Some(
+            provider
+                .get_block(tag.into())
+                .await?
+                .ok_or_else(|| eyre::eyre!("block {tag} not found"))?
+                .header
+                .number,
+        ),
+        None => None,
+    })
+}
+
+/// Follows the logs of new blocks.
+struct Follower {
+    from_block: Option<u64>,
+    to_block: Option<u64>,
+    decoder: LogDecoder,
+    format: LogFormat,
+    poll_interval: Duration,
+}
+
+impl Follower {
+    /// Prints the logs of the filter in new blocks, until interrupted or `to_block` is reached.
+    ///
+    /// Waits for new blocks with a subscription if `subscribe` is set, or by polling.
+    async fn follow<P: Provider<AnyNetwork>>(
+        &self,
+        provider: &P,
+        filter: Filter,
+        subscribe: bool,
+    ) -> Result<()> {
+        let mut heads =
+            if subscribe { Some(provider.subscribe_blocks().await?.into_stream()) } else { None };
+        let mut next = match self.from_block {
+            Some(from_block) => from_block,
+            None => provider.get_block_number().await? + 1,
+        };
+        // the hashes of the last followed blocks
+        let mut followed = VecDeque::<(u64, B256)>::new();
+
+        loop {
+            if let Some(replay_from) = find_reorg(provider, &followed).await? {
+                sh_warn!("Reorg detected, replaying logs from block {replay_from}")?;
+                followed.retain(|(number, _)| *number < replay_from);
+                next = next.min(replay_from);
+            }
+
+            let latest = provider
+                .get_block(BlockId::latest())
+                .await?
+                .ok_or_else(|| eyre::eyre!("latest block not found"))?;
+            let head = match self.to_block {
+                Some(to_block) => latest.header.number.min(to_block),
+                None => latest.header.number,
+            };
+            if head >= next {
+                let logs =
+                    provider.get_logs(&filter.clone().from_block(next).to_block(head)).await?;
+                for log in &logs {
+                    self.print_log(log)?;
+                }
+                if head == latest.header.number {
+                    followed.push_back((head, latest.header.hash));
+                    if followed.len() > MAX_FOLLOWED_BLOCKS {
+                        followed.pop_front();
+                    }
+                }
+                next = head + 1;
+            }
+            if self.to_block.is_some_and(|to_block| next > to_block) {
+                break;
+            }
+
+            let new_block = async {
+                match &mut heads {
+                    Some(heads) => heads.next().await.is_some(),
+                    None => {
+                        tokio::time::sleep(self.poll_interval).await;
+                        true
+                    }
+                }
+            };
+            tokio::select! {
+                new_block = new_block => if !new_block { break },
+                _ = ctrl_c() => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn print_log(&self, log: &Log) -> Result<()> {
+        let decoded = self.decoder.decode(log.data());
+        match self.format {
+            LogFormat::Json => {
+                let log = FollowedLog {
+                    block_number: log.block_number,
+                    block_hash: log.block_hash,
+                    transaction_hash: log.transaction_hash,
+                    log_index: log.log_index,
+                    address: log.address(),
+                    event: decoded.as_ref().map(|(event, _)| event.signature()),
+                    args: decoded.map(|(_, args)| {
+                        args.iter()
+                            .map(|(name, value)| EventArg {
+                                name: name.clone(),
+                                value: format_token_raw(value),
+                            })
+                            .collect()
+                    }),
+                    topics: log.topics(),
+                    data: &log.data().data,
+                };
+                sh_println!("{}", serde_json::to_string(&log)?)?;
+            }
+            LogFormat::Table => {
+                let event = match decoded {
+                    Some((event, args)) => format!(
+                        "{}({})",
+                        event.name,
+                        args.iter()
+                            .map(|(name, value)| format!("{name}: {}", format_token_raw(value)))
+                            .format(", ")
+                    ),
+                    None => format!(
+                        "topics: [{}] data: {}",
+                        log.topics().iter().format(", "),
+                        log.data().data
+                    ),
+                };
+                sh_println!(
+                    "{:<10} {} {:<4} {} {event}",
+                    log.block_number.map(|number| number.to_string()).unwrap_or_default(),
+                    log.transaction_hash.unwrap_or_default(),
+                    log.log_index.map(|index| index.to_string()).unwrap_or_default(),
+                    log.address(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the first block to replay if a followed block was reorged, comparing the hashes of the
+/// followed blocks with the ones of the chain from the newest.
+async fn find_reorg<P: Provider<AnyNetwork>>(
+    provider: &P,
+    followed: &VecDeque<(u64, B256)>,
+) -> Result<Option<u64>> {
+    for (i, (number, hash)) in followed.iter().enumerate().rev() {
+        let block = provider.get_block((*number).into()).await?;
+        if block.is_some_and(|block| block.header.hash == *hash) {
+            return Ok((i + 1 < followed.len()).then_some(number + 1));
+        }
+    }
+    Ok(followed.front().map(|(number, _)| *number))
+}
+
+/// A followed log, as printed with `--format json`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FollowedLog<'a> {
+    block_number: Option<u64>,
+    block_hash: Option<B256>,
+    transaction_hash: Option<B256>,
+    log_index: Option<u64>,
+    address: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    args: Option<Vec<EventArg>>,
+    topics: &'a [B256],
+    data: &'a Bytes,
+}
+
+#[derive(Serialize)]
+struct EventArg {
+    name: String,
+    value: String,
+}
+
+/// Decodes logs with known events.
+#[derive(Debug, Default)]
+struct LogDecoder {
+    events: HashMap<B256, Vec<Event>>,
+}
+
+impl LogDecoder {
+    fn add(&mut self, event: Event) {
+        let events = self.events.entry(event.selector()).or_default();
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+
+    /// Decodes a log with the first matching event, returns the event and its named arguments.
+    fn decode(&self, log: &LogData) -> Option<(Event, Vec<(String, DynSolValue)>)> {
+        let events = self.events.get(log.topics().first()?)?;
+        events.iter().find_map(|event| {
+            // events from signatures may not mark their indexed parameters
+            let event = get_indexed_event(event.clone(), log);
+            let decoded = event.decode_log(log, false).ok()?;
+            let (mut indexed, mut body) = (decoded.indexed.into_iter(), decoded.body.into_iter());
+            let args = event
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, input)| {
+                    let value = if input.indexed { indexed.next() } else { body.next() }?;
+                    let name =
+                        if input.name.is_empty() { i.to_string() } else { input.name.clone() };
+                    Some((name, value))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some((event, args))
+        })
+    }
+}
+
 /// Builds a Filter by first trying to parse the `sig_or_topic` as an event signature. If
 /// successful, `topics_or_args` is parsed as indexed inputs and converted to topics. Otherwise,
 /// `sig_or_topic` is prepended to `topics_or_args` and used as raw topics.
@@ -420,4 +735,29 @@ fn test_build_filter_with_invalid_topic() {
 
         assert_eq!(err, "invalid string length");
     }
+
+    #[test]
+    fn decodes_followed_logs() {
+        let mut decoder = LogDecoder::default();
+        decoder.add(get_event("Transfer(address from, address to, uint256 value)").unwrap());
+
+        let address = Address::from_str(ADDRESS).unwrap();
+        let log = LogData::new_unchecked(
+            vec![B256::from_str(TRANSFER_TOPIC).unwrap(), address.into_word(), B256::ZERO],
+            U256::from(42).to_be_bytes_vec().into(),
+        );
+        let (event, args) = decoder.decode(&log).unwrap();
+        assert_eq!(event.signature(), "Transfer(address,address,uint256)");
+        assert_eq!(
+            args,
+            [
+                ("from".to_string(), DynSolValue::Address(address)),
+                ("to".to_string(), DynSolValue::Address(Address::ZERO)),
+                ("value".to_string(), DynSolValue::Uint(U256::from(42), 256)),
+            ]
+        );
+
+        let unknown = LogData::new_unchecked(vec![B256::ZERO], Bytes::new());
+        assert!(decoder.decode(&unknown).is_none());
+    }
 }
@@ -1,4 +1,7 @@
-use crate::tx::{self, CastTxBuilder};
+use crate::{
+    cmd::blob,
+    tx::{self, CastTxBuilder},
+};
 use alloy_network::{eip2718::Encodable2718, EthereumWallet, TransactionBuilder};
 use alloy_primitives::hex;
 use alloy_signer::Signer;
@@ -32,15 +35,19 @@ pub struct MakeTxArgs {
     #[command(flatten)]
     tx: TransactionOpts,
 
-    /// The path of blob data to be sent.
+    /// The file with the blobs of the transaction.
+    ///
+    /// Either a sidecar or blobs as written by `cast blob encode`, or raw data which is encoded
+    /// into blobs.
     #[arg(
         long,
-        value_name = "BLOB_DATA_PATH",
+        visible_alias = "path",
+        value_name = "BLOB_FILE",
         conflicts_with = "legacy",
         requires = "blob",
         help_heading = "Transaction options"
     )]
-    path: Option<PathBuf>,
+    blob_file: Option<PathBuf>,
 
     #[command(flatten)]
     eth: EthereumOpts,
@@ -70,9 +77,9 @@ pub enum MakeTxSubcommands {
 
 impl MakeTxArgs {
     pub async fn run(self) -> Result<()> {
-        let Self { to, mut sig, mut args, command, tx, path, eth, raw_unsigned } = self;
+        let Self { to, mut sig, mut args, command, tx, blob_file, eth, raw_unsigned } = self;
 
-        let blob_data = if let Some(path) = path { Some(std::fs::read(path)?) } else { None };
+        let blob_sidecar = blob_file.as_deref().map(blob::read_blob_file).transpose()?;
 
         let code = if let Some(MakeTxSubcommands::Create {
             code,
@@ -97,7 +104,7 @@ pub async fn run(self) -> Result<()> {
             .await?
             .with_code_sig_and_args(code, sig, args)
             .await?
-            .with_blob_data(blob_data)?;
+            .with_blob_sidecar(blob_sidecar);
 
         if raw_unsigned {
             // Build unsigned raw tx
@@ -8,15 +8,23 @@
 pub mod access_list;
 pub mod artifact;
 pub mod bind;
+pub mod blob;
+pub mod bundle;
 pub mod call;
+pub mod chain;
 pub mod constructor_args;
 pub mod create2;
 pub mod creation_code;
+pub mod ens;
 pub mod estimate;
+pub mod explain;
 pub mod find_block;
 pub mod interface;
+pub mod l1_fee;
 pub mod logs;
 pub mod mktx;
+pub mod multicall;
+pub mod proof;
 pub mod rpc;
 pub mod run;
 pub mod send;
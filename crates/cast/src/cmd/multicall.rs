@@ -0,0 +1,259 @@
+use alloy_dyn_abi::FunctionExt;
+use alloy_json_abi::Function;
+use alloy_network::TransactionBuilder;
+use alloy_primitives::{address, hex, Address, Bytes, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{
+    state::{AccountOverride, StateOverride},
+    BlockId, TransactionRequest,
+};
+use alloy_serde::WithOtherFields;
+use alloy_sol_types::{sol, SolCall};
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use foundry_cli::{
+    opts::RpcOpts,
+    utils::{self, LoadConfig},
+};
+use foundry_common::{
+    abi::{encode_function_args, get_func},
+    ens::NameOrAddress,
+    fmt::format_token_raw,
+    provider::RetryProvider,
+    stdin,
+};
+use foundry_evm::decode::RevertDecoder;
+use serde::Serialize;
+use std::{path::PathBuf, str::FromStr};
+
+/// The address of Multicall3, deployed at the same address on most chains.
+const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// Runtime code of a minimal batching contract, set at the Multicall3 address with a state
+/// override on chains where it's missing.
+///
+/// The calldata is a sequence of `target (32 bytes) || length (32 bytes) || data`, and the return
+/// data a sequence of `success (32 bytes) || length (32 bytes) || data`, one for each call.
+const BATCH_CALLER_CODE: &[u8] = &hex!("600060005b3681101561004c5780358160200135808360400185604001376000600082866040016000865af184523d84602001523d6000856040013e9050913d0160400191016040016004565b506000f3");
+
+sol! {
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
+    }
+}
+
+/// CLI arguments for `cast multicall`.
+#[derive(Clone, Debug, Parser)]
+pub struct MulticallArgs {
+    /// Path to the file with the calls, one per line as `ADDRESS SIGNATURE [ARGS...]`, e.g.
+    /// `0x6B17...1d0F balanceOf(address)(uint256) 0xd8dA...6045`.
+    ///
+    /// Empty lines and lines starting with `#` are ignored. Reads the calls from stdin if omitted.
+    file: Option<PathBuf>,
+
+    /// The block height to query at.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending.
+    #[arg(long, short)]
+    block: Option<BlockId>,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+/// A call of the batch.
+struct Call {
+    target: Address,
+    signature: String,
+    func: Function,
+    calldata: Bytes,
+}
+
+/// The result of a call of the batch.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CallResult {
+    target: Address,
+    signature: String,
+    success: bool,
+    return_data: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decoded: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl MulticallArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { file, block, rpc } = self;
+
+        let input = match &file {
+            Some(path) => foundry_common::fs::read_to_string(path)?,
+            None => stdin::read(false)?,
+        };
+
+        let config = rpc.load_config()?;
+        let provider = utils::get_provider(&config)?;
+        let block = block.unwrap_or_default();
+
+        let mut calls = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let call = parse_call(line, &provider)
+                .await
+                .wrap_err_with(|| format!("invalid call on line {}", i + 1))?;
+            calls.push(call);
+        }
+        eyre::ensure!(!calls.is_empty(), "no calls to make");
+
+        let results = if provider.get_code_at(MULTICALL3_ADDRESS).block_id(block).await?.is_empty()
+        {
+            batch_with_override(&provider, &calls, block).await?
+        } else {
+            aggregate3(&provider, &calls, block).await?
+        };
+
+        let results = calls
+            .into_iter()
+            .zip(results)
+            .map(|(call, (success, return_data))| {
+                let (decoded, error) = if !success {
+                    (None, Some(RevertDecoder::new().decode(&return_data, None)))
+                } else if call.func.outputs.is_empty() {
+                    (None, None)
+                } else {
+                    match call.func.abi_decode_output(&return_data, false) {
+                        Ok(values) => (Some(values.iter().map(format_token_raw).collect()), None),
+                        Err(err) => (None, Some(format!("could not decode output: {err}"))),
+                    }
+                };
+                CallResult {
+                    target: call.target,
+                    signature: call.signature,
+                    success,
+                    return_data,
+                    decoded,
+                    error,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        sh_println!("{}", serde_json::to_string_pretty(&results)?)?;
+        Ok(())
+    }
+}
+
+/// Parses a call from a line of the call list.
+async fn parse_call(line: &str, provider: &RetryProvider) -> Result<Call> {
+    let mut parts = line.split_whitespace();
+    let (Some(target), Some(signature)) = (parts.next(), parts.next()) else {
+        eyre::bail!("expected `ADDRESS SIGNATURE [ARGS...]`");
+    };
+    let target = NameOrAddress::from_str(target)?.resolve(provider).await?;
+    let func = get_func(signature)?;
+    let args = parts.collect::<Vec<_>>();
+    let calldata = encode_function_args(&func, &args)?.into();
+    Ok(Call { target, signature: signature.to_string(), func, calldata })
+}
+
+/// Batches the calls through the Multicall3 deployed on the chain.
+async fn aggregate3(
+    provider: &RetryProvider,
+    calls: &[Call],
+    block: BlockId,
+) -> Result<Vec<(bool, Bytes)>> {
+    let calls = calls
+        .iter()
+        .map(|call| IMulticall3::Call3 {
+            target: call.target,
+            allowFailure: true,
+            callData: call.calldata.clone(),
+        })
+        .collect();
+    let input = IMulticall3::aggregate3Call { calls }.abi_encode();
+    let tx = TransactionRequest::default().with_to(MULTICALL3_ADDRESS).with_input(input);
+
+    let output = provider.call(WithOtherFields::new(tx)).block(block).await?;
+    let results = IMulticall3::aggregate3Call::abi_decode_returns(&output, false)?.returnData;
+    Ok(results.into_iter().map(|result| (result.success, result.returnData)).collect())
+}
+
+/// Batches the calls through a minimal batching contract set at the Multicall3 address with a
+/// state override, for chains without Multicall3.
+async fn batch_with_override(
+    provider: &RetryProvider,
+    calls: &[Call],
+    block: BlockId,
+) -> Result<Vec<(bool, Bytes)>> {
+    let mut input = Vec::new();
+    for call in calls {
+        input.extend_from_slice(B256::left_padding_from(call.target.as_slice()).as_slice());
+        input.extend_from_slice(&U256::from(call.calldata.len()).to_be_bytes::<32>());
+        input.extend_from_slice(&call.calldata);
+    }
+    let tx = TransactionRequest::default().with_to(MULTICALL3_ADDRESS).with_input(input);
+
+    let mut state_override = StateOverride::default();
+    state_override.insert(
+        MULTICALL3_ADDRESS,
+        AccountOverride { code: Some(Bytes::from_static(BATCH_CALLER_CODE)), ..Default::default() },
+    );
+    let output = provider
+        .call(WithOtherFields::new(tx))
+        .block(block)
+        .overrides(state_override)
+        .await
+        .wrap_err("Multicall3 is not deployed and the RPC doesn't support state overrides")?;
+
+    decode_batch_output(&output, calls.len())
+}
+
+/// Decodes the return data of the batching contract.
+fn decode_batch_output(mut output: &[u8], len: usize) -> Result<Vec<(bool, Bytes)>> {
+    let mut results = Vec::with_capacity(len);
+    for _ in 0..len {
+        eyre::ensure!(output.len() >= 64, "unexpected end of the batch output");
+        let success = !output[..32].iter().all(|b| *b == 0);
+        let size = U256::from_be_slice(&output[32..64]).saturating_to::<usize>();
+        let data = output[64..]
+            .get(..size)
+            .ok_or_else(|| eyre::eyre!("unexpected end of the batch output"))?;
+        results.push((success, Bytes::copy_from_slice(data)));
+        output = &output[64 + size..];
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_batch_output() {
+        let mut output = Vec::new();
+        output.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+        output.extend_from_slice(&U256::from(2).to_be_bytes::<32>());
+        output.extend_from_slice(&[0x12, 0x34]);
+        output.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
+        output.extend_from_slice(&U256::ZERO.to_be_bytes::<32>());
+
+        let results = decode_batch_output(&output, 2).unwrap();
+        assert_eq!(results[0], (true, Bytes::from_static(&[0x12, 0x34])));
+        assert_eq!(results[1], (false, Bytes::new()));
+        assert!(decode_batch_output(&output[..70], 2).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use alloy_consensus::TrieAccount;
+use alloy_network::AnyNetwork;
+use alloy_primitives::{keccak256, Address, B256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockId, EIP1186AccountProofResponse};
+use alloy_trie::{proof::verify_proof, Nibbles, EMPTY_ROOT_HASH, KECCAK_EMPTY};
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use foundry_cli::{
+    opts::RpcOpts,
+    utils::{self, LoadConfig},
+};
+use foundry_common::{shell, stdin};
+use serde::Serialize;
+
+/// CLI arguments for `cast verify-proof`.
+#[derive(Clone, Debug, Parser)]
+pub struct VerifyProofArgs {
+    /// The proof as returned by `eth_getProof` or `cast proof`, either as JSON or as a path to a
+    /// JSON file.
+    ///
+    /// Reads the proof from stdin if omitted.
+    proof: Option<String>,
+
+    /// The state root to verify the proof against.
+    ///
+    /// If omitted, the state root of the block is fetched from the RPC.
+    #[arg(long, conflicts_with = "block")]
+    state_root: Option<B256>,
+
+    /// The block height of the proof.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending.
+    #[arg(long, short = 'B')]
+    block: Option<BlockId>,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+impl VerifyProofArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { proof, state_root, block, rpc } = self;
+
+        let proof = match proof {
+            Some(proof) if proof.trim_start().starts_with('{') => proof,
+            Some(path) => foundry_common::fs::read_to_string(path)?,
+            None => stdin::read(false)?,
+        };
+        let proof: EIP1186AccountProofResponse =
+            serde_json::from_str(&proof).wrap_err("invalid account proof")?;
+
+        let state_root = match state_root {
+            Some(state_root) => state_root,
+            None => {
+                let config = rpc.load_config()?;
+                let provider = utils::get_provider(&config)?;
+                state_root_at(&provider, block.unwrap_or_default()).await?.1
+            }
+        };
+
+        print_checks(&verify_account_proof(&proof, state_root))
+    }
+}
+
+/// The result of the verification of a proof of the account or of one of its storage slots.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProofCheck {
+    /// The address of the account, or the storage slot.
+    pub key: B256,
+    /// Whether this is the proof of a storage slot.
+    pub storage: bool,
+    /// Why the proof is invalid, if it is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProofCheck {
+    fn new(key: B256, storage: bool, result: Result<(), impl std::fmt::Display>) -> Self {
+        Self { key, storage, error: result.err().map(|err| err.to_string()) }
+    }
+}
+
+/// Returns the number and state root of a block.
+pub async fn state_root_at<P: Provider<AnyNetwork>>(
+    provider: &P,
+    block: BlockId,
+) -> Result<(u64, B256)> {
+    let block =
+        provider.get_block(block).await?.ok_or_else(|| eyre::eyre!("block {block:?} not found"))?;
+    Ok((block.header.number, block.header.state_root))
+}
+
+/// Verifies the account proof against the state root, and the storage proofs against the storage
+/// root of the account.
+pub fn verify_account_proof(
+    proof: &EIP1186AccountProofResponse,
+    state_root: B256,
+) -> Vec<ProofCheck> {
+    let account_key = Nibbles::unpack(keccak256(proof.address));
+    let account = verify_proof(state_root, account_key, account_value(proof), &proof.account_proof);
+
+    let mut checks = vec![ProofCheck::new(proof.address.into_word(), false, account)];
+    for storage in &proof.storage_proof {
+        let key = storage.key.as_b256();
+        // zero values are not stored in the trie
+        let value = (!storage.value.is_zero()).then(|| alloy_rlp::encode(storage.value));
+        let result = verify_proof(
+            proof.storage_hash,
+            Nibbles::unpack(keccak256(key)),
+            value,
+            &storage.proof,
+        );
+        checks.push(ProofCheck::new(key, true, result));
+    }
+    checks
+}
+
+/// Returns the RLP encoded account of the proof, `None` for an account that doesn't exist.
+fn account_value(proof: &EIP1186AccountProofResponse) -> Option<Vec<u8>> {
+    // nodes return either empty or zero hashes for accounts that don't exist
+    let is_empty = proof.nonce == 0 &&
+        proof.balance.is_zero() &&
+        (proof.code_hash == KECCAK_EMPTY || proof.code_hash.is_zero()) &&
+        (proof.storage_hash == EMPTY_ROOT_HASH || proof.storage_hash.is_zero());
+    if is_empty {
+        return None;
+    }
+
+    let account = TrieAccount {
+        nonce: proof.nonce,
+        balance: proof.balance,
+        storage_root: proof.storage_hash,
+        code_hash: proof.code_hash,
+    };
+    Some(alloy_rlp::encode(account))
+}
+
+/// Prints the results of the verification, and fails if any proof is invalid.
+pub fn print_checks(checks: &[ProofCheck]) -> Result<()> {
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string_pretty(checks)?)?;
+    } else {
+        for check in checks {
+            let name = if check.storage {
+                format!("Storage slot {}", check.key)
+            } else {
+                format!("Account {}", Address::from_word(check.key))
+            };
+            match &check.error {
+                None => sh_println!("{name}: valid")?,
+                Some(err) => sh_println!("{name}: invalid ({err})")?,
+            }
+        }
+    }
+
+    let invalid = checks.iter().filter(|check| check.error.is_some()).count();
+    if invalid > 0 {
+        eyre::bail!("proof verification failed: {invalid} of {} proofs are invalid", checks.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+    use alloy_trie::{proof::ProofRetainer, HashBuilder};
+
+    #[test]
+    fn verifies_account_proofs() {
+        let mut proof = EIP1186AccountProofResponse {
+            address: address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+            balance: U256::from(10).pow(U256::from(22)),
+            code_hash: KECCAK_EMPTY,
+            nonce: 3,
+            storage_hash: EMPTY_ROOT_HASH,
+            ..Default::default()
+        };
+
+        // a state with the account alone
+        let key = Nibbles::unpack(keccak256(proof.address));
+        let mut builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![key.clone()]));
+        builder.add_leaf(key, &account_value(&proof).unwrap());
+        let state_root = builder.root();
+        proof.account_proof = builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+
+        let checks = verify_account_proof(&proof, state_root);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].error, None);
+        assert!(print_checks(&checks).is_ok());
+
+        proof.balance += U256::from(1);
+        let checks = verify_account_proof(&proof, state_root);
+        assert!(checks[0].error.is_some());
+        assert!(print_checks(&checks).is_err());
+    }
+}
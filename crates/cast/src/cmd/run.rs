@@ -1,5 +1,6 @@
 use crate::{
-    revm::primitives::EnvWithHandlerCfg, utils::apply_chain_and_block_specific_env_changes,
+    revm::primitives::EnvWithHandlerCfg, state_override::StateOverrideArgs,
+    utils::apply_chain_and_block_specific_env_changes,
 };
 use alloy_consensus::Transaction;
 use alloy_network::{AnyNetwork, TransactionResponse};
@@ -20,7 +21,7 @@
         value::{Dict, Map},
         Figment, Metadata, Profile,
     },
-    Config,
+    Chain, Config,
 };
 use foundry_evm::{
     executors::{EvmError, TracingExecutor},
@@ -33,43 +34,46 @@
 #[derive(Clone, Debug, Parser)]
 pub struct RunArgs {
     /// The transaction hash.
-    tx_hash: String,
+    pub(crate) tx_hash: String,
 
     /// Opens the transaction in the debugger.
     #[arg(long, short)]
-    debug: bool,
+    pub(crate) debug: bool,
 
     /// Whether to identify internal functions in traces.
     #[arg(long)]
-    decode_internal: bool,
+    pub(crate) decode_internal: bool,
 
     /// Print out opcode traces.
     #[arg(long, short)]
-    trace_printer: bool,
+    pub(crate) trace_printer: bool,
 
     /// Executes the transaction only with the state from the previous block.
     ///
     /// May result in different results than the live execution!
     #[arg(long)]
-    quick: bool,
+    pub(crate) quick: bool,
 
     /// Label addresses in the trace.
     ///
     /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:vitalik.eth
     #[arg(long, short)]
-    label: Vec<String>,
+    pub(crate) label: Vec<String>,
 
     #[command(flatten)]
-    etherscan: EtherscanOpts,
+    pub(crate) etherscan: EtherscanOpts,
 
     #[command(flatten)]
-    rpc: RpcOpts,
+    pub(crate) rpc: RpcOpts,
+
+    #[command(flatten)]
+    pub(crate) state_override: StateOverrideArgs,
 
     /// The EVM version to use.
     ///
     /// Overrides the version specified in the config.
     #[arg(long)]
-    evm_version: Option<EvmVersion>,
+    pub(crate) evm_version: Option<EvmVersion>,
 
     /// Sets the number of assumed available compute units per second for this provider
     ///
@@ -107,7 +111,26 @@ impl RunArgs {
     ///
     /// Note: This executes the transaction(s) as is: Cheatcodes are disabled
     pub async fn run(self) -> Result<()> {
-        let figment = Into::<Figment>::into(&self.rpc).merge(&self);
+        let (result, config, chain) = self.replay().await?;
+
+        handle_traces(
+            result,
+            &config,
+            chain,
+            self.label,
+            self.with_local_artifacts,
+            self.debug,
+            self.decode_internal,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replays the transaction and returns its traces, along with the config and the chain it was
+    /// replayed with.
+    pub(crate) async fn replay(&self) -> Result<(TraceResult, Config, Option<Chain>)> {
+        let figment = Into::<Figment>::into(&self.rpc).merge(self);
         let evm_opts = figment.extract::<EvmOpts>()?;
         let mut config = Config::from_provider(figment)?.sanitized();
 
@@ -253,6 +276,9 @@ pub async fn run(self) -> Result<()> {
             }
         }
 
+        // Modify the state right before the transaction
+        self.state_override.apply(&mut executor)?;
+
         // Execute our transaction
         let result = {
             executor.set_trace_printer(self.trace_printer);
@@ -266,20 +292,10 @@ pub async fn run(self) -> Result<()> {
                 trace!(tx=?tx.tx_hash(), "executing create transaction");
                 TraceResult::try_from(executor.deploy_with_env(env, None))?
             }
-        };
-
-        handle_traces(
-            result,
-            &config,
-            chain,
-            self.label,
-            self.with_local_artifacts,
-            self.debug,
-            self.decode_internal,
-        )
-        .await?;
+        }
+        .with_runtime_codes(&executor);
 
-        Ok(())
+        Ok((result, config, chain))
     }
 }
 
@@ -1,21 +1,28 @@
 use crate::{
-    tx::{self, CastTxBuilder},
+    cmd::blob,
+    tx::{self, CastTxBuilder, SenderKind},
     Cast,
 };
 use alloy_network::{AnyNetwork, EthereumWallet};
+use alloy_primitives::{Address, TxHash, U64};
 use alloy_provider::{Provider, ProviderBuilder};
 use alloy_rpc_types::TransactionRequest;
 use alloy_serde::WithOtherFields;
 use alloy_signer::Signer;
 use clap::Parser;
-use eyre::Result;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Cell, Table};
+use eyre::{Result, WrapErr};
 use foundry_cli::{
     opts::{EthereumOpts, TransactionOpts},
     utils,
     utils::LoadConfig,
 };
-use foundry_common::ens::NameOrAddress;
-use std::{path::PathBuf, str::FromStr};
+use foundry_common::{ens::NameOrAddress, provider::RetryProvider, shell};
+use foundry_config::Config;
+use foundry_wallets::WalletSigner;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, str::FromStr, time::Duration};
 
 /// CLI arguments for `cast send`.
 #[derive(Debug, Parser)]
@@ -51,21 +58,91 @@ pub struct SendTxArgs {
     #[arg(long, env = "ETH_TIMEOUT")]
     pub timeout: Option<u64>,
 
+    /// Create the access list of the transaction, and include it if it lowers the gas estimate.
+    #[arg(long, conflicts_with_all = ["access_list", "legacy", "batch_txs", "batch_file"])]
+    auto_access_list: bool,
+
     #[command(flatten)]
     tx: TransactionOpts,
 
     #[command(flatten)]
     eth: EthereumOpts,
 
-    /// The path of blob data to be sent.
+    /// The file with the blobs of the transaction.
+    ///
+    /// Either a sidecar or blobs as written by `cast blob encode`, or raw data which is encoded
+    /// into blobs.
     #[arg(
         long,
-        value_name = "BLOB_DATA_PATH",
+        visible_alias = "path",
+        value_name = "BLOB_FILE",
         conflicts_with = "legacy",
         requires = "blob",
         help_heading = "Transaction options"
     )]
-    path: Option<PathBuf>,
+    blob_file: Option<PathBuf>,
+
+    /// A transaction to send in a batch, as `TO [SIG] [ARGS...]`. Can be repeated.
+    ///
+    /// The transactions of the batch get sequential nonces, starting from `--nonce` or the pending
+    /// nonce of the sender, and are submitted concurrently. Their receipts are then reported in a
+    /// table, unless `--async` is set.
+    #[arg(
+        long = "batch",
+        value_name = "TX",
+        conflicts_with_all = ["to", "blob_file"],
+        help_heading = "Batch options"
+    )]
+    batch_txs: Vec<String>,
+
+    /// Path to a JSON file of transactions to send in a batch, see `--batch`.
+    ///
+    /// The file holds an array of transactions like `{"to": "0x...", "sig":
+    /// "transfer(address,uint256)", "args": ["0x...", "1"], "value": "1ether"}`, where `sig`,
+    /// `args` and `value` are optional.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["to", "blob_file"],
+        help_heading = "Batch options"
+    )]
+    batch_file: Option<PathBuf>,
+}
+
+/// A transaction of a batch.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct BatchTx {
+    pub(crate) to: String,
+    #[serde(default)]
+    pub(crate) sig: Option<String>,
+    #[serde(default)]
+    pub(crate) args: Vec<String>,
+    #[serde(default)]
+    pub(crate) value: Option<String>,
+}
+
+impl FromStr for BatchTx {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.split_whitespace().map(String::from);
+        let to = parts.next().ok_or_else(|| eyre::eyre!("expected `TO [SIG] [ARGS...]`"))?;
+        Ok(Self { to, sig: parts.next(), args: parts.collect(), value: None })
+    }
+}
+
+/// The outcome of a transaction of a batch.
+#[derive(Debug, Serialize)]
+struct BatchTxResult {
+    nonce: u64,
+    to: String,
+    sig: Option<String>,
+    tx_hash: Option<TxHash>,
+    status: &'static str,
+    block_number: Option<u64>,
+    gas_used: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 #[derive(Debug, Parser)]
@@ -97,11 +174,22 @@ pub async fn run(self) -> eyre::Result<()> {
             confirmations,
             command,
             unlocked,
-            path,
+            blob_file,
             timeout,
+            auto_access_list,
+            batch_txs,
+            batch_file,
         } = self;
 
-        let blob_data = if let Some(path) = path { Some(std::fs::read(path)?) } else { None };
+        let mut batch = match &batch_file {
+            Some(path) => foundry_common::fs::read_json_file::<Vec<BatchTx>>(path)?,
+            None => Vec::new(),
+        };
+        for tx in &batch_txs {
+            batch.push(tx.parse().wrap_err_with(|| format!("invalid batch transaction `{tx}`"))?);
+        }
+
+        let blob_sidecar = blob_file.as_deref().map(blob::read_blob_file).transpose()?;
 
         let code = if let Some(SendTxSubcommands::Create {
             code,
@@ -118,6 +206,23 @@ pub async fn run(self) -> eyre::Result<()> {
 
         let config = eth.load_config()?;
         let provider = utils::get_provider(&config)?;
+        let timeout = timeout.unwrap_or(config.transaction_timeout);
+
+        if !batch.is_empty() {
+            eyre::ensure!(code.is_none(), "contracts can't be deployed in a batch");
+            return send_batch(
+                provider,
+                &config,
+                &eth,
+                tx,
+                batch,
+                unlocked,
+                cast_async,
+                confirmations,
+                timeout,
+            )
+            .await;
+        }
 
         let builder = CastTxBuilder::new(&provider, tx, &config)
             .await?
@@ -125,9 +230,8 @@ pub async fn run(self) -> eyre::Result<()> {
             .await?
             .with_code_sig_and_args(code, sig, args)
             .await?
-            .with_blob_data(blob_data)?;
-
-        let timeout = timeout.unwrap_or(config.transaction_timeout);
+            .with_blob_sidecar(blob_sidecar)
+            .with_auto_access_list(auto_access_list);
 
         // Case 1:
         // Default to sending via eth_sendTransaction if the --unlocked flag is passed.
@@ -201,3 +305,173 @@ async fn cast_send<P: Provider<AnyNetwork>>(
 
     Ok(())
 }
+
+/// Sends a batch of transactions with sequential nonces, either through the unlocked `--from`
+/// account or signed with the wallet.
+#[expect(clippy::too_many_arguments)]
+async fn send_batch(
+    provider: RetryProvider,
+    config: &Config,
+    eth: &EthereumOpts,
+    tx_opts: TransactionOpts,
+    batch: Vec<BatchTx>,
+    unlocked: bool,
+    cast_async: bool,
+    confs: u64,
+    timeout: u64,
+) -> Result<()> {
+    if unlocked {
+        let txs = build_batch(&provider, config, tx_opts, &batch, config.sender, None).await?;
+        cast_send_batch(&provider, batch, txs, cast_async, confs, timeout).await
+    } else {
+        let signer = eth.wallet.signer().await?;
+        tx::validate_from_address(eth.wallet.from, signer.address())?;
+
+        let txs = build_batch(&provider, config, tx_opts, &batch, signer.address(), Some(&signer))
+            .await?;
+
+        let wallet = EthereumWallet::from(signer);
+        let provider =
+            ProviderBuilder::<_, _, AnyNetwork>::default().wallet(wallet).on_provider(&provider);
+        cast_send_batch(&provider, batch, txs, cast_async, confs, timeout).await
+    }
+}
+
+/// Builds the transactions of the batch, with sequential nonces starting from `--nonce` or the
+/// pending nonce of the sender.
+pub(crate) async fn build_batch(
+    provider: &RetryProvider,
+    config: &Config,
+    tx_opts: TransactionOpts,
+    batch: &[BatchTx],
+    from: Address,
+    signer: Option<&WalletSigner>,
+) -> Result<Vec<WithOtherFields<TransactionRequest>>> {
+    let first_nonce = match tx_opts.nonce {
+        Some(nonce) => nonce.to(),
+        None => provider.get_transaction_count(from).pending().await?,
+    };
+
+    let mut txs = Vec::with_capacity(batch.len());
+    for (i, batch_tx) in batch.iter().enumerate() {
+        let mut tx_opts = tx_opts.clone();
+        tx_opts.nonce = Some(U64::from(first_nonce + i as u64));
+        if let Some(value) = &batch_tx.value {
+            tx_opts.value = Some(utils::parse_ether_value(value)?);
+        }
+
+        let to = NameOrAddress::from_str(&batch_tx.to)?;
+        let (tx, _) = CastTxBuilder::new(provider, tx_opts, config)
+            .await?
+            .with_to(Some(to))
+            .await?
+            .with_code_sig_and_args(None, batch_tx.sig.clone(), batch_tx.args.clone())
+            .await?
+            .build(signer.map_or(SenderKind::Address(from), SenderKind::Signer))
+            .await
+            .wrap_err_with(|| format!("failed to build transaction {} of the batch", i + 1))?;
+        txs.push(tx);
+    }
+    Ok(txs)
+}
+
+/// Submits the transactions of the batch concurrently, then waits for their receipts and reports
+/// them, unless `cast_async` is set.
+async fn cast_send_batch<P: Provider<AnyNetwork>>(
+    provider: &P,
+    batch: Vec<BatchTx>,
+    txs: Vec<WithOtherFields<TransactionRequest>>,
+    cast_async: bool,
+    confs: u64,
+    timeout: u64,
+) -> Result<()> {
+    let nonces = txs.iter().map(|tx| tx.nonce.unwrap_or_default()).collect::<Vec<_>>();
+    let pending = join_all(txs.into_iter().map(|tx| provider.send_transaction(tx))).await;
+
+    let results = join_all(pending.into_iter().zip(batch).zip(nonces).map(
+        |((pending, batch_tx), nonce)| async move {
+            let mut result = BatchTxResult {
+                nonce,
+                to: batch_tx.to,
+                sig: batch_tx.sig,
+                tx_hash: None,
+                status: "failed",
+                block_number: None,
+                gas_used: None,
+                error: None,
+            };
+            let pending = match pending {
+                Ok(pending) => pending,
+                Err(err) => {
+                    result.error = Some(err.to_string());
+                    return result;
+                }
+            };
+            result.tx_hash = Some(*pending.tx_hash());
+            if cast_async {
+                result.status = "pending";
+                return result;
+            }
+
+            match pending
+                .with_required_confirmations(confs)
+                .with_timeout(Some(Duration::from_secs(timeout)))
+                .get_receipt()
+                .await
+            {
+                Ok(receipt) => {
+                    result.status = if receipt.inner.inner.inner.receipt.status.coerce_status() {
+                        "success"
+                    } else {
+                        "reverted"
+                    };
+                    result.block_number = receipt.block_number;
+                    result.gas_used = Some(receipt.gas_used);
+                }
+                Err(err) => result.error = Some(err.to_string()),
+            }
+            result
+        },
+    ))
+    .await;
+
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string_pretty(&results)?)?;
+    } else {
+        let mut table = Table::new();
+        table.apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_header(vec![
+            Cell::new("Nonce"),
+            Cell::new("To"),
+            Cell::new("Function"),
+            Cell::new("Tx Hash"),
+            Cell::new("Status"),
+            Cell::new("Block"),
+            Cell::new("Gas Used"),
+        ]);
+        for result in &results {
+            table.add_row([
+                result.nonce.to_string(),
+                result.to.clone(),
+                result.sig.clone().unwrap_or_default(),
+                result.tx_hash.map(|hash| format!("{hash:#x}")).unwrap_or_default(),
+                result.status.to_string(),
+                result.block_number.map(|block| block.to_string()).unwrap_or_default(),
+                result.gas_used.map(|gas| gas.to_string()).unwrap_or_default(),
+            ]);
+        }
+        sh_println!("\n{table}\n")?;
+        for result in &results {
+            if let Some(error) = &result.error {
+                sh_err!("transaction with nonce {} failed: {error}", result.nonce)?;
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|result| matches!(result.status, "failed" | "reverted"));
+    let failed = failed.count();
+    if failed > 0 {
+        eyre::bail!("{failed} of {} transactions of the batch failed", results.len());
+    }
+    Ok(())
+}
@@ -1,4 +1,8 @@
-use crate::{opts::parse_slot, Cast};
+use crate::{
+    opts::parse_slot,
+    storage_layout::{decode_storage, DecodeOptions, DecodedSlot, SourceStructs},
+    Cast,
+};
 use alloy_network::AnyNetwork;
 use alloy_primitives::{Address, B256, U256};
 use alloy_provider::Provider;
@@ -19,12 +23,12 @@
     shell,
 };
 use foundry_compilers::{
-    artifacts::{ConfigurableContractArtifact, Contract, StorageLayout},
+    artifacts::{ast::Ast, ConfigurableContractArtifact, Contract, StorageLayout},
     compilers::{
         solc::{Solc, SolcCompiler},
         Compiler,
     },
-    Artifact, Project,
+    Artifact, Project, ProjectCompileOutput,
 };
 use foundry_config::{
     figment::{self, value::Dict, Metadata, Profile},
@@ -32,7 +36,10 @@
 };
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 /// The minimum Solc version for outputting storage layouts.
 ///
@@ -47,7 +54,7 @@ pub struct StorageArgs {
     address: NameOrAddress,
 
     /// The storage slot number. If not provided, it gets the full storage layout.
-    #[arg(value_parser = parse_slot)]
+    #[arg(value_parser = parse_slot, conflicts_with = "decode")]
     slot: Option<B256>,
 
     /// The known proxy address. If provided, the storage layout is retrieved from this address.
@@ -60,6 +67,22 @@ pub struct StorageArgs {
     #[arg(long, short)]
     block: Option<BlockId>,
 
+    /// Decode every variable of the storage layout, following strings, dynamic arrays, structs
+    /// and ERC-7201 namespaces.
+    #[arg(long)]
+    decode: bool,
+
+    /// Keys of a mapping to decode, as `NAME=KEY[,KEY...]`.
+    ///
+    /// Nested mappings and mappings in arrays are named with `[]` for each level, e.g.
+    /// `allowance[]=0x...` for the inner mapping of `allowance`, and struct members with `.`.
+    #[arg(long = "key", value_name = "NAME=KEYS", requires = "decode")]
+    keys: Vec<String>,
+
+    /// The maximum number of elements of dynamic arrays to decode.
+    #[arg(long, default_value_t = 100, requires = "decode")]
+    max_array_length: usize,
+
     #[command(flatten)]
     rpc: RpcOpts,
 
@@ -90,7 +113,15 @@ impl StorageArgs {
     pub async fn run(self) -> Result<()> {
         let config = self.load_config()?;
 
-        let Self { address, slot, block, build, .. } = self;
+        let Self { address, slot, block, build, decode, .. } = self;
+        let decode = decode
+            .then(|| -> Result<_> {
+                Ok(DecodeOptions {
+                    keys: DecodeOptions::parse_keys(&self.keys)?,
+                    max_array_length: self.max_array_length,
+                })
+            })
+            .transpose()?;
         let provider = utils::get_provider(&config)?;
         let address = address.resolve(&provider).await?;
 
@@ -114,11 +145,21 @@ pub async fn run(self) -> Result<()> {
         if project.paths.has_input_files() {
             // Find in artifacts and pretty print
             add_storage_layout_output(&mut project);
+            if decode.is_some() {
+                add_ast_output(&mut project);
+            }
             let out = ProjectCompiler::new().quiet(shell::is_json()).compile(&project)?;
-            let artifact = out.artifacts().find(|(_, artifact)| {
+            let artifact = out.artifact_ids().find(|(_, artifact)| {
                 artifact.get_deployed_bytecode_bytes().is_some_and(|b| *b == address_code)
             });
-            if let Some((_, artifact)) = artifact {
+            if let Some((id, artifact)) = artifact {
+                if let Some(opts) = &decode {
+                    let sources = source_structs(&out, project.root(), &id.source, &id.name);
+                    return decode_and_print_storage(
+                        provider, address, block, artifact, &sources, opts,
+                    )
+                    .await;
+                }
                 return fetch_and_print_storage(
                     provider,
                     address,
@@ -156,6 +197,9 @@ pub async fn run(self) -> Result<()> {
         let root_path = root.path();
         let mut project = etherscan_project(metadata, root_path)?;
         add_storage_layout_output(&mut project);
+        if decode.is_some() {
+            add_ast_output(&mut project);
+        }
 
         project.compiler = if auto_detect {
             SolcCompiler::AutoDetect
@@ -165,10 +209,10 @@ pub async fn run(self) -> Result<()> {
 
         // Compile
         let mut out = ProjectCompiler::new().quiet(true).compile(&project)?;
-        let artifact = {
-            let (_, mut artifact) = out
-                .artifacts()
-                .find(|(name, _)| name == &metadata.contract_name)
+        let (id, artifact) = {
+            let (mut id, mut artifact) = out
+                .artifact_ids()
+                .find(|(id, _)| id.name == metadata.contract_name)
                 .ok_or_else(|| eyre::eyre!("Could not find artifact"))?;
 
             if is_storage_layout_empty(&artifact.storage_layout) && auto_detect {
@@ -178,20 +222,29 @@ pub async fn run(self) -> Result<()> {
                 project.compiler = SolcCompiler::Specific(solc);
                 if let Ok(output) = ProjectCompiler::new().quiet(true).compile(&project) {
                     out = output;
-                    let (_, new_artifact) = out
-                        .artifacts()
-                        .find(|(name, _)| name == &metadata.contract_name)
+                    let (new_id, new_artifact) = out
+                        .artifact_ids()
+                        .find(|(id, _)| id.name == metadata.contract_name)
                         .ok_or_else(|| eyre::eyre!("Could not find artifact"))?;
+                    id = new_id;
                     artifact = new_artifact;
                 }
             }
 
-            artifact
+            (id, artifact)
         };
+        let sources = decode
+            .is_some()
+            .then(|| source_structs(&out, project.root(), &id.source, &id.name))
+            .unwrap_or_default();
 
         // Clear temp directory
         root.close()?;
 
+        if let Some(opts) = &decode {
+            return decode_and_print_storage(provider, address, block, artifact, &sources, opts)
+                .await;
+        }
         fetch_and_print_storage(provider, address, block, artifact, !shell::is_json()).await
     }
 }
@@ -252,6 +305,23 @@ async fn fetch_and_print_storage<P: Provider<AnyNetwork>>(
     }
 }
 
+async fn decode_and_print_storage<P: Provider<AnyNetwork>>(
+    provider: P,
+    address: Address,
+    block: Option<BlockId>,
+    artifact: &ConfigurableContractArtifact,
+    sources: &SourceStructs,
+    opts: &DecodeOptions,
+) -> Result<()> {
+    if is_storage_layout_empty(&artifact.storage_layout) && !sources.has_namespaces() {
+        sh_warn!("Storage layout is empty.")?;
+        return Ok(());
+    }
+    let layout = artifact.storage_layout.clone().unwrap_or_default();
+    let slots = decode_storage(provider, address, block, &layout, sources, opts).await?;
+    print_decoded_storage(&slots)
+}
+
 async fn fetch_storage_slots<P: Provider<AnyNetwork>>(
     provider: P,
     address: Address,
@@ -331,6 +401,68 @@ fn print_storage(layout: StorageLayout, values: Vec<StorageValue>, pretty: bool)
     Ok(())
 }
 
+fn print_decoded_storage(slots: &[DecodedSlot]) -> Result<()> {
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string_pretty(slots)?)?;
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.apply_modifier(UTF8_ROUND_CORNERS);
+
+    table.set_header(vec![
+        Cell::new("Name"),
+        Cell::new("Type"),
+        Cell::new("Slot"),
+        Cell::new("Offset"),
+        Cell::new("Bytes"),
+        Cell::new("Value"),
+        Cell::new("Hex Value"),
+        Cell::new("Contract"),
+    ]);
+
+    for slot in slots {
+        // derived slots are hashes, too long to be readable in decimal
+        let slot_number = match u64::try_from(slot.slot) {
+            Ok(slot) => slot.to_string(),
+            Err(_) => format!("{:#x}", slot.slot),
+        };
+        table.add_row([
+            slot.name.as_str(),
+            &slot.ty,
+            &slot_number,
+            &slot.offset.to_string(),
+            &slot.bytes.to_string(),
+            &slot.value,
+            &slot.raw.map(|raw| raw.to_string()).unwrap_or_default(),
+            &slot.contract,
+        ]);
+    }
+
+    sh_println!("\n{table}\n")?;
+
+    Ok(())
+}
+
+/// Collects the structs and ERC-7201 namespaces of the contract `name` in `source` from the ASTs
+/// of the compiled artifacts.
+fn source_structs(
+    out: &ProjectCompileOutput<impl Compiler>,
+    root: &Path,
+    source: &Path,
+    name: &str,
+) -> SourceStructs {
+    let mut asts = Vec::<(PathBuf, Ast)>::new();
+    for (id, artifact) in out.artifact_ids() {
+        let Some(ast) = &artifact.ast else { continue };
+        let path = id.source.strip_prefix(root).unwrap_or(&id.source);
+        if !asts.iter().any(|(p, _)| p == path) {
+            asts.push((path.to_path_buf(), ast.clone()));
+        }
+    }
+    SourceStructs::new(&asts, source.strip_prefix(root).unwrap_or(source), name)
+}
+
 fn add_storage_layout_output<C: Compiler<CompilerContract = Contract>>(project: &mut Project<C>) {
     project.artifacts.additional_values.storage_layout = true;
     project.update_output_selection(|selection| {
@@ -342,6 +474,17 @@ fn add_storage_layout_output<C: Compiler<CompilerContract = Contract>>(project:
     })
 }
 
+/// Adds the AST to the compiler output, to read the structs and ERC-7201 namespaces of the
+/// sources.
+fn add_ast_output<C: Compiler<CompilerContract = Contract>>(project: &mut Project<C>) {
+    project.artifacts.additional_values.ast = true;
+    project.update_output_selection(|selection| {
+        selection.0.values_mut().for_each(|contract_selection| {
+            contract_selection.entry(String::new()).or_default().push("ast".to_string())
+        });
+    })
+}
+
 fn is_storage_layout_empty(storage_layout: &Option<StorageLayout>) -> bool {
     if let Some(ref s) = storage_layout {
         s.storage.is_empty()
@@ -1,16 +1,28 @@
-use alloy_primitives::Address;
-use alloy_provider::ext::TxPoolApi;
+use alloy_consensus::Transaction;
+use alloy_network::{AnyRpcTransaction, TransactionResponse};
+use alloy_primitives::{utils::format_units, Address, Bytes, TxKind, B256, U256};
+use alloy_provider::{ext::TxPoolApi, Provider};
 use clap::Parser;
 use foundry_cli::{
     opts::RpcOpts,
     utils::{self, LoadConfig},
 };
+use foundry_common::{provider::RetryProvider, shell};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use yansi::Paint;
 
 /// CLI arguments for `cast tx-pool`.
 #[derive(Debug, Parser, Clone)]
 pub enum TxPoolSubcommands {
     /// Fetches the content of the transaction pool.
+    ///
+    /// Transactions are grouped by sender and ordered by nonce, and the nonces missing between
+    /// the account nonce and the queued transactions are highlighted.
     Content {
+        /// Only show the transactions of this sender.
+        #[arg(short, long)]
+        from: Option<Address>,
         #[command(flatten)]
         args: RpcOpts,
     },
@@ -24,6 +36,9 @@ pub enum TxPoolSubcommands {
     },
     /// Fetches a textual summary of each transaction in the pool.
     Inspect {
+        /// Only show the transactions of this sender.
+        #[arg(short, long)]
+        from: Option<Address>,
         #[command(flatten)]
         args: RpcOpts,
     },
@@ -37,28 +52,219 @@ pub enum TxPoolSubcommands {
 impl TxPoolSubcommands {
     pub async fn run(self) -> eyre::Result<()> {
         match self {
-            Self::Content { args } => {
-                let config = args.load_config()?;
-                let provider = utils::get_provider(&config)?;
-                sh_println!("{:#?}", provider.txpool_content().await?)?;
-            }
-            Self::ContentFrom { from, args } => {
+            Self::Content { from, args } => content(from, args).await?,
+            Self::ContentFrom { from, args } => content(Some(from), args).await?,
+            Self::Inspect { from, args } => {
                 let config = args.load_config()?;
                 let provider = utils::get_provider(&config)?;
-                sh_println!("{:#?}", provider.txpool_content_from(from).await?)?;
-            }
-            Self::Inspect { args } => {
-                let config = args.load_config()?;
-                let provider = utils::get_provider(&config)?;
-                sh_println!("{:#?}", provider.txpool_inspect().await?)?;
+                let inspect = provider.txpool_inspect().await?;
+                let mut senders = BTreeMap::new();
+                for (queued, txs) in [(false, inspect.pending), (true, inspect.queued)] {
+                    for (sender, txs) in txs {
+                        if from.is_some_and(|from| from != sender) {
+                            continue;
+                        }
+                        for (nonce, summary) in txs {
+                            let tx = PoolTx {
+                                nonce: nonce.parse()?,
+                                queued,
+                                hash: None,
+                                to: summary.to,
+                                value: summary.value,
+                                gas: summary.gas,
+                                gas_price: summary.gas_price,
+                                input: None,
+                            };
+                            senders.entry(sender).or_insert_with(Vec::new).push(tx);
+                        }
+                    }
+                }
+                print_senders(&provider, senders).await?;
             }
             Self::Status { args } => {
                 let config = args.load_config()?;
                 let provider = utils::get_provider(&config)?;
-                sh_println!("{:#?}", provider.txpool_status().await?)?;
+                let status = provider.txpool_status().await?;
+                if shell::is_json() {
+                    sh_println!("{}", serde_json::to_string_pretty(&status)?)?;
+                } else {
+                    sh_println!("pending: {}", status.pending)?;
+                    sh_println!("queued: {}", status.queued)?;
+                }
             }
         };
 
         Ok(())
     }
 }
+
+/// Prints the content of the transaction pool, of all senders or of `from`.
+async fn content(from: Option<Address>, args: RpcOpts) -> eyre::Result<()> {
+    let config = args.load_config()?;
+    let provider = utils::get_provider(&config)?;
+
+    let mut senders = BTreeMap::new();
+    let content = provider.txpool_content().await?;
+    for (queued, txs) in [(false, content.pending), (true, content.queued)] {
+        for (sender, txs) in txs {
+            if from.is_some_and(|from| from != sender) {
+                continue;
+            }
+            let txs = txs.into_values().map(|tx| PoolTx::from_tx(&tx, queued));
+            senders.entry(sender).or_insert_with(Vec::new).extend(txs);
+        }
+    }
+    print_senders(&provider, senders).await
+}
+
+/// A transaction of the pool.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PoolTx {
+    nonce: u64,
+    /// Whether the transaction is queued, i.e. not executable yet.
+    queued: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hash: Option<B256>,
+    /// The recipient, `None` for contract creations.
+    to: Option<Address>,
+    value: U256,
+    gas: u64,
+    /// The gas price, or the max fee per gas of EIP-1559 transactions.
+    gas_price: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<Bytes>,
+}
+
+impl PoolTx {
+    fn from_tx(tx: &AnyRpcTransaction, queued: bool) -> Self {
+        Self {
+            nonce: tx.nonce(),
+            queued,
+            hash: Some(tx.tx_hash()),
+            to: match tx.kind() {
+                TxKind::Call(to) => Some(to),
+                TxKind::Create => None,
+            },
+            value: tx.value(),
+            gas: tx.gas_limit(),
+            gas_price: tx.max_fee_per_gas(),
+            input: Some(tx.input().clone()),
+        }
+    }
+}
+
+/// The transactions of a sender in the pool.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Sender {
+    address: Address,
+    /// The nonce of the account, i.e. the nonce of its next transaction.
+    nonce: u64,
+    /// The ranges of nonces missing before the transactions of the pool, which keep them queued.
+    gaps: Vec<(u64, u64)>,
+    transactions: Vec<PoolTx>,
+}
+
+impl Sender {
+    fn new(address: Address, nonce: u64, mut transactions: Vec<PoolTx>) -> Self {
+        transactions.sort_by_key(|tx| tx.nonce);
+        Self { address, nonce, gaps: nonce_gaps(nonce, &transactions), transactions }
+    }
+}
+
+/// Returns the inclusive ranges of nonces missing from the transactions, sorted by nonce, after
+/// the account nonce.
+fn nonce_gaps(nonce: u64, transactions: &[PoolTx]) -> Vec<(u64, u64)> {
+    let mut gaps = Vec::new();
+    let mut next = nonce;
+    for tx in transactions {
+        if tx.nonce > next {
+            gaps.push((next, tx.nonce - 1));
+        }
+        next = next.max(tx.nonce + 1);
+    }
+    gaps
+}
+
+async fn print_senders(
+    provider: &RetryProvider,
+    senders: BTreeMap<Address, Vec<PoolTx>>,
+) -> eyre::Result<()> {
+    let mut output = Vec::with_capacity(senders.len());
+    for (address, transactions) in senders {
+        let nonce = provider.get_transaction_count(address).await?;
+        output.push(Sender::new(address, nonce, transactions));
+    }
+
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string_pretty(&output)?)?;
+        return Ok(());
+    }
+    if output.is_empty() {
+        sh_println!("No transactions in the pool")?;
+    }
+    for sender in output {
+        sh_println!("{} (nonce {})", sender.address, sender.nonce)?;
+        let mut gaps = sender.gaps.iter().peekable();
+        for tx in &sender.transactions {
+            if let Some((start, end)) = gaps.next_if(|(start, _)| *start < tx.nonce) {
+                let missing = if start == end {
+                    format!("nonce {start} is missing")
+                } else {
+                    format!("nonces {start} to {end} are missing")
+                };
+                sh_println!("  {}", missing.yellow())?;
+            }
+            sh_println!("  {}", format_tx(tx))?;
+        }
+    }
+    Ok(())
+}
+
+fn format_tx(tx: &PoolTx) -> String {
+    let status = if tx.queued { "queued" } else { "pending" };
+    let mut line = format!("{status:<7} nonce {}", tx.nonce);
+    if let Some(hash) = tx.hash {
+        line += &format!(" {hash}");
+    }
+    match tx.to {
+        Some(to) => line += &format!(" to {to}"),
+        None => line += " contract creation",
+    }
+    line += &format!(
+        " value {} gas {} gas price {} gwei",
+        tx.value,
+        tx.gas,
+        format_units(tx.gas_price, "gwei").unwrap_or_default()
+    );
+    if let Some(selector) = tx.input.as_ref().and_then(|input| input.get(..4)) {
+        line += &format!(" selector {}", Bytes::copy_from_slice(selector));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_nonce_gaps() {
+        let tx = |nonce| PoolTx {
+            nonce,
+            queued: false,
+            hash: None,
+            to: None,
+            value: U256::ZERO,
+            gas: 21000,
+            gas_price: 1,
+            input: None,
+        };
+        let txs = [tx(3), tx(4), tx(7), tx(9)];
+        assert_eq!(nonce_gaps(3, &txs), [(5, 6), (8, 8)]);
+        assert_eq!(nonce_gaps(1, &txs[..2]), [(1, 2)]);
+        assert!(nonce_gaps(3, &[]).is_empty());
+        // stale transactions below the account nonce
+        assert!(nonce_gaps(5, &txs[..2]).is_empty());
+    }
+}
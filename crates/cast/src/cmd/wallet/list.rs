@@ -3,7 +3,10 @@
 
 use foundry_common::{fs, sh_err, sh_println};
 use foundry_config::Config;
-use foundry_wallets::multi_wallet::MultiWalletOptsBuilder;
+use foundry_wallets::{
+    keystore::{AccountLabels, WatchOnlyAccount},
+    multi_wallet::MultiWalletOptsBuilder,
+};
 
 /// CLI arguments for `cast wallet list`.
 #[derive(Clone, Debug, Parser)]
@@ -93,13 +96,24 @@ fn list_local_senders(&self) -> Result<()> {
             dunce::canonicalize(keystore_path)?
         };
 
-        // List all files within the keystore directory.
-        for entry in std::fs::read_dir(keystore_dir)? {
+        let labels = AccountLabels::read(&keystore_dir)?;
+
+        // List all files within the keystore directory, skipping hidden ones like the labels.
+        for entry in std::fs::read_dir(&keystore_dir)? {
             let path = entry?.path();
             if path.is_file() {
                 if let Some(file_name) = path.file_name() {
-                    if let Some(name) = file_name.to_str() {
-                        sh_println!("{name} (Local)")?;
+                    if let Some(name) = file_name.to_str().filter(|name| !name.starts_with('.')) {
+                        let kind = match WatchOnlyAccount::read(&path)? {
+                            Some(account) => format!("{}, watch-only", account.wallet),
+                            None => "Local".to_string(),
+                        };
+                        let account_labels = labels.labels_of(name).collect::<Vec<_>>();
+                        if account_labels.is_empty() {
+                            sh_println!("{name} ({kind})")?;
+                        } else {
+                            sh_println!("{name} ({kind}) [{}]", account_labels.join(", "))?;
+                        }
                     }
                 }
             }
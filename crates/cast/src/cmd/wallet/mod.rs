@@ -13,10 +13,14 @@
 use foundry_cli::{opts::RpcOpts, utils, utils::LoadConfig};
 use foundry_common::{fs, sh_println, shell};
 use foundry_config::Config;
-use foundry_wallets::{RawWalletOpts, WalletOpts, WalletSigner};
+use foundry_wallets::{
+    keystore::{self, AccountLabels, HardwareWallet, WatchOnlyAccount},
+    utils::{create_ledger_signer, create_mnemonic_signer, create_trezor_signer},
+    RawWalletOpts, WalletOpts, WalletSigner,
+};
 use rand::thread_rng;
 use serde_json::json;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use yansi::Paint;
 
 pub mod vanity;
@@ -72,6 +76,33 @@ pub enum WalletSubcommands {
         entropy: Option<String>,
     },
 
+    /// Derive accounts from a BIP-39 mnemonic phrase.
+    #[command(visible_alias = "d")]
+    Derive {
+        /// The mnemonic phrase, or the path to a file containing it.
+        mnemonic: String,
+
+        /// Number of accounts to derive.
+        #[arg(long, short, default_value = "1")]
+        accounts: u32,
+
+        /// The derivation path, where `{}` is replaced with the account index.
+        #[arg(long, default_value = "m/44'/60'/0'/0/{}", value_name = "PATH")]
+        derivation_path: String,
+
+        /// Index of the first account to derive.
+        #[arg(long, default_value = "0", value_name = "INDEX")]
+        start: u32,
+
+        /// The passphrase of the mnemonic.
+        #[arg(long, value_name = "PASSPHRASE")]
+        passphrase: Option<String>,
+
+        /// Also print the private keys of the accounts.
+        #[arg(long)]
+        private_keys: bool,
+    },
+
     /// Generate a vanity address.
     #[command(visible_alias = "va")]
     Vanity(VanityArgs),
@@ -155,6 +186,9 @@ pub enum WalletSubcommands {
     },
 
     /// Import a private key into an encrypted keystore.
+    ///
+    /// With `--ledger` or `--trezor`, saves the address of the hardware wallet account as a
+    /// watch-only entry instead, which signs with the device when used with `--account`.
     #[command(visible_alias = "i")]
     Import {
         /// The name for the account in the keystore.
@@ -168,14 +202,56 @@ pub enum WalletSubcommands {
         /// This is unsafe, we recommend using the default hidden password prompt
         #[arg(long, env = "CAST_UNSAFE_PASSWORD", value_name = "PASSWORD")]
         unsafe_password: Option<String>,
+        /// Import a watch-only account of a Ledger hardware wallet.
+        ///
+        /// The account is derived from `--mnemonic-derivation-path` or `--mnemonic-index`.
+        #[arg(long, conflicts_with = "trezor")]
+        ledger: bool,
+        /// Import a watch-only account of a Trezor hardware wallet.
+        ///
+        /// The account is derived from `--mnemonic-derivation-path` or `--mnemonic-index`.
+        #[arg(long)]
+        trezor: bool,
         #[command(flatten)]
         raw_wallet_options: RawWalletOpts,
     },
 
+    /// Export an encrypted keystore, e.g. to back it up or to import it on another machine.
+    ///
+    /// The keystore stays encrypted with its password.
+    #[command(visible_alias = "e")]
+    Export {
+        /// The name or label of the account in the keystore.
+        #[arg(value_name = "ACCOUNT_NAME")]
+        account_name: String,
+        /// If not provided, keystore will try to be located at the default keystores directory
+        /// (~/.foundry/keystores)
+        #[arg(long, short)]
+        keystore_dir: Option<String>,
+        /// Write the keystore to this file instead of stdout.
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+
     /// List all the accounts in the keystore default directory
     #[command(visible_alias = "ls")]
     List(ListArgs),
 
+    /// Label an account of the keystore, the label can then be used in place of its name, e.g.
+    /// with `--account <LABEL>`.
+    #[command(visible_alias = "lb")]
+    Label {
+        /// The name of the account in the keystore.
+        #[arg(value_name = "ACCOUNT_NAME")]
+        account_name: String,
+        /// The label of the account.
+        label: String,
+        /// If not provided, keystore will try to be located at the default keystores directory
+        /// (~/.foundry/keystores)
+        #[arg(long, short)]
+        keystore_dir: Option<String>,
+    },
+
     /// Remove a wallet from the keystore.
     ///
     /// This command requires the wallet alias and will prompt for a password to ensure that only
@@ -214,7 +290,7 @@ pub enum WalletSubcommands {
     /// Decrypt a keystore file to get the private key
     #[command(name = "decrypt-keystore", visible_alias = "dk")]
     DecryptKeystore {
-        /// The name for the account in the keystore.
+        /// The name or label of the account in the keystore.
         #[arg(value_name = "ACCOUNT_NAME")]
         account_name: String,
         /// If not provided, keystore will try to be located at the default keystores directory
@@ -230,7 +306,7 @@ pub enum WalletSubcommands {
     /// Change the password of a keystore file
     #[command(name = "change-password", visible_alias = "cp")]
     ChangePassword {
-        /// The name for the account in the keystore.
+        /// The name or label of the account in the keystore.
         #[arg(value_name = "ACCOUNT_NAME")]
         account_name: String,
         /// If not provided, keystore will try to be located at the default keystores directory
@@ -383,6 +459,57 @@ pub async fn run(self) -> Result<()> {
                     sh_println!("{}", serde_json::to_string_pretty(&obj)?)?;
                 }
             }
+            Self::Derive {
+                mnemonic,
+                accounts,
+                derivation_path,
+                start,
+                passphrase,
+                private_keys,
+            } => {
+                if !derivation_path.contains("{}") && accounts > 1 {
+                    eyre::bail!(
+                        "The derivation path must contain `{{}}` to derive more than one account"
+                    );
+                }
+
+                let format_json = shell::is_json();
+                let mut json_values = vec![];
+                for i in start..start + accounts {
+                    let path = derivation_path.replace("{}", &i.to_string());
+                    let wallet = match create_mnemonic_signer(
+                        &mnemonic,
+                        passphrase.as_deref(),
+                        Some(&path),
+                        0,
+                    )? {
+                        WalletSigner::Local(wallet) => wallet,
+                        _ => unreachable!("mnemonic signers are local"),
+                    };
+                    let private_key = hex::encode_prefixed(wallet.credential().to_bytes());
+
+                    if format_json {
+                        let mut value = json!({
+                            "path": path,
+                            "address": wallet.address().to_checksum(None),
+                        });
+                        if private_keys {
+                            value["private_key"] = private_key.into();
+                        }
+                        json_values.push(value);
+                    } else {
+                        sh_println!("- Account {i} ({path}):")?;
+                        sh_println!("Address:     {}", wallet.address())?;
+                        if private_keys {
+                            sh_println!("Private key: {private_key}")?;
+                        }
+                    }
+                }
+
+                if format_json {
+                    sh_println!("{}", serde_json::to_string_pretty(&json_values)?)?;
+                }
+            }
             Self::Vanity(cmd) => {
                 cmd.run()?;
             }
@@ -442,7 +569,14 @@ pub async fn run(self) -> Result<()> {
                     eyre::bail!("Validation failed. Address {address} did not sign this message.");
                 }
             }
-            Self::Import { account_name, keystore_dir, unsafe_password, raw_wallet_options } => {
+            Self::Import {
+                account_name,
+                keystore_dir,
+                unsafe_password,
+                ledger,
+                trezor,
+                raw_wallet_options,
+            } => {
                 // Set up keystore directory
                 let dir = if let Some(path) = keystore_dir {
                     Path::new(&path).to_path_buf()
@@ -460,6 +594,28 @@ pub async fn run(self) -> Result<()> {
                     eyre::bail!("Keystore file already exists at {}", keystore_path.display());
                 }
 
+                if ledger || trezor {
+                    let RawWalletOpts { hd_path, mnemonic_index, .. } = raw_wallet_options;
+                    let (wallet, signer) = if ledger {
+                        let signer =
+                            create_ledger_signer(hd_path.as_deref(), mnemonic_index).await?;
+                        (HardwareWallet::Ledger, signer)
+                    } else {
+                        let signer =
+                            create_trezor_signer(hd_path.as_deref(), mnemonic_index).await?;
+                        (HardwareWallet::Trezor, signer)
+                    };
+                    let address = signer.address();
+                    WatchOnlyAccount { address, wallet, hd_path, mnemonic_index }
+                        .write(&keystore_path)?;
+
+                    let success_message = format!(
+                        "`{account_name}` watch-only {wallet} account was saved successfully. Address: {address:?}",
+                    );
+                    sh_println!("{}", success_message.green())?;
+                    return Ok(());
+                }
+
                 // get wallet
                 let wallet = raw_wallet_options
                     .signer()?
@@ -500,9 +656,61 @@ pub async fn run(self) -> Result<()> {
                 );
                 sh_println!("{}", success_message.green())?;
             }
+            Self::Export { account_name, keystore_dir, output } => {
+                let dir = Self::keystore_dir(keystore_dir)?;
+                let keypath = keystore::account_path(&dir, &account_name)?;
+                if !keypath.is_file() {
+                    eyre::bail!("Keystore file does not exist at {}", keypath.display());
+                }
+                if let Some(account) = WatchOnlyAccount::read(&keypath)? {
+                    eyre::bail!(
+                        "`{account_name}` is a watch-only {} account, it has no key to export",
+                        account.wallet
+                    );
+                }
+
+                let keystore = fs::read_to_string(&keypath)?;
+                if let Some(output) = output {
+                    fs::write(&output, keystore)?;
+                    let success_message = format!(
+                        "`{account_name}` keystore was exported successfully to {}",
+                        output.display()
+                    );
+                    sh_println!("{}", success_message.green())?;
+                } else {
+                    sh_println!("{}", keystore.trim_end())?;
+                }
+            }
             Self::List(cmd) => {
                 cmd.run().await?;
             }
+            Self::Label { account_name, label, keystore_dir } => {
+                let dir = Self::keystore_dir(keystore_dir)?;
+                if !dir.join(&account_name).is_file() {
+                    eyre::bail!(
+                        "Keystore file does not exist at {}",
+                        dir.join(&account_name).display()
+                    );
+                }
+                if label.starts_with('.') || label.contains(['/', '\\']) {
+                    eyre::bail!("Invalid label `{label}`");
+                }
+                if dir.join(&label).exists() {
+                    eyre::bail!("An account is already named `{label}`");
+                }
+
+                let mut labels = AccountLabels::read(&dir)?;
+                let previous = labels.insert(label.clone(), account_name.clone());
+                labels.write(&dir)?;
+
+                let success_message = match previous {
+                    Some(previous) if previous != account_name => format!(
+                        "`{account_name}` was labeled `{label}` successfully, replacing `{previous}`."
+                    ),
+                    _ => format!("`{account_name}` was labeled `{label}` successfully."),
+                };
+                sh_println!("{}", success_message.green())?;
+            }
             Self::Remove { name, dir, unsafe_password } => {
                 let dir = if let Some(path) = dir {
                     Path::new(&path).to_path_buf()
@@ -517,20 +725,28 @@ pub async fn run(self) -> Result<()> {
                     eyre::bail!("Keystore file does not exist at {}", keystore_path.display());
                 }
 
-                let password = if let Some(pwd) = unsafe_password {
-                    pwd
-                } else {
-                    rpassword::prompt_password("Enter password: ")?
-                };
+                // watch-only accounts hold no key, there's no password to check
+                if WatchOnlyAccount::read(&keystore_path)?.is_none() {
+                    let password = if let Some(pwd) = unsafe_password {
+                        pwd
+                    } else {
+                        rpassword::prompt_password("Enter password: ")?
+                    };
 
-                if PrivateKeySigner::decrypt_keystore(&keystore_path, password).is_err() {
-                    eyre::bail!("Invalid password - wallet removal cancelled");
+                    if PrivateKeySigner::decrypt_keystore(&keystore_path, password).is_err() {
+                        eyre::bail!("Invalid password - wallet removal cancelled");
+                    }
                 }
 
                 std::fs::remove_file(&keystore_path).wrap_err_with(|| {
                     format!("Failed to remove keystore file at {}", keystore_path.display())
                 })?;
 
+                let mut labels = AccountLabels::read(&dir)?;
+                if labels.remove_account(&name) {
+                    labels.write(&dir)?;
+                }
+
                 let success_message = format!("`{}` keystore was removed successfully.", &name);
                 sh_println!("{}", success_message.green())?;
             }
@@ -585,7 +801,7 @@ pub async fn run(self) -> Result<()> {
                     })?
                 };
 
-                let keypath = dir.join(&account_name);
+                let keypath = keystore::account_path(&dir, &account_name)?;
 
                 if !keypath.exists() {
                     eyre::bail!("Keystore file does not exist at {}", keypath.display());
@@ -622,11 +838,20 @@ pub async fn run(self) -> Result<()> {
                     })?
                 };
 
-                let keypath = dir.join(&account_name);
+                let keypath = keystore::account_path(&dir, &account_name)?;
 
                 if !keypath.exists() {
                     eyre::bail!("Keystore file does not exist at {}", keypath.display());
                 }
+                if let Some(account) = WatchOnlyAccount::read(&keypath)? {
+                    eyre::bail!(
+                        "`{account_name}` is a watch-only {} account, it has no password",
+                        account.wallet
+                    );
+                }
+                // the account may be given by its label
+                let file_name =
+                    keypath.file_name().and_then(|name| name.to_str()).unwrap_or_default();
 
                 let current_password = if let Some(password) = unsafe_password {
                     password
@@ -658,7 +883,7 @@ pub async fn run(self) -> Result<()> {
                     &mut rng,
                     private_key,
                     new_password,
-                    Some(&account_name),
+                    Some(file_name),
                 )?;
 
                 let success_message = format!(
@@ -673,6 +898,15 @@ pub async fn run(self) -> Result<()> {
         Ok(())
     }
 
+    /// Returns the keystore directory, the default one if not provided.
+    fn keystore_dir(keystore_dir: Option<String>) -> Result<PathBuf> {
+        match keystore_dir {
+            Some(path) => Ok(PathBuf::from(path)),
+            None => Config::foundry_keystores_dir()
+                .ok_or_else(|| eyre::eyre!("Could not find the default keystore directory.")),
+        }
+    }
+
     /// Recovers an address from the specified message and signature
     fn recover_address_from_message(message: &str, signature: &Signature) -> Result<Address> {
         Ok(signature.recover_address_from_msg(message)?)
@@ -2,9 +2,9 @@
 
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-use alloy_consensus::TxEnvelope;
+use alloy_consensus::{SignableTransaction, TxEnvelope, TxType, TypedTransaction};
 use alloy_dyn_abi::{DynSolType, DynSolValue, FunctionExt};
-use alloy_json_abi::Function;
+use alloy_json_abi::{Function, JsonAbi};
 use alloy_network::AnyNetwork;
 use alloy_primitives::{
     hex,
@@ -16,7 +16,9 @@
     PendingTransactionBuilder, Provider,
 };
 use alloy_rlp::Decodable;
-use alloy_rpc_types::{BlockId, BlockNumberOrTag, Filter, TransactionRequest};
+use alloy_rpc_types::{
+    state::StateOverride, BlockId, BlockNumberOrTag, Filter, TransactionRequest,
+};
 use alloy_serde::WithOtherFields;
 use alloy_sol_types::sol;
 use base::{Base, NumberWithBase, ToBase};
@@ -53,9 +55,13 @@
 pub mod cmd;
 pub mod opts;
 
+mod abi_json;
 pub mod base;
 pub mod errors;
 mod rlp_converter;
+pub mod signatures;
+pub mod state_override;
+mod storage_layout;
 pub mod tx;
 
 use rlp_converter::Item;
@@ -73,6 +79,10 @@
     interface IERC20 {
         #[derive(Debug)]
         function balanceOf(address owner) external view returns (uint256);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+
+        event Transfer(address indexed from, address indexed to, uint256 value);
     }
 }
 
@@ -100,7 +110,8 @@ pub fn new(provider: P) -> Self {
         Self { provider }
     }
 
-    /// Makes a read-only call to the specified address
+    /// Makes a read-only call to the specified address, with the state modified by
+    /// `state_override` if set.
     ///
     /// # Example
     ///
@@ -125,7 +136,7 @@ pub fn new(provider: P) -> Self {
     /// let tx = TransactionRequest::default().to(to).input(bytes.into());
     /// let tx = WithOtherFields::new(tx);
     /// let cast = Cast::new(alloy_provider);
-    /// let data = cast.call(&tx, None, None).await?;
+    /// let data = cast.call(&tx, None, None, None).await?;
     /// println!("{}", data);
     /// # Ok(())
     /// # }
@@ -135,8 +146,13 @@ pub async fn call(
         req: &WithOtherFields<TransactionRequest>,
         func: Option<&Function>,
         block: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> Result<String> {
-        let res = self.provider.call(req.clone()).block(block.unwrap_or_default()).await?;
+        let mut call = self.provider.call(req.clone()).block(block.unwrap_or_default());
+        if let Some(state_override) = state_override {
+            call = call.overrides(state_override);
+        }
+        let res = call.await?;
 
         let mut decoded = vec![];
 
@@ -1757,6 +1773,57 @@ pub fn abi_encode_packed(sig: &str, args: &[impl AsRef<str>]) -> Result<String>
         Ok(format!("0x{encoded}"))
     }
 
+    /// Performs ABI encoding of the arguments of a function of `abi`, given as JSON. Does not
+    /// include the function selector in the result.
+    ///
+    /// The arguments, and the structs among them, are either arrays in the order of the
+    /// parameters or objects keyed by their names.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use alloy_json_abi::JsonAbi;
+    /// use cast::SimpleCast as Cast;
+    ///
+    /// let abi = JsonAbi::parse(["function f((uint a, address[] b) s)"])?;
+    /// let args = serde_json::json!({ "s": { "a": 1, "b": [] } });
+    /// assert_eq!(
+    ///     Cast::abi_encode_json(&abi, "f", &args)?,
+    ///     "0x0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000000"
+    /// );
+    /// # Ok::<_, eyre::Report>(())
+    /// ```
+    pub fn abi_encode_json(
+        abi: &JsonAbi,
+        function: &str,
+        args: &serde_json::Value,
+    ) -> Result<String> {
+        let func = abi_json::find_function(abi, function)?;
+        let values = abi_json::values_from_json(&func.inputs, args)?;
+        Ok(hex::encode_prefixed(DynSolValue::Tuple(values).abi_encode_params()))
+    }
+
+    /// Decodes the ABI-encoded input or output data of a function of `abi` into JSON, with the
+    /// structs as objects keyed by the names of their fields.
+    ///
+    /// The input data may be prefixed with the function selector.
+    pub fn abi_decode_json(
+        abi: &JsonAbi,
+        function: &str,
+        data: &str,
+        input: bool,
+    ) -> Result<serde_json::Value> {
+        let func = abi_json::find_function(abi, function)?;
+        let data = hex::decode(data)?;
+        let (params, values) = if input {
+            let data = data.strip_prefix(func.selector().as_slice()).unwrap_or(&data);
+            (&func.inputs, func.abi_decode_input(data, false)?)
+        } else {
+            (&func.outputs, func.abi_decode_output(&data, false)?)
+        };
+        Ok(abi_json::values_to_json(params, &values))
+    }
+
     /// Performs ABI encoding to produce the hexadecimal calldata with the given arguments.
     ///
     /// # Example
@@ -2185,6 +2252,70 @@ pub fn decode_raw_transaction(tx: &str) -> Result<TxEnvelope> {
         Ok(tx)
     }
 
+    /// Decodes a raw EIP2718 transaction payload into JSON, labeled with the name of its type and
+    /// the sender recovered from its signature, and the authority of each EIP-7702
+    /// authorization
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cast::SimpleCast as Cast;
+    ///
+    /// let tx = "0x02f8f582a86a82058d8459682f008508351050808303fd84948e42f2f4101563bf679975178e880fd87d3efd4e80b884659ac74b00000000000000000000000080f0c1c49891dcfdd40b6e0f960f84e6042bcb6f000000000000000000000000b97ef9ef8734c71904d8002f8b6bc66dd9c48a6e00000000000000000000000000000000000000000000000000000000007ff4e20000000000000000000000000000000000000000000000000000000000000064c001a05d429597befe2835396206781b199122f2e8297327ed4a05483339e7a8b2022aa04c23a7f70fb29dda1b4ee342fb10a625e9b8ddc6a603fb4e170d4f6f37700cb8";
+    /// let tx = Cast::decode_raw_transaction_json(&tx)?;
+    /// assert_eq!(tx["txType"], "eip1559");
+    /// # Ok::<(), eyre::Report>(())
+    /// ```
+    pub fn decode_raw_transaction_json(tx: &str) -> Result<serde_json::Value> {
+        let tx = Self::decode_raw_transaction(tx)?;
+        let mut json = serde_json::to_value(&tx)?;
+        json["txType"] = match tx.tx_type() {
+            TxType::Legacy => "legacy",
+            TxType::Eip2930 => "eip2930",
+            TxType::Eip1559 => "eip1559",
+            TxType::Eip4844 => "eip4844",
+            TxType::Eip7702 => "eip7702",
+        }
+        .into();
+        json["from"] = serde_json::to_value(tx.recover_signer().ok())?;
+        if let (TxEnvelope::Eip7702(tx), Some(authorizations)) =
+            (&tx, json["authorizationList"].as_array_mut())
+        {
+            for (auth, json) in tx.tx().authorization_list.iter().zip(authorizations) {
+                json["authority"] = serde_json::to_value(auth.recover_authority().ok())?;
+            }
+        }
+        Ok(json)
+    }
+
+    /// Encodes a transaction from JSON, as printed by `cast decode-tx`
+    ///
+    /// Signed transactions are encoded as raw EIP2718 payloads, and unsigned transactions as the
+    /// payload to sign
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cast::SimpleCast as Cast;
+    ///
+    /// let tx = r#"{"type":"0x2","chainId":"0x1","nonce":"0x0","gas":"0x5208","maxFeePerGas":"0x3b9aca00","maxPriorityFeePerGas":"0x1","to":"0x0000000000000000000000000000000000001234","value":"0x1","accessList":[],"input":"0x"}"#;
+    /// let tx = Cast::build_raw_transaction(tx)?;
+    /// assert_eq!(
+    ///     tx,
+    ///     "0x02e3018001843b9aca008252089400000000000000000000000000000000000012340180c0"
+    /// );
+    /// # Ok::<(), eyre::Report>(())
+    /// ```
+    pub fn build_raw_transaction(json: &str) -> Result<String> {
+        let json: serde_json::Value = serde_json::from_str(json).wrap_err("invalid JSON")?;
+        if let Ok(tx) = serde_json::from_value::<TxEnvelope>(json.clone()) {
+            return Ok(hex::encode_prefixed(tx.encoded_2718()));
+        }
+        let tx = serde_json::from_value::<TypedTransaction>(json)
+            .wrap_err("invalid transaction, the type or some fields are missing")?;
+        Ok(hex::encode_prefixed(tx.encoded_for_signing()))
+    }
+
     /// Decodes EOF container bytes
     /// Pretty prints the decoded EOF container contents
     ///
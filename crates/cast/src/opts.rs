@@ -1,9 +1,11 @@
 use crate::cmd::{
-    access_list::AccessListArgs, artifact::ArtifactArgs, bind::BindArgs, call::CallArgs,
+    access_list::AccessListArgs, artifact::ArtifactArgs, bind::BindArgs, blob::BlobSubcommands,
+    bundle::BundleSubcommands, call::CallArgs, chain::ChainSubcommands,
     constructor_args::ConstructorArgsArgs, create2::Create2Args, creation_code::CreationCodeArgs,
-    estimate::EstimateArgs, find_block::FindBlockArgs, interface::InterfaceArgs, logs::LogsArgs,
-    mktx::MakeTxArgs, rpc::RpcArgs, run::RunArgs, send::SendTxArgs, storage::StorageArgs,
-    txpool::TxPoolSubcommands, wallet::WalletSubcommands,
+    ens::EnsSubcommands, estimate::EstimateArgs, explain::ExplainArgs, find_block::FindBlockArgs,
+    interface::InterfaceArgs, l1_fee::EstimateL1FeeArgs, logs::LogsArgs, mktx::MakeTxArgs,
+    multicall::MulticallArgs, proof::VerifyProofArgs, rpc::RpcArgs, run::RunArgs, send::SendTxArgs,
+    storage::StorageArgs, txpool::TxPoolSubcommands, wallet::WalletSubcommands,
 };
 use alloy_primitives::{Address, B256, U256};
 use alloy_rpc_types::BlockId;
@@ -382,6 +384,13 @@ pub enum CastSubcommand {
     #[command(visible_alias = "c")]
     Call(CallArgs),
 
+    /// Perform many calls in a single Multicall3 call, printing their results as JSON.
+    ///
+    /// On chains without Multicall3, a minimal batching contract is set at its address with a
+    /// state override.
+    #[command(visible_alias = "mc")]
+    Multicall(MulticallArgs),
+
     /// ABI-encode a function with arguments.
     #[command(name = "calldata", visible_alias = "cd")]
     CalldataEncode {
@@ -393,8 +402,12 @@ pub enum CastSubcommand {
         args: Vec<String>,
     },
 
-    /// Get the symbolic name of the current chain.
+    /// Get the symbolic name of the current chain, or the details of a chain with `cast chain
+    /// info`.
     Chain {
+        #[command(subcommand)]
+        command: Option<ChainSubcommands>,
+
         #[command(flatten)]
         rpc: RpcOpts,
     },
@@ -503,6 +516,13 @@ pub enum CastSubcommand {
     #[command(visible_alias = "e")]
     Estimate(EstimateArgs),
 
+    /// Estimate the L1 data fee of a transaction on an L2.
+    ///
+    /// Computes the fee with the formula of the OP stack or of Arbitrum, from the current L1 base
+    /// fee and blob base fee.
+    #[command(visible_alias = "l1f")]
+    EstimateL1Fee(EstimateL1FeeArgs),
+
     /// Decode ABI-encoded input data.
     ///
     /// Similar to `abi-decode --input`, but function selector MUST be prefixed in `calldata`
@@ -553,20 +573,35 @@ pub enum CastSubcommand {
     #[command(name = "decode-abi", visible_aliases = &["abi-decode", "--abi-decode", "ad"])]
     DecodeAbi {
         /// The function signature in the format `<name>(<in-types>)(<out-types>)`.
+        ///
+        /// With `--abi`, the ABI-encoded calldata instead.
         sig: String,
 
         /// The ABI-encoded calldata.
-        calldata: String,
+        #[arg(required_unless_present = "abi", conflicts_with = "abi")]
+        calldata: Option<String>,
 
         /// Whether to decode the input or output data.
         #[arg(long, short, help_heading = "Decode input data instead of output data")]
         input: bool,
+
+        /// Path to the ABI or artifact of a contract, to decode the data of `--function` into
+        /// JSON.
+        #[arg(long, value_name = "PATH", requires = "function")]
+        abi: Option<String>,
+
+        /// The name or signature of the function of `--abi`.
+        #[arg(long, requires = "abi")]
+        function: Option<String>,
     },
 
     /// ABI encode the given function argument, excluding the selector.
     #[command(visible_alias = "ae")]
     AbiEncode {
         /// The function signature.
+        ///
+        /// With `--abi`, the path to a JSON file with the arguments instead, as an array or as an
+        /// object keyed by the parameter names, with structs likewise.
         sig: String,
 
         /// Whether to use packed encoding.
@@ -574,21 +609,39 @@ pub enum CastSubcommand {
         packed: bool,
 
         /// The arguments of the function.
-        #[arg(allow_hyphen_values = true)]
+        #[arg(allow_hyphen_values = true, conflicts_with = "abi")]
         args: Vec<String>,
+
+        /// Path to the ABI or artifact of a contract, to encode the arguments of `--function`.
+        #[arg(long, value_name = "PATH", requires = "function", conflicts_with = "packed")]
+        abi: Option<String>,
+
+        /// The name or signature of the function of `--abi`.
+        #[arg(long, requires = "abi")]
+        function: Option<String>,
     },
 
-    /// Compute the storage slot for an entry in a mapping.
+    /// Compute the storage slot for an entry in a mapping, or in nested mappings and arrays.
+    ///
+    /// Examples:
+    /// - `cast index address 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045 2`
+    /// - `cast index --type "mapping(address => mapping(uint256 => uint256))" 0xd8dA...6045 42 2`
+    /// - `cast index --type "uint256[][]" 1 3 5`
     #[command(visible_alias = "in")]
     Index {
-        /// The mapping key type.
-        key_type: String,
-
-        /// The mapping key.
-        key: String,
+        /// The mapping key type, the mapping key and the storage slot of the mapping.
+        ///
+        /// With `--type`, the mapping keys and array indices from the outermost, followed by the
+        /// storage slot of the variable.
+        #[arg(value_name = "ARGS", num_args = 2.., required = true)]
+        args: Vec<String>,
 
-        /// The storage slot of the mapping.
-        slot_number: String,
+        /// The type of the variable, to index nested mappings and arrays.
+        ///
+        /// Elements of arrays of up to 16 bytes are packed, in which case the slot holding the
+        /// element is printed.
+        #[arg(long = "type", value_name = "TYPE")]
+        ty: Option<String>,
     },
 
     /// Compute storage slots as specified by `ERC-7201: Namespaced Storage Layout`.
@@ -642,26 +695,51 @@ pub enum CastSubcommand {
         rpc: RpcOpts,
     },
 
-    /// Get the function signatures for the given selector from <https://openchain.xyz>.
+    /// Get the function signatures for the given selector.
+    ///
+    /// Signatures are looked up in the artifacts of the current project, then in the local
+    /// signature bundle, then on <https://openchain.xyz>.
     #[command(name = "4byte", visible_aliases = &["4", "4b"])]
     FourByte {
         /// The function selector.
         selector: Option<String>,
+
+        /// Download all the signatures of <https://openchain.xyz> into the local signature bundle.
+        #[arg(long, conflicts_with = "selector")]
+        sync: bool,
+
+        /// Skip the <https://openchain.xyz> lookup.
+        #[arg(long)]
+        offline: bool,
     },
 
-    /// Decode ABI-encoded calldata using <https://openchain.xyz>.
+    /// Decode ABI-encoded calldata.
+    ///
+    /// Signatures are looked up in the artifacts of the current project, then in the local
+    /// signature bundle, then on <https://openchain.xyz>.
     #[command(name = "4byte-calldata", aliases = &["4byte-decode", "4d", "4bd"], visible_aliases = &["4c", "4bc"])]
     FourByteCalldata {
         /// The ABI-encoded calldata.
         calldata: Option<String>,
+
+        /// Skip the <https://openchain.xyz> lookup.
+        #[arg(long)]
+        offline: bool,
     },
 
-    /// Get the event signature for a given topic 0 from <https://openchain.xyz>.
+    /// Get the event signature for a given topic 0.
+    ///
+    /// Signatures are looked up in the artifacts of the current project, then in the local
+    /// signature bundle, then on <https://openchain.xyz>.
     #[command(name = "4byte-event", visible_aliases = &["4e", "4be", "topic0-event", "t0e"])]
     FourByteEvent {
         /// Topic 0
         #[arg(value_name = "TOPIC_0")]
         topic: Option<String>,
+
+        /// Skip the <https://openchain.xyz> lookup.
+        #[arg(long)]
+        offline: bool,
     },
 
     /// Upload the given signatures to <https://openchain.xyz>.
@@ -837,6 +915,12 @@ pub enum CastSubcommand {
         rpc: RpcOpts,
     },
 
+    /// Get the text records, avatar and content hash of ENS names.
+    Ens {
+        #[command(subcommand)]
+        command: EnsSubcommands,
+    },
+
     /// Get the raw value of a contract's storage slot.
     #[command(visible_alias = "st")]
     Storage(StorageArgs),
@@ -858,10 +942,20 @@ pub enum CastSubcommand {
         #[arg(long, short = 'B')]
         block: Option<BlockId>,
 
+        /// Verify the proofs against the state root of the block instead of printing them.
+        #[arg(long)]
+        verify: bool,
+
         #[command(flatten)]
         rpc: RpcOpts,
     },
 
+    /// Verify an account proof and its storage proofs against a state root.
+    ///
+    /// The state root is either given or fetched from the block of the proof.
+    #[command(visible_alias = "vp")]
+    VerifyProof(VerifyProofArgs),
+
     /// Get the nonce for an account.
     #[command(visible_alias = "n")]
     Nonce {
@@ -1010,6 +1104,10 @@ pub enum CastSubcommand {
     #[command(visible_alias = "r")]
     Run(RunArgs),
 
+    /// Explains what a published transaction did: its calls, token transfers and events.
+    #[command(visible_alias = "ex")]
+    Explain(ExplainArgs),
+
     /// Perform a raw JSON-RPC request.
     #[command(visible_alias = "rp")]
     Rpc(RpcArgs),
@@ -1035,9 +1133,22 @@ pub enum CastSubcommand {
     },
 
     /// Decodes a raw signed EIP 2718 typed transaction
+    ///
+    /// Supports legacy, EIP-2930, EIP-1559, EIP-4844 and EIP-7702 transactions, and recovers their
+    /// sender and the authorities of their EIP-7702 authorizations.
     #[command(visible_aliases = &["dt", "decode-tx"])]
     DecodeTransaction { tx: Option<String> },
 
+    /// Encodes a transaction from JSON, as printed by `cast decode-tx`
+    ///
+    /// Signed transactions are encoded as raw EIP 2718 transactions, and unsigned transactions as
+    /// the payload to sign.
+    #[command(visible_aliases = &["bt", "build-tx"])]
+    BuildTransaction {
+        /// The transaction as JSON, read from stdin if not provided.
+        tx: Option<String>,
+    },
+
     /// Extracts function selectors and arguments from bytecode
     #[command(visible_alias = "sel")]
     Selectors {
@@ -1059,6 +1170,18 @@ pub enum CastSubcommand {
         #[command(subcommand)]
         command: TxPoolSubcommands,
     },
+
+    /// Encode, decode and commit to EIP-4844 blobs.
+    Blob {
+        #[command(subcommand)]
+        command: BlobSubcommands,
+    },
+
+    /// Simulate and send transaction bundles through MEV relays, like Flashbots or MEV-Share.
+    Bundle {
+        #[command(subcommand)]
+        command: BundleSubcommands,
+    },
 }
 
 /// CLI arguments for `cast --to-base`.
@@ -0,0 +1,263 @@
+//! Offline lookup of function, error and event signatures for `cast 4byte` and friends.
+//!
+//! Signatures are looked up, in order, in the artifacts of the current project, in the local
+//! signature bundle synced from OpenChain with `cast 4byte --sync`, and finally on OpenChain.
+
+use alloy_json_abi::JsonAbi;
+use alloy_primitives::{hex, keccak256};
+use eyre::{Result, WrapErr};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use foundry_common::{
+    abi::abi_decode_calldata,
+    fs,
+    selectors::{OpenChainClient, SelectorType},
+};
+use foundry_config::Config;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// The name of the signature bundle in the Foundry cache directory.
+const BUNDLE_FILE_NAME: &str = "signatures-bundle.json.gz";
+
+/// A local database of signatures, indexed by selector.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureBundle {
+    /// Function and error signatures by 4 byte selector.
+    pub functions: BTreeMap<String, Vec<String>>,
+    /// Event signatures by topic 0.
+    pub events: BTreeMap<String, Vec<String>>,
+}
+
+impl SignatureBundle {
+    /// Returns the default location of the bundle, in the Foundry cache directory.
+    pub fn default_path() -> Option<PathBuf> {
+        Config::foundry_cache_dir().map(|dir| dir.join(BUNDLE_FILE_NAME))
+    }
+
+    /// Reads a gzip compressed bundle, returns `None` if it doesn't exist.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let reader = BufReader::new(GzDecoder::new(fs::open(path)?));
+        let bundle = serde_json::from_reader(reader)
+            .wrap_err_with(|| format!("invalid signature bundle {}", path.display()))?;
+        Ok(Some(bundle))
+    }
+
+    /// Writes the bundle gzip compressed to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut writer =
+            BufWriter::new(GzEncoder::new(fs::create_file(path)?, Compression::default()));
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        writer.into_inner().map_err(|err| err.into_error())?.finish()?;
+        Ok(())
+    }
+
+    /// Builds a bundle from the OpenChain signature export.
+    ///
+    /// Each line holds a signature, optionally preceded by its selector. Signatures without a
+    /// selector are indexed both as functions and as events.
+    pub fn from_export(export: &str) -> Self {
+        let mut bundle = Self::default();
+        for line in export.lines() {
+            let mut fields = line.split([',', '\t', ' ']).filter(|field| !field.is_empty());
+            let Some(signature) = fields.next_back() else { continue };
+            if !signature.ends_with(')') || !signature.contains('(') {
+                continue;
+            }
+            match fields.find(|field| field.starts_with("0x")).map(str::len) {
+                Some(10) => bundle.insert(SelectorType::Function, signature),
+                Some(66) => bundle.insert(SelectorType::Event, signature),
+                _ => {
+                    bundle.insert(SelectorType::Function, signature);
+                    bundle.insert(SelectorType::Event, signature);
+                }
+            }
+        }
+        bundle
+    }
+
+    /// Indexes the functions, errors and events of the artifacts in the `out` directory.
+    pub fn from_artifacts(out: &Path) -> Self {
+        #[derive(Deserialize)]
+        struct Artifact {
+            abi: Option<JsonAbi>,
+        }
+
+        let mut bundle = Self::default();
+        let artifacts = fs::json_files(out)
+            .filter(|path| !path.components().any(|c| c.as_os_str() == "build-info"));
+        for path in artifacts {
+            let Ok(Artifact { abi: Some(abi) }) = fs::read_json_file(&path) else { continue };
+            for function in abi.functions() {
+                bundle.insert(SelectorType::Function, &function.signature());
+            }
+            for error in abi.errors() {
+                bundle.insert(SelectorType::Error, &error.signature());
+            }
+            for event in abi.events() {
+                bundle.insert(SelectorType::Event, &event.signature());
+            }
+        }
+        bundle
+    }
+
+    /// Adds a signature to the bundle, under the selector computed from it.
+    pub fn insert(&mut self, selector_type: SelectorType, signature: &str) {
+        let hash = keccak256(signature);
+        let (map, selector) = match selector_type {
+            SelectorType::Function | SelectorType::Error => {
+                (&mut self.functions, hex::encode_prefixed(&hash[..4]))
+            }
+            SelectorType::Event => (&mut self.events, hex::encode_prefixed(hash)),
+        };
+        let signatures = map.entry(selector).or_default();
+        if !signatures.iter().any(|s| s == signature) {
+            signatures.push(signature.to_string());
+        }
+    }
+
+    /// Returns the signatures matching the selector.
+    pub fn get(&self, selector_type: SelectorType, selector: &str) -> &[String] {
+        let map = match selector_type {
+            SelectorType::Function | SelectorType::Error => &self.functions,
+            SelectorType::Event => &self.events,
+        };
+        map.get(&normalize_selector(selector)).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the number of indexed selectors.
+    pub fn len(&self) -> usize {
+        self.functions.len() + self.events.len()
+    }
+
+    /// Returns `true` if the bundle has no signatures.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.events.is_empty()
+    }
+}
+
+/// Downloads all the signatures of OpenChain into the local bundle, returns the bundle.
+pub async fn sync_bundle() -> Result<SignatureBundle> {
+    let path = SignatureBundle::default_path()
+        .ok_or_else(|| eyre::eyre!("could not find the Foundry cache directory"))?;
+    let export = OpenChainClient::new()?.export_signatures().await?;
+    let bundle = SignatureBundle::from_export(&export);
+    eyre::ensure!(!bundle.is_empty(), "the OpenChain export has no signatures");
+    bundle.save(&path)?;
+    Ok(bundle)
+}
+
+/// Looks up the signatures of a selector in the artifacts of the current project, then in the
+/// local bundle, and unless `offline` is set, on OpenChain.
+///
+/// Project matches are preferred: the other sources are only searched if there are none.
+pub async fn lookup_signatures(
+    selector_type: SelectorType,
+    selector: &str,
+    offline: bool,
+) -> Result<Vec<String>> {
+    let expected_len = match selector_type {
+        SelectorType::Function | SelectorType::Error => 10,
+        SelectorType::Event => 66,
+    };
+    let normalized = normalize_selector(selector);
+    if normalized.len() != expected_len {
+        eyre::bail!(
+            "Invalid selector {normalized}: expected {expected_len} characters (including 0x prefix)."
+        )
+    }
+
+    let project = Config::load().ok().map(|config| config.root.join(&config.out));
+    if let Some(out) = project.filter(|out| out.is_dir()) {
+        let signatures =
+            SignatureBundle::from_artifacts(&out).get(selector_type, selector).to_vec();
+        if !signatures.is_empty() {
+            return Ok(signatures);
+        }
+    }
+
+    if let Some(path) = SignatureBundle::default_path() {
+        if let Some(bundle) = SignatureBundle::load(&path)? {
+            let signatures = bundle.get(selector_type, selector).to_vec();
+            if !signatures.is_empty() {
+                return Ok(signatures);
+            }
+        }
+    }
+
+    if offline {
+        return Ok(vec![]);
+    }
+    OpenChainClient::new()?.decode_selector(selector, selector_type).await
+}
+
+/// Looks up the function signatures of the selector of `calldata`, keeping only those which can
+/// decode it.
+pub async fn lookup_calldata_signatures(calldata: &str, offline: bool) -> Result<Vec<String>> {
+    let calldata = calldata.strip_prefix("0x").unwrap_or(calldata);
+    if calldata.len() < 8 {
+        eyre::bail!(
+            "Calldata too short: expected at least 8 characters (excluding 0x prefix), got {}.",
+            calldata.len()
+        )
+    }
+
+    let signatures = lookup_signatures(SelectorType::Function, &calldata[..8], offline).await?;
+    Ok(signatures
+        .into_iter()
+        .filter(|sig| abi_decode_calldata(sig, calldata, true, true).is_ok())
+        .collect())
+}
+
+/// Formats a selector as lowercase hex with a `0x` prefix.
+fn normalize_selector(selector: &str) -> String {
+    let selector = selector.to_lowercase();
+    if selector.starts_with("0x") {
+        selector
+    } else {
+        format!("0x{selector}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_export() {
+        let export = "\
+function,0xa9059cbb,transfer(address,uint256)
+event,0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef,Transfer(address,address,uint256)
+balanceOf(address)
+not a signature
+";
+        let bundle = SignatureBundle::from_export(export);
+        assert_eq!(bundle.get(SelectorType::Function, "0xa9059cbb"), ["transfer(address,uint256)"]);
+        assert_eq!(bundle.get(SelectorType::Function, "70A08231"), ["balanceOf(address)"]);
+        assert_eq!(
+            bundle.get(
+                SelectorType::Event,
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            ),
+            ["Transfer(address,address,uint256)"]
+        );
+        assert!(bundle.get(SelectorType::Function, "0xddf252ad").is_empty());
+        assert_eq!(bundle.len(), 4);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(BUNDLE_FILE_NAME);
+        bundle.save(&path).unwrap();
+        assert_eq!(SignatureBundle::load(&path).unwrap(), Some(bundle));
+        assert_eq!(SignatureBundle::load(&dir.path().join("missing")).unwrap(), None);
+    }
+}
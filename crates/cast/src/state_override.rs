@@ -0,0 +1,190 @@
+//! State overrides of `cast call` and `cast run`.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types::state::StateOverride;
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use foundry_cli::utils::parse_ether_value;
+use foundry_common::fs;
+use foundry_evm::{executors::Executor, revm::primitives::Bytecode};
+use std::{path::PathBuf, str::FromStr};
+
+/// CLI arguments to execute with a modified state, like the state overrides of `eth_call`.
+#[derive(Clone, Debug, Default, Parser)]
+#[command(next_help_heading = "State override options")]
+pub struct StateOverrideArgs {
+    /// Override the balance of an account, either specified in wei, or as a string with a unit
+    /// type.
+    ///
+    /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:10ether
+    #[arg(long = "override-balance", value_name = "ADDRESS:BALANCE")]
+    pub balance_overrides: Vec<String>,
+
+    /// Override the nonce of an account.
+    ///
+    /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:5
+    #[arg(long = "override-nonce", value_name = "ADDRESS:NONCE")]
+    pub nonce_overrides: Vec<String>,
+
+    /// Override the runtime code of an account.
+    ///
+    /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:0x6080...
+    #[arg(long = "override-code", value_name = "ADDRESS:CODE")]
+    pub code_overrides: Vec<String>,
+
+    /// Override a storage slot of an account, the other slots keep their value.
+    ///
+    /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:0x0:0x1
+    #[arg(long = "override-storage", value_name = "ADDRESS:SLOT:VALUE")]
+    pub storage_overrides: Vec<String>,
+
+    /// Path to a JSON file of state overrides, in the format of the `eth_call` state overrides.
+    ///
+    /// The overrides of the other flags are applied on top of it.
+    #[arg(long, value_name = "PATH")]
+    pub state_override_file: Option<PathBuf>,
+}
+
+impl StateOverrideArgs {
+    /// Returns the state overrides, if any are set.
+    pub fn state_override(&self) -> Result<Option<StateOverride>> {
+        let Self {
+            balance_overrides,
+            nonce_overrides,
+            code_overrides,
+            storage_overrides,
+            state_override_file,
+        } = self;
+        if balance_overrides.is_empty() &&
+            nonce_overrides.is_empty() &&
+            code_overrides.is_empty() &&
+            storage_overrides.is_empty() &&
+            state_override_file.is_none()
+        {
+            return Ok(None);
+        }
+
+        let mut overrides = match state_override_file {
+            Some(path) => fs::read_json_file::<StateOverride>(path)
+                .wrap_err_with(|| format!("invalid state override file {}", path.display()))?,
+            None => StateOverride::default(),
+        };
+
+        for s in balance_overrides {
+            let (address, balance) = split_address(s)?;
+            overrides.entry(address).or_default().balance = Some(parse_ether_value(balance)?);
+        }
+        for s in nonce_overrides {
+            let (address, nonce) = split_address(s)?;
+            overrides.entry(address).or_default().nonce =
+                Some(nonce.parse().wrap_err_with(|| format!("invalid nonce `{nonce}`"))?);
+        }
+        for s in code_overrides {
+            let (address, code) = split_address(s)?;
+            overrides.entry(address).or_default().code =
+                Some(Bytes::from_str(code).wrap_err_with(|| format!("invalid code `{code}`"))?);
+        }
+        for s in storage_overrides {
+            let (address, slot_and_value) = split_address(s)?;
+            let (slot, value) = slot_and_value.split_once(':').ok_or_else(|| {
+                eyre::eyre!("invalid storage override `{s}`, expected `address:slot:value`")
+            })?;
+            let account = overrides.entry(address).or_default();
+            eyre::ensure!(
+                account.state.is_none(),
+                "the storage of {address} is both replaced and modified"
+            );
+            account
+                .state_diff
+                .get_or_insert_with(Default::default)
+                .insert(B256::from(parse_word(slot)?), B256::from(parse_word(value)?));
+        }
+
+        Ok(Some(overrides))
+    }
+
+    /// Applies the state overrides to the state of a local executor.
+    pub fn apply(&self, executor: &mut Executor) -> Result<()> {
+        let Some(overrides) = self.state_override()? else { return Ok(()) };
+
+        for (address, account) in overrides {
+            if let Some(balance) = account.balance {
+                executor.set_balance(address, balance)?;
+            }
+            if let Some(nonce) = account.nonce {
+                executor.set_nonce(address, nonce)?;
+            }
+            if let Some(code) = account.code {
+                executor.set_code(address, Bytecode::new_raw(code))?;
+            }
+            match (account.state, account.state_diff) {
+                (Some(_), Some(_)) => {
+                    eyre::bail!("the storage of {address} is both replaced and modified")
+                }
+                (Some(state), None) => {
+                    let storage = state
+                        .into_iter()
+                        .map(|(slot, value)| (slot.into(), value.into()))
+                        .collect();
+                    executor.set_storage(address, storage)?;
+                }
+                (None, Some(state_diff)) => {
+                    for (slot, value) in state_diff {
+                        executor.set_storage_slot(address, slot.into(), value.into())?;
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits an override into the address of the account and the overridden value.
+fn split_address(s: &str) -> Result<(Address, &str)> {
+    let (address, value) = s
+        .split_once(':')
+        .ok_or_else(|| eyre::eyre!("invalid override `{s}`, expected `address:value`"))?;
+    let address = address.parse().wrap_err_with(|| format!("invalid address `{address}`"))?;
+    Ok((address, value))
+}
+
+/// Parses a storage slot or value, either as a decimal or a hex number.
+fn parse_word(s: &str) -> Result<U256> {
+    U256::from_str(s).wrap_err_with(|| format!("invalid storage word `{s}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_state_overrides() {
+        let args = StateOverrideArgs::parse_from([
+            "foundry-cli",
+            "--override-balance",
+            "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:1ether",
+            "--override-nonce",
+            "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:5",
+            "--override-code",
+            "0x0000000000000000000000000000000000000001:0x6000",
+            "--override-storage",
+            "0x0000000000000000000000000000000000000001:1:0x2a",
+        ]);
+        let overrides = args.state_override().unwrap().unwrap();
+
+        let vitalik: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
+        assert_eq!(overrides[&vitalik].balance, Some(U256::from(10).pow(U256::from(18))));
+        assert_eq!(overrides[&vitalik].nonce, Some(5));
+
+        let contract = Address::with_last_byte(1);
+        assert_eq!(overrides[&contract].code, Some(Bytes::from_static(&[0x60, 0x00])));
+        let state_diff = overrides[&contract].state_diff.as_ref().unwrap();
+        assert_eq!(state_diff[&B256::with_last_byte(1)], B256::with_last_byte(0x2a));
+
+        assert!(StateOverrideArgs::default().state_override().unwrap().is_none());
+        let args = StateOverrideArgs::parse_from(["foundry-cli", "--override-balance", "1ether"]);
+        assert!(args.state_override().is_err());
+    }
+}
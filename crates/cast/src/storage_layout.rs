@@ -0,0 +1,868 @@
+//! Decoding of the full storage of a contract from its storage layout, for `cast storage
+//! --decode`.
+//!
+//! Besides the variables of the layout, this follows strings, dynamic arrays, structs, the given
+//! mapping keys, and lays out the ERC-7201 namespaces of the contract from its AST.
+//!
+//! The same layout of types computes the slots of values in nested mappings and arrays, for `cast
+//! index --type`.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_network::AnyNetwork;
+use alloy_primitives::{hex, keccak256, map::HashMap, Address, B256, I256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockId;
+use eyre::{OptionExt, Result, WrapErr};
+use foundry_common::{erc7201, fmt::format_token_raw};
+use foundry_compilers::artifacts::{
+    ast::{Ast, Node, NodeType},
+    Storage, StorageLayout,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Maximum number of slots read for the data of a `string` or `bytes` variable.
+const MAX_BYTES_SLOTS: usize = 256;
+
+/// Options of the storage decoding.
+#[derive(Clone, Debug, Default)]
+pub struct DecodeOptions {
+    /// Keys of the mappings to decode, by mapping path, e.g. `balances` or `allowance[]`.
+    pub keys: HashMap<String, Vec<String>>,
+    /// Maximum number of elements of dynamic arrays to decode.
+    pub max_array_length: usize,
+}
+
+impl DecodeOptions {
+    /// Parses mapping keys given as `NAME=KEY[,KEY...]`.
+    pub fn parse_keys(keys: &[String]) -> Result<HashMap<String, Vec<String>>> {
+        let mut parsed = HashMap::<String, Vec<String>>::default();
+        for key in keys {
+            let (name, keys) = key
+                .split_once('=')
+                .ok_or_else(|| eyre::eyre!("invalid mapping keys `{key}`, expected `NAME=KEYS`"))?;
+            parsed
+                .entry(name.trim().to_string())
+                .or_default()
+                .extend(keys.split(',').map(|key| key.trim().to_string()));
+        }
+        Ok(parsed)
+    }
+}
+
+/// A decoded variable, or element of a variable.
+#[derive(Clone, Debug, Serialize)]
+pub struct DecodedSlot {
+    /// The path of the variable, e.g. `balances[0x...]` or `config.owner`.
+    pub name: String,
+    /// The type of the variable.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The slot of the variable.
+    pub slot: U256,
+    /// The offset of the variable in the slot.
+    pub offset: usize,
+    /// The number of bytes of the variable.
+    pub bytes: usize,
+    /// The decoded value. Empty for mappings, the length for dynamic arrays.
+    pub value: String,
+    /// The raw value of the slot, if read.
+    pub raw: Option<B256>,
+    /// The contract declaring the variable.
+    pub contract: String,
+}
+
+/// A type of the storage layout.
+#[derive(Clone, Debug)]
+struct LayoutType {
+    label: String,
+    /// The number of bytes occupied in storage.
+    size: usize,
+    kind: TypeKind,
+}
+
+#[derive(Clone, Debug)]
+enum TypeKind {
+    /// A value type stored in place.
+    Value,
+    /// `string` or `bytes`.
+    Bytes,
+    Mapping {
+        key: String,
+        value: String,
+    },
+    DynamicArray {
+        base: String,
+    },
+    StaticArray {
+        base: String,
+        len: usize,
+    },
+    Struct {
+        members: Vec<Member>,
+    },
+}
+
+/// A member of a struct.
+#[derive(Clone, Debug)]
+struct Member {
+    label: String,
+    slot: U256,
+    offset: usize,
+    ty: String,
+}
+
+impl Member {
+    fn from_storage(storage: &Storage) -> Result<Self> {
+        Ok(Self {
+            label: storage.label.clone(),
+            slot: U256::from_str(&storage.slot)?,
+            offset: storage.offset as usize,
+            ty: storage.storage_type.clone(),
+        })
+    }
+}
+
+/// The types of a storage layout by identifier, the types laid out from the AST being identified
+/// by their type string.
+#[derive(Debug, Default)]
+struct Types(BTreeMap<String, LayoutType>);
+
+impl Types {
+    fn from_layout(layout: &StorageLayout) -> Result<Self> {
+        let mut types = Self::default();
+        for (id, ty) in &layout.types {
+            let size = ty
+                .number_of_bytes
+                .parse()
+                .wrap_err_with(|| format!("invalid size of type `{}`", ty.label))?;
+            let base = || {
+                ty.other
+                    .get("base")
+                    .and_then(Value::as_str)
+                    .map(String::from)
+                    .ok_or_else(|| eyre::eyre!("missing base type of `{}`", ty.label))
+            };
+            let kind = match ty.encoding.as_str() {
+                "bytes" => TypeKind::Bytes,
+                "mapping" => TypeKind::Mapping {
+                    key: ty.key.clone().ok_or_eyre("missing mapping key type")?,
+                    value: ty.value.clone().ok_or_eyre("missing mapping value type")?,
+                },
+                "dynamic_array" => TypeKind::DynamicArray { base: base()? },
+                _ => {
+                    if let Some(members) = ty.other.get("members") {
+                        let members = serde_json::from_value::<Vec<Storage>>(members.clone())?;
+                        TypeKind::Struct {
+                            members: members
+                                .iter()
+                                .map(Member::from_storage)
+                                .collect::<Result<_>>()?,
+                        }
+                    } else if ty.other.contains_key("base") {
+                        TypeKind::StaticArray { base: base()?, len: static_array_len(&ty.label)? }
+                    } else {
+                        TypeKind::Value
+                    }
+                }
+            };
+            types.0.insert(id.clone(), LayoutType { label: ty.label.clone(), size, kind });
+        }
+        Ok(types)
+    }
+
+    fn get(&self, id: &str) -> Result<&LayoutType> {
+        self.0.get(id).ok_or_else(|| eyre::eyre!("unknown storage type `{id}`"))
+    }
+
+    /// Lays out a type from its type string in the AST, e.g. `mapping(address => uint256)`,
+    /// returns its identifier.
+    fn insert_type_string(&mut self, ty: &str, sources: &SourceStructs) -> Result<String> {
+        let ty = ty.trim().trim_end_matches(" storage ref").trim_end_matches(" storage pointer");
+        if self.0.contains_key(ty) {
+            return Ok(ty.to_string());
+        }
+
+        let (size, kind) = if let Some(inner) =
+            ty.strip_prefix("mapping(").and_then(|inner| inner.strip_suffix(')'))
+        {
+            let (key, value) = inner
+                .split_once(" => ")
+                .ok_or_else(|| eyre::eyre!("invalid mapping type `{ty}`"))?;
+            let key = self.insert_type_string(key, sources)?;
+            let value = self.insert_type_string(value, sources)?;
+            (32, TypeKind::Mapping { key, value })
+        } else if let Some(base) = ty.strip_suffix("[]") {
+            (32, TypeKind::DynamicArray { base: self.insert_type_string(base, sources)? })
+        } else if let Some((base, _)) = ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('[')) {
+            let len = static_array_len(ty)?;
+            let base = self.insert_type_string(base, sources)?;
+            let base_size = self.get(&base)?.size;
+            let slots = if base_size <= 32 {
+                len.div_ceil(32 / base_size.max(1))
+            } else {
+                len * base_size.div_ceil(32)
+            };
+            (slots.max(1) * 32, TypeKind::StaticArray { base, len })
+        } else if ty == "string" || ty == "bytes" {
+            (32, TypeKind::Bytes)
+        } else if let Some(name) = ty.strip_prefix("struct ") {
+            let members =
+                sources.structs.get(name).ok_or_else(|| eyre::eyre!("unknown struct `{name}`"))?;
+            // recursive structs are only allowed behind mappings and dynamic arrays, which
+            // don't need the size of the struct
+            self.0.insert(
+                ty.to_string(),
+                LayoutType {
+                    label: ty.to_string(),
+                    size: 32,
+                    kind: TypeKind::Struct { members: vec![] },
+                },
+            );
+            let (members, slots) = self.layout_members(members, sources)?;
+            (slots * 32, TypeKind::Struct { members })
+        } else {
+            let size = value_type_size(ty).ok_or_else(|| eyre::eyre!("unsupported type `{ty}`"))?;
+            (size, TypeKind::Value)
+        };
+
+        self.0.insert(ty.to_string(), LayoutType { label: ty.to_string(), size, kind });
+        Ok(ty.to_string())
+    }
+
+    /// Lays out the members of a struct, packing them like the compiler does. Returns the members
+    /// and the number of slots they occupy.
+    fn layout_members(
+        &mut self,
+        members: &[(String, String)],
+        sources: &SourceStructs,
+    ) -> Result<(Vec<Member>, usize)> {
+        let mut laid_out = Vec::with_capacity(members.len());
+        let mut slot = 0;
+        let mut offset = 0;
+        for (label, ty) in members {
+            let ty = self.insert_type_string(ty, sources)?;
+            let LayoutType { size, kind, .. } = self.get(&ty)?;
+            // structs and static arrays start a new slot, and so does what follows them
+            let own_slots = matches!(kind, TypeKind::Struct { .. } | TypeKind::StaticArray { .. });
+            if offset > 0 && (own_slots || offset + size > 32) {
+                slot += 1;
+                offset = 0;
+            }
+            laid_out.push(Member { label: label.clone(), slot: U256::from(slot), offset, ty });
+            if own_slots || *size >= 32 {
+                slot += size.div_ceil(32);
+                offset = 0;
+            } else {
+                offset += size;
+            }
+        }
+        if offset > 0 {
+            slot += 1;
+        }
+        Ok((laid_out, slot.max(1)))
+    }
+}
+
+/// An ERC-7201 namespace of a contract.
+#[derive(Clone, Debug)]
+struct Namespace {
+    /// The namespace id, e.g. `openzeppelin.storage.Ownable`.
+    id: String,
+    /// The canonical name of the struct of the namespace.
+    struct_name: String,
+    /// The contract declaring the namespace, as `path:name`.
+    contract: String,
+}
+
+/// The structs defined in the sources of a contract, and its ERC-7201 namespaces.
+#[derive(Clone, Debug, Default)]
+pub struct SourceStructs {
+    /// Members of the structs as `(name, type string)`, by canonical name.
+    structs: HashMap<String, Vec<(String, String)>>,
+    /// The namespaces of the contract and its bases, in inheritance order.
+    namespaces: Vec<Namespace>,
+}
+
+impl SourceStructs {
+    /// Collects the structs of the source ASTs, and the namespaces of the contract `name` in
+    /// `source` and its bases, i.e. the structs annotated with
+    /// `@custom:storage-location erc7201:<id>`.
+    pub fn new(asts: &[(PathBuf, Ast)], source: &Path, name: &str) -> Self {
+        let mut sources = Self::default();
+        let mut contracts = HashMap::default();
+        let mut target = None;
+        for (path, ast) in asts {
+            for node in &ast.nodes {
+                match node.node_type {
+                    NodeType::StructDefinition => sources.insert_struct(node),
+                    NodeType::ContractDefinition => {
+                        node.nodes
+                            .iter()
+                            .filter(|n| n.node_type == NodeType::StructDefinition)
+                            .for_each(|n| sources.insert_struct(n));
+                        let contract = node.other.get("name").and_then(Value::as_str);
+                        if let Some(id) = node.id {
+                            contracts.insert(id, (path, contract.unwrap_or_default(), node));
+                        }
+                        if path == source && contract == Some(name) {
+                            target = Some(node);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let bases = target
+            .and_then(|node| node.other.get("linearizedBaseContracts"))
+            .and_then(Value::as_array);
+        // Bases are linearized from most derived to most base.
+        for id in bases.into_iter().flatten().rev() {
+            let Some((path, contract, node)) =
+                id.as_u64().and_then(|id| contracts.get(&(id as usize)))
+            else {
+                continue;
+            };
+            for node in &node.nodes {
+                let Some(id) = namespace_id(node) else { continue };
+                let Some(struct_name) = node.other.get("canonicalName").and_then(Value::as_str)
+                else {
+                    continue;
+                };
+                sources.namespaces.push(Namespace {
+                    id,
+                    struct_name: struct_name.to_string(),
+                    contract: format!("{}:{contract}", path.display()),
+                });
+            }
+        }
+        sources
+    }
+
+    /// Returns `true` if the contract has ERC-7201 namespaces.
+    pub fn has_namespaces(&self) -> bool {
+        !self.namespaces.is_empty()
+    }
+
+    fn insert_struct(&mut self, node: &Node) {
+        let Some(name) = node.other.get("canonicalName").and_then(Value::as_str) else { return };
+        let members = node
+            .other
+            .get("members")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|member| {
+                let name = member.get("name")?.as_str()?;
+                let ty = member.get("typeDescriptions")?.get("typeString")?.as_str()?;
+                Some((name.to_string(), ty.to_string()))
+            })
+            .collect();
+        self.structs.insert(name.to_string(), members);
+    }
+}
+
+/// Returns the ERC-7201 namespace id of a struct definition, from its documentation.
+fn namespace_id(node: &Node) -> Option<String> {
+    if node.node_type != NodeType::StructDefinition {
+        return None;
+    }
+    let docs = node.other.get("documentation")?.get("text")?.as_str()?;
+    let id = docs.split_once("@custom:storage-location erc7201:")?.1;
+    id.split_whitespace().next().map(String::from)
+}
+
+/// A variable, or element of a variable, left to decode.
+struct Item {
+    name: String,
+    /// The name with mapping keys and array indexes replaced by `[]`, to look up mapping keys.
+    path: String,
+    ty: String,
+    slot: U256,
+    offset: usize,
+    contract: String,
+}
+
+/// Reads and caches the storage slots of a contract.
+struct SlotReader<P> {
+    provider: P,
+    address: Address,
+    block: BlockId,
+    cache: HashMap<U256, B256>,
+}
+
+impl<P: Provider<AnyNetwork>> SlotReader<P> {
+    async fn read(&mut self, slot: U256) -> Result<B256> {
+        if let Some(value) = self.cache.get(&slot) {
+            return Ok(*value);
+        }
+        let value =
+            self.provider.get_storage_at(self.address, slot).block_id(self.block).await?.into();
+        self.cache.insert(slot, value);
+        Ok(value)
+    }
+
+    /// Reads the slots concurrently.
+    async fn prefetch(&mut self, slots: impl IntoIterator<Item = U256>) -> Result<()> {
+        let mut slots = slots.into_iter().collect::<Vec<_>>();
+        slots.sort_unstable();
+        slots.dedup();
+        let provider = &self.provider;
+        let (address, block) = (self.address, self.block);
+        let values = futures::future::try_join_all(slots.iter().map(|slot| async move {
+            provider.get_storage_at(address, *slot).block_id(block).await
+        }))
+        .await?;
+        self.cache.extend(slots.into_iter().zip(values.into_iter().map(B256::from)));
+        Ok(())
+    }
+}
+
+/// Decodes the storage of the contract at `address`: the variables of the layout and of the
+/// ERC-7201 namespaces of the contract.
+pub async fn decode_storage<P: Provider<AnyNetwork>>(
+    provider: P,
+    address: Address,
+    block: Option<BlockId>,
+    layout: &StorageLayout,
+    sources: &SourceStructs,
+    opts: &DecodeOptions,
+) -> Result<Vec<DecodedSlot>> {
+    let mut types = Types::from_layout(layout)?;
+
+    let mut items = Vec::new();
+    for var in &layout.storage {
+        items.push(Item {
+            name: var.label.clone(),
+            path: var.label.clone(),
+            ty: var.storage_type.clone(),
+            slot: U256::from_str(&var.slot)?,
+            offset: var.offset as usize,
+            contract: var.contract.clone(),
+        });
+    }
+    for namespace in &sources.namespaces {
+        match types.insert_type_string(&format!("struct {}", namespace.struct_name), sources) {
+            Ok(ty) => {
+                let name = namespace.struct_name.rsplit('.').next().unwrap_or_default();
+                items.push(Item {
+                    name: name.to_string(),
+                    path: name.to_string(),
+                    ty,
+                    slot: erc7201(&namespace.id).into(),
+                    offset: 0,
+                    contract: namespace.contract.clone(),
+                });
+            }
+            Err(err) => {
+                sh_warn!("Could not lay out the ERC-7201 namespace `{}`: {err}", namespace.id)?
+            }
+        }
+    }
+
+    let mut reader = SlotReader {
+        provider,
+        address,
+        block: block.unwrap_or_default(),
+        cache: HashMap::default(),
+    };
+    reader.prefetch(items.iter().map(|item| item.slot)).await?;
+
+    let mut decoded = Vec::new();
+    let mut stack = items;
+    stack.reverse();
+    while let Some(item) = stack.pop() {
+        let ty = types.get(&item.ty)?;
+        let mut slot = DecodedSlot {
+            name: item.name.clone(),
+            ty: ty.label.clone(),
+            slot: item.slot,
+            offset: item.offset,
+            bytes: ty.size,
+            value: String::new(),
+            raw: None,
+            contract: item.contract.clone(),
+        };
+        match &ty.kind {
+            TypeKind::Value => {
+                let raw = reader.read(item.slot).await?;
+                slot.value =
+                    decode_value(&ty.label, ty.size, slot_value(raw, item.offset, ty.size));
+                slot.raw = Some(raw);
+            }
+            TypeKind::Bytes => {
+                let raw = reader.read(item.slot).await?;
+                let data = read_bytes(&mut reader, item.slot, raw).await?;
+                slot.value = if ty.label == "string" {
+                    format!("{:?}", String::from_utf8_lossy(&data))
+                } else {
+                    hex::encode_prefixed(data)
+                };
+                slot.raw = Some(raw);
+            }
+            TypeKind::Mapping { key, value } => {
+                let key_label = &types.get(key)?.label;
+                for key in opts.keys.get(&item.path).into_iter().flatten().rev() {
+                    stack.push(Item {
+                        name: format!("{}[{key}]", item.name),
+                        path: format!("{}[]", item.path),
+                        ty: value.clone(),
+                        slot: mapping_slot(key_label, key, item.slot)?,
+                        offset: 0,
+                        contract: item.contract.clone(),
+                    });
+                }
+            }
+            TypeKind::DynamicArray { base } => {
+                let raw = reader.read(item.slot).await?;
+                let len = U256::from_be_bytes(raw.0);
+                slot.value = len.to_string();
+                slot.raw = Some(raw);
+
+                let len = len.saturating_to::<usize>();
+                if len > opts.max_array_length {
+                    sh_warn!(
+                        "Only the first {} of the {len} elements of `{}` are decoded",
+                        opts.max_array_length,
+                        item.name
+                    )?;
+                }
+                let data = U256::from_be_bytes(keccak256(item.slot.to_be_bytes::<32>()).0);
+                let base_size = types.get(base)?.size;
+                for i in (0..len.min(opts.max_array_length)).rev() {
+                    let (slot, offset) = element_position(base_size, i);
+                    stack.push(Item {
+                        name: format!("{}[{i}]", item.name),
+                        path: format!("{}[]", item.path),
+                        ty: base.clone(),
+                        slot: data.wrapping_add(slot),
+                        offset,
+                        contract: item.contract.clone(),
+                    });
+                }
+            }
+            TypeKind::StaticArray { base, len } => {
+                let base_size = types.get(base)?.size;
+                for i in (0..*len).rev() {
+                    let (slot, offset) = element_position(base_size, i);
+                    stack.push(Item {
+                        name: format!("{}[{i}]", item.name),
+                        path: format!("{}[]", item.path),
+                        ty: base.clone(),
+                        slot: item.slot.wrapping_add(slot),
+                        offset,
+                        contract: item.contract.clone(),
+                    });
+                }
+                continue;
+            }
+            TypeKind::Struct { members } => {
+                for member in members.iter().rev() {
+                    stack.push(Item {
+                        name: format!("{}.{}", item.name, member.label),
+                        path: format!("{}.{}", item.path, member.label),
+                        ty: member.ty.clone(),
+                        slot: item.slot.wrapping_add(member.slot),
+                        offset: member.offset,
+                        contract: item.contract.clone(),
+                    });
+                }
+                continue;
+            }
+        }
+        decoded.push(slot);
+    }
+
+    Ok(decoded)
+}
+
+/// Reads the data of a `string` or `bytes` variable, whose slot holds `raw`.
+async fn read_bytes<P: Provider<AnyNetwork>>(
+    reader: &mut SlotReader<P>,
+    slot: U256,
+    raw: B256,
+) -> Result<Vec<u8>> {
+    // short values are stored in place with twice their length in the lowest byte, long values
+    // have twice their length plus one in the slot and their data from `keccak256(slot)`
+    if raw[31] & 1 == 0 {
+        let len = (raw[31] / 2).min(31) as usize;
+        return Ok(raw[..len].to_vec());
+    }
+
+    let len =
+        ((U256::from_be_bytes(raw.0) - U256::from(1)) / U256::from(2)).saturating_to::<usize>();
+    let slots = len.div_ceil(32);
+    if slots > MAX_BYTES_SLOTS {
+        sh_warn!(
+            "Only the first {} bytes of the {len} bytes at slot {slot} are decoded",
+            MAX_BYTES_SLOTS * 32
+        )?;
+    }
+    let data_slot = U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0);
+    let data_slots = (0..slots.min(MAX_BYTES_SLOTS)).map(|i| data_slot.wrapping_add(U256::from(i)));
+    reader.prefetch(data_slots.clone()).await?;
+
+    let mut data = Vec::with_capacity(len);
+    for slot in data_slots {
+        data.extend_from_slice(reader.read(slot).await?.as_slice());
+    }
+    data.truncate(len);
+    Ok(data)
+}
+
+/// Returns the slot of a mapping value, given its key type, key and the slot of the mapping.
+fn mapping_slot(key_label: &str, key: &str, slot: U256) -> Result<U256> {
+    let ty = key_sol_type(key_label)
+        .ok_or_else(|| eyre::eyre!("unsupported mapping key type `{key_label}`"))?;
+    let key_value =
+        ty.coerce_str(key).wrap_err_with(|| format!("invalid `{key_label}` mapping key"))?;
+    let mut preimage = match key_value {
+        DynSolValue::String(s) => s.into_bytes(),
+        DynSolValue::Bytes(b) => b,
+        // value types are padded to 32 bytes like in memory
+        value => value.abi_encode(),
+    };
+    preimage.extend_from_slice(&slot.to_be_bytes::<32>());
+    Ok(U256::from_be_bytes(keccak256(preimage).0))
+}
+
+/// Returns the slot of a value in nested mappings and arrays of type `ty`, e.g.
+/// `mapping(address => uint256[])`, given the keys and indices of each level from the outermost,
+/// and the slot of the variable.
+///
+/// Elements of arrays of up to 16 bytes are packed, the slot holding the element is returned.
+pub fn nested_slot(ty: &str, keys: &[String], slot: &str) -> Result<B256> {
+    let mut types = Types::default();
+    let id = types.insert_type_string(&normalize_type(ty), &SourceStructs::default())?;
+    let mut ty = types.get(&id)?;
+    let mut slot = U256::from_str(slot).wrap_err("invalid slot")?;
+    for key in keys {
+        let (base, first_slot) = match &ty.kind {
+            TypeKind::Mapping { key: key_ty, value } => {
+                slot = mapping_slot(&types.get(key_ty)?.label, key, slot)?;
+                ty = types.get(value)?;
+                continue;
+            }
+            TypeKind::DynamicArray { base } => {
+                (base, U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0))
+            }
+            TypeKind::StaticArray { base, .. } => (base, slot),
+            _ => eyre::bail!("`{}` is not a mapping or an array, too many keys", ty.label),
+        };
+        let index: usize =
+            key.parse().wrap_err_with(|| format!("invalid index `{key}` of `{}`", ty.label))?;
+        if let TypeKind::StaticArray { len, .. } = ty.kind {
+            eyre::ensure!(index < len, "index {index} out of bounds of `{}`", ty.label);
+        }
+        ty = types.get(base)?;
+        slot = first_slot.wrapping_add(element_position(ty.size, index).0);
+    }
+    Ok(slot.into())
+}
+
+/// Normalizes the spacing of a type string to the one of the compiler, e.g.
+/// `mapping(address => uint256)` for `mapping(address=>uint256)`.
+fn normalize_type(ty: &str) -> String {
+    ty.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(" =>", "=>")
+        .replace("=> ", "=>")
+        .replace("=>", " => ")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" [", "[")
+}
+
+/// Returns the Solidity type of a mapping key.
+fn key_sol_type(label: &str) -> Option<DynSolType> {
+    if label.starts_with("contract ") || label == "address payable" {
+        Some(DynSolType::Address)
+    } else if label.starts_with("enum ") {
+        Some(DynSolType::Uint(8))
+    } else {
+        DynSolType::parse(label).ok()
+    }
+}
+
+/// Returns the size of a value type from its type string, `None` if it isn't a known value type.
+fn value_type_size(ty: &str) -> Option<usize> {
+    if ty.starts_with("contract ") || ty == "address" || ty == "address payable" {
+        return Some(20);
+    }
+    if ty.starts_with("enum ") || ty == "bool" {
+        return Some(1);
+    }
+    match key_sol_type(ty)? {
+        DynSolType::Uint(bits) | DynSolType::Int(bits) => Some(bits / 8),
+        DynSolType::FixedBytes(size) => Some(size),
+        DynSolType::Function => Some(24),
+        _ => None,
+    }
+}
+
+/// Returns the length of a static array from its type label, e.g. `uint256[3]`.
+fn static_array_len(label: &str) -> Result<usize> {
+    label
+        .strip_suffix(']')
+        .and_then(|label| label.rsplit_once('['))
+        .and_then(|(_, len)| len.parse().ok())
+        .ok_or_else(|| eyre::eyre!("invalid static array type `{label}`"))
+}
+
+/// Returns the slot and offset of the element `index` of an array, relative to the first slot of
+/// the array. Elements of up to 16 bytes are packed.
+fn element_position(size: usize, index: usize) -> (U256, usize) {
+    if size <= 32 {
+        let per_slot = 32 / size.max(1);
+        (U256::from(index / per_slot), (index % per_slot) * size)
+    } else {
+        (U256::from(index * size.div_ceil(32)), 0)
+    }
+}
+
+/// Returns the `size` bytes at `offset` of a slot, right-aligned.
+fn slot_value(raw: B256, offset: usize, size: usize) -> B256 {
+    let end = (offset + size).min(32);
+    let bytes = &raw[32 - end..32 - offset.min(end)];
+    let mut value = B256::ZERO;
+    value[32 - bytes.len()..].copy_from_slice(bytes);
+    value
+}
+
+/// Formats a value type from its right-aligned bytes in `word`.
+fn decode_value(label: &str, size: usize, word: B256) -> String {
+    let value = match key_sol_type(label) {
+        Some(DynSolType::Bool) => DynSolValue::Bool(!word.is_zero()),
+        Some(DynSolType::Address) => DynSolValue::Address(Address::from_word(word)),
+        Some(DynSolType::Uint(bits)) => DynSolValue::Uint(U256::from_be_bytes(word.0), bits),
+        Some(DynSolType::Int(bits)) => {
+            let mut value = U256::from_be_bytes(word.0);
+            if bits < 256 && value.bit(bits - 1) {
+                value |= U256::MAX << bits;
+            }
+            DynSolValue::Int(I256::from_raw(value), bits)
+        }
+        Some(DynSolType::FixedBytes(len)) if len <= size => {
+            DynSolValue::FixedBytes(B256::right_padding_from(&word[32 - len..]), len)
+        }
+        _ => return hex::encode_prefixed(&word[32 - size.min(32)..]),
+    };
+    format_token_raw(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn lays_out_namespace_structs() {
+        let ast = serde_json::json!({
+            "absolutePath": "src/Token.sol",
+            "id": 0,
+            "exportedSymbols": {},
+            "nodeType": "SourceUnit",
+            "src": "0:0:0",
+            "nodes": [{
+                "nodeType": "ContractDefinition",
+                "src": "0:0:0",
+                "id": 1,
+                "name": "Token",
+                "linearizedBaseContracts": [1],
+                "nodes": [{
+                    "nodeType": "StructDefinition",
+                    "src": "0:0:0",
+                    "id": 2,
+                    "canonicalName": "Token.TokenStorage",
+                    "documentation": {
+                        "text": "@custom:storage-location erc7201:example.token"
+                    },
+                    "members": [
+                        { "name": "paused", "typeDescriptions": { "typeString": "bool" } },
+                        { "name": "owner", "typeDescriptions": { "typeString": "address" } },
+                        { "name": "supply", "typeDescriptions": { "typeString": "uint256" } },
+                        { "name": "decimals", "typeDescriptions": { "typeString": "uint8[3]" } },
+                        {
+                            "name": "balances",
+                            "typeDescriptions": { "typeString": "mapping(address => uint256)" }
+                        }
+                    ]
+                }]
+            }]
+        });
+        let path = PathBuf::from("src/Token.sol");
+        let asts = [(path.clone(), serde_json::from_value(ast).unwrap())];
+        let sources = SourceStructs::new(&asts, &path, "Token");
+        assert_eq!(sources.namespaces.len(), 1);
+        assert_eq!(sources.namespaces[0].id, "example.token");
+        assert_eq!(sources.namespaces[0].contract, "src/Token.sol:Token");
+
+        let mut types = Types::default();
+        let ty = types.insert_type_string("struct Token.TokenStorage", &sources).unwrap();
+        let ty = types.get(&ty).unwrap();
+        assert_eq!(ty.size, 4 * 32);
+        let TypeKind::Struct { members } = &ty.kind else { panic!("expected a struct") };
+        let positions = members
+            .iter()
+            .map(|member| (member.label.as_str(), member.slot.to::<u64>(), member.offset))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            [
+                ("paused", 0, 0),
+                ("owner", 0, 1),
+                ("supply", 1, 0),
+                ("decimals", 2, 0),
+                ("balances", 3, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_values() {
+        let word = B256::with_last_byte(0xff);
+        assert_eq!(decode_value("int8", 1, word), "-1");
+        assert_eq!(decode_value("uint8", 1, word), "255");
+        assert_eq!(decode_value("bool", 1, word), "true");
+        assert_eq!(decode_value("bytes1", 1, word), "0xff");
+        assert_eq!(decode_value("enum Token.State", 1, word), "255");
+
+        let raw = b256!("00000000000000000000000000000000000000000000000000000000000a0b01");
+        assert_eq!(slot_value(raw, 1, 1), B256::with_last_byte(0x0b));
+        assert_eq!(element_position(1, 33), (U256::from(1), 1));
+        assert_eq!(element_position(64, 2), (U256::from(4), 0));
+    }
+
+    #[test]
+    fn computes_nested_slots() {
+        let owner = "0xD0074F4E6490ae3f888d1d4f7E3E43326bD3f0f5";
+        let keys = [owner.to_string(), "42".to_string()];
+        let inner = crate::SimpleCast::index("address", owner, "2").unwrap();
+        let expected = crate::SimpleCast::index("uint256", "42", &inner).unwrap();
+        let slot = nested_slot("mapping(address=>mapping(uint => uint256))", &keys, "2").unwrap();
+        assert_eq!(slot.to_string(), expected);
+
+        let data = |slot: U256| U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0);
+        let keys = ["1".to_string(), "33".to_string()];
+        let outer = data(U256::from(2)) + U256::from(1);
+        assert_eq!(
+            nested_slot("uint8[][]", &keys, "2").unwrap(),
+            B256::from(data(outer) + U256::from(1))
+        );
+        // static arrays are laid out in place
+        assert_eq!(
+            nested_slot("uint256[2][3]", &keys[..1], "2").unwrap(),
+            B256::from(U256::from(4))
+        );
+
+        assert!(nested_slot("uint256[2]", &["2".to_string()], "0").is_err());
+        assert!(nested_slot("uint256", &keys, "0").is_err());
+    }
+}
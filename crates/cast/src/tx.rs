@@ -1,5 +1,5 @@
 use crate::traces::identifier::SignaturesIdentifier;
-use alloy_consensus::{SidecarBuilder, SignableTransaction, SimpleCoder};
+use alloy_consensus::{BlobTransactionSidecar, SignableTransaction};
 use alloy_dyn_abi::ErrorExt;
 use alloy_json_abi::Function;
 use alloy_network::{
@@ -8,7 +8,7 @@
 };
 use alloy_primitives::{hex, Address, Bytes, TxKind, U256};
 use alloy_provider::Provider;
-use alloy_rpc_types::{AccessList, Authorization, TransactionInput, TransactionRequest};
+use alloy_rpc_types::{AccessList, Authorization, BlockId, TransactionInput, TransactionRequest};
 use alloy_serde::WithOtherFields;
 use alloy_signer::Signer;
 use alloy_transport::TransportError;
@@ -21,6 +21,7 @@
 use foundry_config::{Chain, Config};
 use foundry_wallets::{WalletOpts, WalletSigner};
 use itertools::Itertools;
+use serde::Serialize;
 use serde_json::value::RawValue;
 use std::fmt::Write;
 
@@ -142,6 +143,8 @@ pub struct CastTxBuilder<P, S> {
     chain: Chain,
     etherscan_api_key: Option<String>,
     access_list: Option<Option<AccessList>>,
+    /// Whether to apply the access list created by the node when it lowers the gas estimate.
+    auto_access_list: bool,
     state: S,
 }
 
@@ -194,6 +197,7 @@ pub async fn new(provider: P, tx_opts: TransactionOpts, config: &Config) -> Resu
             etherscan_api_key,
             auth: tx_opts.auth,
             access_list: tx_opts.access_list,
+            auto_access_list: false,
             state: InitState,
         })
     }
@@ -210,6 +214,7 @@ pub async fn with_to(self, to: Option<NameOrAddress>) -> Result<CastTxBuilder<P,
             etherscan_api_key: self.etherscan_api_key,
             auth: self.auth,
             access_list: self.access_list,
+            auto_access_list: self.auto_access_list,
             state: ToState { to },
         })
     }
@@ -266,6 +271,7 @@ pub async fn with_code_sig_and_args(
             etherscan_api_key: self.etherscan_api_key,
             auth: self.auth,
             access_list: self.access_list,
+            auto_access_list: self.auto_access_list,
             state: InputState { kind: self.state.to.into(), input, func },
         })
     }
@@ -380,6 +386,17 @@ async fn _build(
             }
         }
 
+        if self.auto_access_list && !self.legacy && self.tx.access_list.is_none() {
+            let estimate =
+                estimate_access_list(&self.provider, &self.tx, BlockId::latest()).await?;
+            if estimate.is_beneficial() {
+                self.tx.set_access_list(estimate.access_list);
+                if self.tx.gas.is_none() {
+                    self.tx.gas = Some(estimate.gas_with_access_list);
+                }
+            }
+        }
+
         if self.tx.gas.is_none() {
             self.estimate_gas().await?;
         }
@@ -443,20 +460,60 @@ impl<P, S> CastTxBuilder<P, S>
 where
     P: Provider<AnyNetwork>,
 {
-    pub fn with_blob_data(mut self, blob_data: Option<Vec<u8>>) -> Result<Self> {
-        let Some(blob_data) = blob_data else { return Ok(self) };
+    pub fn with_blob_sidecar(mut self, sidecar: Option<BlobTransactionSidecar>) -> Self {
+        if let Some(sidecar) = sidecar {
+            self.tx.set_blob_sidecar(sidecar);
+            self.tx.populate_blob_hashes();
+        }
+        self
+    }
 
-        let mut coder = SidecarBuilder::<SimpleCoder>::default();
-        coder.ingest(&blob_data);
-        let sidecar = coder.build()?;
+    /// Applies the access list created by the node to the transaction if it lowers its gas
+    /// estimate, unless an access list is already set.
+    pub fn with_auto_access_list(mut self, auto_access_list: bool) -> Self {
+        self.auto_access_list = auto_access_list;
+        self
+    }
+}
+
+/// The gas estimates of a transaction without and with the access list created by the node.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListEstimate {
+    pub access_list: AccessList,
+    pub gas: u64,
+    pub gas_with_access_list: u64,
+}
 
-        self.tx.set_blob_sidecar(sidecar);
-        self.tx.populate_blob_hashes();
+impl AccessListEstimate {
+    /// Returns the gas saved by the access list, negative if it costs more gas.
+    pub fn delta(&self) -> i128 {
+        self.gas as i128 - self.gas_with_access_list as i128
+    }
 
-        Ok(self)
+    /// Whether the access list lowers the gas of the transaction.
+    pub fn is_beneficial(&self) -> bool {
+        !self.access_list.0.is_empty() && self.gas_with_access_list < self.gas
     }
 }
 
+/// Creates the access list of a transaction with `eth_createAccessList`, and estimates its gas
+/// without and with the access list.
+pub async fn estimate_access_list<P: Provider<AnyNetwork>>(
+    provider: &P,
+    tx: &WithOtherFields<TransactionRequest>,
+    block: BlockId,
+) -> Result<AccessListEstimate> {
+    let gas = provider.estimate_gas(tx.clone()).block(block).await?;
+    let access_list = provider.create_access_list(tx).block_id(block).await?.access_list;
+
+    let mut tx = tx.clone();
+    tx.set_access_list(access_list.clone());
+    let gas_with_access_list = provider.estimate_gas(tx).block(block).await?;
+
+    Ok(AccessListEstimate { access_list, gas, gas_with_access_list })
+}
+
 /// Helper function that tries to decode custom error name and inputs from error payload data.
 async fn decode_execution_revert(data: &RawValue) -> Result<Option<String>> {
     if let Some(err_data) = serde_json::from_str::<String>(data.get())?.strip_prefix("0x") {
@@ -401,6 +401,117 @@
     ]);
 });
 
+// tests that keystore accounts can be labeled, used by label and exported
+casttest!(wallet_label_and_export_keystore, |prj, cmd| {
+    let keystore_path = prj.root().join("keystore");
+    cmd.set_current_dir(prj.root());
+
+    cmd.cast_fuse()
+        .args([
+            "wallet",
+            "import",
+            "deployer",
+            "--private-key",
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            "-k",
+            "keystore",
+            "--unsafe-password",
+            "test",
+        ])
+        .assert_success();
+
+    cmd.cast_fuse()
+        .args(["wallet", "label", "deployer", "prod", "-k", "keystore"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+`deployer` was labeled `prod` successfully.
+
+"#]]);
+
+    cmd.cast_fuse().args(["wallet", "list", "--dir", "keystore"]).assert_success().stdout_eq(str![
+        [r#"
+deployer (Local) [prod]
+
+"#]
+    ]);
+
+    cmd.cast_fuse()
+        .args(["wallet", "decrypt-keystore", "prod", "-k", "keystore", "--unsafe-password", "test"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+prod's private key is: 0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80
+
+"#]]);
+
+    let exported = prj.root().join("deployer.json");
+    cmd.cast_fuse()
+        .args(["wallet", "export", "prod", "-k", "keystore", "-o"])
+        .arg(&exported)
+        .assert_success()
+        .stdout_eq(str![[r#"
+`prod` keystore was exported successfully to [..]
+
+"#]]);
+    assert_eq!(
+        fs::read_to_string(&exported).unwrap(),
+        fs::read_to_string(keystore_path.join("deployer")).unwrap()
+    );
+
+    // removing the account removes its labels
+    cmd.cast_fuse()
+        .args([
+            "wallet",
+            "rm",
+            "--name",
+            "deployer",
+            "--dir",
+            "keystore",
+            "--unsafe-password",
+            "test",
+        ])
+        .assert_success();
+    cmd.cast_fuse()
+        .args(["wallet", "export", "prod", "-k", "keystore"])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: Keystore file does not exist at [..]
+
+"#]]);
+});
+
+// tests that `cast wallet derive` derives the accounts of a mnemonic with a custom path
+casttest!(wallet_derive_mnemonic_accounts, |_prj, cmd| {
+    let mnemonic = "test test test test test test test test test test test junk";
+    cmd.args(["wallet", "derive", mnemonic, "--accounts", "2"]).assert_success().stdout_eq(str![[
+        r#"
+- Account 0 (m/44'/60'/0'/0/0):
+Address:     0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+- Account 1 (m/44'/60'/0'/0/1):
+Address:     0x70997970C51812dc3A010C7d01b50e0d17dc79C8
+
+"#
+    ]]);
+
+    cmd.cast_fuse()
+        .args([
+            "wallet",
+            "derive",
+            mnemonic,
+            "--derivation-path",
+            "m/44'/60'/0'/0/{}",
+            "--start",
+            "1",
+            "--private-keys",
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+- Account 1 (m/44'/60'/0'/0/1):
+Address:     0x70997970C51812dc3A010C7d01b50e0d17dc79C8
+Private key: 0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d
+
+"#]]);
+});
+
 // tests that `cast wallet new-mnemonic --entropy` outputs the expected mnemonic
 casttest!(wallet_mnemonic_from_entropy, |_prj, cmd| {
     cmd.args([
@@ -849,6 +960,98 @@
 "#]]);
 });
 
+// tests that `cast multicall` batches calls and prints their results in order
+casttest!(multicall, async |prj, cmd| {
+    let (_api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+
+    // Deploy a contract which returns 42 for any call.
+    cmd.args([
+        "send",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &endpoint,
+        "--create",
+        "0x69602a60005260206000f3600052600a6016f3",
+    ])
+    .assert_success();
+
+    let calls = prj.root().join("calls.txt");
+    std::fs::write(
+        &calls,
+        "# both calls return 42\n\
+         0x5FbDB2315678afecb367f032d93F642f64180aa3 number()(uint256)\n\
+         0x5FbDB2315678afecb367f032d93F642f64180aa3 balanceOf(address)(uint256) 0x0000000000000000000000000000000000000001\n",
+    )
+    .unwrap();
+
+    cmd.cast_fuse()
+        .args(["multicall", "--rpc-url", &endpoint])
+        .arg(&calls)
+        .assert_success()
+        .stdout_eq(str![[r#"
+[
+  {
+    "target": "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+    "signature": "number()(uint256)",
+    "success": true,
+    "returnData": "0x000000000000000000000000000000000000000000000000000000000000002a",
+    "decoded": [
+      "42"
+    ]
+  },
+  {
+    "target": "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+    "signature": "balanceOf(address)(uint256)",
+    "success": true,
+    "returnData": "0x000000000000000000000000000000000000000000000000000000000000002a",
+    "decoded": [
+      "42"
+    ]
+  }
+]
+
+"#]]);
+});
+
+// checks `cast abi-encode` and `cast abi-decode` can handle structs with a contract ABI
+casttest!(abi_encode_decode_with_abi_file, |prj, cmd| {
+    let abi = prj.root().join("abi.json");
+    std::fs::write(
+        &abi,
+        r#"[{"type":"function","name":"doThing","stateMutability":"nonpayable","outputs":[],"inputs":[{"name":"s","type":"tuple","components":[{"name":"a","type":"uint256"},{"name":"b","type":"address[]"}]}]}]"#,
+    )
+    .unwrap();
+    let args = prj.root().join("args.json");
+    std::fs::write(&args, r#"{"s":{"a":1,"b":["0x0000000000000000000000000000000000000001"]}}"#)
+        .unwrap();
+
+    cmd.args(["abi-encode", "--abi", abi.to_str().unwrap(), "--function", "doThing"])
+        .arg(&args)
+        .assert_success()
+        .stdout_eq(str![[r#"
+0x00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001
+
+"#]]);
+
+    cmd.cast_fuse()
+        .args(["abi-decode", "--abi", abi.to_str().unwrap(), "--function", "doThing", "--input"])
+        .arg("0x00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001")
+        .assert_success()
+        .stdout_eq(str![[r#"
+{
+  "s": {
+    "a": "1",
+    "b": [
+      "0x0000000000000000000000000000000000000001"
+    ]
+  }
+}
+
+"#]]);
+});
+
 // <https://github.com/foundry-rs/foundry/issues/2705>
 casttest!(run_succeeds, |_prj, cmd| {
     let rpc = next_http_archive_rpc_url();
@@ -1625,6 +1828,16 @@
 "#]]);
 });
 
+casttest!(ens_text_no_resolver, |_prj, cmd| {
+    let eth_rpc_url = next_http_rpc_endpoint();
+    cmd.args(["ens", "text", "emo", "url", "--rpc-url", &eth_rpc_url]).assert_failure().stderr_eq(
+        str![[r#"
+Error: ENS resolver not found for name "emo"
+
+"#]],
+    );
+});
+
 casttest!(index7201, |_prj, cmd| {
     cmd.args(["index-erc7201", "example.main"]).assert_success().stdout_eq(str![[r#"
 0x183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500
@@ -1641,6 +1854,43 @@
     );
 });
 
+// tests that `cast index --type` indexes nested mappings like successive `cast index`
+casttest!(index_nested_mapping, |_prj, cmd| {
+    let owner = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+    let inner = cmd
+        .args(["index", "address", owner, "2"])
+        .assert_success()
+        .get_output()
+        .stdout_lossy()
+        .trim()
+        .to_string();
+    let expected = cmd
+        .cast_fuse()
+        .args(["index", "uint256", "42", &inner])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+
+    cmd.cast_fuse()
+        .args([
+            "index",
+            "--type",
+            "mapping(address => mapping(uint256 => uint256))",
+            owner,
+            "42",
+            "2",
+        ])
+        .assert_success()
+        .stdout_eq(expected);
+
+    cmd.cast_fuse().args(["index", "--type", "uint256", "1", "2"]).assert_failure().stderr_eq(
+        str![[r#"
+Error: `uint256` is not a mapping or an array, too many keys
+
+"#]],
+    );
+});
+
 casttest!(block_number, |_prj, cmd| {
     let eth_rpc_url = next_http_rpc_endpoint();
     let s = cmd
@@ -1767,67 +2017,459 @@
 "#]]);
 });
 
-casttest!(hash_message, |_prj, cmd| {
-    cmd.args(["hash-message", "hello"]).assert_success().stdout_eq(str![[r#"
-0x50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750
-
-"#]]);
-
-    cmd.cast_fuse().args(["hash-message", "0x68656c6c6f"]).assert_success().stdout_eq(str![[r#"
-0x83a0870b6c63a71efdd3b2749ef700653d97454152c4b53fa9b102dc430c7c32
+// tests that `cast send --batch` sends the transactions with sequential nonces
+casttest!(send_batch, async |prj, cmd| {
+    let (_api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
 
-"#]]);
-});
+    let batch = prj.root().join("batch.json");
+    fs::write(
+        &batch,
+        r#"[{"to": "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC", "value": "2ether"}]"#,
+    )
+    .unwrap();
 
-casttest!(parse_units, |_prj, cmd| {
-    cmd.args(["parse-units", "1.5", "6"]).assert_success().stdout_eq(str![[r#"
-1500000
+    cmd.args([
+        "send",
+        "--batch",
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+        "--batch",
+        "0x90F79bf6EB2c4f870365E785982E1f101E93b906",
+        "--batch-file",
+        batch.to_str().unwrap(),
+        "--value",
+        "1ether",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &endpoint,
+        "--json",
+    ])
+    .assert_success()
+    .stdout_eq(str![[r#"
+[
+  {
+    "nonce": 0,
+    "to": "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+    "sig": null,
+    "tx_hash": "[..]",
+    "status": "success",
+    "block_number": [..],
+    "gas_used": 21000
+  },
+  {
+    "nonce": 1,
+    "to": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+    "sig": null,
+    "tx_hash": "[..]",
+    "status": "success",
+    "block_number": [..],
+    "gas_used": 21000
+  },
+  {
+    "nonce": 2,
+    "to": "0x90F79bf6EB2c4f870365E785982E1f101E93b906",
+    "sig": null,
+    "tx_hash": "[..]",
+    "status": "success",
+    "block_number": [..],
+    "gas_used": 21000
+  }
+]
 
 "#]]);
 
-    cmd.cast_fuse().args(["pun", "1.23", "18"]).assert_success().stdout_eq(str![[r#"
-1230000000000000000
+    cmd.cast_fuse()
+        .args(["nonce", "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "--rpc-url", &endpoint])
+        .assert_success()
+        .stdout_eq(str![[r#"
+3
 
 "#]]);
 
-    cmd.cast_fuse().args(["--parse-units", "1.23", "3"]).assert_success().stdout_eq(str![[r#"
-1230
+    cmd.cast_fuse()
+        .args([
+            "balance",
+            "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+            "--ether",
+            "--rpc-url",
+            &endpoint,
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+10002.000000000000000000
 
 "#]]);
 });
 
-casttest!(string_decode, |_prj, cmd| {
-    cmd.args(["string-decode", "0x88c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000054753303235000000000000000000000000000000000000000000000000000000"]).assert_success().stdout_eq(str![[r#"
-"GS025"
-
-"#]]);
-});
+// tests that `cast storage --decode` decodes the layout and namespaces of local contracts
+forgetest_async!(storage_decode_local_layout, |prj, cmd| {
+    let (_api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
 
-casttest!(format_units, |_prj, cmd| {
-    cmd.args(["format-units", "1000000", "6"]).assert_success().stdout_eq(str![[r#"
-1
+    foundry_test_utils::util::initialize(prj.root());
+    prj.add_source(
+        "Vault",
+        r#"
+contract Vault {
+    /// @custom:storage-location erc7201:example.vault
+    struct VaultStorage {
+        address admin;
+        uint64 fee;
+    }
 
-"#]]);
+    bool public paused = true;
+    uint8 public version = 3;
+    address public owner = 0x70997970C51812dc3A010C7d01b50e0d17dc79C8;
+    string public name = "Vault";
+    uint16[] public ids;
+    mapping(address => uint256) public balances;
 
-    cmd.cast_fuse().args(["--format-units", "2500000", "6"]).assert_success().stdout_eq(str![[
+    constructor() {
+        ids.push(1);
+        ids.push(2);
+        balances[0x70997970C51812dc3A010C7d01b50e0d17dc79C8] = 42;
+
+        bytes32 location = keccak256(abi.encode(uint256(keccak256("example.vault")) - 1))
+            & ~bytes32(uint256(0xff));
+        VaultStorage storage $;
+        assembly {
+            $.slot := location
+        }
+        $.admin = msg.sender;
+        $.fee = 5;
+    }
+}
+   "#,
+    )
+    .unwrap();
+    prj.add_script(
+        "VaultScript",
         r#"
-2.500000
+import "forge-std/Script.sol";
+import {Vault} from "../src/Vault.sol";
+contract VaultScript is Script {
+    function run() public {
+        vm.startBroadcast();
+        new Vault();
+        vm.stopBroadcast();
+    }
+}
+   "#,
+    )
+    .unwrap();
 
-"#
-    ]]);
+    cmd.args([
+        "script",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &endpoint,
+        "--broadcast",
+        "VaultScript",
+    ])
+    .assert_success();
 
-    cmd.cast_fuse().args(["fun", "1230", "3"]).assert_success().stdout_eq(str![[r#"
-1.230
+    cmd.cast_fuse().set_current_dir(prj.root());
+    cmd.cast_fuse()
+        .args([
+            "storage",
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            "--decode",
+            "--key",
+            "balances=0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "--rpc-url",
+            &endpoint,
+            "--json",
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+[
+  {
+    "name": "paused",
+    "type": "bool",
+    "slot": "0x0",
+    "offset": 0,
+    "bytes": 1,
+    "value": "true",
+    "raw": "0x[..]",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "version",
+    "type": "uint8",
+    "slot": "0x0",
+    "offset": 1,
+    "bytes": 1,
+    "value": "3",
+    "raw": "0x[..]",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "owner",
+    "type": "address",
+    "slot": "0x0",
+    "offset": 2,
+    "bytes": 20,
+    "value": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+    "raw": "0x[..]",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "name",
+    "type": "string",
+    "slot": "0x1",
+    "offset": 0,
+    "bytes": 32,
+    "value": "\"Vault\"",
+    "raw": "0x[..]",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "ids",
+    "type": "uint16[]",
+    "slot": "0x2",
+    "offset": 0,
+    "bytes": 32,
+    "value": "2",
+    "raw": "0x0000000000000000000000000000000000000000000000000000000000000002",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "ids[0]",
+    "type": "uint16",
+    "slot": "0x405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ace",
+    "offset": 0,
+    "bytes": 2,
+    "value": "1",
+    "raw": "0x[..]",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "ids[1]",
+    "type": "uint16",
+    "slot": "0x405787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5ace",
+    "offset": 2,
+    "bytes": 2,
+    "value": "2",
+    "raw": "0x[..]",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "balances",
+    "type": "mapping(address => uint256)",
+    "slot": "0x3",
+    "offset": 0,
+    "bytes": 32,
+    "value": "",
+    "raw": null,
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "balances[0x70997970C51812dc3A010C7d01b50e0d17dc79C8]",
+    "type": "uint256",
+    "slot": "0x[..]",
+    "offset": 0,
+    "bytes": 32,
+    "value": "42",
+    "raw": "0x000000000000000000000000000000000000000000000000000000000000002a",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "VaultStorage.admin",
+    "type": "address",
+    "slot": "0x[..]",
+    "offset": 0,
+    "bytes": 20,
+    "value": "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+    "raw": "0x[..]",
+    "contract": "src/Vault.sol:Vault"
+  },
+  {
+    "name": "VaultStorage.fee",
+    "type": "uint64",
+    "slot": "0x[..]",
+    "offset": 20,
+    "bytes": 8,
+    "value": "5",
+    "raw": "0x[..]",
+    "contract": "src/Vault.sol:Vault"
+  }
+]
 
 "#]]);
 });
 
-// tests that fetches a sample contract creation code
-// <https://etherscan.io/address/0x0923cad07f06b2d0e5e49e63b8b35738d4156b95>
-casttest!(fetch_creation_code_from_etherscan, |_prj, cmd| {
-    let eth_rpc_url = next_http_rpc_endpoint();
+casttest!(proof_verify, async |_prj, cmd| {
+    let (_api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+
+    // mine a block, so that the state root of the latest block is the current state
     cmd.args([
-        "creation-code",
+        "send",
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+        "--value",
+        "1ether",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &endpoint,
+    ])
+    .assert_success();
+
+    cmd.cast_fuse()
+        .args(["proof", "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "0", "--verify"])
+        .args(["--rpc-url", &endpoint])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Account 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266: valid
+Storage slot 0x0000000000000000000000000000000000000000000000000000000000000000: valid
+
+"#]]);
+
+    let proof = cmd
+        .cast_fuse()
+        .args(["proof", "0x70997970C51812dc3A010C7d01b50e0d17dc79C8", "--rpc-url", &endpoint])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    cmd.cast_fuse()
+        .args(["verify-proof", proof.trim(), "--rpc-url", &endpoint])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Account 0x70997970C51812dc3A010C7d01b50e0d17dc79C8: valid
+
+"#]]);
+
+    // a proof of another balance
+    let mut proof: serde_json::Value = serde_json::from_str(&proof).unwrap();
+    proof["balance"] = "0x1".into();
+    cmd.cast_fuse()
+        .args(["verify-proof", &proof.to_string(), "--rpc-url", &endpoint])
+        .assert_failure()
+        .stdout_eq(str![[r#"
+Account 0x70997970C51812dc3A010C7d01b50e0d17dc79C8: invalid ([..])
+
+"#]])
+        .stderr_eq(str![[r#"
+Error: proof verification failed: 1 of 1 proofs are invalid
+
+"#]]);
+});
+
+casttest!(blob_encode_decode_send, async |prj, cmd| {
+    let data = prj.root().join("data.txt");
+    fs::write(&data, "hello blob").unwrap();
+    let sidecar = prj.root().join("sidecar.json");
+
+    cmd.args(["blob", "encode", data.to_str().unwrap(), "--out", sidecar.to_str().unwrap()])
+        .assert_success()
+        .stdout_eq(str![[""]]);
+
+    cmd.cast_fuse().args(["blob", "decode", sidecar.to_str().unwrap()]).assert_success().stdout_eq(
+        str![[r#"
+0x68656c6c6f20626c6f62
+
+"#]],
+    );
+
+    cmd.cast_fuse()
+        .args(["blob", "commitment", sidecar.to_str().unwrap(), "--json"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+[
+  {
+    "versionedHash": "0x01[..]",
+    "commitment": "0x[..]"
+  }
+]
+
+"#]]);
+
+    let (_api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let output = cmd
+        .cast_fuse()
+        .args([
+            "send",
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "--blob",
+            "--blob-file",
+            sidecar.to_str().unwrap(),
+            "--private-key",
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            "--rpc-url",
+            &handle.http_endpoint(),
+            "--json",
+        ])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let receipt: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(receipt["status"], "0x1");
+    assert_eq!(receipt["type"], "0x3");
+});
+
+casttest!(hash_message, |_prj, cmd| {
+    cmd.args(["hash-message", "hello"]).assert_success().stdout_eq(str![[r#"
+0x50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750
+
+"#]]);
+
+    cmd.cast_fuse().args(["hash-message", "0x68656c6c6f"]).assert_success().stdout_eq(str![[r#"
+0x83a0870b6c63a71efdd3b2749ef700653d97454152c4b53fa9b102dc430c7c32
+
+"#]]);
+});
+
+casttest!(parse_units, |_prj, cmd| {
+    cmd.args(["parse-units", "1.5", "6"]).assert_success().stdout_eq(str![[r#"
+1500000
+
+"#]]);
+
+    cmd.cast_fuse().args(["pun", "1.23", "18"]).assert_success().stdout_eq(str![[r#"
+1230000000000000000
+
+"#]]);
+
+    cmd.cast_fuse().args(["--parse-units", "1.23", "3"]).assert_success().stdout_eq(str![[r#"
+1230
+
+"#]]);
+});
+
+casttest!(string_decode, |_prj, cmd| {
+    cmd.args(["string-decode", "0x88c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000054753303235000000000000000000000000000000000000000000000000000000"]).assert_success().stdout_eq(str![[r#"
+"GS025"
+
+"#]]);
+});
+
+casttest!(format_units, |_prj, cmd| {
+    cmd.args(["format-units", "1000000", "6"]).assert_success().stdout_eq(str![[r#"
+1
+
+"#]]);
+
+    cmd.cast_fuse().args(["--format-units", "2500000", "6"]).assert_success().stdout_eq(str![[
+        r#"
+2.500000
+
+"#
+    ]]);
+
+    cmd.cast_fuse().args(["fun", "1230", "3"]).assert_success().stdout_eq(str![[r#"
+1.230
+
+"#]]);
+});
+
+// tests that fetches a sample contract creation code
+// <https://etherscan.io/address/0x0923cad07f06b2d0e5e49e63b8b35738d4156b95>
+casttest!(fetch_creation_code_from_etherscan, |_prj, cmd| {
+    let eth_rpc_url = next_http_rpc_endpoint();
+    cmd.args([
+        "creation-code",
         "--etherscan-api-key",
         &next_mainnet_etherscan_api_key(),
         "0x0923cad07f06b2d0e5e49e63b8b35738d4156b95",
@@ -2092,6 +2734,50 @@
     └─ ← [Stop]
 
 
+Transaction successfully executed.
+[GAS]
+
+"#]]);
+});
+
+// tests cast call --trace identifies contracts deployed before the call with local artifacts
+forgetest_async!(call_trace_identifies_deployed_contracts, |prj, cmd| {
+    let (_api, handle) = anvil::spawn(NodeConfig::test()).await;
+
+    foundry_test_utils::util::initialize(prj.root());
+    // Deploy counter contract.
+    cmd.args([
+        "script",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &handle.http_endpoint(),
+        "--broadcast",
+        "CounterScript",
+    ])
+    .assert_success();
+
+    // Assert the counter is matched by its deployed bytecode.
+    cmd.cast_fuse()
+        .set_current_dir(prj.root())
+        .args([
+            "call",
+            "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            "number()",
+            "--trace",
+            "--la",
+            "--rpc-url",
+            &handle.http_endpoint(),
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Compiling project to generate artifacts
+No files changed, compilation skipped
+Traces:
+  [..] Counter::number()[..]
+    └─ ← [Return] 0
+
+
 Transaction successfully executed.
 [GAS]
 
@@ -2352,3 +3038,399 @@ struct Info {
 
 "#]]);
 });
+
+// tests that `cast explain` describes a transaction sending ether
+casttest!(explain_ether_transfer, async |_prj, cmd| {
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+
+    let output = cmd
+        .args([
+            "send",
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "--value",
+            "1ether",
+            "--private-key",
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            "--rpc-url",
+            &rpc,
+            "--json",
+        ])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let receipt: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let tx_hash = receipt["transactionHash"].as_str().unwrap();
+
+    cmd.cast_fuse().args(["explain", tx_hash, "--quick", "--rpc-url", &rpc]).assert_success().stdout_eq(
+        str![[r#"
+Transaction [..] succeeded in block 1.
+0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266 sent 1 ETH to 0x70997970C51812dc3A010C7d01b50e0d17dc79C8.
+It used 21000 gas at [..] gwei, paying [..] ETH.
+
+Ether transfers:
+  1 ETH from 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266 to 0x70997970C51812dc3A010C7d01b50e0d17dc79C8
+
+"#]],
+    );
+});
+
+// tests that `cast call` executes with overridden code, on the node and locally
+casttest!(call_with_state_override, async |_prj, cmd| {
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+
+    // returns 42
+    let code_override =
+        "0x0000000000000000000000000000000000001234:0x602a60005260206000f3".to_string();
+    cmd.args([
+        "call",
+        "0x0000000000000000000000000000000000001234",
+        "--override-code",
+        &code_override,
+        "--rpc-url",
+        &rpc,
+    ])
+    .assert_success()
+    .stdout_eq(str![[r#"
+0x000000000000000000000000000000000000000000000000000000000000002a
+
+"#]]);
+
+    cmd.cast_fuse()
+        .args([
+            "call",
+            "0x0000000000000000000000000000000000001234",
+            "--override-code",
+            &code_override,
+            "--trace",
+            "--rpc-url",
+            &rpc,
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+[..] ← [Return] 0x000000000000000000000000000000000000000000000000000000000000002a
+...
+"#]]);
+});
+
+// tests that `cast estimate-l1-fee` detects the L2 stack from the GasPriceOracle predeploy
+casttest!(estimate_l1_fee, async |_prj, cmd| {
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+
+    cmd.args([
+        "estimate-l1-fee",
+        "0x0000000000000000000000000000000000001234",
+        "--from",
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+        "--rpc-url",
+        &rpc,
+    ])
+    .assert_failure()
+    .stderr_eq(str![[r#"
+Error: could not detect the L2 stack of chain [..], use `--stack` to specify it
+
+"#]]);
+
+    // an oracle returning 1 for all fee parameters, on Fjord
+    cmd.cast_fuse()
+        .args([
+            "rpc",
+            "anvil_setCode",
+            "0x420000000000000000000000000000000000000F",
+            "0x600160005260206000f3",
+            "--rpc-url",
+            &rpc,
+        ])
+        .assert_success();
+
+    cmd.cast_fuse()
+        .args([
+            "estimate-l1-fee",
+            "0x0000000000000000000000000000000000001234",
+            "--from",
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            "--json",
+            "--rpc-url",
+            &rpc,
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+{
+  "l1Fee": "0x0",
+  "l1BaseFee": "0x1",
+  "blobBaseFee": "0x1",
+  "baseFeeScalar": 1,
+  "blobBaseFeeScalar": 1,
+  "size": [..],
+  "compressedSize": 100,
+  "l1Gas": 1600
+}
+
+"#]]);
+});
+
+// tests that `cast tx-pool content` highlights the nonces missing before queued transactions
+casttest!(txpool_content_nonce_gaps, async |_prj, cmd| {
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+    let sender = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    cmd.args([
+        "send",
+        "0x0000000000000000000000000000000000001234",
+        "--value",
+        "1",
+        "--nonce",
+        "2",
+        "--async",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &rpc,
+    ])
+    .assert_success();
+
+    let output = cmd
+        .cast_fuse()
+        .args(["tx-pool", "content", "--from", sender, "--json", "--rpc-url", &rpc])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let senders: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(senders[0]["nonce"], 0);
+    assert_eq!(senders[0]["gaps"], serde_json::json!([[0, 1]]));
+    assert_eq!(senders[0]["transactions"][0]["nonce"], 2);
+
+    cmd.cast_fuse()
+        .args(["tx-pool", "inspect", "--from", sender, "--rpc-url", &rpc])
+        .assert_success()
+        .stdout_eq(str![[r#"
+0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266 (nonce 0)
+  nonces 0 to 1 are missing
+[..] nonce 2 to 0x0000000000000000000000000000000000001234 value 1 gas [..] gas price [..] gwei
+
+"#]]);
+});
+
+// tests that `cast logs --follow` streams decoded logs until the end block
+casttest!(logs_follow_decoded, async |_prj, cmd| {
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+
+    // deploys a contract whose constructor emits `Ping(42)`
+    cmd.args([
+        "send",
+        "--create",
+        "0x602a6000527f48257dc961b6f792c2b78a080dacfed693b660960a702de21cee364e20270e2f60206000a100",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &rpc,
+    ])
+    .assert_success();
+
+    cmd.cast_fuse()
+        .args([
+            "logs",
+            "--follow",
+            "--from-block",
+            "0",
+            "--to-block",
+            "1",
+            "Ping(uint256 value)",
+            "--rpc-url",
+            &rpc,
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+1          0x[..] 0    0x5FbDB2315678afecb367f032d93F642f64180aa3 Ping(value: 42)
+
+"#]]);
+
+    let output = cmd
+        .cast_fuse()
+        .args([
+            "logs",
+            "--follow",
+            "--from-block",
+            "1",
+            "--to-block",
+            "1",
+            "--format",
+            "json",
+            "Ping(uint256 value)",
+            "--rpc-url",
+            &rpc,
+        ])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let log: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(log["blockNumber"], 1);
+    assert_eq!(log["event"], "Ping(uint256)");
+    assert_eq!(log["args"], serde_json::json!([{ "name": "value", "value": "42" }]));
+});
+
+// tests that `cast estimate --with-access-list` compares the gas with the access list applied
+casttest!(estimate_with_access_list, async |_prj, cmd| {
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+
+    cmd.args([
+        "estimate",
+        "0x0000000000000000000000000000000000001234",
+        "--value",
+        "1",
+        "--from",
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+        "--with-access-list",
+        "--rpc-url",
+        &rpc,
+    ])
+    .assert_success()
+    .stdout_eq(str![[r#"
+gas without access list: 21000
+gas with access list: 21000
+the access list does not change the gas
+access list: []
+
+"#]]);
+
+    // an access list that doesn't lower the gas isn't included
+    let hash = cmd
+        .cast_fuse()
+        .args([
+            "send",
+            "0x0000000000000000000000000000000000001234",
+            "--value",
+            "1",
+            "--auto-access-list",
+            "--async",
+            "--private-key",
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            "--rpc-url",
+            &rpc,
+        ])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let output = cmd
+        .cast_fuse()
+        .args(["tx", hash.trim(), "--json", "--rpc-url", &rpc])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let tx: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(tx["accessList"], serde_json::json!([]));
+    assert_eq!(tx["gas"], "0x5208");
+});
+
+// tests that `cast chain info` shows chains of the bundled registry
+casttest!(chain_info, async |_prj, cmd| {
+    cmd.args(["chain", "info", "mainnet"]).assert_success().stdout_eq(str![[r#"
+name: Ethereum Mainnet
+chain id: 1
+short name: eth
+currency: Ether (ETH, 18 decimals)
+EIP-1559: true
+EIP-4844: true
+rpc: https://eth.llamarpc.com
+rpc: https://ethereum-rpc.publicnode.com
+rpc: https://rpc.flashbots.net
+explorer: https://etherscan.io (etherscan)
+
+"#]]);
+
+    let output = cmd
+        .cast_fuse()
+        .args(["chain", "info", "8453", "--json"])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let info: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(info["name"], "Base");
+    assert_eq!(info["rpc"][0], "https://mainnet.base.org");
+
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    cmd.cast_fuse()
+        .args(["chain", "info", "--rpc-url", &handle.http_endpoint(), "--json"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+  "chainId": 31337,
+...
+"#]]);
+
+    cmd.cast_fuse().args(["chain", "info", "unknown"]).assert_failure().stderr_eq(str![[r#"
+Error: chain `unknown` not found in the registry
+
+"#]]);
+});
+
+// tests that `cast bundle simulate` signs the bundle and surfaces the errors of the relay
+casttest!(bundle_simulate_relay_error, async |_prj, cmd| {
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+
+    cmd.args([
+        "bundle",
+        "simulate",
+        "0x0000000000000000000000000000000000001234",
+        "--value",
+        "1",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &rpc,
+        // anvil doesn't support bundles
+        "--relay",
+        &rpc,
+    ])
+    .assert_failure()
+    .stderr_eq(str![[r#"
+Error: relay error: [..]
+
+"#]]);
+});
+
+// tests that `cast decode-tx` recovers the sender and authorities, and `cast build-tx` re-encodes
+// the decoded transaction
+casttest!(decode_and_build_tx, async |_prj, cmd| {
+    let (_, handle) = anvil::spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+    let sender = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    let raw = cmd
+        .args([
+            "mktx",
+            "0x0000000000000000000000000000000000001234",
+            "--auth",
+            "0x0000000000000000000000000000000000005678",
+            "--private-key",
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            "--rpc-url",
+            &rpc,
+        ])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let raw = raw.trim();
+
+    let json =
+        cmd.cast_fuse().args(["decode-tx", raw]).assert_success().get_output().stdout_lossy();
+    let tx: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(tx["txType"], "eip7702");
+    assert_eq!(tx["from"], sender);
+    assert_eq!(tx["authorizationList"][0]["authority"], sender);
+    assert_eq!(tx["authorizationList"][0]["address"], "0x0000000000000000000000000000000000005678");
+
+    cmd.cast_fuse().args(["build-tx", &json]).assert_success().stdout_eq(format!("{raw}\n"));
+
+    cmd.cast_fuse().args(["build-tx", "{}"]).assert_failure().stderr_eq(str![[r#"
+Error: invalid transaction, the type or some fields are missing
+...
+"#]]);
+});
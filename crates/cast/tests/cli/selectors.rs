@@ -209,3 +209,36 @@
 
 "#]]);
 });
+
+forgetest_init!(fourbyte_with_project_artifacts, |prj, cmd| {
+    prj.add_source(
+        "LocalProjectContract",
+        r#"
+contract ContractWithCustomError {
+    error AnotherValueTooHigh(uint256, address);
+    event MyUniqueEventWithinLocalProject(uint256 a, address b);
+}
+   "#,
+    )
+    .unwrap();
+    cmd.forge_fuse().arg("build").assert_success();
+
+    // Assert cast finds the selectors in the project artifacts without OpenChain.
+    cmd.cast_fuse().args(["4byte", "0x7191bc62", "--offline"]).assert_success().stdout_eq(str![[
+        r#"
+AnotherValueTooHigh(uint256,address)
+
+"#
+    ]]);
+    cmd.cast_fuse()
+        .args([
+            "4byte-event",
+            "0xbd3699995dcc867b64dbb607be2c33be38df9134bef1178df13bfb9446e73104",
+            "--offline",
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+MyUniqueEventWithinLocalProject(uint256,address)
+
+"#]]);
+});
@@ -116,6 +116,12 @@ pub enum Group {
     ///
     /// Safety: safe.
     Toml,
+    /// Utility cheatcodes that deal with parsing values from and writing values to CSV.
+    ///
+    /// Examples: `parseCsv`, `parseCsvUint`, `writeCsv`.
+    ///
+    /// Safety: safe.
+    Csv,
     /// Cryptography-related cheatcodes.
     ///
     /// Examples: `sign*`.
@@ -145,6 +151,7 @@ pub const fn safety(self) -> Option<Safety> {
             Self::String |
             Self::Json |
             Self::Toml |
+            Self::Csv |
             Self::Crypto |
             Self::Utilities => Some(Safety::Safe),
         }
@@ -162,6 +169,7 @@ pub const fn as_str(self) -> &'static str {
             Self::String => "string",
             Self::Json => "json",
             Self::Toml => "toml",
+            Self::Csv => "csv",
             Self::Crypto => "crypto",
             Self::Utilities => "utilities",
         }
@@ -231,6 +231,20 @@ struct AccessListItem {
         bytes32[] storageKeys;
     }
 
+    /// The result of a `simulate` call.
+    struct Simulation {
+        /// Whether the call succeeded.
+        bool success;
+        /// The gas used by the call.
+        uint64 gasUsed;
+        /// The data returned by the call, or its revert data.
+        bytes returnData;
+        /// The logs emitted by the call.
+        Log[] logs;
+        /// The EIP-2930 access list of the call, excluding the sender and the target.
+        AccessListItem[] accessList;
+    }
+
     /// The result of a `stopAndReturnStateDiff` call.
     struct AccountAccess {
         /// The chain and fork the access occurred.
@@ -560,6 +574,11 @@ struct PotentialRevert {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function noAccessList() external;
 
+    /// Executes a call from `from` to `to` without committing any state changes, returning the gas
+    /// used, the return data, the emitted logs and the EIP-2930 access list of the call.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function simulate(address from, address to, uint256 value, bytes calldata data) external returns (Simulation memory simulation);
+
     /// Utility cheatcode to mark specific storage slot as warm, simulating a prior read.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function warmSlot(address target, bytes32 slot) external;
@@ -633,6 +652,23 @@ struct PotentialRevert {
     function mockCallRevert(address callee, uint256 msgValue, bytes4 data, bytes calldata revertData)
         external;
 
+    /// Mocks calls to an address whose calldata matches `data` on the bits set in `mask`, returning specified data.
+    /// `mask` has the length of `data`, a zero byte matching any byte of the calldata, e.g. to mock a function
+    /// whatever the value of one of its arguments. Calldata longer than `data` matches as a partial match.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function mockCallMasked(address callee, bytes calldata data, bytes calldata mask, bytes calldata returnData)
+        external;
+
+    /// Reverts calls to an address whose calldata matches `data` on the bits set in `mask`, with specified revert data.
+    /// See `mockCallMasked` for the matching of the calldata.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function mockCallRevertMasked(address callee, bytes calldata data, bytes calldata mask, bytes calldata revertData)
+        external;
+
+    /// Returns the number of calls to `callee` answered by the mocks registered with calldata `data`.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function mockCallCount(address callee, bytes calldata data) external view returns (uint256 count);
+
     /// Whenever a call is made to `callee` with calldata `data`, this cheatcode instead calls
     /// `target` with the same calldata. This functionality is similar to a delegate call made to
     /// `target` contract from `callee`.
@@ -642,6 +678,13 @@ struct PotentialRevert {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function mockFunction(address callee, address target, bytes calldata data) external;
 
+    /// Whenever a call is made to `callee` with the function `selector`, whatever its arguments, this cheatcode
+    /// instead calls `target` with the same calldata, similar to a delegate call made to `target` from `callee`.
+    ///
+    /// Overload to pass the function selector directly `token.approve.selector` instead of `abi.encodeWithSelector(token.approve.selector)`.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function mockFunction(address callee, address target, bytes4 selector) external;
+
     // --- Impersonation (pranks) ---
 
     /// Sets the *next* call's `msg.sender` to be the input address.
@@ -727,6 +770,20 @@ struct PotentialRevert {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function stopSnapshotGas(string calldata group, string calldata name) external returns (uint256 gasUsed);
 
+    /// Marks the current gas usage with `label`, to get the gas used since with `gasUsedSince` or `snapshotGas`.
+    /// Setting a mark with an existing label restarts it.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function markGas(string calldata label) external;
+
+    /// Returns the gas used in the current call frame since `markGas(label)` was called.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function gasUsedSince(string calldata label) external view returns (uint256 gasUsed);
+
+    /// Snapshot capture the gas used since `markGas(name)` was called by name, removing the mark.
+    /// The group name is derived from the contract name.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function snapshotGas(string calldata name) external returns (uint256 gasUsed);
+
     // -------- State Snapshots --------
 
     /// `snapshot` is being deprecated in favor of `snapshotState`. It will be removed in future versions.
@@ -840,6 +897,19 @@ struct PotentialRevert {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function transact(uint256 forkId, bytes32 txHash) external;
 
+    /// Relays the logs with the given `topic0` recorded from `emitter` to the fork `forkId`, which is selected.
+    /// Each log is relayed by calling `target` with `selector` followed by the log data, so that the event's
+    /// non-indexed parameters are passed as the arguments of the function. Relayed logs are removed from the
+    /// recorded logs. Requires `recordLogs` to have been called.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function relayLogs(uint256 forkId, address emitter, bytes32 topic0, address target, bytes4 selector)
+        external
+        returns (uint256 relayed);
+
+    /// Sets the `block.timestamp` of all the other forks to the `block.timestamp` of the active fork.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function syncForkTimestamps() external;
+
     /// Performs an Ethereum JSON-RPC request to the current fork URL.
     #[cheatcode(group = Evm, safety = Safe)]
     function rpc(string calldata method, string calldata params) external returns (bytes memory data);
@@ -850,6 +920,17 @@ struct PotentialRevert {
         external
         returns (bytes memory data);
 
+    /// Performs a batch of Ethereum JSON-RPC requests to the current fork URL in a single round trip.
+    /// `params[i]` are the parameters of `methods[i]`, and `data[i]` is encoded as the result of `rpc`.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function rpcBatch(string[] calldata methods, string[] calldata params) external returns (bytes[] memory data);
+
+    /// Performs a batch of Ethereum JSON-RPC requests to the given endpoint in a single round trip.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function rpcBatch(string calldata urlOrAlias, string[] calldata methods, string[] calldata params)
+        external
+        returns (bytes[] memory data);
+
     /// Gets all the logs according to specified filter.
     #[cheatcode(group = Evm, safety = Safe)]
     function eth_getLogs(uint256 fromBlock, uint256 toBlock, address target, bytes32[] calldata topics)
@@ -897,6 +978,10 @@ struct PotentialRevert {
     #[cheatcode(group = Evm, safety = Safe)]
     function getRecordedLogs() external returns (Log[] memory logs);
 
+    /// Gets the recorded logs emitted by `emitter`, keeping the logs of the other emitters recorded.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function getRecordedLogsFrom(address emitter) external returns (Log[] memory logs);
+
     // -------- Gas Metering --------
 
     // It's recommend to use the `noGasMetering` modifier included with forge-std, instead of
@@ -1005,6 +1090,14 @@ struct PotentialRevert {
     function expectCallMinGas(address callee, uint256 msgValue, uint64 minGas, bytes calldata data, uint64 count)
         external;
 
+    /// Expects exactly `count` calls to `callee` with the function `selector`, whatever their arguments and `msg.value`.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectCallCount(address callee, bytes4 selector, uint64 count) external;
+
+    /// Expects the next call to use less than `gas` gas, reverting it otherwise.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectGasLt(uint256 gas) external;
+
     /// Prepare an expected log with (bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData.).
     /// Call this function, then emit an event, then call a function. Internally after the call, we check if
     /// logs were emitted in the expected order with the expected topics and data (as specified by the booleans).
@@ -1043,6 +1136,21 @@ struct PotentialRevert {
     #[cheatcode(group = Testing, safety = Unsafe)]
     function expectEmit(address emitter, uint64 count) external;
 
+    /// Prepare an expected log checking its data on the bits set in `dataMask`, a zero byte matching any byte of the
+    /// emitted data, e.g. to skip a timestamp or an amount computed by the contract. The data past the end of the mask,
+    /// and the length of the data, are checked as a whole. Topics are checked as in `expectEmit`.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectEmitMasked(bool checkTopic1, bool checkTopic2, bool checkTopic3, bytes calldata dataMask) external;
+
+    /// Same as the previous method, but also checks supplied address against emitting contract.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectEmitMasked(bool checkTopic1, bool checkTopic2, bool checkTopic3, bytes calldata dataMask, address emitter)
+        external;
+
+    /// Sets the number of times the log of the last `expectEmit` call, not emitted yet, is expected to be emitted.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectEmitCount(uint64 count) external;
+
     /// Prepare an expected anonymous log with (bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData.).
     /// Call this function, then emit an anonymous event, then call a function. Internally after the call, we check if
     /// logs were emitted in the expected order with the expected topics and data (as specified by the booleans).
@@ -1709,6 +1817,16 @@ struct PotentialRevert {
         string calldata error
     ) external pure;
 
+    /// Compares `value` with the golden file `name` in the `golden` subdirectory of the snapshots directory, creating it if it does not exist.
+    /// Run `forge test --update-snapshots` to overwrite golden files with the current values. Requires `fs_permissions` for the golden files.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function assertMatchesSnapshot(string calldata name, bytes calldata value) external;
+
+    /// Compares `value` with the golden file `name` in the `golden` subdirectory of the snapshots directory, creating it if it does not exist.
+    /// Run `forge test --update-snapshots` to overwrite golden files with the current values. Requires `fs_permissions` for the golden files.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function assertMatchesSnapshot(string calldata name, string calldata value) external;
+
     /// Returns true if the current Foundry version is greater than or equal to the given version.
     /// The given version string must be in the format `major.minor.patch`.
     ///
@@ -2587,6 +2705,56 @@ struct PotentialRevert {
     #[cheatcode(group = Toml)]
     function writeToml(string calldata json, string calldata path, string calldata valueKey) external;
 
+    // ======== CSV Parsing and Writing ========
+
+    // CSV data must start with a header row naming the columns. Fields may be quoted with `"`, escaping quotes by
+    // doubling them, and all the rows must have as many fields as the header.
+
+    /// Reads the CSV file at `path` and returns its records after the header row, as strings.
+    #[cheatcode(group = Csv)]
+    function readCsv(string calldata path) external view returns (string[][] memory rows);
+
+    /// Parses CSV data and returns its records after the header row, as strings.
+    #[cheatcode(group = Csv)]
+    function parseCsv(string calldata csv) external pure returns (string[][] memory rows);
+
+    /// Parses CSV data and returns the names of its columns, read from the header row.
+    #[cheatcode(group = Csv)]
+    function parseCsvHeader(string calldata csv) external pure returns (string[] memory columns);
+
+    /// Parses CSV data and coerces the values of `column` to `uint256[]`.
+    #[cheatcode(group = Csv)]
+    function parseCsvUint(string calldata csv, string calldata column) external pure returns (uint256[] memory);
+
+    /// Parses CSV data and coerces the values of `column` to `int256[]`.
+    #[cheatcode(group = Csv)]
+    function parseCsvInt(string calldata csv, string calldata column) external pure returns (int256[] memory);
+
+    /// Parses CSV data and coerces the values of `column` to `bool[]`.
+    #[cheatcode(group = Csv)]
+    function parseCsvBool(string calldata csv, string calldata column) external pure returns (bool[] memory);
+
+    /// Parses CSV data and coerces the values of `column` to `address[]`.
+    #[cheatcode(group = Csv)]
+    function parseCsvAddress(string calldata csv, string calldata column) external pure returns (address[] memory);
+
+    /// Parses CSV data and coerces the values of `column` to `string[]`.
+    #[cheatcode(group = Csv)]
+    function parseCsvString(string calldata csv, string calldata column) external pure returns (string[] memory);
+
+    /// Parses CSV data and coerces the values of `column` to `bytes[]`.
+    #[cheatcode(group = Csv)]
+    function parseCsvBytes(string calldata csv, string calldata column) external pure returns (bytes[] memory);
+
+    /// Parses CSV data and coerces the values of `column` to `bytes32[]`.
+    #[cheatcode(group = Csv)]
+    function parseCsvBytes32(string calldata csv, string calldata column) external pure returns (bytes32[] memory);
+
+    /// Writes the `header` and `rows` as CSV to the file at `path`, quoting the fields when needed.
+    /// If the file exists, it will be overwritten.
+    #[cheatcode(group = Csv)]
+    function writeCsv(string calldata path, string[] calldata header, string[][] calldata rows) external;
+
     // ======== Cryptography ========
 
     // -------- Key Management --------
@@ -2675,6 +2843,30 @@ struct PotentialRevert {
     #[cheatcode(group = Crypto)]
     function publicKeyP256(uint256 privateKey) external pure returns (uint256 publicKeyX, uint256 publicKeyY);
 
+    /// Signs `message` with `privateKey` using BLS12-381 with the ciphersuite of the Ethereum consensus layer,
+    /// returning the compressed G2 signature of 96 bytes.
+    #[cheatcode(group = Crypto)]
+    function signBls(uint256 privateKey, bytes calldata message) external pure returns (bytes memory signature);
+
+    /// Derives the BLS12-381 public key from the provided `privateKey`, returning the compressed G1 point of 48 bytes.
+    #[cheatcode(group = Crypto)]
+    function publicKeyBls(uint256 privateKey) external pure returns (bytes memory publicKey);
+
+    /// Aggregates compressed BLS12-381 signatures into a single compressed signature.
+    #[cheatcode(group = Crypto)]
+    function aggregateBlsSignatures(bytes[] calldata signatures) external pure returns (bytes memory signature);
+
+    /// Aggregates compressed BLS12-381 public keys into a single compressed public key.
+    #[cheatcode(group = Crypto)]
+    function aggregateBlsPublicKeys(bytes[] calldata publicKeys) external pure returns (bytes memory publicKey);
+
+    /// Verifies the compressed BLS12-381 `signature` of `message` by `publicKey`, as signed by `signBls`.
+    #[cheatcode(group = Crypto)]
+    function verifyBls(bytes calldata publicKey, bytes calldata message, bytes calldata signature)
+        external
+        pure
+        returns (bool);
+
     /// Derive a private key from a provided mnenomic string (or mnenomic file path)
     /// at the derivation path `m/44'/60'/0'/0/{index}`.
     #[cheatcode(group = Crypto)]
@@ -38,6 +38,13 @@ pub struct CheatsConfig {
     pub root: PathBuf,
     /// Absolute Path to broadcast dir i.e project_root/broadcast
     pub broadcast: PathBuf,
+    /// Absolute Path to deployments dir i.e project_root/deployments
+    pub deployments: PathBuf,
+    /// Absolute path to the golden files of `assertMatchesSnapshot`, i.e.
+    /// project_root/snapshots/golden
+    pub golden_snapshots: PathBuf,
+    /// Whether to overwrite golden files with the current values
+    pub update_snapshots: bool,
     /// Paths (directories) where file reading/writing is allowed
     pub allowed_paths: Vec<PathBuf>,
     /// How the evm was configured by the user
@@ -88,6 +95,9 @@ pub fn new(
             fs_permissions: config.fs_permissions.clone().joined(config.root.as_ref()),
             root: config.root.clone(),
             broadcast: config.root.clone().join(&config.broadcast),
+            deployments: config.root.join(&config.deployments),
+            golden_snapshots: config.root.join(&config.snapshots).join("golden"),
+            update_snapshots: config.update_snapshots,
             allowed_paths,
             evm_opts,
             labels: config.labels.clone(),
@@ -218,6 +228,9 @@ fn default() -> Self {
             fs_permissions: Default::default(),
             root: Default::default(),
             broadcast: Default::default(),
+            deployments: Default::default(),
+            golden_snapshots: Default::default(),
+            update_snapshots: false,
             allowed_paths: vec![],
             evm_opts: Default::default(),
             labels: Default::default(),
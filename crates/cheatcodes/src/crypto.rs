@@ -1,7 +1,7 @@
 //! Implementations of [`Crypto`](spec::Group::Crypto) Cheatcodes.
 
 use crate::{Cheatcode, Cheatcodes, Result, Vm::*};
-use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_primitives::{hex, keccak256, Address, B256, U256};
 use alloy_signer::{Signer, SignerSync};
 use alloy_signer_local::{
     coins_bip39::{
@@ -11,6 +11,13 @@
     LocalSigner, MnemonicBuilder, PrivateKeySigner,
 };
 use alloy_sol_types::SolValue;
+use blst::{
+    min_pk::{
+        AggregatePublicKey, AggregateSignature, PublicKey as BlsPublicKey,
+        SecretKey as BlsSecretKey, Signature as BlsSignature,
+    },
+    BLST_ERROR,
+};
 use k256::{
     ecdsa::SigningKey,
     elliptic_curve::{bigint::ArrayEncoding, sec1::ToEncodedPoint},
@@ -22,6 +29,9 @@
 /// The BIP32 default derivation path prefix.
 const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
 
+/// The domain separation tag of the BLS signatures of the Ethereum consensus layer.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
 impl Cheatcode for createWallet_0Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { walletLabel } = self;
@@ -197,6 +207,59 @@ fn apply(&self, _state: &mut Cheatcodes) -> Result {
     }
 }
 
+impl Cheatcode for signBlsCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { privateKey, message } = self;
+        let signature = parse_private_key_bls(privateKey)?.sign(message, BLS_DST, &[]);
+        Ok(signature.compress().abi_encode())
+    }
+}
+
+impl Cheatcode for publicKeyBlsCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { privateKey } = self;
+        Ok(parse_private_key_bls(privateKey)?.sk_to_pk().compress().abi_encode())
+    }
+}
+
+impl Cheatcode for aggregateBlsSignaturesCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { signatures } = self;
+        let signatures = signatures
+            .iter()
+            .map(|signature| parse_bls_signature(signature))
+            .collect::<Result<Vec<_>>>()?;
+        let signatures = signatures.iter().collect::<Vec<_>>();
+        let aggregate = AggregateSignature::aggregate(&signatures, false)
+            .map_err(|e| fmt_err!("failed to aggregate BLS signatures: {e:?}"))?;
+        Ok(aggregate.to_signature().compress().abi_encode())
+    }
+}
+
+impl Cheatcode for aggregateBlsPublicKeysCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { publicKeys } = self;
+        let public_keys = publicKeys
+            .iter()
+            .map(|public_key| parse_bls_public_key(public_key))
+            .collect::<Result<Vec<_>>>()?;
+        let public_keys = public_keys.iter().collect::<Vec<_>>();
+        let aggregate = AggregatePublicKey::aggregate(&public_keys, false)
+            .map_err(|e| fmt_err!("failed to aggregate BLS public keys: {e:?}"))?;
+        Ok(aggregate.to_public_key().compress().abi_encode())
+    }
+}
+
+impl Cheatcode for verifyBlsCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { publicKey, message, signature } = self;
+        let public_key = parse_bls_public_key(publicKey)?;
+        let signature = parse_bls_signature(signature)?;
+        let result = signature.verify(true, message, BLS_DST, &[], &public_key, true);
+        Ok((result == BLST_ERROR::BLST_SUCCESS).abi_encode())
+    }
+}
+
 /// Using a given private key, return its public ETH address, its public key affine x and y
 /// coordinates, and its private key (see the 'Wallet' struct)
 ///
@@ -305,6 +368,22 @@ fn parse_private_key_p256(private_key: &U256) -> Result<P256SigningKey> {
     Ok(P256SigningKey::from_bytes((&private_key.to_be_bytes()).into())?)
 }
 
+fn parse_private_key_bls(private_key: &U256) -> Result<BlsSecretKey> {
+    ensure!(*private_key != U256::ZERO, "private key cannot be 0");
+    BlsSecretKey::from_bytes(&private_key.to_be_bytes::<32>())
+        .map_err(|_| fmt_err!("private key must be less than the BLS12-381 curve order"))
+}
+
+fn parse_bls_public_key(public_key: &[u8]) -> Result<BlsPublicKey> {
+    BlsPublicKey::key_validate(public_key)
+        .map_err(|e| fmt_err!("invalid BLS public key {}: {e:?}", hex::encode_prefixed(public_key)))
+}
+
+fn parse_bls_signature(signature: &[u8]) -> Result<BlsSignature> {
+    BlsSignature::sig_validate(signature, true)
+        .map_err(|e| fmt_err!("invalid BLS signature {}: {e:?}", hex::encode_prefixed(signature)))
+}
+
 pub(super) fn parse_wallet(private_key: &U256) -> Result<PrivateKeySigner> {
     parse_private_key(private_key).map(PrivateKeySigner::from)
 }
@@ -0,0 +1,204 @@
+//! Implementations of [`Csv`](spec::Group::Csv) cheatcodes.
+
+use crate::{string, Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_sol_types::SolValue;
+use foundry_common::fs;
+use foundry_config::fs_permissions::FsAccessKind;
+
+impl Cheatcode for readCsvCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { path } = self;
+        let path = state.config.ensure_path_allowed(path, FsAccessKind::Read)?;
+        let (_, rows) = parse_csv(&fs::read_to_string(path)?)?;
+        Ok(rows.abi_encode())
+    }
+}
+
+impl Cheatcode for parseCsvCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv } = self;
+        let (_, rows) = parse_csv(csv)?;
+        Ok(rows.abi_encode())
+    }
+}
+
+impl Cheatcode for parseCsvHeaderCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv } = self;
+        let (header, _) = parse_csv(csv)?;
+        Ok(header.abi_encode())
+    }
+}
+
+impl Cheatcode for parseCsvUintCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv, column } = self;
+        parse_csv_column(csv, column, &DynSolType::Uint(256))
+    }
+}
+
+impl Cheatcode for parseCsvIntCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv, column } = self;
+        parse_csv_column(csv, column, &DynSolType::Int(256))
+    }
+}
+
+impl Cheatcode for parseCsvBoolCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv, column } = self;
+        parse_csv_column(csv, column, &DynSolType::Bool)
+    }
+}
+
+impl Cheatcode for parseCsvAddressCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv, column } = self;
+        parse_csv_column(csv, column, &DynSolType::Address)
+    }
+}
+
+impl Cheatcode for parseCsvStringCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv, column } = self;
+        parse_csv_column(csv, column, &DynSolType::String)
+    }
+}
+
+impl Cheatcode for parseCsvBytesCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv, column } = self;
+        parse_csv_column(csv, column, &DynSolType::Bytes)
+    }
+}
+
+impl Cheatcode for parseCsvBytes32Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { csv, column } = self;
+        parse_csv_column(csv, column, &DynSolType::FixedBytes(32))
+    }
+}
+
+impl Cheatcode for writeCsvCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { path, header, rows } = self;
+        let csv = format_csv(header, rows)?;
+        super::fs::write_file(state, path.as_ref(), csv.as_bytes())
+    }
+}
+
+/// Parses the values of `column` as `ty`, returning the ABI-encoded array.
+fn parse_csv_column(csv: &str, column: &str, ty: &DynSolType) -> Result {
+    let (header, rows) = parse_csv(csv)?;
+    let Some(index) = header.iter().position(|name| name == column) else {
+        bail!("column {column:?} not found in CSV header");
+    };
+    let values =
+        rows.iter().map(|row| string::parse_value(&row[index], ty)).collect::<Result<Vec<_>>>()?;
+    Ok(DynSolValue::Array(values).abi_encode())
+}
+
+/// Parses CSV data as defined by RFC 4180, returning the header and the following records.
+///
+/// Fields may be quoted with `"`, escaping quotes by doubling them. Empty lines are skipped and
+/// all the records must have as many fields as the header.
+fn parse_csv(csv: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                if !record.is_empty() || !field.is_empty() {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+    ensure!(!quoted, "unterminated quoted field in CSV");
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    let mut records = records.into_iter();
+    let Some(header) = records.next() else { bail!("CSV data must have a header") };
+    let rows = records.collect::<Vec<_>>();
+    for (i, row) in rows.iter().enumerate() {
+        ensure!(
+            row.len() == header.len(),
+            "CSV row {} has {} fields, expected {}",
+            i + 1,
+            row.len(),
+            header.len()
+        );
+    }
+    Ok((header, rows))
+}
+
+/// Formats the header and rows as CSV data, quoting the fields when needed.
+fn format_csv(header: &[String], rows: &[Vec<String>]) -> Result<String> {
+    let mut csv = String::new();
+    for (i, record) in std::iter::once(header).chain(rows.iter().map(Vec::as_slice)).enumerate() {
+        ensure!(
+            record.len() == header.len(),
+            "CSV row {i} has {} fields, expected {}",
+            record.len(),
+            header.len()
+        );
+        let fields = record
+            .iter()
+            .map(|field| {
+                if field.contains([',', '"', '\r', '\n']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csv() {
+        let csv = "name,amount\r\nalice,1\n\n\"bob, \"\"jr\"\"\",2\n";
+        let (header, rows) = parse_csv(csv).unwrap();
+        assert_eq!(header, ["name", "amount"]);
+        assert_eq!(rows, [vec!["alice", "1"], vec!["bob, \"jr\"", "2"]]);
+
+        assert!(parse_csv("a,b\n1\n").is_err());
+        assert!(parse_csv("a\n\"1\n").is_err());
+    }
+
+    #[test]
+    fn formats_csv() {
+        let header = vec!["name".to_string(), "amount".to_string()];
+        let rows = vec![vec!["bob, \"jr\"".to_string(), "2".to_string()]];
+        let csv = format_csv(&header, &rows).unwrap();
+        assert_eq!(csv, "name,amount\n\"bob, \"\"jr\"\"\",2\n");
+        assert_eq!(parse_csv(&csv).unwrap(), (header, rows));
+    }
+}
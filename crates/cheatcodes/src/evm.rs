@@ -18,7 +18,11 @@
 use foundry_evm_traces::StackSnapshotType;
 use itertools::Itertools;
 use rand::Rng;
-use revm::primitives::{Account, Bytecode, SpecId, KECCAK_EMPTY};
+use revm::{
+    interpreter::{CallInputs, CallScheme, CallValue},
+    primitives::{Account, Bytecode, SpecId, KECCAK_EMPTY},
+    JournalEntry,
+};
 use std::{
     collections::{btree_map::Entry, BTreeMap},
     fmt::Display,
@@ -102,6 +106,28 @@ struct BalanceDiff {
     new_value: U256,
 }
 
+/// Nonce diff info.
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct NonceDiff {
+    /// Initial nonce.
+    previous_value: u64,
+    /// Current nonce.
+    new_value: u64,
+}
+
+/// Code diff info.
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct CodeDiff {
+    /// Initial code hash.
+    previous_code_hash: B256,
+    /// Current code hash.
+    new_code_hash: B256,
+    /// Current runtime bytecode.
+    new_code: Bytes,
+}
+
 /// Account state diff info.
 #[derive(Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -110,6 +136,12 @@ struct AccountStateDiffs {
     label: Option<String>,
     /// Account balance changes.
     balance_diff: Option<BalanceDiff>,
+    /// Account nonce changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce_diff: Option<NonceDiff>,
+    /// Account code changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code_diff: Option<CodeDiff>,
     /// State changes, per slot.
     state_diff: BTreeMap<B256, SlotStateDiff>,
 }
@@ -130,6 +162,18 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> eyre::Result<(), std::fmt::Err
                 )?;
             }
         }
+        // Print nonce diff if changed.
+        if let Some(nonce_diff) = &self.nonce_diff {
+            writeln!(f, "- nonce diff: {} → {}", nonce_diff.previous_value, nonce_diff.new_value)?;
+        }
+        // Print code diff if changed.
+        if let Some(code_diff) = &self.code_diff {
+            writeln!(
+                f,
+                "- code diff: {} → {}",
+                code_diff.previous_code_hash, code_diff.new_code_hash
+            )?;
+        }
         // Print state diff if any.
         if !&self.state_diff.is_empty() {
             writeln!(f, "- state diff:")?;
@@ -317,6 +361,19 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
     }
 }
 
+impl Cheatcode for getRecordedLogsFromCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { emitter } = *self;
+        let logs = state.recorded_logs.as_mut().map(std::mem::take).unwrap_or_default();
+        let (from_emitter, others): (Vec<_>, Vec<_>) =
+            logs.into_iter().partition(|log| log.emitter == emitter);
+        if let Some(recorded_logs) = &mut state.recorded_logs {
+            *recorded_logs = others;
+        }
+        Ok(from_emitter.abi_encode())
+    }
+}
+
 impl Cheatcode for pauseGasMeteringCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self {} = self;
@@ -516,6 +573,7 @@ impl Cheatcode for etchCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let Self { target, newRuntimeBytecode } = self;
         ensure_not_precompile!(target, ccx);
+        ccx.state.record_account_info(ccx.ecx, *target);
         ccx.ecx.load_account(*target)?;
         let bytecode = Bytecode::new_raw_checked(Bytes::copy_from_slice(newRuntimeBytecode))
             .map_err(|e| fmt_err!("failed to create bytecode: {e}"))?;
@@ -527,6 +585,7 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
 impl Cheatcode for resetNonceCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let Self { account } = self;
+        ccx.state.record_account_info(ccx.ecx, *account);
         let account = journaled_account(ccx.ecx, *account)?;
         // Per EIP-161, EOA nonces start at 0, but contract nonces
         // start at 1. Comparing by code_hash instead of code
@@ -542,6 +601,7 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
 impl Cheatcode for setNonceCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let Self { account, newNonce } = *self;
+        ccx.state.record_account_info(ccx.ecx, account);
         let account = journaled_account(ccx.ecx, account)?;
         // nonce must increment only
         let current = account.info.nonce;
@@ -558,6 +618,7 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
 impl Cheatcode for setNonceUnsafeCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let Self { account, newNonce } = *self;
+        ccx.state.record_account_info(ccx.ecx, account);
         let account = journaled_account(ccx.ecx, account)?;
         account.info.nonce = newNonce;
         Ok(Default::default())
@@ -612,6 +673,80 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
     }
 }
 
+impl Cheatcode for simulateCall {
+    fn apply_full(&self, ccx: &mut CheatsCtxt, executor: &mut dyn CheatcodesExecutor) -> Result {
+        let Self { from, to, value, data } = self;
+
+        // Mark all accounts and slots cold, as in a new transaction, so that every access made by
+        // the call is journaled and ends up in the access list.
+        let state = ccx.ecx.journaled_state.state.clone();
+        for (address, account) in &mut ccx.ecx.journaled_state.state {
+            if !ccx.ecx.journaled_state.warm_preloaded_addresses.contains(address) {
+                account.mark_cold();
+            }
+            for slot in account.storage.values_mut() {
+                slot.is_cold = true;
+            }
+        }
+
+        let checkpoint = ccx.ecx.journaled_state.checkpoint();
+        let inputs = CallInputs {
+            input: data.clone(),
+            return_memory_offset: 0..0,
+            gas_limit: ccx.gas_limit,
+            bytecode_address: *to,
+            target_address: *to,
+            caller: *from,
+            value: CallValue::Transfer(*value),
+            scheme: CallScheme::Call,
+            is_static: false,
+            is_eof: false,
+        };
+        let outcome = executor.exec_call(inputs, ccx);
+
+        let journaled_state = &mut ccx.ecx.journaled_state;
+        let logs = journaled_state.logs[checkpoint.log_i..]
+            .iter()
+            .map(|log| Log {
+                topics: log.data.topics().to_vec(),
+                data: log.data.data.clone(),
+                emitter: log.address,
+            })
+            .collect();
+        let mut access_list: Vec<AccessListItem> = Vec::new();
+        for entry in journaled_state.journal[checkpoint.journal_i..].iter().flatten() {
+            let (address, key) = match entry {
+                JournalEntry::AccountWarmed { address } => (*address, None),
+                JournalEntry::StorageWarmed { address, key } => (*address, Some(*key)),
+                _ => continue,
+            };
+            if address == *from || address == *to || address == CHEATCODE_ADDRESS {
+                continue;
+            }
+            let index =
+                access_list.iter().position(|item| item.target == address).unwrap_or_else(|| {
+                    access_list.push(AccessListItem { target: address, storageKeys: vec![] });
+                    access_list.len() - 1
+                });
+            if let Some(key) = key {
+                access_list[index].storageKeys.push(key.into());
+            }
+        }
+        journaled_state.checkpoint_revert(checkpoint);
+        journaled_state.state = state;
+
+        let outcome = outcome?;
+        Ok(Simulation {
+            success: outcome.result.is_ok(),
+            gasUsed: outcome.result.gas.spent(),
+            returnData: outcome.result.output,
+            logs,
+            accessList: access_list,
+        }
+        .abi_encode())
+    }
+}
+
 impl Cheatcode for warmSlotCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let Self { target, slot } = *self;
@@ -709,6 +844,55 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
     }
 }
 
+impl Cheatcode for markGasCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { label } = self;
+        let (group, name) = derive_snapshot_name(ccx, None, Some(label.clone()));
+        let gas_metering = &mut ccx.state.gas_metering;
+        // Skip the first opcode after the mark if no gas is being recorded, as it includes the
+        // gas cost of this call.
+        if !gas_metering.recording && gas_metering.gas_marks.is_empty() {
+            gas_metering.last_gas_used = 0;
+        }
+        gas_metering.gas_marks.retain(|record| record.name != name);
+        gas_metering.gas_marks.push(GasRecord {
+            group,
+            name,
+            gas_used: 0,
+            depth: ccx.ecx.journaled_state.depth(),
+        });
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for gasUsedSinceCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { label } = self;
+        let Some(record) = state.gas_metering.gas_marks.iter().find(|record| &record.name == label)
+        else {
+            bail!("no gas mark was set with the label: {label}");
+        };
+        Ok(record.gas_used.abi_encode())
+    }
+}
+
+impl Cheatcode for snapshotGasCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { name } = self;
+        let gas_marks = &mut ccx.state.gas_metering.gas_marks;
+        let Some(index) = gas_marks.iter().position(|record| &record.name == name) else {
+            bail!("no gas mark was set with the label: {name}");
+        };
+        let record = gas_marks.remove(index);
+        ccx.state
+            .gas_snapshots
+            .entry(record.group)
+            .or_default()
+            .insert(record.name, record.gas_used.to_string());
+        Ok(record.gas_used.abi_encode())
+    }
+}
+
 // Deprecated in favor of `snapshotStateCall`
 impl Cheatcode for snapshotCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
@@ -788,6 +972,7 @@ impl Cheatcode for startStateDiffRecordingCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self {} = self;
         state.recorded_account_diffs_stack = Some(Default::default());
+        state.recorded_account_infos.clear();
         Ok(Default::default())
     }
 }
@@ -800,9 +985,9 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
 }
 
 impl Cheatcode for getStateDiffCall {
-    fn apply(&self, state: &mut Cheatcodes) -> Result {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let mut diffs = String::new();
-        let state_diffs = get_recorded_state_diffs(state);
+        let state_diffs = get_recorded_state_diffs(ccx);
         for (address, state_diffs) in state_diffs {
             diffs.push_str(&format!("{address}\n"));
             diffs.push_str(&format!("{state_diffs}\n"));
@@ -812,8 +997,8 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
 }
 
 impl Cheatcode for getStateDiffJsonCall {
-    fn apply(&self, state: &mut Cheatcodes) -> Result {
-        let state_diffs = get_recorded_state_diffs(state);
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let state_diffs = get_recorded_state_diffs(ccx);
         Ok(serde_json::to_string(&state_diffs)?.abi_encode())
     }
 }
@@ -1180,7 +1365,8 @@ fn genesis_account(account: &Account) -> GenesisAccount {
 }
 
 /// Helper function to returns state diffs recorded for each changed account.
-fn get_recorded_state_diffs(state: &mut Cheatcodes) -> BTreeMap<Address, AccountStateDiffs> {
+fn get_recorded_state_diffs(ccx: &mut CheatsCtxt) -> BTreeMap<Address, AccountStateDiffs> {
+    let state = &*ccx.state;
     let mut state_diffs: BTreeMap<Address, AccountStateDiffs> = BTreeMap::default();
     if let Some(records) = &state.recorded_account_diffs_stack {
         records
@@ -1236,6 +1422,30 @@ fn get_recorded_state_diffs(state: &mut Cheatcodes) -> BTreeMap<Address, Account
                 }
             });
     }
+
+    // Record account nonce and code diffs.
+    for (address, (previous_nonce, previous_code_hash)) in &state.recorded_account_infos {
+        let Some(account) = ccx.ecx.journaled_state.state.get(address) else { continue };
+        let info = &account.info;
+        if info.nonce == *previous_nonce && info.code_hash == *previous_code_hash {
+            continue;
+        }
+        let account_diff = state_diffs.entry(*address).or_insert_with(|| AccountStateDiffs {
+            label: state.labels.get(address).cloned(),
+            ..Default::default()
+        });
+        if info.nonce != *previous_nonce {
+            account_diff.nonce_diff =
+                Some(NonceDiff { previous_value: *previous_nonce, new_value: info.nonce });
+        }
+        if info.code_hash != *previous_code_hash {
+            account_diff.code_diff = Some(CodeDiff {
+                previous_code_hash: *previous_code_hash,
+                new_code_hash: info.code_hash,
+                new_code: info.code.as_ref().map(|code| code.original_bytes()).unwrap_or_default(),
+            });
+        }
+    }
     state_diffs
 }
 
@@ -3,12 +3,14 @@
     Result, Vm::*,
 };
 use alloy_dyn_abi::DynSolValue;
-use alloy_primitives::{B256, U256};
+use alloy_primitives::{Bytes, B256, U256};
 use alloy_provider::Provider;
+use alloy_rpc_client::BatchRequest;
 use alloy_rpc_types::Filter;
 use alloy_sol_types::SolValue;
 use foundry_common::provider::ProviderBuilder;
-use foundry_evm_core::fork::CreateFork;
+use foundry_evm_core::{decode::RevertDecoder, fork::CreateFork};
+use revm::interpreter::{CallInputs, CallScheme, CallValue};
 
 impl Cheatcode for activeForkCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
@@ -130,6 +132,56 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
     }
 }
 
+impl Cheatcode for relayLogsCall {
+    fn apply_full(&self, ccx: &mut CheatsCtxt, executor: &mut dyn CheatcodesExecutor) -> Result {
+        let Self { forkId, emitter, topic0, target, selector } = *self;
+        persist_caller(ccx);
+        check_broadcast(ccx.state)?;
+
+        ensure!(
+            ccx.state.recorded_logs.is_some(),
+            "logs are not being recorded; call `recordLogs` before emitting the logs to relay"
+        );
+        ccx.ecx.db.select_fork(forkId, &mut ccx.ecx.env, &mut ccx.ecx.journaled_state)?;
+
+        let recorded_logs = ccx.state.recorded_logs.as_mut().unwrap();
+        let (logs, others): (Vec<_>, Vec<_>) = std::mem::take(recorded_logs)
+            .into_iter()
+            .partition(|log| log.emitter == emitter && log.topics.first() == Some(&topic0));
+        *recorded_logs = others;
+
+        for log in &logs {
+            let inputs = CallInputs {
+                input: [selector.as_slice(), &log.data].concat().into(),
+                return_memory_offset: 0..0,
+                gas_limit: ccx.gas_limit,
+                bytecode_address: target,
+                target_address: target,
+                caller: ccx.caller,
+                value: CallValue::Transfer(U256::ZERO),
+                scheme: CallScheme::Call,
+                is_static: false,
+                is_eof: false,
+            };
+            let outcome = executor.exec_call(inputs, ccx)?;
+            if !outcome.result.is_ok() {
+                let reason = RevertDecoder::new()
+                    .decode(&outcome.result.output, Some(outcome.result.result));
+                bail!("relayed call to {target} reverted: {reason}");
+            }
+        }
+        Ok(U256::from(logs.len()).abi_encode())
+    }
+}
+
+impl Cheatcode for syncForkTimestampsCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self {} = self;
+        ccx.ecx.db.sync_fork_timestamps(ccx.ecx.env.block.timestamp)?;
+        Ok(Default::default())
+    }
+}
+
 impl Cheatcode for transact_0Call {
     fn apply_full(&self, ccx: &mut CheatsCtxt, executor: &mut dyn CheatcodesExecutor) -> Result {
         let Self { txHash } = *self;
@@ -231,6 +283,23 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
     }
 }
 
+impl Cheatcode for rpcBatch_0Call {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { methods, params } = self;
+        let url =
+            ccx.ecx.db.active_fork_url().ok_or_else(|| fmt_err!("no active fork URL found"))?;
+        rpc_batch(&url, methods, params)
+    }
+}
+
+impl Cheatcode for rpcBatch_1Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { urlOrAlias, methods, params } = self;
+        let url = state.config.rpc_endpoint(urlOrAlias)?.url()?;
+        rpc_batch(&url, methods, params)
+    }
+}
+
 impl Cheatcode for eth_getLogsCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let Self { fromBlock, toBlock, target, topics } = self;
@@ -391,6 +460,44 @@ fn rpc_call(url: &str, method: &str, params: &str) -> Result {
     Ok(result_as_tokens.abi_encode())
 }
 
+/// Sends the requests in a single JSON-RPC batch, returning the results in the order of the
+/// requests, each encoded as by `rpc`.
+fn rpc_batch(url: &str, methods: &[String], params: &[String]) -> Result {
+    ensure!(
+        methods.len() == params.len(),
+        "methods and params must have the same length, got {} and {}",
+        methods.len(),
+        params.len()
+    );
+    let provider = ProviderBuilder::new(url).build()?;
+    let params = params
+        .iter()
+        .map(|params| serde_json::from_str::<serde_json::Value>(params))
+        .collect::<Result<Vec<_>, _>>()?;
+    let results = foundry_common::block_on(async {
+        let mut batch = BatchRequest::new(provider.client());
+        let mut waiters = Vec::with_capacity(methods.len());
+        for (method, params) in methods.iter().zip(&params) {
+            let waiter = batch
+                .add_call::<_, serde_json::Value>(method.clone(), params)
+                .map_err(|err| fmt_err!("{method:?}: {err}"))?;
+            waiters.push((method, waiter));
+        }
+        batch.send().await.map_err(|err| fmt_err!("failed to send batch: {err}"))?;
+
+        let mut results = Vec::with_capacity(waiters.len());
+        for (method, waiter) in waiters {
+            let result = waiter.await.map_err(|err| fmt_err!("{method:?}: {err}"))?;
+            let token = json_value_to_token(&result)
+                .map_err(|err| fmt_err!("failed to parse result of {method:?}: {err}"))?;
+            results.push(Bytes::from(convert_to_bytes(&token).abi_encode()));
+        }
+        Ok::<_, crate::Error>(results)
+    })?;
+
+    Ok(results.abi_encode())
+}
+
 /// Convert fixed bytes and address values to bytes in order to prevent encoding issues.
 fn convert_to_bytes(token: &DynSolValue) -> DynSolValue {
     match token {
@@ -1,5 +1,6 @@
 use crate::{inspector::InnerEcx, Cheatcode, Cheatcodes, CheatsCtxt, Result, Vm::*};
 use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::SolValue;
 use revm::{interpreter::InstructionResult, primitives::Bytecode};
 use std::{cmp::Ordering, collections::VecDeque};
 
@@ -10,6 +11,24 @@ pub struct MockCallDataContext {
     pub calldata: Bytes,
     /// The value to match for mock
     pub value: Option<U256>,
+    /// The bits of the calldata to match, all of them if `None`
+    pub mask: Option<Bytes>,
+}
+
+impl MockCallDataContext {
+    /// Returns whether the mock calldata is a prefix of `input`, ignoring the bits cleared in the
+    /// mask.
+    pub fn matches_calldata(&self, input: &[u8]) -> bool {
+        let Some(input) = input.get(..self.calldata.len()) else { return false };
+        match &self.mask {
+            Some(mask) => input
+                .iter()
+                .zip(self.calldata.iter())
+                .zip(mask.iter())
+                .all(|((input, data), mask)| input & mask == data & mask),
+            None => input == &self.calldata[..],
+        }
+    }
 }
 
 /// Mocked return data.
@@ -34,7 +53,11 @@ fn cmp(&self, other: &Self) -> Ordering {
         // a partial match to calldata that is more specific than
         // a match to a msg.value, then the more specific calldata takes
         // precedence.
-        self.calldata.cmp(&other.calldata).reverse().then(self.value.cmp(&other.value).reverse())
+        self.calldata
+            .cmp(&other.calldata)
+            .reverse()
+            .then(self.value.cmp(&other.value).reverse())
+            .then(self.mask.cmp(&other.mask))
     }
 }
 
@@ -42,6 +65,7 @@ impl Cheatcode for clearMockedCallsCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self {} = self;
         state.mocked_calls = Default::default();
+        state.mocked_call_counts = Default::default();
         Ok(Default::default())
     }
 }
@@ -171,7 +195,35 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
     }
 }
 
-impl Cheatcode for mockFunctionCall {
+impl Cheatcode for mockCallMaskedCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { callee, data, mask, returnData } = self;
+        let _ = make_acc_non_empty(callee, ccx.ecx)?;
+
+        mock_call_masked(ccx.state, callee, data, mask, returnData, InstructionResult::Return)
+    }
+}
+
+impl Cheatcode for mockCallRevertMaskedCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { callee, data, mask, revertData } = self;
+        let _ = make_acc_non_empty(callee, ccx.ecx)?;
+
+        mock_call_masked(ccx.state, callee, data, mask, revertData, InstructionResult::Revert)
+    }
+}
+
+impl Cheatcode for mockCallCountCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { callee, data } = self;
+        let count = state.mocked_call_counts.get(callee).map_or(0, |counts| {
+            counts.iter().filter(|(mock, _)| mock.calldata == *data).map(|(_, count)| count).sum()
+        });
+        Ok(U256::from(count).abi_encode())
+    }
+}
+
+impl Cheatcode for mockFunction_0Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { callee, target, data } = self;
         state.mocked_functions.entry(*callee).or_default().insert(data.clone(), *target);
@@ -180,6 +232,15 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
     }
 }
 
+impl Cheatcode for mockFunction_1Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { callee, target, selector } = self;
+        state.mocked_functions.entry(*callee).or_default().insert(Bytes::from(*selector), *target);
+
+        Ok(Default::default())
+    }
+}
+
 fn mock_call(
     state: &mut Cheatcodes,
     callee: &Address,
@@ -191,6 +252,27 @@ fn mock_call(
     mock_calls(state, callee, cdata, value, std::slice::from_ref(rdata), ret_type)
 }
 
+fn mock_call_masked(
+    state: &mut Cheatcodes,
+    callee: &Address,
+    cdata: &Bytes,
+    mask: &Bytes,
+    rdata: &Bytes,
+    ret_type: InstructionResult,
+) -> Result {
+    ensure!(
+        mask.len() == cdata.len(),
+        "mask length ({}) must match calldata length ({})",
+        mask.len(),
+        cdata.len()
+    );
+    state.mocked_calls.entry(*callee).or_default().insert(
+        MockCallDataContext { calldata: cdata.clone(), value: None, mask: Some(mask.clone()) },
+        VecDeque::from([MockCallReturnData { ret_type, data: rdata.clone() }]),
+    );
+    Ok(Default::default())
+}
+
 fn mock_calls(
     state: &mut Cheatcodes,
     callee: &Address,
@@ -200,7 +282,11 @@ fn mock_calls(
     ret_type: InstructionResult,
 ) {
     state.mocked_calls.entry(*callee).or_default().insert(
-        MockCallDataContext { calldata: Bytes::copy_from_slice(cdata), value: value.copied() },
+        MockCallDataContext {
+            calldata: Bytes::copy_from_slice(cdata),
+            value: value.copied(),
+            mask: None,
+        },
         rdata_vec
             .iter()
             .map(|rdata| MockCallReturnData { ret_type, data: rdata.clone() })
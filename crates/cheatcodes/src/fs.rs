@@ -5,11 +5,11 @@
 use alloy_dyn_abi::DynSolType;
 use alloy_json_abi::ContractObject;
 use alloy_network::AnyTransactionReceipt;
-use alloy_primitives::{hex, map::Entry, Bytes, U256};
+use alloy_primitives::{hex, map::Entry, Address, Bytes, U256};
 use alloy_provider::network::ReceiptResponse;
 use alloy_sol_types::SolValue;
 use dialoguer::{Input, Password};
-use forge_script_sequence::{BroadcastReader, TransactionWithMetadata};
+use forge_script_sequence::{BroadcastReader, Deployment, TransactionWithMetadata};
 use foundry_common::fs;
 use foundry_config::fs_permissions::FsAccessKind;
 use revm::interpreter::CreateInputs;
@@ -752,14 +752,7 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let Self { contractName } = self;
         let chain_id = ccx.ecx.env.cfg.chain_id;
 
-        let latest_broadcast = latest_broadcast(
-            contractName,
-            chain_id,
-            &ccx.state.config.broadcast,
-            vec![CallKind::Create, CallKind::Create2],
-        )?;
-
-        Ok(latest_broadcast.contractAddress.abi_encode())
+        latest_deployment(ccx.state, contractName, chain_id).map(|address| address.abi_encode())
     }
 }
 
@@ -767,14 +760,7 @@ impl Cheatcode for getDeployment_1Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { contractName, chainId } = self;
 
-        let latest_broadcast = latest_broadcast(
-            contractName,
-            *chainId,
-            &state.config.broadcast,
-            vec![CallKind::Create, CallKind::Create2],
-        )?;
-
-        Ok(latest_broadcast.contractAddress.abi_encode())
+        latest_deployment(state, contractName, *chainId).map(|address| address.abi_encode())
     }
 }
 
@@ -832,6 +818,23 @@ fn parse_broadcast_results(
         .collect()
 }
 
+/// Returns the address of the latest deployment of the contract, read from its deployment manifest
+/// or, if there is none, from the broadcast logs.
+fn latest_deployment(state: &Cheatcodes, contract_name: &String, chain_id: u64) -> Result<Address> {
+    if let Ok(deployment) = Deployment::load(&state.config.deployments, chain_id, contract_name) {
+        return Ok(deployment.address);
+    }
+
+    let latest_broadcast = latest_broadcast(
+        contract_name,
+        chain_id,
+        &state.config.broadcast,
+        vec![CallKind::Create, CallKind::Create2],
+    )?;
+
+    Ok(latest_broadcast.contractAddress)
+}
+
 fn latest_broadcast(
     contract_name: &String,
     chain_id: u64,
@@ -127,6 +127,36 @@ fn exec_create(
         })
     }
 
+    /// Obtains [revm::Evm] instance and executes the given CALL frame.
+    fn exec_call(
+        &mut self,
+        inputs: CallInputs,
+        ccx: &mut CheatsCtxt,
+    ) -> Result<CallOutcome, EVMError<DatabaseError>> {
+        with_evm(self, ccx, |evm| {
+            evm.context.evm.inner.journaled_state.depth += 1;
+
+            let first_frame_or_result =
+                evm.handler.execution().call(&mut evm.context, Box::new(inputs))?;
+
+            let mut result = match first_frame_or_result {
+                revm::FrameOrResult::Frame(first_frame) => evm.run_the_loop(first_frame)?,
+                revm::FrameOrResult::Result(result) => result,
+            };
+
+            evm.handler.execution().last_frame_return(&mut evm.context, &mut result)?;
+
+            let outcome = match result {
+                revm::FrameResult::Call(call) => call,
+                revm::FrameResult::Create(_) | revm::FrameResult::EOFCreate(_) => unreachable!(),
+            };
+
+            evm.context.evm.inner.journaled_state.depth -= 1;
+
+            Ok(outcome)
+        })
+    }
+
     fn console_log(&mut self, ccx: &mut CheatsCtxt, msg: &str) {
         self.get_inspector(ccx.state).console_log(msg);
     }
@@ -261,6 +291,12 @@ pub struct GasMetering {
     pub last_gas_used: u64,
     /// Gas records for the active snapshots.
     pub gas_records: Vec<GasRecord>,
+    /// Gas records for the marks set by `markGas`.
+    pub gas_marks: Vec<GasRecord>,
+
+    /// The depth and the exclusive upper bound of gas set by `expectGasLt`, checked against the
+    /// next call made at that depth.
+    pub expected_gas_lt: Option<(u64, u64)>,
 }
 
 impl GasMetering {
@@ -417,6 +453,10 @@ pub struct Cheatcodes {
     /// merged into the previous vector.
     pub recorded_account_diffs_stack: Option<Vec<Vec<AccountAccess>>>,
 
+    /// The nonce and code hash of the accounts whose nonce or code was set with a cheatcode while
+    /// recording state diffs, before the first change.
+    pub recorded_account_infos: HashMap<Address, (u64, B256)>,
+
     /// The information of the debug step recording.
     pub record_debug_steps_info: Option<RecordDebugStepInfo>,
 
@@ -427,6 +467,9 @@ pub struct Cheatcodes {
     // **Note**: inner must a BTreeMap because of special `Ord` impl for `MockCallDataContext`
     pub mocked_calls: HashMap<Address, BTreeMap<MockCallDataContext, VecDeque<MockCallReturnData>>>,
 
+    /// Number of calls answered by each mock.
+    pub mocked_call_counts: HashMap<Address, HashMap<MockCallDataContext, u64>>,
+
     /// Mocked functions. Maps target address to be mocked to pair of (calldata, mock address).
     pub mocked_functions: HashMap<Address, HashMap<Bytes, Address>>,
 
@@ -523,9 +566,11 @@ pub fn new(config: Arc<CheatsConfig>) -> Self {
             fork_revert_diagnostic: Default::default(),
             accesses: Default::default(),
             recorded_account_diffs_stack: Default::default(),
+            recorded_account_infos: Default::default(),
             recorded_logs: Default::default(),
             record_debug_steps_info: Default::default(),
             mocked_calls: Default::default(),
+            mocked_call_counts: Default::default(),
             mocked_functions: Default::default(),
             expected_calls: Default::default(),
             expected_emits: Default::default(),
@@ -550,6 +595,23 @@ pub fn new(config: Arc<CheatsConfig>) -> Self {
         }
     }
 
+    /// Records the nonce and code hash of `address` before a cheatcode first changes them while
+    /// recording state diffs, to report their changes.
+    ///
+    /// Nonce and code changes caused by the executed code, e.g. contract creations, are not
+    /// reported.
+    pub(crate) fn record_account_info(&mut self, ecx: InnerEcx, address: Address) {
+        if self.recorded_account_diffs_stack.is_none() ||
+            self.recorded_account_infos.contains_key(&address)
+        {
+            return;
+        }
+        if let Ok(account) = ecx.load_account(address) {
+            let info = (account.info.nonce, account.info.code_hash);
+            self.recorded_account_infos.insert(address, info);
+        }
+    }
+
     /// Returns the configured prank at given depth or the first prank configured at a lower depth.
     /// For example, if pranks configured for depth 1, 3 and 5, the prank for depth 4 is the one
     /// configured at depth 3.
@@ -978,19 +1040,26 @@ pub fn call_with_executor(
 
         // Handle mocked calls
         if let Some(mocks) = self.mocked_calls.get_mut(&call.bytecode_address) {
-            let ctx =
-                MockCallDataContext { calldata: call.input.clone(), value: call.transfer_value() };
-
-            if let Some(return_data_queue) = match mocks.get_mut(&ctx) {
-                Some(queue) => Some(queue),
-                None => mocks
-                    .iter_mut()
-                    .find(|(mock, _)| {
-                        call.input.get(..mock.calldata.len()) == Some(&mock.calldata[..]) &&
+            let ctx = MockCallDataContext {
+                calldata: call.input.clone(),
+                value: call.transfer_value(),
+                mask: None,
+            };
+
+            let mock = if mocks.contains_key(&ctx) {
+                Some(ctx)
+            } else {
+                mocks
+                    .keys()
+                    .find(|mock| {
+                        mock.matches_calldata(&call.input) &&
                             mock.value.is_none_or(|value| Some(value) == call.transfer_value())
                     })
-                    .map(|(_, v)| v),
-            } {
+                    .cloned()
+            };
+            if let Some((mock, return_data_queue)) =
+                mock.and_then(|mock| mocks.get_mut(&mock).map(|queue| (mock, queue)))
+            {
                 if let Some(return_data) = if return_data_queue.len() == 1 {
                     // If the mocked calls stack has a single element in it, don't empty it
                     return_data_queue.front().map(|x| x.to_owned())
@@ -998,6 +1067,12 @@ pub fn call_with_executor(
                     // Else, we pop the front element
                     return_data_queue.pop_front()
                 } {
+                    *self
+                        .mocked_call_counts
+                        .entry(call.bytecode_address)
+                        .or_default()
+                        .entry(mock)
+                        .or_default() += 1;
                     return Some(CallOutcome {
                         result: InterpreterResult {
                             result: return_data.ret_type,
@@ -1276,8 +1351,8 @@ fn step(&mut self, interpreter: &mut Interpreter, ecx: Ecx) {
             mapping::step(mapping_slots, interpreter);
         }
 
-        // `snapshotGas*`: take a snapshot of the current gas.
-        if self.gas_metering.recording {
+        // `snapshotGas*` and `markGas`: take a snapshot of the current gas.
+        if self.gas_metering.recording || !self.gas_metering.gas_marks.is_empty() {
             self.meter_gas_record(interpreter, ecx);
         }
     }
@@ -1475,6 +1550,20 @@ fn call_end(&mut self, ecx: Ecx, call: &CallInputs, mut outcome: CallOutcome) ->
             gasRemaining: gas.remaining(),
         });
 
+        // Check the gas usage of the call expected by `expectGasLt`.
+        if let Some((depth, max_gas)) = self.gas_metering.expected_gas_lt {
+            if depth == ecx.journaled_state.depth() {
+                self.gas_metering.expected_gas_lt = None;
+                if gas.spent() >= max_gas && !outcome.result.is_revert() {
+                    outcome.result.result = InstructionResult::Revert;
+                    outcome.result.output = Error::encode(format!(
+                        "expected call to use less than {max_gas} gas, but it used {}",
+                        gas.spent()
+                    ));
+                }
+            }
+        }
+
         // If `startStateDiffRecording` has been called, update the `reverted` status of the
         // previous call depth's recorded accesses, if any
         if let Some(recorded_account_diffs_stack) = &mut self.recorded_account_diffs_stack {
@@ -1779,21 +1868,30 @@ fn meter_gas(&mut self, interpreter: &mut Interpreter) {
     #[cold]
     fn meter_gas_record(&mut self, interpreter: &mut Interpreter, ecx: Ecx) {
         if matches!(interpreter.instruction_result, InstructionResult::Continue) {
-            self.gas_metering.gas_records.iter_mut().for_each(|record| {
-                if ecx.journaled_state.depth() == record.depth {
-                    // Skip the first opcode of the first call frame as it includes the gas cost of
-                    // creating the snapshot.
-                    if self.gas_metering.last_gas_used != 0 {
-                        let gas_diff =
-                            interpreter.gas.spent().saturating_sub(self.gas_metering.last_gas_used);
-                        record.gas_used = record.gas_used.saturating_add(gas_diff);
-                    }
+            let depth = ecx.journaled_state.depth();
+            // Skip the first opcode of the first call frame as it includes the gas cost of
+            // creating the snapshot.
+            let gas_diff = if self.gas_metering.last_gas_used != 0 {
+                interpreter.gas.spent().saturating_sub(self.gas_metering.last_gas_used)
+            } else {
+                0
+            };
+            let mut recorded = false;
+            self.gas_metering
+                .gas_records
+                .iter_mut()
+                .chain(self.gas_metering.gas_marks.iter_mut())
+                .filter(|record| record.depth == depth)
+                .for_each(|record| {
+                    record.gas_used = record.gas_used.saturating_add(gas_diff);
+                    recorded = true;
+                });
 
-                    // Update `last_gas_used` to the current spent gas for the next iteration to
-                    // compare against.
-                    self.gas_metering.last_gas_used = interpreter.gas.spent();
-                }
-            });
+            // Update `last_gas_used` to the current spent gas for the next iteration to
+            // compare against.
+            if recorded {
+                self.gas_metering.last_gas_used = interpreter.gas.spent();
+            }
         }
     }
 
@@ -37,6 +37,8 @@
 
 mod crypto;
 
+mod csv;
+
 mod version;
 
 mod env;
@@ -1,12 +1,17 @@
-use crate::{CheatcodesExecutor, CheatsCtxt, Result, Vm::*};
+use crate::{CheatcodesExecutor, CheatsConfig, CheatsCtxt, Result, Vm::*};
 use alloy_primitives::{hex, I256, U256};
+use foundry_common::fs;
+use foundry_config::fs_permissions::FsAccessKind;
 use foundry_evm_core::{
     abi::console::{format_units_int, format_units_uint},
     backend::GLOBAL_FAIL_SLOT,
     constants::CHEATCODE_ADDRESS,
 };
 use itertools::Itertools;
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    path::{Component, Path},
+};
 
 const EQ_REL_DELTA_RESOLUTION: U256 = U256::from_limbs([18, 0, 0, 0]);
 
@@ -447,6 +452,58 @@ fn apply_full(
     (assertApproxEqRelDecimal_2Call, assertApproxEqRelDecimal_3Call),
 }
 
+impl crate::Cheatcode for assertMatchesSnapshot_0Call {
+    fn apply_full(&self, ccx: &mut CheatsCtxt, executor: &mut dyn CheatcodesExecutor) -> Result {
+        let Self { name, value } = self;
+        let result =
+            assert_matches_snapshot(&ccx.state.config, name, &hex::encode_prefixed(value))?;
+        handle_assertion_result(result, ccx, executor, String::clone, None, true)
+    }
+}
+
+impl crate::Cheatcode for assertMatchesSnapshot_1Call {
+    fn apply_full(&self, ccx: &mut CheatsCtxt, executor: &mut dyn CheatcodesExecutor) -> Result {
+        let Self { name, value } = self;
+        let result = assert_matches_snapshot(&ccx.state.config, name, value)?;
+        handle_assertion_result(result, ccx, executor, String::clone, None, true)
+    }
+}
+
+/// Compares `value` with the golden file `<snapshots>/golden/<name>`, which is written instead if
+/// it does not exist yet or if snapshots are being updated.
+///
+/// Golden files are kept apart from the gas snapshots, and are accessed with the file system
+/// permissions of the project like any other file. Bytes are stored as hex strings to keep golden
+/// files readable in diffs.
+fn assert_matches_snapshot(
+    config: &CheatsConfig,
+    name: &str,
+    value: &str,
+) -> Result<Result<Vec<u8>, String>> {
+    ensure!(
+        !name.is_empty() && Path::new(name).components().all(|c| matches!(c, Component::Normal(_))),
+        "invalid snapshot name `{name}`: must be a relative path inside the snapshots directory"
+    );
+    let path = config.golden_snapshots.join(name);
+
+    if !config.update_snapshots {
+        let path = config.ensure_path_allowed(&path, FsAccessKind::Read)?;
+        if let Ok(expected) = fs::read_to_string(&path) {
+            if expected == value {
+                return Ok(Ok(Default::default()));
+            }
+            return Ok(Err(format!("snapshot `{name}` does not match: {value} != {expected}")));
+        }
+    }
+
+    let path = config.ensure_path_allowed(&path, FsAccessKind::Write)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, value)?;
+    Ok(Ok(Default::default()))
+}
+
 fn assert_true(condition: bool) -> Result<Vec<u8>, SimpleAssertionError> {
     if condition {
         Ok(Default::default())
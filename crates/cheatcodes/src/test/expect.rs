@@ -96,6 +96,9 @@ pub struct ExpectedEmit {
     /// └───────┴───────┴───────┴───────┴────┘
     /// ```
     pub checks: [bool; 5],
+    /// If present, only the data bytes set in the mask are checked, the bytes past its end being
+    /// checked as a whole
+    pub data_mask: Option<Bytes>,
     /// If present, check originating address against this
     pub address: Option<Address>,
     /// If present, relax the requirement that topic 0 must be present. This allows anonymous
@@ -211,6 +214,22 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
     }
 }
 
+impl Cheatcode for expectCallCountCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { callee, selector, count } = self;
+        expect_call(
+            state,
+            callee,
+            &Bytes::from(*selector),
+            None,
+            None,
+            None,
+            *count,
+            ExpectedCallType::Count,
+        )
+    }
+}
+
 impl Cheatcode for expectCallMinGas_0Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { callee, msgValue, minGas, data } = self;
@@ -376,6 +395,55 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
     }
 }
 
+impl Cheatcode for expectEmitMasked_0Call {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { checkTopic1, checkTopic2, checkTopic3, dataMask } = self;
+        expect_emit(
+            ccx.state,
+            ccx.ecx.journaled_state.depth(),
+            [true, *checkTopic1, *checkTopic2, *checkTopic3, true],
+            None,
+            false,
+            1,
+        )?;
+        pending_emit(ccx.state)?.data_mask = Some(dataMask.clone());
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for expectEmitMasked_1Call {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { checkTopic1, checkTopic2, checkTopic3, dataMask, emitter } = self;
+        expect_emit(
+            ccx.state,
+            ccx.ecx.journaled_state.depth(),
+            [true, *checkTopic1, *checkTopic2, *checkTopic3, true],
+            Some(*emitter),
+            false,
+            1,
+        )?;
+        pending_emit(ccx.state)?.data_mask = Some(dataMask.clone());
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for expectEmitCountCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { count } = *self;
+        pending_emit(state)?.count = count;
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for expectGasLtCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { gas } = *self;
+        let gas = gas.saturating_to();
+        ccx.state.gas_metering.expected_gas_lt = Some((ccx.ecx.journaled_state.depth(), gas));
+        Ok(Default::default())
+    }
+}
+
 impl Cheatcode for expectCreateCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { bytecode, deployer } = self;
@@ -748,8 +816,16 @@ fn expect_emit(
     anonymous: bool,
     count: u64,
 ) -> Result {
-    let expected_emit =
-        ExpectedEmit { depth, checks, address, found: false, log: None, anonymous, count };
+    let expected_emit = ExpectedEmit {
+        depth,
+        checks,
+        data_mask: None,
+        address,
+        found: false,
+        log: None,
+        anonymous,
+        count,
+    };
     if let Some(found_emit_pos) = state.expected_emits.iter().position(|(emit, _)| emit.found) {
         // The order of emits already found (back of queue) should not be modified, hence push any
         // new emit before first found emit.
@@ -762,6 +838,22 @@ fn expect_emit(
     Ok(Default::default())
 }
 
+/// Returns the last declared expected emit, if its log has not been emitted yet.
+fn pending_emit(state: &mut Cheatcodes) -> Result<&mut ExpectedEmit> {
+    state
+        .expected_emits
+        .iter_mut()
+        .rev()
+        .map(|(emit, _)| emit)
+        .find(|emit| !emit.found)
+        .filter(|emit| emit.log.is_none())
+        .ok_or_else(|| {
+            fmt_err!(
+                "no pending `expectEmit`; call `expectEmit` before emitting the expected event"
+            )
+        })
+}
+
 pub(crate) fn handle_expect_emit(
     state: &mut Cheatcodes,
     log: &alloy_primitives::Log,
@@ -845,7 +937,12 @@ pub(crate) fn handle_expect_emit(
     }
 
     event_to_fill_or_check.found = || -> bool {
-        if !checks_topics_and_data(event_to_fill_or_check.checks, expected, log) {
+        if !checks_topics_and_data(
+            event_to_fill_or_check.checks,
+            event_to_fill_or_check.data_mask.as_deref(),
+            expected,
+            log,
+        ) {
             return false
         }
 
@@ -887,6 +984,7 @@ pub(crate) fn handle_expect_emit(
 #[derive(Clone, Debug, Default)]
 pub struct LogCountMap {
     checks: [bool; 5],
+    data_mask: Option<Bytes>,
     expected_log: RawLog,
     map: HashMap<RawLog, u64>,
 }
@@ -896,6 +994,7 @@ impl LogCountMap {
     fn new(expected_emit: &ExpectedEmit) -> Self {
         Self {
             checks: expected_emit.checks,
+            data_mask: expected_emit.data_mask.clone(),
             expected_log: expected_emit.log.clone().expect("log should be filled here"),
             map: Default::default(),
         }
@@ -925,7 +1024,7 @@ fn insert(&mut self, log: &RawLog) -> bool {
 
     /// Checks the incoming raw log against the expected logs topics and data.
     fn satisfies_checks(&self, log: &RawLog) -> bool {
-        checks_topics_and_data(self.checks, &self.expected_log, log)
+        checks_topics_and_data(self.checks, self.data_mask.as_deref(), &self.expected_log, log)
     }
 
     pub fn count(&self, log: &RawLog) -> u64 {
@@ -984,7 +1083,12 @@ fn expect_revert(
     Ok(Default::default())
 }
 
-fn checks_topics_and_data(checks: [bool; 5], expected: &RawLog, log: &RawLog) -> bool {
+fn checks_topics_and_data(
+    checks: [bool; 5],
+    data_mask: Option<&[u8]>,
+    expected: &RawLog,
+    log: &RawLog,
+) -> bool {
     if log.topics().len() != expected.topics().len() {
         return false
     }
@@ -1001,8 +1105,30 @@ fn expect_revert(
     }
 
     // Check data
-    if checks[4] && expected.data.as_ref() != log.data.as_ref() {
-        return false
+    if checks[4] {
+        let (expected, actual) = (expected.data.as_ref(), log.data.as_ref());
+        match data_mask {
+            Some(mask) => {
+                if expected.len() != actual.len() {
+                    return false
+                }
+                let masked = mask.len().min(expected.len());
+                if !expected[..masked]
+                    .iter()
+                    .zip(&actual[..masked])
+                    .zip(mask)
+                    .all(|((expected, actual), mask)| expected & mask == actual & mask) ||
+                    expected[masked..] != actual[masked..]
+                {
+                    return false
+                }
+            }
+            None => {
+                if expected != actual {
+                    return false
+                }
+            }
+        }
     }
 
     true
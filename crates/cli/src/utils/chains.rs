@@ -0,0 +1,108 @@
+use alloy_chains::NamedChain;
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::LazyLock};
+
+/// The bundled chain registry, in the format of <https://chainid.network/chains.json>.
+static CHAINS: LazyLock<Vec<ChainInfo>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../assets/chains.json"))
+        .expect("invalid bundled chain registry")
+});
+
+/// A chain of the chain registry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainInfo {
+    pub name: String,
+    pub chain: String,
+    pub chain_id: u64,
+    pub short_name: String,
+    pub native_currency: NativeCurrency,
+    /// The public RPC URLs of the chain.
+    pub rpc: Vec<String>,
+    #[serde(default)]
+    pub explorers: Vec<Explorer>,
+    /// The EIPs supported by the chain, e.g. `EIP1559`.
+    #[serde(default)]
+    pub features: Vec<Feature>,
+    #[serde(rename = "infoURL")]
+    pub info_url: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NativeCurrency {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Explorer {
+    pub name: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standard: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Feature {
+    pub name: String,
+}
+
+impl ChainInfo {
+    /// Whether the chain supports the EIP, e.g. `1559`.
+    pub fn supports(&self, eip: &str) -> bool {
+        self.features.iter().any(|feature| feature.name.strip_prefix("EIP") == Some(eip))
+    }
+
+    /// Returns the first RPC URL which doesn't require an API key.
+    pub fn default_rpc_url(&self) -> Option<&str> {
+        self.rpc
+            .iter()
+            .map(String::as_str)
+            .find(|url| url.starts_with("http") && !url.contains("${"))
+    }
+}
+
+/// Returns the chains of the bundled chain registry.
+pub fn chain_registry() -> &'static [ChainInfo] {
+    &CHAINS
+}
+
+/// Finds a chain of the registry by ID, name, short name or alias of [NamedChain], e.g. `1`,
+/// `Ethereum Mainnet`, `eth` or `mainnet`.
+pub fn find_chain(chain: &str) -> Option<&'static ChainInfo> {
+    let id = match chain.parse::<u64>() {
+        Ok(id) => Some(id),
+        Err(_) => NamedChain::from_str(&chain.to_lowercase()).ok().map(|chain| chain as u64),
+    };
+    chain_registry().iter().find(|info| {
+        Some(info.chain_id) == id ||
+            info.name.eq_ignore_ascii_case(chain) ||
+            info.short_name.eq_ignore_ascii_case(chain)
+    })
+}
+
+/// Finds a chain of the registry by ID.
+pub fn find_chain_by_id(id: u64) -> Option<&'static ChainInfo> {
+    chain_registry().iter().find(|info| info.chain_id == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_registry_chains() {
+        assert!(!chain_registry().is_empty());
+        for query in ["1", "mainnet", "Ethereum Mainnet", "eth"] {
+            assert_eq!(find_chain(query).map(|chain| chain.chain_id), Some(1), "{query}");
+        }
+        assert_eq!(find_chain("base-sepolia").map(|chain| chain.chain_id), Some(84532));
+        assert_eq!(find_chain("unknown"), None);
+
+        let mainnet = find_chain_by_id(1).unwrap();
+        assert!(mainnet.supports("1559") && mainnet.supports("4844"));
+        assert!(!find_chain_by_id(56).unwrap().supports("1559"));
+        assert_eq!(mainnet.default_rpc_url(), Some("https://eth.llamarpc.com"));
+    }
+}
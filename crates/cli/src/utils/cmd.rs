@@ -1,7 +1,9 @@
 use alloy_json_abi::JsonAbi;
-use alloy_primitives::Address;
+use alloy_primitives::{map::AddressHashMap, Address, Bytes};
 use eyre::{Result, WrapErr};
-use foundry_common::{compile::ProjectCompiler, fs, shell, ContractsByArtifact, TestFunctionExt};
+use foundry_common::{
+    compile::ProjectCompiler, ens::ProviderEnsExt, fs, shell, ContractsByArtifact, TestFunctionExt,
+};
 use foundry_compilers::{
     artifacts::{CompactBytecode, Settings},
     cache::{CacheEntry, CompilerCache},
@@ -11,8 +13,9 @@
 use foundry_config::{error::ExtractConfigError, figment::Figment, Chain, Config, NamedChain};
 use foundry_debugger::Debugger;
 use foundry_evm::{
-    executors::{DeployResult, EvmError, RawCallResult},
+    executors::{DeployResult, EvmError, Executor, RawCallResult},
     opts::EvmOpts,
+    revm::DatabaseRef,
     traces::{
         debug::{ContractSources, DebugTraceIdentifier},
         decode_trace_arena,
@@ -280,13 +283,45 @@ pub struct TraceResult {
     pub success: bool,
     pub traces: Option<Traces>,
     pub gas_used: u64,
+    /// The runtime code of the called accounts that were not created in the traces.
+    pub runtime_codes: AddressHashMap<Bytes>,
 }
 
 impl TraceResult {
     /// Create a new [`TraceResult`] from a [`RawCallResult`].
     pub fn from_raw(raw: RawCallResult, trace_kind: TraceKind) -> Self {
         let RawCallResult { gas_used, traces, reverted, .. } = raw;
-        Self { success: !reverted, traces: traces.map(|arena| vec![(trace_kind, arena)]), gas_used }
+        Self {
+            success: !reverted,
+            traces: traces.map(|arena| vec![(trace_kind, arena)]),
+            gas_used,
+            runtime_codes: Default::default(),
+        }
+    }
+
+    /// Collects the runtime code of the accounts called in the traces from the state of the
+    /// executor, so that contracts which already existed can be matched against local artifacts.
+    pub fn with_runtime_codes(mut self, executor: &Executor) -> Self {
+        let db = executor.backend();
+        let nodes = self.traces.iter().flatten().flat_map(|(_, arena)| arena.nodes());
+        for node in nodes {
+            let address = node.trace.address;
+            if node.trace.kind.is_any_create() || self.runtime_codes.contains_key(&address) {
+                continue;
+            }
+            let Ok(Some(info)) = db.basic_ref(address) else { continue };
+            let code = match info.code {
+                Some(code) => code,
+                None => match db.code_by_hash_ref(info.code_hash) {
+                    Ok(code) => code,
+                    Err(_) => continue,
+                },
+            };
+            if !code.is_empty() {
+                self.runtime_codes.insert(address, code.original_bytes());
+            }
+        }
+        self
     }
 }
 
@@ -362,8 +397,23 @@ pub async fn handle_traces(
     });
     let config_labels = config.labels.clone().into_iter();
 
+    // Primary ENS names of the accounts, overridden by the explicit labels.
+    let ens_names = match super::get_provider(config) {
+        Ok(provider) if !config.offline => {
+            let addresses = result
+                .traces
+                .iter()
+                .flatten()
+                .flat_map(|(_, trace)| trace.arena.nodes())
+                .flat_map(|node| [node.trace.caller, node.trace.address])
+                .collect();
+            provider.lookup_addresses(addresses).await.unwrap_or_default()
+        }
+        _ => Default::default(),
+    };
+
     let mut builder = CallTraceDecoderBuilder::new()
-        .with_labels(labels.chain(config_labels))
+        .with_labels(ens_names.into_iter().chain(labels).chain(config_labels))
         .with_signature_identifier(SignaturesIdentifier::new(
             Config::foundry_cache_dir(),
             config.offline,
@@ -371,7 +421,7 @@ pub async fn handle_traces(
     let mut identifier = TraceIdentifiers::new().with_etherscan(config, chain)?;
     if let Some(contracts) = &known_contracts {
         builder = builder.with_known_contracts(contracts);
-        identifier = identifier.with_local(contracts);
+        identifier = identifier.with_local(contracts).with_runtime_codes(&result.runtime_codes);
     }
 
     let mut decoder = builder.build();
@@ -9,6 +9,7 @@
 use foundry_config::{Chain, Config};
 use serde::de::DeserializeOwned;
 use std::{
+    borrow::Cow,
     ffi::OsStr,
     future::Future,
     path::{Path, PathBuf},
@@ -26,6 +27,9 @@
 mod abi;
 pub use abi::*;
 
+mod chains;
+pub use chains::*;
+
 // reexport all `foundry_config::utils`
 #[doc(hidden)]
 pub use foundry_config::utils::*;
@@ -89,11 +93,27 @@ pub fn get_provider(config: &Config) -> Result<RetryProvider> {
     get_provider_builder(config)?.build()
 }
 
+/// Returns the RPC URL of the [Config].
+///
+/// Defaults to the public RPC URL of the configured chain in the bundled chain registry, or
+/// `http://localhost:8545` if no chain is configured.
+pub fn get_rpc_url(config: &Config) -> Result<Cow<'_, str>> {
+    if let Some(url) = config.get_rpc_url() {
+        return Ok(url?);
+    }
+    let url = config
+        .chain
+        .and_then(|chain| find_chain_by_id(chain.id()))
+        .and_then(ChainInfo::default_rpc_url)
+        .unwrap_or("http://localhost:8545");
+    Ok(Cow::Borrowed(url))
+}
+
 /// Returns a [ProviderBuilder] instantiated using [Config] values.
 ///
-/// Defaults to `http://localhost:8545` and `Mainnet`.
+/// Defaults to the RPC URL of [get_rpc_url] and `Mainnet`.
 pub fn get_provider_builder(config: &Config) -> Result<ProviderBuilder> {
-    let url = config.get_rpc_url_or_localhost_http()?;
+    let url = get_rpc_url(config)?;
     let mut builder = ProviderBuilder::new(url.as_ref());
 
     if let Ok(chain) = config.chain.unwrap_or_default().try_into() {
@@ -0,0 +1,434 @@
+//! A content-addressed store of compiled artifacts.
+//!
+//! The compiler cache of a project is keyed by source path and only holds the artifacts of the
+//! checked out sources, so switching branches recompiles every changed file even if the same
+//! content was compiled before. The [`ArtifactStore`] keeps the cache entry and the artifacts of
+//! every compiled source, keyed by the compiler settings, the content of the source and the
+//! content of all its imports, and restores them into the project cache before compiling.
+//!
+//! Since keys only depend on contents, a store can be shared between worktrees of a repository,
+//! and between machines with a [`RemoteCache`].
+
+use alloy_primitives::{hex, keccak256};
+use eyre::Result;
+use foundry_compilers::{compilers::Compiler, Project};
+use foundry_config::Config;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+mod remote;
+pub use remote::RemoteCache;
+
+/// A content-addressed store of compiled artifacts, see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct ArtifactStore {
+    root: PathBuf,
+    remote: Option<RemoteCache>,
+}
+
+/// A cache entry of a compiled source with its artifacts.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredEntry {
+    /// The cache entry, as written by the compiler.
+    entry: Value,
+    /// The hashes of all transitive imports, by source name.
+    imports: BTreeMap<String, String>,
+    /// The hashes of the artifacts, by path relative to the artifacts directory.
+    artifacts: BTreeMap<String, String>,
+    /// The ids of the build info files of the artifacts.
+    build_ids: BTreeSet<String>,
+}
+
+impl ArtifactStore {
+    /// Creates a new store at `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into(), remote: None }
+    }
+
+    /// Sets the remote cache to download artifacts from, and upload them to if writable.
+    pub fn with_remote(mut self, remote: RemoteCache) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
+    /// Returns the store configured with `content_cache` and `remote_cache`, if enabled.
+    ///
+    /// The local store defaults to `~/.foundry/cache/artifacts`, and is always used when a remote
+    /// cache is configured.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        if !config.content_cache && config.remote_cache.is_none() {
+            return Ok(None);
+        }
+        let root = match &config.content_cache_path {
+            Some(path) => path.clone(),
+            None => match Config::foundry_cache_dir() {
+                Some(dir) => dir.join("artifacts"),
+                None => return Ok(None),
+            },
+        };
+        let mut store = Self::new(root);
+        if let Some(remote) = &config.remote_cache {
+            store = store.with_remote(RemoteCache::new(remote)?);
+        }
+        Ok(Some(store))
+    }
+
+    /// Returns the root directory of the store.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Stores the cache entries and artifacts of all sources compiled by `project`.
+    ///
+    /// Returns the number of stored entries.
+    pub fn save<C: Compiler>(&self, project: &Project<C>) -> Result<usize> {
+        let cache_path = &project.paths.cache;
+        if !cache_path.exists() {
+            return Ok(0);
+        }
+        let mut cache: Value = crate::fs::read_json_file(cache_path)?;
+        let ns = namespace(project)?;
+        let mut hashes = FileHashes::new(project.root());
+
+        let mut stored = 0;
+        let files = cache["files"].as_object().cloned().unwrap_or_default();
+        'files: for (name, entry) in &files {
+            let Some(own) = hashes.get(name) else { continue };
+            let Some(imports) = transitive_imports(&files, name, &mut hashes) else { continue };
+            let index_key = format!("{ns}/entries/{}.json", hash(key(name, &own)));
+            let mut index = self.index(&index_key, false)?;
+            if index.iter().any(|stored| stored.imports == imports) {
+                continue;
+            }
+            // merge the entries uploaded by others, so that they are not overwritten
+            if self.remote.as_ref().is_some_and(|remote| remote.is_writable()) {
+                index = self.index(&index_key, true)?;
+                if index.iter().any(|stored| stored.imports == imports) {
+                    continue;
+                }
+            }
+
+            let mut artifact_paths = BTreeSet::new();
+            let mut build_ids = BTreeSet::new();
+            collect_artifacts(&entry["artifacts"], &mut artifact_paths, &mut build_ids);
+
+            let mut artifacts = BTreeMap::new();
+            for artifact in artifact_paths {
+                let Ok(content) = std::fs::read(project.paths.artifacts.join(&artifact)) else {
+                    continue 'files;
+                };
+                let blob = hash(&content);
+                self.put_missing(&format!("{ns}/blobs/{blob}"), &content)?;
+                artifacts.insert(artifact, blob);
+            }
+            for id in &build_ids {
+                let build_info = project.paths.build_infos.join(format!("{id}.json"));
+                if let Ok(content) = std::fs::read(build_info) {
+                    self.put_missing(&format!("{ns}/build-info/{id}.json"), &content)?;
+                }
+            }
+
+            index.push(StoredEntry { entry: entry.clone(), imports, artifacts, build_ids });
+            self.put(&index_key, &serde_json::to_vec(&index)?)?;
+            stored += 1;
+        }
+
+        // keep an empty cache to restore into projects which were never compiled
+        cache["files"] = Value::Object(Default::default());
+        cache["builds"] = Value::Array(Vec::new());
+        self.put_missing(&format!("{ns}/cache.json"), &serde_json::to_vec(&cache)?)?;
+
+        Ok(stored)
+    }
+
+    /// Restores the stored cache entries and artifacts of the sources of `project` which match
+    /// the sources on disk, so that they are not recompiled.
+    ///
+    /// Returns the number of restored entries.
+    pub fn restore<C: Compiler>(&self, project: &Project<C>) -> Result<usize> {
+        let ns = namespace(project)?;
+        let cache_path = &project.paths.cache;
+        let mut cache: Value = if cache_path.exists() {
+            crate::fs::read_json_file(cache_path)?
+        } else if let Some(cache) = self.get_json(&format!("{ns}/cache.json"))? {
+            cache
+        } else {
+            return Ok(0);
+        };
+
+        let root = project.root();
+        let mut hashes = FileHashes::new(root);
+        let mut worklist = project
+            .paths
+            .input_files()
+            .into_iter()
+            .filter_map(|path| source_name(root, &path))
+            .collect::<Vec<_>>();
+        let mut visited = HashSet::new();
+        let mut restored = 0;
+        while let Some(name) = worklist.pop() {
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            let Some(own) = hashes.get(&name) else { continue };
+            let index_key = format!("{ns}/entries/{}.json", hash(key(&name, &own)));
+            let mut matches = |stored: &StoredEntry| {
+                stored.imports.iter().all(|(path, h)| hashes.get(path).as_ref() == Some(h))
+            };
+            let mut stored = self.index(&index_key, false)?.into_iter().find(&mut matches);
+            if stored.is_none() && self.remote.is_some() {
+                stored = self.index(&index_key, true)?.into_iter().find(&mut matches);
+            }
+            let Some(stored) = stored else { continue };
+            worklist.extend(stored.imports.keys().cloned());
+
+            let current = &cache["files"][&name];
+            let artifacts_exist = stored
+                .artifacts
+                .keys()
+                .all(|artifact| project.paths.artifacts.join(artifact).exists());
+            if current["contentHash"] == stored.entry["contentHash"] && artifacts_exist {
+                continue;
+            }
+
+            let mut blobs = Vec::with_capacity(stored.artifacts.len());
+            for (artifact, blob) in &stored.artifacts {
+                let Some(content) = self.get(&format!("{ns}/blobs/{blob}"))? else { break };
+                blobs.push((project.paths.artifacts.join(artifact), content));
+            }
+            if blobs.len() != stored.artifacts.len() {
+                continue;
+            }
+            for (path, content) in blobs {
+                write(&path, &content)?;
+            }
+            for id in &stored.build_ids {
+                let build_info = project.paths.build_infos.join(format!("{id}.json"));
+                if !build_info.exists() {
+                    if let Some(content) = self.get(&format!("{ns}/build-info/{id}.json"))? {
+                        write(&build_info, &content)?;
+                    }
+                }
+                if let Some(builds) = cache["builds"].as_array_mut() {
+                    if !builds.iter().any(|b| b == id) {
+                        builds.push(Value::String(id.clone()));
+                    }
+                }
+            }
+
+            let mut entry = stored.entry;
+            if let Some(modified) = last_modified(&root.join(&name)) {
+                entry["lastModificationDate"] = modified.into();
+            }
+            if !cache["files"].is_object() {
+                cache["files"] = Value::Object(Default::default());
+            }
+            cache["files"][&name] = entry;
+            restored += 1;
+        }
+
+        if restored > 0 {
+            write(cache_path, &serde_json::to_vec(&cache)?)?;
+        }
+        Ok(restored)
+    }
+
+    /// Returns the stored entries of a source, merged with the entries of the remote cache if
+    /// `remote` is set.
+    fn index(&self, key: &str, remote: bool) -> Result<Vec<StoredEntry>> {
+        let path = self.root.join(key);
+        let mut index: Vec<StoredEntry> =
+            if path.exists() { crate::fs::read_json_file(&path)? } else { Vec::new() };
+        let Some(cache) = self.remote.as_ref().filter(|_| remote) else { return Ok(index) };
+        if let Some(content) = cache.get(key)? {
+            for entry in serde_json::from_slice::<Vec<StoredEntry>>(&content)? {
+                if !index.iter().any(|stored| stored.imports == entry.imports) {
+                    index.push(entry);
+                }
+            }
+            write(&path, &serde_json::to_vec(&index)?)?;
+        }
+        Ok(index)
+    }
+
+    /// Returns the content stored at `key`, downloading it from the remote cache if it is not
+    /// available locally.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.root.join(key);
+        if path.exists() {
+            return Ok(Some(crate::fs::read(&path)?));
+        }
+        let Some(remote) = &self.remote else { return Ok(None) };
+        let Some(content) = remote.get(key)? else { return Ok(None) };
+        write(&path, &content)?;
+        Ok(Some(content))
+    }
+
+    fn get_json<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.get(key)?.map(|content| Ok(serde_json::from_slice(&content)?)).transpose()
+    }
+
+    /// Stores `content` at `key`, and uploads it to the remote cache if writable.
+    fn put(&self, key: &str, content: &[u8]) -> Result<()> {
+        write(&self.root.join(key), content)?;
+        if let Some(remote) = self.remote.as_ref().filter(|remote| remote.is_writable()) {
+            remote.put(key, content)?;
+        }
+        Ok(())
+    }
+
+    /// Stores content-addressed `content` at `key`, unless it is already stored.
+    fn put_missing(&self, key: &str, content: &[u8]) -> Result<()> {
+        let path = self.root.join(key);
+        if !path.exists() {
+            write(&path, content)?;
+        }
+        if let Some(remote) = self.remote.as_ref().filter(|remote| remote.is_writable()) {
+            if !remote.contains(key)? {
+                remote.put(key, content)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the namespace of the entries compiled with the settings of `project`.
+fn namespace<C: Compiler>(project: &Project<C>) -> Result<String> {
+    let settings = serde_json::to_vec(&project.settings)?;
+    Ok(hash(settings)[..16].to_string())
+}
+
+/// The hashes of source files, by source name.
+struct FileHashes<'a> {
+    root: &'a Path,
+    hashes: HashMap<String, Option<String>>,
+}
+
+impl<'a> FileHashes<'a> {
+    fn new(root: &'a Path) -> Self {
+        Self { root, hashes: HashMap::new() }
+    }
+
+    /// Returns the hash of the source `name`, or `None` if it does not exist.
+    fn get(&mut self, name: &str) -> Option<String> {
+        let root = self.root;
+        self.hashes
+            .entry(name.to_string())
+            .or_insert_with(|| std::fs::read(root.join(name)).ok().map(hash))
+            .clone()
+    }
+}
+
+/// Returns the hashes of all sources imported by `name`, directly or transitively.
+///
+/// Returns `None` if an import does not exist.
+fn transitive_imports(
+    files: &serde_json::Map<String, Value>,
+    name: &str,
+    hashes: &mut FileHashes<'_>,
+) -> Option<BTreeMap<String, String>> {
+    let mut imports = BTreeMap::new();
+    let mut worklist = vec![name.to_string()];
+    while let Some(file) = worklist.pop() {
+        let Some(direct) = files.get(&file).and_then(|entry| entry["imports"].as_array()) else {
+            continue;
+        };
+        for import in direct.iter().filter_map(Value::as_str) {
+            if imports.contains_key(import) || import == name {
+                continue;
+            }
+            imports.insert(import.to_string(), hashes.get(import)?);
+            worklist.push(import.to_string());
+        }
+    }
+    Some(imports)
+}
+
+/// Collects the artifact paths and build ids referenced by the `artifacts` of a cache entry.
+fn collect_artifacts(
+    value: &Value,
+    paths: &mut BTreeSet<String>,
+    build_ids: &mut BTreeSet<String>,
+) {
+    match value {
+        Value::Object(object) => {
+            if let Some(path) = object.get("path").and_then(Value::as_str) {
+                paths.insert(path.to_string());
+                if let Some(id) = object.get("build_id").and_then(Value::as_str) {
+                    build_ids.insert(id.to_string());
+                }
+                return;
+            }
+            for value in object.values() {
+                collect_artifacts(value, paths, build_ids);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_artifacts(value, paths, build_ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn write(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        crate::fs::create_dir_all(parent)?;
+    }
+    Ok(crate::fs::write(path, content)?)
+}
+
+fn key(name: &str, hash: &str) -> Vec<u8> {
+    format!("{name}\0{hash}").into_bytes()
+}
+
+fn hash(data: impl AsRef<[u8]>) -> String {
+    hex::encode(keccak256(data))
+}
+
+/// Returns the source name of `path`: the path relative to the project root.
+fn source_name(root: &Path, path: &Path) -> Option<String> {
+    Some(path.strip_prefix(root).ok()?.to_string_lossy().replace('\\', "/"))
+}
+
+/// Returns the modification time of `path` in milliseconds, like the compiler cache.
+fn last_modified(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_artifacts_of_cache_entries() {
+        let artifacts = serde_json::json!({
+            "Counter": {
+                "0.8.28": {
+                    "default": { "path": "Counter.sol/Counter.json", "build_id": "abc" }
+                }
+            },
+            "Lib": {
+                "0.8.28": {
+                    "default": { "path": "Counter.sol/Lib.json", "build_id": "abc" }
+                }
+            }
+        });
+        let mut paths = BTreeSet::new();
+        let mut build_ids = BTreeSet::new();
+        collect_artifacts(&artifacts, &mut paths, &mut build_ids);
+        assert_eq!(
+            paths.into_iter().collect::<Vec<_>>(),
+            ["Counter.sol/Counter.json", "Counter.sol/Lib.json"]
+        );
+        assert_eq!(build_ids.into_iter().collect::<Vec<_>>(), ["abc"]);
+    }
+}
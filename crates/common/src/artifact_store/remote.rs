@@ -0,0 +1,217 @@
+//! Remote backends of the [`ArtifactStore`](super::ArtifactStore).
+
+use eyre::{Context, Result};
+use foundry_config::cache::{RemoteCacheConfig, RemoteCacheMode};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use std::future::Future;
+use url::Url;
+
+/// A remote cache of compiled artifacts, accessed over HTTP.
+///
+/// Objects are stored under the same keys as in the local store, relative to the base URL, and
+/// are downloaded with `GET` and uploaded with `PUT`. The base URL is either an `http(s)://` URL,
+/// e.g. of a caching proxy, or a `gs://<bucket>/<prefix>` bucket, accessed at
+/// `https://storage.googleapis.com/<bucket>/<prefix>`.
+///
+/// Requests are authenticated with the configured bearer token, if any. S3 buckets are not
+/// supported directly, as their requests must be signed with AWS credentials: they are used
+/// through a proxy which signs the requests.
+#[derive(Clone, Debug)]
+pub struct RemoteCache {
+    client: Client,
+    base: Url,
+    auth_token: Option<String>,
+    mode: RemoteCacheMode,
+}
+
+impl RemoteCache {
+    /// Creates a new remote cache from its configuration.
+    pub fn new(config: &RemoteCacheConfig) -> Result<Self> {
+        let base = base_url(&config.url)?;
+        let auth_token = config.resolved_auth_token()?;
+        Ok(Self { client: Client::new(), base, auth_token, mode: config.mode })
+    }
+
+    /// Whether newly compiled artifacts are uploaded to the cache.
+    pub fn is_writable(&self) -> bool {
+        self.mode.is_writable()
+    }
+
+    /// Downloads the object at `key`, returns `None` if it does not exist.
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let request = self.request(self.client.get(self.url(key)?));
+        block_on(async {
+            let response = request.send().await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            let response = response.error_for_status()?;
+            Ok(Some(response.bytes().await?.to_vec()))
+        })
+        .wrap_err_with(|| format!("failed to download `{key}` from the remote cache"))
+    }
+
+    /// Returns whether the object at `key` exists.
+    pub fn contains(&self, key: &str) -> Result<bool> {
+        let request = self.request(self.client.head(self.url(key)?));
+        block_on(async {
+            let response = request.send().await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(false);
+            }
+            response.error_for_status()?;
+            Ok(true)
+        })
+        .wrap_err_with(|| format!("failed to query `{key}` in the remote cache"))
+    }
+
+    /// Uploads `content` to `key`.
+    pub fn put(&self, key: &str, content: &[u8]) -> Result<()> {
+        let request = self.request(self.client.put(self.url(key)?).body(content.to_vec()));
+        block_on(async {
+            request.send().await?.error_for_status()?;
+            Ok(())
+        })
+        .wrap_err_with(|| format!("failed to upload `{key}` to the remote cache"))
+    }
+
+    fn url(&self, key: &str) -> Result<Url> {
+        Ok(self.base.join(key)?)
+    }
+
+    fn request(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.auth_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+/// Returns the HTTP base URL of a remote cache location, see [`RemoteCache`].
+fn base_url(location: &str) -> Result<Url> {
+    let url = if location.starts_with("s3://") {
+        eyre::bail!(
+            "unsupported remote cache URL `{location}`, S3 requests must be signed with AWS \
+             credentials: use the http(s):// URL of a proxy which signs the requests"
+        )
+    } else if let Some(path) = location.strip_prefix("gs://") {
+        format!("https://storage.googleapis.com/{path}")
+    } else if location.starts_with("http://") || location.starts_with("https://") {
+        location.to_string()
+    } else {
+        eyre::bail!("unsupported remote cache URL `{location}`, expected http(s):// or gs://")
+    };
+    // objects are joined relative to the base, which must be a directory
+    let url = if url.ends_with('/') { url } else { format!("{url}/") };
+    Url::parse(&url).wrap_err_with(|| format!("invalid remote cache URL `{location}`"))
+}
+
+/// Runs `future` on the current runtime, or on a new one when called outside of a runtime.
+fn block_on<T>(future: impl Future<Output = Result<T>>) -> Result<T> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => crate::block_on_handle(&handle, future),
+        Err(_) => {
+            tokio::runtime::Builder::new_current_thread().enable_all().build()?.block_on(future)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifact_store::ArtifactStore;
+    use axum::{
+        body::Bytes,
+        http::{header::AUTHORIZATION, HeaderMap, Method, Uri},
+        response::IntoResponse,
+    };
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    #[test]
+    fn resolves_base_urls() {
+        assert_eq!(
+            base_url("gs://bucket/foundry").unwrap().as_str(),
+            "https://storage.googleapis.com/bucket/foundry/"
+        );
+        assert_eq!(
+            base_url("https://cache.example.com/foundry/").unwrap().as_str(),
+            "https://cache.example.com/foundry/"
+        );
+        assert!(base_url("ftp://cache.example.com").is_err());
+        assert!(base_url("s3://bucket/foundry").is_err());
+    }
+
+    type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// Spawns an HTTP server storing the objects uploaded with `PUT` in memory, returning its URL
+    /// and the objects by path.
+    async fn spawn_server() -> (String, Objects) {
+        let objects = Objects::default();
+        let stored = objects.clone();
+        let handler = move |method: Method, uri: Uri, headers: HeaderMap, body: Bytes| {
+            let stored = stored.clone();
+            async move {
+                assert_eq!(headers[AUTHORIZATION], "Bearer secret");
+                let mut objects = stored.lock().unwrap();
+                if method == Method::PUT {
+                    objects.insert(uri.path().to_string(), body.to_vec());
+                    return StatusCode::OK.into_response();
+                }
+                match objects.get(uri.path()) {
+                    Some(content) => content.clone().into_response(),
+                    None => StatusCode::NOT_FOUND.into_response(),
+                }
+            }
+        };
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = axum::Router::new().fallback(handler);
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, objects)
+    }
+
+    fn remote_cache(url: String, mode: RemoteCacheMode) -> RemoteCache {
+        RemoteCache::new(&RemoteCacheConfig { url, mode, auth_token: Some("secret".to_string()) })
+            .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reads_and_writes_objects() {
+        let (url, objects) = spawn_server().await;
+        let cache = remote_cache(format!("{url}/foundry"), RemoteCacheMode::ReadWrite);
+        assert!(cache.is_writable());
+
+        assert_eq!(cache.get("ns/blobs/a").unwrap(), None);
+        assert!(!cache.contains("ns/blobs/a").unwrap());
+
+        cache.put("ns/blobs/a", b"artifact").unwrap();
+        assert!(objects.lock().unwrap().contains_key("/foundry/ns/blobs/a"));
+        assert!(cache.contains("ns/blobs/a").unwrap());
+        assert_eq!(cache.get("ns/blobs/a").unwrap().as_deref(), Some(&b"artifact"[..]));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn read_only_cache_is_not_written() {
+        let (url, objects) = spawn_server().await;
+        objects.lock().unwrap().insert("/ns/blobs/a".to_string(), b"remote".to_vec());
+        let cache = remote_cache(url, RemoteCacheMode::ReadOnly);
+        assert!(!cache.is_writable());
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::new(dir.path()).with_remote(cache);
+        // downloads missing objects into the local store
+        assert_eq!(store.get("ns/blobs/a").unwrap().as_deref(), Some(&b"remote"[..]));
+        assert!(dir.path().join("ns/blobs/a").exists());
+
+        // only stores new objects locally
+        store.put("ns/entries/b.json", b"[]").unwrap();
+        store.put_missing("ns/blobs/c", b"local").unwrap();
+        assert!(dir.path().join("ns/entries/b.json").exists());
+        assert!(dir.path().join("ns/blobs/c").exists());
+        assert_eq!(objects.lock().unwrap().len(), 1);
+    }
+}
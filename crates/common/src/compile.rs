@@ -1,6 +1,7 @@
 //! Support for compiling [foundry_compilers::Project]
 
 use crate::{
+    artifact_store::ArtifactStore,
     reports::{report_kind, ReportKind},
     shell,
     term::SpinnerReporter,
@@ -59,6 +60,15 @@ pub struct ProjectCompiler {
 
     /// Extra files to include, that are not necessarily in the project's source dir.
     files: Vec<PathBuf>,
+
+    /// The store to restore artifacts from before compiling and to save them to afterwards.
+    artifact_store: Option<ArtifactStore>,
+
+    /// Previous contract sizes to compare the sizes against.
+    size_baseline: Option<BTreeMap<String, ContractInfo>>,
+
+    /// The maximum size increase in bytes compared to the baseline before failing.
+    max_size_increase: Option<usize>,
 }
 
 impl Default for ProjectCompiler {
@@ -81,6 +91,9 @@ pub fn new() -> Self {
             bail: None,
             ignore_eip_3860: false,
             files: Vec::new(),
+            artifact_store: None,
+            size_baseline: None,
+            max_size_increase: None,
         }
     }
 
@@ -134,6 +147,27 @@ pub fn files(mut self, files: impl IntoIterator<Item = PathBuf>) -> Self {
         self
     }
 
+    /// Sets the content-addressed store of artifacts to reuse across checkouts.
+    #[inline]
+    pub fn artifact_store(mut self, store: Option<ArtifactStore>) -> Self {
+        self.artifact_store = store;
+        self
+    }
+
+    /// Sets the previous contract sizes to compare the printed sizes against.
+    #[inline]
+    pub fn size_baseline(mut self, baseline: Option<BTreeMap<String, ContractInfo>>) -> Self {
+        self.size_baseline = baseline;
+        self
+    }
+
+    /// Sets the maximum size increase in bytes compared to the size baseline before failing.
+    #[inline]
+    pub fn max_size_increase(mut self, bytes: Option<usize>) -> Self {
+        self.max_size_increase = bytes;
+        self
+    }
+
     /// Compiles the project.
     pub fn compile<C: Compiler<CompilerContract = Contract>>(
         mut self,
@@ -148,9 +182,18 @@ pub fn compile<C: Compiler<CompilerContract = Contract>>(
             std::process::exit(0);
         }
 
+        let store = self.artifact_store.take().filter(|_| project.cached);
+        if let Some(store) = &store {
+            match store.restore(project) {
+                Ok(0) => {}
+                Ok(n) => debug!(n, store = %store.root().display(), "restored cached artifacts"),
+                Err(err) => warn!(%err, "failed to restore cached artifacts"),
+            }
+        }
+
         // Taking is fine since we don't need these in `compile_with`.
         let files = std::mem::take(&mut self.files);
-        self.compile_with(|| {
+        let output = self.compile_with(|| {
             let sources = if !files.is_empty() {
                 Source::read_all(files)?
             } else {
@@ -160,7 +203,15 @@ pub fn compile<C: Compiler<CompilerContract = Contract>>(
             foundry_compilers::project::ProjectCompiler::with_sources(project, sources)?
                 .compile()
                 .map_err(Into::into)
-        })
+        })?;
+
+        if let Some(store) = &store {
+            if let Err(err) = store.save(project) {
+                warn!(%err, "failed to save artifacts to the cache");
+            }
+        }
+
+        Ok(output)
     }
 
     /// Compiles the project with the given closure
@@ -253,49 +304,8 @@ fn handle_output<C: Compiler<CompilerContract = Contract>>(
                 let _ = sh_println!();
             }
 
-            let mut size_report =
-                SizeReport { report_kind: report_kind(), contracts: BTreeMap::new() };
-
-            let mut artifacts: BTreeMap<String, Vec<_>> = BTreeMap::new();
-            for (id, artifact) in output.artifact_ids().filter(|(id, _)| {
-                // filter out forge-std specific contracts
-                !id.source.to_string_lossy().contains("/forge-std/src/")
-            }) {
-                artifacts.entry(id.name.clone()).or_default().push((id.source.clone(), artifact));
-            }
-
-            for (name, artifact_list) in artifacts {
-                for (path, artifact) in &artifact_list {
-                    let runtime_size = contract_size(*artifact, false).unwrap_or_default();
-                    let init_size = contract_size(*artifact, true).unwrap_or_default();
-
-                    let is_dev_contract = artifact
-                        .abi
-                        .as_ref()
-                        .map(|abi| {
-                            abi.functions().any(|f| {
-                                f.test_function_kind().is_known() ||
-                                    matches!(f.name.as_str(), "IS_TEST" | "IS_SCRIPT")
-                            })
-                        })
-                        .unwrap_or(false);
-
-                    let unique_name = if artifact_list.len() > 1 {
-                        format!(
-                            "{} ({})",
-                            name,
-                            path.strip_prefix(&self.project_root).unwrap_or(path).display()
-                        )
-                    } else {
-                        name.clone()
-                    };
-
-                    size_report.contracts.insert(
-                        unique_name,
-                        ContractInfo { runtime_size, init_size, is_dev_contract },
-                    );
-                }
-            }
+            let mut size_report = SizeReport::new(output, &self.project_root);
+            size_report.baseline = self.size_baseline.clone();
 
             let _ = sh_println!("{size_report}");
 
@@ -309,6 +319,17 @@ fn handle_output<C: Compiler<CompilerContract = Contract>>(
             if !self.ignore_eip_3860 && size_report.exceeds_initcode_size_limit() {
                 std::process::exit(1);
             }
+
+            if let Some(max_increase) = self.max_size_increase {
+                let regressions = size_report.regressions(max_increase);
+                if !regressions.is_empty() {
+                    let _ = sh_err!(
+                        "contract sizes increased by more than {max_increase} bytes: {}",
+                        regressions.join(", ")
+                    );
+                    std::process::exit(1);
+                }
+            }
         }
     }
 }
@@ -325,9 +346,103 @@ pub struct SizeReport {
     report_kind: ReportKind,
     /// `contract name -> info`
     pub contracts: BTreeMap<String, ContractInfo>,
+    /// Previous sizes to compare against, `contract name -> info`
+    pub baseline: Option<BTreeMap<String, ContractInfo>>,
 }
 
 impl SizeReport {
+    /// Creates the size report of all compiled contracts, excluding forge-std.
+    pub fn new<C: Compiler<CompilerContract = Contract>>(
+        output: &ProjectCompileOutput<C>,
+        root: &Path,
+    ) -> Self {
+        let mut contracts = BTreeMap::new();
+
+        let mut artifacts: BTreeMap<String, Vec<_>> = BTreeMap::new();
+        for (id, artifact) in output.artifact_ids().filter(|(id, _)| {
+            // filter out forge-std specific contracts
+            !id.source.to_string_lossy().contains("/forge-std/src/")
+        }) {
+            artifacts.entry(id.name.clone()).or_default().push((id.source.clone(), artifact));
+        }
+
+        for (name, artifact_list) in artifacts {
+            for (path, artifact) in &artifact_list {
+                let runtime_size = contract_size(*artifact, false).unwrap_or_default();
+                let init_size = contract_size(*artifact, true).unwrap_or_default();
+
+                let is_dev_contract = artifact
+                    .abi
+                    .as_ref()
+                    .map(|abi| {
+                        abi.functions().any(|f| {
+                            f.test_function_kind().is_known() ||
+                                matches!(f.name.as_str(), "IS_TEST" | "IS_SCRIPT")
+                        })
+                    })
+                    .unwrap_or(false);
+
+                let unique_name = if artifact_list.len() > 1 {
+                    format!("{} ({})", name, path.strip_prefix(root).unwrap_or(path).display())
+                } else {
+                    name.clone()
+                };
+
+                contracts
+                    .insert(unique_name, ContractInfo { runtime_size, init_size, is_dev_contract });
+            }
+        }
+
+        Self { report_kind: report_kind(), contracts, baseline: None }
+    }
+
+    /// Parses the sizes of a report saved with `forge build --sizes --json`.
+    pub fn parse_json(s: &str) -> Result<BTreeMap<String, ContractInfo>> {
+        #[derive(serde::Deserialize)]
+        struct Sizes {
+            runtime_size: usize,
+            init_size: usize,
+        }
+
+        let sizes: BTreeMap<String, Sizes> = serde_json::from_str(s)?;
+        Ok(sizes
+            .into_iter()
+            .map(|(name, sizes)| {
+                let info = ContractInfo {
+                    runtime_size: sizes.runtime_size,
+                    init_size: sizes.init_size,
+                    is_dev_contract: false,
+                };
+                (name, info)
+            })
+            .collect())
+    }
+
+    /// Returns the names of the contracts whose runtime or initcode size increased by more than
+    /// `max_increase` bytes compared to the baseline, excluding dev contracts.
+    pub fn regressions(&self, max_increase: usize) -> Vec<String> {
+        let Some(baseline) = &self.baseline else { return Vec::new() };
+        self.contracts
+            .iter()
+            .filter(|(_, c)| !c.is_dev_contract)
+            .filter(|(name, c)| {
+                baseline.get(*name).is_some_and(|prev| {
+                    c.runtime_size.saturating_sub(prev.runtime_size) > max_increase ||
+                        c.init_size.saturating_sub(prev.init_size) > max_increase
+                })
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Returns the runtime and initcode size differences of a contract to the baseline.
+    fn diff(&self, name: &str, contract: &ContractInfo) -> Option<(isize, isize)> {
+        let prev = self.baseline.as_ref()?.get(name)?;
+        Some((
+            contract.runtime_size as isize - prev.runtime_size as isize,
+            contract.init_size as isize - prev.init_size as isize,
+        ))
+    }
     /// Returns the maximum runtime code size, excluding dev contracts.
     pub fn max_runtime_size(&self) -> usize {
         self.contracts
@@ -381,15 +496,17 @@ fn format_json_output(&self) -> String {
             .iter()
             .filter(|(_, c)| !c.is_dev_contract && (c.runtime_size > 0 || c.init_size > 0))
             .map(|(name, contract)| {
-                (
-                    name.clone(),
-                    serde_json::json!({
-                        "runtime_size": contract.runtime_size,
-                        "init_size": contract.init_size,
-                        "runtime_margin": CONTRACT_RUNTIME_SIZE_LIMIT as isize - contract.runtime_size as isize,
-                        "init_margin": CONTRACT_INITCODE_SIZE_LIMIT as isize - contract.init_size as isize,
-                    }),
-                )
+                let mut value = serde_json::json!({
+                    "runtime_size": contract.runtime_size,
+                    "init_size": contract.init_size,
+                    "runtime_margin": CONTRACT_RUNTIME_SIZE_LIMIT as isize - contract.runtime_size as isize,
+                    "init_margin": CONTRACT_INITCODE_SIZE_LIMIT as isize - contract.init_size as isize,
+                });
+                if let Some((runtime_diff, init_diff)) = self.diff(name, contract) {
+                    value["runtime_diff"] = runtime_diff.into();
+                    value["init_diff"] = init_diff.into();
+                }
+                (name.clone(), value)
             })
             .collect::<serde_json::Map<_, _>>();
 
@@ -400,13 +517,18 @@ fn format_table_output(&self) -> Table {
         let mut table = Table::new();
         table.apply_modifier(UTF8_ROUND_CORNERS);
 
-        table.set_header(vec![
+        let mut header = vec![
             Cell::new("Contract"),
             Cell::new("Runtime Size (B)"),
             Cell::new("Initcode Size (B)"),
             Cell::new("Runtime Margin (B)"),
             Cell::new("Initcode Margin (B)"),
-        ]);
+        ];
+        if self.baseline.is_some() {
+            header.push(Cell::new("Runtime Diff (B)"));
+            header.push(Cell::new("Initcode Diff (B)"));
+        }
+        table.set_header(header);
 
         // Filters out dev contracts (Test or Script)
         let contracts = self
@@ -431,21 +553,50 @@ fn format_table_output(&self) -> Table {
             };
 
             let locale = &Locale::en;
-            table.add_row([
+            let mut row = vec![
                 Cell::new(name),
                 Cell::new(contract.runtime_size.to_formatted_string(locale)).fg(runtime_color),
                 Cell::new(contract.init_size.to_formatted_string(locale)).fg(init_color),
                 Cell::new(runtime_margin.to_formatted_string(locale)).fg(runtime_color),
                 Cell::new(init_margin.to_formatted_string(locale)).fg(init_color),
-            ]);
+            ];
+            if self.baseline.is_some() {
+                match self.diff(name, contract) {
+                    Some((runtime_diff, init_diff)) => {
+                        row.push(diff_cell(runtime_diff, runtime_margin < 0));
+                        row.push(diff_cell(init_diff, init_margin < 0));
+                    }
+                    None => {
+                        row.push(Cell::new("new"));
+                        row.push(Cell::new("new"));
+                    }
+                }
+            }
+            table.add_row(row);
         }
 
         table
     }
 }
 
+/// Returns the cell of a size difference, highlighting increases and contracts over the limit.
+fn diff_cell(diff: isize, exceeds_limit: bool) -> Cell {
+    let text = match diff {
+        0 => "0".to_string(),
+        diff if diff > 0 => format!("+{}", diff.to_formatted_string(&Locale::en)),
+        diff => diff.to_formatted_string(&Locale::en),
+    };
+    let color = match diff {
+        _ if exceeds_limit && diff > 0 => Color::Red,
+        1.. => Color::Yellow,
+        ..0 => Color::Green,
+        0 => Color::Reset,
+    };
+    Cell::new(text).fg(color)
+}
+
 /// Returns the deployed or init size of the contract.
-fn contract_size<T: Artifact>(artifact: &T, initcode: bool) -> Option<usize> {
+pub fn contract_size<T: Artifact>(artifact: &T, initcode: bool) -> Option<usize> {
     let bytecode = if initcode {
         artifact.get_bytecode_object()?
     } else {
@@ -3,7 +3,7 @@
 #![allow(missing_docs)]
 
 use self::EnsResolver::EnsResolverInstance;
-use alloy_primitives::{address, Address, Keccak256, B256};
+use alloy_primitives::{address, map::HashMap, Address, Bytes, Keccak256, B256};
 use alloy_provider::{Network, Provider};
 use alloy_sol_types::sol;
 use async_trait::async_trait;
@@ -26,6 +26,12 @@
 
         /// Returns the name associated with an ENS node, for reverse records.
         function name(bytes32 node) view returns (string);
+
+        /// Returns the text record of an ENS node for the specified key.
+        function text(bytes32 node, string key) view returns (string);
+
+        /// Returns the content hash associated with an ENS node.
+        function contenthash(bytes32 node) view returns (bytes);
     }
 }
 
@@ -49,6 +55,9 @@ pub enum EnsError {
     /// Failed to resolve ENS name to an address.
     #[error("Failed to resolve ENS name to an address: {0}")]
     Resolve(alloy_contract::Error),
+    /// Failed to get a record of an ENS name.
+    #[error("Failed to get ENS record: {0}")]
+    Record(alloy_contract::Error),
 }
 
 /// ENS name or Ethereum Address.
@@ -142,6 +151,32 @@ async fn lookup_address(&self, address: &Address) -> Result<String, EnsError> {
         let name = resolver.name(node).call().await.map_err(EnsError::Lookup)?._0;
         Ok(name)
     }
+
+    /// Returns the text record of an ENS name for the specified key, e.g. `url` or `avatar`.
+    async fn get_text(&self, name: &str, key: &str) -> Result<String, EnsError> {
+        let node = namehash(name);
+        let resolver = self.get_resolver(node, name).await?;
+        let text = resolver.text(node, key.to_string()).call().await.map_err(EnsError::Record)?._0;
+        Ok(text)
+    }
+
+    /// Returns the raw content hash of an ENS name, see [`decode_contenthash`].
+    async fn get_contenthash(&self, name: &str) -> Result<Bytes, EnsError> {
+        let node = namehash(name);
+        let resolver = self.get_resolver(node, name).await?;
+        let hash = resolver.contenthash(node).call().await.map_err(EnsError::Record)?._0;
+        Ok(hash)
+    }
+
+    /// Returns the primary names of the addresses, i.e. the names of their reverse records which
+    /// resolve back to them.
+    ///
+    /// Addresses without a primary name are skipped, and no lookup is made on chains without the
+    /// ENS registry.
+    async fn lookup_addresses(
+        &self,
+        addresses: Vec<Address>,
+    ) -> Result<HashMap<Address, String>, EnsError>;
 }
 
 #[async_trait]
@@ -162,6 +197,29 @@ async fn get_resolver(
         }
         Ok(EnsResolverInstance::new(address, self))
     }
+
+    async fn lookup_addresses(
+        &self,
+        addresses: Vec<Address>,
+    ) -> Result<HashMap<Address, String>, EnsError> {
+        let mut names = HashMap::default();
+        if !self.get_code_at(ENS_ADDRESS).await.is_ok_and(|code| !code.is_empty()) {
+            return Ok(names);
+        }
+        for address in addresses {
+            if names.contains_key(&address) {
+                continue;
+            }
+            let Ok(name) = self.lookup_address(&address).await else { continue };
+            // the reverse record is set by the owner of the address, so it must be verified
+            let node = namehash(&name);
+            let Ok(resolver) = self.get_resolver(node, &name).await else { continue };
+            if resolver.addr(node).call().await.is_ok_and(|addr| addr._0 == address) {
+                names.insert(address, name);
+            }
+        }
+        Ok(names)
+    }
 }
 
 /// Returns the ENS namehash as specified in [EIP-137](https://eips.ethereum.org/EIPS/eip-137)
@@ -202,6 +260,93 @@ pub fn reverse_address(addr: &Address) -> String {
     format!("{addr:x}.{ENS_REVERSE_REGISTRAR_DOMAIN}")
 }
 
+/// Decodes a content hash as specified in [ENSIP-7](https://docs.ens.domains/ensip/7) into a URI,
+/// e.g. `ipfs://Qm...`.
+///
+/// Returns `None` for unsupported protocols and malformed content hashes.
+pub fn decode_contenthash(hash: &[u8]) -> Option<String> {
+    let (protocol, cid) = read_varint(hash)?;
+    match protocol {
+        // ipfs-ns and ipns-ns, followed by a CID
+        0xe3 | 0xe5 => {
+            let scheme = if protocol == 0xe3 { "ipfs" } else { "ipns" };
+            let (version, rest) = read_varint(cid)?;
+            let (codec, multihash) = read_varint(rest)?;
+            // CIDv1 of dag-pb sha2-256 content is displayed as the CIDv0 `Qm...`
+            if version == 1 &&
+                codec == 0x70 &&
+                multihash.len() == 34 &&
+                multihash[..2] == [0x12, 32]
+            {
+                return Some(format!("{scheme}://{}", base58(multihash)));
+            }
+            (version == 1).then(|| format!("{scheme}://b{}", base32(cid)))
+        }
+        // swarm-ns, followed by a CID of a keccak-256 hash
+        0xe4 => {
+            let hash = cid.get(cid.len().checked_sub(32)?..)?;
+            Some(format!("bzz://{}", alloy_primitives::hex::encode(hash)))
+        }
+        // onion and onion3
+        0x01bc | 0x01bd => Some(format!("onion://{}", std::str::from_utf8(cid).ok()?)),
+        _ => None,
+    }
+}
+
+/// Reads an unsigned varint, returning it with the remaining bytes.
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
+
+/// Encodes bytes in base58 with the Bitcoin alphabet.
+fn base58(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    // little-endian base58 digits
+    let mut digits = Vec::<u8>::new();
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in &mut digits {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    std::iter::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|&digit| ALPHABET[digit as usize] as char))
+        .collect()
+}
+
+/// Encodes bytes in unpadded lowercase base32, as used by CIDv1.
+fn base32(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -251,4 +396,27 @@ fn test_invalid_address() {
             assert!(NameOrAddress::from_str(addr).is_err());
         }
     }
+
+    #[test]
+    fn test_decode_contenthash() {
+        for (hash, expected) in [
+            (
+                "e3010170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f",
+                "ipfs://QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4",
+            ),
+            (
+                "e3010155122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f",
+                "ipfs://bafkreibj6lixxzqtsb45ysdjnupvqkufgdvzqbnvmhw2kf7cfkesy7r7d4",
+            ),
+            (
+                "e40101fa011b20d1de9994b4d039f6548d191eb26786769f580809256b4685ef316805265ea162",
+                "bzz://d1de9994b4d039f6548d191eb26786769f580809256b4685ef316805265ea162",
+            ),
+            ("bc037a71736e6c6e70756a7a6d61766a7a34", "onion://zqsnlnpujzmavjz4"),
+        ] {
+            assert_eq!(decode_contenthash(&hex::decode(hash).unwrap()).unwrap(), expected);
+        }
+        assert_eq!(decode_contenthash(&[]), None);
+        assert_eq!(decode_contenthash(&[0x99, 0x01]), None);
+    }
 }
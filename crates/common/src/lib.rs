@@ -17,6 +17,7 @@
 pub use foundry_common_fmt as fmt;
 
 pub mod abi;
+pub mod artifact_store;
 pub mod calc;
 pub mod compile;
 pub mod constants;
@@ -19,6 +19,7 @@
 const BASE_URL: &str = "https://api.openchain.xyz";
 const SELECTOR_LOOKUP_URL: &str = "https://api.openchain.xyz/signature-database/v1/lookup";
 const SELECTOR_IMPORT_URL: &str = "https://api.openchain.xyz/signature-database/v1/import";
+const SELECTOR_EXPORT_URL: &str = "https://api.openchain.xyz/signature-database/v1/export";
 
 /// The standard request timeout for API requests.
 const REQ_TIMEOUT: Duration = Duration::from_secs(15);
@@ -305,6 +306,12 @@ pub async fn pretty_calldata(
         Ok(possible_info)
     }
 
+    /// Downloads the export of all the signatures known to OpenChain.
+    pub async fn export_signatures(&self) -> eyre::Result<String> {
+        self.ensure_not_spurious()?;
+        Ok(self.get_text(SELECTOR_EXPORT_URL).await?)
+    }
+
     /// uploads selectors to OpenChain using the given data
     pub async fn import_selectors(
         &self,
@@ -29,7 +29,7 @@ fn is_setup(&self) -> bool {
         self.test_function_kind().is_setup()
     }
 
-    /// Returns `true` if this function is a unit, fuzz, or invariant test.
+    /// Returns `true` if this function is a unit, fuzz, invariant or table test.
     fn is_any_test(&self) -> bool {
         self.test_function_kind().is_any_test()
     }
@@ -59,6 +59,11 @@ fn is_invariant_test(&self) -> bool {
         self.test_function_kind().is_invariant_test()
     }
 
+    /// Returns `true` if this function is a table test.
+    fn is_table_test(&self) -> bool {
+        self.test_function_kind().is_table_test()
+    }
+
     /// Returns `true` if this function is an `afterInvariant` function.
     fn is_after_invariant(&self) -> bool {
         self.test_function_kind().is_after_invariant()
@@ -116,6 +121,8 @@ pub enum TestFunctionKind {
     FuzzTest { should_fail: bool },
     /// `invariant*` or `statefulFuzz*`.
     InvariantTest,
+    /// `table*`, run once per entry of the fixtures of its parameters.
+    TableTest,
     /// `afterInvariant`.
     AfterInvariant,
     /// `fixture*`.
@@ -140,6 +147,7 @@ pub fn classify(name: &str, has_inputs: bool) -> Self {
             _ if name.starts_with("invariant") || name.starts_with("statefulFuzz") => {
                 Self::InvariantTest
             }
+            _ if name.starts_with("table") => Self::TableTest,
             _ if name.eq_ignore_ascii_case("setup") => Self::Setup,
             _ if name.eq_ignore_ascii_case("afterinvariant") => Self::AfterInvariant,
             _ if name.starts_with("fixture") => Self::Fixture,
@@ -156,6 +164,7 @@ pub const fn name(&self) -> &'static str {
             Self::FuzzTest { should_fail: false } => "fuzz",
             Self::FuzzTest { should_fail: true } => "fuzz fail",
             Self::InvariantTest => "invariant",
+            Self::TableTest => "table",
             Self::AfterInvariant => "afterInvariant",
             Self::Fixture => "fixture",
             Self::Unknown => "unknown",
@@ -168,10 +177,13 @@ pub const fn is_setup(&self) -> bool {
         matches!(self, Self::Setup)
     }
 
-    /// Returns `true` if this function is a unit, fuzz, or invariant test.
+    /// Returns `true` if this function is a unit, fuzz, invariant or table test.
     #[inline]
     pub const fn is_any_test(&self) -> bool {
-        matches!(self, Self::UnitTest { .. } | Self::FuzzTest { .. } | Self::InvariantTest)
+        matches!(
+            self,
+            Self::UnitTest { .. } | Self::FuzzTest { .. } | Self::InvariantTest | Self::TableTest
+        )
     }
 
     /// Returns `true` if this function is a test that should fail.
@@ -198,6 +210,12 @@ pub const fn is_invariant_test(&self) -> bool {
         matches!(self, Self::InvariantTest)
     }
 
+    /// Returns `true` if this function is a table test.
+    #[inline]
+    pub const fn is_table_test(&self) -> bool {
+        matches!(self, Self::TableTest)
+    }
+
     /// Returns `true` if this function is an `afterInvariant` function.
     #[inline]
     pub const fn is_after_invariant(&self) -> bool {
@@ -1,6 +1,9 @@
 //! Support types for configuring storage caching
 
-use crate::Chain;
+use crate::{
+    resolve::{interpolate, UnresolvedEnvVarError},
+    Chain,
+};
 use number_prefix::NumberPrefix;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{fmt, fmt::Formatter, str::FromStr};
@@ -225,11 +228,79 @@ pub struct ChainCache {
     pub block_explorer: u64,
 }
 
+/// Settings of a remote cache of compiled artifacts, shared between machines.
+///
+/// ```toml
+/// [remote_cache]
+/// url = "gs://my-bucket/foundry"
+/// mode = "read-write"
+/// auth_token = "${CACHE_TOKEN}"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteCacheConfig {
+    /// The location of the cache: an `http(s)://` URL, or a `gs://<bucket>/<prefix>` bucket.
+    pub url: String,
+    /// Whether artifacts are only downloaded from the cache, or also uploaded to it.
+    #[serde(default)]
+    pub mode: RemoteCacheMode,
+    /// The bearer token to authenticate requests with, may contain `${ENV_VAR}` placeholders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<String>,
+}
+
+impl RemoteCacheConfig {
+    /// Returns the auth token with all env var placeholders resolved.
+    pub fn resolved_auth_token(&self) -> Result<Option<String>, UnresolvedEnvVarError> {
+        self.auth_token.as_deref().map(interpolate).transpose()
+    }
+}
+
+/// Access mode of a [`RemoteCacheConfig`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RemoteCacheMode {
+    /// Only download artifacts.
+    #[default]
+    ReadOnly,
+    /// Download artifacts and upload newly compiled ones.
+    ReadWrite,
+}
+
+impl RemoteCacheMode {
+    /// Whether artifacts are uploaded to the cache.
+    pub fn is_writable(&self) -> bool {
+        matches!(self, Self::ReadWrite)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use similar_asserts::assert_eq;
 
+    #[test]
+    fn can_parse_remote_cache_config() {
+        #[derive(Serialize, Deserialize)]
+        pub struct Wrapper {
+            pub remote_cache: RemoteCacheConfig,
+        }
+
+        let s = r#"remote_cache = { url = "gs://bucket/foundry" }"#;
+        let w: Wrapper = toml::from_str(s).unwrap();
+        assert_eq!(
+            w.remote_cache,
+            RemoteCacheConfig {
+                url: "gs://bucket/foundry".to_string(),
+                mode: RemoteCacheMode::ReadOnly,
+                auth_token: None,
+            }
+        );
+
+        let s = r#"remote_cache = { url = "https://cache.example.com", mode = "read-write" }"#;
+        let w: Wrapper = toml::from_str(s).unwrap();
+        assert!(w.remote_cache.mode.is_writable());
+    }
+
     #[test]
     fn can_parse_storage_config() {
         #[derive(Serialize, Deserialize)]
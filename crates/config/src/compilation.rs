@@ -9,6 +9,24 @@
 use semver::VersionReq;
 use serde::{Deserialize, Serialize};
 
+/// The language the contracts of a project are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectLanguage {
+    /// Solidity contracts, the default.
+    #[default]
+    Solidity,
+    /// Pure Yul objects, deployed from Solidity tests and scripts.
+    Yul,
+}
+
+impl ProjectLanguage {
+    /// Whether the project contracts are Yul objects.
+    pub fn is_yul(&self) -> bool {
+        matches!(self, Self::Yul)
+    }
+}
+
 /// Keeps possible overrides for default settings which users may configure to construct additional
 /// settings profile.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
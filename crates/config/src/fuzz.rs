@@ -30,6 +30,22 @@ pub struct FuzzConfig {
     pub show_logs: bool,
     /// Optional timeout (in seconds) for each property test
     pub timeout: Option<u32>,
+    /// Optional `[min, max]` range of seconds by which `block.timestamp` is advanced before each
+    /// fuzzed call, in both fuzz and invariant tests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_range: Option<[u64; 2]>,
+    /// Optional `[min, max]` range of blocks by which `block.number` is advanced before each
+    /// fuzzed call, in both fuzz and invariant tests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_range: Option<[u64; 2]>,
+    /// Optional directory where the inputs which increased coverage are recorded, enabling
+    /// corpus-guided fuzzing: the recorded inputs are mutated in later runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corpus_dir: Option<PathBuf>,
+    /// Optional reference implementation the return data of the fuzz test is compared to, making
+    /// it a differential test.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub differential: Option<FuzzDifferentialConfig>,
 }
 
 impl Default for FuzzConfig {
@@ -44,6 +60,10 @@ fn default() -> Self {
             failure_persist_file: None,
             show_logs: false,
             timeout: None,
+            timestamp_range: None,
+            block_number_range: None,
+            corpus_dir: None,
+            differential: None,
         }
     }
 }
@@ -59,6 +79,19 @@ pub fn new(cache_dir: PathBuf) -> Self {
     }
 }
 
+/// Reference implementation of a differential fuzz test, set either as a command or as a function.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzDifferentialConfig {
+    /// Command run through FFI with the hex encoded arguments of each fuzz case appended, which
+    /// must print the hex encoded expected return data. Requires `ffi = true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ffi: Option<Vec<String>>,
+    /// Name of a function of the test contract taking the same arguments and returning the
+    /// expected return data, e.g. by calling a reference contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+}
+
 /// Contains for fuzz testing
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FuzzDictionaryConfig {
@@ -79,6 +112,9 @@ pub struct FuzzDictionaryConfig {
     /// Once the fuzzer exceeds this limit, it will start evicting random entries
     #[serde(deserialize_with = "crate::deserialize_usize_or_max")]
     pub max_fuzz_dictionary_values: usize,
+    /// The number of recent blocks whose logs, emitted by the accounts touched when fuzzing
+    /// against a fork, are collected. `0` disables the collection of fork logs.
+    pub fork_log_blocks: u64,
 }
 
 impl Default for FuzzDictionaryConfig {
@@ -91,6 +127,7 @@ fn default() -> Self {
             max_fuzz_dictionary_addresses: (300 * 1024 * 1024) / 20,
             // limit this to 200MB
             max_fuzz_dictionary_values: (200 * 1024 * 1024) / 32,
+            fork_log_blocks: 16,
         }
     }
 }
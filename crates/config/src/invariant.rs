@@ -34,6 +34,10 @@ pub struct InvariantConfig {
     pub timeout: Option<u32>,
     /// Display counterexample as solidity calls.
     pub show_solidity: bool,
+    /// Optional file of a recorded invariant failure to replay instead of running a campaign, for
+    /// the invariant named like the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay_file: Option<PathBuf>,
 }
 
 impl Default for InvariantConfig {
@@ -51,6 +55,7 @@ fn default() -> Self {
             show_metrics: false,
             timeout: None,
             show_solidity: false,
+            replay_file: None,
         }
     }
 }
@@ -71,6 +76,7 @@ pub fn new(cache_dir: PathBuf) -> Self {
             show_metrics: false,
             timeout: None,
             show_solidity: false,
+            replay_file: None,
         }
     }
 }
@@ -69,7 +69,7 @@
 pub use resolve::UnresolvedEnvVarError;
 
 pub mod cache;
-use cache::{Cache, ChainCache};
+use cache::{Cache, ChainCache, RemoteCacheConfig};
 
 pub mod fmt;
 pub use fmt::FormatterConfig;
@@ -102,7 +102,7 @@
 use providers::*;
 
 mod fuzz;
-pub use fuzz::{FuzzConfig, FuzzDictionaryConfig};
+pub use fuzz::{FuzzConfig, FuzzDictionaryConfig, FuzzDifferentialConfig};
 
 mod invariant;
 pub use invariant::InvariantConfig;
@@ -119,8 +119,17 @@
 mod bind_json;
 use bind_json::BindJsonConfig;
 
+mod workspace;
+pub use workspace::{WorkspaceConfig, WorkspaceMember};
+
+mod wallets;
+pub use wallets::WalletConfig;
+
+mod verifiers;
+pub use verifiers::VerifierConfig;
+
 mod compilation;
-pub use compilation::{CompilationRestrictions, SettingsOverrides};
+pub use compilation::{CompilationRestrictions, ProjectLanguage, SettingsOverrides};
 
 /// Foundry configuration
 ///
@@ -192,6 +201,12 @@ pub struct Config {
     pub auto_detect_remappings: bool,
     /// library addresses to link
     pub libraries: Vec<String>,
+    /// Addresses of already deployed libraries to link in tests, by chain name or id, in the same
+    /// form as `libraries`.
+    ///
+    /// Libraries which are not pinned for the chain the tests run on are deployed automatically.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub chain_libraries: BTreeMap<String, Vec<String>>,
     /// whether to enable cache
     pub cache: bool,
     /// where the cache is stored if enabled
@@ -202,8 +217,17 @@ pub struct Config {
     pub gas_snapshot_check: bool,
     /// whether to emit gas snapshots to disk
     pub gas_snapshot_emit: bool,
+    /// The tolerated gas deviations of the tests checked with `forge snapshot --check`.
+    ///
+    /// The most specific entry that matches a test applies to it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gas_snapshot_tolerances: Vec<GasSnapshotTolerance>,
+    /// whether to overwrite the golden files of `vm.assertMatchesSnapshot` with the current values
+    pub update_snapshots: bool,
     /// where the broadcast logs are stored
     pub broadcast: PathBuf,
+    /// where the deployment manifests of broadcasted scripts are stored
+    pub deployments: PathBuf,
     /// additional solc allow paths for `--allow-paths`
     pub allow_paths: Vec<PathBuf>,
     /// additional solc include paths for `--include-path`
@@ -282,6 +306,9 @@ pub struct Config {
     /// Multiple etherscan api configs and their aliases
     #[serde(default, skip_serializing_if = "EtherscanConfigs::is_empty")]
     pub etherscan: EtherscanConfigs,
+    /// Verifiers accepting the standard JSON input of contracts, keyed by chain name or ID
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub verifiers: BTreeMap<String, VerifierConfig>,
     /// list of solidity error codes to always silence in the compiler output
     pub ignored_error_codes: Vec<SolidityErrorCode>,
     /// list of file paths to ignore
@@ -312,6 +339,12 @@ pub struct Config {
     pub coverage_pattern_inverse: Option<RegexWrapper>,
     /// Path where last test run failures are recorded.
     pub test_failures_file: PathBuf,
+    /// Path where `forge test --write-timings` records the durations of the tests, to balance
+    /// `forge test --shard`. Meant to be committed, so that every shard sees the same durations.
+    pub test_timings_file: PathBuf,
+    /// Tests whose failures are reported but don't fail the run, as `<test>` or
+    /// `<contract>::<test>`, where `<test>` is the name or the signature of the test function.
+    pub quarantine: Vec<String>,
     /// Max concurrent threads to use.
     pub threads: Option<usize>,
     /// Whether to show test execution progress.
@@ -432,6 +465,11 @@ pub struct Config {
     /// How to treat revert (and require) reason strings.
     #[serde(with = "serde_helpers::display_from_str_opt")]
     pub revert_strings: Option<RevertStrings>,
+    /// The language of the project contracts.
+    ///
+    /// With `yul`, the Yul objects in `src` are compiled into artifacts and a `YulDeployer`
+    /// library is generated to deploy them from tests.
+    pub language: ProjectLanguage,
     /// Whether to compile in sparse mode
     ///
     /// If this option is enabled, only the required contracts/files will be selected to be
@@ -442,12 +480,20 @@ pub struct Config {
     pub build_info: bool,
     /// The path to the `build-info` directory that contains the build info json files.
     pub build_info_path: Option<PathBuf>,
+    /// Whether to keep the artifacts of every compiled source in a content-addressed store, to
+    /// reuse them when switching branches or across worktrees.
+    pub content_cache: bool,
+    /// The path to the content-addressed artifact store, defaults to
+    /// `~/.foundry/cache/artifacts`.
+    pub content_cache_path: Option<PathBuf>,
     /// Configuration for `forge fmt`
     pub fmt: FormatterConfig,
     /// Configuration for `forge doc`
     pub doc: DocConfig,
     /// Configuration for `forge bind-json`
     pub bind_json: BindJsonConfig,
+    /// Configuration of a multi-package workspace.
+    pub workspace: WorkspaceConfig,
     /// Configures the permissions of cheat codes that touch the file system.
     ///
     /// This includes what operations can be executed (read, write)
@@ -464,6 +510,9 @@ pub struct Config {
     /// Address labels
     pub labels: AddressHashMap<String>,
 
+    /// Wallets signing the transactions of addresses in scripts
+    pub wallets: AddressHashMap<WalletConfig>,
+
     /// Whether to enable safety checks for `vm.getCode` and `vm.getDeployedCode` invocations.
     /// If disabled, it is possible to access artifacts which were not recompiled or cached.
     pub unchecked_cheatcode_artifacts: bool,
@@ -483,6 +532,9 @@ pub struct Config {
     /// Soldeer custom configs
     pub soldeer: Option<SoldeerConfig>,
 
+    /// Remote cache of compiled artifacts, see also `content_cache`
+    pub remote_cache: Option<RemoteCacheConfig>,
+
     /// Whether failed assertions should revert.
     ///
     /// Note that this only applies to native (cheatcode) assertions, invoked on Vm contract.
@@ -506,6 +558,11 @@ pub struct Config {
     /// Timeout for transactions in seconds.
     pub transaction_timeout: u64,
 
+    /// Caps of the EIP-1559 priority fee per gas of broadcasted transactions, in wei, keyed by
+    /// chain name or ID.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub priority_fee_caps: BTreeMap<String, u64>,
+
     /// Use EOF-enabled solc for compilation.
     pub eof: bool,
 
@@ -561,10 +618,14 @@ impl Config {
         "fuzz",
         "invariant",
         "labels",
+        "wallets",
+        "verifiers",
         "dependencies",
         "soldeer",
+        "remote_cache",
         "vyper",
         "bind_json",
+        "workspace",
     ];
 
     /// File name of config toml file
@@ -785,6 +846,7 @@ fn p(root: &Path, rem: &Path) -> PathBuf {
         self.script = p(&root, &self.script);
         self.out = p(&root, &self.out);
         self.broadcast = p(&root, &self.broadcast);
+        self.deployments = p(&root, &self.deployments);
         self.cache_path = p(&root, &self.cache_path);
         self.snapshots = p(&root, &self.snapshots);
 
@@ -792,6 +854,10 @@ fn p(root: &Path, rem: &Path) -> PathBuf {
             self.build_info_path = Some(p(&root, &build_info_path));
         }
 
+        if let Some(content_cache_path) = self.content_cache_path {
+            self.content_cache_path = Some(p(&root, &content_cache_path));
+        }
+
         self.libs = self.libs.into_iter().map(|lib| p(&root, &lib)).collect();
 
         self.remappings =
@@ -1417,6 +1483,31 @@ pub fn get_etherscan_api_key(&self, chain: Option<Chain>) -> Option<String> {
         self.get_etherscan_config_with_chain(chain).ok().flatten().map(|c| c.key)
     }
 
+    /// Returns the cap of the priority fee per gas of transactions broadcasted to `chain` in
+    /// `priority_fee_caps`, if any.
+    pub fn priority_fee_cap(&self, chain: Chain) -> eyre::Result<Option<u64>> {
+        for (key, cap) in &self.priority_fee_caps {
+            let key = Chain::from_str(key)
+                .map_err(|_| eyre::eyre!("invalid chain `{key}` in `priority_fee_caps`"))?;
+            if key.id() == chain.id() {
+                return Ok(Some(*cap));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the verifier of `chain` in `verifiers`, if any.
+    pub fn verifier(&self, chain: Chain) -> eyre::Result<Option<&VerifierConfig>> {
+        for (key, verifier) in &self.verifiers {
+            let key = Chain::from_str(key)
+                .map_err(|_| eyre::eyre!("invalid chain `{key}` in `verifiers`"))?;
+            if key.id() == chain.id() {
+                return Ok(Some(verifier));
+            }
+        }
+        Ok(None)
+    }
+
     /// Returns the remapping for the project's _src_ directory
     ///
     /// **Note:** this will add an additional `<src>/=<src path>` remapping here so imports that
@@ -1489,6 +1580,22 @@ pub fn libraries_with_remappings(&self) -> Result<Libraries, SolcError> {
         Ok(self.parsed_libraries()?.apply(|libs| paths.apply_lib_remappings(libs)))
     }
 
+    /// Returns the libraries pinned to the chain `chain_id` in `chain_libraries`, with applied
+    /// remappings.
+    pub fn chain_libraries_with_remappings(&self, chain_id: u64) -> Result<Libraries, SolcError> {
+        let mut libraries = Vec::new();
+        for (chain, libs) in &self.chain_libraries {
+            let chain = Chain::from_str(chain).map_err(|_| {
+                SolcError::msg(format!("invalid chain `{chain}` in `chain_libraries`"))
+            })?;
+            if chain.id() == chain_id {
+                libraries.extend(libs.iter().cloned());
+            }
+        }
+        let paths: ProjectPathsConfig = self.project_paths();
+        Ok(Libraries::parse(&libraries)?.apply(|libs| paths.apply_lib_remappings(libs)))
+    }
+
     /// Returns the configured `solc` `Settings` that includes:
     /// - all libraries
     /// - the optimizer (including details, if configured)
@@ -1773,6 +1880,30 @@ pub fn to_string_pretty(&self) -> Result<String, toml::ser::Error> {
         toml::to_string_pretty(&toml::Value::Table(wrapping_table))
     }
 
+    /// Returns the config of the given workspace `member`.
+    ///
+    /// If a profile named after the member exists, it is selected from `figment`. Otherwise the
+    /// `src`, `test` and `script` directories of the member are used, and artifacts and cache are
+    /// written to `<out>/<member>` and `<cache_path>/<member>` respectively.
+    pub fn for_workspace_member(
+        &self,
+        figment: Figment,
+        member: &WorkspaceMember,
+    ) -> Result<Self, ExtractConfigError> {
+        let profile = Profile::new(&member.name);
+        if self.profiles.contains(&profile) {
+            return Self::from_figment(figment.select(profile)).map(Self::sanitized);
+        }
+
+        let mut config = self.clone();
+        config.src = member.root.join("src");
+        config.test = member.root.join("test");
+        config.script = member.root.join("script");
+        config.out = self.out.join(&member.name);
+        config.cache_path = self.cache_path.join(&member.name);
+        Ok(config)
+    }
+
     /// Returns the path to the `foundry.toml` of this `Config`.
     pub fn get_config_path(&self) -> PathBuf {
         self.root.join(Self::FILE_NAME)
@@ -2282,9 +2413,12 @@ fn default() -> Self {
             cache: true,
             cache_path: "cache".into(),
             broadcast: "broadcast".into(),
+            deployments: "deployments".into(),
             snapshots: "snapshots".into(),
             gas_snapshot_check: false,
             gas_snapshot_emit: true,
+            gas_snapshot_tolerances: vec![],
+            update_snapshots: false,
             allow_paths: vec![],
             include_paths: vec![],
             force: false,
@@ -2312,6 +2446,8 @@ fn default() -> Self {
             path_pattern_inverse: None,
             coverage_pattern_inverse: None,
             test_failures_file: "cache/test-failures".into(),
+            test_timings_file: "test-timings.json".into(),
+            quarantine: vec![],
             threads: None,
             show_progress: false,
             fuzz: FuzzConfig::new("cache/fuzz".into()),
@@ -2346,6 +2482,7 @@ fn default() -> Self {
             remappings: vec![],
             auto_detect_remappings: true,
             libraries: vec![],
+            chain_libraries: Default::default(),
             ignored_error_codes: vec![
                 SolidityErrorCode::SpdxLicenseNotProvided,
                 SolidityErrorCode::ContractExceeds24576Bytes,
@@ -2359,25 +2496,32 @@ fn default() -> Self {
             rpc_storage_caching: Default::default(),
             rpc_endpoints: Default::default(),
             etherscan: Default::default(),
+            verifiers: Default::default(),
             no_storage_caching: false,
             no_rpc_rate_limit: false,
             use_literal_content: false,
             bytecode_hash: BytecodeHash::Ipfs,
             cbor_metadata: true,
             revert_strings: None,
+            language: ProjectLanguage::Solidity,
             sparse_mode: false,
             build_info: false,
             build_info_path: None,
+            content_cache: false,
+            content_cache_path: None,
             fmt: Default::default(),
             doc: Default::default(),
             bind_json: Default::default(),
+            workspace: Default::default(),
             labels: Default::default(),
+            wallets: Default::default(),
             unchecked_cheatcode_artifacts: false,
             create2_library_salt: Self::DEFAULT_CREATE2_LIBRARY_SALT,
             create2_deployer: Self::DEFAULT_CREATE2_DEPLOYER,
             skip: vec![],
             dependencies: Default::default(),
             soldeer: Default::default(),
+            remote_cache: None,
             assertions_revert: true,
             legacy_assertions: false,
             warnings: vec![],
@@ -2385,6 +2529,7 @@ fn default() -> Self {
             eof_version: None,
             odyssey: false,
             transaction_timeout: 120,
+            priority_fee_caps: Default::default(),
             additional_compiler_profiles: Default::default(),
             compilation_restrictions: Default::default(),
             eof: false,
@@ -2393,6 +2538,49 @@ fn default() -> Self {
     }
 }
 
+/// A tolerated gas deviation of the tests checked with `forge snapshot --check`.
+///
+/// A deviation is tolerated if it is within either the absolute or the percentage tolerance.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GasSnapshotTolerance {
+    /// The name of the test contract the tolerance applies to, all contracts if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+    /// The name or signature of the test the tolerance applies to, all tests if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test: Option<String>,
+    /// The tolerated deviation, in gas.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub absolute: Option<u64>,
+    /// The tolerated deviation, in percent of the larger gas value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent: Option<f64>,
+}
+
+impl GasSnapshotTolerance {
+    /// Returns whether the tolerance applies to the test `signature` of `contract`.
+    pub fn matches(&self, contract: &str, signature: &str) -> bool {
+        let name = signature.split('(').next().unwrap_or(signature);
+        self.contract.as_deref().is_none_or(|c| c == contract) &&
+            self.test.as_deref().is_none_or(|t| t == name || t == signature)
+    }
+
+    /// Returns the specificity of the tolerance: test tolerances take precedence over contract
+    /// tolerances, which take precedence over global ones.
+    pub fn specificity(&self) -> u8 {
+        (u8::from(self.test.is_some()) << 1) | u8::from(self.contract.is_some())
+    }
+
+    /// Returns whether the deviation between the two gas values is tolerated.
+    pub fn allows(&self, a: u64, b: u64) -> bool {
+        let diff = a.abs_diff(b);
+        let percent = if diff == 0 { 0. } else { diff as f64 / a.max(b) as f64 * 100. };
+        self.absolute.is_some_and(|absolute| diff <= absolute) ||
+            self.percent.is_some_and(|max| percent <= max) ||
+            diff == 0
+    }
+}
+
 /// Wrapper for the config's `gas_limit` value necessary because toml-rs can't handle larger number
 /// because integers are stored signed: <https://github.com/alexcrichton/toml-rs/issues/256>
 ///
@@ -4185,6 +4373,99 @@ fn test_parse_many_libraries() {
         });
     }
 
+    #[test]
+    fn can_parse_gas_snapshot_tolerances() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default]
+                gas_snapshot_tolerances = [
+                    { percent = 0.5 },
+                    { contract = "CounterTest", test = "test_Increment", absolute = 100 },
+                ]
+            "#,
+            )?;
+            let config = Config::load().unwrap();
+            assert_eq!(
+                config.gas_snapshot_tolerances,
+                vec![
+                    GasSnapshotTolerance { percent: Some(0.5), ..Default::default() },
+                    GasSnapshotTolerance {
+                        contract: Some("CounterTest".to_string()),
+                        test: Some("test_Increment".to_string()),
+                        absolute: Some(100),
+                        percent: None,
+                    },
+                ]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn can_parse_chain_libraries() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default.chain_libraries]
+                mainnet = ["src/Math.sol:Math:0x902f6cf364b8d9470d5793a9b2b2e86bddd21e0c"]
+                10 = ["src/Math.sol:Math:0xffedba5e171c4f15abaaabc86e8bd01f9b54dae5"]
+            "#,
+            )?;
+            let config = Config::load().unwrap();
+            assert_eq!(config.chain_libraries.len(), 2);
+
+            let addresses = |chain_id| {
+                config
+                    .chain_libraries_with_remappings(chain_id)
+                    .unwrap()
+                    .libs
+                    .into_values()
+                    .flat_map(|libs| libs.into_iter())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                addresses(1),
+                vec![(
+                    "Math".to_string(),
+                    "0x902f6cf364b8d9470d5793a9b2b2e86bddd21e0c".to_string()
+                )]
+            );
+            assert_eq!(
+                addresses(10),
+                vec![(
+                    "Math".to_string(),
+                    "0xffedba5e171c4f15abaaabc86e8bd01f9b54dae5".to_string()
+                )]
+            );
+            assert!(addresses(31337).is_empty());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn can_parse_priority_fee_caps() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default.priority_fee_caps]
+                mainnet = 2000000000
+                10 = 1000000
+            "#,
+            )?;
+            let config = Config::load().unwrap();
+            assert_eq!(config.priority_fee_cap(Chain::mainnet()).unwrap(), Some(2000000000));
+            assert_eq!(config.priority_fee_cap(Chain::from_id(10)).unwrap(), Some(1000000));
+            assert_eq!(config.priority_fee_cap(Chain::from_id(31337)).unwrap(), None);
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn config_roundtrip() {
         figment::Jail::expect_with(|jail| {
@@ -4809,6 +5090,71 @@ fn test_parse_labels() {
         });
     }
 
+    #[test]
+    fn test_parse_wallets() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [wallets]
+                0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266 = { ledger = true, mnemonic_index = 1 }
+                0x70997970C51812dc3A010C7d01b50e0d17dc79C8 = { account = "funder" }
+            "#,
+            )?;
+
+            let config = Config::load().unwrap();
+            assert_eq!(
+                config.wallets,
+                AddressHashMap::from_iter(vec![
+                    (
+                        Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap(),
+                        WalletConfig {
+                            ledger: true,
+                            mnemonic_index: Some(1),
+                            ..Default::default()
+                        }
+                    ),
+                    (
+                        Address::from_str("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap(),
+                        WalletConfig { account: Some("funder".to_string()), ..Default::default() }
+                    ),
+                ])
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_parse_verifiers() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("EXPLORER_KEY", "secret");
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [verifiers]
+                mainnet = { url = "https://verifier.example.com" }
+                12345 = { url = "https://explorer.example.com/api/verify", key = "${EXPLORER_KEY}" }
+            "#,
+            )?;
+
+            let config = Config::load().unwrap();
+            assert_eq!(
+                config.verifier(Chain::mainnet()).unwrap(),
+                Some(&VerifierConfig {
+                    url: "https://verifier.example.com".to_string(),
+                    key: None
+                })
+            );
+            let verifier = config.verifier(Chain::from_id(12345)).unwrap().unwrap();
+            assert_eq!(verifier.url, "https://explorer.example.com/api/verify");
+            assert_eq!(verifier.resolved_key().unwrap(), Some("secret".to_string()));
+            assert_eq!(config.verifier(Chain::from_id(10)).unwrap(), None);
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_parse_vyper() {
         figment::Jail::expect_with(|jail| {
@@ -4869,4 +5215,31 @@ fn test_parse_soldeer() {
             Ok(())
         });
     }
+
+    #[test]
+    fn test_parse_remote_cache() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default]
+                content_cache = true
+
+                [remote_cache]
+                url = "gs://bucket/foundry"
+                mode = "read-write"
+                auth_token = "${CACHE_TOKEN}"
+            "#,
+            )?;
+            jail.set_env("CACHE_TOKEN", "secret");
+
+            let config = Config::load().unwrap();
+            let remote = config.remote_cache.unwrap();
+            assert_eq!(remote.url, "gs://bucket/foundry");
+            assert_eq!(remote.mode, cache::RemoteCacheMode::ReadWrite);
+            assert_eq!(remote.resolved_auth_token().unwrap(), Some("secret".to_string()));
+
+            Ok(())
+        });
+    }
 }
@@ -0,0 +1,28 @@
+//! Configuration of the contract verifiers of chains with custom explorers.
+
+use crate::resolve::{interpolate, UnresolvedEnvVarError};
+use serde::{Deserialize, Serialize};
+
+/// A verifier accepting the standard JSON input of contracts, configured for a chain in the
+/// `[verifiers]` table.
+///
+/// ```toml
+/// [verifiers]
+/// 12345 = { url = "https://explorer.example.com/api/verify", key = "${EXPLORER_KEY}" }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerifierConfig {
+    /// The endpoint the verification requests are posted to.
+    pub url: String,
+    /// The bearer token to authenticate requests with, may contain `${ENV_VAR}` placeholders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+impl VerifierConfig {
+    /// Returns the key with all env var placeholders resolved.
+    pub fn resolved_key(&self) -> Result<Option<String>, UnresolvedEnvVarError> {
+        self.key.as_deref().map(interpolate).transpose()
+    }
+}
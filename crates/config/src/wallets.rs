@@ -0,0 +1,38 @@
+//! Configuration of the wallets signing the transactions of scripts.
+
+use serde::{Deserialize, Serialize};
+
+/// The wallet signing the transactions of an address, configured in the `[wallets]` table.
+///
+/// This allows a script to broadcast from multiple addresses using different wallet backends:
+///
+/// ```toml
+/// [wallets]
+/// "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266" = { ledger = true, mnemonic_index = 1 }
+/// "0x70997970C51812dc3A010C7d01b50e0d17dc79C8" = { account = "funder" }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WalletConfig {
+    /// Use a Ledger hardware wallet.
+    #[serde(default)]
+    pub ledger: bool,
+    /// Use a Trezor hardware wallet.
+    #[serde(default)]
+    pub trezor: bool,
+    /// The derivation path of the hardware wallet account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hd_path: Option<String>,
+    /// The index of the hardware wallet account, if `hd_path` is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mnemonic_index: Option<u32>,
+    /// The path of an encrypted keystore.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystore: Option<String>,
+    /// The name of an encrypted keystore in `~/.foundry/keystores`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// The file containing the password of the keystore. The password is prompted for if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_file: Option<String>,
+}
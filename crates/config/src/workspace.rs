@@ -0,0 +1,74 @@
+//! Configuration for multi-package workspaces.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Contains the config of a workspace: `[workspace]`.
+///
+/// A workspace groups multiple packages, each with their own `src`, `test` and `script`
+/// directories, under a single root `foundry.toml` and shared `lib` directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    /// Glob patterns, relative to the project root, matching the package directories of the
+    /// workspace, e.g. `["packages/*"]`.
+    pub members: Vec<String>,
+}
+
+/// A member package of a workspace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// The name of the package, which is the name of its directory.
+    ///
+    /// If a profile with the same name exists, it is used to configure the package.
+    pub name: String,
+    /// The absolute path to the package directory.
+    pub root: PathBuf,
+}
+
+impl WorkspaceConfig {
+    /// Returns `true` if the project is not a workspace.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Resolves all member packages relative to `root`, sorted by name.
+    pub fn resolve_members(&self, root: &Path) -> Vec<WorkspaceMember> {
+        let mut members = Vec::new();
+        for pattern in &self.members {
+            let pattern = root.join(pattern);
+            let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+                warn!(?pattern, "invalid workspace member pattern");
+                continue;
+            };
+            for path in paths.filter_map(Result::ok).filter(|p| p.is_dir()) {
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+                if members.iter().all(|m: &WorkspaceMember| m.root != path) {
+                    members.push(WorkspaceMember { name: name.to_string(), root: path.clone() });
+                }
+            }
+        }
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+        members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_members() {
+        let root = tempfile::tempdir().unwrap();
+        for dir in ["packages/vault", "packages/token", "tools/deployer"] {
+            std::fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        std::fs::write(root.path().join("packages/README.md"), "").unwrap();
+
+        let config =
+            WorkspaceConfig { members: vec!["packages/*".into(), "tools/deployer".into()] };
+        let members = config.resolve_members(root.path());
+        let names = members.iter().map(|m| m.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["deployer", "token", "vault"]);
+        assert_eq!(members[1].root, root.path().join("packages/token"));
+    }
+}
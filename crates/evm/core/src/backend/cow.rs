@@ -209,6 +209,10 @@ fn active_fork_url(&self) -> Option<String> {
         self.backend.active_fork_url()
     }
 
+    fn sync_fork_timestamps(&mut self, timestamp: U256) -> eyre::Result<()> {
+        self.backend.to_mut().sync_fork_timestamps(timestamp)
+    }
+
     fn ensure_fork(&self, id: Option<LocalForkId>) -> eyre::Result<LocalForkId> {
         self.backend.ensure_fork(id)
     }
@@ -218,6 +218,9 @@ fn transact_from_tx(
     /// Returns the Fork url that's currently used in the database, if fork mode is on
     fn active_fork_url(&self) -> Option<String>;
 
+    /// Sets the `block.timestamp` of all the forks, except the active one, to `timestamp`.
+    fn sync_fork_timestamps(&mut self, timestamp: U256) -> eyre::Result<()>;
+
     /// Whether the database is currently in forked mode.
     fn is_forked_mode(&self) -> bool {
         self.active_fork_id().is_some()
@@ -1305,6 +1308,19 @@ fn active_fork_url(&self) -> Option<String> {
         self.forks.get_fork_url(fork.clone()).ok()?
     }
 
+    fn sync_fork_timestamps(&mut self, timestamp: U256) -> eyre::Result<()> {
+        let active_fork_id = self.active_fork_id();
+        for (id, fork_id) in &self.inner.issued_local_fork_ids {
+            if Some(*id) == active_fork_id {
+                continue;
+            }
+            if let Some(env) = self.forks.get_env(fork_id.clone())? {
+                self.forks.update_block(fork_id.clone(), env.block.number, timestamp)?;
+            }
+        }
+        Ok(())
+    }
+
     fn ensure_fork(&self, id: Option<LocalForkId>) -> eyre::Result<LocalForkId> {
         if let Some(id) = id {
             if self.inner.issued_local_fork_ids.contains_key(&id) {
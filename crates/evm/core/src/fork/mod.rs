@@ -1,5 +1,10 @@
 use super::opts::EvmOpts;
+use crate::backend::BlockchainDbMeta;
+use alloy_primitives::{hex, keccak256, U256};
+use eyre::WrapErr;
 use revm::primitives::Env;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
 
 mod init;
 pub use init::environment;
@@ -21,3 +26,61 @@ pub struct CreateFork {
     /// All env settings as configured by the user
     pub evm_opts: EvmOpts,
 }
+
+/// Records the state fetched by forks into local snapshot files, or replays forks from these files
+/// without connecting to their endpoints.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkSnapshots {
+    /// The directory containing the snapshot files.
+    pub dir: PathBuf,
+    /// Whether to replay forks from the snapshots instead of recording them.
+    pub replay: bool,
+}
+
+impl ForkSnapshots {
+    /// Returns the path of the snapshot file for the fork of `url`, pinned at `block` if any.
+    ///
+    /// The URL is hashed so that API keys don't end up in file names.
+    pub fn path(&self, url: &str, block: Option<u64>) -> PathBuf {
+        let block = block.map_or_else(|| "latest".to_string(), |block| block.to_string());
+        let hash = keccak256(url);
+        self.dir.join(format!("{block}-{}.json", hex::encode(&hash[..8])))
+    }
+
+    /// Returns the environment of the fork recorded in the snapshot file at `path`.
+    pub fn load_env(path: &Path, evm_opts: &EvmOpts) -> eyre::Result<Env> {
+        #[derive(Deserialize)]
+        struct Snapshot {
+            meta: BlockchainDbMeta,
+        }
+
+        let Snapshot { meta } = foundry_common::fs::read_json_file(path)
+            .wrap_err("failed to load fork snapshot, record it first with `--fork-record`")?;
+        let mut env = Env { cfg: meta.cfg_env, block: meta.block_env, ..Default::default() };
+        env.tx.caller = evm_opts.sender;
+        env.tx.gas_price = evm_opts.env.gas_price.map(U256::from).unwrap_or(env.block.basefee);
+        env.tx.chain_id = Some(env.cfg.chain_id);
+        env.tx.gas_limit = env.block.gas_limit.saturating_to();
+        Ok(env)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_path() {
+        let snapshots = ForkSnapshots { dir: PathBuf::from("cache/fork"), replay: false };
+        let path = snapshots.path("https://eth.llamarpc.com", Some(21_000_000));
+        assert!(path.starts_with("cache/fork"));
+        assert!(path.file_name().unwrap().to_str().unwrap().starts_with("21000000-"));
+        assert_eq!(path, snapshots.path("https://eth.llamarpc.com", Some(21_000_000)));
+        assert_ne!(path, snapshots.path("https://rpc.ankr.com/eth", Some(21_000_000)));
+        assert!(snapshots
+            .path("https://eth.llamarpc.com", None)
+            .to_str()
+            .unwrap()
+            .contains("latest-"));
+    }
+}
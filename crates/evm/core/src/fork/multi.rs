@@ -516,23 +516,38 @@ async fn create_fork(mut fork: CreateFork) -> eyre::Result<(ForkId, CreatedFork,
             .build()?,
     );
 
+    // Forks recorded into or replayed from snapshots use the snapshot file as their cache.
+    let snapshot = fork.evm_opts.fork_snapshots.as_ref().map(|snapshots| {
+        (snapshots.path(&fork.url, fork.evm_opts.fork_block_number), snapshots.replay)
+    });
+
     // Initialise the fork environment.
-    let (env, block) = fork.evm_opts.fork_evm_env(&fork.url).await?;
-    fork.env = env;
+    let number = if let Some(env) = fork.evm_opts.replay_fork_env(&fork.url) {
+        fork.env = env?;
+        fork.env.block.number.to()
+    } else {
+        let (env, block) = fork.evm_opts.fork_evm_env(&fork.url).await?;
+        fork.env = env;
+        // We need to use the block number from the block because the env's number can be
+        // different on some L2s (e.g. Arbitrum).
+        block.header().number()
+    };
     let meta = BlockchainDbMeta::new(fork.env.clone(), fork.url.clone());
 
-    // We need to use the block number from the block because the env's number can be different on
-    // some L2s (e.g. Arbitrum).
-    let number = block.header().number();
-
     // Determine the cache path if caching is enabled.
-    let cache_path = if fork.enable_caching {
+    let cache_path = if let Some((path, _)) = &snapshot {
+        Some(path.clone())
+    } else if fork.enable_caching {
         Config::foundry_block_cache_dir(meta.cfg_env.chain_id, number)
     } else {
         None
     };
 
-    let db = BlockchainDb::new(meta, cache_path);
+    let db = if snapshot.is_some_and(|(_, replay)| replay) {
+        BlockchainDb::new_skip_check(meta, cache_path)
+    } else {
+        BlockchainDb::new(meta, cache_path)
+    };
     let (backend, handler) = SharedBackend::new(provider, db, Some(number.into()));
     let fork = CreatedFork::new(fork, backend);
     let fork_id = ForkId::new(&fork.opts.url, number.into());
@@ -1,5 +1,8 @@
 use super::fork::environment;
-use crate::{constants::DEFAULT_CREATE2_DEPLOYER, fork::CreateFork};
+use crate::{
+    constants::DEFAULT_CREATE2_DEPLOYER,
+    fork::{CreateFork, ForkSnapshots},
+};
 use alloy_primitives::{Address, B256, U256};
 use alloy_provider::{network::AnyRpcBlock, Provider};
 use eyre::WrapErr;
@@ -43,6 +46,10 @@ pub struct EvmOpts {
     /// Disables storage caching entirely.
     pub no_storage_caching: bool,
 
+    /// Records forks into, or replays them from, local snapshot files.
+    #[serde(skip)]
+    pub fork_snapshots: Option<ForkSnapshots>,
+
     /// The initial balance of each deployed test contract.
     pub initial_balance: U256,
 
@@ -87,6 +94,7 @@ fn default() -> Self {
             compute_units_per_second: None,
             no_rpc_rate_limit: false,
             no_storage_caching: false,
+            fork_snapshots: None,
             initial_balance: U256::default(),
             sender: Address::default(),
             ffi: false,
@@ -108,6 +116,9 @@ impl EvmOpts {
     /// id, )
     pub async fn evm_env(&self) -> eyre::Result<revm::primitives::Env> {
         if let Some(ref fork_url) = self.fork_url {
+            if let Some(env) = self.replay_fork_env(fork_url) {
+                return env;
+            }
             Ok(self.fork_evm_env(fork_url).await?.0)
         } else {
             Ok(self.local_evm_env())
@@ -144,6 +155,14 @@ pub async fn fork_evm_env(
         })
     }
 
+    /// Returns the `revm::Env` of the fork of `fork_url` recorded in its snapshot file, if forks
+    /// are replayed from snapshots.
+    pub fn replay_fork_env(&self, fork_url: &str) -> Option<eyre::Result<revm::primitives::Env>> {
+        let snapshots = self.fork_snapshots.as_ref().filter(|snapshots| snapshots.replay)?;
+        let path = snapshots.path(fork_url, self.fork_block_number);
+        Some(ForkSnapshots::load_env(&path, self))
+    }
+
     /// Returns the `revm::Env` configured with only local settings
     pub fn local_evm_env(&self) -> revm::primitives::Env {
         let mut cfg = CfgEnv::default();
@@ -230,6 +249,10 @@ pub fn get_compute_units_per_second(&self) -> u64 {
     /// Returns the chain ID from the RPC, if any.
     pub async fn get_remote_chain_id(&self) -> Option<Chain> {
         if let Some(ref url) = self.fork_url {
+            if let Some(env) = self.replay_fork_env(url) {
+                return env.ok().map(|env| Chain::from(env.cfg.chain_id));
+            }
+
             trace!(?url, "retrieving chain via eth_chainId");
             let provider = ProviderBuilder::new(url.as_str())
                 .compute_units_per_second(self.get_compute_units_per_second())
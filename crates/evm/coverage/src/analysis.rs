@@ -82,9 +82,14 @@ fn visit_modifier_or_yul_fn_definition(&mut self, node: &Node) -> eyre::Result<(
         let name: String =
             node.attribute("name").ok_or_else(|| eyre::eyre!("Modifier has no name"))?;
 
+        let kind = if matches!(node.node_type, NodeType::ModifierDefinition) {
+            CoverageItemKind::Modifier { name }
+        } else {
+            CoverageItemKind::Function { name }
+        };
         match &node.body {
             Some(body) => {
-                self.push_item_kind(CoverageItemKind::Function { name }, &node.src);
+                self.push_item_kind(kind, &node.src);
                 self.visit_block(body)
             }
             _ => Ok(()),
@@ -411,26 +416,18 @@ fn visit_expression(&mut self, node: &Node) -> eyre::Result<()> {
 
                     let expr: Option<Node> = node.attribute("expression");
                     if let Some(NodeType::Identifier) = expr.as_ref().map(|expr| &expr.node_type) {
-                        // Might be a require call, add branch coverage.
-                        // Asserts should not be considered branches: <https://github.com/foundry-rs/foundry/issues/9460>.
+                        // Might be a require or assert call, add condition coverage.
+                        // These are not considered branches: <https://github.com/foundry-rs/foundry/issues/9460>.
                         let name: Option<String> = expr.and_then(|expr| expr.attribute("name"));
-                        if let Some("require") = name.as_deref() {
+                        if let Some("require" | "assert") = name.as_deref() {
                             let branch_id = self.branch_id;
                             self.branch_id += 1;
                             self.push_item_kind(
-                                CoverageItemKind::Branch {
-                                    branch_id,
-                                    path_id: 0,
-                                    is_first_opcode: false,
-                                },
+                                CoverageItemKind::Condition { branch_id, path_id: 0 },
                                 &node.src,
                             );
                             self.push_item_kind(
-                                CoverageItemKind::Branch {
-                                    branch_id,
-                                    path_id: 1,
-                                    is_first_opcode: false,
-                                },
+                                CoverageItemKind::Condition { branch_id, path_id: 1 },
                                 &node.src,
                             );
                         }
@@ -21,7 +21,8 @@ pub fn find_anchors(
         .flat_map(|source| analysis.items_for_source_enumerated(source))
         .filter_map(|(item_id, item)| {
             match item.kind {
-                CoverageItemKind::Branch { path_id, is_first_opcode: false, .. } => {
+                CoverageItemKind::Branch { path_id, is_first_opcode: false, .. } |
+                CoverageItemKind::Condition { path_id, .. } => {
                     find_anchor_branch(bytecode, source_map, item_id, &item.loc).map(|anchors| {
                         match path_id {
                             0 => anchors.0,
@@ -328,6 +328,23 @@ pub enum CoverageItemKind {
         /// The name of the function.
         name: String,
     },
+    /// A `require` or `assert` condition in the code.
+    ///
+    /// Conditions are reported separately from branches, since their failing path is often not
+    /// meant to be reachable.
+    Condition {
+        /// The ID that identifies the condition, shared with the branch IDs.
+        branch_id: u32,
+        /// The path ID for this condition.
+        ///
+        /// The failing path has ID 0 and the passing path has ID 1.
+        path_id: u32,
+    },
+    /// A modifier in the code.
+    Modifier {
+        /// The name of the modifier.
+        name: String,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -355,6 +372,12 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             CoverageItemKind::Function { name } => {
                 write!(f, r#"Function "{name}""#)?;
             }
+            CoverageItemKind::Condition { branch_id, path_id } => {
+                write!(f, "Condition (branch: {branch_id}, path: {path_id})")?;
+            }
+            CoverageItemKind::Modifier { name } => {
+                write!(f, r#"Modifier "{name}""#)?;
+            }
         }
         write!(f, " (location: {}, hits: {})", self.loc, self.hits)
     }
@@ -410,6 +433,14 @@ pub struct CoverageSummary {
     pub function_count: usize,
     /// The number of functions hit.
     pub function_hits: usize,
+    /// The number of `require` and `assert` condition paths in the source file.
+    pub condition_count: usize,
+    /// The number of condition paths that were hit.
+    pub condition_hits: usize,
+    /// The number of modifiers in the source file.
+    pub modifier_count: usize,
+    /// The number of modifiers hit.
+    pub modifier_hits: usize,
 }
 
 impl CoverageSummary {
@@ -436,6 +467,10 @@ pub fn merge(&mut self, other: &Self) {
             branch_hits,
             function_count,
             function_hits,
+            condition_count,
+            condition_hits,
+            modifier_count,
+            modifier_hits,
         } = self;
         *line_count += other.line_count;
         *line_hits += other.line_hits;
@@ -445,6 +480,10 @@ pub fn merge(&mut self, other: &Self) {
         *branch_hits += other.branch_hits;
         *function_count += other.function_count;
         *function_hits += other.function_hits;
+        *condition_count += other.condition_count;
+        *condition_hits += other.condition_hits;
+        *modifier_count += other.modifier_count;
+        *modifier_hits += other.modifier_hits;
     }
 
     /// Adds a coverage item to this summary.
@@ -474,6 +513,18 @@ pub fn add_item(&mut self, item: &CoverageItem) {
                     self.function_hits += 1;
                 }
             }
+            CoverageItemKind::Condition { .. } => {
+                self.condition_count += 1;
+                if item.hits > 0 {
+                    self.condition_hits += 1;
+                }
+            }
+            CoverageItemKind::Modifier { .. } => {
+                self.modifier_count += 1;
+                if item.hits > 0 {
+                    self.modifier_hits += 1;
+                }
+            }
         }
     }
 
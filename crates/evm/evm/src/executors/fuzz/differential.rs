@@ -0,0 +1,125 @@
+use crate::executors::Executor;
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::Function;
+use alloy_primitives::{hex, Address, Bytes, U256};
+use foundry_common::fmt::format_tokens;
+use foundry_evm_core::decode::RevertDecoder;
+use std::{fmt, path::PathBuf, process::Command};
+
+/// Reference implementation the return data of a differential fuzz test is compared to.
+#[derive(Clone, Debug)]
+pub enum DifferentialReference {
+    /// Command run from `root` with the hex encoded arguments of each fuzz case appended, printing
+    /// the hex encoded expected return data.
+    Ffi { command: Vec<String>, root: PathBuf },
+    /// Function of the test contract taking the same arguments, returning the expected return
+    /// data.
+    Function(Function),
+}
+
+impl fmt::Display for DifferentialReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ffi { command, .. } => write!(f, "`{}`", command.join(" ")),
+            Self::Function(func) => write!(f, "`{}`", func.signature()),
+        }
+    }
+}
+
+impl DifferentialReference {
+    /// Returns the return data expected for the fuzz case `calldata`, executing the reference
+    /// function with `executor` if the reference is a function.
+    pub(crate) fn expected_output(
+        &self,
+        executor: &Executor,
+        sender: Address,
+        address: Address,
+        calldata: &Bytes,
+    ) -> Result<Bytes, String> {
+        let args = calldata.get(4..).unwrap_or_default();
+        match self {
+            Self::Ffi { command, root } => {
+                let (program, command_args) =
+                    command.split_first().ok_or("empty differential command")?;
+                let output = Command::new(program)
+                    .args(command_args)
+                    .arg(hex::encode_prefixed(args))
+                    .current_dir(root)
+                    .output()
+                    .map_err(|err| format!("failed to execute reference {self}: {err}"))?;
+                if !output.status.success() {
+                    return Err(format!(
+                        "reference {self} failed: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ));
+                }
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                hex::decode(stdout.trim()).map(Into::into).map_err(|err| {
+                    format!("reference {self} did not print hex encoded return data: {err}")
+                })
+            }
+            Self::Function(func) => {
+                let calldata = [func.selector().as_slice(), args].concat();
+                let call = executor
+                    .call_raw(sender, address, calldata.into(), U256::ZERO)
+                    .map_err(|err| format!("reference {self} failed: {err}"))?;
+                if call.reverted {
+                    let reason = RevertDecoder::new().decode(&call.result, Some(call.exit_reason));
+                    return Err(format!("reference {self} reverted: {reason}"));
+                }
+                Ok(call.result)
+            }
+        }
+    }
+
+    /// Returns the report of a mismatch between the `output` of the test `func` and the `expected`
+    /// output of the reference.
+    pub(crate) fn mismatch_reason(
+        &self,
+        func: &Function,
+        output: &Bytes,
+        expected: &Bytes,
+    ) -> String {
+        let format = |data: &Bytes| match func.abi_decode_output(data, false) {
+            Ok(values) if !values.is_empty() => format_values(&values),
+            _ => hex::encode_prefixed(data),
+        };
+        format!(
+            "differential mismatch: returned {} but reference {self} returned {}",
+            format(output),
+            format(expected)
+        )
+    }
+}
+
+fn format_values(values: &[DynSolValue]) -> String {
+    format!("({})", format_tokens(values).collect::<Vec<_>>().join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_decoded_mismatch() {
+        let func = Function::parse("testAdd(uint256 a, uint256 b) returns (uint256)").unwrap();
+        let reference = DifferentialReference::Function(
+            Function::parse("referenceAdd(uint256,uint256)").unwrap(),
+        );
+        let output = DynSolValue::Uint(U256::from(3), 256).abi_encode().into();
+        let expected = DynSolValue::Uint(U256::from(4), 256).abi_encode().into();
+        assert_eq!(
+            reference.mismatch_reason(&func, &output, &expected),
+            "differential mismatch: returned (3) but reference `referenceAdd(uint256,uint256)` returned (4)"
+        );
+
+        let reference = DifferentialReference::Ffi {
+            command: vec!["python3".to_string(), "reference.py".to_string()],
+            root: PathBuf::new(),
+        };
+        assert_eq!(
+            reference.mismatch_reason(&func, &Bytes::from_static(&[1]), &Bytes::new()),
+            "differential mismatch: returned 0x01 but reference `python3 reference.py` returned 0x"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::executors::Executor;
+use alloy_primitives::{Address, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::Filter;
+use alloy_sol_types::sol;
+use foundry_common::provider::ProviderBuilder;
+use foundry_config::FuzzDictionaryConfig;
+use foundry_evm_core::{backend::DatabaseExt, constants::CALLER};
+
+/// The maximum number of `balanceOf` calls made to collect token balances from a fork.
+const MAX_BALANCE_CALLS: usize = 256;
+
+sol! {
+    interface IERC20 {
+        function balanceOf(address owner) external view returns (uint256 balance);
+    }
+}
+
+/// Collects values from the state of the active fork, if any, for the fuzz dictionary:
+/// - the native balances of the accounts touched in the fork
+/// - the balances of the touched accounts in the touched contracts implementing `balanceOf`, if
+///   storage values are included
+/// - the emitters, topics and data of the logs of the touched contracts in the last
+///   `fork_log_blocks` blocks
+///
+/// The storage of the touched accounts is already part of the fork database.
+pub(crate) fn fork_values(executor: &Executor, config: &FuzzDictionaryConfig) -> Vec<B256> {
+    let Some(fork_db) = executor.backend().active_fork_db() else { return vec![] };
+
+    // Sort accounts to collect the same values in the same order from the same setUp state.
+    let mut accounts = fork_db
+        .accounts
+        .iter()
+        .map(|(address, account)| {
+            let is_contract = account.info.code.as_ref().is_some_and(|code| !code.is_empty());
+            (*address, account.info.balance, is_contract)
+        })
+        .collect::<Vec<_>>();
+    accounts.sort_by_key(|(address, ..)| *address);
+    let contracts = accounts
+        .iter()
+        .filter(|(_, _, is_contract)| *is_contract)
+        .map(|(address, ..)| *address)
+        .collect::<Vec<_>>();
+
+    let mut values = Vec::new();
+    values.extend(accounts.iter().map(|(_, balance, _)| B256::from(*balance)));
+
+    if config.include_storage {
+        let owners = accounts.iter().map(|(address, ..)| *address).collect::<Vec<_>>();
+        values.extend(token_balances(executor, &contracts, &owners));
+    }
+
+    if config.fork_log_blocks > 0 && !contracts.is_empty() {
+        if let Some(url) = executor.backend().active_fork_url() {
+            let to_block = executor.env().block.number.saturating_to::<u64>();
+            let from_block = to_block.saturating_sub(config.fork_log_blocks - 1);
+            values.extend(log_values(&url, contracts, from_block, to_block));
+        }
+    }
+
+    values
+}
+
+/// Returns the non-zero balances of `owners` in the `tokens` implementing `balanceOf`.
+fn token_balances(executor: &Executor, tokens: &[Address], owners: &[Address]) -> Vec<B256> {
+    let mut balances = Vec::new();
+    let calls = tokens.iter().flat_map(|token| owners.iter().map(move |owner| (*token, *owner)));
+    for (token, owner) in calls.take(MAX_BALANCE_CALLS) {
+        let call = IERC20::balanceOfCall { owner };
+        if let Ok(result) = executor.call_sol(CALLER, token, &call, U256::ZERO, None) {
+            if !result.decoded_result.balance.is_zero() {
+                balances.push(result.decoded_result.balance.into());
+            }
+        }
+    }
+    balances
+}
+
+/// Returns the emitters, topics and 32 bytes words of the data of the logs emitted by `addresses`
+/// between `from_block` and `to_block`.
+fn log_values(url: &str, addresses: Vec<Address>, from_block: u64, to_block: u64) -> Vec<B256> {
+    let filter = Filter::new().address(addresses).from_block(from_block).to_block(to_block);
+    let logs =
+        ProviderBuilder::new(url).build().map_err(|err| err.to_string()).and_then(|provider| {
+            foundry_common::block_on(provider.get_logs(&filter)).map_err(|err| err.to_string())
+        });
+    let logs = match logs {
+        Ok(logs) => logs,
+        Err(err) => {
+            debug!(%err, "failed to collect fork logs for the fuzz dictionary");
+            return vec![];
+        }
+    };
+
+    let mut values = Vec::new();
+    for log in logs {
+        values.push(log.address().into_word());
+        values.extend(log.topics().iter().copied());
+        let chunks = log.data().data.chunks(32);
+        values.extend(chunks.map(B256::right_padding_from));
+    }
+    values
+}
@@ -11,13 +11,26 @@
 };
 use foundry_evm_coverage::HitMaps;
 use foundry_evm_fuzz::{
-    strategies::{fuzz_calldata, fuzz_calldata_from_state, EvmFuzzState},
-    BaseCounterExample, CounterExample, FuzzCase, FuzzError, FuzzFixtures, FuzzTestResult,
+    strategies::{
+        fuzz_block_advance, fuzz_calldata, fuzz_calldata_from_corpus, fuzz_calldata_from_state,
+        EvmFuzzState,
+    },
+    BaseCounterExample, CounterExample, FuzzCase, FuzzCorpus, FuzzError, FuzzFixtures,
+    FuzzTestResult,
 };
 use foundry_evm_traces::SparsedTraceArena;
 use indicatif::ProgressBar;
-use proptest::test_runner::{TestCaseError, TestError, TestRunner};
-use std::{cell::RefCell, collections::BTreeMap};
+use proptest::{
+    strategy::Strategy,
+    test_runner::{TestCaseError, TestError, TestRunner},
+};
+use std::{borrow::Cow, cell::RefCell, collections::BTreeMap};
+
+mod differential;
+pub use differential::DifferentialReference;
+
+mod fork;
+pub(crate) use fork::fork_values;
 
 mod types;
 pub use types::{CaseOutcome, CounterExampleOutcome, FuzzOutcome};
@@ -31,6 +44,8 @@ pub struct FuzzTestData {
     pub gas_by_case: Vec<(u64, u64)>,
     // Stores the result and calldata of the last failed call, if any.
     pub counterexample: (Bytes, RawCallResult),
+    // Stores the block timestamp and number advances of the last failed call, if any.
+    pub counterexample_block_advance: (Option<U256>, Option<U256>),
     // Stores up to `max_traces_to_collect` traces.
     pub traces: Vec<SparsedTraceArena>,
     // Stores breakpoints for the last fuzz case.
@@ -59,17 +74,30 @@ pub struct FuzzedExecutor {
     sender: Address,
     /// The fuzz configuration
     config: FuzzConfig,
+    /// The reference implementation the test is compared to, if differential
+    differential: Option<DifferentialReference>,
 }
 
 impl FuzzedExecutor {
     /// Instantiates a fuzzed executor given a testrunner
     pub fn new(
-        executor: Executor,
+        mut executor: Executor,
         runner: TestRunner,
         sender: Address,
         config: FuzzConfig,
     ) -> Self {
-        Self { executor, runner, sender, config }
+        // Corpus-guided fuzzing needs the coverage of every fuzz case.
+        if config.corpus_dir.is_some() && executor.inspector().coverage.is_none() {
+            executor.inspector_mut().collect_coverage(true);
+        }
+        Self { executor, runner, sender, config, differential: None }
+    }
+
+    /// Compares the return data of every fuzz case to the one of the `reference` implementation,
+    /// failing on mismatch.
+    pub fn with_differential(mut self, reference: Option<DifferentialReference>) -> Self {
+        self.differential = reference;
+        self
     }
 
     /// Fuzzes the provided function, assuming it is available at the contract at `address`
@@ -89,11 +117,25 @@ pub fn fuzz(
         // Stores the fuzz test execution data.
         let execution_data = RefCell::new(FuzzTestData::default());
         let state = self.build_fuzz_state(deployed_libs);
+        let corpus = self.load_corpus(func, address);
         let dictionary_weight = self.config.dictionary.dictionary_weight.min(100);
-        let strategy = proptest::prop_oneof![
+        let calldata = proptest::prop_oneof![
             100 - dictionary_weight => fuzz_calldata(func.clone(), fuzz_fixtures),
             dictionary_weight => fuzz_calldata_from_state(func.clone(), &state),
         ];
+        // Corpus entries are mutated preferentially when fuzzing is corpus-guided.
+        let calldata = match &corpus {
+            Some(corpus) => proptest::prop_oneof![
+                30 => calldata.clone(),
+                70 => fuzz_calldata_from_corpus(func.clone(), calldata, corpus.clone()),
+            ]
+            .boxed(),
+            None => calldata.boxed(),
+        };
+        let strategy = (
+            calldata,
+            fuzz_block_advance(self.config.timestamp_range, self.config.block_number_range),
+        );
         // We want to collect at least one trace which will be displayed to user.
         let max_traces_to_collect = std::cmp::max(1, self.config.gas_report_samples) as usize;
         let show_logs = self.config.show_logs;
@@ -101,13 +143,13 @@ pub fn fuzz(
         // Start timer for this fuzz test.
         let timer = FuzzTestTimer::new(self.config.timeout);
 
-        let run_result = self.runner.clone().run(&strategy, |calldata| {
+        let run_result = self.runner.clone().run(&strategy, |(calldata, (warp, roll))| {
             // Check if the timeout has been reached.
             if timer.is_timed_out() {
                 return Err(TestCaseError::fail(TEST_TIMEOUT));
             }
 
-            let fuzz_res = self.single_fuzz(address, calldata)?;
+            let fuzz_res = self.single_fuzz(func, address, calldata, warp, roll)?;
 
             // If running with progress then increment current run.
             if let Some(progress) = progress {
@@ -116,6 +158,10 @@ pub fn fuzz(
 
             match fuzz_res {
                 FuzzOutcome::Case(case) => {
+                    if let (Some(corpus), Some(coverage)) = (&corpus, &case.coverage) {
+                        corpus.observe(&case.case.calldata, coverage);
+                    }
+
                     let mut data = execution_data.borrow_mut();
                     data.gas_by_case.push((case.case.gas, case.case.stipend));
 
@@ -144,15 +190,18 @@ pub fn fuzz(
                 FuzzOutcome::CounterExample(CounterExampleOutcome {
                     exit_reason: status,
                     counterexample: outcome,
+                    reason,
                     ..
                 }) => {
                     // We cannot use the calldata returned by the test runner in `TestError::Fail`,
                     // since that input represents the last run case, which may not correspond with
                     // our failure - when a fuzz case fails, proptest will try to run at least one
                     // more case to find a minimal failure case.
-                    let reason = rd.maybe_decode(&outcome.1.result, Some(status));
+                    let reason =
+                        reason.or_else(|| rd.maybe_decode(&outcome.1.result, Some(status)));
                     execution_data.borrow_mut().logs.extend(outcome.1.logs.clone());
                     execution_data.borrow_mut().counterexample = outcome;
+                    execution_data.borrow_mut().counterexample_block_advance = (warp, roll);
                     // HACK: we have to use an empty string here to denote `None`.
                     Err(TestCaseError::fail(reason.unwrap_or_default()))
                 }
@@ -161,6 +210,7 @@ pub fn fuzz(
 
         let fuzz_result = execution_data.into_inner();
         let (calldata, call) = fuzz_result.counterexample;
+        let (warp, roll) = fuzz_result.counterexample_block_advance;
 
         let mut traces = fuzz_result.traces;
         let (last_run_traces, last_run_breakpoints) = if run_result.is_ok() {
@@ -213,7 +263,7 @@ pub fn fuzz(
                     };
 
                     result.counterexample = Some(CounterExample::Single(
-                        BaseCounterExample::from_fuzz_call(calldata, args, call.traces),
+                        BaseCounterExample::from_fuzz_call(calldata, args, warp, roll, call.traces),
                     ));
                 }
             }
@@ -233,13 +283,23 @@ pub fn fuzz(
 
     /// Granular and single-step function that runs only one fuzz and returns either a `CaseOutcome`
     /// or a `CounterExampleOutcome`
+    ///
+    /// The call is executed with `block.timestamp` and `block.number` advanced by `warp` and
+    /// `roll`. If the test is differential, its return data is compared to the one of the
+    /// reference.
     pub fn single_fuzz(
         &self,
+        func: &Function,
         address: Address,
         calldata: alloy_primitives::Bytes,
+        warp: Option<U256>,
+        roll: Option<U256>,
     ) -> Result<FuzzOutcome, TestCaseError> {
-        let mut call = self
-            .executor
+        let mut executor = Cow::Borrowed(&self.executor);
+        if warp.is_some() || roll.is_some() {
+            executor.to_mut().advance_block(warp, roll);
+        }
+        let mut call = executor
             .call_raw(self.sender, address, calldata.clone(), U256::ZERO)
             .map_err(|e| TestCaseError::fail(e.to_string()))?;
 
@@ -253,8 +313,25 @@ pub fn single_fuzz(
                 (cheats.breakpoints.clone(), cheats.deprecated.clone())
             });
 
-        let success = self.executor.is_raw_call_mut_success(address, &mut call, false);
+        let success = executor.is_raw_call_mut_success(address, &mut call, false);
         if success {
+            if let Some(reference) = &self.differential {
+                let mismatch =
+                    match reference.expected_output(&executor, self.sender, address, &calldata) {
+                        Ok(expected) => (expected != call.result)
+                            .then(|| reference.mismatch_reason(func, &call.result, &expected)),
+                        Err(err) => Some(err),
+                    };
+                if let Some(reason) = mismatch {
+                    return Ok(FuzzOutcome::CounterExample(CounterExampleOutcome {
+                        exit_reason: call.exit_reason,
+                        counterexample: (calldata, call),
+                        breakpoints,
+                        reason: Some(reason),
+                    }));
+                }
+            }
+
             Ok(FuzzOutcome::Case(CaseOutcome {
                 case: FuzzCase { calldata, gas: call.gas_used, stipend: call.stipend },
                 traces: call.traces,
@@ -268,14 +345,34 @@ pub fn single_fuzz(
                 exit_reason: call.exit_reason,
                 counterexample: (calldata, call),
                 breakpoints,
+                reason: None,
             }))
         }
     }
 
+    /// Loads the corpus of the fuzz test if fuzzing is corpus-guided, and replays its entries to
+    /// only add the inputs reaching coverage beyond the one of the corpus.
+    fn load_corpus(&self, func: &Function, address: Address) -> Option<FuzzCorpus> {
+        let corpus = FuzzCorpus::load(self.config.corpus_dir.clone()?, func);
+        for calldata in corpus.entries() {
+            match self.executor.call_raw(self.sender, address, calldata, U256::ZERO) {
+                Ok(call) => {
+                    if let Some(coverage) = &call.coverage {
+                        corpus.merge_coverage(coverage);
+                    }
+                }
+                Err(err) => warn!(%err, "failed to replay fuzz corpus entry"),
+            }
+        }
+        Some(corpus)
+    }
+
     /// Stores fuzz state for use with [fuzz_calldata_from_state]
     pub fn build_fuzz_state(&self, deployed_libs: &[Address]) -> EvmFuzzState {
         if let Some(fork_db) = self.executor.backend().active_fork_db() {
-            EvmFuzzState::new(fork_db, self.config.dictionary, deployed_libs)
+            let state = EvmFuzzState::new(fork_db, self.config.dictionary, deployed_libs);
+            state.collect_db_values(fork_values(&self.executor, &self.config.dictionary));
+            state
         } else {
             EvmFuzzState::new(
                 self.executor.backend().mem_db(),
@@ -32,6 +32,8 @@ pub struct CounterExampleOutcome {
     pub exit_reason: InstructionResult,
     /// Breakpoints char pc map.
     pub breakpoints: Breakpoints,
+    /// The failure reason, if not the revert reason of the call.
+    pub reason: Option<String>,
 }
 
 /// Outcome of a single fuzz
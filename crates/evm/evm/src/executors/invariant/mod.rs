@@ -1,12 +1,12 @@
 use crate::{
-    executors::{Executor, RawCallResult},
+    executors::{fuzz::fork_values, Executor, RawCallResult},
     inspectors::Fuzzer,
 };
 use alloy_primitives::{Address, Bytes, FixedBytes, Selector, U256};
 use alloy_sol_types::{sol, SolCall};
 use eyre::{eyre, ContextCompat, Result};
 use foundry_common::contracts::{ContractsByAddress, ContractsByArtifact};
-use foundry_config::InvariantConfig;
+use foundry_config::{FuzzConfig, InvariantConfig};
 use foundry_evm_core::{
     constants::{
         CALLER, CHEATCODE_ADDRESS, DEFAULT_CREATE2_DEPLOYER, HARDHAT_CONSOLE_ADDRESS, MAGIC_ASSUME,
@@ -43,7 +43,7 @@
 use foundry_evm_coverage::HitMaps;
 
 mod replay;
-pub use replay::{replay_error, replay_run};
+pub use replay::{replay_error, replay_run, replay_test_function};
 
 mod result;
 pub use result::InvariantFuzzTestResult;
@@ -297,6 +297,10 @@ pub struct InvariantExecutor<'a> {
     project_contracts: &'a ContractsByArtifact,
     /// Filters contracts to be fuzzed through their artifact identifiers.
     artifact_filters: ArtifactFilters,
+    /// Range by which to advance `block.timestamp` before each call.
+    timestamp_range: Option<[u64; 2]>,
+    /// Range by which to advance `block.number` before each call.
+    block_number_range: Option<[u64; 2]>,
 }
 
 impl<'a> InvariantExecutor<'a> {
@@ -305,6 +309,7 @@ pub fn new(
         executor: Executor,
         runner: TestRunner,
         config: InvariantConfig,
+        fuzz_config: &FuzzConfig,
         setup_contracts: &'a ContractsByAddress,
         project_contracts: &'a ContractsByArtifact,
     ) -> Self {
@@ -315,6 +320,8 @@ pub fn new(
             setup_contracts,
             project_contracts,
             artifact_filters: ArtifactFilters::default(),
+            timestamp_range: fuzz_config.timestamp_range,
+            block_number_range: fuzz_config.block_number_range,
         }
     }
 
@@ -365,6 +372,10 @@ pub fn invariant_fuzz(
                     TestCaseError::fail("no input generated to called fuzz target")
                 })?;
 
+                // Advance the block as generated for the call, restored if the call is discarded.
+                let block = current_run.executor.current_block();
+                current_run.executor.advance_block(tx.warp, tx.roll);
+
                 // Execute call from the randomly generated sequence without committing state.
                 // State is committed only if call is not a magic assume.
                 let mut call_result = current_run
@@ -386,6 +397,7 @@ pub fn invariant_fuzz(
                 invariant_test.merge_coverage(call_result.coverage.clone());
 
                 if discarded {
+                    current_run.executor.set_block(block);
                     current_run.inputs.pop();
                     current_run.assume_rejects_counter += 1;
                     if current_run.assume_rejects_counter > self.config.max_assume_rejects {
@@ -526,6 +538,7 @@ fn prepare_test(
             self.config.dictionary,
             deployed_libs,
         );
+        fuzz_state.collect_db_values(fork_values(&self.executor, &self.config.dictionary));
 
         // Creates the invariant strategy.
         let strategy = invariant_strat(
@@ -534,6 +547,8 @@ fn prepare_test(
             targeted_contracts.clone(),
             self.config.dictionary.dictionary_weight,
             fuzz_fixtures.clone(),
+            self.timestamp_range,
+            self.block_number_range,
         )
         .no_shrink();
 
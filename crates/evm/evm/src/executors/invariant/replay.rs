@@ -17,7 +17,7 @@
 use parking_lot::RwLock;
 use proptest::test_runner::TestError;
 use revm::primitives::U256;
-use std::sync::Arc;
+use std::{fmt::Write, sync::Arc};
 
 /// Replays a call sequence for collecting logs and traces.
 /// Returns counterexample to be used when the call sequence is a failed scenario.
@@ -43,6 +43,7 @@ pub fn replay_run(
 
     // Replay each call from the sequence, collect logs, traces and coverage.
     for tx in inputs {
+        executor.advance_block(tx.warp, tx.roll);
         let call_result = executor.transact_raw(
             tx.sender,
             tx.call_details.target,
@@ -60,9 +61,7 @@ pub fn replay_run(
 
         // Create counter example to be used in failed case.
         counterexample_sequence.push(BaseCounterExample::from_invariant_call(
-            tx.sender,
-            tx.call_details.target,
-            &tx.call_details.calldata,
+            tx,
             &ided_contracts,
             call_result.traces,
             show_solidity,
@@ -146,6 +145,25 @@ pub fn replay_error(
     }
 }
 
+/// Returns a Solidity test function executing the call sequence and then checking the invariant,
+/// to be pasted in the invariant test contract.
+///
+/// Calls to unknown contracts are emitted as comments.
+pub fn replay_test_function(invariant_name: &str, call_sequence: &[BaseCounterExample]) -> String {
+    let mut function = format!("function test_replay_{invariant_name}() public {{\n");
+    for call in call_sequence {
+        let call = BaseCounterExample { show_solidity: true, traces: None, ..call.clone() };
+        if call.func_name.is_some() {
+            writeln!(function, "{call}").unwrap();
+        } else {
+            writeln!(function, "\t\t// {}", call.to_string().trim()).unwrap();
+        }
+    }
+    writeln!(function, "\t\t{invariant_name}();\n\t}}").unwrap();
+    // The calls are indented for the body of a function of a contract.
+    function.replace("\t\t", "        ")
+}
+
 /// Sets up the calls generated by the internal fuzzer, if they exist.
 fn set_up_inner_replay(executor: &mut Executor, inner_sequence: &[Option<BasicTxDetails>]) {
     if let Some(fuzzer) = &mut executor.inspector_mut().fuzzer {
@@ -155,3 +173,47 @@ fn set_up_inner_replay(executor: &mut Executor, inner_sequence: &[Option<BasicTx
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes};
+
+    #[test]
+    fn formats_replay_test_function() {
+        let call = BaseCounterExample {
+            sender: Some(address!("0x0000000000000000000000000000000000000001")),
+            addr: Some(address!("0x0000000000000000000000000000000000000002")),
+            calldata: Bytes::new(),
+            contract_name: Some("src/Counter.sol:Counter".to_string()),
+            func_name: Some("setNumber".to_string()),
+            signature: Some("setNumber(uint256)".to_string()),
+            args: Some("1".to_string()),
+            raw_args: Some("1".to_string()),
+            warp: Some(U256::from(60)),
+            roll: None,
+            traces: None,
+            show_solidity: false,
+        };
+        let unknown = BaseCounterExample {
+            contract_name: None,
+            func_name: None,
+            signature: None,
+            args: None,
+            raw_args: None,
+            warp: None,
+            ..call.clone()
+        };
+        assert_eq!(
+            replay_test_function("invariant_number", &[call, unknown]),
+            "function test_replay_invariant_number() public {
+        vm.warp(block.timestamp + 60);
+        vm.prank(0x0000000000000000000000000000000000000001);
+        Counter(0x0000000000000000000000000000000000000002).setNumber(1);
+        // sender=0x0000000000000000000000000000000000000001 addr=0x0000000000000000000000000000000000000002 calldata=0x args=[]
+        invariant_number();
+    }
+"
+        );
+    }
+}
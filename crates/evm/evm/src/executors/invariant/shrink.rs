@@ -155,6 +155,7 @@ pub fn check_sequence(
     // Apply the call sequence.
     for call_index in sequence {
         let tx = &calls[call_index];
+        executor.advance_block(tx.warp, tx.roll);
         let call_result = executor.transact_raw(
             tx.sender,
             tx.call_details.target,
@@ -38,6 +38,7 @@
 };
 use std::{
     borrow::Cow,
+    cell::Cell,
     time::{Duration, Instant},
 };
 
@@ -53,6 +54,34 @@
 mod trace;
 pub use trace::TracingExecutor;
 
+thread_local! {
+    /// The time spent executing the EVM in the innermost [`measure_evm_time`] call on the current
+    /// thread.
+    static EVM_TIME: Cell<Option<Duration>> = const { Cell::new(None) };
+}
+
+/// Runs `f`, returning its result and the time it spent executing the EVM.
+///
+/// Every call measures only its own executions: if the thread runs other work inside `f`, e.g. a
+/// test stolen by rayon while `f` waits, that work measures its time separately, which is not
+/// added to the time of `f`.
+pub fn measure_evm_time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let outer = EVM_TIME.replace(Some(Duration::ZERO));
+    let res = f();
+    let time = EVM_TIME.replace(outer).unwrap_or_default();
+    (res, time)
+}
+
+/// Runs `f`, adding the time it took to the innermost [`measure_evm_time`] call, if any.
+fn record_evm_time<T>(f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let res = f();
+    if let Some(time) = EVM_TIME.get() {
+        EVM_TIME.set(Some(time + start.elapsed()));
+    }
+    res
+}
+
 sol! {
     interface ITest {
         function setUp() external;
@@ -149,6 +178,38 @@ pub fn env_mut(&mut self) -> &mut Env {
         &mut self.env.env
     }
 
+    /// Returns the block the next calls are executed in, including any `vm.warp` and `vm.roll`
+    /// persisted by previously committed calls.
+    pub fn current_block(&self) -> BlockEnv {
+        self.inspector()
+            .cheatcodes
+            .as_ref()
+            .and_then(|cheats| cheats.block.clone())
+            .unwrap_or_else(|| self.env().block.clone())
+    }
+
+    /// Sets the block the next calls are executed in.
+    pub fn set_block(&mut self, block: BlockEnv) {
+        self.inspector_mut().set_block(&block);
+        self.env_mut().block = block;
+    }
+
+    /// Advances `block.timestamp` and `block.number` of the next calls by the given amounts.
+    pub fn advance_block(&mut self, warp: Option<U256>, roll: Option<U256>) {
+        if warp.is_none() && roll.is_none() {
+            return;
+        }
+
+        let mut block = self.current_block();
+        if let Some(warp) = warp {
+            block.timestamp = block.timestamp.saturating_add(warp);
+        }
+        if let Some(roll) = roll {
+            block.number = block.number.saturating_add(roll);
+        }
+        self.set_block(block);
+    }
+
     /// Returns a reference to the EVM inspector.
     pub fn inspector(&self) -> &InspectorStack {
         &self.inspector
@@ -246,6 +307,36 @@ pub fn get_nonce(&self, address: Address) -> BackendResult<u64> {
         Ok(self.backend().basic_ref(address)?.map(|acc| acc.nonce).unwrap_or_default())
     }
 
+    /// Set the code of an account.
+    pub fn set_code(&mut self, address: Address, code: Bytecode) -> BackendResult<()> {
+        let mut account = self.backend().basic_ref(address)?.unwrap_or_default();
+        account.code_hash = code.hash_slow();
+        account.code = Some(code);
+        self.backend_mut().insert_account_info(address, account);
+        Ok(())
+    }
+
+    /// Set the value of a storage slot of an account.
+    pub fn set_storage_slot(
+        &mut self,
+        address: Address,
+        slot: U256,
+        value: U256,
+    ) -> BackendResult<()> {
+        self.backend_mut().insert_account_storage(address, slot, value)?;
+        Ok(())
+    }
+
+    /// Replace the whole storage of an account, all the slots not in `storage` being zero.
+    pub fn set_storage(
+        &mut self,
+        address: Address,
+        storage: HashMap<U256, U256>,
+    ) -> BackendResult<()> {
+        self.backend_mut().replace_account_storage(address, storage)?;
+        Ok(())
+    }
+
     /// Returns `true` if the account has no code.
     pub fn is_empty_code(&self, address: Address) -> BackendResult<bool> {
         Ok(self.backend().basic_ref(address)?.map(|acc| acc.is_empty_code_hash()).unwrap_or(true))
@@ -443,7 +534,7 @@ pub fn transact_raw(
     pub fn call_with_env(&self, mut env: EnvWithHandlerCfg) -> eyre::Result<RawCallResult> {
         let mut inspector = self.inspector().clone();
         let mut backend = CowBackend::new_borrowed(self.backend());
-        let result = backend.inspect(&mut env, &mut inspector)?;
+        let result = record_evm_time(|| backend.inspect(&mut env, &mut inspector))?;
         convert_executed_result(env, inspector, result, backend.has_state_snapshot_failure())
     }
 
@@ -452,7 +543,7 @@ pub fn call_with_env(&self, mut env: EnvWithHandlerCfg) -> eyre::Result<RawCallR
     pub fn transact_with_env(&mut self, mut env: EnvWithHandlerCfg) -> eyre::Result<RawCallResult> {
         let mut inspector = self.inspector().clone();
         let backend = self.backend_mut();
-        let result = backend.inspect(&mut env, &mut inspector)?;
+        let result = record_evm_time(|| backend.inspect(&mut env, &mut inspector))?;
         let mut result =
             convert_executed_result(env, inspector, result, backend.has_state_snapshot_failure())?;
         self.commit(&mut result);
@@ -0,0 +1,171 @@
+//! Corpus of the fuzz inputs which increased coverage, used for corpus-guided fuzzing.
+
+use alloy_dyn_abi::JsonAbiExt;
+use alloy_json_abi::Function;
+use alloy_primitives::{hex, keccak256, map::HashSet, Bytes, B256};
+use foundry_common::fs;
+use foundry_evm_coverage::HitMaps;
+use parking_lot::RwLock;
+use proptest::sample::Index;
+use std::{path::PathBuf, sync::Arc};
+
+/// The inputs of a fuzz test which reached new coverage, persisted to a directory to be mutated in
+/// later runs.
+///
+/// An input is considered interesting when it hits a program counter which was not hit before, or
+/// hits it a number of times falling in a new bucket (1, 2, 3, 4-7, 8-15, 16-31, 32-127, 128+),
+/// similarly to AFL.
+#[derive(Clone, Debug, Default)]
+pub struct FuzzCorpus {
+    inner: Arc<RwLock<FuzzCorpusInner>>,
+}
+
+#[derive(Debug, Default)]
+struct FuzzCorpusInner {
+    /// Directory the entries are persisted to, one hex encoded calldata per file.
+    dir: Option<PathBuf>,
+    /// The calldata of the entries.
+    entries: Vec<Bytes>,
+    /// The coverage reached so far, by bytecode hash, program counter and hit count bucket.
+    coverage: HashSet<(B256, u32, u8)>,
+}
+
+impl FuzzCorpus {
+    /// Loads the entries for `func` persisted in `dir`, entries of other functions are ignored.
+    pub fn load(dir: PathBuf, func: &Function) -> Self {
+        let mut entries = Vec::new();
+        if let Ok(read_dir) = std::fs::read_dir(&dir) {
+            let mut paths = read_dir.flatten().map(|entry| entry.path()).collect::<Vec<_>>();
+            // sort for the entries to be mutated deterministically for a given seed
+            paths.sort();
+            for path in paths {
+                let Ok(calldata) = fs::read_to_string(&path) else { continue };
+                let Ok(calldata) = hex::decode(calldata.trim()) else { continue };
+                if calldata.get(..4) == Some(func.selector().as_slice()) {
+                    entries.push(calldata.into());
+                }
+            }
+        }
+        trace!(
+            target: "forge::test",
+            dir = %dir.display(),
+            entries = entries.len(),
+            "loaded fuzz corpus"
+        );
+        let inner = FuzzCorpusInner { dir: Some(dir), entries, coverage: HashSet::default() };
+        Self { inner: Arc::new(RwLock::new(inner)) }
+    }
+
+    /// Returns the calldata of the entries.
+    pub fn entries(&self) -> Vec<Bytes> {
+        self.inner.read().entries.clone()
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.inner.read().entries.len()
+    }
+
+    /// Returns `true` if the corpus has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().entries.is_empty()
+    }
+
+    /// Records the coverage reached by an input, without adding it to the corpus.
+    ///
+    /// Returns `true` if the input reached new coverage.
+    pub fn merge_coverage(&self, coverage: &HitMaps) -> bool {
+        let mut inner = self.inner.write();
+        let mut is_new = false;
+        for (hash, map) in coverage.iter() {
+            for (pc, hits) in map.iter() {
+                is_new |= inner.coverage.insert((*hash, pc, hit_bucket(hits)));
+            }
+        }
+        is_new
+    }
+
+    /// Records the coverage reached by `calldata`, adding it to the corpus and persisting it if
+    /// it reached new coverage.
+    pub fn observe(&self, calldata: &Bytes, coverage: &HitMaps) {
+        if !self.merge_coverage(coverage) {
+            return;
+        }
+
+        let mut inner = self.inner.write();
+        if let Some(dir) = &inner.dir {
+            let path = dir.join(hex::encode(&keccak256(calldata)[..8]));
+            if let Err(err) =
+                fs::create_dir_all(dir).and_then(|()| fs::write(&path, hex::encode(calldata)))
+            {
+                warn!(target: "forge::test", %err, "failed to persist fuzz corpus entry");
+            }
+        }
+        inner.entries.push(calldata.clone());
+    }
+
+    /// Mutates an entry of the corpus by replacing one of its arguments with the one of `fresh`,
+    /// newly generated calldata for `func`. Returns `fresh` if the corpus is empty.
+    pub fn mutate(&self, func: &Function, fresh: Bytes, entry: Index, arg: Index) -> Bytes {
+        let inner = self.inner.read();
+        if inner.entries.is_empty() || func.inputs.is_empty() {
+            return fresh;
+        }
+        mutate_entry(func, entry.get(&inner.entries), fresh, arg.index(func.inputs.len()))
+    }
+}
+
+/// Replaces the argument at index `arg` of `entry` with the one of `fresh`.
+fn mutate_entry(func: &Function, entry: &Bytes, fresh: Bytes, arg: usize) -> Bytes {
+    let (Ok(mut args), Ok(fresh_args)) =
+        (func.abi_decode_input(&entry[4..], false), func.abi_decode_input(&fresh[4..], false))
+    else {
+        return fresh;
+    };
+    args[arg] = fresh_args[arg].clone();
+    func.abi_encode_input(&args).map(Into::into).unwrap_or(fresh)
+}
+
+/// Returns the bucket of a hit count.
+fn hit_bucket(hits: u32) -> u8 {
+    match hits {
+        0 => 0,
+        1 => 1,
+        2 => 2,
+        3 => 3,
+        4..=7 => 4,
+        8..=15 => 5,
+        16..=31 => 6,
+        32..=127 => 7,
+        _ => 8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_primitives::U256;
+
+    #[test]
+    fn mutates_single_argument() {
+        let func = Function::parse("testFuzz(uint256 a, bool b)").unwrap();
+        let encode = |a: u64, b: bool| -> Bytes {
+            func.abi_encode_input(&[DynSolValue::Uint(U256::from(a), 256), DynSolValue::Bool(b)])
+                .unwrap()
+                .into()
+        };
+        let entry = encode(1, true);
+        let fresh = encode(2, false);
+        assert_eq!(mutate_entry(&func, &entry, fresh.clone(), 0), encode(2, true));
+        assert_eq!(mutate_entry(&func, &entry, fresh, 1), encode(1, false));
+    }
+
+    #[test]
+    fn buckets_hit_counts() {
+        assert_eq!(hit_bucket(1), 1);
+        assert_eq!(hit_bucket(5), hit_bucket(7));
+        assert_ne!(hit_bucket(7), hit_bucket(8));
+        assert_eq!(hit_bucket(1000), 8);
+    }
+}
@@ -75,12 +75,9 @@ pub fn next(
             *self.target_reference.write() = original_caller;
 
             // `original_caller` has a 80% chance of being the `new_target`.
-            let choice = self
-                .strategy
-                .new_tree(&mut self.runner.lock())
-                .unwrap()
-                .current()
-                .map(|call_details| BasicTxDetails { sender, call_details });
+            let choice = self.strategy.new_tree(&mut self.runner.lock()).unwrap().current().map(
+                |call_details| BasicTxDetails { sender, call_details, warp: None, roll: None },
+            );
 
             self.last_sequence.write().push(choice.clone());
             choice
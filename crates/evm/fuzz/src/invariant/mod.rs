@@ -1,5 +1,5 @@
 use alloy_json_abi::{Function, JsonAbi};
-use alloy_primitives::{Address, Bytes, Selector};
+use alloy_primitives::{Address, Bytes, Selector, U256};
 use itertools::Either;
 use parking_lot::Mutex;
 use std::{collections::BTreeMap, sync::Arc};
@@ -217,6 +217,10 @@ pub struct BasicTxDetails {
     pub sender: Address,
     // Transaction call details.
     pub call_details: CallDetails,
+    // Seconds by which to advance `block.timestamp` before the transaction.
+    pub warp: Option<U256>,
+    // Blocks by which to advance `block.number` before the transaction.
+    pub roll: Option<U256>,
 }
 
 /// Call details of a transaction generated to fuzz invariant target.
@@ -11,17 +11,21 @@
 use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
 use alloy_primitives::{
     map::{AddressHashMap, HashMap},
-    Address, Bytes, Log,
+    Address, Bytes, Log, U256,
 };
 use foundry_common::{calc, contracts::ContractsByAddress, evm::Breakpoints};
 use foundry_evm_coverage::HitMaps;
 use foundry_evm_traces::{CallTraceArena, SparsedTraceArena};
+use invariant::BasicTxDetails;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::{fmt, sync::Arc};
 
 pub use proptest::test_runner::{Config as FuzzConfig, Reason};
 
+mod corpus;
+pub use corpus::FuzzCorpus;
+
 mod error;
 pub use error::FuzzError;
 
@@ -58,6 +62,12 @@ pub struct BaseCounterExample {
     pub args: Option<String>,
     /// Unformatted args used to call the function.
     pub raw_args: Option<String>,
+    /// Seconds by which `block.timestamp` was advanced before the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warp: Option<U256>,
+    /// Blocks by which `block.number` was advanced before the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roll: Option<U256>,
     /// Counter example traces.
     #[serde(skip)]
     pub traces: Option<SparsedTraceArena>,
@@ -69,13 +79,14 @@ pub struct BaseCounterExample {
 impl BaseCounterExample {
     /// Creates counter example representing a step from invariant call sequence.
     pub fn from_invariant_call(
-        sender: Address,
-        addr: Address,
-        bytes: &Bytes,
+        tx: &BasicTxDetails,
         contracts: &ContractsByAddress,
         traces: Option<SparsedTraceArena>,
         show_solidity: bool,
     ) -> Self {
+        let sender = tx.sender;
+        let addr = tx.call_details.target;
+        let bytes = &tx.call_details.calldata;
         if let Some((name, abi)) = &contracts.get(&addr) {
             if let Some(func) = abi.functions().find(|f| f.selector() == bytes[..4]) {
                 // skip the function selector when decoding
@@ -93,6 +104,8 @@ pub fn from_invariant_call(
                         raw_args: Some(
                             foundry_common::fmt::format_tokens_raw(&args).format(", ").to_string(),
                         ),
+                        warp: tx.warp,
+                        roll: tx.roll,
                         traces,
                         show_solidity,
                     };
@@ -109,6 +122,8 @@ pub fn from_invariant_call(
             signature: None,
             args: None,
             raw_args: None,
+            warp: tx.warp,
+            roll: tx.roll,
             traces,
             show_solidity: false,
         }
@@ -118,6 +133,8 @@ pub fn from_invariant_call(
     pub fn from_fuzz_call(
         bytes: Bytes,
         args: Vec<DynSolValue>,
+        warp: Option<U256>,
+        roll: Option<U256>,
         traces: Option<SparsedTraceArena>,
     ) -> Self {
         Self {
@@ -129,6 +146,8 @@ pub fn from_fuzz_call(
             signature: None,
             args: Some(foundry_common::fmt::format_tokens(&args).format(", ").to_string()),
             raw_args: Some(foundry_common::fmt::format_tokens_raw(&args).format(", ").to_string()),
+            warp,
+            roll,
             traces,
             show_solidity: false,
         }
@@ -142,6 +161,12 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             if let (Some(sender), Some(contract), Some(address), Some(func_name), Some(args)) =
                 (&self.sender, &self.contract_name, &self.addr, &self.func_name, &self.raw_args)
             {
+                if let Some(warp) = self.warp {
+                    writeln!(f, "\t\tvm.warp(block.timestamp + {warp});")?;
+                }
+                if let Some(roll) = self.roll {
+                    writeln!(f, "\t\tvm.roll(block.number + {roll});")?;
+                }
                 writeln!(f, "\t\tvm.prank({sender});")?;
                 write!(
                     f,
@@ -176,10 +201,20 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         }
 
         if let Some(args) = &self.args {
-            write!(f, " args=[{args}]")
+            write!(f, " args=[{args}]")?
         } else {
-            write!(f, " args=[]")
+            write!(f, " args=[]")?
+        }
+
+        if let Some(warp) = self.warp {
+            write!(f, " warp={warp}")?
+        }
+
+        if let Some(roll) = self.roll {
+            write!(f, " roll={roll}")?
         }
+
+        Ok(())
     }
 }
 
@@ -0,0 +1,38 @@
+use alloy_primitives::U256;
+use proptest::prelude::*;
+
+/// Given the configured `[min, max]` ranges, generates by how much to advance `block.timestamp`
+/// and `block.number` before a fuzzed call. Nothing is generated for the ranges which are unset.
+pub fn fuzz_block_advance(
+    timestamp_range: Option<[u64; 2]>,
+    block_number_range: Option<[u64; 2]>,
+) -> impl Strategy<Value = (Option<U256>, Option<U256>)> + Clone {
+    (fuzz_advance(timestamp_range), fuzz_advance(block_number_range))
+}
+
+fn fuzz_advance(range: Option<[u64; 2]>) -> BoxedStrategy<Option<U256>> {
+    match range {
+        Some([min, max]) => {
+            (min.min(max)..=min.max(max)).prop_map(|advance| Some(U256::from(advance))).boxed()
+        }
+        None => Just(None).boxed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{strategy::ValueTree, test_runner::TestRunner};
+
+    #[test]
+    fn advances_within_range() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = fuzz_block_advance(Some([10, 20]), None);
+        for _ in 0..100 {
+            let (warp, roll) = strategy.new_tree(&mut runner).unwrap().current();
+            let warp = warp.unwrap();
+            assert!(warp >= U256::from(10) && warp <= U256::from(20));
+            assert_eq!(roll, None);
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use crate::{
     strategies::{fuzz_param_from_state, fuzz_param_with_fixtures, EvmFuzzState},
-    FuzzFixtures,
+    FuzzCorpus, FuzzFixtures,
 };
 use alloy_dyn_abi::JsonAbiExt;
 use alloy_json_abi::Function;
 use alloy_primitives::Bytes;
-use proptest::prelude::Strategy;
+use proptest::{
+    prelude::{any, Strategy},
+    sample::Index,
+};
 
 /// Given a function, it returns a strategy which generates valid calldata
 /// for that function's input types, following declared test fixtures.
@@ -60,6 +63,17 @@ pub fn fuzz_calldata_from_state(
         .no_shrink()
 }
 
+/// Given a function and its corpus, it returns a strategy which mutates the corpus entries with
+/// the calldata generated by `fresh`, or uses the fresh calldata as is while the corpus is empty.
+pub fn fuzz_calldata_from_corpus(
+    func: Function,
+    fresh: impl Strategy<Value = Bytes>,
+    corpus: FuzzCorpus,
+) -> impl Strategy<Value = Bytes> {
+    (fresh, any::<Index>(), any::<Index>())
+        .prop_map(move |(fresh, entry, arg)| corpus.mutate(&func, fresh, entry, arg))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{strategies::fuzz_calldata, FuzzFixtures};
@@ -1,4 +1,4 @@
-use super::{fuzz_calldata, fuzz_param_from_state};
+use super::{fuzz_block_advance, fuzz_calldata, fuzz_param_from_state};
 use crate::{
     invariant::{BasicTxDetails, CallDetails, FuzzRunIdentifiedContracts, SenderFilters},
     strategies::{fuzz_calldata_from_state, fuzz_param, EvmFuzzState},
@@ -49,8 +49,9 @@ pub fn override_call_strat(
 /// Creates the invariant strategy.
 ///
 /// Given the known and future contracts, it generates the next call by fuzzing the `caller`,
-/// `calldata` and `target`. The generated data is evaluated lazily for every single call to fully
-/// leverage the evolving fuzz dictionary.
+/// `calldata` and `target`, and by how much to advance the block timestamp and number within the
+/// given ranges. The generated data is evaluated lazily for every single call to fully leverage the
+/// evolving fuzz dictionary.
 ///
 /// The fuzzed parameters can be filtered through different methods implemented in the test
 /// contract:
@@ -62,8 +63,11 @@ pub fn invariant_strat(
     contracts: FuzzRunIdentifiedContracts,
     dictionary_weight: u32,
     fuzz_fixtures: FuzzFixtures,
+    timestamp_range: Option<[u64; 2]>,
+    block_number_range: Option<[u64; 2]>,
 ) -> impl Strategy<Value = BasicTxDetails> {
     let senders = Rc::new(senders);
+    let block_advance = fuzz_block_advance(timestamp_range, block_number_range);
     any::<prop::sample::Selector>()
         .prop_flat_map(move |selector| {
             let contracts = contracts.targets.lock();
@@ -76,9 +80,14 @@ pub fn invariant_strat(
                 *target_address,
                 target_function.clone(),
             );
-            (sender, call_details)
+            (sender, call_details, block_advance.clone())
+        })
+        .prop_map(|(sender, call_details, (warp, roll))| BasicTxDetails {
+            sender,
+            call_details,
+            warp,
+            roll,
         })
-        .prop_map(|(sender, call_details)| BasicTxDetails { sender, call_details })
 }
 
 /// Strategy to select a sender address:
@@ -47,6 +47,17 @@ pub fn new<DB: DatabaseRef>(
         Self { inner: Arc::new(RwLock::new(dictionary)), deployed_libs: deployed_libs.to_vec() }
     }
 
+    /// Collects values which are kept across runs, like the ones collected from the db.
+    ///
+    /// Must be called before the first run.
+    pub fn collect_db_values(&self, values: impl IntoIterator<Item = B256>) {
+        let mut dict = self.inner.write();
+        for value in values {
+            dict.insert_value(value);
+        }
+        dict.db_state_values = dict.state_values.len();
+    }
+
     pub fn collect_values(&self, values: impl IntoIterator<Item = B256>) {
         let mut dict = self.inner.write();
         for value in values {
@@ -384,3 +395,24 @@ pub fn log_stats(&self) {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::EmptyDB;
+
+    #[test]
+    fn keeps_db_values_across_runs() {
+        let db = CacheDB::new(EmptyDB::default());
+        let state = EvmFuzzState::new(&db, FuzzDictionaryConfig::default(), &[]);
+        let fork_value = B256::with_last_byte(1);
+        let run_value = B256::with_last_byte(2);
+        state.collect_db_values([fork_value]);
+        state.collect_values([run_value]);
+
+        state.revert();
+        let dict = state.dictionary_read();
+        assert!(dict.values().contains(&fork_value));
+        assert!(!dict.values().contains(&run_value));
+    }
+}
@@ -41,6 +41,16 @@ pub fn new(source: Arc<String>, language: MultiCompilerLanguage, path: PathBuf)
                     contract_definitions.push((name, 0..source.len()));
                 }
             }
+            MultiCompilerLanguage::Solc(_) if path.extension().is_some_and(|ext| ext == "yul") => {
+                // Yul files contain a single top-level object, which is named like its artifact.
+                let name = source
+                    .split_once("object \"")
+                    .and_then(|(_, rest)| rest.split_once('"'))
+                    .map(|(name, _)| name.to_string());
+                if let Some(name) = name {
+                    contract_definitions.push((name, 0..source.len()));
+                }
+            }
             MultiCompilerLanguage::Solc(_) => {
                 let sess = Session::builder().with_silent_emitter(None).build();
                 let _ = sess.enter(|| -> solar_parse::interface::Result<()> {
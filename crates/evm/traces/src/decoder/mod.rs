@@ -11,6 +11,7 @@
     map::{hash_map::Entry, HashMap},
     Address, LogData, Selector, B256,
 };
+use alloy_sol_types::{Panic, Revert, SolError};
 use foundry_common::{
     abi::get_indexed_event, fmt::format_token, get_contract_name, ContractsByArtifact, SELECTOR_LEN,
 };
@@ -665,6 +666,32 @@ pub async fn prefetch_signatures(&self, nodes: &[CallTraceNode]) {
         identifier.write().await.identify_functions(funcs_it).await;
     }
 
+    /// Identifies the custom errors of the given revert data which are not known yet, and adds
+    /// them to the revert decoder.
+    pub async fn identify_errors<'a>(&mut self, revert_data: impl IntoIterator<Item = &'a [u8]>) {
+        let Some(identifier) = self.signature_identifier.clone() else { return };
+
+        let selectors = revert_data
+            .into_iter()
+            .filter_map(|data| data.get(..SELECTOR_LEN))
+            .map(Selector::from_slice)
+            .filter(|selector| {
+                *selector != Revert::SELECTOR &&
+                    *selector != Panic::SELECTOR &&
+                    !self.revert_decoder.errors.contains_key(selector)
+            })
+            .unique()
+            .collect::<Vec<_>>();
+        if selectors.is_empty() {
+            return;
+        }
+
+        let errors = identifier.write().await.identify_errors(&selectors).await;
+        for error in errors.into_iter().flatten() {
+            self.revert_decoder.push_error(error);
+        }
+    }
+
     /// Pretty-prints a value.
     fn format_value(&self, value: &DynSolValue) -> String {
         if let DynSolValue::Address(addr) = value {
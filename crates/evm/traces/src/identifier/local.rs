@@ -1,6 +1,6 @@
 use super::{AddressIdentity, TraceIdentifier};
 use alloy_json_abi::JsonAbi;
-use alloy_primitives::Address;
+use alloy_primitives::{map::AddressHashMap, Address, Bytes};
 use foundry_common::contracts::{bytecode_diff_score, ContractsByArtifact};
 use foundry_compilers::ArtifactId;
 use std::borrow::Cow;
@@ -11,6 +11,8 @@ pub struct LocalTraceIdentifier<'a> {
     known_contracts: &'a ContractsByArtifact,
     /// Vector of pairs of artifact ID and the runtime code length of the given artifact.
     ordered_ids: Vec<(&'a ArtifactId, usize)>,
+    /// Runtime code of the accounts that were not created in the traces.
+    runtime_codes: Option<&'a AddressHashMap<Bytes>>,
 }
 
 impl<'a> LocalTraceIdentifier<'a> {
@@ -23,7 +25,15 @@ pub fn new(known_contracts: &'a ContractsByArtifact) -> Self {
             .map(|(id, bytecode)| (id, bytecode.len()))
             .collect::<Vec<_>>();
         ordered_ids.sort_by_key(|(_, len)| *len);
-        Self { known_contracts, ordered_ids }
+        Self { known_contracts, ordered_ids, runtime_codes: None }
+    }
+
+    /// Sets the runtime code of the accounts that were not created in the traces, e.g. contracts
+    /// already deployed on a fork, so that they can be identified by their deployed bytecode.
+    #[inline]
+    pub fn with_runtime_codes(mut self, runtime_codes: &'a AddressHashMap<Bytes>) -> Self {
+        self.runtime_codes = Some(runtime_codes);
+        self
     }
 
     /// Returns the known contracts.
@@ -138,7 +148,19 @@ fn identify_addresses<'a, A>(&mut self, addresses: A) -> Vec<AddressIdentity<'_>
                 let _span = trace_span!(target: "evm::traces", "identify", %address).entered();
 
                 trace!(target: "evm::traces", "identifying");
-                let (id, abi) = self.identify_code(runtime_code?, creation_code?)?;
+                let (id, abi) = match (runtime_code, creation_code) {
+                    (Some(runtime_code), Some(creation_code)) => {
+                        self.identify_code(runtime_code, creation_code)?
+                    }
+                    // Without the creation code, only an exact match of the deployed bytecode is
+                    // accepted.
+                    _ => {
+                        let code = self.runtime_codes?.get(address)?;
+                        let (id, contract) =
+                            self.known_contracts.find_by_deployed_code_exact(code)?;
+                        (id, &contract.abi)
+                    }
+                };
                 trace!(target: "evm::traces", id=%id.identifier(), "identified");
 
                 Some(AddressIdentity {
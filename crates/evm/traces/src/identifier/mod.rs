@@ -1,5 +1,5 @@
 use alloy_json_abi::JsonAbi;
-use alloy_primitives::Address;
+use alloy_primitives::{map::AddressHashMap, Address, Bytes};
 use foundry_common::ContractsByArtifact;
 use foundry_compilers::ArtifactId;
 use foundry_config::{Chain, Config};
@@ -80,6 +80,13 @@ pub fn with_local(mut self, known_contracts: &'a ContractsByArtifact) -> Self {
         self
     }
 
+    /// Sets the runtime code of the accounts that were not created in the traces, for the local
+    /// identifier to match them against the deployed bytecode of the known contracts.
+    pub fn with_runtime_codes(mut self, runtime_codes: &'a AddressHashMap<Bytes>) -> Self {
+        self.local = self.local.map(|local| local.with_runtime_codes(runtime_codes));
+        self
+    }
+
     /// Sets the etherscan identifier.
     pub fn with_etherscan(mut self, config: &Config, chain: Option<Chain>) -> eyre::Result<Self> {
         self.etherscan = EtherscanIdentifier::new(config, chain)?;
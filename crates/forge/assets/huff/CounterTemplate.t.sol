@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Test, console} from "forge-std/Test.sol";
+import {HuffDeployer} from "../src/utils/HuffDeployer.sol";
+import {ICounter} from "../src/interface/ICounter.sol";
+
+contract CounterTest is Test {
+    ICounter public counter;
+
+    function setUp() public {
+        counter = ICounter(HuffDeployer.deploy("Counter"));
+        counter.setNumber(0);
+    }
+
+    function test_Increment() public {
+        counter.increment();
+        assertEq(counter.number(), 1);
+    }
+
+    function testFuzz_SetNumber(uint256 x) public {
+        counter.setNumber(x);
+        assertEq(counter.number(), x);
+    }
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Vm} from "forge-std/Vm.sol";
+
+/// @notice Compiles Huff contracts with `huffc` through FFI and deploys them.
+/// @dev Requires `ffi = true` and `huffc` to be available in `PATH`.
+library HuffDeployer {
+    Vm private constant vm = Vm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    /// @notice Deploys `src/<fileName>.huff` without constructor arguments.
+    function deploy(string memory fileName) internal returns (address) {
+        return deploy(fileName, "");
+    }
+
+    /// @notice Deploys `src/<fileName>.huff` with the ABI-encoded constructor `args`.
+    function deploy(string memory fileName, bytes memory args) internal returns (address deployed) {
+        string[] memory cmds = new string[](3);
+        cmds[0] = "huffc";
+        cmds[1] = "-b";
+        cmds[2] = string.concat("src/", fileName, ".huff");
+
+        bytes memory bytecode = abi.encodePacked(vm.ffi(cmds), args);
+        assembly {
+            deployed := create(0, add(bytecode, 0x20), mload(bytecode))
+        }
+        require(deployed != address(0), "HuffDeployer: deployment failed");
+    }
+}
@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Script, console} from "forge-std/Script.sol";
+import {ICounter} from "../src/interface/ICounter.sol";
+
+contract CounterScript is Script {
+    ICounter public counter;
+
+    function setUp() public {}
+
+    function run() public {
+        vm.startBroadcast();
+
+        counter = ICounter(deployCode("Counter.vy"));
+
+        vm.stopBroadcast();
+    }
+}
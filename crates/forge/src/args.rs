@@ -1,5 +1,5 @@
 use crate::{
-    cmd::{cache::CacheSubcommands, generate::GenerateSubcommands, watch},
+    cmd::{cache::CacheSubcommands, clean, generate::GenerateSubcommands, watch},
     opts::{Forge, ForgeSubcommand},
 };
 use clap::{CommandFactory, Parser};
@@ -36,6 +36,7 @@ pub fn run_command(args: Forge) -> Result<()> {
         ForgeSubcommand::Test(_) => ForgeContext::Test,
         ForgeSubcommand::Coverage(_) => ForgeContext::Coverage,
         ForgeSubcommand::Snapshot(_) => ForgeContext::Snapshot,
+        ForgeSubcommand::GasCheck(_) => ForgeContext::Test,
         ForgeSubcommand::Script(cmd) => {
             if cmd.broadcast {
                 ForgeContext::ScriptBroadcast
@@ -68,7 +69,10 @@ pub fn run_command(args: Forge) -> Result<()> {
                 utils::block_on(cmd.run())
             }
         }
+        ForgeSubcommand::Prove(cmd) => cmd.run(),
+        ForgeSubcommand::Mutate(cmd) => cmd.run(),
         ForgeSubcommand::Bind(cmd) => cmd.run(),
+        ForgeSubcommand::BindTs(cmd) => cmd.run(),
         ForgeSubcommand::Build(cmd) => {
             if cmd.is_watch() {
                 utils::block_on(watch::watch_build(cmd))
@@ -90,6 +94,7 @@ pub fn run_command(args: Forge) -> Result<()> {
         ForgeSubcommand::Remove(cmd) => cmd.run(),
         ForgeSubcommand::Remappings(cmd) => cmd.run(),
         ForgeSubcommand::Init(cmd) => cmd.run(),
+        ForgeSubcommand::Migrate(cmd) => cmd.run(),
         ForgeSubcommand::Completions { shell } => {
             generate(shell, &mut Forge::command(), "forge", &mut std::io::stdout());
             Ok(())
@@ -103,10 +108,14 @@ pub fn run_command(args: Forge) -> Result<()> {
             );
             Ok(())
         }
-        ForgeSubcommand::Clean { root } => {
+        ForgeSubcommand::Clean { root, stale } => {
             let config = utils::load_config_with_root(root.as_deref())?;
             let project = config.project()?;
-            config.cleanup(&project)?;
+            if stale {
+                clean::prune_stale(&project)?.print()?;
+            } else {
+                config.cleanup(&project)?;
+            }
             Ok(())
         }
         ForgeSubcommand::Snapshot(cmd) => {
@@ -116,6 +125,7 @@ pub fn run_command(args: Forge) -> Result<()> {
                 utils::block_on(cmd.run())
             }
         }
+        ForgeSubcommand::GasCheck(cmd) => utils::block_on(cmd.run()),
         ForgeSubcommand::Fmt(cmd) => {
             if cmd.is_watch() {
                 utils::block_on(watch::watch_fmt(cmd))
@@ -126,6 +136,8 @@ pub fn run_command(args: Forge) -> Result<()> {
         ForgeSubcommand::Config(cmd) => cmd.run(),
         ForgeSubcommand::Flatten(cmd) => cmd.run(),
         ForgeSubcommand::Inspect(cmd) => cmd.run(),
+        ForgeSubcommand::StorageDiff(cmd) => utils::block_on(cmd.run()),
+        ForgeSubcommand::ValidateUpgrade(cmd) => cmd.run(),
         ForgeSubcommand::Tree(cmd) => cmd.run(),
         ForgeSubcommand::Geiger(cmd) => {
             let n = cmd.run()?;
@@ -134,6 +146,13 @@ pub fn run_command(args: Forge) -> Result<()> {
             }
             Ok(())
         }
+        ForgeSubcommand::AuditDeps(cmd) => {
+            let n = utils::block_on(cmd.run())?;
+            if n > 0 {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
         ForgeSubcommand::Doc(cmd) => {
             if cmd.is_watch() {
                 utils::block_on(watch::watch_doc(cmd))
@@ -145,6 +164,7 @@ pub fn run_command(args: Forge) -> Result<()> {
         ForgeSubcommand::Selectors { command } => utils::block_on(command.run()),
         ForgeSubcommand::Generate(cmd) => match cmd.sub {
             GenerateSubcommands::Test(cmd) => cmd.run(),
+            GenerateSubcommands::InvariantHandler(cmd) => cmd.run(),
         },
         ForgeSubcommand::Compiler(cmd) => cmd.run(),
         ForgeSubcommand::Soldeer(cmd) => utils::block_on(cmd.run()),
@@ -0,0 +1,384 @@
+//! `forge audit-deps`: checks the installed dependencies against known vulnerabilities.
+//!
+//! Dependencies are identified by the `package.json` they ship with, and looked up in the
+//! [OSV](https://osv.dev) database, which aggregates the GitHub security advisories of npm
+//! packages such as `@openzeppelin/contracts`.
+
+use clap::{Parser, ValueHint};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Table};
+use eyre::{Context, Result};
+use foundry_cli::utils::LoadConfig;
+use foundry_common::{fs, shell};
+use foundry_compilers::{resolver::parse::SolData, Graph};
+use foundry_config::{impl_figment_convert_basic, Config};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The default URL of the OSV API.
+pub const DEFAULT_OSV_API: &str = "https://api.osv.dev";
+
+/// The OSV ecosystem of Solidity packages.
+const ECOSYSTEM: &str = "npm";
+
+/// CLI arguments for `forge audit-deps`.
+#[derive(Clone, Debug, Parser)]
+pub struct AuditDepsArgs {
+    /// The project's root path.
+    ///
+    /// By default root of the Git repository, if in one,
+    /// or the current working directory.
+    #[arg(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
+    root: Option<PathBuf>,
+
+    /// Read the advisories from a JSON file of OSV records instead of querying the OSV API.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "FILE")]
+    advisories: Option<PathBuf>,
+
+    /// The URL of the OSV API.
+    #[arg(long, env = "OSV_API_URL", value_name = "URL", default_value = DEFAULT_OSV_API)]
+    osv_url: String,
+}
+impl_figment_convert_basic!(AuditDepsArgs);
+
+impl AuditDepsArgs {
+    /// Returns the number of vulnerabilities found.
+    pub async fn run(self) -> Result<usize> {
+        let config = self.load_config()?;
+        let packages = installed_packages(&config)?;
+
+        let local = match &self.advisories {
+            Some(path) => {
+                Some(fs::read_json_file::<Vec<Advisory>>(path).wrap_err_with(|| {
+                    format!("failed to read advisories from {}", path.display())
+                })?)
+            }
+            None => None,
+        };
+
+        let mut findings = Vec::new();
+        for package in &packages {
+            let advisories = match &local {
+                Some(advisories) => advisories
+                    .iter()
+                    .filter(|advisory| advisory.affects(&package.name, &package.version))
+                    .cloned()
+                    .collect(),
+                None => query_osv(&self.osv_url, package).await?,
+            };
+            for advisory in advisories {
+                findings.push(Finding {
+                    dependency: package.dir.clone(),
+                    package: package.name.clone(),
+                    version: package.version.to_string(),
+                    fixed: advisory.fixed_versions(&package.name),
+                    id: advisory.id,
+                    aliases: advisory.aliases,
+                    summary: advisory.summary.unwrap_or_default(),
+                    imported_by: Vec::new(),
+                });
+            }
+        }
+
+        if !findings.is_empty() {
+            importing_files(&config, &mut findings)?;
+        }
+
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string_pretty(&findings)?)?;
+        } else if findings.is_empty() {
+            sh_println!(
+                "No known vulnerabilities found in {} audited dependencies.",
+                packages.len()
+            )?;
+        } else {
+            let mut table = Table::new();
+            table.apply_modifier(UTF8_ROUND_CORNERS);
+            table.set_header(["Dependency", "Version", "Advisory", "Fixed in", "Imported by"]);
+            for finding in &findings {
+                let mut advisory = finding.id.clone();
+                if !finding.summary.is_empty() {
+                    advisory = format!("{advisory}\n{}", finding.summary);
+                }
+                table.add_row([
+                    format!("{}\n{}", finding.dependency.display(), finding.package),
+                    finding.version.clone(),
+                    advisory,
+                    finding.fixed.join(", "),
+                    finding
+                        .imported_by
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ]);
+            }
+            sh_println!("{} vulnerabilities found:", findings.len())?;
+            sh_println!("\n{table}\n")?;
+        }
+
+        Ok(findings.len())
+    }
+}
+
+/// A package installed in one of the libraries directories.
+#[derive(Clone, Debug)]
+struct InstalledPackage {
+    /// The installation directory, relative to the project root.
+    dir: PathBuf,
+    /// The npm package name.
+    name: String,
+    version: Version,
+}
+
+#[derive(Deserialize)]
+struct PackageJson {
+    name: String,
+    version: String,
+}
+
+/// Returns all installed packages which can be identified by their `package.json`.
+///
+/// Git repositories of Solidity libraries often keep the published package in `contracts/`, so
+/// its `package.json` is preferred over the one of the repository.
+fn installed_packages(config: &Config) -> Result<Vec<InstalledPackage>> {
+    let mut packages = Vec::new();
+    for lib in &config.libs {
+        let lib = config.root.join(lib);
+        let Ok(entries) = std::fs::read_dir(&lib) else { continue };
+        let mut dirs = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        dirs.sort();
+        for dir in dirs {
+            let Some(package) = ["contracts/package.json", "package.json"]
+                .iter()
+                .map(|manifest| dir.join(manifest))
+                .find(|manifest| manifest.exists())
+            else {
+                trace!(?dir, "skipping dependency without package.json");
+                continue;
+            };
+            let Ok(PackageJson { name, version }) = fs::read_json_file(&package) else {
+                trace!(?package, "skipping invalid package.json");
+                continue;
+            };
+            let Ok(version) = Version::parse(&version) else {
+                trace!(?package, ?version, "skipping package without semver version");
+                continue;
+            };
+            let dir = dir.strip_prefix(&config.root).unwrap_or(&dir).to_path_buf();
+            packages.push(InstalledPackage { dir, name, version });
+        }
+    }
+    Ok(packages)
+}
+
+/// Queries the advisories affecting `package` from the OSV API.
+async fn query_osv(url: &str, package: &InstalledPackage) -> Result<Vec<Advisory>> {
+    #[derive(Deserialize)]
+    struct Response {
+        #[serde(default)]
+        vulns: Vec<Advisory>,
+    }
+
+    let body = serde_json::json!({
+        "package": { "name": package.name, "ecosystem": ECOSYSTEM },
+        "version": package.version.to_string(),
+    });
+    let response: Response = reqwest::Client::new()
+        .post(format!("{}/v1/query", url.trim_end_matches('/')))
+        .json(&body)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .wrap_err_with(|| format!("failed to query advisories for {}", package.name))?
+        .json()
+        .await?;
+    Ok(response.vulns)
+}
+
+/// Records the project files which import an affected dependency, directly or transitively.
+fn importing_files(config: &Config, findings: &mut [Finding]) -> Result<()> {
+    let paths = config.project_paths();
+    let graph = Graph::<SolData>::resolve(&paths)?;
+    for file in graph.files().keys().filter(|file| !paths.has_library_ancestor(file)) {
+        let imports = graph.imports(file);
+        for finding in findings.iter_mut() {
+            let dependency = config.root.join(&finding.dependency);
+            if imports.iter().any(|import| import.starts_with(&dependency)) {
+                finding
+                    .imported_by
+                    .push(file.strip_prefix(&config.root).unwrap_or(file).to_path_buf());
+            }
+        }
+    }
+    for finding in findings {
+        finding.imported_by.sort();
+    }
+    Ok(())
+}
+
+/// A vulnerable dependency.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Finding {
+    dependency: PathBuf,
+    package: String,
+    version: String,
+    id: String,
+    aliases: Vec<String>,
+    summary: String,
+    fixed: Vec<String>,
+    imported_by: Vec<PathBuf>,
+}
+
+/// A vulnerability record in the [OSV format](https://ossf.github.io/osv-schema/).
+#[derive(Clone, Debug, Deserialize)]
+struct Advisory {
+    id: String,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Affected {
+    package: AffectedPackage,
+    #[serde(default)]
+    ranges: Vec<AffectedRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct AffectedPackage {
+    name: String,
+    ecosystem: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct AffectedRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<RangeEvent>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct RangeEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+impl Advisory {
+    /// Returns the entries affecting the npm package `name`.
+    fn affected_entries<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Affected> {
+        self.affected.iter().filter(move |affected| {
+            affected.package.name == name && affected.package.ecosystem == ECOSYSTEM
+        })
+    }
+
+    /// Returns `true` if `version` of the npm package `name` is affected.
+    fn affects(&self, name: &str, version: &Version) -> bool {
+        self.affected_entries(name).any(|affected| {
+            affected.versions.iter().any(|v| Version::parse(v).is_ok_and(|v| v == *version)) ||
+                affected
+                    .ranges
+                    .iter()
+                    .filter(|range| range.kind != "GIT")
+                    .any(|range| range.contains(version))
+        })
+    }
+
+    /// Returns the versions of the npm package `name` which fix the vulnerability.
+    fn fixed_versions(&self, name: &str) -> Vec<String> {
+        let mut fixed = self
+            .affected_entries(name)
+            .flat_map(|affected| &affected.ranges)
+            .flat_map(|range| &range.events)
+            .filter_map(|event| event.fixed.clone())
+            .collect::<Vec<_>>();
+        fixed.sort_by_key(|v| Version::parse(v).ok());
+        fixed.dedup();
+        fixed
+    }
+}
+
+impl AffectedRange {
+    /// Returns `true` if `version` is within the range, evaluating the events in version order.
+    fn contains(&self, version: &Version) -> bool {
+        let parse = |v: &str| if v == "0" { Some(Version::new(0, 0, 0)) } else { v.parse().ok() };
+        let mut events = self
+            .events
+            .iter()
+            .filter_map(|event| {
+                if let Some(v) = &event.introduced {
+                    parse(v).map(|v| (v, true, false))
+                } else if let Some(v) = &event.fixed {
+                    parse(v).map(|v| (v, false, false))
+                } else {
+                    event.last_affected.as_deref().and_then(parse).map(|v| (v, false, true))
+                }
+            })
+            .collect::<Vec<_>>();
+        events.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+        let mut affected = false;
+        for (v, introduced, last_affected) in events {
+            if introduced {
+                if *version >= v {
+                    affected = true;
+                }
+            } else if last_affected {
+                if *version > v {
+                    affected = false;
+                }
+            } else if *version >= v {
+                affected = false;
+            }
+        }
+        affected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_affected_ranges() {
+        let advisory: Advisory = serde_json::from_str(
+            r#"{
+                "id": "GHSA-xxxx",
+                "affected": [{
+                    "package": { "name": "@openzeppelin/contracts", "ecosystem": "npm" },
+                    "ranges": [{
+                        "type": "SEMVER",
+                        "events": [
+                            { "introduced": "4.0.0" }, { "fixed": "4.9.3" },
+                            { "introduced": "5.0.0" }, { "last_affected": "5.0.1" }
+                        ]
+                    }],
+                    "versions": ["3.4.2"]
+                }]
+            }"#,
+        )
+        .unwrap();
+        let affects = |v: &str| advisory.affects("@openzeppelin/contracts", &v.parse().unwrap());
+        assert!(affects("3.4.2"));
+        assert!(!affects("3.4.1"));
+        assert!(affects("4.0.0"));
+        assert!(affects("4.9.2"));
+        assert!(!affects("4.9.3"));
+        assert!(affects("5.0.1"));
+        assert!(!affects("5.0.2"));
+        assert!(!advisory.affects("solady", &"4.9.2".parse().unwrap()));
+        assert_eq!(advisory.fixed_versions("@openzeppelin/contracts"), ["4.9.3"]);
+    }
+}
@@ -4,7 +4,7 @@
 use forge_sol_macro_gen::{MultiSolMacroGen, SolMacroGen};
 use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
 use foundry_common::{compile::ProjectCompiler, fs::json_files};
-use foundry_config::impl_figment_convert;
+use foundry_config::{filter::SkipBuildFilter, impl_figment_convert};
 use regex::Regex;
 use std::{
     fs,
@@ -67,6 +67,10 @@ pub struct BindArgs {
     #[arg(long)]
     single_file: bool,
 
+    /// Generate each contract as a directory module, with the helpers in a separate file.
+    #[arg(long, conflicts_with = "single_file")]
+    module_per_contract: bool,
+
     /// Skip Cargo.toml consistency checks.
     #[arg(long)]
     skip_cargo_toml: bool,
@@ -131,57 +135,12 @@ pub fn run(self) -> Result<()> {
     }
 
     fn get_filter(&self) -> Result<Filter> {
-        if self.select_all {
-            // Select all json files
-            return Ok(Filter::All);
-        }
-        if !self.select.is_empty() {
-            // Return json files that match the select regex
-            return Ok(Filter::Select(self.select.clone()));
-        }
-
-        if let Some(skip) = self.build.skip.as_ref().filter(|s| !s.is_empty()) {
-            return Ok(Filter::Skip(
-                skip.clone()
-                    .into_iter()
-                    .map(|s| Regex::new(s.file_pattern()))
-                    .collect::<Result<Vec<_>, _>>()?,
-            ));
-        }
-
-        // Exclude defaults
-        Ok(Filter::skip_default())
+        Filter::new(&self.select, self.select_all, self.build.skip.as_deref())
     }
 
     /// Returns an iterator over the JSON files and the contract name in the `artifacts` directory.
     fn get_json_files(&self, artifacts: &Path) -> Result<impl Iterator<Item = (String, PathBuf)>> {
-        let filter = self.get_filter()?;
-        Ok(json_files(artifacts)
-            .filter_map(|path| {
-                // Ignore the build info JSON.
-                if path.to_str()?.contains("build-info") {
-                    return None;
-                }
-
-                // Ignore the `target` directory in case the user has built the project.
-                if path.iter().any(|comp| comp == "target") {
-                    return None;
-                }
-
-                // We don't want `.metadata.json` files.
-                let stem = path.file_stem()?.to_str()?;
-                if stem.ends_with(".metadata") {
-                    return None;
-                }
-
-                let name = stem.split('.').next().unwrap();
-
-                // Best effort identifier cleanup.
-                let name = name.replace(char::is_whitespace, "").replace('-', "_");
-
-                Some((name, path))
-            })
-            .filter(move |(name, _path)| filter.is_match(name)))
+        Ok(artifact_files(artifacts, self.get_filter()?))
     }
 
     fn get_solmacrogen(&self, artifacts: &Path) -> Result<MultiSolMacroGen> {
@@ -198,7 +157,8 @@ fn get_solmacrogen(&self, artifacts: &Path) -> Result<MultiSolMacroGen> {
             })
             .collect::<Vec<_>>();
 
-        let multi = MultiSolMacroGen::new(artifacts, instances);
+        let multi = MultiSolMacroGen::new(artifacts, instances)
+            .with_module_per_contract(self.module_per_contract);
         eyre::ensure!(!multi.instances.is_empty(), "No contract artifacts found");
         Ok(multi)
     }
@@ -258,6 +218,33 @@ pub enum Filter {
 }
 
 impl Filter {
+    /// Returns the filter of the `--select`, `--select-all` and `--skip` arguments.
+    ///
+    /// By default, test, script and `forge-std` contracts are skipped.
+    pub fn new(
+        select: &[Regex],
+        select_all: bool,
+        skip: Option<&[SkipBuildFilter]>,
+    ) -> Result<Self> {
+        if select_all {
+            // Select all json files
+            return Ok(Self::All);
+        }
+        if !select.is_empty() {
+            // Return json files that match the select regex
+            return Ok(Self::Select(select.to_vec()));
+        }
+
+        if let Some(skip) = skip.filter(|s| !s.is_empty()) {
+            return Ok(Self::Skip(
+                skip.iter().map(|s| Regex::new(s.file_pattern())).collect::<Result<Vec<_>, _>>()?,
+            ));
+        }
+
+        // Exclude defaults
+        Ok(Self::skip_default())
+    }
+
     pub fn is_match(&self, name: &str) -> bool {
         match self {
             Self::All => true,
@@ -284,3 +271,34 @@ pub fn skip_default() -> Self {
         Self::Skip(skip)
     }
 }
+
+/// Returns an iterator over the JSON files and the contract name in the `artifacts` directory
+/// which match `filter`.
+pub fn artifact_files(artifacts: &Path, filter: Filter) -> impl Iterator<Item = (String, PathBuf)> {
+    json_files(artifacts)
+        .filter_map(|path| {
+            // Ignore the build info JSON.
+            if path.to_str()?.contains("build-info") {
+                return None;
+            }
+
+            // Ignore the `target` directory in case the user has built the project.
+            if path.iter().any(|comp| comp == "target") {
+                return None;
+            }
+
+            // We don't want `.metadata.json` files.
+            let stem = path.file_stem()?.to_str()?;
+            if stem.ends_with(".metadata") {
+                return None;
+            }
+
+            let name = stem.split('.').next().unwrap();
+
+            // Best effort identifier cleanup.
+            let name = name.replace(char::is_whitespace, "").replace('-', "_");
+
+            Some((name, path))
+        })
+        .filter(move |(name, _path)| filter.is_match(name))
+}
@@ -0,0 +1,294 @@
+use crate::cmd::bind::{artifact_files, Filter};
+use clap::{Parser, ValueHint};
+use eyre::Result;
+use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
+use foundry_common::{compile::ProjectCompiler, fs};
+use foundry_config::impl_figment_convert;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+impl_figment_convert!(BindTsArgs, build);
+
+const HEADER: &str = "// This file is generated by `forge bind-ts`, do not edit.\n";
+
+/// CLI arguments for `forge bind-ts`.
+#[derive(Clone, Debug, Parser)]
+pub struct BindTsArgs {
+    /// Path to the directory to write the bindings to.
+    ///
+    /// Defaults to `<out>/bindings-ts`.
+    #[arg(
+        long = "bindings-path",
+        short,
+        value_hint = ValueHint::DirPath,
+        value_name = "PATH"
+    )]
+    pub bindings: Option<PathBuf>,
+
+    /// Create bindings only for contracts whose names match the specified filter(s)
+    #[arg(long)]
+    pub select: Vec<regex::Regex>,
+
+    /// Explicitly generate bindings for all contracts
+    ///
+    /// By default all contracts ending with `Test` or `Script` are excluded.
+    #[arg(long, conflicts_with_all = &["select", "skip"])]
+    pub select_all: bool,
+
+    /// Overwrite existing generated bindings.
+    ///
+    /// By default, the command will check that the bindings are correct, and then exit. If
+    /// --overwrite is passed, it will instead delete and overwrite the bindings.
+    #[arg(long)]
+    overwrite: bool,
+
+    /// Skips running forge build before generating binding
+    #[arg(long)]
+    skip_build: bool,
+
+    #[command(flatten)]
+    build: BuildOpts,
+}
+
+impl BindTsArgs {
+    pub fn run(self) -> Result<()> {
+        if !self.skip_build {
+            let project = self.build.project()?;
+            let _ = ProjectCompiler::new().compile(&project)?;
+        }
+
+        let config = self.load_config()?;
+        let bindings_root = self.bindings.clone().unwrap_or_else(|| config.out.join("bindings-ts"));
+
+        let filter = Filter::new(&self.select, self.select_all, self.build.skip.as_deref())?;
+        let contracts = contracts(&config.out, filter)?;
+        eyre::ensure!(!contracts.is_empty(), "No contract artifacts found");
+        let addresses = deployed_addresses(&config.broadcast, &contracts)?;
+        let files = generate(&contracts, &addresses);
+
+        if bindings_root.exists() {
+            if !self.overwrite {
+                sh_println!("Bindings found. Checking for consistency.")?;
+                for (name, content) in &files {
+                    let path = bindings_root.join(name);
+                    if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+                        eyre::bail!(
+                            "Bindings are out of date: `{}` differs. Run with `--overwrite` to \
+                             regenerate them.",
+                            path.display()
+                        );
+                    }
+                }
+                sh_println!("OK.")?;
+                return Ok(());
+            }
+
+            trace!(?bindings_root, "Removing existing bindings");
+            fs::remove_dir_all(&bindings_root)?;
+        }
+
+        sh_println!("Generating bindings for {} contracts", contracts.len())?;
+        fs::create_dir_all(&bindings_root)?;
+        for (name, content) in files {
+            fs::write(bindings_root.join(name), content)?;
+        }
+
+        sh_println!("Bindings have been generated to {}", bindings_root.display())?;
+        Ok(())
+    }
+}
+
+/// The ABI and creation bytecode of a contract artifact.
+#[derive(Clone, Debug)]
+struct Contract {
+    abi: Value,
+    bytecode: Option<String>,
+}
+
+/// Reads the ABIs and bytecodes of the artifacts in `artifacts` matching `filter`, by name.
+fn contracts(artifacts: &Path, filter: Filter) -> Result<BTreeMap<String, Contract>> {
+    let mut contracts = BTreeMap::new();
+    let mut dup = HashSet::new();
+    for (name, path) in artifact_files(artifacts, filter) {
+        if !dup.insert(name.clone()) {
+            continue;
+        }
+        trace!(?path, "reading artifact");
+        let mut artifact: Value = fs::read_json_file(&path)?;
+        let abi = artifact["abi"].take();
+        if !abi.is_array() {
+            continue;
+        }
+        // abstract contracts and interfaces have no bytecode
+        let bytecode = artifact["bytecode"]["object"]
+            .as_str()
+            .filter(|code| !code.is_empty() && *code != "0x")
+            .map(|code| format!("0x{}", code.trim_start_matches("0x")));
+        contracts.insert(name, Contract { abi, bytecode });
+    }
+    Ok(contracts)
+}
+
+/// Collects the addresses of the contracts deployed by the latest broadcasted script runs, by
+/// chain id.
+///
+/// When a contract is deployed multiple times, the address of the latest deployment is kept.
+fn deployed_addresses(
+    broadcast: &Path,
+    contracts: &BTreeMap<String, Contract>,
+) -> Result<BTreeMap<u64, BTreeMap<String, String>>> {
+    let mut runs = Vec::new();
+    // broadcast/<script>/<chain id>/run-latest.json
+    for script in read_dirs(broadcast)? {
+        for chain in read_dirs(&script)? {
+            let path = chain.join("run-latest.json");
+            if path.is_file() {
+                let sequence: Value = fs::read_json_file(&path)?;
+                runs.push(sequence);
+            }
+        }
+    }
+    runs.sort_by_key(|sequence| sequence["timestamp"].as_u64().unwrap_or_default());
+
+    let mut addresses: BTreeMap<_, BTreeMap<_, _>> = BTreeMap::new();
+    for sequence in runs {
+        let Some(chain) = sequence["chain"].as_u64() else { continue };
+        for tx in sequence["transactions"].as_array().into_iter().flatten() {
+            if !matches!(tx["transactionType"].as_str(), Some("CREATE" | "CREATE2")) {
+                continue;
+            }
+            let (Some(name), Some(address)) =
+                (tx["contractName"].as_str(), tx["contractAddress"].as_str())
+            else {
+                continue;
+            };
+            if contracts.contains_key(name) {
+                addresses.entry(chain).or_default().insert(name.to_string(), address.to_string());
+            }
+        }
+    }
+    Ok(addresses)
+}
+
+/// Returns the subdirectories of `dir`, sorted.
+fn read_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut dirs = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Generates the binding files, by file name.
+///
+/// Each contract has its own module exporting its `as const` ABI, which makes viem infer the
+/// types of calls and events, and, if it is deployable, its bytecode and a `{ abi, bytecode }`
+/// factory accepted by both viem's `deployContract` and ethers' `ContractFactory`.
+fn generate(
+    contracts: &BTreeMap<String, Contract>,
+    addresses: &BTreeMap<u64, BTreeMap<String, String>>,
+) -> BTreeMap<String, String> {
+    let mut files = BTreeMap::new();
+    let mut index = String::from(HEADER);
+    index.push('\n');
+
+    for (name, contract) in contracts {
+        let ident = camel_case(name);
+        let abi = serde_json::to_string_pretty(&contract.abi).unwrap_or_default();
+        let mut source = format!("{HEADER}\nexport const {ident}Abi = {abi} as const;\n");
+        if let Some(bytecode) = &contract.bytecode {
+            let _ = write!(
+                source,
+                "\nexport const {ident}Bytecode = \"{bytecode}\" as const;\n\n\
+                 export const {ident}Factory = {{\n  abi: {ident}Abi,\n  bytecode: {ident}Bytecode,\n}} as const;\n"
+            );
+        }
+        files.insert(format!("{name}.ts"), source);
+        let _ = writeln!(index, "export * from \"./{name}\";");
+    }
+
+    let mut source = format!("{HEADER}\nexport const addresses = {{\n");
+    for (chain, deployments) in addresses {
+        let _ = writeln!(source, "  {chain}: {{");
+        for (name, address) in deployments {
+            let _ = writeln!(source, "    {name}: \"{address}\",");
+        }
+        source.push_str("  },\n");
+    }
+    source.push_str("} as const;\n");
+    files.insert("addresses.ts".to_string(), source);
+    let _ = writeln!(index, "export * from \"./addresses\";");
+
+    files.insert("index.ts".to_string(), index);
+    files
+}
+
+/// Converts a contract name to a camelCase identifier, e.g. `ERC20Token` to `erc20Token`.
+fn camel_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let upper = chars.iter().take_while(|c| c.is_ascii_uppercase()).count();
+    // keep the first letter of the next word in an acronym prefix, e.g. `USDCoin`
+    let lower = if upper > 1 && chars.get(upper).is_some_and(|c| c.is_ascii_lowercase()) {
+        upper - 1
+    } else {
+        upper
+    };
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| if i < lower { c.to_ascii_lowercase() } else { *c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_names_to_camel_case() {
+        assert_eq!(camel_case("Counter"), "counter");
+        assert_eq!(camel_case("ERC20"), "erc20");
+        assert_eq!(camel_case("ERC20Token"), "erc20Token");
+        assert_eq!(camel_case("USDCoin"), "usdCoin");
+        assert_eq!(camel_case("counter"), "counter");
+    }
+
+    #[test]
+    fn generates_modules() {
+        let contracts = BTreeMap::from([
+            (
+                "Counter".to_string(),
+                Contract { abi: serde_json::json!([]), bytecode: Some("0x6080".to_string()) },
+            ),
+            ("ICounter".to_string(), Contract { abi: serde_json::json!([]), bytecode: None }),
+        ]);
+        let addresses = BTreeMap::from([(
+            1,
+            BTreeMap::from([(
+                "Counter".to_string(),
+                "0x0000000000000000000000000000000000000001".to_string(),
+            )]),
+        )]);
+        let files = generate(&contracts, &addresses);
+
+        assert!(files["Counter.ts"].contains("export const counterBytecode = \"0x6080\" as const;"));
+        assert!(files["Counter.ts"].contains("export const counterFactory = {"));
+        assert!(files["ICounter.ts"].contains("export const iCounterAbi = [] as const;"));
+        assert!(!files["ICounter.ts"].contains("Factory"));
+        assert!(files["addresses.ts"].contains(
+            "  1: {\n    Counter: \"0x0000000000000000000000000000000000000001\",\n  },"
+        ));
+        assert!(files["index.ts"].contains("export * from \"./ICounter\";"));
+    }
+}
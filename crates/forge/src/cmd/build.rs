@@ -1,8 +1,15 @@
 use super::{install, watch::WatchArgs};
 use clap::Parser;
-use eyre::Result;
-use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
-use foundry_common::{compile::ProjectCompiler, shell};
+use eyre::{Context, Result};
+use foundry_cli::{
+    opts::BuildOpts,
+    utils::{CommandUtils, Git, LoadConfig},
+};
+use foundry_common::{
+    artifact_store::ArtifactStore,
+    compile::{ContractInfo, ProjectCompiler, SizeReport},
+    fs, shell,
+};
 use foundry_compilers::{
     compilers::{multi::MultiCompilerLanguage, Language},
     utils::source_files_iter,
@@ -18,7 +25,10 @@
     Config,
 };
 use serde::Serialize;
-use std::path::PathBuf;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 foundry_config::merge_impl_figment_convert!(BuildArgs, build);
 
@@ -51,6 +61,28 @@ pub struct BuildArgs {
     #[serde(skip)]
     pub sizes: bool,
 
+    /// Compare contract sizes against a previous run.
+    ///
+    /// Either a JSON report saved with `forge build --sizes --json`, or a git ref which is built
+    /// in a temporary worktree.
+    #[arg(long, value_name = "REF|FILE", requires = "sizes")]
+    #[serde(skip)]
+    pub compare: Option<String>,
+
+    /// Fail if the runtime or initcode size of any contract increased by more than this many
+    /// bytes compared to `--compare`.
+    #[arg(long, value_name = "BYTES", requires = "compare")]
+    #[serde(skip)]
+    pub max_size_increase: Option<usize>,
+
+    /// Print the compiler diagnostics in the given format instead of the compiler output.
+    ///
+    /// Only the diagnostics of compiled files are reported, use `--force` to also include
+    /// unchanged files.
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["names", "sizes"])]
+    #[serde(skip)]
+    pub format: Option<DiagnosticsFormat>,
+
     /// Ignore initcode contract bytecode size limit introduced by EIP-3860.
     #[arg(long, alias = "ignore-initcode-size")]
     #[serde(skip)]
@@ -66,7 +98,11 @@ pub struct BuildArgs {
 }
 
 impl BuildArgs {
-    pub fn run(self) -> Result<ProjectCompileOutput> {
+    /// Compiles the project, or every member of the workspace, see [`Config::workspace`].
+    ///
+    /// Returns the output of the project, or the output of each workspace member in the order
+    /// they were compiled.
+    pub fn run(self) -> Result<Vec<ProjectCompileOutput>> {
         let mut config = self.load_config()?;
 
         if install::install_missing_dependencies(&mut config) && config.auto_detect_remappings {
@@ -74,6 +110,33 @@ pub fn run(self) -> Result<ProjectCompileOutput> {
             config = self.load_config()?;
         }
 
+        if !config.workspace.is_empty() && self.paths.is_none() {
+            return self.compile_workspace(&config);
+        }
+
+        Ok(vec![self.compile(&config)?])
+    }
+
+    /// Compiles every member of the workspace, returning the output of each member.
+    fn compile_workspace(&self, config: &Config) -> Result<Vec<ProjectCompileOutput>> {
+        let members = config.workspace.resolve_members(&config.root);
+        if members.is_empty() {
+            eyre::bail!("No workspace members found matching {:?}", config.workspace.members);
+        }
+        members
+            .iter()
+            .map(|member| {
+                if !shell::is_json() {
+                    sh_println!("Compiling workspace member {}...", member.name)?;
+                }
+                let member_config = config.for_workspace_member(self.figment(), member)?;
+                self.compile(&member_config)
+            })
+            .collect()
+    }
+
+    fn compile(&self, config: &Config) -> Result<ProjectCompileOutput> {
+        crate::yul::write_deployer(config)?;
         let project = config.project()?;
 
         // Collect sources to compile if build subdirectories specified.
@@ -89,16 +152,35 @@ pub fn run(self) -> Result<ProjectCompileOutput> {
             }
         }
 
+        let size_baseline =
+            self.compare.as_deref().map(|target| size_baseline(config, target)).transpose()?;
+
         let format_json = shell::is_json();
-        let compiler = ProjectCompiler::new()
+        let format_sarif = self.format == Some(DiagnosticsFormat::Sarif);
+        let mut compiler = ProjectCompiler::new()
             .files(files)
             .print_names(self.names)
             .print_sizes(self.sizes)
             .ignore_eip_3860(self.ignore_eip_3860)
-            .bail(!format_json);
+            .size_baseline(size_baseline)
+            .max_size_increase(self.max_size_increase)
+            .artifact_store(ArtifactStore::from_config(config)?)
+            .bail(!format_json && !format_sarif);
+        if format_sarif {
+            compiler = compiler.quiet(true);
+        }
 
         let output = compiler.compile(&project)?;
 
+        if format_sarif {
+            let sarif = crate::sarif::compiler_diagnostics(&output, config);
+            sh_println!("{}", serde_json::to_string_pretty(&sarif)?)?;
+            if output.has_compiler_errors() {
+                eyre::bail!("Compilation failed");
+            }
+            return Ok(output);
+        }
+
         if format_json && !self.names && !self.sizes {
             sh_println!("{}", serde_json::to_string_pretty(&output.output())?)?;
         }
@@ -132,6 +214,61 @@ pub(crate) fn watchexec_config(&self) -> Result<watchexec::Config> {
     }
 }
 
+/// Formats of the compiler diagnostics of `forge build --format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum DiagnosticsFormat {
+    /// SARIF 2.1.0, as read by GitHub code scanning.
+    Sarif,
+}
+
+/// Returns the contract sizes to compare against: read from a JSON size report, or measured by
+/// building the project at a git ref in a temporary worktree.
+fn size_baseline(config: &Config, target: &str) -> Result<BTreeMap<String, ContractInfo>> {
+    let path = Path::new(target);
+    if path.is_file() {
+        return SizeReport::parse_json(&fs::read_to_string(path)?)
+            .wrap_err_with(|| format!("invalid size report `{target}`"));
+    }
+
+    let git = Git::new(&config.root);
+    let worktree = std::env::temp_dir().join(format!("forge-sizes-{}", std::process::id()));
+    git.cmd()
+        .args(["worktree", "add", "--detach", "--quiet"])
+        .arg(&worktree)
+        .arg(target)
+        .exec()
+        .wrap_err_with(|| format!("`{target}` is neither a size report nor a git ref"))?;
+    let sizes = measure_sizes(config, &worktree);
+    let _ = git.cmd().args(["worktree", "remove", "--force"]).arg(&worktree).exec();
+    sizes
+}
+
+/// Builds the project checked out at `worktree` and returns its contract sizes.
+fn measure_sizes(config: &Config, worktree: &Path) -> Result<BTreeMap<String, ContractInfo>> {
+    // dependencies are usually not checked in, reuse the ones of the current checkout
+    for lib in &config.libs {
+        let Ok(relative) = lib.strip_prefix(&config.root) else { continue };
+        let target = worktree.join(relative);
+        if !lib.exists() || target.read_dir().is_ok_and(|mut dir| dir.next().is_some()) {
+            continue;
+        }
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        } else if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(lib, &target)?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(lib, &target)?;
+    }
+
+    let config = Config::load_with_root(worktree)?.sanitized();
+    let project = config.project()?;
+    let output = ProjectCompiler::new().quiet(true).compile(&project)?;
+    Ok(SizeReport::new(&output, project.root()).contracts)
+}
+
 // Make this args a `figment::Provider` so that it can be merged into the `Config`
 impl Provider for BuildArgs {
     fn metadata(&self) -> Metadata {
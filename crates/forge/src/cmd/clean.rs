@@ -0,0 +1,210 @@
+//! `forge clean --stale`: prunes the artifacts and cache entries of sources which no longer
+//! exist, instead of removing the whole build output.
+
+use eyre::Result;
+use foundry_common::{fs, shell};
+use foundry_compilers::{compilers::Compiler, Project};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+/// The outcome of [`prune_stale`].
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PruneSummary {
+    /// The number of removed compiler cache entries.
+    pub cache_entries: usize,
+    /// The number of removed artifact files.
+    pub artifacts: usize,
+    /// The number of removed build info files.
+    pub build_infos: usize,
+    /// The total size of the removed files, in bytes.
+    pub reclaimed_bytes: u64,
+}
+
+impl PruneSummary {
+    /// Prints the summary, as JSON if requested.
+    pub fn print(&self) -> Result<()> {
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string(self)?)?;
+        } else if self.cache_entries + self.artifacts + self.build_infos == 0 {
+            sh_println!("No stale artifacts found.")?;
+        } else {
+            sh_println!(
+                "Removed {} stale cache entries, {} artifacts and {} build info files, reclaimed {}.",
+                self.cache_entries,
+                self.artifacts,
+                self.build_infos,
+                format_size(self.reclaimed_bytes)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Removes the compiler cache entries of sources which no longer exist, and all artifacts and
+/// build info files which are not referenced by the remaining entries.
+///
+/// Extra output files, such as `<Name>.metadata.json`, are kept alongside their artifacts.
+pub fn prune_stale<C: Compiler>(project: &Project<C>) -> Result<PruneSummary> {
+    let mut summary = PruneSummary::default();
+    let cache_path = &project.paths.cache;
+    if !cache_path.exists() {
+        let _ = sh_warn!("No compiler cache found at {}, nothing to prune", cache_path.display());
+        return Ok(summary);
+    }
+    let mut cache: Value = fs::read_json_file(cache_path)?;
+
+    let root = project.root();
+    if let Some(files) = cache["files"].as_object_mut() {
+        let before = files.len();
+        files.retain(|name, _| root.join(name).exists());
+        summary.cache_entries = before - files.len();
+    }
+
+    let mut artifacts = BTreeSet::new();
+    let mut build_ids = BTreeSet::new();
+    collect_artifacts(&cache["files"], &mut artifacts, &mut build_ids);
+    // artifacts without their `.json` extension, to keep the extra output files next to them
+    let stems = artifacts
+        .iter()
+        .filter_map(|path| path.strip_suffix(".json"))
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>();
+
+    let build_infos = &project.paths.build_infos;
+    for path in files(&project.paths.artifacts)? {
+        if path.starts_with(build_infos) {
+            continue;
+        }
+        let Ok(rel) = path.strip_prefix(&project.paths.artifacts) else { continue };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        let referenced = artifacts.contains(&rel) ||
+            rel.match_indices('.').any(|(i, _)| stems.contains(&rel[..i]));
+        if !referenced {
+            summary.reclaimed_bytes += remove(&path)?;
+            summary.artifacts += 1;
+        }
+    }
+
+    for path in files(build_infos)? {
+        let id = path.file_stem().unwrap_or_default().to_string_lossy();
+        if !build_ids.contains(id.as_ref()) {
+            summary.reclaimed_bytes += remove(&path)?;
+            summary.build_infos += 1;
+        }
+    }
+    if let Some(builds) = cache["builds"].as_array_mut() {
+        builds.retain(|id| id.as_str().is_some_and(|id| build_ids.contains(id)));
+    }
+
+    remove_empty_dirs(&project.paths.artifacts)?;
+    if summary.cache_entries > 0 || summary.build_infos > 0 {
+        fs::write_json_file(cache_path, &cache)?;
+    }
+
+    Ok(summary)
+}
+
+/// Collects the artifact paths and build ids referenced by the entries of a compiler cache.
+fn collect_artifacts(
+    value: &Value,
+    paths: &mut BTreeSet<String>,
+    build_ids: &mut BTreeSet<String>,
+) {
+    match value {
+        Value::Object(object) => {
+            if let Some(path) = object.get("path").and_then(Value::as_str) {
+                paths.insert(path.replace('\\', "/"));
+                if let Some(id) = object.get("build_id").and_then(Value::as_str) {
+                    build_ids.insert(id.to_string());
+                }
+                return;
+            }
+            for value in object.values() {
+                collect_artifacts(value, paths, build_ids);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_artifacts(value, paths, build_ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns all files in `dir`, recursively.
+fn files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Removes the file at `path` and returns its size.
+fn remove(path: &Path) -> Result<u64> {
+    let size = std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or_default();
+    fs::remove_file(path)?;
+    Ok(size)
+}
+
+/// Removes the empty subdirectories of `dir`, recursively.
+fn remove_empty_dirs(dir: &Path) -> Result<bool> {
+    if !dir.is_dir() {
+        return Ok(false);
+    }
+    let mut empty = true;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() || !remove_empty_dirs(&path)? || std::fs::remove_dir(&path).is_err() {
+            empty = false;
+        }
+    }
+    Ok(empty)
+}
+
+/// Formats a size in bytes with a decimal unit prefix.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["kB", "MB", "GB", "TB"];
+    if bytes < 1000 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64;
+    let mut unit = "B";
+    for prefix in UNITS {
+        if size < 1000.0 {
+            break;
+        }
+        size /= 1000.0;
+        unit = prefix;
+    }
+    format!("{size:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(999), "999 B");
+        assert_eq!(format_size(1_500), "1.5 kB");
+        assert_eq!(format_size(2_340_000), "2.3 MB");
+    }
+}
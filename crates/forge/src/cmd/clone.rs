@@ -1,6 +1,6 @@
 use super::{init::InitArgs, install::DependencyInstallOpts};
 use alloy_primitives::{Address, Bytes, ChainId, TxHash};
-use clap::{Parser, ValueHint};
+use clap::{Parser, ValueEnum, ValueHint};
 use eyre::Result;
 use foundry_block_explorers::{
     contract::{ContractCreationData, ContractMetadata, Metadata},
@@ -22,12 +22,18 @@
     ProjectCompileOutput, ProjectPathsConfig,
 };
 use foundry_config::{Chain, Config};
+use serde::Deserialize;
 use std::{
+    collections::BTreeMap,
+    fmt,
     fs::read_dir,
     path::{Path, PathBuf},
     time::Duration,
 };
 
+/// The URL of the Sourcify server the sources of verified contracts are fetched from.
+const SOURCIFY_SERVER_URL: &str = "https://sourcify.dev/server";
+
 /// CloneMetadata stores the metadata that are not included by `foundry.toml` but necessary for a
 /// cloned contract. The metadata can be serialized to a metadata file in the cloned project root.
 #[derive(Debug, Clone, serde::Serialize)]
@@ -54,12 +60,13 @@ pub struct CloneMetadata {
 
 /// CLI arguments for `forge clone`.
 ///
-/// `forge clone` clones an on-chain contract from block explorers (e.g., Etherscan) in the
-/// following steps:
+/// `forge clone` clones an on-chain contract from block explorers (e.g., Etherscan) or Sourcify in
+/// the following steps:
 /// 1. Fetch the contract source code from the block explorer.
 /// 2. Initialize a empty foundry project at the `root` directory specified in `CloneArgs`.
 /// 3. Dump the contract sources to the source directory.
-/// 4. Update the `foundry.toml` configuration file with the compiler settings from Etherscan.
+/// 4. Update the `foundry.toml` configuration file with the compiler settings from Etherscan, and
+///    record the address of the contract and an RPC endpoint of its chain for fork tests.
 /// 5. Try compile the cloned contract, so that we can get the original storage layout. This
 ///    original storage layout is preserved in the `CloneMetadata` so that if the user later
 ///    modifies the contract, it is possible to quickly check the storage layout compatibility with
@@ -86,6 +93,10 @@ pub struct CloneArgs {
     #[arg(long)]
     pub keep_directory_structure: bool,
 
+    /// Where to fetch the verified source code and compiler settings of the contract from.
+    #[arg(long, value_enum, default_value = "etherscan")]
+    pub source: CloneSource,
+
     #[command(flatten)]
     pub etherscan: EtherscanOpts,
 
@@ -95,17 +106,29 @@ pub struct CloneArgs {
 
 impl CloneArgs {
     pub async fn run(self) -> Result<()> {
-        let Self { address, root, install, etherscan, no_remappings_txt, keep_directory_structure } =
-            self;
+        let Self {
+            address,
+            root,
+            install,
+            etherscan,
+            no_remappings_txt,
+            keep_directory_structure,
+            source,
+        } = self;
 
         // step 0. get the chain and api key from the config
         let config = etherscan.load_config()?;
         let chain = config.chain.unwrap_or_default();
         let etherscan_api_key = config.get_etherscan_api_key(Some(chain)).unwrap_or_default();
-        let client = Client::new(chain, etherscan_api_key.clone())?;
+        let client = match source {
+            CloneSource::Etherscan => {
+                ExplorerClient::Etherscan(Client::new(chain, etherscan_api_key.clone())?)
+            }
+            CloneSource::Sourcify => ExplorerClient::Sourcify(SourcifyClient::new(chain)),
+        };
 
         // step 1. get the metadata from client
-        sh_println!("Downloading the source code of {address} from Etherscan...")?;
+        sh_println!("Downloading the source code of {address} from {source}...")?;
 
         let meta = Self::collect_metadata_from_client(address, &client).await?;
 
@@ -118,12 +141,13 @@ pub async fn run(self) -> Result<()> {
         // step 3. parse the metadata
         Self::parse_metadata(&meta, chain, &root, no_remappings_txt, keep_directory_structure)
             .await?;
+        Self::record_fork_target(&root, address, chain, &meta.contract_name)?;
 
         // step 4. collect the compilation metadata
         // if the etherscan api key is not set, we need to wait for 3 seconds between calls
-        sh_println!("Collecting the creation information of {address} from Etherscan...")?;
+        sh_println!("Collecting the creation information of {address} from {source}...")?;
 
-        if etherscan_api_key.is_empty() {
+        if source == CloneSource::Etherscan && etherscan_api_key.is_empty() {
             sh_warn!("Waiting for 5 seconds to avoid rate limit...")?;
             tokio::time::sleep(Duration::from_secs(5)).await;
         }
@@ -284,6 +308,60 @@ pub(crate) async fn parse_metadata(
 
         Ok(())
     }
+
+    /// Record the cloned contract in the configuration, so that fork tests can reach it.
+    ///
+    /// The address is labeled with the contract name, and an RPC endpoint named after the chain
+    /// is added, reading its URL from the `<CHAIN>_RPC_URL` environment variable, so that
+    /// `vm.createSelectFork("<chain>")` forks the chain the contract is deployed on.
+    pub(crate) fn record_fork_target(
+        root: &Path,
+        address: Address,
+        chain: Chain,
+        contract_name: &str,
+    ) -> Result<()> {
+        let (alias, env_var) = match chain.named() {
+            Some(named) => {
+                let alias = named.to_string();
+                let env_var = format!("{}_RPC_URL", alias.to_uppercase().replace('-', "_"));
+                (alias, env_var)
+            }
+            None => (chain.id().to_string(), format!("RPC_URL_{}", chain.id())),
+        };
+
+        Config::update_at(root, |_, doc| {
+            if doc.get("labels").is_none() {
+                doc["labels"] = toml_edit::table();
+            }
+            doc["labels"][address.to_string()] = toml_edit::value(contract_name);
+
+            if doc.get("rpc_endpoints").is_none() {
+                doc["rpc_endpoints"] = toml_edit::table();
+            }
+            if doc["rpc_endpoints"].get(&alias).is_none() {
+                doc["rpc_endpoints"][&alias] = toml_edit::value(format!("${{{env_var}}}"));
+            }
+            true
+        })?;
+
+        Ok(())
+    }
+}
+
+/// The sources `forge clone` can fetch the verified code of contracts from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CloneSource {
+    Etherscan,
+    Sourcify,
+}
+
+impl fmt::Display for CloneSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Etherscan => f.write_str("Etherscan"),
+            Self::Sourcify => f.write_str("Sourcify"),
+        }
+    }
 }
 
 /// Update the configuration file with the metadata.
@@ -594,7 +672,8 @@ pub fn find_main_contract<'a>(
 }
 
 /// EtherscanClient is a trait that defines the methods to interact with Etherscan.
-/// It is defined as a wrapper of the `foundry_block_explorers::Client` to allow mocking.
+/// It is defined as a wrapper of the `foundry_block_explorers::Client` to allow mocking, and is
+/// implemented for Sourcify by converting its responses to the Etherscan ones.
 #[cfg_attr(test, mockall::automock)]
 pub(crate) trait EtherscanClient {
     async fn contract_source_code(
@@ -625,6 +704,192 @@ async fn contract_creation_data(
     }
 }
 
+/// The client of the source the contract is cloned from.
+enum ExplorerClient {
+    Etherscan(Client),
+    Sourcify(SourcifyClient),
+}
+
+impl EtherscanClient for ExplorerClient {
+    async fn contract_source_code(
+        &self,
+        address: Address,
+    ) -> std::result::Result<ContractMetadata, EtherscanError> {
+        match self {
+            Self::Etherscan(client) => client.contract_source_code(address).await,
+            Self::Sourcify(client) => client.contract_source_code(address).await,
+        }
+    }
+
+    async fn contract_creation_data(
+        &self,
+        address: Address,
+    ) -> std::result::Result<ContractCreationData, EtherscanError> {
+        match self {
+            Self::Etherscan(client) => client.contract_creation_data(address).await,
+            Self::Sourcify(client) => client.contract_creation_data(address).await,
+        }
+    }
+}
+
+/// A client of the Sourcify API, fetching the verified contracts of a chain.
+struct SourcifyClient {
+    chain: Chain,
+    client: reqwest::Client,
+}
+
+impl SourcifyClient {
+    fn new(chain: Chain) -> Self {
+        Self { chain, client: reqwest::Client::new() }
+    }
+
+    /// Fetch the given `fields` of the contract at `address`.
+    async fn contract(
+        &self,
+        address: Address,
+        fields: &str,
+    ) -> std::result::Result<SourcifyContract, EtherscanError> {
+        let url = format!("{SOURCIFY_SERVER_URL}/v2/contract/{}/{address}", self.chain.id());
+        let response = self.client.get(url).query(&[("fields", fields)]).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(EtherscanError::ContractCodeNotVerified(address));
+        }
+        if !response.status().is_success() {
+            return Err(EtherscanError::Unknown(format!(
+                "Sourcify request failed with status code {}",
+                response.status()
+            )));
+        }
+        let content = response.text().await?;
+        serde_json::from_str(&content).map_err(|err| {
+            EtherscanError::Unknown(format!("failed to parse Sourcify response: {err}"))
+        })
+    }
+}
+
+impl EtherscanClient for SourcifyClient {
+    async fn contract_source_code(
+        &self,
+        address: Address,
+    ) -> std::result::Result<ContractMetadata, EtherscanError> {
+        self.contract(address, "sources,compilation,abi,creationBytecode")
+            .await?
+            .into_contract_metadata(address)
+    }
+
+    async fn contract_creation_data(
+        &self,
+        address: Address,
+    ) -> std::result::Result<ContractCreationData, EtherscanError> {
+        self.contract(address, "deployment").await?.into_creation_data(address)
+    }
+}
+
+/// A verified contract, as returned by the Sourcify API.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourcifyContract {
+    #[serde(default)]
+    sources: BTreeMap<String, serde_json::Value>,
+    compilation: Option<SourcifyCompilation>,
+    #[serde(default)]
+    abi: serde_json::Value,
+    creation_bytecode: Option<SourcifyCreationBytecode>,
+    deployment: Option<SourcifyDeployment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourcifyCompilation {
+    language: String,
+    compiler_version: String,
+    compiler_settings: serde_json::Value,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourcifyCreationBytecode {
+    #[serde(default)]
+    transformation_values: SourcifyTransformationValues,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourcifyTransformationValues {
+    constructor_arguments: Option<Bytes>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourcifyDeployment {
+    transaction_hash: Option<TxHash>,
+    deployer: Option<Address>,
+}
+
+impl SourcifyContract {
+    /// Convert the contract to the metadata Etherscan returns for it, with the sources and the
+    /// standard JSON settings they were compiled with.
+    fn into_contract_metadata(
+        self,
+        address: Address,
+    ) -> std::result::Result<ContractMetadata, EtherscanError> {
+        let Some(compilation) = self.compilation else {
+            return Err(EtherscanError::ContractCodeNotVerified(address));
+        };
+        let settings = compilation.compiler_settings;
+        let constructor_arguments = self
+            .creation_bytecode
+            .and_then(|bytecode| bytecode.transformation_values.constructor_arguments)
+            .unwrap_or_default();
+
+        let metadata = serde_json::json!([{
+            "SourceCode": {
+                "language": compilation.language,
+                "sources": self.sources,
+                "settings": settings,
+            },
+            "ABI": self.abi.to_string(),
+            "ContractName": compilation.name,
+            "CompilerVersion": format!("v{}", compilation.compiler_version.trim_start_matches('v')),
+            "OptimizationUsed": settings["optimizer"]["enabled"].as_bool().unwrap_or_default() as u64,
+            "Runs": settings["optimizer"]["runs"].as_u64().unwrap_or_default(),
+            "ConstructorArguments": constructor_arguments,
+            "EVMVersion": settings["evmVersion"].as_str().unwrap_or("Default"),
+            "Library": "",
+            "LicenseType": "",
+            "Proxy": 0,
+            "SwarmSource": "",
+        }]);
+        serde_json::from_value(metadata).map_err(|err| {
+            EtherscanError::Unknown(format!("failed to convert Sourcify metadata: {err}"))
+        })
+    }
+
+    /// Convert the deployment of the contract to the creation data Etherscan returns for it.
+    fn into_creation_data(
+        self,
+        address: Address,
+    ) -> std::result::Result<ContractCreationData, EtherscanError> {
+        let Some(SourcifyDeployment { transaction_hash: Some(tx_hash), deployer: Some(deployer) }) =
+            self.deployment
+        else {
+            return Err(EtherscanError::Unknown(format!(
+                "the creation transaction of {address} is unknown to Sourcify"
+            )));
+        };
+
+        let creation_data = serde_json::json!({
+            "contractAddress": address,
+            "contractCreator": deployer,
+            "txHash": tx_hash,
+        });
+        serde_json::from_value(creation_data).map_err(|err| {
+            EtherscanError::Unknown(format!("failed to convert Sourcify deployment: {err}"))
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -732,6 +997,16 @@ async fn one_test_case(address: Address, check_compilation_result: bool) {
         CloneArgs::parse_metadata(&meta, Chain::mainnet(), &project_root, false, false)
             .await
             .unwrap();
+        CloneArgs::record_fork_target(
+            &project_root,
+            address,
+            Chain::mainnet(),
+            &meta.contract_name,
+        )
+        .unwrap();
+        let config = Config::load_with_root(&project_root).unwrap();
+        assert_eq!(config.labels.get(&address), Some(&meta.contract_name));
+        assert!(config.rpc_endpoints.contains_key("mainnet"));
         CloneArgs::collect_compilation_metadata(
             &meta,
             Chain::mainnet(),
@@ -798,6 +1073,60 @@ async fn test_clone_contract_with_nested_src() {
         one_test_case(address, false).await
     }
 
+    #[test]
+    fn test_convert_sourcify_contract() {
+        let address: Address = "0x35Fb958109b70799a8f9Bc2a8b1Ee4cC62034193".parse().unwrap();
+        let contract: SourcifyContract = serde_json::from_value(serde_json::json!({
+            "sources": {
+                "contracts/Counter.sol": { "content": "pragma solidity 0.8.19;\ncontract Counter {}" }
+            },
+            "compilation": {
+                "language": "Solidity",
+                "compiler": "solc",
+                "compilerVersion": "0.8.19+commit.7dd6d404",
+                "compilerSettings": {
+                    "optimizer": { "enabled": true, "runs": 1000 },
+                    "evmVersion": "paris",
+                    "remappings": [],
+                    "libraries": {}
+                },
+                "name": "Counter",
+                "fullyQualifiedName": "contracts/Counter.sol:Counter"
+            },
+            "abi": [],
+            "creationBytecode": {
+                "transformationValues": { "constructorArguments": "0x2a" }
+            },
+            "deployment": {
+                "transactionHash": "0x41e3517f8262b55e1eb1707ba0760b603a70e89ea4a86eff56072fcc80c3d0a1",
+                "deployer": "0x3e32324277e96b69750bc6f7c4ba27e122413e07"
+            }
+        }))
+        .unwrap();
+
+        let mut meta = contract.into_contract_metadata(address).unwrap();
+        let meta = meta.items.remove(0);
+        assert_eq!(meta.contract_name, "Counter");
+        assert_eq!(meta.compiler_version().unwrap().to_string(), "0.8.19+commit.7dd6d404");
+        assert_eq!(meta.constructor_arguments, Bytes::from_static(&[0x2a]));
+        let settings = meta.settings().unwrap();
+        assert_eq!(settings.optimizer.runs, Some(1000));
+        assert_eq!(settings.evm_version.unwrap().to_string(), "paris");
+
+        let contract: SourcifyContract = serde_json::from_value(serde_json::json!({
+            "deployment": {
+                "transactionHash": "0x41e3517f8262b55e1eb1707ba0760b603a70e89ea4a86eff56072fcc80c3d0a1",
+                "deployer": "0x3e32324277e96b69750bc6f7c4ba27e122413e07"
+            }
+        }))
+        .unwrap();
+        let creation_data = contract.into_creation_data(address).unwrap();
+        assert_eq!(
+            creation_data.contract_creator,
+            "0x3e32324277e96b69750bc6f7c4ba27e122413e07".parse::<Address>().unwrap()
+        );
+    }
+
     fn pick_creation_info(address: &str) -> Option<(&'static str, &'static str)> {
         for (addr, contract_name, creation_code) in &CREATION_ARRAY {
             if address == *addr {
@@ -3,8 +3,9 @@
     coverage::{
         analysis::{SourceAnalysis, SourceFile, SourceFiles},
         anchors::find_anchors,
-        BytecodeReporter, ContractId, CoverageReport, CoverageReporter, CoverageSummaryReporter,
-        DebugReporter, ItemAnchor, LcovReporter,
+        BytecodeReporter, CoberturaReporter, ContractId, CoverageReport, CoverageReporter,
+        CoverageSummaryReporter, DebugReporter, DiffReporter, HtmlReporter, ItemAnchor,
+        LcovReporter,
     },
     utils::IcPcMap,
     MultiContractRunnerBuilder,
@@ -38,6 +39,9 @@
 pub struct CoverageArgs {
     /// The report type to use for coverage.
     ///
+    /// `cobertura` writes `cobertura.xml` and `html` writes a browsable report to the
+    /// `coverage-report` directory, both in the root of the project.
+    ///
     /// This flag can be used multiple times.
     #[arg(long, value_enum, default_value = "summary")]
     report: Vec<CoverageReportKind>,
@@ -57,6 +61,9 @@ pub struct CoverageArgs {
     ///
     /// This can fix most of the "stack too deep" errors while resulting a
     /// relatively accurate source map.
+    ///
+    /// Always enabled for projects with `viaIR` enabled, as the source mappings of optimized code
+    /// are not precise enough to detect branches.
     #[arg(long)]
     ir_minimum: bool,
 
@@ -75,6 +82,18 @@ pub struct CoverageArgs {
     #[arg(long)]
     include_libs: bool,
 
+    /// Report the coverage of the lines changed relative to the given git ref.
+    ///
+    /// Changes in the working tree are included.
+    #[arg(long, value_name = "REF")]
+    diff: Option<String>,
+
+    /// The minimum coverage percentage of the lines changed relative to `--diff`.
+    ///
+    /// The command fails if the coverage of the changed lines is below this threshold.
+    #[arg(long, value_name = "PERCENT", default_value_t = 0., requires = "diff")]
+    diff_threshold: f64,
+
     /// The coverage reporters to use. Constructed from the other fields.
     #[arg(skip)]
     reporters: Vec<Box<dyn CoverageReporter>>,
@@ -131,23 +150,44 @@ fn populate_reporters(&mut self, root: &Path) {
                     root.join("bytecode-coverage"),
                 )),
                 CoverageReportKind::Debug => Box::new(DebugReporter),
+                CoverageReportKind::Cobertura => {
+                    Box::new(CoberturaReporter::new(root.to_path_buf(), root.join("cobertura.xml")))
+                }
+                CoverageReportKind::Html => {
+                    Box::new(HtmlReporter::new(root.to_path_buf(), root.join("coverage-report")))
+                }
             })
             .collect::<Vec<_>>();
+
+        if let Some(base) = &self.diff {
+            self.reporters.push(Box::new(DiffReporter::new(
+                root.to_path_buf(),
+                base.clone(),
+                self.diff_threshold,
+            )));
+        }
     }
 
     /// Builds the project.
     fn build(&self, config: &Config) -> Result<(Project, ProjectCompileOutput)> {
         let mut project = config.ephemeral_project()?;
 
-        if self.ir_minimum {
+        if self.ir_minimum || config.via_ir {
             // print warning message
-            sh_warn!(
-                "`--ir-minimum` enables `viaIR` with minimum optimization, \
-                 which can result in inaccurate source mappings.\n\
-                 Only use this flag as a workaround if you are experiencing \"stack too deep\" errors.\n\
-                 Note that `viaIR` is production ready since Solidity 0.8.13 and above.\n\
-                 See more: https://github.com/foundry-rs/foundry/issues/3357"
-            )?;
+            if self.ir_minimum {
+                sh_warn!(
+                    "`--ir-minimum` enables `viaIR` with minimum optimization, \
+                     which can result in inaccurate source mappings.\n\
+                     Only use this flag as a workaround if you are experiencing \"stack too deep\" errors.\n\
+                     Note that `viaIR` is production ready since Solidity 0.8.13 and above.\n\
+                     See more: https://github.com/foundry-rs/foundry/issues/3357"
+                )?;
+            } else {
+                sh_warn!(
+                    "`viaIR` is enabled, coverage is collected with minimum optimization, \
+                     which can result in inaccurate source mappings."
+                )?;
+            }
 
             // Enable viaIR with minimum optimization: https://github.com/ethereum/solidity/issues/12533#issuecomment-1013073350
             // And also in new releases of Solidity: https://github.com/ethereum/solidity/issues/13972#issuecomment-1628632202
@@ -347,6 +387,8 @@ pub enum CoverageReportKind {
     Lcov,
     Debug,
     Bytecode,
+    Cobertura,
+    Html,
 }
 
 /// Helper function that will link references in unlinked bytecode to the 0 address.
@@ -1,9 +1,11 @@
 use crate::cmd::install;
 use alloy_chains::Chain;
-use alloy_dyn_abi::{DynSolValue, JsonAbiExt, Specifier};
-use alloy_json_abi::{Constructor, JsonAbi};
-use alloy_network::{AnyNetwork, AnyTransactionReceipt, EthereumWallet, TransactionBuilder};
-use alloy_primitives::{hex, Address, Bytes};
+use alloy_dyn_abi::{DynSolType, DynSolValue, JsonAbiExt, Specifier};
+use alloy_json_abi::{Constructor, JsonAbi, Param};
+use alloy_network::{
+    AnyNetwork, AnyTransactionReceipt, EthereumWallet, ReceiptResponse, TransactionBuilder,
+};
+use alloy_primitives::{hex, keccak256, Address, Bytes, B256, U256};
 use alloy_provider::{PendingTransactionError, Provider, ProviderBuilder};
 use alloy_rpc_types::TransactionRequest;
 use alloy_serde::WithOtherFields;
@@ -11,6 +13,7 @@
 use alloy_transport::TransportError;
 use clap::{Parser, ValueHint};
 use eyre::{Context, Result};
+use forge_script_sequence::Deployment;
 use forge_verify::{RetryArgs, VerifierArgs, VerifyArgs};
 use foundry_cli::{
     opts::{BuildOpts, EthereumOpts, EtherscanOpts, TransactionOpts},
@@ -19,10 +22,13 @@
 use foundry_common::{
     compile::{self},
     fmt::parse_tokens,
-    shell,
+    fs, shell,
 };
 use foundry_compilers::{
-    artifacts::BytecodeObject, info::ContractInfo, utils::canonicalize, ArtifactId,
+    artifacts::{BytecodeObject, CompactBytecode},
+    info::ContractInfo,
+    utils::canonicalize,
+    ArtifactId,
 };
 use foundry_config::{
     figment::{
@@ -33,7 +39,17 @@
     merge_impl_figment_convert, Config,
 };
 use serde_json::json;
-use std::{borrow::Borrow, marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    borrow::Borrow,
+    ffi::OsStr,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 merge_impl_figment_convert!(CreateArgs, build, eth);
 
@@ -54,6 +70,9 @@ pub struct CreateArgs {
     constructor_args: Vec<String>,
 
     /// The path to a file containing the constructor arguments.
+    ///
+    /// A JSON file can contain an array of the arguments or an object keyed by the names of the
+    /// constructor inputs, with arrays and structs given as JSON arrays and objects.
     #[arg(
         long,
         value_hint = ValueHint::FilePath,
@@ -84,6 +103,26 @@ pub struct CreateArgs {
     #[arg(long, env = "ETH_TIMEOUT")]
     pub timeout: Option<u64>,
 
+    /// Deploy the contract with CREATE2 through the deterministic deployer.
+    #[arg(long)]
+    create2: bool,
+
+    /// The salt of the CREATE2 deployment. Defaults to zero.
+    #[arg(long, requires = "create2", value_name = "SALT")]
+    salt: Option<B256>,
+
+    /// Mine a salt for which the contract address starts with the given hex.
+    #[arg(long, requires = "create2", conflicts_with = "salt", value_name = "HEX")]
+    starts_with: Option<String>,
+
+    /// Mine a salt for which the contract address contains the given hex.
+    #[arg(long, requires = "create2", conflicts_with = "salt", value_name = "HEX")]
+    contains: Option<String>,
+
+    /// The address of the deterministic deployer used with `--create2`.
+    #[arg(long, requires = "create2", value_name = "ADDRESS")]
+    create2_deployer: Option<Address>,
+
     #[command(flatten)]
     build: BuildOpts,
 
@@ -122,34 +161,7 @@ pub async fn run(mut self) -> Result<()> {
 
         let output = compile::compile_target(&target_path, &project, shell::is_json())?;
 
-        let (abi, bin, id) = remove_contract(output, &target_path, &self.contract.name)?;
-
-        let bin = match bin.object {
-            BytecodeObject::Bytecode(_) => bin.object,
-            _ => {
-                let link_refs = bin
-                    .link_references
-                    .iter()
-                    .flat_map(|(path, names)| {
-                        names.keys().map(move |name| format!("\t{name}: {path}"))
-                    })
-                    .collect::<Vec<String>>()
-                    .join("\n");
-                eyre::bail!("Dynamic linking not supported in `create` command - deploy the following library contracts first, then provide the address to link at compile time\n{}", link_refs)
-            }
-        };
-
-        // Add arguments to constructor
-        let params = if let Some(constructor) = &abi.constructor {
-            let constructor_args =
-                self.constructor_args_path.clone().map(read_constructor_args_file).transpose()?;
-            self.parse_constructor_args(
-                constructor,
-                constructor_args.as_deref().unwrap_or(&self.constructor_args),
-            )?
-        } else {
-            vec![]
-        };
+        let (abi, mut bin, id) = remove_contract(output, &target_path, &self.contract.name)?;
 
         let provider = utils::get_provider(&config)?;
 
@@ -160,6 +172,32 @@ pub async fn run(mut self) -> Result<()> {
             provider.get_chain_id().await?
         };
 
+        if !matches!(bin.object, BytecodeObject::Bytecode(_)) {
+            let libraries = link_deployed_libraries(&mut bin, &config.deployments, chain_id)?;
+            // Verification needs the addresses of the linked libraries.
+            self.build.libraries.extend(libraries);
+        }
+        let bin = bin.object;
+
+        // Add arguments to constructor
+        let params = if let Some(constructor) = &abi.constructor {
+            match &self.constructor_args_path {
+                Some(path) if path.extension() == Some(OsStr::new("json")) => {
+                    parse_constructor_args_json(constructor, path)?
+                }
+                path => {
+                    let constructor_args =
+                        path.clone().map(read_constructor_args_file).transpose()?;
+                    self.parse_constructor_args(
+                        constructor,
+                        constructor_args.as_deref().unwrap_or(&self.constructor_args),
+                    )?
+                }
+            }
+        } else {
+            vec![]
+        };
+
         // Whether to broadcast the transaction or not
         let dry_run = !self.broadcast;
 
@@ -174,6 +212,7 @@ pub async fn run(mut self) -> Result<()> {
                 chain_id,
                 sender,
                 config.transaction_timeout,
+                config.create2_deployer,
                 id,
                 dry_run,
             )
@@ -193,6 +232,7 @@ pub async fn run(mut self) -> Result<()> {
                 chain_id,
                 deployer,
                 config.transaction_timeout,
+                config.create2_deployer,
                 id,
                 dry_run,
             )
@@ -269,6 +309,7 @@ async fn deploy<P: Provider<AnyNetwork>>(
         chain: u64,
         deployer_address: Address,
         timeout: u64,
+        create2_deployer: Address,
         id: ArtifactId,
         dry_run: bool,
     ) -> Result<()> {
@@ -291,6 +332,39 @@ async fn deploy<P: Provider<AnyNetwork>>(
             })?;
         let is_legacy = self.tx.legacy || Chain::from(chain).is_legacy();
 
+        let mut salt = None;
+        if self.create2 {
+            if provider.get_code_at(create2_deployer).await?.is_empty() {
+                eyre::bail!(
+                    "the deterministic deployer {create2_deployer} is not deployed on chain {chain}"
+                );
+            }
+
+            let init_code = deployer.tx.input.input().cloned().unwrap_or_default();
+            let create2_salt = if self.starts_with.is_some() || self.contains.is_some() {
+                mine_salt(
+                    create2_deployer,
+                    keccak256(&init_code),
+                    self.starts_with.as_deref(),
+                    self.contains.as_deref(),
+                )?
+            } else {
+                self.salt.unwrap_or_default()
+            };
+            let address = deployer.create2(create2_deployer, create2_salt);
+
+            if !provider.get_code_at(address).await?.is_empty() {
+                eyre::bail!(
+                    "a contract is already deployed at {address}, the address of the deployment with salt {create2_salt}"
+                );
+            }
+            if !shell::is_json() {
+                sh_println!("Predicted address: {address}")?;
+                sh_println!("Salt: {create2_salt}")?;
+            }
+            salt = Some(create2_salt);
+        }
+
         deployer.tx.set_from(deployer_address);
         deployer.tx.set_chain_id(chain);
         // `to` field must be set explicitly, cannot be None.
@@ -365,11 +439,15 @@ async fn deploy<P: Provider<AnyNetwork>>(
 
                 sh_warn!("To broadcast this transaction, add --broadcast to the previous command. See forge create --help for more.")?;
             } else {
-                let output = json!({
+                let mut output = json!({
                     "contract": self.contract.name,
                     "transaction": &deployer.tx,
                     "abi":&abi
                 });
+                if let (Some(address), Some(salt)) = (deployer.create2_address, salt) {
+                    output["predictedAddress"] = json!(address.to_string());
+                    output["salt"] = json!(salt);
+                }
                 sh_println!("{}", serde_json::to_string_pretty(&output)?)?;
             }
 
@@ -381,11 +459,14 @@ async fn deploy<P: Provider<AnyNetwork>>(
 
         let address = deployed_contract;
         if shell::is_json() {
-            let output = json!({
+            let mut output = json!({
                 "deployer": deployer_address.to_string(),
                 "deployedTo": address.to_string(),
                 "transactionHash": receipt.transaction_hash
             });
+            if let Some(salt) = salt {
+                output["salt"] = json!(salt);
+            }
             sh_println!("{}", serde_json::to_string_pretty(&output)?)?;
         } else {
             sh_println!("Deployer: {deployer_address}")?;
@@ -458,6 +539,201 @@ fn parse_constructor_args(
     }
 }
 
+/// Parses the constructor arguments from a JSON file, either an array of the arguments or an
+/// object keyed by the names of the constructor inputs.
+///
+/// Arrays are given as JSON arrays and structs as arrays of their fields or objects keyed by the
+/// field names. Other values, and values given as strings, are parsed like command line arguments.
+fn parse_constructor_args_json(constructor: &Constructor, path: &Path) -> Result<Vec<DynSolValue>> {
+    let value: serde_json::Value = fs::read_json_file(path).wrap_err_with(|| {
+        format!("Constructor args file \"{}\" must encode a json array or object", path.display())
+    })?;
+
+    let args = match value {
+        serde_json::Value::Array(args) => args,
+        serde_json::Value::Object(mut args) => constructor
+            .inputs
+            .iter()
+            .map(|input| {
+                args.remove(&input.name).ok_or_else(|| {
+                    eyre::eyre!(
+                        "missing constructor argument `{}` in {}",
+                        input.name,
+                        path.display()
+                    )
+                })
+            })
+            .collect::<Result<_>>()?,
+        _ => eyre::bail!(
+            "Constructor args file \"{}\" must encode a json array or object",
+            path.display()
+        ),
+    };
+    if args.len() != constructor.inputs.len() {
+        eyre::bail!(
+            "expected {} constructor arguments, found {} in {}",
+            constructor.inputs.len(),
+            args.len(),
+            path.display()
+        );
+    }
+
+    constructor
+        .inputs
+        .iter()
+        .zip(&args)
+        .map(|(input, arg)| {
+            let ty = input
+                .resolve()
+                .wrap_err_with(|| format!("Could not resolve constructor arg: input={input}"))?;
+            json_to_sol_value(&ty, &input.components, arg).wrap_err_with(|| {
+                format!("invalid value for constructor argument `{}`", input.name)
+            })
+        })
+        .collect()
+}
+
+/// Converts a JSON value to a value of type `ty`, whose struct fields are `components`.
+fn json_to_sol_value(
+    ty: &DynSolType,
+    components: &[Param],
+    value: &serde_json::Value,
+) -> Result<DynSolValue> {
+    use serde_json::Value;
+
+    let values = match (ty, value) {
+        (_, Value::String(s)) => return ty.coerce_str(s).map_err(Into::into),
+        (DynSolType::Bool, Value::Bool(b)) => return Ok(DynSolValue::Bool(*b)),
+        (_, Value::Number(n)) => return ty.coerce_str(&n.to_string()).map_err(Into::into),
+        (DynSolType::Array(inner), Value::Array(values)) => {
+            let values = values
+                .iter()
+                .map(|value| json_to_sol_value(inner, components, value))
+                .collect::<Result<_>>()?;
+            return Ok(DynSolValue::Array(values));
+        }
+        (DynSolType::FixedArray(inner, len), Value::Array(values)) => {
+            if values.len() != *len {
+                eyre::bail!("expected an array of {len} values, found {}", values.len());
+            }
+            let values = values
+                .iter()
+                .map(|value| json_to_sol_value(inner, components, value))
+                .collect::<Result<_>>()?;
+            return Ok(DynSolValue::FixedArray(values));
+        }
+        (DynSolType::Tuple(_), Value::Array(values)) => values.iter().collect::<Vec<_>>(),
+        (DynSolType::Tuple(_), Value::Object(fields)) => components
+            .iter()
+            .map(|component| {
+                fields
+                    .get(&component.name)
+                    .ok_or_else(|| eyre::eyre!("missing struct field `{}`", component.name))
+            })
+            .collect::<Result<_>>()?,
+        _ => eyre::bail!("expected a value of type {ty}, found {value}"),
+    };
+
+    // Tuples, whose field types are resolved from the components to parse nested structs.
+    if values.len() != components.len() {
+        eyre::bail!("expected a tuple of {} values, found {}", components.len(), values.len());
+    }
+    components
+        .iter()
+        .zip(values)
+        .map(|(component, value)| {
+            let ty = component.resolve()?;
+            json_to_sol_value(&ty, &component.components, value)
+                .wrap_err_with(|| format!("invalid value for field `{}`", component.name))
+        })
+        .collect::<Result<_>>()
+        .map(DynSolValue::Tuple)
+}
+
+/// Links the libraries that `bytecode` depends on to their latest deployments on `chain`, read from
+/// the deployment manifests, and returns them in the `<path>:<name>:<address>` format of
+/// `--libraries`.
+fn link_deployed_libraries(
+    bytecode: &mut CompactBytecode,
+    deployments: &Path,
+    chain: u64,
+) -> Result<Vec<String>> {
+    let mut libraries = Vec::new();
+    let mut missing = Vec::new();
+    for (path, names) in bytecode.link_references.clone() {
+        for name in names.keys() {
+            match Deployment::load(deployments, chain, name) {
+                Ok(deployment) => {
+                    bytecode.object.link(&path, name, deployment.address);
+                    libraries.push(format!("{path}:{name}:{}", deployment.address));
+                }
+                Err(_) => missing.push(format!("\t{name}: {path}")),
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        eyre::bail!(
+            "Dynamic linking not supported in `create` command - deploy the following library contracts first, then provide the address to link at compile time or record their deployments in {}\n{}",
+            deployments.display(),
+            missing.join("\n")
+        )
+    }
+
+    Ok(libraries)
+}
+
+/// Mines a salt for which the address of the contract with `init_code_hash` deployed by the
+/// CREATE2 `factory` starts with `starts_with` and contains `contains`, ignoring case.
+fn mine_salt(
+    factory: Address,
+    init_code_hash: B256,
+    starts_with: Option<&str>,
+    contains: Option<&str>,
+) -> Result<B256> {
+    let normalize = |pattern: &str| -> Result<String> {
+        let pattern = pattern.strip_prefix("0x").unwrap_or(pattern).to_lowercase();
+        if pattern.len() > 40 || !pattern.chars().all(|c| c.is_ascii_hexdigit()) {
+            eyre::bail!("invalid address pattern `{pattern}`, expected at most 40 hex characters");
+        }
+        Ok(pattern)
+    };
+    let starts_with = starts_with.map(normalize).transpose()?.unwrap_or_default();
+    let contains = contains.map(normalize).transpose()?.unwrap_or_default();
+
+    if !shell::is_json() {
+        sh_println!("Mining a salt for an address starting with `{starts_with}` and containing `{contains}`...")?;
+    }
+
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let found = Arc::new(AtomicBool::new(false));
+    let handles = (0..threads)
+        .map(|i| {
+            let found = found.clone();
+            let (starts_with, contains) = (starts_with.clone(), contains.clone());
+            std::thread::spawn(move || {
+                // Each thread tries the salts `i, i + threads, i + 2 * threads, ...`.
+                let mut salt = U256::from(i);
+                while !found.load(Ordering::Relaxed) {
+                    let address = hex::encode(factory.create2(B256::from(salt), init_code_hash));
+                    if address.starts_with(&starts_with) && address.contains(&contains) {
+                        found.store(true, Ordering::Relaxed);
+                        return Some(B256::from(salt));
+                    }
+                    salt += U256::from(threads);
+                }
+                None
+            })
+        })
+        .collect::<Vec<_>>();
+
+    handles
+        .into_iter()
+        .filter_map(|handle| handle.join().ok().flatten())
+        .next()
+        .ok_or_else(|| eyre::eyre!("failed to mine a salt"))
+}
+
 impl figment::Provider for CreateArgs {
     fn metadata(&self) -> Metadata {
         Metadata::named("Create Args Provider")
@@ -468,6 +744,9 @@ fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
         if let Some(timeout) = self.timeout {
             dict.insert("transaction_timeout".to_string(), timeout.into());
         }
+        if let Some(create2_deployer) = self.create2_deployer {
+            dict.insert("create2_deployer".to_string(), create2_deployer.to_string().into());
+        }
         Ok(Map::from([(Config::selected_profile(), dict)]))
     }
 }
@@ -511,11 +790,27 @@ fn from(deployer: Deployer<P>) -> Self {
 pub struct Deployer<P> {
     /// The deployer's transaction, exposed for overriding the defaults
     pub tx: WithOtherFields<TransactionRequest>,
+    /// The address of the contract when deployed through a CREATE2 deployer, which isn't included
+    /// in the receipt.
+    pub create2_address: Option<Address>,
     client: P,
     confs: usize,
     timeout: u64,
 }
 
+impl<P> Deployer<P> {
+    /// Deploys the contract through the CREATE2 `factory` with `salt` instead of with a CREATE
+    /// transaction, and returns its address.
+    pub fn create2(&mut self, factory: Address, salt: B256) -> Address {
+        let init_code = self.tx.input.input().cloned().unwrap_or_default();
+        let address = factory.create2_from_code(salt, &init_code);
+        self.tx.set_to(factory);
+        self.tx.set_input(salt.iter().copied().chain(init_code).collect::<Bytes>());
+        self.create2_address = Some(address);
+        address
+    }
+}
+
 impl<P: Provider<AnyNetwork>> Deployer<P> {
     /// Broadcasts the contract deployment transaction and after waiting for it to
     /// be sufficiently confirmed (default: 1), it returns a tuple with the [`Address`] at the
@@ -533,8 +828,11 @@ pub async fn send_with_receipt(
             .get_receipt()
             .await?;
 
-        let address =
-            receipt.contract_address.ok_or(ContractDeploymentError::ContractNotDeployed)?;
+        let address = match self.create2_address {
+            Some(address) if receipt.status() => address,
+            Some(_) => return Err(ContractDeploymentError::ContractNotDeployed),
+            None => receipt.contract_address.ok_or(ContractDeploymentError::ContractNotDeployed)?,
+        };
 
         Ok((address, receipt))
     }
@@ -582,7 +880,13 @@ pub fn deploy_tokens(
         // create the tx object. Since we're deploying a contract, `to` is `None`
         let tx = WithOtherFields::new(TransactionRequest::default().input(data.into()));
 
-        Ok(Deployer { client: self.client.clone(), tx, confs: 1, timeout: self.timeout })
+        Ok(Deployer {
+            client: self.client.clone(),
+            tx,
+            create2_address: None,
+            confs: 1,
+            timeout: self.timeout,
+        })
     }
 }
 
@@ -640,6 +944,37 @@ fn can_parse_chain_id() {
         assert_eq!(args.chain_id(), Some(9999));
     }
 
+    #[test]
+    fn can_parse_create2() {
+        let args: CreateArgs = CreateArgs::parse_from([
+            "foundry-cli",
+            "src/Domains.sol:Domains",
+            "--create2",
+            "--starts-with",
+            "dead",
+        ]);
+        assert!(args.create2);
+        assert_eq!(args.starts_with.as_deref(), Some("dead"));
+
+        assert!(CreateArgs::try_parse_from([
+            "foundry-cli",
+            "src/Domains.sol:Domains",
+            "--salt",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn can_mine_salt() {
+        let factory = Config::DEFAULT_CREATE2_DEPLOYER;
+        let init_code_hash = keccak256("init code");
+        let salt = mine_salt(factory, init_code_hash, Some("0xAB"), None).unwrap();
+        assert!(hex::encode(factory.create2(salt, init_code_hash)).starts_with("ab"));
+
+        assert!(mine_salt(factory, init_code_hash, Some("xyz"), None).is_err());
+    }
+
     #[test]
     fn test_parse_constructor_args() {
         let args: CreateArgs = CreateArgs::parse_from([
@@ -677,4 +1012,38 @@ fn test_parse_int_constructor_args() {
         let params = args.parse_constructor_args(&constructor, &args.constructor_args).unwrap();
         assert_eq!(params, vec![DynSolValue::Int(I256::unchecked_from(-5), 256)]);
     }
+
+    #[test]
+    fn test_parse_json_constructor_args() {
+        let constructor: Constructor = serde_json::from_str(r#"{"type":"constructor","inputs":[{"name":"_owner","type":"address","internalType":"address"},{"name":"_points","type":"tuple[]","internalType":"struct Point[]","components":[{"name":"x","type":"uint256","internalType":"uint256"},{"name":"y","type":"uint256","internalType":"uint256"}]}],"stateMutability":"nonpayable"}"#).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("args.json");
+        let point = |x: u64, y: u64| {
+            DynSolValue::Tuple(vec![
+                DynSolValue::Uint(U256::from(x), 256),
+                DynSolValue::Uint(U256::from(y), 256),
+            ])
+        };
+        let expected = vec![
+            DynSolValue::Address(Address::ZERO),
+            DynSolValue::Array(vec![point(1, 2), point(3, 4)]),
+        ];
+
+        std::fs::write(
+            &path,
+            r#"["0x0000000000000000000000000000000000000000", [[1, "2"], {"x": 3, "y": 4}]]"#,
+        )
+        .unwrap();
+        assert_eq!(parse_constructor_args_json(&constructor, &path).unwrap(), expected);
+
+        std::fs::write(
+            &path,
+            r#"{"_points": [{"x": "1", "y": 2}, [3, 4]], "_owner": "0x0000000000000000000000000000000000000000"}"#,
+        )
+        .unwrap();
+        assert_eq!(parse_constructor_args_json(&constructor, &path).unwrap(), expected);
+
+        std::fs::write(&path, r#"{"_points": []}"#).unwrap();
+        assert!(parse_constructor_args_json(&constructor, &path).is_err());
+    }
 }
@@ -4,13 +4,31 @@
     opts::{BuildOpts, ProjectPathOpts},
     utils::LoadConfig,
 };
-use foundry_common::{compile::with_compilation_reporter, fs};
+use foundry_common::{
+    compile::{with_compilation_reporter, ProjectCompiler},
+    fs,
+};
 use foundry_compilers::{
+    artifacts::{BytecodeHash, BytecodeObject},
     compilers::solc::SolcLanguage,
     error::SolcError,
     flatten::{Flattener, FlattenerError},
+    Artifact,
+};
+use foundry_config::Config;
+use regex::Regex;
+use semver::{Comparator, Op, Version};
+use solar_parse::{
+    ast::{Arena, ItemKind},
+    interface::Session,
+    Parser as SolarParser,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::LazyLock,
 };
-use std::path::PathBuf;
 
 /// CLI arguments for `forge flatten`.
 #[derive(Clone, Debug, Parser)]
@@ -30,13 +48,23 @@ pub struct FlattenArgs {
     )]
     pub output: Option<PathBuf>,
 
+    /// Strip all comments from the flattened contract, except for the license identifier.
+    #[arg(long)]
+    pub strip_comments: bool,
+
+    /// Check that the flattened contract compiles to the same bytecode as the original sources.
+    ///
+    /// The flattened contract is only printed to stdout if the check is disabled.
+    #[arg(long)]
+    pub check: bool,
+
     #[command(flatten)]
     project_paths: ProjectPathOpts,
 }
 
 impl FlattenArgs {
     pub fn run(self) -> Result<()> {
-        let Self { target_path, output, project_paths } = self;
+        let Self { target_path, output, strip_comments, check, project_paths } = self;
 
         // flatten is a subset of `BuildArgs` so we can reuse that to get the config
         let build = BuildOpts { project_paths, ..Default::default() };
@@ -59,6 +87,15 @@ pub fn run(self) -> Result<()> {
             Err(FlattenerError::Other(err)) => Err(err),
         }
         .map_err(|err: SolcError| eyre::eyre!("Failed to flatten: {err}"))?;
+        let flattened = tidy(&flattened, strip_comments)?;
+
+        if check {
+            check_bytecode(&config, &target_path, &flattened)?;
+            sh_println!(
+                "Flattened {} compiles to identical bytecode",
+                target_path.strip_prefix(&config.root).unwrap_or(&target_path).display()
+            )?;
+        }
 
         match output {
             Some(output) => {
@@ -66,9 +103,487 @@ pub fn run(self) -> Result<()> {
                 fs::write(&output, flattened)?;
                 sh_println!("Flattened file written at {}", output.display())?;
             }
-            None => sh_println!("{flattened}")?,
+            None if !check => sh_println!("{flattened}")?,
+            None => {}
         };
 
         Ok(())
     }
 }
+
+/// Rewrites the flattened source with a single license identifier and version pragma at the top,
+/// base contracts defined before the contracts inheriting from them, and optionally without
+/// comments.
+fn tidy(flattened: &str, strip_comments: bool) -> Result<String> {
+    let mut licenses = Vec::new();
+    let mut versions = Vec::new();
+    let mut pragmas = Vec::new();
+    let mut body = String::new();
+    for line in flattened.lines() {
+        let trimmed = line.trim();
+        if let Some(license) = trimmed.strip_prefix("// SPDX-License-Identifier:") {
+            push_unique(&mut licenses, license.trim().to_string());
+        } else if let Some(version) =
+            trimmed.strip_prefix("pragma solidity").and_then(|s| s.strip_suffix(';'))
+        {
+            push_unique(&mut versions, version.trim().to_string());
+        } else if trimmed.starts_with("pragma ") {
+            push_unique(&mut pragmas, trimmed.to_string());
+        } else {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+
+    if strip_comments {
+        body = remove_comments(&body);
+    }
+    let body = order_definitions(&body);
+
+    let mut out = String::new();
+    if let Some(license) = merge_licenses(&licenses) {
+        out.push_str(&format!("// SPDX-License-Identifier: {license}\n"));
+    }
+    for version in unify_versions(&versions)? {
+        out.push_str(&format!("pragma solidity {version};\n"));
+    }
+    for pragma in pragmas {
+        out.push_str(&pragma);
+        out.push('\n');
+    }
+    out.push('\n');
+
+    // collapse the blank lines left behind by removed lines
+    let mut blank = true;
+    for line in body.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            if !blank {
+                out.push('\n');
+            }
+            blank = true;
+        } else {
+            out.push_str(line);
+            out.push('\n');
+            blank = false;
+        }
+    }
+    Ok(out.trim_end().to_string() + "\n")
+}
+
+fn push_unique(items: &mut Vec<String>, item: String) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+/// Merges license identifiers into a single SPDX expression.
+fn merge_licenses(licenses: &[String]) -> Option<String> {
+    match licenses {
+        [] => None,
+        [license] => Some(license.clone()),
+        licenses => Some(
+            licenses
+                .iter()
+                .map(|l| if l.contains(' ') { format!("({l})") } else { l.clone() })
+                .collect::<Vec<_>>()
+                .join(" AND "),
+        ),
+    }
+}
+
+/// A lower or upper version bound, and whether it is inclusive.
+type Bound = (Version, bool);
+
+/// Returns a single version requirement satisfying all `versions`, or the requirements as is if
+/// they can't be merged, e.g. because of `||`.
+fn unify_versions(versions: &[String]) -> Result<Vec<String>> {
+    if versions.len() < 2 {
+        return Ok(versions.to_vec());
+    }
+
+    let mut lower: Option<Bound> = None;
+    let mut upper: Option<Bound> = None;
+    for version in versions {
+        let Some(comparators) = parse_comparators(version) else {
+            return Ok(versions.to_vec());
+        };
+        for comparator in comparators {
+            let (low, up) = bounds(&comparator);
+            if let Some(low) = low {
+                if lower.as_ref().is_none_or(|lower| cmp_lower(&low, lower).is_gt()) {
+                    lower = Some(low);
+                }
+            }
+            if let Some(up) = up {
+                if upper.as_ref().is_none_or(|upper| cmp_upper(&up, upper).is_lt()) {
+                    upper = Some(up);
+                }
+            }
+        }
+    }
+
+    if let (Some((low, low_inclusive)), Some((up, up_inclusive))) = (&lower, &upper) {
+        match low.cmp(up) {
+            Ordering::Greater => {
+                eyre::bail!("incompatible version pragmas: {}", versions.join(", "))
+            }
+            Ordering::Equal if !(*low_inclusive && *up_inclusive) => {
+                eyre::bail!("incompatible version pragmas: {}", versions.join(", "))
+            }
+            Ordering::Equal => return Ok(vec![low.to_string()]),
+            Ordering::Less => {}
+        }
+        if *low_inclusive && !*up_inclusive && caret_upper(low) == *up {
+            return Ok(vec![format!("^{low}")]);
+        }
+    }
+
+    let mut parts = Vec::new();
+    if let Some((low, inclusive)) = lower {
+        parts.push(format!("{}{low}", if inclusive { ">=" } else { ">" }));
+    }
+    if let Some((up, inclusive)) = upper {
+        parts.push(format!("{}{up}", if inclusive { "<=" } else { "<" }));
+    }
+    Ok(vec![parts.join(" ")])
+}
+
+/// Parses a Solidity version requirement, returns `None` for requirements with alternatives.
+fn parse_comparators(version: &str) -> Option<Vec<Comparator>> {
+    static OPERATOR_SPACE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"([<>=^~]+)\s+").unwrap());
+
+    if version.contains("||") {
+        return None;
+    }
+    OPERATOR_SPACE
+        .replace_all(version, "$1")
+        .split_whitespace()
+        .map(|token| {
+            // a plain version is an exact requirement in Solidity
+            let token = if token.starts_with(|c: char| c.is_ascii_digit()) {
+                format!("={token}")
+            } else {
+                token.to_string()
+            };
+            token.parse().ok()
+        })
+        .collect()
+}
+
+/// Returns the lower and upper bound of a comparator.
+fn bounds(c: &Comparator) -> (Option<Bound>, Option<Bound>) {
+    let version = Version::new(c.major, c.minor.unwrap_or(0), c.patch.unwrap_or(0));
+    // the next version not matched by a partial version, e.g. `0.9.0` for `0.8`
+    let next = match (c.minor, c.patch) {
+        (None, _) => Version::new(c.major + 1, 0, 0),
+        (Some(minor), None) => Version::new(c.major, minor + 1, 0),
+        (Some(minor), Some(patch)) => Version::new(c.major, minor, patch + 1),
+    };
+    let partial = c.minor.is_none() || c.patch.is_none();
+    match c.op {
+        Op::Exact if partial => (Some((version, true)), Some((next, false))),
+        Op::Exact => (Some((version.clone(), true)), Some((version, true))),
+        Op::Greater if partial => (Some((next, true)), None),
+        Op::Greater => (Some((version, false)), None),
+        Op::GreaterEq => (Some((version, true)), None),
+        Op::Less => (None, Some((version, false))),
+        Op::LessEq if partial => (None, Some((next, false))),
+        Op::LessEq => (None, Some((version, true))),
+        Op::Tilde => {
+            let up = match c.minor {
+                Some(minor) => Version::new(c.major, minor + 1, 0),
+                None => Version::new(c.major + 1, 0, 0),
+            };
+            (Some((version, true)), Some((up, false)))
+        }
+        Op::Caret => {
+            let up = match (c.major, c.minor, c.patch) {
+                (0, Some(0), Some(patch)) => Version::new(0, 0, patch + 1),
+                (0, Some(minor), _) => Version::new(0, minor + 1, 0),
+                (major, _, _) => Version::new(major + 1, 0, 0),
+            };
+            (Some((version, true)), Some((up, false)))
+        }
+        _ => (None, None),
+    }
+}
+
+/// Compares lower bounds, an exclusive bound is greater than an inclusive one.
+fn cmp_lower(a: &Bound, b: &Bound) -> Ordering {
+    a.0.cmp(&b.0).then(b.1.cmp(&a.1))
+}
+
+/// Compares upper bounds, an exclusive bound is less than an inclusive one.
+fn cmp_upper(a: &Bound, b: &Bound) -> Ordering {
+    a.0.cmp(&b.0).then(a.1.cmp(&b.1))
+}
+
+/// Returns the exclusive upper bound of `^version`.
+fn caret_upper(version: &Version) -> Version {
+    match (version.major, version.minor) {
+        (0, 0) => Version::new(0, 0, version.patch + 1),
+        (0, minor) => Version::new(0, minor + 1, 0),
+        (major, _) => Version::new(major + 1, 0, 0),
+    }
+}
+
+/// Removes all comments from Solidity source code.
+fn remove_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                out.push(c);
+                while let Some(s) = chars.next() {
+                    out.push(s);
+                    if s == '\\' {
+                        out.extend(chars.next());
+                    } else if s == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Orders the top-level definitions so that base contracts precede the contracts inheriting from
+/// them, keeping the original order otherwise.
+///
+/// Returns the source as is if it can't be parsed.
+fn order_definitions(source: &str) -> String {
+    // (end of the item, name of the contract, names of its bases)
+    let mut items: Vec<(usize, Option<String>, Vec<String>)> = Vec::new();
+    let sess = Session::builder().with_silent_emitter(None).build();
+    let parsed = sess.enter(|| -> solar_parse::interface::Result<()> {
+        let arena = Arena::new();
+        let filename = PathBuf::from("flattened.sol").into();
+        let mut parser =
+            SolarParser::from_source_code(&sess, &arena, filename, source.to_string())?;
+        let ast = parser.parse_file().map_err(|e| e.emit())?;
+        for item in ast.items.iter() {
+            let (start, end) = (item.span.lo().to_usize(), item.span.hi().to_usize());
+            match &item.kind {
+                ItemKind::Contract(contract) => {
+                    let header = &source[start..end];
+                    let header = &header[..header.find('{').unwrap_or(header.len())];
+                    items.push((end, Some(contract.name.to_string()), base_names(header)));
+                }
+                _ => items.push((end, None, Vec::new())),
+            }
+        }
+        Ok(())
+    });
+    if parsed.is_err() || items.iter().any(|(end, _, _)| *end > source.len()) {
+        return source.to_string();
+    }
+
+    // each segment holds an item with the comments preceding it
+    let mut segments = Vec::with_capacity(items.len());
+    let mut start = 0;
+    for (end, _, _) in &items {
+        segments.push(&source[start..*end]);
+        start = *end;
+    }
+    let trailing = &source[start..];
+
+    let defined: HashMap<&str, usize> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (_, name, _))| Some((name.as_deref()?, i)))
+        .collect();
+    let dependencies: Vec<BTreeSet<usize>> = items
+        .iter()
+        .enumerate()
+        .map(|(i, (_, _, bases))| {
+            bases
+                .iter()
+                .filter_map(|base| defined.get(base.as_str()).copied())
+                .filter(|&dep| dep != i)
+                .collect()
+        })
+        .collect();
+
+    // stable topological sort, emitting the first item whose bases were all emitted
+    let mut emitted = vec![false; items.len()];
+    let mut order = Vec::with_capacity(items.len());
+    while order.len() < items.len() {
+        let next = (0..items.len())
+            .find(|&i| !emitted[i] && dependencies[i].iter().all(|&dep| emitted[dep]))
+            // inheritance cycles are invalid anyway, keep the original order
+            .unwrap_or_else(|| (0..items.len()).find(|&i| !emitted[i]).unwrap());
+        emitted[next] = true;
+        order.push(next);
+    }
+
+    if order.iter().enumerate().all(|(i, &item)| i == item) {
+        return source.to_string();
+    }
+    let mut out = order.iter().map(|&i| segments[i].trim_start()).collect::<Vec<_>>().join("\n\n");
+    out.push_str(trailing);
+    out
+}
+
+/// Returns the names of the base contracts in a contract header, e.g. `contract A is B, C(1)`.
+fn base_names(header: &str) -> Vec<String> {
+    static IS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bis\b").unwrap());
+
+    let Some(is) = IS.find(header) else { return Vec::new() };
+    let mut bases = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in header[is.end()..].chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => bases.push(std::mem::take(&mut current)),
+            c if depth == 0 => current.push(c),
+            _ => {}
+        }
+    }
+    bases.push(current);
+    bases
+        .into_iter()
+        .filter_map(|base| {
+            // qualified bases like `Lib.Base` are defined as `Base`
+            let name = base.trim().rsplit('.').next()?.trim().to_string();
+            (!name.is_empty()).then_some(name)
+        })
+        .collect()
+}
+
+/// Compiles the original sources and the flattened contract without metadata, and checks that
+/// the contracts of `target` compile to identical bytecode.
+fn check_bytecode(config: &Config, target: &Path, flattened: &str) -> Result<()> {
+    let mut config = config.clone();
+    config.bytecode_hash = BytecodeHash::None;
+    config.cbor_metadata = false;
+    let project = config.ephemeral_project()?;
+
+    let dir = std::env::temp_dir().join(format!("forge-flatten-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let dir = dunce::canonicalize(dir)?;
+    let flattened_path = dir.join(target.file_name().unwrap_or_default());
+    fs::write(&flattened_path, flattened)?;
+
+    let compile = |path: &Path| -> Result<BTreeMap<String, (String, String)>> {
+        let output =
+            ProjectCompiler::new().quiet(true).files([path.to_path_buf()]).compile(&project)?;
+        Ok(output
+            .artifact_ids()
+            .filter(|(id, _)| id.source == path)
+            .map(|(id, artifact)| {
+                let code = |object: Option<std::borrow::Cow<'_, BytecodeObject>>| {
+                    object.map(|object| normalize_bytecode(&object)).unwrap_or_default()
+                };
+                let codes = (
+                    code(artifact.get_bytecode_object()),
+                    code(artifact.get_deployed_bytecode_object()),
+                );
+                (id.name, codes)
+            })
+            .collect())
+    };
+    let original = compile(target);
+    let flattened = compile(&flattened_path);
+    let _ = fs::remove_dir_all(&dir);
+    let (original, flattened) = (original?, flattened?);
+
+    let mismatches: Vec<_> = original
+        .iter()
+        .filter(|(name, codes)| flattened.get(*name) != Some(*codes))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !mismatches.is_empty() {
+        eyre::bail!(
+            "flattened contract does not compile to the same bytecode: {}",
+            mismatches.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Returns the hex bytecode, with library placeholders zeroed since they depend on the source
+/// path of the library.
+fn normalize_bytecode(object: &BytecodeObject) -> String {
+    static PLACEHOLDER: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"__\$[0-9a-fA-F]{34}\$__").unwrap());
+
+    match object {
+        BytecodeObject::Bytecode(bytes) => alloy_primitives::hex::encode(bytes),
+        BytecodeObject::Unlinked(code) => {
+            PLACEHOLDER.replace_all(code.trim_start_matches("0x"), "0".repeat(40)).into_owned()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_licenses() {
+        assert_eq!(merge_licenses(&[]), None);
+        assert_eq!(merge_licenses(&["MIT".into()]), Some("MIT".into()));
+        assert_eq!(
+            merge_licenses(&["MIT".into(), "MIT OR Apache-2.0".into()]),
+            Some("MIT AND (MIT OR Apache-2.0)".into())
+        );
+    }
+
+    #[test]
+    fn unifies_versions() {
+        let unify = |versions: &[&str]| {
+            unify_versions(&versions.iter().map(|v| v.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(unify(&["^0.8.0", "^0.8.4"]).unwrap(), ["^0.8.4"]);
+        assert_eq!(unify(&[">=0.8.0 <0.9.0", "^0.8.10"]).unwrap(), ["^0.8.10"]);
+        assert_eq!(unify(&[">=0.6.2", "^0.8.0", "0.8.19"]).unwrap(), ["0.8.19"]);
+        assert_eq!(unify(&[">= 0.7.0", "<0.8.20"]).unwrap(), [">=0.7.0 <0.8.20"]);
+        assert_eq!(unify(&["^0.8.0 || ^0.7.0", "^0.8.4"]).unwrap(), ["^0.8.0 || ^0.7.0", "^0.8.4"]);
+        assert!(unify(&["^0.7.0", "^0.8.0"]).is_err());
+    }
+
+    #[test]
+    fn removes_comments() {
+        let source = "// comment\ncontract A { /* block\n */ string s = \"// not a comment\"; }\n";
+        assert_eq!(remove_comments(source), "\ncontract A {  string s = \"// not a comment\"; }\n");
+    }
+
+    #[test]
+    fn orders_bases_first() {
+        let source = "contract B is A(1), I {}\n\ninterface I {}\n\ncontract A {}\n";
+        assert_eq!(
+            order_definitions(source),
+            "interface I {}\n\ncontract A {}\n\ncontract B is A(1), I {}\n"
+        );
+        let ordered = "interface I {}\n\ncontract B is I {}\n";
+        assert_eq!(order_definitions(ordered), ordered);
+        assert_eq!(base_names("abstract contract B is Lib.A, C(x, y) "), ["A", "C"]);
+    }
+
+    #[test]
+    fn tidies_flattened_source() {
+        let flattened = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n\n// src/A.sol\n\n// SPDX-License-Identifier: UNLICENSED\npragma solidity ^0.8.4;\npragma abicoder v2;\n\ncontract A {}\n";
+        assert_eq!(
+            tidy(flattened, false).unwrap(),
+            "// SPDX-License-Identifier: MIT AND UNLICENSED\npragma solidity ^0.8.4;\npragma abicoder v2;\n\n// src/A.sol\n\ncontract A {}\n"
+        );
+    }
+}
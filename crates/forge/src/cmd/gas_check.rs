@@ -0,0 +1,241 @@
+//! `forge gas-check`: compares the gas report of the tests against a committed baseline.
+//!
+//! The baseline has the format of `forge test --gas-report --json`. Unlike gas snapshots, only
+//! increases above a threshold fail the check, so small changes don't require updating it.
+
+use super::test;
+use crate::gas_report::{GasInfo, GasReport};
+use alloy_primitives::U256;
+use clap::{Parser, ValueHint};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Cell, Color, Table};
+use eyre::{Context, Result};
+use foundry_cli::utils::STATIC_FUZZ_SEED;
+use foundry_common::fs;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// CLI arguments for `forge gas-check`.
+#[derive(Clone, Debug, Parser)]
+pub struct GasCheckArgs {
+    /// The baseline to compare the gas report against.
+    #[arg(
+        long,
+        default_value = "gas-baseline.json",
+        value_hint = ValueHint::FilePath,
+        value_name = "FILE",
+    )]
+    baseline: PathBuf,
+
+    /// The maximum tolerated gas increase of a function or deployment, in percent.
+    ///
+    /// Accepts values such as `2` or `2%`.
+    #[arg(long, default_value = "0", value_parser = parse_percentage, value_name = "PERCENT")]
+    max_increase: f64,
+
+    /// Write the current gas report to the baseline instead of comparing against it.
+    #[arg(long)]
+    update: bool,
+
+    /// All test arguments are supported
+    #[command(flatten)]
+    pub(crate) test: test::TestArgs,
+}
+
+impl GasCheckArgs {
+    pub async fn run(mut self) -> Result<()> {
+        // Set fuzz seed so gas reports are deterministic
+        self.test.fuzz_seed = Some(U256::from_be_bytes(STATIC_FUZZ_SEED));
+        self.test.gas_report = true;
+
+        let outcome = self.test.execute_tests().await?;
+        outcome.ensure_ok(false)?;
+        let report = outcome.gas_report.as_ref().map(baseline_from_report).unwrap_or_default();
+
+        if self.update {
+            fs::write_pretty_json_file(&self.baseline, &report)?;
+            sh_println!("Wrote gas baseline to {}.", self.baseline.display())?;
+            return Ok(());
+        }
+
+        let baseline: Vec<BaselineContract> = fs::read_json_file(&self.baseline)
+            .wrap_err_with(|| format!("failed to read baseline {}", self.baseline.display()))?;
+        let diffs = compare(&baseline, &report);
+
+        if diffs.is_empty() {
+            sh_println!("\nNo gas changes compared to {}.", self.baseline.display())?;
+            return Ok(());
+        }
+
+        let exceeded = diffs.iter().filter(|diff| diff.change() > self.max_increase).count();
+        sh_println!(
+            "\nGas changes compared to {}:\n{}",
+            self.baseline.display(),
+            diff_table(&diffs, self.max_increase)
+        )?;
+        if exceeded > 0 {
+            eyre::bail!(
+                "gas increased by more than {}% for {exceeded} of {} entries",
+                self.max_increase,
+                diffs.len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// A contract of the gas baseline, in the format of `forge test --gas-report --json`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BaselineContract {
+    pub contract: String,
+    pub deployment: BaselineDeployment,
+    /// Function signature -> GasInfo
+    pub functions: BTreeMap<String, GasInfo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BaselineDeployment {
+    pub gas: u64,
+    pub size: usize,
+}
+
+/// A change in the gas used by a function or deployment.
+#[derive(Clone, Debug, PartialEq)]
+struct GasDiff {
+    contract: String,
+    /// The function signature, `None` for the deployment.
+    function: Option<String>,
+    baseline: u64,
+    current: u64,
+}
+
+impl GasDiff {
+    /// Returns the change in percent of the baseline.
+    fn change(&self) -> f64 {
+        if self.baseline == 0 {
+            return if self.current == 0 { 0. } else { f64::INFINITY };
+        }
+        (self.current as f64 - self.baseline as f64) / self.baseline as f64 * 100.
+    }
+}
+
+/// Converts a gas report to the baseline format, skipping contracts without functions.
+fn baseline_from_report(report: &GasReport) -> Vec<BaselineContract> {
+    report
+        .contracts
+        .iter()
+        .filter(|(_, contract)| !contract.functions.is_empty())
+        .map(|(name, contract)| BaselineContract {
+            contract: name.clone(),
+            deployment: BaselineDeployment { gas: contract.gas, size: contract.size },
+            functions: contract
+                .functions
+                .values()
+                .flat_map(|sigs| sigs.iter().map(|(sig, info)| (sig.clone(), info.clone())))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Compares the mean gas of the functions and the deployment gas of the contracts present in
+/// both reports.
+fn compare(baseline: &[BaselineContract], current: &[BaselineContract]) -> Vec<GasDiff> {
+    let baseline = baseline.iter().map(|c| (c.contract.as_str(), c)).collect::<BTreeMap<_, _>>();
+    let mut diffs = Vec::new();
+    for contract in current {
+        let Some(base) = baseline.get(contract.contract.as_str()) else { continue };
+        let mut push = |function: Option<&String>, baseline: u64, current: u64| {
+            if baseline != current {
+                diffs.push(GasDiff {
+                    contract: contract.contract.clone(),
+                    function: function.cloned(),
+                    baseline,
+                    current,
+                });
+            }
+        };
+        push(None, base.deployment.gas, contract.deployment.gas);
+        for (sig, info) in &contract.functions {
+            if let Some(base_info) = base.functions.get(sig) {
+                push(Some(sig), base_info.mean, info.mean);
+            }
+        }
+    }
+    diffs
+}
+
+fn diff_table(diffs: &[GasDiff], max_increase: f64) -> Table {
+    let mut table = Table::new();
+    table.apply_modifier(UTF8_ROUND_CORNERS);
+    table.set_header(vec!["Contract", "Function", "Baseline", "Current", "Change"]);
+    for diff in diffs {
+        let change = diff.change();
+        let color = if change > max_increase {
+            Color::Red
+        } else if change > 0. {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        let delta = diff.current as i128 - diff.baseline as i128;
+        table.add_row(vec![
+            Cell::new(&diff.contract),
+            Cell::new(diff.function.as_deref().unwrap_or("(deployment)")),
+            Cell::new(diff.baseline),
+            Cell::new(diff.current),
+            Cell::new(format!("{delta:+} ({change:+.2}%)")).fg(color),
+        ]);
+    }
+    table
+}
+
+/// Parses a percentage such as `2` or `2%`.
+fn parse_percentage(s: &str) -> Result<f64, String> {
+    let value = s.trim().trim_end_matches('%');
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|value| *value >= 0.)
+        .ok_or_else(|| format!("invalid percentage `{s}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(gas: u64, functions: &[(&str, u64)]) -> BaselineContract {
+        BaselineContract {
+            contract: "src/Counter.sol:Counter".to_string(),
+            deployment: BaselineDeployment { gas, size: 0 },
+            functions: functions
+                .iter()
+                .map(|(sig, mean)| (sig.to_string(), GasInfo { mean: *mean, ..Default::default() }))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn percentage() {
+        assert_eq!(parse_percentage("2"), Ok(2.));
+        assert_eq!(parse_percentage("2.5%"), Ok(2.5));
+        assert!(parse_percentage("-1").is_err());
+        assert!(parse_percentage("two").is_err());
+    }
+
+    #[test]
+    fn compares_mean_gas() {
+        let baseline = [contract(1000, &[("increment()", 100), ("number()", 50)])];
+        let current = [contract(1000, &[("increment()", 103), ("number()", 50), ("new()", 10)])];
+        let diffs = compare(&baseline, &current);
+        assert_eq!(
+            diffs,
+            [GasDiff {
+                contract: "src/Counter.sol:Counter".to_string(),
+                function: Some("increment()".to_string()),
+                baseline: 100,
+                current: 103,
+            }]
+        );
+        assert_eq!(diffs[0].change(), 3.);
+    }
+}
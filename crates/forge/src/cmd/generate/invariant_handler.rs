@@ -0,0 +1,268 @@
+use super::format_identifier;
+use alloy_json_abi::{Function, InternalType, JsonAbi, Param, StateMutability};
+use clap::Parser;
+use eyre::{OptionExt, Result};
+use foundry_cli::opts::BuildOpts;
+use foundry_common::{
+    compile::{PathOrContractInfo, ProjectCompiler},
+    find_matching_contract_artifact, find_target_path, fs,
+};
+use std::{fmt::Write, path::Path, str::FromStr};
+use yansi::Paint;
+
+/// Number of actors created by the generated handler.
+const ACTORS: usize = 3;
+
+/// CLI arguments for `forge generate invariant-handler`.
+#[derive(Clone, Debug, Parser)]
+pub struct GenerateInvariantHandlerArgs {
+    /// The identifier of the contract to generate a handler for, in the form
+    /// `(<path>:)?<contractname>`.
+    #[arg(long, short, value_parser = PathOrContractInfo::from_str, value_name = "CONTRACT")]
+    pub contract: PathOrContractInfo,
+
+    #[command(flatten)]
+    build: BuildOpts,
+}
+
+impl GenerateInvariantHandlerArgs {
+    pub fn run(self) -> Result<()> {
+        let project = self.build.project()?;
+        let target_path = find_target_path(&project, &self.contract)?;
+        let mut output =
+            ProjectCompiler::new().quiet(true).files([target_path.clone()]).compile(&project)?;
+        let artifact =
+            find_matching_contract_artifact(&mut output, &target_path, self.contract.name())?;
+        let abi = artifact.abi.as_ref().ok_or_eyre("failed to fetch the contract ABI")?;
+
+        let contract_name = match self.contract.name() {
+            Some(name) => name.to_string(),
+            None => target_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .ok_or_eyre("failed to get the contract name")?
+                .to_string(),
+        };
+        let import_path = target_path.strip_prefix(project.root()).unwrap_or(&target_path);
+        let handler = handler_source(&contract_name, import_path, abi);
+
+        let handlers_dir = project.root().join("test").join("handlers");
+        fs::create_dir_all(&handlers_dir)?;
+        let handler_path = handlers_dir.join(format!("{contract_name}Handler.sol"));
+        fs::write(&handler_path, handler)?;
+
+        sh_println!(
+            "{} invariant handler: {}",
+            "Generated".green(),
+            handler_path.strip_prefix(project.root()).unwrap_or(&handler_path).display()
+        )?;
+        Ok(())
+    }
+}
+
+/// Returns the source of a handler contract calling the state-changing functions of
+/// `contract_name` on behalf of a set of actors, with its arguments bounded.
+fn handler_source(contract_name: &str, import_path: &Path, abi: &JsonAbi) -> String {
+    let instance_name = format_identifier(contract_name, false);
+    let mut functions = String::new();
+    for function in abi.functions().filter(|function| {
+        matches!(function.state_mutability, StateMutability::NonPayable | StateMutability::Payable)
+    }) {
+        functions.push('\n');
+        handler_function(&mut functions, &instance_name, function);
+    }
+
+    format!(
+        r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {{CommonBase}} from "forge-std/Base.sol";
+import {{StdCheats}} from "forge-std/StdCheats.sol";
+import {{StdUtils}} from "forge-std/StdUtils.sol";
+import "{import_path}";
+
+/// Handler of the invariant tests of `{contract_name}`, to be targeted with
+/// `targetContract(address(handler))`.
+///
+/// The arguments are bounded to the full range of their type, tighten the bounds to the values
+/// `{contract_name}` accepts.
+contract {contract_name}Handler is CommonBase, StdCheats, StdUtils {{
+    {contract_name} public {instance_name};
+
+    address[] public actors;
+    address internal currentActor;
+
+    /// Number of calls to each function of the handler.
+    mapping(bytes4 => uint256) public calls;
+
+    // Ghost variables tracking the state the invariants are checked against.
+    // TODO: declare ghost variables, e.g. `uint256 public ghost_sum;`
+
+    modifier useActor(uint256 actorSeed) {{
+        currentActor = actors[bound(actorSeed, 0, actors.length - 1)];
+        vm.startPrank(currentActor);
+        _;
+        vm.stopPrank();
+    }}
+
+    modifier countCall() {{
+        calls[msg.sig]++;
+        _;
+    }}
+
+    constructor({contract_name} _{instance_name}) {{
+        {instance_name} = _{instance_name};
+        for (uint256 i = 1; i <= {ACTORS}; i++) {{
+            actors.push(makeAddr(string.concat("actor", vm.toString(i))));
+        }}
+    }}
+{functions}}}
+"#,
+        import_path = import_path.display().to_string().replace('\\', "/"),
+    )
+}
+
+/// Writes the handler function wrapping `function`.
+fn handler_function(out: &mut String, instance_name: &str, function: &Function) {
+    let names = function
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(i, param)| match param.name.as_str() {
+            "" => format!("arg{i}"),
+            "actorSeed" | "msgValue" => format!("{}_", param.name),
+            name => name.to_string(),
+        })
+        .collect::<Vec<_>>();
+    let payable = function.state_mutability == StateMutability::Payable;
+
+    let mut params = vec!["uint256 actorSeed".to_string()];
+    params.extend(
+        function
+            .inputs
+            .iter()
+            .zip(&names)
+            .map(|(param, name)| format!("{}{} {name}", param_type(param), data_location(param))),
+    );
+    if payable {
+        params.push("uint256 msgValue".to_string());
+    }
+
+    writeln!(
+        out,
+        "    function {}({}) public useActor(actorSeed) countCall {{",
+        function.name,
+        params.join(", ")
+    )
+    .unwrap();
+    for (param, name) in function.inputs.iter().zip(&names) {
+        if let Some(bounded) = bounded_param(param, name) {
+            writeln!(out, "        {name} = {bounded};").unwrap();
+        }
+    }
+    let value = if payable {
+        writeln!(out, "        msgValue = bound(msgValue, 0, 100 ether);").unwrap();
+        writeln!(out, "        deal(currentActor, msgValue);").unwrap();
+        "{value: msgValue}"
+    } else {
+        ""
+    };
+    writeln!(out, "        {instance_name}.{}{value}({});", function.name, names.join(", "))
+        .unwrap();
+    writeln!(out, "        // TODO: update ghost variables.").unwrap();
+    writeln!(out, "    }}").unwrap();
+}
+
+/// Returns the Solidity type of a parameter, using the name of user defined types.
+fn param_type(param: &Param) -> String {
+    match &param.internal_type {
+        Some(InternalType::AddressPayable(_)) => "address payable".to_string(),
+        Some(InternalType::Contract(contract)) => {
+            contract.strip_prefix("contract ").unwrap_or(contract).to_string()
+        }
+        Some(
+            InternalType::Enum { contract, ty } |
+            InternalType::Struct { contract, ty } |
+            InternalType::Other { contract, ty },
+        ) => contract.as_ref().map_or_else(|| ty.clone(), |contract| format!("{contract}.{ty}")),
+        None => param.ty.clone(),
+    }
+}
+
+/// Returns the data location of a parameter of the handler function, if required.
+fn data_location(param: &Param) -> &'static str {
+    let ty = param.ty.as_str();
+    if ty == "bytes" || ty == "string" || ty.ends_with(']') || ty.starts_with("tuple") {
+        " memory"
+    } else {
+        ""
+    }
+}
+
+/// Returns the expression bounding a parameter to its valid values, if any: integers are bounded
+/// to the range of their type and addresses to the actors.
+fn bounded_param(param: &Param, name: &str) -> Option<String> {
+    let ty = param.ty.as_str();
+    if ty.ends_with(']') {
+        return None;
+    }
+    if !matches!(param.internal_type, None | Some(InternalType::AddressPayable(_))) &&
+        !matches!(&param.internal_type, Some(InternalType::Other { contract: None, ty: internal }) if internal == ty)
+    {
+        return None;
+    }
+
+    if let Some(bits) = ty.strip_prefix("uint") {
+        let bound = format!("bound({name}, 0, type({ty}).max)");
+        return Some(if bits == "256" { bound } else { format!("{ty}({bound})") });
+    }
+    if let Some(bits) = ty.strip_prefix("int") {
+        let bound = format!("bound({name}, type({ty}).min, type({ty}).max)");
+        return Some(if bits == "256" { bound } else { format!("{ty}({bound})") });
+    }
+    if ty == "address" {
+        let actor =
+            format!("actors[bound(uint256(uint160(address({name}))), 0, actors.length - 1)]");
+        return Some(match param.internal_type {
+            Some(InternalType::AddressPayable(_)) => format!("payable({actor})"),
+            _ => actor,
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_handler_functions() {
+        let abi = JsonAbi::parse([
+            "function deposit(uint256 amount, address receiver) external",
+            "function fund() external payable",
+            "function setFee(uint16) external",
+            "function totalAssets() external view returns (uint256)",
+        ])
+        .unwrap();
+        let handler = handler_source("MyVault", Path::new("src/MyVault.sol"), &abi);
+
+        assert!(handler.contains("import \"src/MyVault.sol\";"));
+        assert!(handler.contains("contract MyVaultHandler is CommonBase, StdCheats, StdUtils {"));
+        assert!(handler.contains(
+            "    function deposit(uint256 actorSeed, uint256 amount, address receiver) public useActor(actorSeed) countCall {
+        amount = bound(amount, 0, type(uint256).max);
+        receiver = actors[bound(uint256(uint160(address(receiver))), 0, actors.length - 1)];
+        myVault.deposit(amount, receiver);
+        // TODO: update ghost variables.
+    }"
+        ));
+        assert!(handler.contains(
+            "    function fund(uint256 actorSeed, uint256 msgValue) public useActor(actorSeed) countCall {
+        msgValue = bound(msgValue, 0, 100 ether);
+        deal(currentActor, msgValue);
+        myVault.fund{value: msgValue}();"
+        ));
+        assert!(handler.contains("        arg0 = uint16(bound(arg0, 0, type(uint16).max));"));
+        assert!(!handler.contains("totalAssets"));
+    }
+}
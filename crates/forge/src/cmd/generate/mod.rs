@@ -4,6 +4,9 @@
 use std::path::Path;
 use yansi::Paint;
 
+mod invariant_handler;
+pub use invariant_handler::GenerateInvariantHandlerArgs;
+
 /// CLI arguments for `forge generate`.
 #[derive(Debug, Parser)]
 pub struct GenerateArgs {
@@ -15,6 +18,9 @@ pub struct GenerateArgs {
 pub enum GenerateSubcommands {
     /// Scaffolds test file for given contract.
     Test(GenerateTestArgs),
+
+    /// Scaffolds an invariant test handler for given contract.
+    InvariantHandler(GenerateInvariantHandlerArgs),
 }
 
 #[derive(Debug, Parser)]
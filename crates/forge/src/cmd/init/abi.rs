@@ -0,0 +1,370 @@
+//! Project scaffolding from an existing ABI: `forge init --from-abi <PATH>`.
+//!
+//! Generates a Solidity interface, a programmable mock implementation and a test harness wired to
+//! the interface.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue, Specifier};
+use alloy_json_abi::{Function, InternalType, JsonAbi, Param, StateMutability};
+use alloy_primitives::{hex, Address, B256, I256, U256};
+use eyre::{Context, OptionExt, Result};
+use foundry_common::fs;
+use std::{collections::BTreeSet, fmt::Write, path::Path};
+
+/// A contract ABI to scaffold a project from.
+#[derive(Clone, Debug)]
+pub struct AbiScaffold {
+    /// The name of the contract, used to derive the names of the generated files.
+    pub name: String,
+    /// The contract ABI.
+    pub abi: JsonAbi,
+}
+
+impl AbiScaffold {
+    /// Loads the ABI at `path`.
+    ///
+    /// Accepts either a plain JSON ABI array or an artifact object with an `abi` field. The name is
+    /// derived from the file name, e.g. `Vault.abi.json` -> `Vault`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .wrap_err_with(|| format!("failed to parse ABI file {}", path.display()))?;
+        if let Some(abi) = value.get_mut("abi") {
+            value = abi.take();
+        }
+        let abi: JsonAbi = serde_json::from_value(value)
+            .wrap_err_with(|| format!("invalid ABI in {}", path.display()))?;
+
+        let stem = path.file_name().and_then(|n| n.to_str()).ok_or_eyre("invalid ABI path")?;
+        let name = contract_name(stem);
+        if name.is_empty() {
+            eyre::bail!("could not derive a contract name from {}", path.display());
+        }
+
+        Ok(Self { name, abi })
+    }
+
+    /// Writes the interface, mock and test harness into the project at `root`.
+    pub fn write(&self, root: &Path) -> Result<()> {
+        let interface_dir = root.join("src/interface");
+        fs::create_dir_all(&interface_dir)?;
+        let mocks_dir = root.join("src/mocks");
+        fs::create_dir_all(&mocks_dir)?;
+        fs::create_dir_all(root.join("test"))?;
+
+        let Self { name, .. } = self;
+        fs::write(interface_dir.join(format!("I{name}.sol")), self.interface())?;
+        fs::write(mocks_dir.join(format!("Mock{name}.sol")), self.mock()?)?;
+        fs::write(root.join(format!("test/{name}.t.sol")), self.test_harness())?;
+        Ok(())
+    }
+
+    /// Returns the Solidity interface of the ABI.
+    pub fn interface(&self) -> String {
+        let name = format!("I{}", self.name);
+        let source = foundry_cli::utils::abi_to_solidity(&self.abi, &name)
+            .unwrap_or_else(|_| self.abi.to_sol(&name, None));
+        format!("{HEADER}{source}")
+    }
+
+    /// Returns a mock whose return data and reverts can be programmed per selector.
+    ///
+    /// Unprogrammed functions return the ABI encoding of the default value of every return type.
+    pub fn mock(&self) -> Result<String> {
+        let name = &self.name;
+        let mut defaults = String::new();
+        for function in self.functions() {
+            let selector = function.selector();
+            let signature = function.signature();
+            let data = default_return_data(&function.outputs)
+                .wrap_err_with(|| format!("unsupported return types of {signature}"))?;
+            if !data.is_empty() {
+                let data = hex::encode(data);
+                let _ = writeln!(
+                    defaults,
+                    "        _defaultReturn[{selector}] = hex\"{data}\"; // {signature}"
+                );
+            }
+            if is_read_only(function) {
+                let _ = writeln!(defaults, "        _readOnly[{selector}] = true; // {signature}");
+            }
+        }
+
+        Ok(format!(
+            r#"{HEADER}/// @notice Programmable mock of `I{name}`.
+/// @dev Unmocked calls return the default values of the function's return types.
+contract Mock{name} {{
+    mapping(bytes4 => bytes) internal _defaultReturn;
+    mapping(bytes4 => bool) internal _readOnly;
+    mapping(bytes4 => bool) internal _mocked;
+    mapping(bytes4 => bytes) internal _returnData;
+    mapping(bytes4 => bool) internal _reverts;
+
+    /// @notice Number of calls received per selector.
+    /// @dev Calls to view and pure functions are made with STATICCALL and aren't counted.
+    mapping(bytes4 => uint256) public calls;
+
+    constructor() {{
+{defaults}    }}
+
+    /// @notice Makes calls to `selector` return `data`.
+    function mockReturn(bytes4 selector, bytes memory data) external {{
+        _mocked[selector] = true;
+        _reverts[selector] = false;
+        _returnData[selector] = data;
+    }}
+
+    /// @notice Makes calls to `selector` revert with `data`.
+    function mockRevert(bytes4 selector, bytes memory data) external {{
+        _mocked[selector] = true;
+        _reverts[selector] = true;
+        _returnData[selector] = data;
+    }}
+
+    fallback(bytes calldata input) external payable returns (bytes memory) {{
+        bytes4 selector = bytes4(input);
+        if (!_readOnly[selector]) {{
+            calls[selector]++;
+        }}
+        if (!_mocked[selector]) {{
+            return _defaultReturn[selector];
+        }}
+        bytes memory data = _returnData[selector];
+        if (_reverts[selector]) {{
+            assembly {{
+                revert(add(data, 0x20), mload(data))
+            }}
+        }}
+        return data;
+    }}
+
+    receive() external payable {{}}
+}}
+"#
+        ))
+    }
+
+    /// Returns a test harness deploying the mock behind the interface, with one smoke test per
+    /// function calling it through the interface with default arguments.
+    pub fn test_harness(&self) -> String {
+        let name = &self.name;
+        let interface = format!("I{name}");
+        let mut imports = BTreeSet::from([interface.clone()]);
+        let mut tests = String::new();
+        for (i, function) in self.functions().enumerate() {
+            let mut args = Vec::with_capacity(function.inputs.len());
+            let mut decls = String::new();
+            for (j, input) in function.inputs.iter().enumerate() {
+                let ty = sol_type(input, &interface, &mut imports);
+                let _ = writeln!(decls, "        {ty} arg{j};");
+                args.push(format!("arg{j}"));
+            }
+            // calls to view and pure functions aren't counted by the mock
+            let (mutability, assertion) = if is_read_only(function) {
+                (" view", String::new())
+            } else {
+                ("", format!("        assertEq(mock.calls(bytes4({})), 1);\n", function.selector()))
+            };
+            let _ = write!(
+                tests,
+                r#"
+    /// `{signature}`
+    function test_{fn_name}_{i}() public{mutability} {{
+{decls}        target.{fn_name}({args});
+{assertion}    }}
+"#,
+                signature = function.signature(),
+                fn_name = function.name,
+                args = args.join(", "),
+            );
+        }
+        let imports = imports.into_iter().collect::<Vec<_>>().join(", ");
+
+        format!(
+            r#"{HEADER}import {{Test}} from "forge-std/Test.sol";
+import {{{imports}}} from "../src/interface/I{name}.sol";
+import {{Mock{name}}} from "../src/mocks/Mock{name}.sol";
+
+contract {name}Test is Test {{
+    Mock{name} internal mock;
+    I{name} internal target;
+
+    function setUp() public {{
+        mock = new Mock{name}();
+        target = I{name}(address(mock));
+    }}
+{tests}}}
+"#
+        )
+    }
+
+    fn functions(&self) -> impl Iterator<Item = &Function> {
+        self.abi.functions()
+    }
+}
+
+const HEADER: &str = "// SPDX-License-Identifier: UNLICENSED\npragma solidity ^0.8.13;\n\n";
+
+/// Derives a Solidity identifier from a file name: `vault.abi.json` -> `Vault`.
+fn contract_name(file_name: &str) -> String {
+    let stem = file_name.split('.').next().unwrap_or_default();
+    let mut name: String =
+        stem.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '_').collect();
+    if let Some(first) = name.get(..1) {
+        let upper = first.to_ascii_uppercase();
+        name.replace_range(..1, &upper);
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Returns `true` if `function` is called with STATICCALL.
+fn is_read_only(function: &Function) -> bool {
+    matches!(function.state_mutability, StateMutability::View | StateMutability::Pure)
+}
+
+/// Returns the ABI encoding of the default values of `params`.
+fn default_return_data(params: &[Param]) -> Result<Vec<u8>> {
+    let values = params
+        .iter()
+        .map(|param| Ok(default_value(&param.resolve()?)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DynSolValue::Tuple(values).abi_encode_params())
+}
+
+fn default_value(ty: &DynSolType) -> DynSolValue {
+    match ty {
+        DynSolType::Bool => DynSolValue::Bool(false),
+        DynSolType::Int(bits) => DynSolValue::Int(I256::ZERO, *bits),
+        DynSolType::Uint(bits) => DynSolValue::Uint(U256::ZERO, *bits),
+        DynSolType::FixedBytes(size) => DynSolValue::FixedBytes(B256::ZERO, *size),
+        DynSolType::Address => DynSolValue::Address(Address::ZERO),
+        DynSolType::Function => DynSolValue::Function(Default::default()),
+        DynSolType::Bytes => DynSolValue::Bytes(Vec::new()),
+        DynSolType::String => DynSolValue::String(String::new()),
+        DynSolType::Array(_) => DynSolValue::Array(Vec::new()),
+        DynSolType::FixedArray(ty, len) => DynSolValue::FixedArray(vec![default_value(ty); *len]),
+        DynSolType::Tuple(types) | DynSolType::CustomStruct { tuple: types, .. } => {
+            DynSolValue::Tuple(types.iter().map(default_value).collect())
+        }
+    }
+}
+
+/// Returns the Solidity type of a variable passed as `param` to a function of `interface`,
+/// matching the declaration generated by [`JsonAbi::to_sol`].
+///
+/// Types declared in other contracts are generated as libraries of the interface file, and their
+/// names are added to `imports`.
+fn sol_type(param: &Param, interface: &str, imports: &mut BTreeSet<String>) -> String {
+    let ty = match &param.internal_type {
+        Some(InternalType::Contract(ty)) => {
+            format!("address{}", ty.find('[').map(|start| &ty[start..]).unwrap_or_default())
+        }
+        Some(InternalType::AddressPayable(ty)) => ty.clone(),
+        Some(InternalType::Enum { .. }) => "uint8".to_string(),
+        Some(InternalType::Struct { contract, ty } | InternalType::Other { contract, ty }) => {
+            let custom = matches!(param.internal_type, Some(InternalType::Struct { .. })) ||
+                DynSolType::parse(ty).is_err();
+            match contract {
+                Some(contract) if contract != interface => {
+                    imports.insert(contract.clone());
+                    format!("{contract}.{ty}")
+                }
+                _ if custom => format!("{interface}.{ty}"),
+                _ => ty.clone(),
+            }
+        }
+        None => param.ty.clone(),
+    };
+    let memory =
+        ty == "bytes" || ty == "string" || ty.ends_with(']') || !param.components.is_empty();
+    if memory {
+        format!("{ty} memory")
+    } else {
+        ty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_contract_name() {
+        assert_eq!(contract_name("Vault.json"), "Vault");
+        assert_eq!(contract_name("vault.abi.json"), "Vault");
+        assert_eq!(contract_name("my-pool.json"), "Mypool");
+        assert_eq!(contract_name("1inch.json"), "_1inch");
+    }
+
+    #[test]
+    fn encodes_default_return_data() {
+        let abi = JsonAbi::parse([
+            "function a() returns (bool)",
+            "function b() returns (string)",
+            "function c() returns (uint256, (uint256,string), uint8[2])",
+            "function d()",
+        ])
+        .unwrap();
+        let data = |name: &str| default_return_data(&abi.function(name).unwrap()[0].outputs);
+        assert_eq!(data("a").unwrap(), vec![0; 32]);
+        assert_eq!(hex::encode(data("b").unwrap()), format!("{:064x}{:064x}", 0x20, 0));
+        assert_eq!(data("c").unwrap().len(), 7 * 32);
+        assert!(data("d").unwrap().is_empty());
+    }
+
+    #[test]
+    fn derives_argument_types() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "function",
+                "name": "f",
+                "stateMutability": "nonpayable",
+                "outputs": [],
+                "inputs": [
+                    { "name": "a", "type": "uint256", "internalType": "uint256" },
+                    { "name": "b", "type": "address", "internalType": "contract IERC20" },
+                    { "name": "c", "type": "string", "internalType": "string" },
+                    { "name": "d", "type": "uint8", "internalType": "enum Vault.Kind" },
+                    {
+                        "name": "e",
+                        "type": "tuple[]",
+                        "internalType": "struct Vault.Position[]",
+                        "components": [{ "name": "x", "type": "uint256", "internalType": "uint256" }]
+                    },
+                    {
+                        "name": "f",
+                        "type": "tuple",
+                        "internalType": "struct Config",
+                        "components": [{ "name": "y", "type": "bool", "internalType": "bool" }]
+                    },
+                    { "name": "g", "type": "uint128", "internalType": "Price" },
+                    { "name": "h", "type": "bytes32", "internalType": "bytes32" }
+                ]
+            }]"#,
+        )
+        .unwrap();
+
+        let mut imports = BTreeSet::new();
+        let types = abi.function("f").unwrap()[0]
+            .inputs
+            .iter()
+            .map(|input| sol_type(input, "IVault", &mut imports))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            [
+                "uint256",
+                "address",
+                "string memory",
+                "uint8",
+                "Vault.Position[] memory",
+                "IVault.Config memory",
+                "IVault.Price",
+                "bytes32",
+            ]
+        );
+        assert_eq!(imports, BTreeSet::from(["Vault".to_string()]));
+    }
+}
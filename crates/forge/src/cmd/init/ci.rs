@@ -0,0 +1,69 @@
+//! CI pipelines generated by `forge init --ci <PROVIDER>`.
+
+use clap::ValueEnum;
+use eyre::Result;
+use foundry_common::fs;
+use std::{fmt, path::Path};
+
+/// The CI provider to generate a pipeline for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum CiProvider {
+    /// GitHub Actions: `.github/workflows/test.yml`.
+    #[default]
+    Github,
+    /// GitLab CI/CD: `.gitlab-ci.yml`.
+    Gitlab,
+    /// CircleCI: `.circleci/config.yml`.
+    Circle,
+    /// Do not generate a pipeline.
+    None,
+}
+
+impl CiProvider {
+    /// All providers, in the order they are offered by the interactive wizard.
+    pub const ALL: [Self; 4] = [Self::Github, Self::Gitlab, Self::Circle, Self::None];
+
+    /// Returns the path of the pipeline file, relative to the project root.
+    pub fn path(self) -> Option<&'static str> {
+        match self {
+            Self::Github => Some(".github/workflows/test.yml"),
+            Self::Gitlab => Some(".gitlab-ci.yml"),
+            Self::Circle => Some(".circleci/config.yml"),
+            Self::None => None,
+        }
+    }
+
+    /// Returns the content of the pipeline file.
+    pub fn template(self) -> Option<&'static str> {
+        match self {
+            Self::Github => Some(include_str!("../../../assets/workflowTemplate.yml")),
+            Self::Gitlab => Some(include_str!("../../../assets/gitlabCiTemplate.yml")),
+            Self::Circle => Some(include_str!("../../../assets/circleCiTemplate.yml")),
+            Self::None => None,
+        }
+    }
+
+    /// Writes the pipeline file into `root`, if it doesn't exist already.
+    pub fn write(self, root: &Path) -> Result<()> {
+        let (Some(path), Some(template)) = (self.path(), self.template()) else { return Ok(()) };
+        let path = root.join(path);
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, template)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CiProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Github => "GitHub Actions",
+            Self::Gitlab => "GitLab CI/CD",
+            Self::Circle => "CircleCI",
+            Self::None => "None",
+        })
+    }
+}
@@ -0,0 +1,241 @@
+//! Templates from a local directory: `forge init --template ../my-starter`.
+
+use eyre::{Context, Result};
+use foundry_cli::utils::Git;
+use foundry_common::fs;
+use foundry_config::filter::GlobMatcher;
+use std::path::{Component, Path, PathBuf};
+
+/// File listing gitignore-style patterns of template files that should not be copied.
+pub const FORGE_IGNORE_FILE: &str = ".forgeignore";
+
+/// File listing gitignore-style patterns of template files to copy, all others are skipped.
+pub const FORGE_INCLUDE_FILE: &str = ".forgeinclude";
+
+/// A template in a local directory.
+#[derive(Clone, Debug)]
+pub struct LocalTemplate {
+    /// The root directory of the template.
+    root: PathBuf,
+    /// Patterns from the `.forgeignore` file.
+    ignore: Vec<GlobMatcher>,
+    /// Patterns from the `.forgeinclude` file, if any.
+    include: Option<Vec<GlobMatcher>>,
+}
+
+impl LocalTemplate {
+    /// Returns `true` if `template` is an explicit local path, i.e. it is absolute, starts with
+    /// `./` or `../`, or has a `file:` scheme.
+    ///
+    /// Bare names such as `erc20` or `org/repo` are never treated as local, even if a directory of
+    /// the same name exists.
+    pub fn is_local(template: &str) -> bool {
+        if template.starts_with("file:") {
+            return true;
+        }
+        let path = Path::new(template);
+        path.is_absolute() ||
+            matches!(path.components().next(), Some(Component::CurDir | Component::ParentDir))
+    }
+
+    /// Returns the path of a local template, without its `file:` scheme.
+    pub fn path(template: &str) -> &Path {
+        let path = template
+            .strip_prefix("file://")
+            .or_else(|| template.strip_prefix("file:"))
+            .unwrap_or(template);
+        Path::new(path)
+    }
+
+    /// Checks out `branch` of the local git repository at `path` into a temporary directory, to
+    /// be loaded as the template.
+    ///
+    /// The repository is cloned from the local path, so this does not touch the network.
+    pub fn checkout(path: &Path, branch: &str) -> Result<tempfile::TempDir> {
+        let dir = tempfile::tempdir()?;
+        Git::clone_with_branch(false, path, branch, Some(dir.path())).wrap_err_with(|| {
+            format!("failed to check out branch `{branch}` of the template {}", path.display())
+        })?;
+        Ok(dir)
+    }
+
+    /// Loads the template at `root`, reading its `.forgeignore` and `.forgeinclude` files.
+    pub fn new(root: &Path) -> Result<Self> {
+        let root = dunce::canonicalize(root)
+            .wrap_err_with(|| format!("template directory {} not found", root.display()))?;
+        let ignore = read_patterns(&root.join(FORGE_IGNORE_FILE))?.unwrap_or_default();
+        let include = read_patterns(&root.join(FORGE_INCLUDE_FILE))?;
+        Ok(Self { root, ignore, include })
+    }
+
+    /// Returns the root directory of the template.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Copies all files of the template into `dest`, returning the number of copied files.
+    ///
+    /// Fails if `dest` is inside the template, as the copy would recurse into its own output.
+    pub fn copy_to(&self, dest: &Path) -> Result<usize> {
+        let dest = dunce::canonicalize(dest)
+            .wrap_err_with(|| format!("destination directory {} not found", dest.display()))?;
+        if dest.starts_with(&self.root) {
+            eyre::bail!(
+                "cannot initialize {} from the template {} it is located in",
+                dest.display(),
+                self.root.display()
+            );
+        }
+        self.copy_dir(&self.root, &dest)
+    }
+
+    fn copy_dir(&self, dir: &Path, dest: &Path) -> Result<usize> {
+        let mut entries = std::fs::read_dir(dir)
+            .wrap_err_with(|| format!("failed to read {}", dir.display()))?
+            .collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|e| e.file_name());
+
+        let mut copied = 0;
+        for entry in entries {
+            let path = entry.path();
+            let relative = path.strip_prefix(&self.root)?;
+            let is_dir = entry.file_type()?.is_dir();
+            if !self.is_included(relative, is_dir) {
+                continue;
+            }
+
+            let target = dest.join(relative);
+            if is_dir {
+                copied += self.copy_dir(&path, dest)?;
+            } else {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&path, &target)?;
+                copied += 1;
+            }
+        }
+        Ok(copied)
+    }
+
+    /// Returns `true` if the template entry at `relative` should be copied.
+    ///
+    /// Directories are only checked against ignore patterns, as includes are matched by files.
+    fn is_included(&self, relative: &Path, is_dir: bool) -> bool {
+        if relative == Path::new(".git") ||
+            relative == Path::new(FORGE_IGNORE_FILE) ||
+            relative == Path::new(FORGE_INCLUDE_FILE)
+        {
+            return false;
+        }
+        if self.ignore.iter().any(|p| p.matcher.is_match(relative)) {
+            return false;
+        }
+        if is_dir {
+            return true;
+        }
+        self.include
+            .as_ref()
+            .is_none_or(|include| include.iter().any(|p| p.matcher.is_match(relative)))
+    }
+}
+
+/// Reads gitignore-style patterns from `path`, returning `None` if the file does not exist.
+///
+/// Patterns without a `/` match at any depth, patterns ending with `/` also match everything
+/// inside the directory.
+fn read_patterns(path: &Path) -> Result<Option<Vec<GlobMatcher>>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)?;
+    let mut patterns = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (pattern, is_dir) = match line.strip_suffix('/') {
+            Some(dir) => (dir, true),
+            None => (line, false),
+        };
+        let pattern = match pattern.strip_prefix('/') {
+            Some(anchored) => anchored.to_string(),
+            None if !pattern.contains('/') => format!("**/{pattern}"),
+            None => pattern.to_string(),
+        };
+        for pattern in [pattern.clone(), format!("{pattern}/**")] {
+            let glob = pattern
+                .parse()
+                .wrap_err_with(|| format!("invalid pattern {line:?} in {}", path.display()))?;
+            patterns.push(glob);
+            if !is_dir {
+                break;
+            }
+        }
+    }
+    Ok(Some(patterns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_local_template() {
+        let template = tempfile::tempdir().unwrap();
+        let files = [
+            "src/Counter.sol",
+            "test/Counter.t.sol",
+            "node_modules/pkg/index.js",
+            "cache/solidity-files-cache.json",
+            "notes/todo.md",
+            ".git/HEAD",
+        ];
+        for file in files {
+            let path = template.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, file).unwrap();
+        }
+        std::fs::write(template.path().join(FORGE_IGNORE_FILE), "# deps\nnode_modules\n/cache/\n")
+            .unwrap();
+        std::fs::write(template.path().join(FORGE_INCLUDE_FILE), "*.sol\n*.js\n").unwrap();
+
+        let dest = tempfile::tempdir().unwrap();
+        let copied = LocalTemplate::new(template.path()).unwrap().copy_to(dest.path()).unwrap();
+        assert_eq!(copied, 2);
+        assert!(dest.path().join("src/Counter.sol").exists());
+        assert!(dest.path().join("test/Counter.t.sol").exists());
+        assert!(!dest.path().join("node_modules").exists());
+        assert!(!dest.path().join("cache").exists());
+        assert!(!dest.path().join("notes/todo.md").exists());
+        assert!(!dest.path().join(".git").exists());
+        assert!(!dest.path().join(FORGE_IGNORE_FILE).exists());
+    }
+
+    #[test]
+    fn detects_local_templates() {
+        for template in [".", "./starter", "../starter", "file:starter", "file:///tmp/starter"] {
+            assert!(LocalTemplate::is_local(template), "{template}");
+        }
+        assert!(LocalTemplate::is_local(&std::env::temp_dir().to_string_lossy()));
+        for template in ["erc20", "org/repo", "github.com/org/repo", "https://github.com/org/repo"]
+        {
+            assert!(!LocalTemplate::is_local(template), "{template}");
+        }
+        assert_eq!(LocalTemplate::path("file:///tmp/starter"), Path::new("/tmp/starter"));
+        assert_eq!(LocalTemplate::path("file:../starter"), Path::new("../starter"));
+        assert_eq!(LocalTemplate::path("./starter"), Path::new("./starter"));
+    }
+
+    #[test]
+    fn refuses_to_copy_into_itself() {
+        let template = tempfile::tempdir().unwrap();
+        std::fs::write(template.path().join("foundry.toml"), "").unwrap();
+        let dest = template.path().join("sub");
+        std::fs::create_dir(&dest).unwrap();
+
+        let template = LocalTemplate::new(template.path()).unwrap();
+        assert!(template.copy_to(&dest).is_err());
+        assert!(template.copy_to(template.root()).is_err());
+    }
+}
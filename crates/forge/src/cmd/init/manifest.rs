@@ -0,0 +1,240 @@
+//! Template variables declared in a `template.toml` manifest.
+//!
+//! Templates can declare variables which are substituted into file contents and paths during
+//! `forge init`:
+//!
+//! ```toml
+//! [variables]
+//! project_name = { description = "The name of the project" }
+//! author = {}
+//! solc_version = { default = "0.8.28" }
+//! ```
+//!
+//! Every `{{ project_name }}` placeholder is then replaced with the value of the variable, taken
+//! from `--var project_name=...`, the declared default or a built-in default, in that order.
+//! Placeholders of undeclared variables, such as `${{ matrix.os }}` in GitHub workflows, are left
+//! untouched.
+
+use eyre::{Context, Result};
+use foundry_cli::utils::{CommandUtils, Git};
+use foundry_common::fs;
+use serde::Deserialize;
+use std::{collections::BTreeMap, path::Path};
+
+/// The file name of the template manifest.
+pub const TEMPLATE_MANIFEST: &str = "template.toml";
+
+/// The manifest of a template: `template.toml`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateManifest {
+    /// The variables of the template, by name.
+    #[serde(default)]
+    pub variables: BTreeMap<String, TemplateVariable>,
+}
+
+/// A variable declared in the [`TemplateManifest`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateVariable {
+    /// A short human readable description.
+    #[serde(default)]
+    pub description: String,
+    /// The value used if none is provided.
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+impl TemplateManifest {
+    /// Reads the manifest in the template at `root`, if any.
+    pub fn read(root: &Path) -> Result<Option<Self>> {
+        let path = root.join(TEMPLATE_MANIFEST);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        let manifest = toml::from_str(&content)
+            .wrap_err_with(|| format!("failed to parse template manifest {}", path.display()))?;
+        Ok(Some(manifest))
+    }
+
+    /// Resolves the values of all variables for the project at `root`.
+    ///
+    /// Values in `overrides` take precedence over the declared defaults, which take precedence
+    /// over the built-in defaults of `project_name` and `author`.
+    pub fn resolve(
+        &self,
+        root: &Path,
+        overrides: &[(String, String)],
+    ) -> Result<BTreeMap<String, String>> {
+        if let Some((name, _)) = overrides.iter().find(|(n, _)| !self.variables.contains_key(n)) {
+            eyre::bail!("template does not declare a variable named `{name}`");
+        }
+
+        let mut values = BTreeMap::new();
+        let mut missing = Vec::new();
+        for (name, variable) in &self.variables {
+            let value = overrides
+                .iter()
+                .rev()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.clone())
+                .or_else(|| variable.default.clone())
+                .or_else(|| builtin_default(name, root));
+            match value {
+                Some(value) => {
+                    values.insert(name.clone(), value);
+                }
+                None => missing.push(name.as_str()),
+            }
+        }
+        if !missing.is_empty() {
+            eyre::bail!(
+                "missing values for template variables: {}\n\
+                 Provide them with `--var <NAME>=<VALUE>`.",
+                missing.join(", ")
+            );
+        }
+        Ok(values)
+    }
+}
+
+/// Renders the template at `root` in place and removes its manifest.
+///
+/// Returns `false` if the template has no manifest, in which case nothing is rendered.
+pub fn render_template(root: &Path, overrides: &[(String, String)]) -> Result<bool> {
+    let Some(manifest) = TemplateManifest::read(root)? else {
+        if let Some((name, _)) = overrides.first() {
+            eyre::bail!("template does not declare a variable named `{name}`");
+        }
+        return Ok(false);
+    };
+    let values = manifest.resolve(root, overrides)?;
+    fs::remove_file(root.join(TEMPLATE_MANIFEST))?;
+    render_dir(root, root, &values)?;
+    Ok(true)
+}
+
+/// Substitutes all placeholders of the known variables in the contents and names of the entries of
+/// `dir`, skipping git metadata and dependencies.
+fn render_dir(root: &Path, dir: &Path, values: &BTreeMap<String, String>) -> Result<()> {
+    // collect first, as entries are renamed while iterating
+    let entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    for entry in entries {
+        let path = entry.path();
+        if dir == root && (entry.file_name() == ".git" || entry.file_name() == "lib") {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            render_dir(root, &path, values)?;
+        } else if let Ok(content) = std::fs::read_to_string(&path) {
+            let rendered = render(&content, values);
+            if rendered != content {
+                fs::write(&path, rendered)?;
+            }
+        }
+
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let rendered = render(&name, values);
+        if rendered != name {
+            std::fs::rename(&path, dir.join(rendered))
+                .wrap_err_with(|| format!("failed to rename {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Replaces all `{{ name }}` placeholders of variables in `values`.
+pub fn render(s: &str, values: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let (before, after) = rest.split_at(start);
+        out.push_str(before);
+        let value = after[2..]
+            .find("}}")
+            .and_then(|end| values.get(after[2..2 + end].trim()).map(|v| (v, end + 4)));
+        match value {
+            Some((value, len)) => {
+                out.push_str(value);
+                rest = &after[len..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = &after[2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Parses a `NAME=VALUE` template variable.
+pub fn parse_var(s: &str) -> Result<(String, String), String> {
+    let (name, value) =
+        s.split_once('=').ok_or_else(|| format!("expected NAME=VALUE, got `{s}`"))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("missing variable name in `{s}`"));
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+fn builtin_default(name: &str, root: &Path) -> Option<String> {
+    match name {
+        "project_name" => root.file_name().map(|n| n.to_string_lossy().into_owned()),
+        "author" => Git::cmd_no_root()
+            .args(["config", "user.name"])
+            .get_stdout_lossy()
+            .ok()
+            .filter(|author| !author.is_empty()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_placeholders() {
+        let values = BTreeMap::from([("name".to_string(), "Vault".to_string())]);
+        assert_eq!(render("contract {{name}} {}", &values), "contract Vault {}");
+        assert_eq!(render("{{ name }}.t.sol", &values), "Vault.t.sol");
+        assert_eq!(render("os: ${{ matrix.os }}", &values), "os: ${{ matrix.os }}");
+        assert_eq!(render("{{name", &values), "{{name");
+    }
+
+    #[test]
+    fn resolves_variables() {
+        let manifest: TemplateManifest = toml::from_str(
+            r#"
+            [variables]
+            project_name = {}
+            solc_version = { default = "0.8.28" }
+            token = { description = "The token symbol" }
+            "#,
+        )
+        .unwrap();
+        let root = Path::new("/tmp/my-project");
+
+        let err = manifest.resolve(root, &[]).unwrap_err();
+        assert!(err.to_string().contains("token"), "{err}");
+
+        let values = manifest.resolve(root, &[("token".into(), "TKN".into())]).unwrap();
+        assert_eq!(values["project_name"], "my-project");
+        assert_eq!(values["solc_version"], "0.8.28");
+        assert_eq!(values["token"], "TKN");
+
+        assert!(manifest.resolve(root, &[("unknown".into(), "x".into())]).is_err());
+    }
+
+    #[test]
+    fn parses_vars() {
+        assert_eq!(parse_var("author=Jane Doe"), Ok(("author".into(), "Jane Doe".into())));
+        assert_eq!(parse_var("empty="), Ok(("empty".into(), String::new())));
+        assert!(parse_var("author").is_err());
+        assert!(parse_var("=x").is_err());
+    }
+}
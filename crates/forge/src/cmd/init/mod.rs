@@ -0,0 +1,561 @@
+use super::install::DependencyInstallOpts;
+use clap::{Parser, ValueHint};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Table};
+use eyre::{Result, WrapErr};
+use foundry_cli::utils::Git;
+use foundry_common::{fs, shell};
+use foundry_compilers::artifacts::remappings::Remapping;
+use foundry_config::Config;
+use std::path::{Path, PathBuf};
+use yansi::Paint;
+
+pub mod abi;
+use abi::AbiScaffold;
+
+pub mod ci;
+use ci::CiProvider;
+
+pub mod local;
+use local::LocalTemplate;
+
+pub mod manifest;
+
+pub mod registry;
+use registry::{TemplateRegistry, DEFAULT_REGISTRY_URL};
+
+pub mod wizard;
+use wizard::{InitLanguage, WizardAnswers};
+
+/// The directory containing the packages of a workspace.
+const WORKSPACE_PACKAGES_DIR: &str = "packages";
+
+/// The package generated by `forge init --workspace`.
+const WORKSPACE_EXAMPLE_PACKAGE: &str = "counter";
+
+/// CLI arguments for `forge init`.
+#[derive(Clone, Debug, Default, Parser)]
+pub struct InitArgs {
+    /// The root directory of the new project.
+    #[arg(value_hint = ValueHint::DirPath, default_value = ".", value_name = "PATH")]
+    pub root: PathBuf,
+
+    /// The template to start from.
+    ///
+    /// Either a short name from the template registry (e.g. `erc20`), a GitHub `org/repo`, a git
+    /// URL or a path to a local directory. Local paths must be absolute, start with `./` or `../`,
+    /// or use the `file:` scheme.
+    ///
+    /// Local templates are copied without any git network operations. Files matching the patterns
+    /// of a `.forgeignore` file in the template are skipped, and if a `.forgeinclude` file exists
+    /// only files matching its patterns are copied.
+    #[arg(long, short)]
+    pub template: Option<String>,
+
+    /// Branch argument that can only be used with template option.
+    /// If not specified, the default branch is used.
+    ///
+    /// The branch of a local template is checked out from its git repository.
+    #[arg(long, short, requires = "template")]
+    pub branch: Option<String>,
+
+    /// Subdirectory of the template repository to use as the project root.
+    ///
+    /// Useful for templates living inside a monorepo.
+    #[arg(long, requires = "template", value_name = "PATH")]
+    pub template_path: Option<PathBuf>,
+
+    /// Set a variable declared in the `template.toml` manifest of the template.
+    ///
+    /// Every `{{ NAME }}` placeholder in the contents and paths of the template files is replaced
+    /// with the value.
+    #[arg(
+        long = "var",
+        requires = "template",
+        value_name = "NAME=VALUE",
+        value_parser = manifest::parse_var
+    )]
+    pub vars: Vec<(String, String)>,
+
+    /// List the templates available in the template registry and exit.
+    #[arg(long, conflicts_with = "template")]
+    pub list_templates: bool,
+
+    /// The URL of the template registry used to resolve template short names.
+    #[arg(
+        long,
+        env = "FORGE_TEMPLATE_REGISTRY",
+        value_name = "URL",
+        default_value = DEFAULT_REGISTRY_URL
+    )]
+    pub registry_url: String,
+
+    /// Do not install dependencies from the network.
+    #[arg(long, conflicts_with = "template", visible_alias = "no-deps")]
+    pub offline: bool,
+
+    /// Create the project even if the specified root directory is not empty.
+    #[arg(long, conflicts_with = "template")]
+    pub force: bool,
+
+    /// Create a .vscode/settings.json file with Solidity settings, and generate a remappings.txt
+    /// file.
+    #[arg(long, conflicts_with = "template")]
+    pub vscode: bool,
+
+    /// Initialize a Huff project template.
+    ///
+    /// Contracts are compiled with `huffc` through FFI, which is enabled in the generated config.
+    #[arg(long, conflicts_with = "template")]
+    pub huff: bool,
+
+    /// Scaffold the project from an existing contract ABI.
+    ///
+    /// Generates a Solidity interface, a mock implementation and a test harness wired to the
+    /// interface instead of the default `Counter` contract.
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH",
+        conflicts_with_all = ["template", "huff"]
+    )]
+    pub from_abi: Option<PathBuf>,
+
+    /// Initialize a workspace with multiple packages under `packages/`, sharing the `lib`
+    /// directory and root `foundry.toml`.
+    ///
+    /// Each package is configured by a profile of the same name.
+    #[arg(long, conflicts_with = "template")]
+    pub workspace: bool,
+
+    /// Interactively choose the language (Solidity, Vyper or Huff), compiler version, license,
+    /// test settings and CI provider of the new project.
+    #[arg(long, conflicts_with = "template")]
+    pub interactive: bool,
+
+    /// The CI provider to generate a pipeline for.
+    ///
+    /// Defaults to GitHub Actions.
+    #[arg(long, value_enum, conflicts_with = "template", value_name = "PROVIDER")]
+    pub ci: Option<CiProvider>,
+
+    #[command(flatten)]
+    pub install: DependencyInstallOpts,
+}
+
+impl InitArgs {
+    pub fn run(self) -> Result<()> {
+        let Self {
+            root,
+            template,
+            mut branch,
+            mut template_path,
+            vars,
+            list_templates,
+            registry_url,
+            install,
+            offline,
+            force,
+            vscode,
+            huff,
+            from_abi,
+            workspace,
+            interactive,
+            ci,
+        } = self;
+
+        if list_templates {
+            return list_registry_templates(&registry_url);
+        }
+        let DependencyInstallOpts { shallow, no_git, commit, .. } = install;
+
+        // create the root dir if it does not exist
+        if !root.exists() {
+            fs::create_dir_all(&root)?;
+        }
+        let root = dunce::canonicalize(root)?;
+        let git = Git::new(&root).shallow(shallow);
+
+        // a local template is copied as is, without touching the network
+        if let Some(template) = template.as_deref().filter(|t| LocalTemplate::is_local(t)) {
+            // if target is not empty
+            if root.read_dir().is_ok_and(|mut i| i.next().is_some()) {
+                eyre::bail!("Cannot run `init` on a non-empty directory.");
+            }
+
+            let path = LocalTemplate::path(template);
+            let checkout = branch
+                .as_deref()
+                .map(|branch| LocalTemplate::checkout(path, branch))
+                .transpose()?;
+            let mut template =
+                LocalTemplate::new(checkout.as_ref().map_or(path, |dir| dir.path()))?;
+            if let Some(path) = &template_path {
+                template = LocalTemplate::new(&template.root().join(template_subdir(path)?))?;
+            }
+            let source = match &branch {
+                Some(branch) => format!("{} at {branch}", path.display()),
+                None => template.root().display().to_string(),
+            };
+            sh_println!("Initializing {} from {source}...", root.display())?;
+            template.copy_to(&root)?;
+            manifest::render_template(&root, &vars)?;
+
+            if !no_git {
+                if !git.is_in_repo()? {
+                    git.init()?;
+                }
+                if commit {
+                    git.add(Some("--all"))?;
+                    git.commit(&format!("chore: init from {source}"))?;
+                }
+            }
+        }
+        // if a template is provided, then this command initializes a git repo,
+        // fetches the template repo, and resets the git history to the head of the fetched
+        // repo with no other history
+        else if let Some(mut template) = template {
+            // resolve short names such as `erc20` against the template registry
+            if registry::is_short_name(&template) {
+                let registry = TemplateRegistry::load(&registry_url).wrap_err_with(|| {
+                    format!("could not resolve template `{template}` from the template registry")
+                })?;
+                let Some(entry) = registry.find(&template) else {
+                    eyre::bail!(
+                        "could not resolve template `{template}`: no such template in the \
+                         registry at {registry_url}.\n\
+                         Run `forge init --list-templates` to see the available templates, or \
+                         use `org/repo` for a GitHub repository."
+                    );
+                };
+                branch = branch.or_else(|| entry.branch.clone());
+                template_path = template_path.or_else(|| entry.path.clone());
+                template = entry.repository.clone();
+            }
+
+            let template = if template.contains("://") {
+                template
+            } else if template.starts_with("github.com/") {
+                "https://".to_string() + &template
+            } else {
+                "https://github.com/".to_string() + &template
+            };
+            sh_println!("Initializing {} from {}...", root.display(), template)?;
+            // initialize the git repository
+            git.init()?;
+
+            // fetch the template - always fetch shallow for templates since git history will be
+            // collapsed. gitmodules will be initialized after the template is fetched
+            git.fetch(true, &template, branch)?;
+
+            // reset git history to the head of the template
+            // first get the commit hash that was fetched
+            let commit_hash = git.commit_hash(true, "FETCH_HEAD")?;
+            // format a commit message for the new repo
+            let commit_msg = format!("chore: init from {template} at {commit_hash}");
+            // use either the whole tree of the template or only the requested subdirectory
+            let tree = match &template_path {
+                Some(path) => template_subtree(path)?,
+                None => "FETCH_HEAD^{tree}".to_string(),
+            };
+            // get the hash of the tree with the new commit message
+            let new_commit_hash = git.commit_tree(tree, Some(commit_msg))?;
+            // reset head of this repo to be the head of the template repo
+            git.reset(true, new_commit_hash)?;
+
+            // if shallow, just initialize submodules
+            if shallow {
+                git.submodule_init()?;
+            } else {
+                // if not shallow, initialize and clone submodules (without fetching latest)
+                git.submodule_update(false, false, true, true, std::iter::empty::<PathBuf>())?;
+            }
+
+            // substitute the template variables and commit the result on top of the template
+            if manifest::render_template(&root, &vars)? && commit {
+                git.add(Some("--all"))?;
+                git.commit("chore: render template variables")?;
+            }
+        } else {
+            // if target is not empty
+            if root.read_dir().is_ok_and(|mut i| i.next().is_some()) {
+                if !force {
+                    eyre::bail!(
+                        "Cannot run `init` on a non-empty directory.\n\
+                        Run with the `--force` flag to initialize regardless."
+                    );
+                }
+                sh_warn!("Target directory is not empty, but `--force` was specified")?;
+            }
+
+            // ensure git status is clean before generating anything
+            if !no_git && commit && !force && git.is_in_repo()? {
+                git.ensure_clean()?;
+            }
+
+            let mut answers = if interactive {
+                WizardAnswers::prompt(!huff && from_abi.is_none(), ci)?
+            } else {
+                WizardAnswers { ci: ci.unwrap_or_default(), ..Default::default() }
+            };
+            if huff {
+                answers.language = InitLanguage::Huff;
+            }
+
+            sh_println!("Initializing {}...", root.display())?;
+
+            // the directory of the generated sources, which is a package for workspaces
+            let project = if workspace {
+                root.join(WORKSPACE_PACKAGES_DIR).join(WORKSPACE_EXAMPLE_PACKAGE)
+            } else {
+                root.clone()
+            };
+
+            // make the dirs
+            let src = project.join("src");
+            fs::create_dir_all(&src)?;
+
+            let test = project.join("test");
+            fs::create_dir_all(&test)?;
+
+            let script = project.join("script");
+            fs::create_dir_all(&script)?;
+
+            if let Some(abi) = &from_abi {
+                AbiScaffold::load(abi)?.write(&project)?;
+            } else if answers.language == InitLanguage::Huff {
+                write_huff_template(&project)?;
+            } else if answers.language == InitLanguage::Vyper {
+                write_vyper_template(&project)?;
+            } else {
+                // write the contract file
+                let contract_path = src.join("Counter.sol");
+                fs::write(contract_path, include_str!("../../../assets/CounterTemplate.sol"))?;
+                // write the tests
+                let contract_path = test.join("Counter.t.sol");
+                fs::write(contract_path, include_str!("../../../assets/CounterTemplate.t.sol"))?;
+                // write the script
+                let contract_path = script.join("Counter.s.sol");
+                fs::write(contract_path, include_str!("../../../assets/CounterTemplate.s.sol"))?;
+            }
+            answers.apply_license(&project)?;
+
+            // Write the default README file
+            let readme_path = root.join("README.md");
+            fs::write(readme_path, include_str!("../../../assets/README.md"))?;
+
+            // write foundry.toml, if it doesn't exist already
+            let dest = root.join(Config::FILE_NAME);
+            let mut config = Config::load_with_root(&root)?;
+            if !dest.exists() {
+                let content = config.clone().into_basic().to_string_pretty()?;
+                let mut doc = content.parse::<toml_edit::DocumentMut>()?;
+                answers.apply_to_config(&mut doc, config.profile.as_str().as_str());
+                if workspace {
+                    add_workspace_config(&mut doc, &[WORKSPACE_EXAMPLE_PACKAGE]);
+                }
+                fs::write(dest, doc.to_string())?;
+            }
+            let git = self.install.git(&config);
+
+            // set up the repo
+            if !no_git {
+                init_git_repo(git, commit, answers.ci)?;
+            }
+
+            // install forge-std
+            if !offline {
+                if root.join("lib/forge-std").exists() {
+                    sh_warn!("\"lib/forge-std\" already exists, skipping install...")?;
+                    self.install.install(&mut config, vec![])?;
+                } else {
+                    let dep = "https://github.com/foundry-rs/forge-std".parse()?;
+                    self.install.install(&mut config, vec![dep])?;
+                }
+            }
+
+            // init vscode settings
+            if vscode {
+                init_vscode(&root)?;
+            }
+        }
+
+        sh_println!("{}", "    Initialized forge project".green())?;
+        Ok(())
+    }
+}
+
+/// Writes the Huff counter contract, its interface and deployer helper, test and script.
+fn write_huff_template(root: &Path) -> Result<()> {
+    let utils = root.join("src/utils");
+    fs::create_dir_all(&utils)?;
+    let interface = root.join("src/interface");
+    fs::create_dir_all(&interface)?;
+
+    fs::write(
+        root.join("src/Counter.huff"),
+        include_str!("../../../assets/huff/CounterTemplate.huff"),
+    )?;
+    fs::write(
+        utils.join("HuffDeployer.sol"),
+        include_str!("../../../assets/huff/HuffDeployerTemplate.sol"),
+    )?;
+    fs::write(
+        interface.join("ICounter.sol"),
+        include_str!("../../../assets/huff/ICounterTemplate.sol"),
+    )?;
+    fs::write(
+        root.join("test/Counter.t.sol"),
+        include_str!("../../../assets/huff/CounterTemplate.t.sol"),
+    )?;
+    fs::write(
+        root.join("script/Counter.s.sol"),
+        include_str!("../../../assets/huff/CounterTemplate.s.sol"),
+    )?;
+    Ok(())
+}
+
+/// Writes the Vyper counter contract, its interface, test and script.
+fn write_vyper_template(root: &Path) -> Result<()> {
+    let interface = root.join("src/interface");
+    fs::create_dir_all(&interface)?;
+
+    fs::write(
+        root.join("src/Counter.vy"),
+        include_str!("../../../assets/vyper/CounterTemplate.vy"),
+    )?;
+    fs::write(
+        interface.join("ICounter.sol"),
+        include_str!("../../../assets/vyper/ICounterTemplate.sol"),
+    )?;
+    fs::write(
+        root.join("test/Counter.t.sol"),
+        include_str!("../../../assets/vyper/CounterTemplate.t.sol"),
+    )?;
+    fs::write(
+        root.join("script/Counter.s.sol"),
+        include_str!("../../../assets/vyper/CounterTemplate.s.sol"),
+    )?;
+    Ok(())
+}
+
+/// Adds the `[workspace]` section and a profile for each of the `packages`.
+fn add_workspace_config(doc: &mut toml_edit::DocumentMut, packages: &[&str]) {
+    for &package in packages {
+        let dir = format!("{WORKSPACE_PACKAGES_DIR}/{package}");
+        let profile = &mut doc["profile"][package];
+        profile["src"] = toml_edit::value(format!("{dir}/src"));
+        profile["test"] = toml_edit::value(format!("{dir}/test"));
+        profile["script"] = toml_edit::value(format!("{dir}/script"));
+        profile["out"] = toml_edit::value(format!("out/{package}"));
+        profile["cache_path"] = toml_edit::value(format!("cache/{package}"));
+    }
+
+    let mut members = toml_edit::Array::new();
+    members.push(format!("{WORKSPACE_PACKAGES_DIR}/*"));
+    doc["workspace"]["members"] = toml_edit::value(members);
+}
+
+/// Prints all templates of the registry at `url`.
+fn list_registry_templates(url: &str) -> Result<()> {
+    let registry = TemplateRegistry::load(url)?;
+    if shell::is_json() {
+        return sh_println!("{}", serde_json::to_string_pretty(&registry.templates)?);
+    }
+
+    let mut table = Table::new();
+    table.apply_modifier(UTF8_ROUND_CORNERS);
+    table.set_header(["Name", "Repository", "Description"]);
+    for entry in &registry.templates {
+        let name =
+            if entry.official { format!("{} (official)", entry.name) } else { entry.name.clone() };
+        table.add_row([name, entry.repository.clone(), entry.description.clone()]);
+    }
+    sh_println!("{table}")
+}
+
+/// Returns the tree-ish of the given subdirectory of the fetched template.
+fn template_subtree(path: &Path) -> Result<String> {
+    Ok(format!("FETCH_HEAD:{}", template_subdir(path)?))
+}
+
+/// Normalizes the given template subdirectory, rejecting paths escaping the template.
+fn template_subdir(path: &Path) -> Result<String> {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let path = path.trim_start_matches("./").trim_matches('/');
+    if path.is_empty() || path.split('/').any(|c| c == "..") {
+        eyre::bail!("Invalid template path: {path:?}");
+    }
+    Ok(path.to_string())
+}
+
+/// Initialises `root` as a git repository, if it isn't one already.
+///
+/// Creates `.gitignore` and the pipeline file of the `ci` provider, if they don't exist already.
+///
+/// Commits everything in `root` if `commit` is true.
+fn init_git_repo(git: Git<'_>, commit: bool, ci: CiProvider) -> Result<()> {
+    // git init
+    if !git.is_in_repo()? {
+        git.init()?;
+    }
+
+    // .gitignore
+    let gitignore = git.root.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(gitignore, include_str!("../../../assets/.gitignoreTemplate"))?;
+    }
+
+    // ci pipeline
+    ci.write(git.root)?;
+
+    // commit everything
+    if commit {
+        git.add(Some("--all"))?;
+        git.commit("chore: forge init")?;
+    }
+
+    Ok(())
+}
+
+/// initializes the `.vscode/settings.json` file
+fn init_vscode(root: &Path) -> Result<()> {
+    let remappings_file = root.join("remappings.txt");
+    if !remappings_file.exists() {
+        let mut remappings = Remapping::find_many(&root.join("lib"))
+            .into_iter()
+            .map(|r| r.into_relative(root).to_relative_remapping().to_string())
+            .collect::<Vec<_>>();
+        if !remappings.is_empty() {
+            remappings.sort();
+            let content = remappings.join("\n");
+            fs::write(remappings_file, content)?;
+        }
+    }
+
+    let vscode_dir = root.join(".vscode");
+    let settings_file = vscode_dir.join("settings.json");
+    let mut settings = if !vscode_dir.is_dir() {
+        fs::create_dir_all(&vscode_dir)?;
+        serde_json::json!({})
+    } else if settings_file.exists() {
+        foundry_compilers::utils::read_json_file(&settings_file)?
+    } else {
+        serde_json::json!({})
+    };
+
+    let obj = settings.as_object_mut().expect("Expected settings object");
+    // insert [vscode-solidity settings](https://github.com/juanfranblanco/vscode-solidity)
+    let src_key = "solidity.packageDefaultDependenciesContractsDirectory";
+    if !obj.contains_key(src_key) {
+        obj.insert(src_key.to_string(), serde_json::Value::String("src".to_string()));
+    }
+    let lib_key = "solidity.packageDefaultDependenciesDirectory";
+    if !obj.contains_key(lib_key) {
+        obj.insert(lib_key.to_string(), serde_json::Value::String("lib".to_string()));
+    }
+
+    let content = serde_json::to_string_pretty(&settings)?;
+    fs::write(settings_file, content)?;
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+//! Registry of curated `forge init` templates.
+//!
+//! The registry is a JSON document listing templates by short name, e.g.:
+//!
+//! ```json
+//! {
+//!   "templates": [
+//!     {
+//!       "name": "erc20",
+//!       "repository": "foundry-rs/forge-template",
+//!       "description": "ERC20 token starter",
+//!       "branch": "main",
+//!       "path": "erc20"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Each registry URL is cached in `~/.foundry/cache/templates/registry-<hash>.json` and refreshed
+//! once it is older than [`REGISTRY_CACHE_TTL`].
+
+use alloy_primitives::{hex, keccak256};
+use eyre::{Context, Result};
+use foundry_common::fs;
+use foundry_config::Config;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The default URL of the template registry.
+pub const DEFAULT_REGISTRY_URL: &str =
+    "https://raw.githubusercontent.com/foundry-rs/forge-templates/main/registry.json";
+
+/// How long a cached registry is considered fresh.
+pub const REGISTRY_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A template registry.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateRegistry {
+    /// All known templates.
+    #[serde(default)]
+    pub templates: Vec<TemplateEntry>,
+}
+
+/// A single template entry of the [`TemplateRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateEntry {
+    /// The short name of the template, e.g. `erc20`.
+    pub name: String,
+    /// The template repository, either as `org/repo` or as a full URL.
+    pub repository: String,
+    /// A short human readable description.
+    #[serde(default)]
+    pub description: String,
+    /// The branch to fetch, defaults to the default branch of the repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// The subdirectory of the repository to use as the project root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Whether this template is maintained by the Foundry team.
+    #[serde(default)]
+    pub official: bool,
+}
+
+impl TemplateRegistry {
+    /// Loads the registry from `url`, using the local cache if it is still fresh.
+    ///
+    /// If fetching fails, a stale cache is used as a fallback.
+    pub fn load(url: &str) -> Result<Self> {
+        let cache = Self::cache_path(url);
+        if let Some(cache) = &cache {
+            if is_fresh(cache) {
+                if let Ok(registry) = Self::read(cache) {
+                    return Ok(registry);
+                }
+            }
+        }
+
+        match foundry_cli::utils::block_on(Self::fetch(url)) {
+            Ok(registry) => {
+                if let Some(cache) = &cache {
+                    if let Err(err) = registry.write(cache) {
+                        trace!(?err, "failed to cache template registry");
+                    }
+                }
+                Ok(registry)
+            }
+            Err(err) => {
+                if let Some(registry) = cache.as_deref().and_then(|c| Self::read(c).ok()) {
+                    sh_warn!("Failed to fetch template registry, using cached version")?;
+                    return Ok(registry);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Fetches the registry from `url`.
+    pub async fn fetch(url: &str) -> Result<Self> {
+        let response = reqwest::get(url)
+            .await
+            .and_then(|r| r.error_for_status())
+            .wrap_err_with(|| format!("failed to fetch template registry from {url}"))?;
+        let text = response.text().await?;
+        Self::parse(&text)
+    }
+
+    /// Parses a registry from its JSON representation.
+    pub fn parse(s: &str) -> Result<Self> {
+        serde_json::from_str(s).wrap_err("failed to parse template registry")
+    }
+
+    /// Returns the template with the given short name.
+    pub fn find(&self, name: &str) -> Option<&TemplateEntry> {
+        self.templates.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the path of the cached registry fetched from `url`:
+    /// `~/.foundry/cache/templates/registry-<hash>.json`.
+    pub fn cache_path(url: &str) -> Option<PathBuf> {
+        let hash = hex::encode(&keccak256(url)[..8]);
+        Some(Config::foundry_cache_dir()?.join("templates").join(format!("registry-{hash}.json")))
+    }
+
+    fn read(path: &Path) -> Result<Self> {
+        Ok(fs::read_json_file(path)?)
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(fs::write_json_file(path, self)?)
+    }
+}
+
+/// Returns `true` if `template` looks like a registry short name rather than a repository.
+pub fn is_short_name(template: &str) -> bool {
+    !template.is_empty() &&
+        template.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn is_fresh(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|elapsed| elapsed < REGISTRY_CACHE_TTL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_registry() {
+        let registry = TemplateRegistry::parse(
+            r#"{
+                "templates": [
+                    { "name": "erc20", "repository": "foundry-rs/erc20-template", "official": true },
+                    {
+                        "name": "Monorepo",
+                        "repository": "https://github.com/org/starters",
+                        "description": "starter",
+                        "branch": "v1",
+                        "path": "packages/forge"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let erc20 = registry.find("erc20").unwrap();
+        assert_eq!(erc20.repository, "foundry-rs/erc20-template");
+        assert!(erc20.official);
+        assert!(erc20.branch.is_none());
+
+        let mono = registry.find("monorepo").unwrap();
+        assert_eq!(mono.branch.as_deref(), Some("v1"));
+        assert_eq!(mono.path.as_deref(), Some(Path::new("packages/forge")));
+
+        assert!(registry.find("erc721").is_none());
+    }
+
+    #[test]
+    fn detects_short_names() {
+        assert!(is_short_name("erc20"));
+        assert!(is_short_name("uniswap-v4_hook"));
+        assert!(!is_short_name("foundry-rs/forge-template"));
+        assert!(!is_short_name("https://github.com/foundry-rs/forge-template"));
+        assert!(!is_short_name(""));
+    }
+
+    #[test]
+    fn caches_registries_per_url() {
+        let default = TemplateRegistry::cache_path(DEFAULT_REGISTRY_URL);
+        assert_eq!(default, TemplateRegistry::cache_path(DEFAULT_REGISTRY_URL));
+        assert_ne!(default, TemplateRegistry::cache_path("https://example.com/registry.json"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Interactive `forge init --interactive` wizard.
+
+use super::ci::CiProvider;
+use eyre::Result;
+use foundry_common::fs;
+use std::{fmt, path::Path, str::FromStr};
+use toml_edit::{value, DocumentMut};
+
+/// The default number of fuzz runs, see `FuzzConfig`.
+const DEFAULT_FUZZ_RUNS: u32 = 256;
+
+/// The default invariant depth, see `InvariantConfig`.
+const DEFAULT_INVARIANT_DEPTH: u32 = 500;
+
+/// The license used by the default templates.
+pub const DEFAULT_LICENSE: &str = "UNLICENSED";
+
+/// The language of the generated project.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InitLanguage {
+    #[default]
+    Solidity,
+    Vyper,
+    Huff,
+}
+
+/// The answers collected by the wizard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WizardAnswers {
+    /// The language of the generated contracts.
+    pub language: InitLanguage,
+    /// The pinned solc version, `None` to auto-detect.
+    pub solc: Option<semver::Version>,
+    /// The SPDX license identifier of the generated sources.
+    pub license: String,
+    /// The number of fuzz runs.
+    pub fuzz_runs: u32,
+    /// The invariant campaign depth.
+    pub invariant_depth: u32,
+    /// The CI provider to generate a pipeline for.
+    pub ci: CiProvider,
+}
+
+impl Default for WizardAnswers {
+    fn default() -> Self {
+        Self {
+            language: InitLanguage::default(),
+            solc: None,
+            license: DEFAULT_LICENSE.to_string(),
+            fuzz_runs: DEFAULT_FUZZ_RUNS,
+            invariant_depth: DEFAULT_INVARIANT_DEPTH,
+            ci: CiProvider::default(),
+        }
+    }
+}
+
+impl WizardAnswers {
+    /// Prompts the user for all answers.
+    ///
+    /// If `ask_language` is false, the language question is skipped and Solidity is used. If `ci`
+    /// is set, the CI provider question is skipped.
+    pub fn prompt(ask_language: bool, ci: Option<CiProvider>) -> Result<Self> {
+        let defaults = Self::default();
+
+        let language = if ask_language {
+            match choose("Language", &["Solidity", "Vyper", "Huff"], 0)? {
+                0 => InitLanguage::Solidity,
+                1 => InitLanguage::Vyper,
+                _ => InitLanguage::Huff,
+            }
+        } else {
+            InitLanguage::Solidity
+        };
+
+        let solc = loop {
+            let solc: String = ask("Solidity compiler version (e.g. 0.8.28)", "auto".to_string())?;
+            if solc.eq_ignore_ascii_case("auto") {
+                break None;
+            }
+            match solc.parse() {
+                Ok(version) => break Some(version),
+                Err(_) => sh_warn!("Invalid version: {solc}")?,
+            }
+        };
+
+        let license = ask("SPDX license identifier", defaults.license)?;
+        let fuzz_runs = ask("Fuzz runs", defaults.fuzz_runs)?;
+        let invariant_depth = ask("Invariant depth", defaults.invariant_depth)?;
+        let ci = match ci {
+            Some(ci) => ci,
+            None => {
+                let options = CiProvider::ALL.map(|ci| ci.to_string());
+                let options = options.iter().map(String::as_str).collect::<Vec<_>>();
+                CiProvider::ALL[choose("CI provider", &options, 0)?]
+            }
+        };
+
+        Ok(Self { language, solc, license, fuzz_runs, invariant_depth, ci })
+    }
+
+    /// Applies the answers to the given profile of a `foundry.toml` document.
+    ///
+    /// Settings matching the defaults are not written.
+    pub fn apply_to_config(&self, doc: &mut DocumentMut, profile: &str) {
+        let profile = &mut doc["profile"][profile];
+        if let Some(solc) = &self.solc {
+            profile["solc"] = value(solc.to_string());
+        }
+        if self.language == InitLanguage::Huff {
+            profile["ffi"] = value(true);
+        }
+        if self.fuzz_runs != DEFAULT_FUZZ_RUNS {
+            profile["fuzz"]["runs"] = value(i64::from(self.fuzz_runs));
+        }
+        if self.invariant_depth != DEFAULT_INVARIANT_DEPTH {
+            profile["invariant"]["depth"] = value(i64::from(self.invariant_depth));
+        }
+    }
+
+    /// Rewrites the SPDX license identifier of all generated Solidity sources in `root`.
+    pub fn apply_license(&self, root: &Path) -> Result<()> {
+        if self.license == DEFAULT_LICENSE {
+            return Ok(());
+        }
+        for dir in ["src", "test", "script"] {
+            for file in fs::files_with_ext(&root.join(dir), "sol") {
+                let content = fs::read_to_string(&file)?;
+                let updated = content.replacen(
+                    &format!("SPDX-License-Identifier: {DEFAULT_LICENSE}"),
+                    &format!("SPDX-License-Identifier: {}", self.license),
+                    1,
+                );
+                if updated != content {
+                    fs::write(&file, updated)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Asks a question, returning `default` if the answer is empty.
+fn ask<T: FromStr + fmt::Display>(question: &str, default: T) -> Result<T> {
+    loop {
+        let input: String = prompt!("{question} [{default}]: ")?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(default);
+        }
+        match input.parse() {
+            Ok(value) => return Ok(value),
+            Err(_) => sh_warn!("Invalid value: {input}")?,
+        }
+    }
+}
+
+/// Asks the user to choose one of `options`, returning its index.
+fn choose(question: &str, options: &[&str], default: usize) -> Result<usize> {
+    sh_println!("{question}:")?;
+    for (i, option) in options.iter().enumerate() {
+        sh_println!("[{i}] {option}")?;
+    }
+    loop {
+        let input: String =
+            prompt!("Please select (0-{}, default: {default}): ", options.len() - 1)?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(default);
+        }
+        match input.parse::<usize>() {
+            Ok(i) if i < options.len() => return Ok(i),
+            _ => continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_answers_to_config() {
+        let answers = WizardAnswers {
+            language: InitLanguage::Huff,
+            solc: Some("0.8.28".parse().unwrap()),
+            fuzz_runs: 1000,
+            invariant_depth: 20,
+            ..Default::default()
+        };
+        let mut doc: DocumentMut = "[profile.default]\nsrc = \"src\"\n".parse().unwrap();
+        answers.apply_to_config(&mut doc, "default");
+
+        let config: toml::Value = toml::from_str(&doc.to_string()).unwrap();
+        let profile = &config["profile"]["default"];
+        assert_eq!(profile["solc"].as_str(), Some("0.8.28"));
+        assert_eq!(profile["ffi"].as_bool(), Some(true));
+        assert_eq!(profile["fuzz"]["runs"].as_integer(), Some(1000));
+        assert_eq!(profile["invariant"]["depth"].as_integer(), Some(20));
+    }
+}
@@ -6,7 +6,7 @@
 use eyre::{Context, Result};
 use foundry_cli::opts::{BuildOpts, CompilerOpts};
 use foundry_common::{
-    compile::{PathOrContractInfo, ProjectCompiler},
+    compile::{contract_size, PathOrContractInfo, ProjectCompiler},
     find_matching_contract_artifact, find_target_path,
     fmt::pretty_eof,
     shell,
@@ -16,9 +16,10 @@
         BytecodeOutputSelection, ContractOutputSelection, DeployedBytecodeOutputSelection,
         EvmOutputSelection, EwasmOutputSelection,
     },
-    CompactBytecode, StorageLayout,
+    CompactBytecode, ConfigurableContractArtifact, Offsets, StorageLayout,
 };
 use regex::Regex;
+use serde::Serialize;
 use serde_json::{Map, Value};
 use std::{collections::BTreeMap, fmt, str::FromStr, sync::LazyLock};
 
@@ -26,12 +27,16 @@
 #[derive(Clone, Debug, Parser)]
 pub struct InspectArgs {
     /// The identifier of the contract to inspect in the form `(<path>:)?<contractname>`.
-    #[arg(value_parser = PathOrContractInfo::from_str)]
-    pub contract: PathOrContractInfo,
+    #[arg(value_parser = PathOrContractInfo::from_str, required_unless_present = "all")]
+    pub contract: Option<PathOrContractInfo>,
 
     /// The contract artifact field to inspect.
-    #[arg(value_enum)]
-    pub field: ContractArtifactField,
+    #[arg(value_enum, required_unless_present = "all")]
+    pub field: Option<ContractArtifactField>,
+
+    /// Print the `full-report` of every compiled contract, keyed by `<path>:<contractname>`.
+    #[arg(long, conflicts_with_all = ["contract", "field"])]
+    pub all: bool,
 
     /// All build arguments are supported
     #[command(flatten)]
@@ -44,15 +49,23 @@ pub struct InspectArgs {
 
 impl InspectArgs {
     pub fn run(self) -> Result<()> {
-        let Self { contract, field, build, strip_yul_comments } = self;
+        let Self { contract, field, all, build, strip_yul_comments } = self;
+        let field = field.unwrap_or(ContractArtifactField::FullReport);
 
-        trace!(target: "forge", ?field, ?contract, "running forge inspect");
+        trace!(target: "forge", ?field, ?contract, all, "running forge inspect");
 
         // Map field to ContractOutputSelection
         let mut cos = build.compiler.extra_output;
         if !field.is_default() && !cos.iter().any(|selected| field == *selected) {
             cos.push(field.into());
         }
+        if field == ContractArtifactField::FullReport {
+            for selection in [ContractOutputSelection::Abi, ContractOutputSelection::Metadata] {
+                if !cos.contains(&selection) {
+                    cos.push(selection);
+                }
+            }
+        }
 
         // Run Optimized?
         let optimized = if field == ContractArtifactField::AssemblyOptimized {
@@ -70,6 +83,19 @@ pub fn run(self) -> Result<()> {
         // Build the project
         let project = modified_build_args.project()?;
         let compiler = ProjectCompiler::new().quiet(true);
+
+        if all {
+            let output = compiler.compile(&project)?;
+            let mut reports = BTreeMap::new();
+            for (id, artifact) in output.artifact_ids() {
+                let source = id.source.strip_prefix(project.root()).unwrap_or(&id.source);
+                let report = ContractReport::new(artifact)?;
+                reports.insert(format!("{}:{}", source.display(), id.name), report);
+            }
+            return print_json(&reports);
+        }
+
+        let contract = contract.expect("contract is required without --all");
         let target_path = find_target_path(&project, &contract)?;
         let mut output = compiler.files([target_path.clone()]).compile(&project)?;
 
@@ -138,12 +164,52 @@ pub fn run(self) -> Result<()> {
             ContractArtifactField::EofInit => {
                 print_eof(artifact.bytecode)?;
             }
+            ContractArtifactField::FullReport => {
+                print_json(&ContractReport::new(&artifact)?)?;
+            }
         };
 
         Ok(())
     }
 }
 
+/// The `full-report` of a contract, with the build outputs commonly needed by downstream tooling.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ContractReport<'a> {
+    abi: Option<&'a JsonAbi>,
+    storage_layout: Option<&'a StorageLayout>,
+    method_identifiers: Option<&'a BTreeMap<String, String>>,
+    immutable_references: Option<&'a BTreeMap<String, Vec<Offsets>>>,
+    deployed_size: Option<usize>,
+    initcode_size: Option<usize>,
+    compiler_version: Option<String>,
+    /// The compiler settings, as recorded in the metadata.
+    settings: Option<Value>,
+}
+
+impl<'a> ContractReport<'a> {
+    fn new(artifact: &'a ConfigurableContractArtifact) -> Result<Self> {
+        let metadata = artifact.metadata.as_ref().map(serde_json::to_value).transpose()?;
+        Ok(Self {
+            abi: artifact.abi.as_ref(),
+            storage_layout: artifact.storage_layout.as_ref(),
+            method_identifiers: artifact.method_identifiers.as_ref(),
+            immutable_references: artifact
+                .deployed_bytecode
+                .as_ref()
+                .map(|bytecode| &bytecode.immutable_references),
+            deployed_size: contract_size(artifact, false),
+            initcode_size: contract_size(artifact, true),
+            compiler_version: metadata
+                .as_ref()
+                .and_then(|metadata| metadata["compiler"]["version"].as_str())
+                .map(str::to_string),
+            settings: metadata.map(|mut metadata| metadata["settings"].take()),
+        })
+    }
+}
+
 fn parse_errors(abi: &JsonAbi) -> Map<String, Value> {
     let mut out = serde_json::Map::new();
     for er in abi.errors.iter().flat_map(|(_, errors)| errors) {
@@ -364,6 +430,7 @@ pub enum ContractArtifactField {
     Events,
     Eof,
     EofInit,
+    FullReport,
 }
 
 macro_rules! impl_value_enum {
@@ -453,6 +520,7 @@ enum ContractArtifactField {
         Events            => "events" | "ev",
         Eof               => "eof" | "eof-container" | "eof-deployed",
         EofInit           => "eof-init" | "eof-initcode" | "eof-initcontainer",
+        FullReport        => "full-report" | "fullReport" | "report",
     }
 }
 
@@ -482,6 +550,7 @@ fn from(field: ContractArtifactField) -> Self {
                 DeployedBytecodeOutputSelection::All,
             )),
             Caf::EofInit => Self::Evm(EvmOutputSelection::ByteCode(BytecodeOutputSelection::All)),
+            Caf::FullReport => Self::StorageLayout,
         }
     }
 }
@@ -500,7 +569,7 @@ fn eq(&self, other: &ContractOutputSelection) -> bool {
                 (Self::LegacyAssembly, Cos::Evm(Eos::LegacyAssembly)) |
                 (Self::MethodIdentifiers, Cos::Evm(Eos::MethodIdentifiers)) |
                 (Self::GasEstimates, Cos::Evm(Eos::GasEstimates)) |
-                (Self::StorageLayout, Cos::StorageLayout) |
+                (Self::StorageLayout | Self::FullReport, Cos::StorageLayout) |
                 (Self::DevDoc, Cos::DevDoc) |
                 (Self::Ir, Cos::Ir) |
                 (Self::IrOptimized, Cos::IrOptimized) |
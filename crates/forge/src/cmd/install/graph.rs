@@ -0,0 +1,511 @@
+//! The graph of installed dependencies.
+//!
+//! Dependencies can depend on other Foundry libraries, as git submodules or nested libraries, and
+//! on npm packages declared in their `package.json`. The graph records which package requires
+//! which, so that missing npm packages can be installed and incompatible pins reported.
+
+use super::{
+    lockfile::Lockfile,
+    npm::{self, NpmDependency},
+};
+use eyre::Result;
+use foundry_cli::utils::{CommandUtils, Git};
+use foundry_common::fs;
+use foundry_config::Config;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::Path,
+};
+
+/// The maximum depth of transitive npm packages installed by [`install_transitive`].
+const MAX_DEPTH: usize = 16;
+
+/// An installed dependency.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Package {
+    /// The normalized URL of the git repository.
+    pub git: Option<String>,
+    /// The checked out commit of git dependencies.
+    pub rev: Option<String>,
+    /// The release tag of the checked out commit.
+    pub tag: Option<String>,
+    /// The name of the npm package, from its `package.json`.
+    pub npm: Option<String>,
+    /// The version of the npm package.
+    pub version: Option<Version>,
+    /// Whether the package was installed from the npm registry.
+    pub from_registry: bool,
+}
+
+impl Package {
+    /// Reads the package installed at `dir`.
+    fn read(root: &Path, dir: &Path, lockfile: &Lockfile) -> Self {
+        let locked = dir
+            .strip_prefix(root)
+            .ok()
+            .and_then(|path| lockfile.dependencies.get(&path.to_string_lossy().replace('\\', "/")));
+        let mut package = Self {
+            from_registry: locked.is_some_and(|locked| locked.npm.is_some()),
+            ..Default::default()
+        };
+
+        if dir.join(".git").exists() {
+            let git = Git::new(dir);
+            package.git = git
+                .cmd()
+                .args(["remote", "get-url", "origin"])
+                .get_stdout_lossy()
+                .ok()
+                .map(|url| normalize_url(&url));
+            package.rev = git.commit_hash(false, "HEAD").ok();
+            package.tag = git
+                .cmd()
+                .args(["tag", "--points-at", "HEAD"])
+                .get_stdout_lossy()
+                .ok()
+                .and_then(|tags| tags.lines().next().map(str::to_string));
+        } else if let Some(locked) = locked {
+            package.git = locked.git.as_deref().map(normalize_url);
+            package.rev = locked.rev.clone();
+            package.tag = locked.tag.clone();
+        }
+
+        if let Some(manifest) = PackageJson::read(dir) {
+            package.npm = Some(manifest.name);
+            package.version = manifest.version.and_then(|v| Version::parse(&v).ok());
+        }
+        package
+    }
+
+    /// Returns the display name of the package.
+    pub fn name(&self, path: &str) -> String {
+        if let Some(npm) = &self.npm {
+            return npm.clone();
+        }
+        let source = self.git.as_deref().unwrap_or(path);
+        source.rsplit('/').next().unwrap_or(source).to_string()
+    }
+
+    /// Returns the installed version, release tag or abbreviated commit.
+    pub fn version(&self) -> Option<String> {
+        if self.from_registry || self.git.is_none() {
+            if let Some(version) = &self.version {
+                return Some(version.to_string());
+            }
+        }
+        self.tag.clone().or_else(|| self.rev.as_ref().map(|rev| short(rev).to_string()))
+    }
+}
+
+/// A requirement of a package on an npm package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NpmRequirement {
+    /// The path of the requiring package, relative to the project root.
+    pub from: String,
+    /// The name of the required package.
+    pub package: String,
+    /// The required range, as declared.
+    pub range: String,
+}
+
+impl NpmRequirement {
+    /// Returns the required range as a semver requirement, if it is supported.
+    pub fn req(&self) -> Option<VersionReq> {
+        parse_range(&self.range)
+    }
+}
+
+/// A set of incompatible requirements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Conflict {
+    /// The same git repository is checked out at different revisions.
+    Revisions {
+        /// The name of the repository.
+        name: String,
+        /// The paths of the checkouts with their revision.
+        pins: Vec<(String, String)>,
+    },
+    /// No installed version of an npm package satisfies all requirements.
+    Versions {
+        /// The name of the package.
+        package: String,
+        /// The installed versions.
+        installed: Vec<Version>,
+        /// The requirements.
+        requirements: Vec<NpmRequirement>,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Revisions { name, pins } => {
+                write!(f, "{name} is installed at different revisions:")?;
+                for (path, rev) in pins {
+                    write!(f, "\n    {rev} in {path}")?;
+                }
+            }
+            Self::Versions { package, installed, requirements } => {
+                let installed = installed.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                write!(
+                    f,
+                    "no installed version of {package} ({}) satisfies all requirements:",
+                    installed.join(", ")
+                )?;
+                for requirement in requirements {
+                    write!(f, "\n    {} requires {}", requirement.from, requirement.range)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The graph of installed dependencies.
+#[derive(Clone, Debug, Default)]
+pub struct DependencyGraph {
+    /// The installed packages, by path relative to the project root.
+    pub packages: BTreeMap<String, Package>,
+    /// The package which contains each transitive dependency, by path.
+    pub parents: BTreeMap<String, String>,
+    /// The npm packages required by the installed packages.
+    pub npm_requirements: Vec<NpmRequirement>,
+}
+
+impl DependencyGraph {
+    /// Resolves the graph of the dependencies installed in the libraries of `config`.
+    pub fn resolve(config: &Config) -> Result<Self> {
+        let lockfile = Lockfile::read(&config.root)?.unwrap_or_default();
+        let mut graph = Self::default();
+        for lib in &config.libs {
+            for dir in subdirs(&config.root.join(lib)) {
+                graph.visit(&config.root, None, &dir, &lockfile);
+            }
+        }
+        Ok(graph)
+    }
+
+    fn visit(&mut self, root: &Path, parent: Option<&str>, dir: &Path, lockfile: &Lockfile) {
+        let Ok(relative) = dir.strip_prefix(root) else { return };
+        let path = relative.to_string_lossy().replace('\\', "/");
+        if self.packages.contains_key(&path) {
+            return;
+        }
+        self.packages.insert(path.clone(), Package::read(root, dir, lockfile));
+        if let Some(parent) = parent {
+            self.parents.insert(path.clone(), parent.to_string());
+        }
+
+        if let Some(manifest) = PackageJson::read(dir) {
+            for (package, range) in
+                manifest.dependencies.into_iter().chain(manifest.peer_dependencies)
+            {
+                self.npm_requirements.push(NpmRequirement { from: path.clone(), package, range });
+            }
+        }
+
+        // nested dependencies are either submodules or plain directories in `lib`
+        let mut nested = submodule_paths(dir).into_iter().map(|p| dir.join(p)).collect::<Vec<_>>();
+        nested.extend(subdirs(&dir.join("lib")));
+        nested.sort();
+        nested.dedup();
+        for nested in nested.iter().filter(|nested| nested.is_dir()) {
+            self.visit(root, Some(&path), nested, lockfile);
+        }
+    }
+
+    /// Returns the transitive dependencies: the packages installed inside other packages.
+    pub fn transitive(&self) -> impl Iterator<Item = (&String, &Package, &String)> {
+        self.parents.iter().map(|(path, parent)| (path, &self.packages[path], parent))
+    }
+
+    /// Returns the required npm packages which are not installed, with their requirements.
+    pub fn missing_npm(&self) -> BTreeMap<String, Vec<NpmRequirement>> {
+        let installed =
+            self.packages.values().filter_map(|p| p.npm.as_ref()).collect::<BTreeSet<_>>();
+        let mut missing = BTreeMap::<String, Vec<NpmRequirement>>::new();
+        for requirement in &self.npm_requirements {
+            if !installed.contains(&requirement.package) {
+                missing.entry(requirement.package.clone()).or_default().push(requirement.clone());
+            }
+        }
+        missing
+    }
+
+    /// Returns the incompatible requirements of the installed packages.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+
+        let mut checkouts = BTreeMap::<&str, Vec<(String, String)>>::new();
+        for (path, package) in &self.packages {
+            if let (Some(git), Some(rev)) = (&package.git, &package.rev) {
+                let version = package.tag.clone().unwrap_or_else(|| short(rev).to_string());
+                checkouts.entry(git).or_default().push((path.clone(), version));
+            }
+        }
+        for (git, pins) in checkouts {
+            let revisions = pins.iter().map(|(_, rev)| rev).collect::<BTreeSet<_>>();
+            if revisions.len() > 1 {
+                let name = git.rsplit('/').next().unwrap_or(git).to_string();
+                conflicts.push(Conflict::Revisions { name, pins });
+            }
+        }
+
+        let mut requirements = BTreeMap::<&str, Vec<NpmRequirement>>::new();
+        for requirement in &self.npm_requirements {
+            requirements.entry(&requirement.package).or_default().push(requirement.clone());
+        }
+        for (package, requirements) in requirements {
+            let installed = self
+                .packages
+                .values()
+                .filter(|p| p.npm.as_deref() == Some(package))
+                .filter_map(|p| p.version.clone())
+                .collect::<Vec<_>>();
+            if installed.is_empty() {
+                continue;
+            }
+            let satisfied = installed.iter().any(|version| {
+                requirements.iter().all(|r| r.req().is_none_or(|req| req.matches(version)))
+            });
+            if !satisfied {
+                conflicts.push(Conflict::Versions {
+                    package: package.to_string(),
+                    installed,
+                    requirements,
+                });
+            }
+        }
+        conflicts
+    }
+
+    /// Prints the transitive dependencies and the conflicts, if any.
+    pub fn print_report(&self) -> Result<()> {
+        let transitive = self.transitive().collect::<Vec<_>>();
+        if !transitive.is_empty() {
+            let noun = if transitive.len() == 1 { "dependency" } else { "dependencies" };
+            sh_println!("Resolved {} transitive {noun}:", transitive.len())?;
+            for (path, package, parent) in transitive {
+                let mut line = format!("    {}", package.name(path));
+                if let Some(version) = package.version() {
+                    line.push(' ');
+                    line.push_str(&version);
+                }
+                sh_println!("{line} in {path}, required by {parent}")?;
+            }
+        }
+        for conflict in self.conflicts() {
+            sh_warn!("Conflicting dependency versions: {conflict}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Installs the npm packages required by packages from the npm registry which are not installed,
+/// and returns the resolved graph.
+///
+/// The installed version satisfies the requirements of all dependents, if possible.
+pub fn install_transitive(
+    config: &Config,
+    registry: &str,
+    node_modules: bool,
+) -> Result<DependencyGraph> {
+    let mut unresolvable = BTreeSet::new();
+    for _ in 0..MAX_DEPTH {
+        let graph = DependencyGraph::resolve(config)?;
+        let missing = graph
+            .missing_npm()
+            .into_iter()
+            .filter(|(package, _)| !unresolvable.contains(package))
+            .filter(|(_, requirements)| {
+                requirements.iter().any(|r| graph.packages[&r.from].from_registry)
+            })
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(graph);
+        }
+
+        for (package, requirements) in missing {
+            let comparators = requirements
+                .iter()
+                .filter_map(|r| r.req())
+                .flat_map(|req| req.comparators)
+                .collect::<Vec<_>>();
+            let version = (!comparators.is_empty()).then(|| VersionReq { comparators }.to_string());
+            let dep = NpmDependency { package: package.clone(), version, alias: None };
+            if let Err(err) = npm::install(config, &[dep], registry, node_modules) {
+                let requirements =
+                    requirements.iter().map(|r| format!("{} requires {}", r.from, r.range));
+                sh_warn!(
+                    "Could not install {package} ({}): {err}",
+                    requirements.collect::<Vec<_>>().join(", ")
+                )?;
+                unresolvable.insert(package);
+            }
+        }
+    }
+    DependencyGraph::resolve(config)
+}
+
+/// The parts of a `package.json` relevant to the dependency graph.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageJson {
+    name: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    peer_dependencies: BTreeMap<String, String>,
+}
+
+impl PackageJson {
+    /// Reads the manifest of the package at `dir`.
+    ///
+    /// Git repositories of Solidity libraries often keep the published package in `contracts/`,
+    /// so its `package.json` is preferred over the one of the repository.
+    fn read(dir: &Path) -> Option<Self> {
+        ["contracts/package.json", "package.json"]
+            .iter()
+            .map(|manifest| dir.join(manifest))
+            .find(|manifest| manifest.exists())
+            .and_then(|manifest| fs::read_json_file(&manifest).ok())
+    }
+}
+
+/// Parses an npm version range.
+///
+/// Bare versions are exact in npm, and comparators are separated by spaces instead of commas.
+/// Alternatives (`||`) are not supported.
+fn parse_range(range: &str) -> Option<VersionReq> {
+    let range = range.trim();
+    if range.contains("||") {
+        return None;
+    }
+    if Version::parse(range).is_ok() {
+        return VersionReq::parse(&format!("={range}")).ok();
+    }
+    let mut comparators = Vec::new();
+    let mut tokens = range.split_whitespace();
+    while let Some(token) = tokens.next() {
+        // `>= 1.0.0` is a single comparator
+        if token.chars().all(|c| "<>=~^".contains(c)) {
+            comparators.push(format!("{token}{}", tokens.next()?));
+        } else {
+            comparators.push(token.to_string());
+        }
+    }
+    VersionReq::parse(&comparators.join(", ")).ok()
+}
+
+/// Returns the paths of the submodules declared in the `.gitmodules` of `dir`.
+fn submodule_paths(dir: &Path) -> Vec<String> {
+    if !dir.join(".gitmodules").exists() {
+        return Vec::new();
+    }
+    Git::new(dir)
+        .cmd()
+        .args(["config", "-f", ".gitmodules", "--get-regexp", r"^submodule\..*\.path$"])
+        .get_stdout_lossy()
+        .map(|output| {
+            output.lines().filter_map(|line| line.split_once(' ')).map(|(_, p)| p.into()).collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the sorted subdirectories of `dir`, ignoring hidden directories.
+fn subdirs(dir: &Path) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return Vec::new() };
+    let mut dirs = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .filter(|path| {
+            !path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .collect::<Vec<_>>();
+    dirs.sort();
+    dirs
+}
+
+/// Normalizes a git remote URL so that different spellings of the same repository are equal.
+fn normalize_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    let url = match url.strip_prefix("git@") {
+        Some(ssh) => format!("https://{}", ssh.replacen(':', "/", 1)),
+        None => url.to_string(),
+    };
+    url.to_lowercase()
+}
+
+fn short(rev: &str) -> &str {
+    &rev[..rev.len().min(7)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_package(dir: &Path, manifest: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("package.json"), manifest).unwrap();
+    }
+
+    #[test]
+    fn parses_npm_ranges() {
+        let matches =
+            |range: &str, v: &str| parse_range(range).unwrap().matches(&v.parse().unwrap());
+        assert!(matches("5.0.2", "5.0.2"));
+        assert!(!matches("5.0.2", "5.0.3"));
+        assert!(matches("^5.0.0", "5.1.0"));
+        assert!(matches(">=4.0.0 <6", "5.9.0"));
+        assert!(!matches(">= 4.0.0 < 5", "5.0.0"));
+        assert!(parse_range("^4 || ^5").is_none());
+    }
+
+    #[test]
+    fn detects_version_conflicts() {
+        let root = tempfile::tempdir().unwrap();
+        let lib = root.path().join("lib");
+        write_package(
+            &lib.join("a"),
+            r#"{ "name": "a", "version": "1.0.0", "peerDependencies": { "c": "^1.0.0" } }"#,
+        );
+        write_package(
+            &lib.join("b"),
+            r#"{ "name": "b", "version": "1.0.0", "dependencies": { "c": "^2.0.0", "d": "1.0.0" } }"#,
+        );
+        write_package(&lib.join("c"), r#"{ "name": "c", "version": "1.2.0" }"#);
+        write_package(&lib.join("b/lib/e"), r#"{ "name": "e", "version": "0.1.0" }"#);
+
+        let config =
+            Config { root: root.path().into(), libs: vec!["lib".into()], ..Default::default() };
+        let graph = DependencyGraph::resolve(&config).unwrap();
+
+        let transitive = graph.transitive().map(|(path, ..)| path.as_str()).collect::<Vec<_>>();
+        assert_eq!(transitive, ["lib/b/lib/e"]);
+        assert_eq!(graph.missing_npm().keys().collect::<Vec<_>>(), ["d"]);
+
+        let conflicts = graph.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        let Conflict::Versions { package, requirements, .. } = &conflicts[0] else { panic!() };
+        assert_eq!(package, "c");
+        assert_eq!(requirements.len(), 2);
+    }
+
+    #[test]
+    fn normalizes_urls() {
+        assert_eq!(
+            normalize_url("git@github.com:Vectorized/solady.git"),
+            "https://github.com/vectorized/solady"
+        );
+        assert_eq!(
+            normalize_url("https://github.com/Vectorized/solady/"),
+            "https://github.com/vectorized/solady"
+        );
+    }
+}
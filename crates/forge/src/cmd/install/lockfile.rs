@@ -0,0 +1,416 @@
+//! The `foundry.lock` lockfile.
+//!
+//! Pins the revision and the content hash of every dependency installed with `forge install`, so
+//! dependencies installed without git submodules can be reinstalled and verified:
+//!
+//! ```toml
+//! [dependencies."lib/forge-std"]
+//! git = "https://github.com/foundry-rs/forge-std"
+//! rev = "1eea5bae12ae557d589f9f0f0edae2faa47cb262"
+//! tag = "v1.9.6"
+//! integrity = "sha256-..."
+//!
+//! [dependencies."lib/solady"]
+//! git = "https://github.com/Vectorized/solady"
+//! rev = "..."
+//! vendored = true
+//! integrity = "sha256-..."
+//!
+//! [dependencies."lib/openzeppelin-contracts"]
+//! npm = "@openzeppelin/contracts"
+//! version = "5.0.2"
+//! integrity = "sha256-..."
+//! ```
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use eyre::{Context, Result};
+use foundry_cli::utils::{CommandUtils, Git};
+use foundry_common::fs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The file name of the lockfile.
+pub const LOCKFILE_NAME: &str = "foundry.lock";
+
+/// The lockfile of a project: `foundry.lock`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// The locked dependencies, by installation directory relative to the project root.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, LockedDependency>,
+}
+
+/// A dependency pinned in the [`Lockfile`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedDependency {
+    /// The URL of the git repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+    /// The commit hash of git dependencies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// The tag or branch requested when the git dependency was installed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// The name of the npm package.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub npm: Option<String>,
+    /// The version of the npm package.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Whether the dependency was vendored: copied without any git metadata.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub vendored: bool,
+    /// The commit hashes of the git submodules of vendored dependencies, by path relative to the
+    /// dependency.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub submodules: BTreeMap<String, String>,
+    /// The hash of the installed files, see [`integrity`].
+    pub integrity: String,
+}
+
+impl Lockfile {
+    /// Returns the path of the lockfile of the project at `root`.
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(LOCKFILE_NAME)
+    }
+
+    /// Reads the lockfile of the project at `root`, if it exists.
+    pub fn read(root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        let lockfile = toml::from_str(&content)
+            .wrap_err_with(|| format!("failed to parse {}", path.display()))?;
+        Ok(Some(lockfile))
+    }
+
+    /// Writes the lockfile of the project at `root`.
+    pub fn write(&self, root: &Path) -> Result<()> {
+        let content = format!(
+            "# This file is generated by `forge install`, do not edit it manually.\n\n{}",
+            toml::to_string_pretty(self)?
+        );
+        fs::write(Self::path(root), content)?;
+        Ok(())
+    }
+
+    /// Locks the git dependency installed at `path` at revision `rev`.
+    pub fn lock_git(
+        &mut self,
+        root: &Path,
+        path: &Path,
+        url: &str,
+        rev: String,
+        tag: Option<String>,
+    ) -> Result<()> {
+        let dep = LockedDependency {
+            git: Some(url.to_string()),
+            rev: Some(rev),
+            tag: tag.filter(|t| !t.is_empty()),
+            integrity: integrity(path)?,
+            ..Default::default()
+        };
+        self.dependencies.insert(key(root, path)?, dep);
+        Ok(())
+    }
+
+    /// Records the tag the locked git dependency at `path` was updated to, e.g. by
+    /// `forge update --minor`. Dependencies which are not locked are ignored.
+    pub fn lock_tag(&mut self, root: &Path, path: &Path, tag: &str) -> Result<()> {
+        if let Some(dep) = self.dependencies.get_mut(&key(root, path)?) {
+            dep.tag = Some(tag.to_string());
+        }
+        Ok(())
+    }
+
+    /// Marks the locked git dependency at `path` as vendored, recording the revisions of its
+    /// submodules.
+    pub fn vendor(
+        &mut self,
+        root: &Path,
+        path: &Path,
+        submodules: BTreeMap<String, String>,
+    ) -> Result<()> {
+        let key = key(root, path)?;
+        let dep =
+            self.dependencies.get_mut(&key).ok_or_else(|| eyre::eyre!("{key} is not locked"))?;
+        dep.vendored = true;
+        dep.submodules = submodules;
+        Ok(())
+    }
+
+    /// Locks the npm package `package` installed at `path`.
+    pub fn lock_npm(
+        &mut self,
+        root: &Path,
+        path: &Path,
+        package: &str,
+        version: &str,
+    ) -> Result<()> {
+        let dep = LockedDependency {
+            npm: Some(package.to_string()),
+            version: Some(version.to_string()),
+            integrity: integrity(path)?,
+            ..Default::default()
+        };
+        self.dependencies.insert(key(root, path)?, dep);
+        Ok(())
+    }
+
+    /// Removes the dependency installed at `path`, returning `true` if it was locked.
+    pub fn unlock(&mut self, root: &Path, path: &Path) -> Result<bool> {
+        Ok(self.dependencies.remove(&key(root, path)?).is_some())
+    }
+
+    /// Updates the revision and integrity of all installed dependencies, e.g. after
+    /// `forge update`.
+    pub fn refresh(&mut self, root: &Path) -> Result<()> {
+        for (path, dep) in &mut self.dependencies {
+            let path = root.join(path);
+            if !path.exists() {
+                continue;
+            }
+            if dep.git.is_some() && path.join(".git").exists() {
+                dep.rev = Some(Git::new(&path).commit_hash(false, "HEAD")?);
+            }
+            dep.integrity = integrity(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the locked dependencies which are not installed.
+    pub fn missing(&self, root: &Path) -> impl Iterator<Item = (&String, &LockedDependency)> {
+        let root = root.to_path_buf();
+        self.dependencies.iter().filter(move |(path, _)| !root.join(path).exists())
+    }
+
+    /// Checks that all dependencies are installed and match the lockfile.
+    pub fn verify(&self, root: &Path) -> Result<()> {
+        self.check(root, false)
+    }
+
+    /// Checks that the installed dependencies match the lockfile, ignoring missing ones.
+    ///
+    /// Used by `forge install --frozen` before installing anything.
+    pub fn verify_installed(&self, root: &Path) -> Result<()> {
+        self.check(root, true)
+    }
+
+    fn check(&self, root: &Path, skip_missing: bool) -> Result<()> {
+        let mut errors = Vec::new();
+        for (path, dep) in &self.dependencies {
+            let dir = root.join(path);
+            if !dir.exists() {
+                if !skip_missing {
+                    errors.push(format!("{path} is not installed"));
+                }
+                continue;
+            }
+            if let Some(rev) = &dep.rev {
+                if dir.join(".git").exists() {
+                    let installed = Git::new(&dir).commit_hash(false, "HEAD")?;
+                    if installed != *rev {
+                        errors.push(format!("{path} is at {installed}, but locked at {rev}"));
+                        continue;
+                    }
+                }
+            }
+            if integrity(&dir)? != dep.integrity {
+                errors.push(format!("{path} does not match its locked integrity"));
+            }
+        }
+        if !errors.is_empty() {
+            eyre::bail!(
+                "{LOCKFILE_NAME} is out of date:\n- {}\nRun `forge install` without `--frozen` to update it.",
+                errors.join("\n- ")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns the lockfile key of the dependency at `path`.
+fn key(root: &Path, path: &Path) -> Result<String> {
+    let relative = path
+        .strip_prefix(root)
+        .wrap_err_with(|| format!("{} is not inside the project", path.display()))?;
+    Ok(relative.to_string_lossy().replace('\\', "/"))
+}
+
+/// Directories at the root of a dependency which contain build artifacts rather than sources.
+const BUILD_DIRS: &[&str] = &["out", "cache", "broadcast", "node_modules"];
+
+/// Computes the hash of the source files in `dir`: `sha256-<base64>`.
+///
+/// If `dir` is a git repository, only the files tracked by git are hashed. Otherwise all files
+/// are hashed, except git metadata. In both cases the build directories at the root of the
+/// dependency are ignored, so compiling inside the dependency does not change its integrity.
+///
+/// The hash covers the relative paths and contents of the files, so it does not depend on how the
+/// dependency was installed.
+pub fn integrity(dir: &Path) -> Result<String> {
+    let mut files =
+        if dir.join(".git").exists() { tracked_files(dir)? } else { collect_files(dir, dir)? };
+    files.retain(|file| file.split_once('/').is_none_or(|(first, _)| !BUILD_DIRS.contains(&first)));
+    files.sort();
+    files.dedup();
+
+    let mut hasher = Sha256::new();
+    for relative in files {
+        let path = dir.join(&relative);
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        if path.is_symlink() {
+            let target = std::fs::read_link(&path)?;
+            hash_content(&mut hasher, target.to_string_lossy().as_bytes());
+        } else if path.exists() {
+            let content = std::fs::read(&path)
+                .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+            hash_content(&mut hasher, &content);
+        } else {
+            // a tracked file which was deleted
+            hasher.update([1]);
+        }
+    }
+    Ok(format!("sha256-{}", STANDARD.encode(hasher.finalize())))
+}
+
+fn hash_content(hasher: &mut Sha256, content: &[u8]) {
+    hasher.update([0]);
+    hasher.update((content.len() as u64).to_le_bytes());
+    hasher.update(content);
+}
+
+/// Returns the files tracked by the git repository at `dir`, including those of its submodules.
+fn tracked_files(dir: &Path) -> Result<Vec<String>> {
+    let output = Git::new(dir)
+        .cmd()
+        .args(["-c", "core.quotepath=off", "ls-files", "-z", "--recurse-submodules"])
+        .exec()?;
+    Ok(output
+        .stdout
+        .split(|b| *b == 0)
+        .filter(|file| !file.is_empty())
+        .map(|file| String::from_utf8_lossy(file).into_owned())
+        .filter(|file| !dir.join(file).is_dir())
+        .collect())
+}
+
+/// Returns all files in `dir`, except git metadata.
+fn collect_files(root: &Path, dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            files.extend(collect_files(root, &path)?);
+        } else {
+            files.push(key(root, &path)?);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_integrity() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/A.sol"), "contract A {}").unwrap();
+        let hash = integrity(dir.path()).unwrap();
+        assert!(hash.starts_with("sha256-"));
+
+        // git metadata is ignored
+        fs::create_dir_all(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+        assert_eq!(integrity(dir.path()).unwrap(), hash);
+
+        // build artifacts are ignored
+        fs::create_dir_all(dir.path().join("out/A.sol")).unwrap();
+        fs::write(dir.path().join("out/A.sol/A.json"), "{}").unwrap();
+        fs::create_dir_all(dir.path().join("cache")).unwrap();
+        fs::write(dir.path().join("cache/solidity-files-cache.json"), "{}").unwrap();
+        assert_eq!(integrity(dir.path()).unwrap(), hash);
+
+        fs::write(dir.path().join("src/A.sol"), "contract B {}").unwrap();
+        assert_ne!(integrity(dir.path()).unwrap(), hash);
+    }
+
+    #[test]
+    fn computes_integrity_of_tracked_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/A.sol"), "contract A {}").unwrap();
+        let untracked = integrity(dir.path()).unwrap();
+
+        let git = Git::new(dir.path());
+        git.init().unwrap();
+        git.add(Some("src/A.sol")).unwrap();
+        assert_eq!(integrity(dir.path()).unwrap(), untracked);
+
+        // untracked files are ignored
+        fs::write(dir.path().join("src/B.sol"), "contract B {}").unwrap();
+        fs::write(dir.path().join(".env"), "KEY=").unwrap();
+        assert_eq!(integrity(dir.path()).unwrap(), untracked);
+
+        fs::remove_file(dir.path().join("src/A.sol")).unwrap();
+        assert_ne!(integrity(dir.path()).unwrap(), untracked);
+    }
+
+    #[test]
+    fn verifies_lockfile() {
+        let root = tempfile::tempdir().unwrap();
+        let dep = root.path().join("lib/dep");
+        fs::create_dir_all(&dep).unwrap();
+        fs::write(dep.join("A.sol"), "contract A {}").unwrap();
+
+        let mut lockfile = Lockfile::default();
+        lockfile.lock_npm(root.path(), &dep, "dep", "1.0.0").unwrap();
+        lockfile.write(root.path()).unwrap();
+
+        let lockfile = Lockfile::read(root.path()).unwrap().unwrap();
+        assert_eq!(lockfile.dependencies["lib/dep"].version.as_deref(), Some("1.0.0"));
+        lockfile.verify(root.path()).unwrap();
+
+        fs::write(dep.join("A.sol"), "contract B {}").unwrap();
+        assert!(lockfile.verify(root.path()).is_err());
+
+        assert!(lockfile.verify_installed(root.path()).is_err());
+
+        fs::remove_dir_all(&dep).unwrap();
+        assert_eq!(lockfile.missing(root.path()).count(), 1);
+        assert!(lockfile.verify(root.path()).is_err());
+        lockfile.verify_installed(root.path()).unwrap();
+    }
+
+    #[test]
+    fn locks_updated_tag() {
+        let root = tempfile::tempdir().unwrap();
+        let dep = root.path().join("lib/dep");
+        fs::create_dir_all(&dep).unwrap();
+        fs::write(dep.join("A.sol"), "contract A {}").unwrap();
+
+        let mut lockfile = Lockfile::default();
+        let url = "https://github.com/foundry-rs/dep";
+        lockfile.lock_git(root.path(), &dep, url, "a".repeat(40), Some("v1.0.0".into())).unwrap();
+        lockfile.lock_tag(root.path(), &dep, "v1.2.0").unwrap();
+        assert_eq!(lockfile.dependencies["lib/dep"].tag.as_deref(), Some("v1.2.0"));
+
+        // dependencies which are not locked are ignored
+        lockfile.lock_tag(root.path(), &root.path().join("lib/other"), "v2.0.0").unwrap();
+        assert_eq!(lockfile.dependencies.len(), 1);
+    }
+}
@@ -9,13 +9,22 @@
 use regex::Regex;
 use semver::Version;
 use std::{
+    collections::BTreeMap,
     io::IsTerminal,
     path::{Path, PathBuf},
-    str,
+    str::{self, FromStr},
     sync::LazyLock,
 };
 use yansi::Paint;
 
+pub mod graph;
+
+pub mod lockfile;
+use lockfile::{Lockfile, LOCKFILE_NAME};
+
+pub mod npm;
+use npm::{NpmDependency, DEFAULT_NPM_REGISTRY};
+
 static DEPENDENCY_VERSION_TAG_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^v?\d+(\.\d+)*$").unwrap());
 
@@ -25,7 +34,8 @@
     forge install [OPTIONS] <github username>/<github project>@<tag>...
     forge install [OPTIONS] <alias>=<github username>/<github project>@<tag>...
     forge install [OPTIONS] <https://<github token>@git url>...)]
-    forge install [OPTIONS] <https:// git url>...")]
+    forge install [OPTIONS] <https:// git url>...
+    forge install [OPTIONS] [<alias>=]npm:<package>@<version>...")]
 pub struct InstallArgs {
     /// The dependencies to install.
     ///
@@ -42,7 +52,26 @@ pub struct InstallArgs {
     ///
     /// Target installation directory can be added via `<alias>=` suffix.
     /// The dependency will installed to `lib/<alias>`.
-    dependencies: Vec<Dependency>,
+    ///
+    /// Packages of the npm registry can be installed with the `npm:` prefix, e.g.
+    /// `npm:@openzeppelin/contracts@5.0.2`. The version can be an exact version, a dist-tag or a
+    /// semver range.
+    dependencies: Vec<InstallDependency>,
+
+    /// The npm registry used to resolve `npm:` dependencies.
+    #[arg(long, env = "NPM_CONFIG_REGISTRY", default_value = DEFAULT_NPM_REGISTRY, value_name = "URL")]
+    npm_registry: String,
+
+    /// Install npm packages into `node_modules` instead of the libraries directory.
+    #[arg(long)]
+    node_modules: bool,
+
+    /// Install the dependencies pinned in `foundry.lock`, failing if it is missing or does not
+    /// match the installed dependencies.
+    ///
+    /// Dependencies cannot be added in this mode.
+    #[arg(long, conflicts_with = "dependencies")]
+    frozen: bool,
 
     /// The project's root path.
     ///
@@ -60,7 +89,66 @@ pub struct InstallArgs {
 impl InstallArgs {
     pub fn run(self) -> Result<()> {
         let mut config = self.load_config()?;
-        self.opts.install(&mut config, self.dependencies)
+        if self.frozen {
+            let Some(lockfile) = Lockfile::read(&config.root)? else {
+                eyre::bail!(
+                    "{LOCKFILE_NAME} not found.\n\
+                     Run `forge install` without `--frozen` to create it."
+                );
+            };
+            // check the installed dependencies before writing anything
+            lockfile.verify_installed(&config.root)?;
+        }
+        let install_locked = self.dependencies.is_empty();
+
+        let mut git_deps = Vec::new();
+        let mut npm_deps = Vec::new();
+        for dep in self.dependencies {
+            match dep {
+                InstallDependency::Git(dep) => git_deps.push(dep),
+                InstallDependency::Npm(dep) => npm_deps.push(dep),
+            }
+        }
+
+        // without npm dependencies, this also installs the existing git dependencies
+        if !git_deps.is_empty() || npm_deps.is_empty() {
+            self.opts.install(&mut config, git_deps)?;
+        }
+        if !npm_deps.is_empty() {
+            npm::install(&config, &npm_deps, &self.npm_registry, self.node_modules)?;
+        }
+        if install_locked {
+            npm::install_locked(&config, &self.npm_registry)?;
+        } else {
+            let graph = graph::install_transitive(&config, &self.npm_registry, self.node_modules)?;
+            graph.print_report()?;
+        }
+
+        if self.frozen {
+            if let Some(lockfile) = Lockfile::read(&config.root)? {
+                lockfile.verify(&config.root)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A dependency to install, either from git or from the npm registry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum InstallDependency {
+    Git(Dependency),
+    Npm(NpmDependency),
+}
+
+impl FromStr for InstallDependency {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if NpmDependency::is_npm(s) {
+            s.parse().map(Self::Npm)
+        } else {
+            s.parse().map(Self::Git)
+        }
     }
 }
 
@@ -76,6 +164,11 @@ pub struct DependencyInstallOpts {
     #[arg(long)]
     pub no_git: bool,
 
+    /// Copy the dependency sources without adding them as a submodule and without any nested git
+    /// repositories, recording their origin in `foundry.lock`.
+    #[arg(long, conflicts_with = "no_git")]
+    pub vendor: bool,
+
     /// Create a commit after installing the dependencies.
     #[arg(long)]
     pub commit: bool,
@@ -108,14 +201,14 @@ pub fn install_missing_dependencies(self, config: &mut Config) -> bool {
 
     /// Installs all dependencies
     pub fn install(self, config: &mut Config, dependencies: Vec<Dependency>) -> Result<()> {
-        let Self { no_git, commit, .. } = self;
+        let Self { no_git, vendor, commit, .. } = self;
 
         let git = self.git(config);
 
         let install_lib_dir = config.install_lib_dir();
         let libs = git.root.join(install_lib_dir);
 
-        if dependencies.is_empty() && !self.no_git {
+        if dependencies.is_empty() && !no_git && !vendor {
             // Use the root of the git repository to look for submodules.
             let root = Git::root_of(git.root)?;
             match git.has_submodules(Some(&root)) {
@@ -138,6 +231,23 @@ pub fn install(self, config: &mut Config, dependencies: Vec<Dependency>) -> Resu
         fs::create_dir_all(&libs)?;
 
         let installer = Installer { git, commit };
+
+        let mut lockfile = Lockfile::read(&config.root)?;
+        // dependencies installed without git are only tracked by the lockfile
+        if dependencies.is_empty() {
+            if let Some(lockfile) = &lockfile {
+                for (path, locked) in lockfile.missing(&config.root) {
+                    if !(no_git || vendor || locked.vendored) {
+                        continue;
+                    }
+                    let (Some(url), Some(rev)) = (&locked.git, &locked.rev) else { continue };
+                    sh_println!(
+                        "Installing {path} from {LOCKFILE_NAME} (url: {url:?}, rev: {rev})"
+                    )?;
+                    installer.install_locked(url, rev, &config.root.join(path), locked.vendored)?;
+                }
+            }
+        }
         for dep in dependencies {
             let path = libs.join(dep.name());
             let rel_path = path
@@ -153,8 +263,20 @@ pub fn install(self, config: &mut Config, dependencies: Vec<Dependency>) -> Resu
 
             // this tracks the actual installed tag
             let installed_tag;
-            if no_git {
-                installed_tag = installer.install_as_folder(&dep, &path)?;
+            if no_git || vendor {
+                let folder = installer.install_as_folder(&dep, &path, vendor)?;
+                installed_tag = folder.tag;
+                lock_dependency(&mut lockfile, config, &dep, &installed_tag, &path, folder.rev)?;
+                if vendor {
+                    let lockfile = lockfile.get_or_insert_with(Default::default);
+                    lockfile.vendor(&config.root, &path, folder.submodules)?;
+                    lockfile.write(&config.root)?;
+
+                    if commit {
+                        git.add([path.as_path(), Lockfile::path(&config.root).as_path()])?;
+                        git.commit(&format!("forge install: {} (vendored)", dep.name()))?;
+                    }
+                }
             } else {
                 if commit {
                     git.ensure_clean()?;
@@ -180,8 +302,12 @@ pub fn install(self, config: &mut Config, dependencies: Vec<Dependency>) -> Resu
                     }
                 }
 
+                let rev = git.root(&path).commit_hash(false, "HEAD")?;
+                lock_dependency(&mut lockfile, config, &dep, &installed_tag, &path, rev)?;
+
                 // commit the installation
                 if commit {
+                    git.add(Some(Lockfile::path(&config.root)))?;
                     let mut msg = String::with_capacity(128);
                     msg.push_str("forge install: ");
                     msg.push_str(dep.name());
@@ -210,10 +336,76 @@ pub fn install(self, config: &mut Config, dependencies: Vec<Dependency>) -> Resu
     }
 }
 
+/// Pins the installed git dependency in the lockfile, creating it if necessary.
+fn lock_dependency(
+    lockfile: &mut Option<Lockfile>,
+    config: &Config,
+    dep: &Dependency,
+    installed_tag: &Option<String>,
+    path: &Path,
+    rev: String,
+) -> Result<()> {
+    let lockfile = lockfile.get_or_insert_with(Default::default);
+    let tag = installed_tag.clone().filter(|t| !t.is_empty()).or_else(|| dep.tag.clone());
+    lockfile.lock_git(&config.root, path, dep.require_url()?, rev, tag)?;
+    lockfile.write(&config.root)
+}
+
 pub fn install_missing_dependencies(config: &mut Config) -> bool {
     DependencyInstallOpts::default().install_missing_dependencies(config)
 }
 
+/// Parses a release tag as a semantic version, ignoring pre-releases and build metadata.
+pub(crate) fn semver_tag(tag: &str) -> Option<Version> {
+    // tags are commonly prefixed which would make them not semver: v1.2.3 is not a semantic
+    // version
+    let common_prefixes = &["v-", "v", "release-", "release"];
+    let mut maybe_semver = tag;
+    for &prefix in common_prefixes {
+        if let Some(rem) = tag.strip_prefix(prefix) {
+            maybe_semver = rem;
+            break
+        }
+    }
+    match Version::parse(maybe_semver) {
+        // ignore if additional metadata, like rc, beta, etc...
+        Ok(v) => (v.build.is_empty() && v.pre.is_empty()).then_some(v),
+        Err(err) => {
+            warn!(?err, ?maybe_semver, "No semver tag");
+            None
+        }
+    }
+}
+
+/// A dependency installed as an ordinary folder.
+struct Folder {
+    /// The installed tag.
+    tag: Option<String>,
+    /// The installed commit hash.
+    rev: String,
+    /// The commit hashes of the submodules of vendored dependencies.
+    submodules: BTreeMap<String, String>,
+}
+
+/// Removes all `.git` directories and files in `dir`, including the ones of nested submodules.
+fn remove_git_metadata(dir: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let is_dir = entry.file_type()?.is_dir();
+        if entry.file_name() == ".git" {
+            if is_dir {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+        } else if is_dir {
+            remove_git_metadata(&path)?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Installer<'a> {
     git: Git<'a>,
@@ -221,8 +413,10 @@ struct Installer<'a> {
 }
 
 impl Installer<'_> {
-    /// Installs the dependency as an ordinary folder instead of a submodule
-    fn install_as_folder(self, dep: &Dependency, path: &Path) -> Result<Option<String>> {
+    /// Installs the dependency as an ordinary folder instead of a submodule.
+    ///
+    /// If `vendor` is true, the git metadata of nested submodules is removed as well.
+    fn install_as_folder(self, dep: &Dependency, path: &Path, vendor: bool) -> Result<Folder> {
         let url = dep.require_url()?;
         Git::clone(dep.tag.is_none(), url, Some(&path))?;
         let mut dep = dep.clone();
@@ -244,10 +438,55 @@ fn install_as_folder(self, dep: &Dependency, path: &Path) -> Result<Option<Strin
             std::iter::empty::<PathBuf>(),
         )?;
 
+        let rev = self.git.root(path).commit_hash(false, "HEAD")?;
+        let submodules = if vendor { self.submodule_revs(path)? } else { BTreeMap::new() };
+
         // remove git artifacts
-        fs::remove_dir_all(path.join(".git"))?;
+        if vendor {
+            remove_git_metadata(path)?;
+        } else {
+            fs::remove_dir_all(path.join(".git"))?;
+        }
 
-        Ok(dep.tag)
+        Ok(Folder { tag: dep.tag, rev, submodules })
+    }
+
+    /// Returns the checked out commits of all submodules of the repository at `path`, by path.
+    fn submodule_revs(self, path: &Path) -> Result<BTreeMap<String, String>> {
+        let output = self
+            .git
+            .root(path)
+            .cmd()
+            .args(["submodule", "status", "--recursive"])
+            .get_stdout_lossy()?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                // ` <commit> <path> (<describe>)`, prefixed by the status of the submodule
+                let mut parts = line.get(1..)?.split_whitespace();
+                let rev = parts.next()?;
+                Some((parts.next()?.to_string(), rev.to_string()))
+            })
+            .collect())
+    }
+
+    /// Installs a dependency pinned in the lockfile as an ordinary folder.
+    fn install_locked(self, url: &str, rev: &str, path: &Path, vendor: bool) -> Result<()> {
+        Git::clone(false, url, Some(path))?;
+        self.git.root(path).checkout(true, rev)?;
+        self.git.root(path).submodule_update(
+            false,
+            false,
+            false,
+            true,
+            std::iter::empty::<PathBuf>(),
+        )?;
+        if vendor {
+            remove_git_metadata(path)?;
+        } else {
+            fs::remove_dir_all(path.join(".git"))?;
+        }
+        Ok(())
     }
 
     /// Installs the dependency as new submodule.
@@ -294,30 +533,10 @@ fn last_tag(self, path: &Path) -> Option<String> {
     /// Returns all semver git tags sorted in ascending order
     fn git_semver_tags(self, path: &Path) -> Result<Vec<(String, Version)>> {
         let out = self.git.root(path).tag()?;
-        let mut tags = Vec::new();
-        // tags are commonly prefixed which would make them not semver: v1.2.3 is not a semantic
-        // version
-        let common_prefixes = &["v-", "v", "release-", "release"];
-        for tag in out.lines() {
-            let mut maybe_semver = tag;
-            for &prefix in common_prefixes {
-                if let Some(rem) = tag.strip_prefix(prefix) {
-                    maybe_semver = rem;
-                    break
-                }
-            }
-            match Version::parse(maybe_semver) {
-                Ok(v) => {
-                    // ignore if additional metadata, like rc, beta, etc...
-                    if v.build.is_empty() && v.pre.is_empty() {
-                        tags.push((tag.to_string(), v));
-                    }
-                }
-                Err(err) => {
-                    warn!(?err, ?maybe_semver, "No semver tag");
-                }
-            }
-        }
+        let mut tags = out
+            .lines()
+            .filter_map(|tag| semver_tag(tag).map(|v| (tag.to_string(), v)))
+            .collect::<Vec<_>>();
 
         tags.sort_by(|(_, a), (_, b)| a.cmp(b));
 
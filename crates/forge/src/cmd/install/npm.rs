@@ -0,0 +1,445 @@
+//! Solidity packages from the npm registry: `forge install npm:@openzeppelin/contracts@5.0.2`.
+//!
+//! Packages are downloaded as tarballs, checked against the integrity hash published in the
+//! registry and extracted into the libraries directory, or into `node_modules` with
+//! `--node-modules`. A remapping from the package name to the installation directory is added to
+//! `remappings.txt`.
+
+use super::lockfile::Lockfile;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use eyre::{Context, OptionExt, Result};
+use flate2::read::GzDecoder;
+use foundry_common::fs;
+use foundry_config::Config;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use sha2::{Digest, Sha512};
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
+use tar::EntryType;
+use yansi::Paint;
+
+/// The prefix of npm dependencies.
+pub const NPM_PREFIX: &str = "npm:";
+
+/// The default npm registry.
+pub const DEFAULT_NPM_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// A package of the npm registry: `[<alias>=]npm:<package>[@<version>]`.
+///
+/// The version can be an exact version, a dist-tag such as `next` or a semver range. If omitted,
+/// the `latest` dist-tag is installed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NpmDependency {
+    /// The name of the package, e.g. `@openzeppelin/contracts`.
+    pub package: String,
+    /// The requested version, dist-tag or range.
+    pub version: Option<String>,
+    /// Optional alias, used as the name of the installation directory.
+    pub alias: Option<String>,
+}
+
+impl NpmDependency {
+    /// Returns `true` if `s` is an npm dependency.
+    pub fn is_npm(s: &str) -> bool {
+        s.starts_with(NPM_PREFIX) ||
+            s.split_once('=').is_some_and(|(_, dep)| dep.starts_with(NPM_PREFIX))
+    }
+
+    /// Returns the name of the installation directory in the libraries directory.
+    ///
+    /// Scoped packages are flattened: `@openzeppelin/contracts` -> `openzeppelin-contracts`.
+    pub fn dir_name(&self) -> String {
+        self.alias.clone().unwrap_or_else(|| self.package.trim_start_matches('@').replace('/', "-"))
+    }
+
+    /// Resolves the requested version against the `registry`.
+    pub async fn resolve(&self, registry: &str) -> Result<NpmRelease> {
+        let url =
+            format!("{}/{}", registry.trim_end_matches('/'), self.package.replace('/', "%2f"));
+        let packument: Packument = reqwest::get(&url)
+            .await
+            .and_then(|r| r.error_for_status())
+            .wrap_err_with(|| format!("failed to fetch package {} from {url}", self.package))?
+            .json()
+            .await
+            .wrap_err_with(|| format!("invalid registry response for package {}", self.package))?;
+
+        let version = packument.select(self.version.as_deref()).ok_or_else(|| {
+            eyre::eyre!(
+                "no version of {} matches {:?}",
+                self.package,
+                self.version.as_deref().unwrap_or("latest")
+            )
+        })?;
+        let dist = packument.versions[&version].dist.clone();
+        Ok(NpmRelease { version, tarball: dist.tarball, integrity: dist.integrity })
+    }
+}
+
+impl FromStr for NpmDependency {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (alias, dep) = match s.split_once('=') {
+            Some((alias, dep)) if dep.starts_with(NPM_PREFIX) => (Some(alias.to_string()), dep),
+            _ => (None, s),
+        };
+        let dep =
+            dep.strip_prefix(NPM_PREFIX).ok_or_eyre("npm dependencies must start with `npm:`")?;
+
+        // the leading `@` of scoped packages is not a version separator
+        let (package, version) = match dep.rfind('@') {
+            Some(i) if i > 0 => (&dep[..i], Some(dep[i + 1..].to_string())),
+            _ => (dep, None),
+        };
+        let is_valid = if let Some(scoped) = package.strip_prefix('@') {
+            scoped
+                .split_once('/')
+                .is_some_and(|(scope, name)| !scope.is_empty() && !name.is_empty())
+        } else {
+            !package.is_empty() && !package.contains('/')
+        };
+        if !is_valid {
+            eyre::bail!("invalid npm package name: {package:?}");
+        }
+
+        Ok(Self { package: package.to_string(), version: version.filter(|v| !v.is_empty()), alias })
+    }
+}
+
+/// A resolved version of an npm package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NpmRelease {
+    /// The exact version.
+    pub version: String,
+    /// The URL of the package tarball.
+    pub tarball: String,
+    /// The subresource integrity of the tarball, e.g. `sha512-...`.
+    pub integrity: Option<String>,
+}
+
+impl NpmRelease {
+    /// Downloads the tarball and checks it against the published integrity.
+    pub async fn download(&self) -> Result<Vec<u8>> {
+        let bytes = reqwest::get(&self.tarball)
+            .await
+            .and_then(|r| r.error_for_status())
+            .wrap_err_with(|| format!("failed to download {}", self.tarball))?
+            .bytes()
+            .await?;
+        match &self.integrity {
+            Some(integrity) => verify_integrity(&bytes, integrity)?,
+            None => sh_warn!("No integrity hash published for {}", self.tarball)?,
+        }
+        Ok(bytes.to_vec())
+    }
+}
+
+/// The registry document of a package.
+#[derive(Clone, Debug, Deserialize)]
+struct Packument {
+    #[serde(rename = "dist-tags", default)]
+    dist_tags: BTreeMap<String, String>,
+    #[serde(default)]
+    versions: BTreeMap<String, PackageVersion>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct PackageVersion {
+    dist: Dist,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Dist {
+    tarball: String,
+    integrity: Option<String>,
+}
+
+impl Packument {
+    /// Selects the version matching `requested`, see [`NpmDependency`].
+    fn select(&self, requested: Option<&str>) -> Option<String> {
+        let requested = requested.unwrap_or("latest");
+        if self.versions.contains_key(requested) {
+            return Some(requested.to_string());
+        }
+        if let Some(version) = self.dist_tags.get(requested) {
+            return self.versions.contains_key(version).then(|| version.clone());
+        }
+        let req = VersionReq::parse(requested).ok()?;
+        self.versions
+            .keys()
+            .filter_map(|v| Some((Version::parse(v).ok()?, v)))
+            .filter(|(version, _)| req.matches(version))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, v)| v.clone())
+    }
+}
+
+/// Installs `deps` into the project of `config` and pins them in the lockfile.
+///
+/// Packages are installed into `node_modules/<package>` if `node_modules` is true, otherwise into
+/// `<lib>/<dir name>`.
+pub fn install(
+    config: &Config,
+    deps: &[NpmDependency],
+    registry: &str,
+    node_modules: bool,
+) -> Result<()> {
+    let mut lockfile = Lockfile::read(&config.root)?.unwrap_or_default();
+    for dep in deps {
+        let release = foundry_cli::utils::block_on(dep.resolve(registry))?;
+        let dest = if node_modules {
+            config.root.join("node_modules").join(&dep.package)
+        } else {
+            config.root.join(config.install_lib_dir()).join(dep.dir_name())
+        };
+        install_release(&dep.package, &release, &dest)?;
+
+        let relative = dest.strip_prefix(&config.root)?.to_string_lossy().replace('\\', "/");
+        add_remappings(&config.root, &[format!("{}/={relative}/", dep.package)])?;
+        lockfile.lock_npm(&config.root, &dest, &dep.package, &release.version)?;
+        lockfile.write(&config.root)?;
+    }
+    Ok(())
+}
+
+/// Installs the npm packages pinned in the lockfile which are not installed.
+pub fn install_locked(config: &Config, registry: &str) -> Result<()> {
+    let Some(lockfile) = Lockfile::read(&config.root)? else { return Ok(()) };
+    for (path, locked) in lockfile.missing(&config.root) {
+        let (Some(package), Some(version)) = (&locked.npm, &locked.version) else { continue };
+        let dep =
+            NpmDependency { package: package.clone(), version: Some(version.clone()), alias: None };
+        let release = foundry_cli::utils::block_on(dep.resolve(registry))?;
+        install_release(package, &release, &config.root.join(path))?;
+    }
+    Ok(())
+}
+
+/// Downloads and extracts `release` of `package` into `dest`.
+fn install_release(package: &str, release: &NpmRelease, dest: &Path) -> Result<()> {
+    sh_println!(
+        "Installing {package}@{} in {} (tarball: {:?})",
+        release.version,
+        dest.display(),
+        release.tarball
+    )?;
+    if dest.exists() {
+        eyre::bail!("{} already exists, remove it first to reinstall", dest.display());
+    }
+
+    let tarball = foundry_cli::utils::block_on(release.download())?;
+    extract(&tarball, dest)?;
+    sh_println!("    {} {package} {}", "Installed".green(), release.version)?;
+    Ok(())
+}
+
+/// Checks `bytes` against a subresource integrity string, using its strongest supported hash.
+fn verify_integrity(bytes: &[u8], integrity: &str) -> Result<()> {
+    let Some(expected) = integrity.split_whitespace().find_map(|h| h.strip_prefix("sha512-"))
+    else {
+        sh_warn!("Unsupported integrity hash {integrity:?}, skipping verification")?;
+        return Ok(());
+    };
+    let actual = STANDARD.encode(Sha512::digest(bytes));
+    if actual != expected {
+        eyre::bail!("integrity check failed: expected sha512-{expected}, got sha512-{actual}");
+    }
+    Ok(())
+}
+
+/// Extracts a gzipped package tarball into `dest`.
+///
+/// npm tarballs contain a single top-level directory, usually `package`, whose contents are moved
+/// to `dest`. Only regular files and directories are extracted, and entries whose path would
+/// escape `dest` are rejected.
+fn extract(tarball: &[u8], dest: &Path) -> Result<()> {
+    let parent = dest.parent().ok_or_eyre("invalid installation directory")?;
+    let name = dest.file_name().ok_or_eyre("invalid installation directory")?;
+    let staging = parent.join(format!(".{}.npm", name.to_string_lossy()));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+
+    let result = (|| -> Result<()> {
+        unpack(tarball, &staging)?;
+
+        let mut entries = std::fs::read_dir(&staging)?
+            .map(|e| e.map(|e| e.path()))
+            .collect::<Result<Vec<PathBuf>, _>>()?;
+        let root = match entries.pop() {
+            Some(dir) if entries.is_empty() && dir.is_dir() => dir,
+            _ => eyre::bail!("unexpected package tarball layout"),
+        };
+        std::fs::rename(&root, dest)
+            .wrap_err_with(|| format!("failed to move package to {}", dest.display()))?;
+        Ok(())
+    })();
+
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+/// Unpacks the regular files and directories of a gzipped tarball into `dir`.
+fn unpack(tarball: &[u8], dir: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(GzDecoder::new(tarball));
+    for entry in archive.entries().wrap_err("failed to read package tarball")? {
+        let mut entry = entry.wrap_err("failed to read package tarball")?;
+        let path = entry.path()?.into_owned();
+        if !path.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+            eyre::bail!(
+                "package tarball entry {} escapes the installation directory",
+                path.display()
+            );
+        }
+
+        let target = dir.join(&path);
+        match entry.header().entry_type() {
+            EntryType::Directory => fs::create_dir_all(&target)?,
+            EntryType::Regular | EntryType::Continuous => {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                entry
+                    .unpack(&target)
+                    .wrap_err_with(|| format!("failed to extract {}", path.display()))?;
+            }
+            // links could point outside of the installation directory
+            _ => continue,
+        }
+    }
+    Ok(())
+}
+
+/// Appends the missing `remappings` to the `remappings.txt` file in `root`.
+fn add_remappings(root: &Path, remappings: &[String]) -> Result<()> {
+    let path = root.join("remappings.txt");
+    let mut content = if path.exists() { fs::read_to_string(&path)? } else { String::new() };
+    let mut changed = false;
+    for remapping in remappings {
+        let prefix = remapping.split('=').next().unwrap_or_default();
+        if content.lines().any(|l| l.trim().split('=').next() == Some(prefix)) {
+            sh_warn!("Remapping for {prefix} already exists, skipping {remapping}")?;
+            continue;
+        }
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(remapping);
+        content.push('\n');
+        changed = true;
+    }
+    if changed {
+        fs::write(&path, content)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_npm_dependencies() {
+        let dep: NpmDependency = "npm:@openzeppelin/contracts@5.0.2".parse().unwrap();
+        assert_eq!(dep.package, "@openzeppelin/contracts");
+        assert_eq!(dep.version.as_deref(), Some("5.0.2"));
+        assert_eq!(dep.dir_name(), "openzeppelin-contracts");
+
+        let dep: NpmDependency = "oz=npm:@openzeppelin/contracts".parse().unwrap();
+        assert_eq!(dep.version, None);
+        assert_eq!(dep.dir_name(), "oz");
+
+        let dep: NpmDependency = "npm:solady@^0.1".parse().unwrap();
+        assert_eq!(dep.package, "solady");
+        assert_eq!(dep.version.as_deref(), Some("^0.1"));
+
+        assert!(NpmDependency::is_npm("npm:solady"));
+        assert!(NpmDependency::is_npm("oz=npm:@openzeppelin/contracts"));
+        assert!(!NpmDependency::is_npm("OpenZeppelin/openzeppelin-contracts"));
+        assert!("npm:@openzeppelin".parse::<NpmDependency>().is_err());
+        assert!("npm:a/b".parse::<NpmDependency>().is_err());
+    }
+
+    #[test]
+    fn selects_versions() {
+        let packument: Packument = serde_json::from_str(
+            r#"{
+                "dist-tags": { "latest": "5.0.2", "next": "5.1.0-rc.0" },
+                "versions": {
+                    "4.9.6": { "dist": { "tarball": "a" } },
+                    "5.0.1": { "dist": { "tarball": "b" } },
+                    "5.0.2": { "dist": { "tarball": "c" } },
+                    "5.1.0-rc.0": { "dist": { "tarball": "d" } }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(packument.select(None).as_deref(), Some("5.0.2"));
+        assert_eq!(packument.select(Some("next")).as_deref(), Some("5.1.0-rc.0"));
+        assert_eq!(packument.select(Some("5.0.1")).as_deref(), Some("5.0.1"));
+        assert_eq!(packument.select(Some("^4")).as_deref(), Some("4.9.6"));
+        assert_eq!(packument.select(Some("^5.0.0")).as_deref(), Some("5.0.2"));
+        assert_eq!(packument.select(Some("^6")), None);
+    }
+
+    #[test]
+    fn verifies_integrity() {
+        let integrity = format!("sha512-{}", STANDARD.encode(Sha512::digest(b"package")));
+        assert!(verify_integrity(b"package", &integrity).is_ok());
+        assert!(verify_integrity(b"tampered", &integrity).is_err());
+    }
+
+    /// Returns a gzipped tarball of `entries`, written with raw header names so that malicious
+    /// paths can be tested.
+    fn tarball(entries: &[(&str, EntryType, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for &(path, kind, data) in entries {
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_entry_type(kind);
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, data).unwrap();
+        }
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+        std::io::Write::write_all(&mut encoder, &builder.into_inner().unwrap()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn extracts_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("solady");
+        let tarball = tarball(&[
+            ("package/package.json", EntryType::Regular, b"{}"),
+            ("package/src/Lib.sol", EntryType::Regular, b"library Lib {}"),
+            ("package/link", EntryType::Symlink, b""),
+        ]);
+        extract(&tarball, &dest).unwrap();
+        assert_eq!(std::fs::read_to_string(dest.join("src/Lib.sol")).unwrap(), "library Lib {}");
+        assert!(dest.join("package.json").exists());
+        assert!(!dest.join("link").exists());
+        assert!(!dir.path().join(".solady.npm").exists());
+    }
+
+    #[test]
+    fn rejects_escaping_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        for path in ["package/../../evil.sol", "/tmp/evil.sol"] {
+            let dest = dir.path().join("lib/pkg");
+            std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+            let tarball = tarball(&[(path, EntryType::Regular, b"evil")]);
+            let err = extract(&tarball, &dest).unwrap_err();
+            assert!(err.to_string().contains("escapes the installation directory"), "{err}");
+            assert!(!dest.exists());
+        }
+        assert!(!dir.path().join("evil.sol").exists());
+    }
+}
@@ -0,0 +1,676 @@
+//! `forge migrate`: converts Hardhat and Truffle projects to the Foundry layout.
+
+use clap::{Parser, ValueHint};
+use eyre::Result;
+use foundry_common::fs;
+use foundry_config::Config;
+use regex::{Captures, Regex};
+use std::{
+    borrow::Cow,
+    fmt,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+use toml_edit::{value, Array, DocumentMut};
+use yansi::Paint;
+
+static IMPORT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(import\s+(?:[^"';]*?\s+from\s+)?["'])([^"']+)(["'])"#).unwrap()
+});
+
+/// CLI arguments for `forge migrate`.
+#[derive(Clone, Debug, Parser)]
+pub struct MigrateArgs {
+    /// The root directory of the project to migrate.
+    #[arg(value_hint = ValueHint::DirPath, default_value = ".", value_name = "PATH")]
+    pub root: PathBuf,
+
+    /// Only print the changes that would be made.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Overwrite an existing `foundry.toml`.
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl MigrateArgs {
+    pub fn run(self) -> Result<()> {
+        let Self { root, dry_run, force } = self;
+        let root = dunce::canonicalize(root)?;
+
+        let Some((framework, config_path)) = Framework::detect(&root) else {
+            eyre::bail!(
+                "No Hardhat or Truffle project found in {}.\n\
+                 Expected one of `hardhat.config.{{js,ts,cjs,mjs}}`, `truffle-config.js` or `truffle.js`.",
+                root.display()
+            );
+        };
+        let foundry_toml = root.join(Config::FILE_NAME);
+        if foundry_toml.exists() && !force {
+            eyre::bail!(
+                "{} already exists.\nRun with the `--force` flag to overwrite it.",
+                foundry_toml.display()
+            );
+        }
+
+        let legacy = LegacyConfig::parse(framework, &fs::read_to_string(&config_path)?);
+        sh_println!("Migrating {framework} project at {}...", root.display())?;
+
+        // move the contracts into `src`
+        let contracts = root.join(&legacy.sources);
+        let src = root.join("src");
+        let move_sources = contracts.is_dir() && contracts != src;
+        if move_sources {
+            if src.exists() {
+                eyre::bail!(
+                    "Cannot move {} to {}: the directory already exists",
+                    contracts.display(),
+                    src.display()
+                );
+            }
+            sh_println!("- move {} to src", legacy.sources)?;
+            if !dry_run {
+                std::fs::rename(&contracts, &src)?;
+            }
+        }
+
+        // move the Solidity tests into `test`, JavaScript tests are left in place
+        let tests = root.join(&legacy.tests);
+        let test = root.join("test");
+        let moved_tests = if tests.is_dir() && tests != test {
+            fs::files_with_ext(&tests, "sol")
+                .map(|file| {
+                    let target = test.join(file.strip_prefix(&tests)?);
+                    Ok((file, target))
+                })
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            Vec::new()
+        };
+        if let Some((_, target)) = moved_tests.iter().find(|(_, target)| target.exists()) {
+            eyre::bail!("Cannot move tests to {}: the file already exists", target.display());
+        }
+        let mut moves = vec![(tests.as_path(), test.as_path())];
+        if move_sources {
+            moves.push((contracts.as_path(), src.as_path()));
+        }
+        for (file, target) in &moved_tests {
+            sh_println!(
+                "- move {} to {}",
+                file.strip_prefix(&root)?.display(),
+                target.strip_prefix(&root)?.display()
+            )?;
+            if !dry_run {
+                let content = fs::read_to_string(file)?;
+                fs::create_dir_all(target.parent().unwrap())?;
+                fs::write(target, relocate_imports(&content, file, target, &moves))?;
+                fs::remove_file(file)?;
+            }
+        }
+
+        // rewrite imports of Solidity sources and tests, in a dry run they have not been moved yet
+        let sources_dir = if move_sources && dry_run { &contracts } else { &src };
+        let mut files =
+            fs::files_with_ext(sources_dir, "sol").map(|file| (file, false)).collect::<Vec<_>>();
+        files.extend(fs::files_with_ext(&test, "sol").map(|file| (file, true)));
+        if dry_run {
+            files.extend(
+                moved_tests
+                    .iter()
+                    .filter(|(file, _)| !file.starts_with(&test))
+                    .map(|(file, _)| (file.clone(), true)),
+            );
+        }
+        for (file, outside_sources) in files {
+            let content = fs::read_to_string(&file)?;
+            let rewritten = rewrite_imports(&content, &legacy.sources, outside_sources);
+            if rewritten != content {
+                sh_println!("- rewrite imports of {}", file.strip_prefix(&root)?.display())?;
+                if !dry_run {
+                    fs::write(&file, rewritten)?;
+                }
+            }
+            if content.contains("truffle/Assert.sol") {
+                sh_warn!(
+                    "{} uses Truffle assertions, which must be ported to forge-std manually",
+                    file.strip_prefix(&root)?.display()
+                )?;
+            }
+        }
+
+        // remappings for Solidity packages installed with npm
+        let remappings = npm_remappings(&root)?;
+        if !remappings.is_empty() {
+            let path = root.join("remappings.txt");
+            let existing = if path.exists() { fs::read_to_string(&path)? } else { String::new() };
+            let missing = remappings
+                .into_iter()
+                .filter(|r| !existing.lines().any(|l| l.trim() == r))
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                sh_println!("- add remappings: {}", missing.join(", "))?;
+                if !dry_run {
+                    let mut content = existing;
+                    if !content.is_empty() && !content.ends_with('\n') {
+                        content.push('\n');
+                    }
+                    for remapping in missing {
+                        content.push_str(&remapping);
+                        content.push('\n');
+                    }
+                    fs::write(&path, content)?;
+                }
+            }
+        }
+
+        // write foundry.toml
+        let doc = legacy.to_foundry_toml(&root)?;
+        sh_println!("- write {}", Config::FILE_NAME)?;
+        if dry_run {
+            sh_println!("\n{doc}")?;
+            return Ok(());
+        }
+        fs::write(&foundry_toml, doc.to_string())?;
+
+        let gitignore = root.join(".gitignore");
+        if gitignore.exists() {
+            let mut content = fs::read_to_string(&gitignore)?;
+            let missing = ["out/", "cache/"]
+                .into_iter()
+                .filter(|entry| !content.lines().any(|l| l.trim() == *entry))
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                if !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push_str("\n# Foundry\n");
+                for entry in missing {
+                    content.push_str(entry);
+                    content.push('\n');
+                }
+                fs::write(&gitignore, content)?;
+            }
+        }
+
+        sh_println!("{}", "    Migrated project to Foundry".green())?;
+        sh_println!(
+            "Run `forge install foundry-rs/forge-std` to install the Foundry test library."
+        )?;
+        Ok(())
+    }
+}
+
+/// The framework of a project to migrate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Framework {
+    Hardhat,
+    Truffle,
+}
+
+impl Framework {
+    /// Detects the framework of the project at `root`, returning it with its config file.
+    pub fn detect(root: &Path) -> Option<(Self, PathBuf)> {
+        let hardhat =
+            ["hardhat.config.js", "hardhat.config.ts", "hardhat.config.cjs", "hardhat.config.mjs"]
+                .into_iter()
+                .map(|name| (Self::Hardhat, name));
+        let truffle =
+            ["truffle-config.js", "truffle.js"].into_iter().map(|name| (Self::Truffle, name));
+        hardhat
+            .chain(truffle)
+            .map(|(framework, name)| (framework, root.join(name)))
+            .find(|(_, path)| path.is_file())
+    }
+}
+
+impl fmt::Display for Framework {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Hardhat => "Hardhat",
+            Self::Truffle => "Truffle",
+        })
+    }
+}
+
+/// The settings extracted from a Hardhat or Truffle config.
+///
+/// The configs are JavaScript, so they are not evaluated but scanned for literal values. Settings
+/// computed at runtime are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LegacyConfig {
+    /// The directory of the contracts, relative to the project root.
+    pub sources: String,
+    /// The directory of the tests, relative to the project root.
+    pub tests: String,
+    /// The solc version.
+    pub solc: Option<String>,
+    /// Whether the optimizer is enabled.
+    pub optimizer: Option<bool>,
+    /// The number of optimizer runs.
+    pub optimizer_runs: Option<u64>,
+    /// The EVM version.
+    pub evm_version: Option<String>,
+    /// Whether to compile via the IR pipeline.
+    pub via_ir: Option<bool>,
+    /// The RPC endpoints of the configured networks, by name.
+    pub networks: Vec<(String, String)>,
+}
+
+impl LegacyConfig {
+    /// Extracts the settings from the config file of `framework`.
+    pub fn parse(framework: Framework, config: &str) -> Self {
+        let (compiler, sources, tests) = match framework {
+            Framework::Hardhat => {
+                let paths = object_body(config, "paths");
+                (
+                    object_body(config, "solidity"),
+                    paths.and_then(|paths| string_value(paths, "sources")),
+                    paths.and_then(|paths| string_value(paths, "tests")),
+                )
+            }
+            Framework::Truffle => (
+                object_body(config, "solc"),
+                string_value(config, "contracts_directory"),
+                string_value(config, "test_directory"),
+            ),
+        };
+        // the compiler settings are only read from their own object, e.g. `solidity: "0.8.19"`
+        // has none
+        let setting = |key: &str| compiler.and_then(|compiler| raw_value(compiler, key));
+        let solc = match framework {
+            Framework::Hardhat => string_value(config, "solidity"),
+            Framework::Truffle => None,
+        }
+        .or_else(|| compiler.and_then(|compiler| string_value(compiler, "version")));
+
+        let networks = object_body(config, "networks")
+            .map(|networks| {
+                entries(networks)
+                    .into_iter()
+                    .filter_map(|(name, body)| Some((name.to_string(), network_url(body)?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let dir = |dir: Option<String>, default: &str| {
+            dir.map(|s| s.trim_start_matches("./").trim_end_matches('/').to_string())
+                .unwrap_or_else(|| default.to_string())
+        };
+        Self {
+            sources: dir(sources, "contracts"),
+            tests: dir(tests, "test"),
+            solc,
+            optimizer: setting("enabled").and_then(|v| v.parse().ok()),
+            optimizer_runs: setting("runs").and_then(|v| v.parse().ok()),
+            evm_version: compiler.and_then(|compiler| string_value(compiler, "evmVersion")),
+            via_ir: setting("viaIR").and_then(|v| v.parse().ok()),
+            networks,
+        }
+    }
+
+    /// Returns the `foundry.toml` of the migrated project at `root`.
+    pub fn to_foundry_toml(&self, root: &Path) -> Result<DocumentMut> {
+        let config = Config { root: root.to_path_buf(), ..Default::default() };
+        let mut doc = config.into_basic().to_string_pretty()?.parse::<DocumentMut>()?;
+
+        let profile = &mut doc["profile"][Config::DEFAULT_PROFILE.as_str().as_str()];
+        let mut libs = Array::new();
+        libs.push("node_modules");
+        libs.push("lib");
+        profile["libs"] = value(libs);
+        if let Some(solc) = &self.solc {
+            profile["solc"] = value(solc.as_str());
+        }
+        if let Some(optimizer) = self.optimizer {
+            profile["optimizer"] = value(optimizer);
+        }
+        if let Some(runs) = self.optimizer_runs {
+            profile["optimizer_runs"] = value(runs as i64);
+        }
+        if let Some(evm_version) = &self.evm_version {
+            profile["evm_version"] = value(evm_version.as_str());
+        }
+        if let Some(via_ir) = self.via_ir {
+            profile["via_ir"] = value(via_ir);
+        }
+        for (name, url) in &self.networks {
+            doc["rpc_endpoints"][name.as_str()] = value(url.as_str());
+        }
+        Ok(doc)
+    }
+}
+
+/// Rewrites the imports of a Solidity source to the Foundry layout.
+///
+/// `hardhat/console.sol` is replaced by the forge-std console and, in files outside of the
+/// contracts directory, relative imports of `sources` are redirected to `src`.
+pub fn rewrite_imports(content: &str, sources: &str, outside_sources: bool) -> String {
+    IMPORT_REGEX
+        .replace_all(content, |caps: &Captures<'_>| {
+            let path = &caps[2];
+            let path = if path == "hardhat/console.sol" {
+                "forge-std/console.sol".to_string()
+            } else if outside_sources {
+                redirect_sources(path, sources).unwrap_or_else(|| path.to_string())
+            } else {
+                path.to_string()
+            };
+            format!("{}{path}{}", &caps[1], &caps[3])
+        })
+        .into_owned()
+}
+
+/// Redirects `path` to `src` if it points into the `sources` directory from a sibling directory.
+fn redirect_sources(path: &str, sources: &str) -> Option<String> {
+    let mut prefix = String::new();
+    let mut rest = path;
+    while let Some(stripped) = rest.strip_prefix("../") {
+        prefix.push_str("../");
+        rest = stripped;
+    }
+    let rest = rest.strip_prefix(sources)?.strip_prefix('/')?;
+    Some(format!("{prefix}src/{rest}"))
+}
+
+/// Rewrites the relative imports of a source moved from `from` to `to`, following the directories
+/// moved by the migration.
+fn relocate_imports(content: &str, from: &Path, to: &Path, moves: &[(&Path, &Path)]) -> String {
+    IMPORT_REGEX
+        .replace_all(content, |caps: &Captures<'_>| {
+            let path = &caps[2];
+            if !path.starts_with("./") && !path.starts_with("../") {
+                return caps[0].to_string();
+            }
+            let mut target = fs::normalize_path(&from.parent().unwrap().join(path));
+            if let Some(moved) =
+                moves.iter().find_map(|(old, new)| Some(new.join(target.strip_prefix(old).ok()?)))
+            {
+                target = moved;
+            }
+            format!("{}{}{}", &caps[1], relative_path(to.parent().unwrap(), &target), &caps[3])
+        })
+        .into_owned()
+}
+
+/// Returns the relative import path of `path` from the directory `base`.
+fn relative_path(base: &Path, path: &Path) -> String {
+    let base = base.components().collect::<Vec<_>>();
+    let path = path.components().collect::<Vec<_>>();
+    let common = base.iter().zip(&path).take_while(|(a, b)| a == b).count();
+    let mut parts = vec![Cow::Borrowed(".."); base.len() - common];
+    parts.extend(path[common..].iter().map(|c| c.as_os_str().to_string_lossy()));
+    let relative = parts.join("/");
+    if relative.starts_with("..") {
+        relative
+    } else {
+        format!("./{relative}")
+    }
+}
+
+/// Returns the remappings of all Solidity packages installed in `node_modules`.
+fn npm_remappings(root: &Path) -> Result<Vec<String>> {
+    let package_json = root.join("package.json");
+    if !package_json.is_file() {
+        return Ok(Vec::new());
+    }
+    let package: serde_json::Value = fs::read_json_file(&package_json)?;
+    let mut remappings = Vec::new();
+    for deps in ["dependencies", "devDependencies"] {
+        let Some(deps) = package.get(deps).and_then(|d| d.as_object()) else { continue };
+        for name in deps.keys() {
+            if name == "hardhat" {
+                continue;
+            }
+            let dir = root.join("node_modules").join(name);
+            if fs::files_with_ext(&dir, "sol").next().is_some() {
+                remappings.push(format!("{name}/=node_modules/{name}/"));
+            }
+        }
+    }
+    if remappings.is_empty() && !root.join("node_modules").exists() {
+        sh_warn!("node_modules not found, install the npm dependencies to generate remappings")?;
+    }
+    remappings.sort();
+    remappings.dedup();
+    Ok(remappings)
+}
+
+/// Returns the RPC URL of a network config.
+///
+/// Environment variables, e.g. `process.env.SEPOLIA_RPC_URL`, are converted to `${SEPOLIA_RPC_URL}`
+/// and Truffle's `host` and `port` to `http://host:port`.
+fn network_url(network: &str) -> Option<String> {
+    if let Some(url) = string_value(network, "url") {
+        return Some(url);
+    }
+    if let Some(raw) = raw_value(network, "url") {
+        let var = raw.strip_prefix("process.env.")?;
+        return Some(format!("${{{var}}}"));
+    }
+    let host = string_value(network, "host")?;
+    let port = raw_value(network, "port")?;
+    Some(format!("http://{host}:{port}"))
+}
+
+/// Returns the index right after `key:` in `s`, where `key` is a whole (optionally quoted)
+/// identifier.
+fn find_key(s: &str, key: &str) -> Option<usize> {
+    let re = Regex::new(&format!(r#"(?:^|[^\w$])["']?{}["']?\s*:\s*"#, regex::escape(key))).ok()?;
+    re.find(s).map(|m| m.end())
+}
+
+/// Returns the literal string value of `key`.
+fn string_value(s: &str, key: &str) -> Option<String> {
+    let rest = &s[find_key(s, key)?..];
+    let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\'' | '`'))?;
+    let end = rest[1..].find(quote)?;
+    Some(rest[1..1 + end].to_string())
+}
+
+/// Returns the raw value of `key`, up to the next `,`, `}` or newline.
+fn raw_value<'a>(s: &'a str, key: &str) -> Option<&'a str> {
+    let rest = &s[find_key(s, key)?..];
+    let end = rest.find([',', '}', '\n']).unwrap_or(rest.len());
+    Some(rest[..end].trim()).filter(|v| !v.is_empty())
+}
+
+/// Returns the body of the object literal assigned to `key`, without the surrounding braces.
+fn object_body<'a>(s: &'a str, key: &str) -> Option<&'a str> {
+    let start = find_key(s, key)?;
+    let rest = s[start..].strip_prefix('{')?;
+    let end = matching_brace(rest)?;
+    Some(&rest[..end])
+}
+
+/// Returns the index of the `}` closing an object whose `{` precedes `s`.
+fn matching_brace(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(i),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the top-level `name: { ... }` entries of an object body.
+fn entries(body: &str) -> Vec<(&str, &str)> {
+    static ENTRY_REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"["']?([\w$-]+)["']?\s*:\s*\{"#).unwrap());
+
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while let Some(caps) = ENTRY_REGEX.captures_at(body, pos) {
+        let m = caps.get(0).unwrap();
+        // skip objects which are not values of top-level entries, e.g. in arrays
+        if let Some(open) = body[pos..m.start()].find('{') {
+            let open = pos + open + 1;
+            let Some(end) = matching_brace(&body[open..]) else { break };
+            pos = open + end + 1;
+            continue;
+        }
+        let Some(end) = matching_brace(&body[m.end()..]) else { break };
+        entries.push((caps.get(1).unwrap().as_str(), &body[m.end()..m.end() + end]));
+        pos = m.end() + end + 1;
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hardhat_config() {
+        let config = r#"
+require("@nomicfoundation/hardhat-toolbox");
+
+module.exports = {
+  solidity: {
+    version: "0.8.20",
+    settings: {
+      optimizer: { enabled: true, runs: 1000 },
+      evmVersion: "paris",
+      viaIR: true,
+    },
+  },
+  paths: { sources: "./contracts/core" },
+  networks: {
+    hardhat: { chainId: 31337 },
+    sepolia: {
+      url: process.env.SEPOLIA_RPC_URL,
+      accounts: [process.env.PRIVATE_KEY],
+    },
+    "base-mainnet": { url: "https://mainnet.base.org" },
+  },
+};
+"#;
+        let legacy = LegacyConfig::parse(Framework::Hardhat, config);
+        assert_eq!(
+            legacy,
+            LegacyConfig {
+                sources: "contracts/core".to_string(),
+                tests: "test".to_string(),
+                solc: Some("0.8.20".to_string()),
+                optimizer: Some(true),
+                optimizer_runs: Some(1000),
+                evm_version: Some("paris".to_string()),
+                via_ir: Some(true),
+                networks: vec![
+                    ("sepolia".to_string(), "${SEPOLIA_RPC_URL}".to_string()),
+                    ("base-mainnet".to_string(), "https://mainnet.base.org".to_string()),
+                ],
+            }
+        );
+
+        let legacy =
+            LegacyConfig::parse(Framework::Hardhat, r#"module.exports = { solidity: "0.8.19" };"#);
+        assert_eq!(legacy.solc.as_deref(), Some("0.8.19"));
+        assert_eq!(legacy.sources, "contracts");
+
+        // settings outside of the `solidity` object are not compiler settings
+        let legacy = LegacyConfig::parse(
+            Framework::Hardhat,
+            r#"module.exports = { solidity: "0.8.19", paths: { tests: "./tests/" }, mocha: { runs: 5 } };"#,
+        );
+        assert_eq!(legacy.solc.as_deref(), Some("0.8.19"));
+        assert_eq!(legacy.optimizer_runs, None);
+        assert_eq!(legacy.tests, "tests");
+    }
+
+    #[test]
+    fn parses_truffle_config() {
+        let config = r#"
+module.exports = {
+  networks: {
+    development: {
+      host: "127.0.0.1",
+      port: 8545,
+      network_id: "*",
+    },
+  },
+  compilers: {
+    solc: {
+      version: "0.8.13",
+      settings: {
+        optimizer: {
+          enabled: false,
+          runs: 200
+        },
+      }
+    }
+  }
+};
+"#;
+        let legacy = LegacyConfig::parse(Framework::Truffle, config);
+        assert_eq!(legacy.solc.as_deref(), Some("0.8.13"));
+        assert_eq!(legacy.optimizer, Some(false));
+        assert_eq!(legacy.optimizer_runs, Some(200));
+        assert_eq!(legacy.tests, "test");
+        assert_eq!(
+            legacy.networks,
+            [("development".to_string(), "http://127.0.0.1:8545".to_string())]
+        );
+    }
+
+    #[test]
+    fn rewrites_imports() {
+        let source = r#"import "hardhat/console.sol";
+import {Token} from "../contracts/Token.sol";
+import "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+import './Lib.sol';
+"#;
+        assert_eq!(
+            rewrite_imports(source, "contracts", true),
+            r#"import "forge-std/console.sol";
+import {Token} from "../src/Token.sol";
+import "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+import './Lib.sol';
+"#
+        );
+        assert_eq!(
+            rewrite_imports(source, "contracts", false),
+            source.replace("hardhat/console.sol", "forge-std/console.sol")
+        );
+    }
+
+    #[test]
+    fn relocates_imports() {
+        let root = Path::new("/project");
+        let moves =
+            [(root.join("tests"), root.join("test")), (root.join("contracts"), root.join("src"))];
+        let moves =
+            moves.iter().map(|(old, new)| (old.as_path(), new.as_path())).collect::<Vec<_>>();
+        let source = r#"import "hardhat/console.sol";
+import {Token} from "../../contracts/Token.sol";
+import "./Helper.sol";
+import "../Base.sol";
+"#;
+        assert_eq!(
+            relocate_imports(
+                source,
+                &root.join("tests/unit/Token.t.sol"),
+                &root.join("test/unit/Token.t.sol"),
+                &moves
+            ),
+            source.replace("../../contracts/", "../../src/")
+        );
+        assert_eq!(
+            relocate_imports(
+                source,
+                &root.join("tests/unit/Token.t.sol"),
+                &root.join("test/Token.t.sol"),
+                &moves
+            ),
+            r#"import "hardhat/console.sol";
+import {Token} from "../src/Token.sol";
+import "./unit/Helper.sol";
+import "./Base.sol";
+"#
+        );
+    }
+}
@@ -5,10 +5,13 @@
 //! implement `figment::Provider` which allows the subcommand to override the config's defaults, see
 //! [`foundry_config::Config`].
 
+pub mod audit_deps;
 pub mod bind;
 pub mod bind_json;
+pub mod bind_ts;
 pub mod build;
 pub mod cache;
+pub mod clean;
 pub mod clone;
 pub mod compiler;
 pub mod config;
@@ -18,17 +21,23 @@
 pub mod eip712;
 pub mod flatten;
 pub mod fmt;
+pub mod gas_check;
 pub mod geiger;
 pub mod generate;
 pub mod init;
 pub mod inspect;
 pub mod install;
+pub mod migrate;
+pub mod mutate;
+pub mod prove;
 pub mod remappings;
 pub mod remove;
 pub mod selectors;
 pub mod snapshot;
 pub mod soldeer;
+pub mod storage_diff;
 pub mod test;
 pub mod tree;
 pub mod update;
+pub mod validate_upgrade;
 pub mod watch;
@@ -0,0 +1,334 @@
+use crate::mutation::{mutants, Mutant};
+use alloy_primitives::{hex, keccak256};
+use clap::{Parser, ValueHint};
+use eyre::{Context, Result};
+use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
+use foundry_common::fs;
+use foundry_config::{impl_figment_convert, Config};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+use yansi::Paint;
+
+impl_figment_convert!(MutateArgs, build);
+
+/// CLI arguments for `forge mutate`.
+///
+/// Applies mutation operators to the sources and runs the test suite against each mutant, reporting
+/// the mutants that no test detects.
+#[derive(Clone, Debug, Parser)]
+pub struct MutateArgs {
+    /// Files to mutate.
+    ///
+    /// Defaults to the source files of the project.
+    #[arg(value_hint = ValueHint::FilePath, value_name = "PATH", num_args(1..))]
+    paths: Vec<PathBuf>,
+
+    /// Number of mutants tested in parallel, each in its own copy of the project.
+    ///
+    /// Defaults to the number of available CPUs.
+    #[arg(long, short, value_name = "JOBS")]
+    jobs: Option<usize>,
+
+    /// Ignore the results of previous runs.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Arguments passed to `forge test`, e.g. `-- --match-contract CounterTest`.
+    #[arg(last = true, value_name = "TEST_ARGS")]
+    test_args: Vec<String>,
+
+    #[command(flatten)]
+    build: BuildOpts,
+}
+
+/// The outcome of running the test suite against a mutant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MutantStatus {
+    /// A test failed.
+    Killed,
+    /// All tests passed.
+    Survived,
+    /// The mutant does not compile.
+    Invalid,
+}
+
+impl MutateArgs {
+    pub fn run(self) -> Result<()> {
+        let config = self.load_config()?;
+        let files = if self.paths.is_empty() {
+            fs::files_with_ext(&config.src, "sol").collect::<Vec<_>>()
+        } else {
+            self.paths.clone()
+        };
+
+        // Mutants are written to a copy of the project by relative path, so mutating a file outside
+        // of the project or in a dependency would modify the original.
+        let root = dunce::canonicalize(&config.root)?;
+        let libs =
+            config.libs.iter().filter_map(|lib| dunce::canonicalize(lib).ok()).collect::<Vec<_>>();
+        let mut sources = BTreeMap::new();
+        let mut all_mutants = Vec::new();
+        for path in files {
+            let path = dunce::canonicalize(&path)
+                .wrap_err_with(|| format!("failed to resolve {}", path.display()))?;
+            let Ok(relative) = path.strip_prefix(&root) else {
+                eyre::bail!("{} is outside of the project root {}", path.display(), root.display())
+            };
+            eyre::ensure!(
+                !libs.iter().any(|lib| path.starts_with(lib)),
+                "{} is in a dependency, only the sources of the project can be mutated",
+                path.display()
+            );
+            let source = fs::read_to_string(&path)?;
+            let path = relative.to_path_buf();
+            all_mutants.extend(mutants(&path, &source));
+            sources.insert(path, source);
+        }
+        eyre::ensure!(!all_mutants.is_empty(), "No mutants to test");
+        sh_println!("Generated {} mutants from {} files", all_mutants.len(), sources.len())?;
+
+        let cache_path = config.cache_path.join("mutation-testing.json");
+        let mut cache: BTreeMap<String, MutantStatus> = if self.no_cache {
+            BTreeMap::new()
+        } else {
+            fs::read_json_file(&cache_path).unwrap_or_default()
+        };
+        let fingerprint = self.fingerprint(&config)?;
+        let key = |mutant: &Mutant| {
+            let mutant = format!(
+                "{}:{}:{}:{}",
+                mutant.path.display(),
+                mutant.start,
+                mutant.end,
+                mutant.replacement
+            );
+            hex::encode(keccak256([fingerprint.as_slice(), mutant.as_bytes()].concat()))
+        };
+
+        let mut statuses = vec![None; all_mutants.len()];
+        let mut pending = Vec::new();
+        for (i, mutant) in all_mutants.iter().enumerate() {
+            match cache.get(&key(mutant)) {
+                Some(status) => {
+                    print_status(mutant, &sources[&mutant.path], *status, true)?;
+                    statuses[i] = Some(*status);
+                }
+                None => pending.push(i),
+            }
+        }
+
+        if !pending.is_empty() {
+            let jobs = self
+                .jobs
+                .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+                .clamp(1, pending.len());
+            let mutated = sources.keys().map(|path| root.join(path)).collect::<Vec<_>>();
+            let workspaces = (0..jobs)
+                .map(|i| Workspace::new(&config, i, &mutated))
+                .collect::<Result<Vec<_>>>()
+                .wrap_err("failed to copy the project")?;
+
+            eyre::ensure!(
+                workspaces[0].forge(&["test", "--fail-fast"], &self.test_args)?,
+                "The test suite must pass before mutation testing"
+            );
+
+            let next = AtomicUsize::new(0);
+            let results = Mutex::new(Vec::new());
+            let (next, results, pending) = (&next, &results, &pending);
+            let (all_mutants, sources, test_args) = (&all_mutants, &sources, &self.test_args);
+            let tested = std::thread::scope(|scope| {
+                let workers = workspaces
+                    .iter()
+                    .map(|workspace| {
+                        scope.spawn(move || -> Result<()> {
+                            loop {
+                                let Some(&i) = pending.get(next.fetch_add(1, Ordering::Relaxed))
+                                else {
+                                    return Ok(());
+                                };
+                                let mutant = &all_mutants[i];
+                                let source = &sources[&mutant.path];
+                                let status = workspace.test(mutant, source, test_args)?;
+                                print_status(mutant, source, status, false)?;
+                                results.lock().unwrap().push((i, status));
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                workers.into_iter().try_for_each(|worker| worker.join().unwrap())
+            });
+            for workspace in workspaces {
+                workspace.remove();
+            }
+            tested?;
+
+            for (i, status) in std::mem::take(&mut *results.lock().unwrap()) {
+                cache.insert(key(&all_mutants[i]), status);
+                statuses[i] = Some(status);
+            }
+            fs::create_dir_all(&config.cache_path)?;
+            fs::write_json_file(&cache_path, &cache)?;
+        }
+
+        let count = |status| statuses.iter().filter(|s| **s == Some(status)).count();
+        let (killed, survived, invalid) = (
+            count(MutantStatus::Killed),
+            count(MutantStatus::Survived),
+            count(MutantStatus::Invalid),
+        );
+        if survived > 0 {
+            sh_println!("\nSurviving mutants:")?;
+            for (mutant, _) in all_mutants
+                .iter()
+                .zip(&statuses)
+                .filter(|(_, status)| **status == Some(MutantStatus::Survived))
+            {
+                let source = &sources[&mutant.path];
+                sh_println!("\n{}", location(mutant, source))?;
+                sh_print!("{}", mutant.diff(source))?;
+            }
+        }
+
+        let score = 100.0 * killed as f64 / (killed + survived).max(1) as f64;
+        sh_println!(
+            "\nMutation score: {score:.2}% ({killed} killed, {survived} survived, {invalid} invalid)"
+        )?;
+        Ok(())
+    }
+
+    /// Returns the hash of the project sources and test arguments, invalidating the cached results
+    /// when they change.
+    fn fingerprint(&self, config: &Config) -> Result<[u8; 32]> {
+        let mut files = [&config.src, &config.test, &config.script]
+            .into_iter()
+            .flat_map(|dir| fs::files_with_ext(dir, "sol"))
+            .collect::<Vec<_>>();
+        files.sort();
+        files.dedup();
+        let mut data = self.test_args.join(" ").into_bytes();
+        for file in files {
+            data.extend_from_slice(file.to_string_lossy().as_bytes());
+            data.extend_from_slice(keccak256(fs::read(&file)?).as_slice());
+        }
+        Ok(keccak256(data).0)
+    }
+}
+
+fn location(mutant: &Mutant, source: &str) -> String {
+    let (line, column) = mutant.location(source);
+    format!("{}:{line}:{column} {}", mutant.path.display(), mutant.describe(source))
+}
+
+fn print_status(mutant: &Mutant, source: &str, status: MutantStatus, cached: bool) -> Result<()> {
+    let status = match status {
+        MutantStatus::Killed => "[KILLED]".green().to_string(),
+        MutantStatus::Survived => "[SURVIVED]".red().to_string(),
+        MutantStatus::Invalid => "[INVALID]".yellow().to_string(),
+    };
+    let cached = if cached { " (cached)" } else { "" };
+    sh_println!("{status} {}{cached}", location(mutant, source))
+}
+
+/// A copy of the project the mutants are applied to, sharing the dependencies of the project.
+struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    /// Copies the project, linking the dependencies unless they contain one of the `mutated` files.
+    fn new(config: &Config, index: usize, mutated: &[PathBuf]) -> Result<Self> {
+        let root =
+            std::env::temp_dir().join(format!("forge-mutate-{}-{index}", std::process::id()));
+        if root.exists() {
+            fs::remove_dir_all(&root)?;
+        }
+        let git = config.root.join(".git");
+        let skip = [&config.out, &config.cache_path, &config.broadcast, &git]
+            .into_iter()
+            .chain(&config.libs)
+            .collect::<Vec<_>>();
+        copy_dir(&config.root, &root, &skip)?;
+
+        for lib in &config.libs {
+            let Ok(relative) = lib.strip_prefix(&config.root) else { continue };
+            if !lib.exists() {
+                continue;
+            }
+            let target = root.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let lib = dunce::canonicalize(lib)?;
+            if mutated.iter().any(|path| path.starts_with(&lib)) {
+                copy_dir(&lib, &target, &[])?;
+                continue;
+            }
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&lib, &target)?;
+            #[cfg(windows)]
+            std::os::windows::fs::symlink_dir(&lib, &target)?;
+        }
+        Ok(Self { root })
+    }
+
+    /// Runs the test suite against `mutant`, restoring the original source afterwards.
+    fn test(&self, mutant: &Mutant, source: &str, test_args: &[String]) -> Result<MutantStatus> {
+        let path = self.root.join(&mutant.path);
+        fs::write(&path, mutant.apply(source))?;
+        let status = if !self.forge(&["build"], &[])? {
+            MutantStatus::Invalid
+        } else if self.forge(&["test", "--fail-fast"], test_args)? {
+            MutantStatus::Survived
+        } else {
+            MutantStatus::Killed
+        };
+        fs::write(&path, source)?;
+        Ok(status)
+    }
+
+    /// Runs a forge command in the workspace, returning whether it succeeded.
+    fn forge(&self, args: &[&str], extra_args: &[String]) -> Result<bool> {
+        let status = Command::new(std::env::current_exe()?)
+            .args(args)
+            .arg("--root")
+            .arg(&self.root)
+            .args(extra_args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+        Ok(status.success())
+    }
+
+    fn remove(self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Recursively copies `from` to `to`, skipping the `skip` paths.
+fn copy_dir(from: &Path, to: &Path, skip: &[&PathBuf]) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        if skip.iter().any(|skip| **skip == path) {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&path, &target, skip)?;
+        } else {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use super::test::FilterArgs;
+use crate::{
+    multi_runner::matches_contract,
+    prove::{prove, Env, Limits, ProveResult, Solver},
+};
+use alloy_json_abi::StateMutability;
+use alloy_primitives::U256;
+use clap::Parser;
+use eyre::Result;
+use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
+use foundry_common::{compile::ProjectCompiler, shell, TestFilter, TestFunctionExt};
+use foundry_compilers::Artifact;
+use foundry_config::impl_figment_convert;
+use foundry_evm::constants::TEST_CONTRACT_ADDRESS;
+use yansi::Paint;
+
+impl_figment_convert!(ProveArgs, build);
+
+/// CLI arguments for `forge prove`.
+///
+/// Executes the pure tests with symbolic arguments, checking that they pass for all inputs.
+#[derive(Clone, Debug, Parser)]
+pub struct ProveArgs {
+    /// The SMT-LIB solver command, reading the queries from its standard input.
+    #[arg(long, value_name = "COMMAND", default_value = "z3 -in")]
+    solver: String,
+
+    /// The maximum number of iterations of loops with symbolic conditions.
+    #[arg(long, value_name = "BOUND", default_value_t = 4)]
+    loop_bound: usize,
+
+    /// The maximum number of instructions executed by each path.
+    #[arg(long, value_name = "STEPS", default_value_t = 100_000)]
+    max_steps: usize,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    #[command(flatten)]
+    build: BuildOpts,
+}
+
+impl ProveArgs {
+    pub fn run(self) -> Result<()> {
+        let config = self.load_config()?;
+        let project = config.project()?;
+        let output = ProjectCompiler::new().quiet(shell::is_json()).compile(&project)?;
+        let filter = self.filter.clone().merge_with_config(&config);
+
+        let solver = Solver::new(self.solver.split_whitespace().map(String::from).collect());
+        let limits = Limits { loop_bound: self.loop_bound, max_steps: self.max_steps };
+        let env = Env {
+            address: TEST_CONTRACT_ADDRESS,
+            caller: config.sender,
+            timestamp: U256::from(config.block_timestamp),
+            number: U256::from(config.block_number),
+            chain_id: U256::from(config.chain.map_or(31337, |chain| chain.id())),
+        };
+
+        let mut failures = 0;
+        for (id, artifact) in output.artifact_ids() {
+            let Some(abi) = &artifact.abi else { continue };
+            if !matches_contract(&id, abi, &filter) {
+                continue;
+            }
+            let tests = abi
+                .functions()
+                .filter(|func| {
+                    func.is_any_test() &&
+                        !func.is_any_test_fail() &&
+                        func.state_mutability == StateMutability::Pure &&
+                        filter.matches_test(&func.signature())
+                })
+                .collect::<Vec<_>>();
+            if tests.is_empty() {
+                continue;
+            }
+            let Some(code) = artifact.get_deployed_bytecode_bytes() else { continue };
+
+            let source = id.source.strip_prefix(project.root()).unwrap_or(&id.source);
+            sh_println!("\nProving {} tests in {}:{}", tests.len(), source.display(), id.name)?;
+            for func in tests {
+                let result = prove(&code, func, &env, limits, &solver)?;
+                let signature = func.signature();
+                match &result {
+                    ProveResult::Pass { paths, bounded } => {
+                        let bounded = if *bounded { ", bounded" } else { "" };
+                        sh_println!(
+                            "{} {signature} (paths: {paths}{bounded})",
+                            result.to_string().green()
+                        )?;
+                    }
+                    ProveResult::Fail { paths, .. } => {
+                        failures += 1;
+                        sh_println!("{} {signature} (paths: {paths})", result.to_string().red())?;
+                    }
+                    ProveResult::Skip(_) => {
+                        sh_println!("{} {signature}", result.to_string().yellow())?;
+                    }
+                }
+            }
+        }
+
+        if failures > 0 {
+            eyre::bail!("{failures} test(s) failed to prove");
+        }
+        Ok(())
+    }
+}
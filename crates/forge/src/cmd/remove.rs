@@ -1,11 +1,13 @@
+use super::install::lockfile::Lockfile;
 use clap::{Parser, ValueHint};
 use eyre::Result;
 use foundry_cli::{
     opts::Dependency,
-    utils::{Git, LoadConfig},
+    utils::{CommandUtils, Git, LoadConfig},
 };
-use foundry_config::impl_figment_convert_basic;
-use std::path::PathBuf;
+use foundry_common::fs;
+use foundry_config::{impl_figment_convert_basic, Config};
+use std::path::{Path, PathBuf};
 
 /// CLI arguments for `forge remove`.
 #[derive(Clone, Debug, Parser)]
@@ -24,6 +26,10 @@ pub struct RemoveArgs {
     /// Override the up-to-date check.
     #[arg(short, long)]
     force: bool,
+
+    /// Print what would be removed, without removing anything.
+    #[arg(long)]
+    dry_run: bool,
 }
 impl_figment_convert_basic!(RemoveArgs);
 
@@ -32,16 +38,172 @@ pub fn run(self) -> Result<()> {
         let config = self.load_config()?;
         let (root, paths) = super::update::dependencies_paths(&self.dependencies, &config)?;
         let git_modules = root.join(".git/modules");
+        let git = Git::new(&root);
+        let dirs = paths.iter().map(|path| root.join(path)).collect::<Vec<_>>();
+
+        let remappings_txt = config.root.join("remappings.txt");
+        let stale_txt = if remappings_txt.exists() {
+            fs::read_to_string(&remappings_txt)?
+                .lines()
+                .filter(|line| is_stale_remapping(&config.root, line, &dirs))
+                .map(str::to_string)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let stale_toml = config_remappings(&config)?
+            .into_iter()
+            .filter(|r| is_stale_remapping(&config.root, r, &dirs))
+            .collect::<Vec<_>>();
+
+        if self.dry_run {
+            for (Dependency { name, .. }, path) in self.dependencies.iter().zip(&paths) {
+                sh_println!("Would remove '{name}' in {}", path.display())?;
+            }
+            for remapping in &stale_txt {
+                sh_println!("Would remove remapping '{remapping}' from remappings.txt")?;
+            }
+            for remapping in &stale_toml {
+                sh_println!("Would remove remapping '{remapping}' from {}", Config::FILE_NAME)?;
+            }
+            for name in stale_submodules(git, &paths)? {
+                sh_println!("Would remove submodule '{name}' from .gitmodules")?;
+            }
+            return Ok(());
+        }
 
         // remove all the dependencies by invoking `git rm` only once with all the paths
-        Git::new(&root).rm(self.force, &paths)?;
+        git.rm(self.force, &paths)?;
 
         // remove all the dependencies from .git/modules
         for (Dependency { name, url, tag, .. }, path) in self.dependencies.iter().zip(&paths) {
             sh_println!("Removing '{name}' in {}, (url: {url:?}, tag: {tag:?})", path.display())?;
-            std::fs::remove_dir_all(git_modules.join(path))?;
+            let module = git_modules.join(path);
+            if module.exists() {
+                std::fs::remove_dir_all(module)?;
+            }
+        }
+
+        // remove leftover submodule entries, e.g. of dependencies whose directory was deleted
+        for name in stale_submodules(git, &paths)? {
+            git.cmd()
+                .args(["config", "-f", ".gitmodules", "--remove-section"])
+                .arg(format!("submodule.{name}"))
+                .exec()?;
+            git.add(Some(".gitmodules"))?;
+        }
+
+        // remove the remappings pointing into the removed dependencies
+        if !stale_txt.is_empty() {
+            let content = fs::read_to_string(&remappings_txt)?
+                .lines()
+                .filter(|line| !stale_txt.iter().any(|r| r == line))
+                .map(|line| format!("{line}\n"))
+                .collect::<String>();
+            fs::write(&remappings_txt, content)?;
+            for remapping in &stale_txt {
+                sh_println!("Removing remapping '{remapping}' from remappings.txt")?;
+            }
+        }
+        if !stale_toml.is_empty() {
+            config.update(|doc| {
+                let mut changed = false;
+                let Some(profiles) =
+                    doc.get_mut(Config::PROFILE_SECTION).and_then(|p| p.as_table_like_mut())
+                else {
+                    return false;
+                };
+                for (_, profile) in profiles.iter_mut() {
+                    let Some(remappings) = profile
+                        .as_table_like_mut()
+                        .and_then(|profile| profile.get_mut("remappings"))
+                        .and_then(|remappings| remappings.as_array_mut())
+                    else {
+                        continue;
+                    };
+                    let len = remappings.len();
+                    remappings.retain(|r| {
+                        !r.as_str().is_some_and(|r| is_stale_remapping(&config.root, r, &dirs))
+                    });
+                    changed |= remappings.len() != len;
+                }
+                changed
+            })?;
+            for remapping in &stale_toml {
+                sh_println!("Removing remapping '{remapping}' from {}", Config::FILE_NAME)?;
+            }
+        }
+
+        // unpin the removed dependencies
+        if let Some(mut lockfile) = Lockfile::read(&config.root)? {
+            let mut changed = false;
+            for path in &paths {
+                changed |= lockfile.unlock(&config.root, &root.join(path))?;
+            }
+            if changed {
+                lockfile.write(&config.root)?;
+            }
         }
 
         Ok(())
     }
 }
+
+/// Returns `true` if the target of `remapping` is inside one of `dirs`.
+fn is_stale_remapping(root: &Path, remapping: &str, dirs: &[PathBuf]) -> bool {
+    let Some((_, target)) = remapping.split_once('=') else { return false };
+    let target = root.join(target.trim().trim_start_matches("./"));
+    dirs.iter().any(|dir| target.starts_with(dir))
+}
+
+/// Returns the remappings declared in the profiles of `foundry.toml`.
+fn config_remappings(config: &Config) -> Result<Vec<String>> {
+    let path = config.get_config_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let doc = fs::read_to_string(&path)?.parse::<toml_edit::DocumentMut>()?;
+    let Some(profiles) = doc.get(Config::PROFILE_SECTION).and_then(|p| p.as_table_like()) else {
+        return Ok(Vec::new());
+    };
+    Ok(profiles
+        .iter()
+        .filter_map(|(_, profile)| profile.as_table_like()?.get("remappings")?.as_array())
+        .flat_map(|remappings| remappings.iter().filter_map(|r| r.as_str().map(str::to_string)))
+        .collect())
+}
+
+/// Returns the names of the `.gitmodules` entries of the submodules at `paths`.
+fn stale_submodules(git: Git<'_>, paths: &[PathBuf]) -> Result<Vec<String>> {
+    if !git.root.join(".gitmodules").exists() {
+        return Ok(Vec::new());
+    }
+    let output = git
+        .cmd()
+        .args(["config", "-f", ".gitmodules", "--get-regexp", r"^submodule\..*\.path$"])
+        .get_stdout_lossy()
+        .unwrap_or_default();
+    Ok(output
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .filter(|(_, path)| paths.iter().any(|p| Path::new(path) == p))
+        .filter_map(|(key, _)| key.strip_prefix("submodule.")?.strip_suffix(".path"))
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_stale_remappings() {
+        let root = Path::new("/project");
+        let dirs = [root.join("lib/forge-std")];
+        assert!(is_stale_remapping(root, "forge-std/=lib/forge-std/src/", &dirs));
+        assert!(is_stale_remapping(root, "ds-test/=./lib/forge-std/lib/ds-test/src/", &dirs));
+        assert!(is_stale_remapping(root, "src:forge-std/=lib/forge-std/src/", &dirs));
+        assert!(!is_stale_remapping(root, "forge-std-extra/=lib/forge-std-extra/", &dirs));
+        assert!(!is_stale_remapping(root, "solady/=lib/solady/src/", &dirs));
+    }
+}
@@ -9,10 +9,14 @@
 use foundry_common::{
     compile::{compile_target, PathOrContractInfo, ProjectCompiler},
     selectors::{import_selectors, SelectorImportData},
+    shell,
 };
 use foundry_compilers::{artifacts::output_selection::ContractOutputSelection, info::ContractInfo};
 use foundry_config::Config;
-use std::fs::canonicalize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::canonicalize,
+};
 
 /// CLI arguments for `forge selectors`.
 #[derive(Clone, Debug, Parser)]
@@ -32,6 +36,19 @@ pub enum SelectorsSubcommands {
         build: Box<BuildOpts>,
     },
 
+    /// Check for function selector collisions between all contracts of the project, including
+    /// dependencies.
+    ///
+    /// Contracts of the test and script directories are not checked.
+    Collisions {
+        /// Exit with a non-zero code if any collisions are found.
+        #[arg(long)]
+        fail_on_collision: bool,
+
+        #[command(flatten)]
+        project_paths: ProjectPathOpts,
+    },
+
     /// Upload selectors to registry
     #[command(visible_alias = "up")]
     Upload {
@@ -226,6 +243,75 @@ pub async fn run(self) -> Result<()> {
                     sh_println!("\n{table}\n")?;
                 }
             }
+            Self::Collisions { fail_on_collision, project_paths } => {
+                let build_args = BuildOpts {
+                    project_paths,
+                    compiler: CompilerOpts {
+                        extra_output: vec![ContractOutputSelection::Abi],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+
+                let project = build_args.project()?;
+                let outcome = ProjectCompiler::new().quiet(true).compile(&project)?;
+
+                // selector -> signature -> contracts declaring it
+                let mut selectors: BTreeMap<_, BTreeMap<_, BTreeSet<_>>> = BTreeMap::new();
+                for (file, contract, artifact) in outcome.into_artifacts_with_files() {
+                    if file.is_sol_test() ||
+                        file.starts_with(&project.paths.tests) ||
+                        file.starts_with(&project.paths.scripts)
+                    {
+                        continue
+                    }
+                    let Some(abi) = artifact.abi else { continue };
+                    let file = file.strip_prefix(project.root()).unwrap_or(&file);
+                    let id = format!("{}:{contract}", file.display());
+                    for func in abi.functions() {
+                        selectors
+                            .entry(hex::encode_prefixed(func.selector()))
+                            .or_default()
+                            .entry(func.signature())
+                            .or_default()
+                            .insert(id.clone());
+                    }
+                }
+                selectors.retain(|_, signatures| signatures.len() > 1);
+
+                if shell::is_json() {
+                    sh_println!("{}", serde_json::to_string_pretty(&selectors)?)?;
+                } else if selectors.is_empty() {
+                    sh_println!("No colliding function selectors found in the project.")?;
+                } else {
+                    let mut table = Table::new();
+                    table.apply_modifier(UTF8_ROUND_CORNERS);
+                    table.set_header(["Selector", "First", "Second"]);
+                    let describe = |(signature, contracts): (&String, &BTreeSet<String>)| {
+                        let contracts = contracts.iter().cloned().collect::<Vec<_>>().join("\n");
+                        format!("{signature}\n{contracts}")
+                    };
+                    for (selector, signatures) in &selectors {
+                        // report every pair of distinct signatures sharing the selector
+                        let signatures = signatures.iter().collect::<Vec<_>>();
+                        for (i, first) in signatures.iter().enumerate() {
+                            for second in &signatures[i + 1..] {
+                                table.add_row([
+                                    selector.clone(),
+                                    describe(*first),
+                                    describe(*second),
+                                ]);
+                            }
+                        }
+                    }
+                    sh_println!("{} colliding selectors found:", selectors.len())?;
+                    sh_println!("\n{table}\n")?;
+                }
+
+                if fail_on_collision && !selectors.is_empty() {
+                    eyre::bail!("found {} colliding function selectors", selectors.len());
+                }
+            }
             Self::List { contract, project_paths } => {
                 sh_println!("Listing selectors for contracts in the project...")?;
                 let build_args = BuildOpts {
@@ -3,10 +3,12 @@
 use alloy_primitives::{map::HashMap, U256};
 use clap::{builder::RangedU64ValueParser, Parser, ValueHint};
 use eyre::{Context, Result};
-use foundry_cli::utils::STATIC_FUZZ_SEED;
+use foundry_cli::utils::{LoadConfig, STATIC_FUZZ_SEED};
+use foundry_config::GasSnapshotTolerance;
 use regex::Regex;
 use std::{
     cmp::Ordering,
+    collections::BTreeMap,
     fs,
     io::{self, BufRead},
     path::{Path, PathBuf},
@@ -18,7 +20,7 @@
 /// A regex that matches a basic snapshot entry like
 /// `Test:testDeposit() (gas: 58804)`
 pub static RE_BASIC_SNAPSHOT_ENTRY: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?P<file>(.*?)):(?P<sig>(\w+)\s*\((.*?)\))\s*\(((gas:)?\s*(?P<gas>\d+)|(runs:\s*(?P<runs>\d+),\s*μ:\s*(?P<avg>\d+),\s*~:\s*(?P<med>\d+))|(runs:\s*(?P<invruns>\d+),\s*calls:\s*(?P<calls>\d+),\s*reverts:\s*(?P<reverts>\d+)))\)").unwrap()
+    Regex::new(r"(?P<file>(.*?)):(?P<sig>(\w+)\s*\((.*?)\)(\[\d+\])?)\s*\(((gas:)?\s*(?P<gas>\d+)|(runs:\s*(?P<runs>\d+),\s*μ:\s*(?P<avg>\d+),\s*~:\s*(?P<med>\d+))|(runs:\s*(?P<invruns>\d+),\s*calls:\s*(?P<calls>\d+),\s*reverts:\s*(?P<reverts>\d+)))\)").unwrap()
 });
 
 /// CLI arguments for `forge snapshot`.
@@ -63,6 +65,8 @@ pub struct GasSnapshotArgs {
     snap: PathBuf,
 
     /// Tolerates gas deviations up to the specified percentage.
+    ///
+    /// Tolerances configured in `gas_snapshot_tolerances` take precedence.
     #[arg(
         long,
         value_parser = RangedU64ValueParser::<u32>::new().range(0..100),
@@ -105,7 +109,8 @@ pub async fn run(mut self) -> Result<()> {
         } else if let Some(path) = self.check {
             let snap = path.as_ref().unwrap_or(&self.snap);
             let snaps = read_gas_snapshot(snap)?;
-            if check(tests, snaps, self.tolerance) {
+            let tolerances = self.test.load_config()?.gas_snapshot_tolerances;
+            if check(tests, snaps, self.tolerance, &tolerances) {
                 std::process::exit(0)
             } else {
                 std::process::exit(1)
@@ -313,44 +318,71 @@ fn gas_diff(&self) -> f64 {
 
 /// Compares the set of tests with an existing gas snapshot.
 ///
-/// Returns true all tests match
+/// Deviations are grouped by test contract. Returns true if all tests match, within their
+/// tolerance.
 fn check(
     tests: Vec<SuiteTestResult>,
     snaps: Vec<GasSnapshotEntry>,
     tolerance: Option<u32>,
+    tolerances: &[GasSnapshotTolerance],
 ) -> bool {
     let snaps = snaps
         .into_iter()
         .map(|s| ((s.contract_name, s.signature), s.gas_used))
         .collect::<HashMap<_, _>>();
+    let mut sections = BTreeMap::<String, Vec<String>>::new();
     let mut has_diff = false;
     for test in tests {
-        if let Some(target_gas) =
-            snaps.get(&(test.contract_name().to_string(), test.signature.clone())).cloned()
+        let contract = test.contract_name();
+        let message = if let Some(target_gas) =
+            snaps.get(&(contract.to_string(), test.signature.clone())).cloned()
         {
             let source_gas = test.result.kind.report();
-            if !within_tolerance(source_gas.gas(), target_gas.gas(), tolerance) {
-                let _ = sh_println!(
-                    "Diff in \"{}::{}\": consumed \"{}\" gas, expected \"{}\" gas ",
-                    test.contract_name(),
-                    test.signature,
-                    source_gas,
-                    target_gas
-                );
-                has_diff = true;
+            if source_gas.gas() == target_gas.gas() {
+                continue;
             }
+            let tolerated = match find_tolerance(tolerances, contract, &test.signature) {
+                Some(tolerance) => tolerance.allows(source_gas.gas(), target_gas.gas()),
+                None => within_tolerance(source_gas.gas(), target_gas.gas(), tolerance),
+            };
+            has_diff |= !tolerated;
+            format!(
+                "{} in \"{}\": consumed \"{}\" gas, expected \"{}\" gas",
+                if tolerated { "Tolerated diff" } else { "Diff" },
+                test.signature,
+                source_gas,
+                target_gas
+            )
         } else {
-            let _ = sh_println!(
-                "No matching snapshot entry found for \"{}::{}\" in snapshot file",
-                test.contract_name(),
-                test.signature
-            );
             has_diff = true;
+            format!("No matching snapshot entry found for \"{}\" in snapshot file", test.signature)
+        };
+        sections.entry(contract.to_string()).or_default().push(message);
+    }
+
+    for (contract, messages) in sections {
+        let _ = sh_println!("{contract}:");
+        for message in messages {
+            let _ = sh_println!("  {message}");
         }
     }
     !has_diff
 }
 
+/// Returns the most specific configured tolerance for the test `signature` of `contract`.
+///
+/// Later entries take precedence over earlier ones with the same specificity.
+fn find_tolerance<'a>(
+    tolerances: &'a [GasSnapshotTolerance],
+    contract: &str,
+    signature: &str,
+) -> Option<&'a GasSnapshotTolerance> {
+    tolerances
+        .iter()
+        .filter(|tolerance| tolerance.matches(contract, signature))
+        .max_by_key(|tolerance| tolerance.specificity())
+}
+
 /// Compare the set of tests with an existing gas snapshot.
 fn diff(tests: Vec<SuiteTestResult>, snaps: Vec<GasSnapshotEntry>) -> Result<()> {
     let snaps = snaps
@@ -447,6 +479,38 @@ fn test_tolerance() {
         assert!(within_tolerance(100, 100, None));
     }
 
+    #[test]
+    fn test_config_tolerance() {
+        let tolerances = [
+            GasSnapshotTolerance { percent: Some(1.), ..Default::default() },
+            GasSnapshotTolerance {
+                contract: Some("ATest".to_string()),
+                absolute: Some(100),
+                ..Default::default()
+            },
+            GasSnapshotTolerance {
+                contract: Some("ATest".to_string()),
+                test: Some("testB".to_string()),
+                absolute: Some(10),
+                ..Default::default()
+            },
+        ];
+
+        let tolerance = find_tolerance(&tolerances, "ATest", "testA()").unwrap();
+        assert_eq!(tolerance.absolute, Some(100));
+        assert!(tolerance.allows(1000, 1100));
+        assert!(!tolerance.allows(1000, 1101));
+
+        let tolerance = find_tolerance(&tolerances, "ATest", "testB()").unwrap();
+        assert_eq!(tolerance.absolute, Some(10));
+
+        let tolerance = find_tolerance(&tolerances, "BTest", "testB()").unwrap();
+        assert!(tolerance.allows(1000, 1010));
+        assert!(!tolerance.allows(1000, 1011));
+
+        assert!(find_tolerance(&tolerances[1..], "BTest", "testA()").is_none());
+    }
+
     #[test]
     fn can_parse_basic_gas_snapshot_entry() {
         let s = "Test:deposit() (gas: 7222)";
@@ -461,6 +525,20 @@ fn can_parse_basic_gas_snapshot_entry() {
         );
     }
 
+    #[test]
+    fn can_parse_table_case_gas_snapshot_entry() {
+        let s = "Test:tableDeposit(uint256)[2] (gas: 7222)";
+        let entry = GasSnapshotEntry::from_str(s).unwrap();
+        assert_eq!(
+            entry,
+            GasSnapshotEntry {
+                contract_name: "Test".to_string(),
+                signature: "tableDeposit(uint256)[2]".to_string(),
+                gas_used: TestKindReport::Unit { gas: 7222 }
+            }
+        );
+    }
+
     #[test]
     fn can_parse_fuzz_gas_snapshot_entry() {
         let s = "Test:deposit() (runs: 256, μ: 100, ~:200)";
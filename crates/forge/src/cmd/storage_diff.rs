@@ -0,0 +1,724 @@
+//! `forge storage-diff`: compares the storage layouts of two implementations of a contract.
+//!
+//! Variables of the old layout must keep their slot, offset and type in the new layout. New
+//! variables may be appended or take the place of a storage gap (`__gap`) as long as the gap is
+//! shrunk accordingly. Members of ERC-7201 namespaces may only be appended.
+
+use alloy_chains::Chain;
+use alloy_primitives::{Address, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::BlockId;
+use clap::Parser;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Cell, Color, Table};
+use eyre::{OptionExt, Result};
+use foundry_block_explorers::Client;
+use foundry_cli::{
+    opts::{BuildOpts, EtherscanOpts, RpcOpts},
+    utils::{self, LoadConfig},
+};
+use foundry_common::{
+    abi::find_source,
+    compile::{etherscan_project, PathOrContractInfo, ProjectCompiler},
+    ens::NameOrAddress,
+    find_matching_contract_artifact, find_target_path, shell,
+};
+use foundry_compilers::{
+    artifacts::{
+        ast::{Ast, Node, NodeType},
+        output_selection::ContractOutputSelection,
+        Storage, StorageLayout,
+    },
+    Artifact,
+};
+use foundry_config::Config;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+foundry_config::impl_figment_convert!(StorageDiffArgs, build, rpc, etherscan);
+
+/// The NatSpec tag marking a struct as an ERC-7201 namespace.
+const ERC7201_TAG: &str = "@custom:storage-location erc7201:";
+
+/// CLI arguments for `forge storage-diff`.
+#[derive(Clone, Debug, Parser)]
+pub struct StorageDiffArgs {
+    /// The old implementation in the form `(<path>:)?<contractname>`.
+    ///
+    /// If `--against` is set, this is the new implementation instead.
+    #[arg(value_parser = PathOrContractInfo::from_str)]
+    old: PathOrContractInfo,
+
+    /// The new implementation in the form `(<path>:)?<contractname>`.
+    #[arg(value_parser = PathOrContractInfo::from_str, required_unless_present = "against")]
+    new: Option<PathOrContractInfo>,
+
+    /// Compare against the implementation deployed at this address instead of a local contract.
+    ///
+    /// The deployed code is matched against the project's artifacts first, falling back to the
+    /// verified source on Etherscan.
+    #[arg(long, value_name = "ADDRESS", value_parser = NameOrAddress::from_str)]
+    against: Option<NameOrAddress>,
+
+    /// The block height to fetch the deployed code at.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending.
+    #[arg(long, short, requires = "against")]
+    block: Option<BlockId>,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+
+    #[command(flatten)]
+    etherscan: EtherscanOpts,
+
+    #[command(flatten)]
+    build: BuildOpts,
+}
+
+impl StorageDiffArgs {
+    pub async fn run(self) -> Result<()> {
+        let mut config = self.load_config()?;
+        if !config.extra_output.contains(&ContractOutputSelection::StorageLayout) {
+            config.extra_output.push(ContractOutputSelection::StorageLayout);
+        }
+        // ERC-7201 namespaces are read from the Solc AST.
+        config.ast = true;
+
+        let project = config.project()?;
+        let mut output = ProjectCompiler::new().quiet(shell::is_json()).compile(&project)?;
+
+        let root = project.root().to_path_buf();
+        let asts = output
+            .output()
+            .sources
+            .sources_with_version()
+            .filter_map(|(path, file, _)| Some((root.join(path), file.ast.clone()?)))
+            .collect::<Vec<_>>();
+
+        let mut local = |contract: &PathOrContractInfo| -> Result<ContractStorage> {
+            let target_path = find_target_path(&project, contract)?;
+            let artifact =
+                find_matching_contract_artifact(&mut output, &target_path, contract.name())?;
+            let name = match contract.name() {
+                Some(name) => name.to_string(),
+                None => target_path.file_stem().unwrap_or_default().to_string_lossy().into_owned(),
+            };
+            ContractStorage::new(
+                contract_label(contract),
+                artifact.storage_layout,
+                namespaces(&asts, &target_path, &name),
+            )
+        };
+
+        let (old, new) = if let Some(address) = &self.against {
+            let new = local(&self.old)?;
+            let provider = utils::get_provider(&config)?;
+            let address = address.resolve(&provider).await?;
+            let code =
+                provider.get_code_at(address).block_id(self.block.unwrap_or_default()).await?;
+            if code.is_empty() {
+                eyre::bail!("Provided address has no deployed code and thus no storage");
+            }
+
+            // Check if the deployed code belongs to a contract of the project.
+            let deployed = output.artifact_ids().find(|(_, artifact)| {
+                artifact.get_deployed_bytecode_bytes().is_some_and(|b| *b == code)
+            });
+            let old = if let Some((id, artifact)) = deployed {
+                ContractStorage::new(
+                    address.to_string(),
+                    artifact.storage_layout.clone(),
+                    namespaces(&asts, &id.source, &id.name),
+                )?
+            } else {
+                let chain = utils::get_chain(config.chain, &provider).await?;
+                self.etherscan_storage(&config, chain, address).await?
+            };
+            (old, new)
+        } else {
+            let old = local(&self.old)?;
+            (old, local(self.new.as_ref().expect("new contract is required without --against"))?)
+        };
+
+        let changes = diff(&old, &new);
+        let unsafe_changes = changes.iter().filter(|change| change.kind.is_unsafe()).count();
+
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string_pretty(&changes)?)?;
+        } else if changes.is_empty() {
+            sh_println!("No storage layout changes between {} and {}.", old.name, new.name)?;
+        } else {
+            sh_println!(
+                "\nStorage layout changes between {} and {}:\n{}",
+                old.name,
+                new.name,
+                changes_table(&changes)
+            )?;
+        }
+
+        if unsafe_changes > 0 {
+            eyre::bail!("found {unsafe_changes} unsafe storage layout change(s)");
+        }
+
+        Ok(())
+    }
+
+    /// Compiles the verified source of the contract deployed at `address` to get its storage.
+    async fn etherscan_storage(
+        &self,
+        config: &Config,
+        chain: Chain,
+        address: Address,
+    ) -> Result<ContractStorage> {
+        if !self.etherscan.has_key() {
+            eyre::bail!("You must provide an Etherscan API key if the deployed code does not match a contract of the project.");
+        }
+
+        let api_key = config.get_etherscan_api_key(Some(chain)).unwrap_or_default();
+        let client = Client::new(chain, api_key)?;
+        let source = find_source(client, address).await?;
+        let metadata = source.items.first().ok_or_eyre("no verified source found")?;
+        if metadata.is_vyper() {
+            eyre::bail!("Contract at provided address is not a valid Solidity contract")
+        }
+
+        // Metadata from Etherscan won't change, so the sources are cached per address.
+        let root = Config::foundry_etherscan_chain_cache_dir(chain)
+            .ok_or_eyre("could not find the Etherscan cache directory")?
+            .join("storage-diff")
+            .join(address.to_string());
+        let mut project = etherscan_project(metadata, &root)?;
+        project.settings.settings = std::mem::take(&mut project.settings.settings).with_ast();
+        project.artifacts.additional_values.storage_layout = true;
+        project.update_output_selection(|selection| {
+            selection.0.values_mut().for_each(|contract_selection| {
+                contract_selection
+                    .values_mut()
+                    .for_each(|selection| selection.push("storageLayout".to_string()))
+            });
+        });
+
+        let output = ProjectCompiler::new().quiet(true).compile(&project)?;
+        let root = project.root();
+        let asts = output
+            .output()
+            .sources
+            .sources_with_version()
+            .filter_map(|(path, file, _)| Some((root.join(path), file.ast.clone()?)))
+            .collect::<Vec<_>>();
+        let (id, artifact) = output
+            .artifact_ids()
+            .find(|(id, _)| id.name == metadata.contract_name)
+            .ok_or_eyre("Could not find artifact")?;
+
+        ContractStorage::new(
+            address.to_string(),
+            artifact.storage_layout.clone(),
+            namespaces(&asts, &id.source, &id.name),
+        )
+    }
+}
+
+/// The storage of a contract: its layout and its ERC-7201 namespaces.
+#[derive(Clone, Debug)]
+struct ContractStorage {
+    /// The name to display for the contract.
+    name: String,
+    layout: StorageLayout,
+    namespaces: Vec<Namespace>,
+}
+
+impl ContractStorage {
+    fn new(
+        name: String,
+        layout: Option<StorageLayout>,
+        namespaces: Vec<Namespace>,
+    ) -> Result<Self> {
+        let layout = layout.ok_or_else(|| eyre::eyre!("Could not get storage layout of {name}"))?;
+        Ok(Self { name, layout, namespaces })
+    }
+}
+
+/// An ERC-7201 namespace, i.e. a struct annotated with `@custom:storage-location erc7201:<id>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Namespace {
+    id: String,
+    /// The struct members as `(name, type)`, in declaration order.
+    members: Vec<(String, String)>,
+}
+
+impl Namespace {
+    fn from_node(node: &Node) -> Option<Self> {
+        if node.node_type != NodeType::StructDefinition {
+            return None;
+        }
+        let docs = node.other.get("documentation")?.get("text")?.as_str()?;
+        let id = docs.split_once(ERC7201_TAG)?.1.split_whitespace().next()?.to_string();
+        let members = node
+            .other
+            .get("members")?
+            .as_array()?
+            .iter()
+            .filter_map(|member| {
+                let name = member.get("name")?.as_str()?;
+                let ty = member.get("typeDescriptions")?.get("typeString")?.as_str()?;
+                Some((name.to_string(), ty.to_string()))
+            })
+            .collect();
+        Some(Self { id, members })
+    }
+}
+
+/// Returns the ERC-7201 namespaces declared by the contract `name` in `source` and its bases, in
+/// inheritance order.
+fn namespaces(asts: &[(PathBuf, Ast)], source: &Path, name: &str) -> Vec<Namespace> {
+    let mut contracts = HashMap::new();
+    let mut target = None;
+    for (path, ast) in asts {
+        for node in ast.nodes.iter().filter(|n| n.node_type == NodeType::ContractDefinition) {
+            if let Some(id) = node.id {
+                contracts.insert(id, node);
+            }
+            if path == source && node.other.get("name").and_then(Value::as_str) == Some(name) {
+                target = Some(node);
+            }
+        }
+    }
+
+    let Some(bases) =
+        target.and_then(|node| node.other.get("linearizedBaseContracts")).and_then(Value::as_array)
+    else {
+        return Vec::new();
+    };
+    // Bases are linearized from most derived to most base.
+    bases
+        .iter()
+        .rev()
+        .filter_map(|id| contracts.get(&(id.as_u64()? as usize)))
+        .flat_map(|contract| contract.nodes.iter().filter_map(Namespace::from_node))
+        .collect()
+}
+
+/// The kind of a storage layout change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ChangeKind {
+    /// A variable was removed or its storage was taken by another variable.
+    Removed,
+    /// A variable was moved to another slot or offset.
+    Moved,
+    /// The type of a variable changed.
+    TypeChanged,
+    /// A variable was renamed but kept its slot, offset and type.
+    Renamed,
+    /// A storage gap was not resized by the size of the variables added before it.
+    GapResized,
+    /// An ERC-7201 namespace was removed.
+    NamespaceRemoved,
+}
+
+impl ChangeKind {
+    /// Returns whether the change may corrupt the storage of an upgraded proxy.
+    fn is_unsafe(self) -> bool {
+        !matches!(self, Self::Renamed)
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Removed => "removed",
+            Self::Moved => "moved",
+            Self::TypeChanged => "type changed",
+            Self::Renamed => "renamed",
+            Self::GapResized => "gap resized",
+            Self::NamespaceRemoved => "namespace removed",
+        })
+    }
+}
+
+/// A change between the old and the new storage layout.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct StorageChange {
+    kind: ChangeKind,
+    /// The variable, or `erc7201:<id>` for namespaces.
+    variable: String,
+    old: String,
+    new: String,
+}
+
+impl StorageChange {
+    fn new(
+        kind: ChangeKind,
+        variable: &str,
+        old: impl Into<String>,
+        new: impl Into<String>,
+    ) -> Self {
+        Self { kind, variable: variable.to_string(), old: old.into(), new: new.into() }
+    }
+}
+
+/// Compares the old and new storage of a contract.
+fn diff(old: &ContractStorage, new: &ContractStorage) -> Vec<StorageChange> {
+    let (old_layout, new_layout) = (&old.layout, &new.layout);
+    let new_at =
+        new_layout.storage.iter().map(|var| (position(var), var)).collect::<HashMap<_, _>>();
+
+    let mut changes = Vec::new();
+    for var in &old_layout.storage {
+        if is_gap(var) {
+            let resized = new_layout
+                .storage
+                .iter()
+                .find(|new| new.label == var.label && new.contract == var.contract)
+                .filter(|new| gap_end(new_layout, new) != gap_end(old_layout, var));
+            if let Some(new) = resized {
+                changes.push(StorageChange::new(
+                    ChangeKind::GapResized,
+                    &var.label,
+                    format!("ends at slot {}", gap_end(old_layout, var)),
+                    format!("ends at slot {}", gap_end(new_layout, new)),
+                ));
+            }
+            continue;
+        }
+
+        let at_position = new_at.get(&position(var)).filter(|new| !is_gap(new));
+        let by_label = new_layout.storage.iter().find(|new| new.label == var.label);
+        match (at_position, by_label) {
+            (Some(new), _) if new.label == var.label => {
+                if let Some(reason) =
+                    type_mismatch(old_layout, &var.storage_type, new_layout, &new.storage_type)
+                {
+                    changes.push(StorageChange::new(
+                        ChangeKind::TypeChanged,
+                        &var.label,
+                        type_label(old_layout, var),
+                        reason,
+                    ));
+                }
+            }
+            (_, Some(new)) => changes.push(StorageChange::new(
+                ChangeKind::Moved,
+                &var.label,
+                location(var),
+                location(new),
+            )),
+            (Some(new), None) => {
+                if let Some(reason) =
+                    type_mismatch(old_layout, &var.storage_type, new_layout, &new.storage_type)
+                {
+                    changes.push(StorageChange::new(
+                        ChangeKind::TypeChanged,
+                        &var.label,
+                        type_label(old_layout, var),
+                        format!("{reason} ({})", new.label),
+                    ));
+                } else {
+                    changes.push(StorageChange::new(
+                        ChangeKind::Renamed,
+                        &var.label,
+                        var.label.as_str(),
+                        new.label.as_str(),
+                    ));
+                }
+            }
+            (None, None) => changes.push(StorageChange::new(
+                ChangeKind::Removed,
+                &var.label,
+                location(var),
+                "-",
+            )),
+        }
+    }
+
+    for namespace in &old.namespaces {
+        let variable = format!("erc7201:{}", namespace.id);
+        let Some(new_namespace) = new.namespaces.iter().find(|new| new.id == namespace.id) else {
+            changes.push(StorageChange::new(
+                ChangeKind::NamespaceRemoved,
+                &variable,
+                format!("{} member(s)", namespace.members.len()),
+                "-",
+            ));
+            continue;
+        };
+
+        for (index, (name, ty)) in namespace.members.iter().enumerate() {
+            let variable = format!("{variable}.{name}");
+            let new_index = new_namespace.members.iter().position(|(new, _)| new == name);
+            match new_namespace.members.get(index) {
+                Some((new_name, new_ty)) if new_name == name => {
+                    if new_ty != ty {
+                        changes.push(StorageChange::new(
+                            ChangeKind::TypeChanged,
+                            &variable,
+                            ty.as_str(),
+                            new_ty.as_str(),
+                        ));
+                    }
+                }
+                _ if new_index.is_some() => changes.push(StorageChange::new(
+                    ChangeKind::Moved,
+                    &variable,
+                    format!("member {index}"),
+                    format!("member {}", new_index.unwrap()),
+                )),
+                Some((new_name, new_ty)) if new_ty == ty => changes.push(StorageChange::new(
+                    ChangeKind::Renamed,
+                    &variable,
+                    name.as_str(),
+                    new_name.as_str(),
+                )),
+                Some((new_name, new_ty)) => changes.push(StorageChange::new(
+                    ChangeKind::TypeChanged,
+                    &variable,
+                    ty.as_str(),
+                    format!("{new_ty} ({new_name})"),
+                )),
+                None => changes.push(StorageChange::new(
+                    ChangeKind::Removed,
+                    &variable,
+                    format!("member {index}"),
+                    "-",
+                )),
+            }
+        }
+    }
+
+    changes
+}
+
+/// Returns a description of why values of type `old_ty` can't be read as `new_ty`, if they can't.
+///
+/// Type identifiers contain AST IDs, so types are compared by their labels and structure. Structs
+/// may have members appended.
+fn type_mismatch(
+    old_layout: &StorageLayout,
+    old_ty: &str,
+    new_layout: &StorageLayout,
+    new_ty: &str,
+) -> Option<String> {
+    let (Some(old), Some(new)) = (old_layout.types.get(old_ty), new_layout.types.get(new_ty))
+    else {
+        return (old_ty != new_ty).then(|| new_ty.to_string());
+    };
+    if old.label != new.label || old.encoding != new.encoding {
+        return Some(new.label.clone());
+    }
+
+    let nested = |old_ty: Option<&str>, new_ty: Option<&str>| match (old_ty, new_ty) {
+        (Some(old_ty), Some(new_ty)) => type_mismatch(old_layout, old_ty, new_layout, new_ty),
+        _ => None,
+    };
+    if let Some(reason) = nested(old.key.as_deref(), new.key.as_deref())
+        .or_else(|| nested(old.value.as_deref(), new.value.as_deref()))
+        .or_else(|| {
+            nested(
+                old.other.get("base").and_then(Value::as_str),
+                new.other.get("base").and_then(Value::as_str),
+            )
+        })
+    {
+        return Some(format!("{} ({reason})", new.label));
+    }
+
+    let members = |other: &BTreeMap<String, Value>| -> Vec<Storage> {
+        other
+            .get("members")
+            .and_then(|m| serde_json::from_value(m.clone()).ok())
+            .unwrap_or_default()
+    };
+    let new_members = members(&new.other);
+    for old_member in members(&old.other) {
+        let Some(new_member) = new_members.iter().find(|m| m.label == old_member.label) else {
+            return Some(format!("{} (member `{}` removed)", new.label, old_member.label));
+        };
+        if position(new_member) != position(&old_member) {
+            return Some(format!("{} (member `{}` moved)", new.label, old_member.label));
+        }
+        if let Some(reason) = type_mismatch(
+            old_layout,
+            &old_member.storage_type,
+            new_layout,
+            &new_member.storage_type,
+        ) {
+            return Some(format!("{} (member `{}`: {reason})", new.label, old_member.label));
+        }
+    }
+
+    None
+}
+
+fn position(var: &Storage) -> (U256, i64) {
+    (U256::from_str(&var.slot).unwrap_or_default(), var.offset)
+}
+
+fn location(var: &Storage) -> String {
+    if var.offset == 0 {
+        format!("slot {}", var.slot)
+    } else {
+        format!("slot {} (offset {})", var.slot, var.offset)
+    }
+}
+
+fn type_label(layout: &StorageLayout, var: &Storage) -> String {
+    layout
+        .types
+        .get(&var.storage_type)
+        .map_or_else(|| var.storage_type.clone(), |t| t.label.clone())
+}
+
+/// Returns whether the variable is a storage gap reserved for future variables.
+fn is_gap(var: &Storage) -> bool {
+    var.label.starts_with("__gap")
+}
+
+/// Returns the first slot after the gap.
+fn gap_end(layout: &StorageLayout, gap: &Storage) -> U256 {
+    let bytes = layout
+        .types
+        .get(&gap.storage_type)
+        .and_then(|ty| ty.number_of_bytes.parse::<u64>().ok())
+        .unwrap_or_default();
+    position(gap).0 + U256::from(bytes.div_ceil(32))
+}
+
+fn contract_label(contract: &PathOrContractInfo) -> String {
+    match contract {
+        PathOrContractInfo::Path(path) => path.display().to_string(),
+        PathOrContractInfo::ContractInfo(info) => info.to_string(),
+    }
+}
+
+fn changes_table(changes: &[StorageChange]) -> Table {
+    let mut table = Table::new();
+    table.apply_modifier(UTF8_ROUND_CORNERS);
+    table.set_header(vec!["Change", "Variable", "Old", "New"]);
+    for change in changes {
+        let color = if change.kind.is_unsafe() { Color::Red } else { Color::Yellow };
+        table.add_row(vec![
+            Cell::new(change.kind).fg(color),
+            Cell::new(&change.variable),
+            Cell::new(&change.old),
+            Cell::new(&change.new),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage(vars: &[(&str, u64, i64, &str)], namespaces: Vec<Namespace>) -> ContractStorage {
+        let types = vars
+            .iter()
+            .map(|(_, _, _, ty)| {
+                let bytes = match *ty {
+                    "uint128" => 16,
+                    "uint256[50]" => 32 * 50,
+                    "uint256[49]" => 32 * 49,
+                    _ => 32,
+                };
+                let value = serde_json::json!({
+                    "encoding": "inplace",
+                    "label": ty,
+                    "numberOfBytes": bytes.to_string(),
+                });
+                (format!("t_{ty}"), serde_json::from_value(value).unwrap())
+            })
+            .collect();
+        let storage = vars
+            .iter()
+            .map(|(label, slot, offset, ty)| Storage {
+                ast_id: 0,
+                contract: "src/Counter.sol:Counter".to_string(),
+                label: label.to_string(),
+                offset: *offset,
+                slot: slot.to_string(),
+                storage_type: format!("t_{ty}"),
+            })
+            .collect();
+        ContractStorage {
+            name: "Counter".to_string(),
+            layout: StorageLayout { storage, types },
+            namespaces,
+        }
+    }
+
+    fn kinds(changes: &[StorageChange]) -> Vec<(ChangeKind, &str)> {
+        changes.iter().map(|c| (c.kind, c.variable.as_str())).collect()
+    }
+
+    #[test]
+    fn allows_appending_and_consuming_gaps() {
+        let old = storage(&[("a", 0, 0, "uint256"), ("__gap", 1, 0, "uint256[50]")], vec![]);
+        let new = storage(
+            &[("a", 0, 0, "uint256"), ("b", 1, 0, "uint256"), ("__gap", 2, 0, "uint256[49]")],
+            vec![],
+        );
+        assert_eq!(diff(&old, &new), []);
+
+        let new = storage(
+            &[("a", 0, 0, "uint256"), ("b", 1, 0, "uint256"), ("__gap", 2, 0, "uint256[50]")],
+            vec![],
+        );
+        assert_eq!(kinds(&diff(&old, &new)), [(ChangeKind::GapResized, "__gap")]);
+    }
+
+    #[test]
+    fn detects_unsafe_changes() {
+        let old = storage(
+            &[("a", 0, 0, "uint256"), ("b", 1, 0, "uint256"), ("c", 2, 0, "uint256")],
+            vec![],
+        );
+        let new = storage(
+            &[("b", 0, 0, "uint256"), ("a", 1, 0, "uint256"), ("d", 2, 0, "uint128")],
+            vec![],
+        );
+        assert_eq!(
+            kinds(&diff(&old, &new)),
+            [(ChangeKind::Moved, "a"), (ChangeKind::Moved, "b"), (ChangeKind::TypeChanged, "c")]
+        );
+
+        let new = storage(&[("a", 0, 0, "uint256"), ("renamed", 1, 0, "uint256")], vec![]);
+        let changes = diff(&old, &new);
+        assert_eq!(kinds(&changes), [(ChangeKind::Renamed, "b"), (ChangeKind::Removed, "c")]);
+        assert!(!changes[0].kind.is_unsafe());
+    }
+
+    #[test]
+    fn compares_namespaces() {
+        let namespace = |members: &[(&str, &str)]| Namespace {
+            id: "example.main".to_string(),
+            members: members.iter().map(|(n, t)| (n.to_string(), t.to_string())).collect(),
+        };
+        let old = storage(&[], vec![namespace(&[("x", "uint256"), ("y", "address")])]);
+
+        let new =
+            storage(&[], vec![namespace(&[("x", "uint256"), ("y", "address"), ("z", "bool")])]);
+        assert_eq!(diff(&old, &new), []);
+
+        let new = storage(&[], vec![namespace(&[("y", "address"), ("x", "uint128")])]);
+        assert_eq!(
+            kinds(&diff(&old, &new)),
+            [
+                (ChangeKind::Moved, "erc7201:example.main.x"),
+                (ChangeKind::Moved, "erc7201:example.main.y")
+            ]
+        );
+
+        let new = storage(&[], vec![]);
+        assert_eq!(
+            kinds(&diff(&old, &new)),
+            [(ChangeKind::NamespaceRemoved, "erc7201:example.main")]
+        );
+    }
+}
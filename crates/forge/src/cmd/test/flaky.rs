@@ -0,0 +1,158 @@
+//! Detection of flaky tests with `forge test --detect-flaky` and the `quarantine` list.
+
+use crate::{
+    result::{SuiteResult, TestOutcome},
+    MultiContractRunner,
+};
+use foundry_common::{get_contract_name, get_file_name, TestFilter};
+use foundry_evm::fork::CreateFork;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::Path,
+};
+use yansi::Paint;
+
+/// Re-runs every failing test of `results` `reruns` times and records how many of the re-runs
+/// passed.
+///
+/// `fork` is the fork the tests initially ran against, which the runner consumes on every run.
+pub fn detect(
+    runner: &mut MultiContractRunner,
+    fork: Option<CreateFork>,
+    results: &mut BTreeMap<String, SuiteResult>,
+    reruns: u32,
+) {
+    let failed = results
+        .iter()
+        .flat_map(|(suite, result)| result.failures().map(|(sig, _)| (suite.clone(), sig.clone())))
+        .collect::<BTreeSet<_>>();
+    if failed.is_empty() || reruns == 0 {
+        return;
+    }
+
+    let filter = FailedTests(&failed);
+    let mut passed = BTreeMap::<&(String, String), u32>::new();
+    for _ in 0..reruns {
+        runner.fork = fork.clone();
+        for (suite, suite_result) in runner.test_iter(&filter) {
+            for (signature, result) in suite_result.test_results {
+                if let Some(test) = failed.get(&(suite.clone(), signature)) {
+                    if result.status.is_success() {
+                        *passed.entry(test).or_default() += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    for test @ (suite, signature) in &failed {
+        if let Some(result) =
+            results.get_mut(suite).and_then(|suite| suite.test_results.get_mut(signature))
+        {
+            result.reruns = Some((passed.get(test).copied().unwrap_or_default(), reruns));
+        }
+    }
+}
+
+/// Marks the failing tests of `results` which are part of `quarantine`.
+pub fn quarantine(results: &mut BTreeMap<String, SuiteResult>, quarantine: &[String]) {
+    if quarantine.is_empty() {
+        return;
+    }
+    for (suite, suite_result) in results {
+        for (signature, result) in &mut suite_result.test_results {
+            if result.status.is_failure() && is_quarantined(quarantine, suite, signature) {
+                result.quarantined = true;
+            }
+        }
+    }
+}
+
+/// Returns whether the test `signature` of `suite` is part of `quarantine`.
+///
+/// Entries are either `<test>` or `<contract>::<test>`, where `<test>` is the name or the
+/// signature of the test function.
+fn is_quarantined(quarantine: &[String], suite: &str, signature: &str) -> bool {
+    let contract = get_contract_name(suite);
+    let name = signature.split('(').next().unwrap_or(signature);
+    quarantine.iter().any(|entry| {
+        let (entry_contract, test) = match entry.rsplit_once("::") {
+            Some((entry_contract, test)) => (Some(entry_contract), test),
+            None => (None, entry.as_str()),
+        };
+        entry_contract.is_none_or(|c| c == contract) && (test == name || test == signature)
+    })
+}
+
+/// Formats the flaky, consistently failing and quarantined tests of `outcome`, if any.
+pub fn format_report(outcome: &TestOutcome) -> Option<String> {
+    let mut flaky = String::new();
+    let mut failing = String::new();
+    let mut quarantined = String::new();
+    for (suite, suite_result) in &outcome.results {
+        let contract = get_contract_name(suite);
+        for (signature, result) in suite_result.failures() {
+            if result.quarantined {
+                writeln!(quarantined, "  {contract}::{signature}").unwrap();
+            }
+            let Some((passed, reruns)) = result.reruns else { continue };
+            if passed > 0 {
+                writeln!(flaky, "  {contract}::{signature} (passed {passed}/{reruns} re-runs)")
+                    .unwrap();
+            } else {
+                writeln!(failing, "  {contract}::{signature} (failed {reruns}/{reruns} re-runs)")
+                    .unwrap();
+            }
+        }
+    }
+
+    let mut report = String::new();
+    if !flaky.is_empty() {
+        write!(report, "\n{}\n{flaky}", "Flaky tests:".yellow()).unwrap();
+    }
+    if !failing.is_empty() {
+        write!(report, "\n{}\n{failing}", "Consistently failing tests:".red()).unwrap();
+    }
+    if !quarantined.is_empty() {
+        write!(report, "\n{}\n{quarantined}", "Quarantined failing tests (ignored):".yellow())
+            .unwrap();
+    }
+    (!report.is_empty()).then_some(report)
+}
+
+/// Matches the tests which failed, by their suite and signature.
+///
+/// Contracts, paths and signatures are matched independently, so the results of other tests
+/// sharing a contract name or a signature must be discarded.
+struct FailedTests<'a>(&'a BTreeSet<(String, String)>);
+
+impl TestFilter for FailedTests<'_> {
+    fn matches_test(&self, test_name: &str) -> bool {
+        self.0.iter().any(|(_, signature)| signature == test_name)
+    }
+
+    fn matches_contract(&self, contract_name: &str) -> bool {
+        self.0.iter().any(|(suite, _)| get_contract_name(suite) == contract_name)
+    }
+
+    fn matches_path(&self, path: &Path) -> bool {
+        self.0.iter().any(|(suite, _)| Path::new(get_file_name(suite)) == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_quarantine() {
+        let quarantine = vec!["CounterTest::test_flaky".to_string(), "test_slow(uint256)".into()];
+        let suite = "test/Counter.t.sol:CounterTest";
+        assert!(is_quarantined(&quarantine, suite, "test_flaky()"));
+        assert!(!is_quarantined(&quarantine, "test/Other.t.sol:OtherTest", "test_flaky()"));
+        assert!(is_quarantined(&quarantine, "test/Other.t.sol:OtherTest", "test_slow(uint256)"));
+        assert!(!is_quarantined(&quarantine, suite, "test_slow()"));
+        assert!(!is_quarantined(&quarantine, suite, "test_flaky_too()"));
+    }
+}
@@ -1,14 +1,14 @@
 use super::{install, test::filter::ProjectPathsAwareFilter, watch::WatchArgs};
 use crate::{
     decode::decode_console_logs,
-    gas_report::GasReport,
+    gas_report::{GasReport, GasReportFormat},
     multi_runner::matches_contract,
     result::{SuiteResult, TestOutcome, TestStatus},
     traces::{
         debug::{ContractSources, DebugTraceIdentifier},
         decode_trace_arena, folded_stack_trace,
         identifier::SignaturesIdentifier,
-        CallTraceDecoderBuilder, InternalTraceMode, TraceKind,
+        CallTraceDecoder, CallTraceDecoderBuilder, InternalTraceMode, TraceKind,
     },
     MultiContractRunner, MultiContractRunnerBuilder, TestFilter,
 };
@@ -20,7 +20,10 @@
     opts::{BuildOpts, GlobalArgs},
     utils::{self, LoadConfig},
 };
-use foundry_common::{compile::ProjectCompiler, evm::EvmArgs, fs, shell, TestFunctionExt};
+use foundry_common::{
+    artifact_store::ArtifactStore, compile::ProjectCompiler, evm::EvmArgs, fs, shell,
+    TestFunctionExt,
+};
 use foundry_compilers::{
     artifacts::output_selection::OutputSelection,
     compilers::{
@@ -40,23 +43,30 @@
     Config,
 };
 use foundry_debugger::Debugger;
-use foundry_evm::traces::identifier::TraceIdentifiers;
+use foundry_evm::{
+    fork::{CreateFork, ForkSnapshots},
+    opts::EvmOpts,
+    traces::identifier::TraceIdentifiers,
+};
 use regex::Regex;
 use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{mpsc::channel, Arc},
     time::{Duration, Instant},
 };
 use yansi::Paint;
 
 mod filter;
+mod flaky;
+mod shard;
 mod summary;
 use crate::{result::TestKind, traces::render_trace_arena_inner};
 pub use filter::FilterArgs;
 use quick_junit::{NonSuccessKind, Report, TestCase, TestCaseStatus, TestSuite};
-use summary::{format_invariant_metrics_table, TestSummaryReport};
+pub use shard::{Shard, ShardBy, TestTimings};
+use summary::{format_invariant_metrics_table, format_timings_table, TestSummaryReport};
 
 // Loads project's figment and merges the build cli arguments into it
 foundry_config::merge_impl_figment_convert!(TestArgs, build, evm);
@@ -116,7 +126,21 @@ pub struct TestArgs {
 
     /// Print a gas report.
     #[arg(long, env = "FORGE_GAS_REPORT")]
-    gas_report: bool,
+    pub gas_report: bool,
+
+    /// The format of the gas report.
+    ///
+    /// Defaults to `json` if `--json` is passed, `text` otherwise. With `json`, only the report is
+    /// printed.
+    #[arg(long, value_enum, value_name = "FORMAT", requires = "gas_report")]
+    gas_report_format: Option<GasReportFormat>,
+
+    /// Write the gas used by each call path of the gas report to a file, in the folded stack
+    /// format consumed by flamegraph tools.
+    ///
+    /// If the file has an `svg` extension, a flamegraph is rendered instead.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "PATH", requires = "gas_report")]
+    gas_report_call_paths: Option<PathBuf>,
 
     /// Check gas snapshots against previous runs.
     #[arg(long, env = "FORGE_SNAPSHOT_CHECK")]
@@ -126,6 +150,10 @@ pub struct TestArgs {
     #[arg(long, env = "FORGE_SNAPSHOT_EMIT")]
     gas_snapshot_emit: Option<bool>,
 
+    /// Overwrite the golden files of `vm.assertMatchesSnapshot` with the current values.
+    #[arg(long)]
+    update_snapshots: bool,
+
     /// Exit with code 0 even if a test fails.
     #[arg(long, env = "FORGE_ALLOW_FAILURE")]
     allow_failure: bool,
@@ -138,10 +166,22 @@ pub struct TestArgs {
     #[arg(long, conflicts_with_all = ["quiet", "json", "gas_report", "summary", "list", "show_progress"], help_heading = "Display options")]
     pub junit: bool,
 
+    /// Output test results in a format for CI systems.
+    ///
+    /// `junit` prints a JUnit XML report, same as `--junit`. `github` prints the regular output
+    /// followed by a GitHub Actions annotation for every failing test.
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["junit", "json", "quiet"], help_heading = "Display options")]
+    pub format: Option<TestOutputFormat>,
+
     /// Stop running tests after the first failure.
     #[arg(long)]
     pub fail_fast: bool,
 
+    /// Re-run failing tests the given number of times, and report them as flaky if any re-run
+    /// passes or as consistently failing otherwise.
+    #[arg(long, value_name = "RUNS")]
+    pub detect_flaky: Option<u32>,
+
     /// The Etherscan (or equivalent) API key.
     #[arg(long, env = "ETHERSCAN_API_KEY", value_name = "KEY")]
     etherscan_api_key: Option<String>,
@@ -165,6 +205,46 @@ pub struct TestArgs {
     #[arg(long)]
     pub fuzz_input_file: Option<String>,
 
+    /// Enable corpus-guided fuzzing, recording the inputs which increased coverage in the given
+    /// directory and mutating them in later runs.
+    ///
+    /// Defaults to `cache/fuzz/corpus` if no directory is given. The directory can be shared
+    /// between runs and CI machines.
+    #[arg(
+        long,
+        value_name = "DIR",
+        num_args = 0..=1,
+        default_missing_value = "cache/fuzz/corpus"
+    )]
+    pub fuzz_corpus: Option<PathBuf>,
+
+    /// Record the state fetched by fork tests into snapshot files in the given directory, so that
+    /// later runs can replay them offline with `--fork-replay`.
+    ///
+    /// Defaults to `cache/fork` if no directory is given.
+    #[arg(
+        long,
+        value_name = "DIR",
+        num_args = 0..=1,
+        default_missing_value = "cache/fork",
+        conflicts_with = "fork_replay"
+    )]
+    pub fork_record: Option<PathBuf>,
+
+    /// Replay fork tests from the snapshot files recorded with `--fork-record`, without
+    /// connecting to the fork endpoints.
+    ///
+    /// Defaults to `cache/fork` if no directory is given.
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = "cache/fork")]
+    pub fork_replay: Option<PathBuf>,
+
+    /// Replay the invariant failure recorded in the given file instead of running a campaign.
+    ///
+    /// The failure is replayed for the invariant named like the file, e.g.
+    /// `cache/invariant/failures/<Contract>/<invariant>`.
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
     /// Show test execution progress.
     #[arg(long, conflicts_with_all = ["quiet", "json"], help_heading = "Display options")]
     pub show_progress: bool,
@@ -174,6 +254,22 @@ pub struct TestArgs {
     #[arg(long)]
     pub rerun: bool,
 
+    /// Only run the tests of a shard, in the form of `<index>/<count>`, e.g. `2/8`.
+    ///
+    /// Tests are deterministically partitioned across the shards, balanced by the durations
+    /// recorded in `test_timings_file` if any.
+    #[arg(long, value_name = "INDEX/COUNT", conflicts_with_all = ["debug", "flamegraph", "flamechart"])]
+    pub shard: Option<Shard>,
+
+    /// Whether to assign whole test contracts or individual tests to shards.
+    #[arg(long, value_enum, default_value_t, value_name = "UNIT", requires = "shard")]
+    pub shard_by: ShardBy,
+
+    /// Record the durations of the tests of this run in `test_timings_file`, to balance later runs
+    /// with `--shard`.
+    #[arg(long)]
+    pub write_timings: bool,
+
     /// Print test summary table.
     #[arg(long, help_heading = "Display options")]
     pub summary: bool,
@@ -182,6 +278,10 @@ pub struct TestArgs {
     #[arg(long, help_heading = "Display options", requires = "summary")]
     pub detailed: bool,
 
+    /// Print the wall time of each test and the time it spent executing the EVM, slowest first.
+    #[arg(long, help_heading = "Display options", conflicts_with = "json")]
+    pub timings: bool,
+
     #[command(flatten)]
     filter: FilterArgs,
 
@@ -195,6 +295,15 @@ pub struct TestArgs {
     pub watch: WatchArgs,
 }
 
+/// The format of the test results reported with `--format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum TestOutputFormat {
+    /// JUnit XML report.
+    Junit,
+    /// GitHub Actions annotations for failing tests.
+    Github,
+}
+
 impl TestArgs {
     pub async fn run(self) -> Result<TestOutcome> {
         trace!(target: "forge::test", "executing test command");
@@ -278,10 +387,60 @@ pub fn get_sources_to_compile(
     /// configured filter will be executed
     ///
     /// Returns the test results for all matching tests.
-    pub async fn execute_tests(mut self) -> Result<TestOutcome> {
+    pub async fn execute_tests(self) -> Result<TestOutcome> {
         // Merge all configs.
-        let (mut config, mut evm_opts) = self.load_config_and_evm_opts()?;
+        let (mut config, evm_opts) = self.load_config_and_evm_opts()?;
 
+        // Install missing dependencies.
+        if install::install_missing_dependencies(&mut config) && config.auto_detect_remappings {
+            // need to re-configure here to also catch additional remappings
+            config = self.load_config()?;
+        }
+
+        if !config.workspace.is_empty() {
+            return self.execute_workspace_tests(config, evm_opts).await;
+        }
+
+        self.execute_tests_with(config, evm_opts).await
+    }
+
+    /// Executes the tests of every member of the workspace, see [`Config::workspace`].
+    ///
+    /// Returns the merged test results of all members.
+    async fn execute_workspace_tests(
+        self,
+        config: Config,
+        evm_opts: EvmOpts,
+    ) -> Result<TestOutcome> {
+        let members = config.workspace.resolve_members(&config.root);
+        if members.is_empty() {
+            bail!("No workspace members found matching {:?}", config.workspace.members);
+        }
+
+        let mut outcome = TestOutcome::empty(self.allow_failure);
+        for member in members {
+            if !shell::is_json() && !self.is_junit() {
+                sh_println!("Testing workspace member {}...", member.name.bold())?;
+            }
+            let member_config = config.for_workspace_member(self.figment(), &member)?;
+            let member_outcome =
+                self.clone().execute_tests_with(member_config, evm_opts.clone()).await?;
+            outcome.results.extend(member_outcome.results);
+            outcome.last_run_decoder = member_outcome.last_run_decoder.or(outcome.last_run_decoder);
+            outcome.gas_report = match (outcome.gas_report.take(), member_outcome.gas_report) {
+                (Some(report), Some(member_report)) => Some(report.merge(member_report)),
+                (report, member_report) => report.or(member_report),
+            };
+        }
+        Ok(outcome)
+    }
+
+    /// Executes all the tests of the project described by `config`.
+    async fn execute_tests_with(
+        mut self,
+        mut config: Config,
+        mut evm_opts: EvmOpts,
+    ) -> Result<TestOutcome> {
         // Explicitly enable isolation for gas reports for more correct gas accounting.
         if self.gas_report {
             evm_opts.isolate = true;
@@ -291,13 +450,8 @@ pub async fn execute_tests(mut self) -> Result<TestOutcome> {
             config.invariant.gas_report_samples = 0;
         }
 
-        // Install missing dependencies.
-        if install::install_missing_dependencies(&mut config) && config.auto_detect_remappings {
-            // need to re-configure here to also catch additional remappings
-            config = self.load_config()?;
-        }
-
         // Set up the project.
+        crate::yul::write_deployer(&config)?;
         let project = config.project()?;
 
         let filter = self.filter(&config)?;
@@ -305,8 +459,10 @@ pub async fn execute_tests(mut self) -> Result<TestOutcome> {
 
         let sources_to_compile = self.get_sources_to_compile(&config, &filter)?;
 
-        let compiler =
-            ProjectCompiler::new().quiet(shell::is_json() || self.junit).files(sources_to_compile);
+        let compiler = ProjectCompiler::new()
+            .quiet(shell::is_json() || self.is_junit())
+            .files(sources_to_compile)
+            .artifact_store(ArtifactStore::from_config(&config)?);
 
         let output = compiler.compile(&project)?;
 
@@ -322,6 +478,16 @@ pub async fn execute_tests(mut self) -> Result<TestOutcome> {
             evm_opts.verbosity = 3;
         }
 
+        // Record or replay the forks from local snapshots.
+        if let Some(dir) = &self.fork_record {
+            let dir = config.root.join(dir);
+            fs::create_dir_all(&dir)?;
+            evm_opts.fork_snapshots = Some(ForkSnapshots { dir, replay: false });
+        } else if let Some(dir) = &self.fork_replay {
+            evm_opts.fork_snapshots =
+                Some(ForkSnapshots { dir: config.root.join(dir), replay: true });
+        }
+
         let env = evm_opts.evm_env().await?;
 
         // Enable internal tracing for more informative flamegraph.
@@ -435,6 +601,12 @@ pub async fn run_tests(
         filter: &ProjectPathsAwareFilter,
         output: &ProjectCompileOutput,
     ) -> eyre::Result<TestOutcome> {
+        let timings_file = config.root.join(&config.test_timings_file);
+        if let Some(shard) = self.shard {
+            let timings = TestTimings::load(&timings_file);
+            shard::apply(&mut runner, filter, shard, self.shard_by, &timings);
+        }
+
         if self.list {
             return list(runner, filter);
         }
@@ -442,7 +614,7 @@ pub async fn run_tests(
         trace!(target: "forge::test", "running all tests");
 
         // If we need to render to a serialized format, we should not print anything else to stdout.
-        let silent = self.gas_report && shell::is_json() || self.summary && shell::is_json();
+        let silent = self.is_gas_report_json() || self.summary && shell::is_json();
 
         let num_filtered = runner.matching_test_functions(filter).count();
         if num_filtered != 1 && (self.debug || self.flamegraph || self.flamechart) {
@@ -471,7 +643,9 @@ pub async fn run_tests(
 
         // Run tests in a non-streaming fashion and collect results for serialization.
         if !self.gas_report && !self.summary && shell::is_json() {
+            let fork = runner.fork.clone();
             let mut results = runner.test_collect(filter);
+            self.check_failures(&mut runner, fork, &mut results, &config);
             results.values_mut().for_each(|suite_result| {
                 for test_result in suite_result.test_results.values_mut() {
                     if verbosity >= 2 {
@@ -484,13 +658,23 @@ pub async fn run_tests(
                 }
             });
             sh_println!("{}", serde_json::to_string(&results)?)?;
-            return Ok(TestOutcome::new(results, self.allow_failure));
+            let outcome = TestOutcome::new(results, self.allow_failure);
+            if self.write_timings {
+                persist_timings(&timings_file, &outcome);
+            }
+            return Ok(outcome);
         }
 
-        if self.junit {
-            let results = runner.test_collect(filter);
+        if self.is_junit() {
+            let fork = runner.fork.clone();
+            let mut results = runner.test_collect(filter);
+            self.check_failures(&mut runner, fork, &mut results, &config);
             sh_println!("{}", junit_xml_report(&results, verbosity).to_string()?)?;
-            return Ok(TestOutcome::new(results, self.allow_failure));
+            let outcome = TestOutcome::new(results, self.allow_failure);
+            if self.write_timings {
+                persist_timings(&timings_file, &outcome);
+            }
+            return Ok(outcome);
         }
 
         let remote_chain_id = runner.evm_opts.get_remote_chain_id().await;
@@ -502,10 +686,15 @@ pub async fn run_tests(
         let (tx, rx) = channel::<(String, SuiteResult)>();
         let timer = Instant::now();
         let show_progress = config.show_progress;
+        let fork = runner.fork.clone();
         let handle = tokio::task::spawn_blocking({
             let filter = filter.clone();
-            move || runner.test(&filter, tx, show_progress)
+            move || {
+                runner.test(&filter, tx, show_progress);
+                runner
+            }
         });
+        let mut handle = Some(handle);
 
         // Set up trace identifiers.
         let mut identifier = TraceIdentifiers::new().with_local(&known_contracts);
@@ -536,11 +725,15 @@ pub async fn run_tests(
         let mut decoder = builder.build();
 
         let mut gas_report = self.gas_report.then(|| {
-            GasReport::new(
+            let report = GasReport::new(
                 config.gas_reports.clone(),
                 config.gas_reports_ignore.clone(),
                 config.gas_reports_include_tests,
-            )
+            );
+            match self.gas_report_format {
+                Some(format) => report.with_format(format),
+                None => report,
+            }
         });
 
         let mut gas_snapshots = BTreeMap::<String, BTreeMap<String, String>>::new();
@@ -548,7 +741,12 @@ pub async fn run_tests(
         let mut outcome = TestOutcome::empty(self.allow_failure);
 
         let mut any_test_failed = false;
-        for (contract_name, suite_result) in rx {
+        for (contract_name, mut suite_result) in rx {
+            // Signatures are only looked up when not generating a gas report.
+            if !self.gas_report {
+                decode_unknown_errors(&mut decoder, &mut suite_result).await;
+            }
+
             let tests = &suite_result.test_results;
 
             // Clear the addresses and labels from previous test.
@@ -782,15 +980,25 @@ pub async fn run_tests(
         outcome.last_run_decoder = Some(decoder);
         let duration = timer.elapsed();
 
+        if self.detect_flaky.is_some() && outcome.failed() > 0 {
+            let mut runner = reattach(handle.take().unwrap()).await?;
+            self.check_failures(&mut runner, fork, &mut outcome.results, &config);
+        } else {
+            flaky::quarantine(&mut outcome.results, &config.quarantine);
+        }
+
         trace!(target: "forge::test", len=outcome.results.len(), %any_test_failed, "done with results");
 
         if let Some(gas_report) = gas_report {
             let finalized = gas_report.finalize();
             sh_println!("{}", &finalized)?;
+            if let Some(path) = &self.gas_report_call_paths {
+                finalized.write_call_paths(path)?;
+            }
             outcome.gas_report = Some(finalized);
         }
 
-        if !self.summary && !shell::is_json() {
+        if !self.summary && !silent && !shell::is_json() {
             sh_println!("{}", outcome.summary(duration))?;
         }
 
@@ -799,20 +1007,59 @@ pub async fn run_tests(
             sh_println!("{}", &summary_report)?;
         }
 
-        // Reattach the task.
-        if let Err(e) = handle.await {
-            match e.try_into_panic() {
-                Ok(payload) => std::panic::resume_unwind(payload),
-                Err(e) => return Err(e.into()),
+        if self.timings && !outcome.results.is_empty() {
+            sh_println!("\n{}", format_timings_table(&outcome))?;
+        }
+
+        if let Some(report) = flaky::format_report(&outcome) {
+            sh_println!("{report}")?;
+        }
+
+        if self.format == Some(TestOutputFormat::Github) {
+            for annotation in github_annotations(&outcome.results, &config.root) {
+                sh_println!("{annotation}")?;
             }
         }
 
+        // Reattach the task.
+        if let Some(handle) = handle {
+            reattach(handle).await?;
+        }
+
         // Persist test run failures to enable replaying.
         persist_run_failures(&config, &outcome);
+        if self.write_timings {
+            persist_timings(&timings_file, &outcome);
+        }
 
         Ok(outcome)
     }
 
+    /// Re-runs the failing tests if detecting flaky tests, and marks the quarantined ones.
+    fn check_failures(
+        &self,
+        runner: &mut MultiContractRunner,
+        fork: Option<CreateFork>,
+        results: &mut BTreeMap<String, SuiteResult>,
+        config: &Config,
+    ) {
+        if let Some(reruns) = self.detect_flaky {
+            flaky::detect(runner, fork, results, reruns);
+        }
+        flaky::quarantine(results, &config.quarantine);
+    }
+
+    /// Returns whether the gas report is printed as JSON.
+    fn is_gas_report_json(&self) -> bool {
+        self.gas_report &&
+            self.gas_report_format.map_or_else(shell::is_json, |f| f == GasReportFormat::Json)
+    }
+
+    /// Returns whether test results are reported as JUnit XML.
+    fn is_junit(&self) -> bool {
+        self.junit || self.format == Some(TestOutputFormat::Junit)
+    }
+
     /// Returns the flattened [`FilterArgs`] arguments merged with [`Config`].
     /// Loads and applies filter from file if only last test run failures performed.
     pub fn filter(&self, config: &Config) -> Result<ProjectPathsAwareFilter> {
@@ -865,8 +1112,17 @@ fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
         if let Some(fuzz_input_file) = self.fuzz_input_file.clone() {
             fuzz_dict.insert("failure_persist_file".to_string(), fuzz_input_file.into());
         }
+        if let Some(fuzz_corpus) = &self.fuzz_corpus {
+            fuzz_dict.insert("corpus_dir".to_string(), fuzz_corpus.to_string_lossy().into());
+        }
         dict.insert("fuzz".to_string(), fuzz_dict.into());
 
+        if let Some(replay) = &self.replay {
+            let mut invariant_dict = Dict::default();
+            invariant_dict.insert("replay_file".to_string(), replay.to_string_lossy().into());
+            dict.insert("invariant".to_string(), invariant_dict.into());
+        }
+
         if let Some(etherscan_api_key) =
             self.etherscan_api_key.as_ref().filter(|s| !s.trim().is_empty())
         {
@@ -877,6 +1133,10 @@ fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
             dict.insert("show_progress".to_string(), true.into());
         }
 
+        if self.update_snapshots {
+            dict.insert("update_snapshots".to_string(), true.into());
+        }
+
         Ok(Map::from([(Config::selected_profile(), dict)]))
     }
 }
@@ -926,6 +1186,55 @@ fn persist_run_failures(config: &Config, outcome: &TestOutcome) {
     }
 }
 
+/// Looks up the custom errors which are not part of the project in the reverts of the failed
+/// tests of `suite`, so that they are decoded in the failure reasons and in the traces.
+async fn decode_unknown_errors(decoder: &mut CallTraceDecoder, suite: &mut SuiteResult) {
+    let revert_data =
+        suite.test_results.values().filter(|result| result.status.is_failure()).flat_map(
+            |result| {
+                let reverts = result
+                    .traces
+                    .iter()
+                    .flat_map(|(_, arena)| arena.nodes())
+                    .filter(|node| !node.trace.success)
+                    .map(|node| &node.trace.output[..]);
+                result.revert_data.as_deref().into_iter().chain(reverts)
+            },
+        );
+    decoder.identify_errors(revert_data).await;
+
+    for result in suite.test_results.values_mut() {
+        let Some(data) = &result.revert_data else { continue };
+        // Only replace the reasons of errors which the runner couldn't decode.
+        if result.reason.as_deref().is_some_and(|reason| reason.starts_with("custom error ")) {
+            if let Some(reason) = decoder.revert_decoder.maybe_decode(data, None) {
+                result.reason = Some(reason);
+            }
+        }
+    }
+}
+
+/// Waits for the test run `handle` to finish, resuming a panic of the runner.
+async fn reattach<T>(handle: tokio::task::JoinHandle<T>) -> Result<T> {
+    match handle.await {
+        Ok(value) => Ok(value),
+        Err(e) => match e.try_into_panic() {
+            Ok(payload) => std::panic::resume_unwind(payload),
+            Err(e) => Err(e.into()),
+        },
+    }
+}
+
+/// Records the durations of the tests of the run, to balance the shards of later runs.
+fn persist_timings(path: &Path, outcome: &TestOutcome) {
+    if outcome.results.is_empty() {
+        return;
+    }
+    let mut timings = TestTimings::load(path);
+    timings.update(outcome);
+    let _ = timings.save(path);
+}
+
 /// Generate test report in JUnit XML report format.
 fn junit_xml_report(results: &BTreeMap<String, SuiteResult>, verbosity: u8) -> Report {
     let mut total_duration = Duration::default();
@@ -969,6 +1278,56 @@ fn junit_xml_report(results: &BTreeMap<String, SuiteResult>, verbosity: u8) -> R
     junit_report
 }
 
+/// Generate a GitHub Actions `error` annotation for every failing test.
+///
+/// Annotations point at the declaration of the failing test function, found by scanning the
+/// test contract's source file. If the source can't be read, the annotation is attached to the
+/// file only.
+fn github_annotations(results: &BTreeMap<String, SuiteResult>, root: &Path) -> Vec<String> {
+    let mut annotations = Vec::new();
+    for (suite_name, suite_result) in results {
+        let (file, contract) = suite_name.rsplit_once(':').unwrap_or((suite_name, ""));
+        let source = std::fs::read_to_string(root.join(file)).ok();
+        for (signature, test_result) in suite_result.failures() {
+            let name = signature.split('(').next().unwrap_or(signature);
+            let mut properties = format!("file={}", escape_annotation_property(file));
+            if let Some(line) = source.as_deref().and_then(|s| function_line(s, contract, name)) {
+                write!(properties, ",line={line}").unwrap();
+            }
+            let title = format!("{contract}::{signature}");
+            write!(properties, ",title={}", escape_annotation_property(&title)).unwrap();
+            let message = match &test_result.reason {
+                Some(reason) => format!("{title} failed: {reason}"),
+                None => format!("{title} failed"),
+            };
+            annotations.push(format!("::error {properties}::{}", escape_annotation_data(&message)));
+        }
+    }
+    annotations
+}
+
+/// Returns the 1-based line of the declaration of `function` in `contract` within `source`.
+///
+/// Falls back to the first declaration of `function` in the file if `contract` isn't found.
+fn function_line(source: &str, contract: &str, function: &str) -> Option<usize> {
+    let contract_re = Regex::new(&format!(r"\bcontract\s+{}\b", regex::escape(contract))).ok()?;
+    let function_re =
+        Regex::new(&format!(r"\bfunction\s+{}\s*\(", regex::escape(function))).ok()?;
+    let start = contract_re.find(source).map_or(0, |m| m.end());
+    let offset = function_re.find_at(source, start).or_else(|| function_re.find(source))?.start();
+    Some(source[..offset].matches('\n').count() + 1)
+}
+
+/// Escapes the message of a GitHub Actions workflow command.
+fn escape_annotation_data(data: &str) -> String {
+    data.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a property value of a GitHub Actions workflow command.
+fn escape_annotation_property(value: &str) -> String {
+    escape_annotation_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -994,6 +1353,27 @@ fn fuzz_seed_exists() {
         assert!(args.fuzz_seed.is_some());
     }
 
+    #[test]
+    fn fuzz_corpus() {
+        let args = TestArgs::parse_from(["foundry-cli", "--fuzz-corpus"]);
+        assert_eq!(args.fuzz_corpus, Some(PathBuf::from("cache/fuzz/corpus")));
+        let args = TestArgs::parse_from(["foundry-cli", "--fuzz-corpus", "corpus"]);
+        assert_eq!(args.fuzz_corpus, Some(PathBuf::from("corpus")));
+        let args = TestArgs::parse_from(["foundry-cli"]);
+        assert_eq!(args.fuzz_corpus, None);
+    }
+
+    #[test]
+    fn fork_record_replay() {
+        let args = TestArgs::parse_from(["foundry-cli", "--fork-record"]);
+        assert_eq!(args.fork_record, Some(PathBuf::from("cache/fork")));
+        let args = TestArgs::parse_from(["foundry-cli", "--fork-replay", "snapshots"]);
+        assert_eq!(args.fork_replay, Some(PathBuf::from("snapshots")));
+        assert!(
+            TestArgs::try_parse_from(["foundry-cli", "--fork-record", "--fork-replay"]).is_err()
+        );
+    }
+
     #[test]
     fn extract_chain() {
         let test = |arg: &str, expected: Chain| {
@@ -1006,4 +1386,32 @@ fn extract_chain() {
         test("--chain-id=1", Chain::mainnet());
         test("--chain-id=42", Chain::from_id(42));
     }
+
+    #[test]
+    fn output_format() {
+        let args = TestArgs::parse_from(["foundry-cli", "--format", "github"]);
+        assert_eq!(args.format, Some(TestOutputFormat::Github));
+        assert!(!args.is_junit());
+        let args = TestArgs::parse_from(["foundry-cli", "--format", "junit"]);
+        assert!(args.is_junit());
+        assert!(TestArgs::try_parse_from(["foundry-cli", "--format", "junit", "--junit"]).is_err());
+    }
+
+    #[test]
+    fn github_annotation_location() {
+        let source = "contract A {\n    function testFoo() public {}\n}\n\ncontract B {\n    function testFoo() public {}\n}\n";
+        assert_eq!(function_line(source, "A", "testFoo"), Some(2));
+        assert_eq!(function_line(source, "B", "testFoo"), Some(6));
+        assert_eq!(function_line(source, "C", "testFoo"), Some(2));
+        assert_eq!(function_line(source, "A", "testBar"), None);
+    }
+
+    #[test]
+    fn github_annotation_escape() {
+        assert_eq!(escape_annotation_data("50%\r\nfailed: a, b"), "50%25%0D%0Afailed: a, b");
+        assert_eq!(
+            escape_annotation_property("A::test(uint256,bool)"),
+            "A%3A%3Atest(uint256%2Cbool)"
+        );
+    }
 }
@@ -0,0 +1,234 @@
+//! Partitioning of the tests across machines with `forge test --shard`.
+
+use crate::{
+    multi_runner::is_matching_test,
+    result::{TestOutcome, TestStatus},
+    MultiContractRunner,
+};
+use alloy_primitives::keccak256;
+use eyre::Result;
+use foundry_common::{fs, TestFilter};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::Path,
+    str::FromStr,
+};
+
+/// A shard of the tests, in the form of `<index>/<count>` with a 1-based index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shard {
+    pub index: usize,
+    pub count: usize,
+}
+
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, count) = s
+            .split_once('/')
+            .ok_or_else(|| format!("invalid shard `{s}`, expected `<index>/<count>`"))?;
+        let index = index.trim().parse().map_err(|_| format!("invalid shard index `{index}`"))?;
+        let count = count.trim().parse().map_err(|_| format!("invalid shard count `{count}`"))?;
+        if count == 0 || index == 0 || index > count {
+            return Err(format!("shard index must be between 1 and the shard count, got `{s}`"));
+        }
+        Ok(Self { index, count })
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+/// The unit of tests assigned to a shard.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ShardBy {
+    /// Assign whole test contracts to shards.
+    #[default]
+    Contract,
+    /// Assign individual test functions to shards.
+    Test,
+}
+
+/// The durations of the tests of a previous run, in microseconds, by test suite and test
+/// signature.
+///
+/// They are only recorded with `forge test --write-timings`, and meant to be committed so that
+/// every machine balances the shards with the same durations.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TestTimings(BTreeMap<String, BTreeMap<String, u64>>);
+
+impl TestTimings {
+    /// Reads the timings at `path`, or returns empty timings if there are none.
+    pub fn load(path: &Path) -> Self {
+        fs::read_json_file(path).unwrap_or_default()
+    }
+
+    /// Writes the timings to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write_json_file(path, self)?;
+        Ok(())
+    }
+
+    /// Records the durations of the executed tests of `outcome`, keeping the timings of the tests
+    /// which did not run, e.g. because they are part of another shard.
+    pub fn update(&mut self, outcome: &TestOutcome) {
+        for (suite, result) in &outcome.results {
+            let tests = self.0.entry(suite.clone()).or_default();
+            for (signature, test) in &result.test_results {
+                if test.status != TestStatus::Skipped {
+                    tests.insert(signature.clone(), test.duration.as_micros() as u64);
+                }
+            }
+        }
+    }
+
+    fn get(&self, suite: &str, signature: &str) -> Option<u64> {
+        self.0.get(suite)?.get(signature).copied()
+    }
+}
+
+/// Removes the tests which are not part of `shard` from the runner.
+///
+/// The tests matching `filter` are split by contract or by test function, and assigned to the
+/// shards from the longest to the shortest according to `timings`, each to the shard with the
+/// lowest total duration so far. Tests without timings are weighted with the average duration, and
+/// units of equal duration are ordered by a stable hash of their name, so without timings the
+/// units are spread evenly. The partition only depends on the tests and the timings, so every
+/// machine computes the same one.
+pub fn apply(
+    runner: &mut MultiContractRunner,
+    filter: &dyn TestFilter,
+    shard: Shard,
+    by: ShardBy,
+    timings: &TestTimings,
+) {
+    // `suite` or `suite::signature` -> duration
+    let mut units = BTreeMap::<String, Option<u64>>::new();
+    for (id, contract) in runner.matching_contracts(filter) {
+        let suite = id.identifier();
+        let tests = contract.abi.functions().filter(|func| is_matching_test(func, filter));
+        match by {
+            ShardBy::Contract => {
+                let durations =
+                    tests.map(|func| timings.get(&suite, &func.signature())).collect::<Vec<_>>();
+                let duration = durations.iter().any(Option::is_some).then(|| {
+                    // tests added since the last run weigh as much as the average known test
+                    let known = durations.iter().flatten().copied().collect::<Vec<_>>();
+                    let average = known.iter().sum::<u64>() / known.len() as u64;
+                    known.iter().sum::<u64>() + average * (durations.len() - known.len()) as u64
+                });
+                units.insert(unit(&suite, None), duration);
+            }
+            ShardBy::Test => {
+                for func in tests {
+                    let signature = func.signature();
+                    let duration = timings.get(&suite, &signature);
+                    units.insert(unit(&suite, Some(&signature)), duration);
+                }
+            }
+        }
+    }
+
+    let selected = partition(units, shard);
+    match by {
+        ShardBy::Contract => {
+            runner.contracts.retain(|id, _| selected.contains(&unit(&id.identifier(), None)));
+        }
+        ShardBy::Test => {
+            for (id, contract) in &mut runner.contracts {
+                let suite = id.identifier();
+                for functions in contract.abi.functions.values_mut() {
+                    functions.retain(|func| {
+                        !is_matching_test(func, filter) ||
+                            selected.contains(&unit(&suite, Some(&func.signature())))
+                    });
+                }
+                contract.abi.functions.retain(|_, functions| !functions.is_empty());
+            }
+        }
+    }
+}
+
+/// Returns the name of a test suite, or of one of its tests.
+fn unit(suite: &str, signature: Option<&str>) -> String {
+    match signature {
+        Some(signature) => format!("{suite}::{signature}"),
+        None => suite.to_string(),
+    }
+}
+
+/// Returns the units assigned to `shard`.
+fn partition<T: Ord + AsRef<str>>(units: BTreeMap<T, Option<u64>>, shard: Shard) -> BTreeSet<T> {
+    let known = units.values().flatten().copied().collect::<Vec<_>>();
+    let average = if known.is_empty() { 1 } else { known.iter().sum::<u64>() / known.len() as u64 };
+
+    // longest first, ties broken by the hash of the unit so that neighbouring units, e.g. the tests
+    // of a contract, don't all land on the same shards
+    let mut units = units
+        .into_iter()
+        .map(|(unit, duration)| {
+            (duration.unwrap_or(average).max(1), keccak256(unit.as_ref().as_bytes()), unit)
+        })
+        .collect::<Vec<_>>();
+    units.sort_by(|(a_duration, a_hash, a), (b_duration, b_hash, b)| {
+        b_duration.cmp(a_duration).then(a_hash.cmp(b_hash)).then(a.cmp(b))
+    });
+
+    let mut loads = vec![0u64; shard.count];
+    let mut selected = BTreeSet::new();
+    for (duration, _, unit) in units {
+        let (lightest, _) =
+            loads.iter().enumerate().min_by_key(|(i, load)| (**load, *i)).expect("count > 0");
+        loads[lightest] += duration;
+        if lightest == shard.index - 1 {
+            selected.insert(unit);
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_shards() {
+        assert_eq!("2/8".parse::<Shard>(), Ok(Shard { index: 2, count: 8 }));
+        assert!("0/8".parse::<Shard>().is_err());
+        assert!("9/8".parse::<Shard>().is_err());
+        assert!("1/0".parse::<Shard>().is_err());
+        assert!("2".parse::<Shard>().is_err());
+    }
+
+    #[test]
+    fn partitions_by_duration() {
+        let units = BTreeMap::from([("a", Some(10)), ("b", Some(6)), ("c", Some(5)), ("d", None)]);
+        let shards = (1..=2)
+            .map(|index| partition(units.clone(), Shard { index, count: 2 }))
+            .collect::<Vec<_>>();
+        // d weighs the average of 7: a (10), c (5) | d (7), b (6)
+        assert_eq!(shards[0], BTreeSet::from(["a", "c"]));
+        assert_eq!(shards[1], BTreeSet::from(["b", "d"]));
+    }
+
+    #[test]
+    fn partitions_every_unit_once() {
+        let units = (0..25).map(|i| (i.to_string(), None)).collect::<BTreeMap<_, _>>();
+        let shards = (1..=4)
+            .map(|index| partition(units.clone(), Shard { index, count: 4 }))
+            .collect::<Vec<_>>();
+        assert_eq!(shards.iter().map(BTreeSet::len).sum::<usize>(), 25);
+        assert!(shards.iter().all(|shard| (6..=7).contains(&shard.len())));
+        assert_eq!(shards.iter().flatten().collect::<BTreeSet<_>>().len(), 25);
+    }
+}
@@ -189,11 +189,64 @@ pub(crate) fn format_invariant_metrics_table(
     table
 }
 
+/// Helper function to create the test timings table, slowest tests first.
+///
+/// ╭------------------------------+-----------+----------+-------╮
+/// | Test                         | Wall Time | EVM Time | EVM % |
+/// +=============================================================+
+/// | CounterTest::invariant_sum() | 1.20s     | 1.02s    | 85.0% |
+/// |------------------------------+-----------+----------+-------|
+/// | CounterTest::test_inc()      | 3.10ms    | 1.55ms   | 50.0% |
+/// ╰------------------------------+-----------+----------+-------╯
+pub(crate) fn format_timings_table(outcome: &TestOutcome) -> Table {
+    let mut table = Table::new();
+    table.apply_modifier(UTF8_ROUND_CORNERS);
+
+    table.set_header(vec![
+        Cell::new("Test"),
+        Cell::new("Wall Time").fg(Color::Cyan),
+        Cell::new("EVM Time").fg(Color::Cyan),
+        Cell::new("EVM %").fg(Color::Cyan),
+    ]);
+
+    let tests = outcome
+        .results
+        .iter()
+        .flat_map(|(contract, suite)| {
+            let name = contract.split_once(':').map_or(contract.as_str(), |(_, name)| name);
+            suite.test_results.iter().map(move |(test, result)| (name, test, result))
+        })
+        .sorted_by(|(_, _, a), (_, _, b)| b.duration.cmp(&a.duration));
+    for (contract, test, result) in tests {
+        let evm_percent = if result.duration.is_zero() {
+            0.0
+        } else {
+            result.evm_duration.as_secs_f64() / result.duration.as_secs_f64() * 100.0
+        };
+        table.add_row(vec![
+            Cell::new(format!("{contract}::{test}")),
+            Cell::new(format!("{:.2?}", result.duration)),
+            Cell::new(format!("{:.2?}", result.evm_duration)),
+            Cell::new(format!("{evm_percent:.1}%")),
+        ]);
+    }
+    table
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::cmd::test::summary::format_invariant_metrics_table;
+    use crate::{
+        cmd::test::{
+            summary::{format_invariant_metrics_table, format_timings_table},
+            TestOutcome,
+        },
+        result::{SuiteResult, TestResult},
+    };
     use foundry_evm::executors::invariant::InvariantMetrics;
-    use std::collections::HashMap;
+    use std::{
+        collections::{BTreeMap, HashMap},
+        time::Duration,
+    };
 
     #[test]
     fn test_invariant_metrics_table() {
@@ -223,4 +276,38 @@ fn test_invariant_metrics_table() {
         assert_eq!(second_row_content.next().unwrap().content(), "2");
         assert_eq!(second_row_content.next().unwrap().content(), "2");
     }
+
+    #[test]
+    fn test_timings_table() {
+        let result = |duration, evm_duration| TestResult {
+            duration: Duration::from_millis(duration),
+            evm_duration: Duration::from_millis(evm_duration),
+            ..Default::default()
+        };
+        let test_results = BTreeMap::from([
+            ("test_fast()".to_string(), result(10, 5)),
+            ("test_slow()".to_string(), result(100, 80)),
+        ]);
+        let outcome = TestOutcome::new(
+            BTreeMap::from([(
+                "test/Counter.t.sol:CounterTest".to_string(),
+                SuiteResult::new(Duration::from_millis(110), test_results, vec![]),
+            )]),
+            false,
+        );
+        let table = format_timings_table(&outcome);
+        assert_eq!(table.row_count(), 2);
+
+        let mut first_row_content = table.row(0).unwrap().cell_iter();
+        assert_eq!(first_row_content.next().unwrap().content(), "CounterTest::test_slow()");
+        assert_eq!(first_row_content.next().unwrap().content(), "100.00ms");
+        assert_eq!(first_row_content.next().unwrap().content(), "80.00ms");
+        assert_eq!(first_row_content.next().unwrap().content(), "80.0%");
+
+        let mut second_row_content = table.row(1).unwrap().cell_iter();
+        assert_eq!(second_row_content.next().unwrap().content(), "CounterTest::test_fast()");
+        assert_eq!(second_row_content.next().unwrap().content(), "10.00ms");
+        assert_eq!(second_row_content.next().unwrap().content(), "5.00ms");
+        assert_eq!(second_row_content.next().unwrap().content(), "50.0%");
+    }
 }
@@ -1,11 +1,16 @@
+use super::install::{lockfile::Lockfile, semver_tag};
 use clap::{Parser, ValueHint};
 use eyre::{Context, Result};
 use foundry_cli::{
     opts::Dependency,
-    utils::{Git, LoadConfig},
+    utils::{CommandUtils, Git, LoadConfig},
 };
 use foundry_config::{impl_figment_convert_basic, Config};
-use std::path::PathBuf;
+use semver::Version;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
 
 /// CLI arguments for `forge update`.
 #[derive(Clone, Debug, Parser)]
@@ -27,6 +32,23 @@ pub struct UpdateArgs {
     /// Recursively update submodules.
     #[arg(short, long)]
     recursive: bool,
+
+    /// Update dependencies pinned to a release tag to their latest release, including breaking
+    /// releases.
+    ///
+    /// Dependencies which are not pinned to a release tag are not updated.
+    #[arg(long, conflicts_with = "minor")]
+    major: bool,
+
+    /// Update dependencies pinned to a release tag to their latest compatible release.
+    ///
+    /// Dependencies which are not pinned to a release tag are not updated.
+    #[arg(long)]
+    minor: bool,
+
+    /// Print the revisions the dependencies would be updated to, without updating them.
+    #[arg(long)]
+    dry_run: bool,
 }
 impl_figment_convert_basic!(UpdateArgs);
 
@@ -34,18 +56,55 @@ impl UpdateArgs {
     pub fn run(self) -> Result<()> {
         let config = self.load_config()?;
         let (root, paths) = dependencies_paths(&self.dependencies, &config)?;
-        // fetch the latest changes for each submodule (recursively if flag is set)
         let git = Git::new(&root);
-        if self.recursive {
+        let bump = if self.major {
+            Some(Bump::Major)
+        } else if self.minor {
+            Some(Bump::Minor)
+        } else {
+            None
+        };
+
+        // the release tags the dependencies were updated to
+        let mut tags = Vec::new();
+        if self.dry_run || bump.is_some() {
+            let paths = if paths.is_empty() { submodule_paths(git)? } else { paths };
+            let mut updates = Vec::with_capacity(paths.len());
+            for path in paths {
+                let update = DependencyUpdate::resolve(&root, path, bump)?;
+                sh_println!("{update}")?;
+                updates.push(update);
+            }
+            if self.dry_run {
+                return Ok(());
+            }
+            for update in &updates {
+                update.apply(&root)?;
+                if let Some(tag) = update.target.as_ref().and_then(|target| target.tag.as_ref()) {
+                    tags.push((root.join(&update.path), tag.clone()));
+                }
+            }
+        } else if self.recursive {
+            // fetch the latest changes for each submodule (recursively if flag is set)
             // update submodules recursively
-            git.submodule_update(self.force, true, false, true, paths)
+            git.submodule_update(self.force, true, false, true, paths)?;
         } else {
             // update root submodules
             git.submodule_update(self.force, true, false, false, paths)?;
             // initialize submodules of each submodule recursively (otherwise direct submodule
             // dependencies will revert to last commit)
-            git.submodule_foreach(false, "git submodule update --init --progress --recursive")
+            git.submodule_foreach(false, "git submodule update --init --progress --recursive")?;
+        }
+
+        // pin the updated revisions
+        if let Some(mut lockfile) = Lockfile::read(&config.root)? {
+            for (path, tag) in &tags {
+                lockfile.lock_tag(&config.root, path, tag)?;
+            }
+            lockfile.refresh(&config.root)?;
+            lockfile.write(&config.root)?;
         }
+        Ok(())
     }
 }
 
@@ -69,3 +128,185 @@ pub fn dependencies_paths(deps: &[Dependency], config: &Config) -> Result<(PathB
     }
     Ok((git_root, paths))
 }
+
+/// Returns the paths of all submodules of the repository, relative to its root.
+fn submodule_paths(git: Git<'_>) -> Result<Vec<PathBuf>> {
+    if !git.root.join(".gitmodules").exists() {
+        return Ok(Vec::new());
+    }
+    let output = git
+        .cmd()
+        .args(["config", "-f", ".gitmodules", "--get-regexp", r"^submodule\..*\.path$"])
+        .get_stdout_lossy()?;
+    Ok(output.lines().filter_map(|line| line.split_once(' ')).map(|(_, p)| p.into()).collect())
+}
+
+/// Which releases a dependency pinned to a release tag may be updated to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Bump {
+    /// Any newer release.
+    Major,
+    /// Newer releases with the same major version, or the same minor version for `0.x` releases.
+    Minor,
+}
+
+impl Bump {
+    fn allows(self, current: &Version, candidate: &Version) -> bool {
+        if candidate <= current {
+            return false;
+        }
+        match self {
+            Self::Major => true,
+            Self::Minor => {
+                candidate.major == current.major &&
+                    (current.major != 0 || candidate.minor == current.minor)
+            }
+        }
+    }
+}
+
+/// A revision of a dependency.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Revision {
+    rev: String,
+    tag: Option<String>,
+}
+
+impl fmt::Display for Revision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let short = &self.rev[..self.rev.len().min(7)];
+        match &self.tag {
+            Some(tag) => write!(f, "{tag} ({short})"),
+            None => f.write_str(short),
+        }
+    }
+}
+
+/// The update of a single dependency.
+#[derive(Clone, Debug)]
+struct DependencyUpdate {
+    /// The path of the dependency, relative to the repository root.
+    path: PathBuf,
+    /// The checked out revision.
+    current: Revision,
+    /// The revision to update to, `None` if the dependency is not pinned to a release tag.
+    target: Option<Revision>,
+}
+
+impl DependencyUpdate {
+    /// Resolves the revision the dependency at `path` would be updated to.
+    ///
+    /// Without `bump`, this is the latest commit of the tracked branch, like `git submodule update
+    /// --remote`.
+    fn resolve(root: &Path, path: PathBuf, bump: Option<Bump>) -> Result<Self> {
+        let dir = root.join(&path);
+        let git = Git::new(&dir);
+        let rev = git.commit_hash(false, "HEAD")?;
+        let current_tag = git
+            .cmd()
+            .args(["tag", "--points-at", "HEAD"])
+            .get_stdout_lossy()?
+            .lines()
+            .filter_map(|tag| semver_tag(tag).map(|v| (tag.to_string(), v)))
+            .max_by(|(_, a), (_, b)| a.cmp(b));
+        let current = Revision { rev, tag: current_tag.as_ref().map(|(tag, _)| tag.clone()) };
+
+        let target = match (bump, current_tag) {
+            (Some(bump), Some((tag, version))) => {
+                let latest = remote_tags(git)?
+                    .into_iter()
+                    .filter(|(_, v, _)| bump.allows(&version, v))
+                    .max_by(|(_, a, _), (_, b, _)| a.cmp(b));
+                Some(match latest {
+                    Some((tag, _, rev)) => Revision { rev, tag: Some(tag) },
+                    None => Revision { rev: current.rev.clone(), tag: Some(tag) },
+                })
+            }
+            (Some(_), None) => None,
+            (None, _) => {
+                let branch = Git::new(root)
+                    .cmd()
+                    .args(["config", "-f", ".gitmodules", "--get"])
+                    .arg(format!("submodule.{}.branch", path.display()))
+                    .get_stdout_lossy()
+                    .ok()
+                    .filter(|branch| !branch.is_empty());
+                let reference = branch.map_or_else(|| "HEAD".into(), |b| format!("refs/heads/{b}"));
+                let output =
+                    git.cmd().args(["ls-remote", "origin"]).arg(&reference).get_stdout_lossy()?;
+                let rev = output.split_whitespace().next().ok_or_else(|| {
+                    eyre::eyre!("could not resolve {reference} of {}", path.display())
+                })?;
+                Some(Revision { rev: rev.to_string(), tag: None })
+            }
+        };
+
+        Ok(Self { path, current, target })
+    }
+
+    /// Checks out the resolved revision.
+    fn apply(&self, root: &Path) -> Result<()> {
+        let Some(target) = self.target.as_ref().filter(|target| **target != self.current) else {
+            return Ok(());
+        };
+        let dir = root.join(&self.path);
+        let git = Git::new(&dir);
+        match &target.tag {
+            Some(tag) => git.cmd().args(["fetch", "origin", "tag", tag]).exec()?,
+            None => git.cmd().args(["fetch", "origin", &target.rev]).exec()?,
+        };
+        git.checkout(true, &target.rev)?;
+        git.submodule_update(false, false, false, true, std::iter::empty::<PathBuf>())
+    }
+}
+
+impl fmt::Display for DependencyUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path.display();
+        match &self.target {
+            None => write!(f, "{path}: not pinned to a release tag, skipping"),
+            Some(target) if target.rev == self.current.rev => {
+                write!(f, "{path}: up to date at {}", self.current)
+            }
+            Some(target) => write!(f, "{path}: {} -> {target}", self.current),
+        }
+    }
+}
+
+/// Returns the semver release tags of the `origin` remote with the commits they point to.
+fn remote_tags(git: Git<'_>) -> Result<Vec<(String, Version, String)>> {
+    let output = git.cmd().args(["ls-remote", "--tags", "origin"]).get_stdout_lossy()?;
+    let mut tags = std::collections::BTreeMap::new();
+    for line in output.lines() {
+        let Some((rev, name)) = line.split_once('\t') else { continue };
+        let Some(name) = name.strip_prefix("refs/tags/") else { continue };
+        // annotated tags are listed twice, prefer the peeled commit
+        if let Some(name) = name.strip_suffix("^{}") {
+            tags.insert(name.to_string(), rev.to_string());
+        } else {
+            tags.entry(name.to_string()).or_insert_with(|| rev.to_string());
+        }
+    }
+    Ok(tags
+        .into_iter()
+        .filter_map(|(tag, rev)| semver_tag(&tag).map(|version| (tag, version, rev)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bump_allows() {
+        let v = |s: &str| Version::parse(s).unwrap();
+        assert!(Bump::Minor.allows(&v("1.2.3"), &v("1.2.4")));
+        assert!(Bump::Minor.allows(&v("1.2.3"), &v("1.9.0")));
+        assert!(!Bump::Minor.allows(&v("1.2.3"), &v("2.0.0")));
+        assert!(!Bump::Minor.allows(&v("1.2.3"), &v("1.2.3")));
+        assert!(Bump::Minor.allows(&v("0.2.3"), &v("0.2.9")));
+        assert!(!Bump::Minor.allows(&v("0.2.3"), &v("0.3.0")));
+        assert!(Bump::Major.allows(&v("1.2.3"), &v("2.0.0")));
+        assert!(!Bump::Major.allows(&v("1.2.3"), &v("1.0.0")));
+    }
+}
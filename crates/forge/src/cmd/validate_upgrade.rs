@@ -0,0 +1,437 @@
+//! `forge validate-upgrade`: checks that contracts are safe to use as proxy implementations.
+//!
+//! The checks mirror the validations of the OpenZeppelin Upgrades plugins and can be allowed with
+//! the same `@custom:oz-upgrades-unsafe-allow <check>...` NatSpec annotations, placed on the
+//! contract or on the offending function or variable.
+
+use clap::Parser;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Cell, Color, Table};
+use eyre::Result;
+use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
+use foundry_common::{
+    compile::{PathOrContractInfo, ProjectCompiler},
+    shell,
+};
+use foundry_compilers::utils::canonicalized;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+foundry_config::impl_figment_convert!(ValidateUpgradeArgs, build);
+
+/// The NatSpec tag allowing unsafe patterns.
+const ALLOW_TAG: &str = "@custom:oz-upgrades-unsafe-allow";
+
+/// CLI arguments for `forge validate-upgrade`.
+#[derive(Clone, Debug, Parser)]
+pub struct ValidateUpgradeArgs {
+    /// The implementation contracts to validate, in the form `(<path>:)?<contractname>`.
+    ///
+    /// Defaults to all contracts of the project sources inheriting from `Initializable`.
+    #[arg(value_parser = PathOrContractInfo::from_str)]
+    contracts: Vec<PathOrContractInfo>,
+
+    #[command(flatten)]
+    build: BuildOpts,
+}
+
+impl ValidateUpgradeArgs {
+    pub fn run(self) -> Result<()> {
+        let mut config = self.load_config()?;
+        // The checks are performed on the Solc AST.
+        config.ast = true;
+
+        let project = config.project()?;
+        let output = ProjectCompiler::new().quiet(shell::is_json()).compile(&project)?;
+        let root = project.root();
+
+        let mut index = ContractIndex { root: root.to_path_buf(), ..Default::default() };
+        for (path, file, _) in output.output().sources.sources_with_version() {
+            if let Some(ast) = &file.ast {
+                index.add(root.join(path), serde_json::to_value(ast)?);
+            }
+        }
+
+        let targets = if self.contracts.is_empty() {
+            index
+                .contracts
+                .iter()
+                .filter(|(_, c)| {
+                    c.path.starts_with(&project.paths.sources) && c.is_upgradeable(&index)
+                })
+                .map(|(id, _)| *id)
+                .collect::<BTreeSet<_>>()
+        } else {
+            let mut targets = BTreeSet::new();
+            for contract in &self.contracts {
+                let path = contract.path().map(|path| canonicalized(root.join(path)));
+                let matches = index
+                    .contracts
+                    .iter()
+                    .filter(|(_, c)| c.kind == "contract" && !c.is_abstract)
+                    .filter(|(_, c)| path.as_ref().is_none_or(|path| c.path == *path))
+                    .filter(|(_, c)| contract.name().is_none_or(|name| c.name == name))
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<_>>();
+                if matches.is_empty() {
+                    eyre::bail!("Could not find contract {contract:?}");
+                }
+                targets.extend(matches);
+            }
+            targets
+        };
+
+        let mut errors = Vec::new();
+        for id in &targets {
+            let contract = &index.contracts[id];
+            errors.extend(index.validate(*id));
+
+            // Linked libraries are deployed separately and may be self-destructed.
+            let linked = output.artifact_ids().any(|(artifact_id, artifact)| {
+                artifact_id.source == contract.path &&
+                    artifact_id.name == contract.name &&
+                    artifact.bytecode.as_ref().is_some_and(|b| !b.link_references.is_empty())
+            });
+            if linked && !contract.allows(UpgradeCheck::ExternalLibraryLinking) {
+                errors.push(UpgradeError {
+                    contract: contract.identifier(root),
+                    check: UpgradeCheck::ExternalLibraryLinking,
+                    location: index.location(&contract.path, &contract.node),
+                    details: "links external libraries".to_string(),
+                });
+            }
+        }
+
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string_pretty(&errors)?)?;
+        } else if errors.is_empty() {
+            sh_println!(
+                "Validated {} contract(s), no upgrade safety errors found.",
+                targets.len()
+            )?;
+        } else {
+            sh_println!("\n{}\n", errors_table(&errors))?;
+        }
+
+        if !errors.is_empty() {
+            let contracts = errors.iter().map(|e| &e.contract).collect::<BTreeSet<_>>().len();
+            eyre::bail!(
+                "found {} upgrade safety error(s) in {contracts} of {} contract(s)",
+                errors.len(),
+                targets.len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// An upgrade safety check, named after the OpenZeppelin Upgrades validation it mirrors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpgradeCheck {
+    /// Constructors are not run in the context of the proxy.
+    Constructor,
+    /// `selfdestruct` in the implementation bricks all proxies using it.
+    Selfdestruct,
+    /// `delegatecall` may execute `selfdestruct` in the context of the implementation.
+    Delegatecall,
+    /// Initial values of state variables are set by the constructor.
+    StateVariableAssignment,
+    /// Immutable variables are stored in the implementation's code, not in the proxy.
+    StateVariableImmutable,
+    /// Linked libraries are deployed separately and may be self-destructed.
+    ExternalLibraryLinking,
+    /// State variables can only be initialized by an initializer.
+    MissingInitializer,
+}
+
+impl UpgradeCheck {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "constructor" => Self::Constructor,
+            "selfdestruct" => Self::Selfdestruct,
+            "delegatecall" => Self::Delegatecall,
+            "state-variable-assignment" => Self::StateVariableAssignment,
+            "state-variable-immutable" => Self::StateVariableImmutable,
+            "external-library-linking" => Self::ExternalLibraryLinking,
+            "missing-initializer" => Self::MissingInitializer,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for UpgradeCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Constructor => "constructor",
+            Self::Selfdestruct => "selfdestruct",
+            Self::Delegatecall => "delegatecall",
+            Self::StateVariableAssignment => "state-variable-assignment",
+            Self::StateVariableImmutable => "state-variable-immutable",
+            Self::ExternalLibraryLinking => "external-library-linking",
+            Self::MissingInitializer => "missing-initializer",
+        })
+    }
+}
+
+/// A failed upgrade safety check.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UpgradeError {
+    /// The validated contract, as `<path>:<name>`.
+    pub contract: String,
+    pub check: UpgradeCheck,
+    /// The offending code, as `<path>:<line>`.
+    pub location: String,
+    pub details: String,
+}
+
+/// A contract definition of the compiled sources.
+#[derive(Clone, Debug)]
+struct ContractNode {
+    path: PathBuf,
+    name: String,
+    /// `contract`, `interface` or `library`.
+    kind: String,
+    is_abstract: bool,
+    /// The AST IDs of the linearized base contracts, from most derived to most base.
+    bases: Vec<u64>,
+    /// The checks allowed for the whole contract.
+    allowed: Vec<UpgradeCheck>,
+    node: Value,
+}
+
+impl ContractNode {
+    fn identifier(&self, root: &Path) -> String {
+        format!("{}:{}", self.path.strip_prefix(root).unwrap_or(&self.path).display(), self.name)
+    }
+
+    fn allows(&self, check: UpgradeCheck) -> bool {
+        self.allowed.contains(&check)
+    }
+
+    fn is_upgradeable(&self, index: &ContractIndex) -> bool {
+        self.kind == "contract" &&
+            !self.is_abstract &&
+            self.bases.iter().any(|id| {
+                index.contracts.get(id).is_some_and(|base| base.name == "Initializable")
+            })
+    }
+}
+
+/// The contract definitions of the compiled sources, keyed by AST ID.
+#[derive(Debug, Default)]
+struct ContractIndex {
+    /// The project root, stripped from displayed paths.
+    root: PathBuf,
+    contracts: HashMap<u64, ContractNode>,
+    /// The source code of the files, used to resolve line numbers.
+    sources: HashMap<PathBuf, String>,
+}
+
+impl ContractIndex {
+    fn add(&mut self, path: PathBuf, ast: Value) {
+        let Some(nodes) = ast.get("nodes").and_then(Value::as_array) else { return };
+        for node in nodes.iter().filter(|n| n["nodeType"] == "ContractDefinition") {
+            let Some(id) = node["id"].as_u64() else { continue };
+            let contract = ContractNode {
+                path: path.clone(),
+                name: node["name"].as_str().unwrap_or_default().to_string(),
+                kind: node["contractKind"].as_str().unwrap_or_default().to_string(),
+                is_abstract: node["abstract"].as_bool().unwrap_or_default(),
+                bases: node["linearizedBaseContracts"]
+                    .as_array()
+                    .map(|bases| bases.iter().filter_map(Value::as_u64).collect())
+                    .unwrap_or_default(),
+                allowed: allowed_checks(node),
+                node: node.clone(),
+            };
+            self.contracts.insert(id, contract);
+        }
+        if let Ok(source) = std::fs::read_to_string(&path) {
+            self.sources.insert(path, source);
+        }
+    }
+
+    /// Validates the contract with the given AST ID and its base contracts.
+    fn validate(&self, id: u64) -> Vec<UpgradeError> {
+        let target = &self.contracts[&id];
+        let contract = target.identifier(&self.root);
+        let mut errors = Vec::new();
+        let mut has_initializer = false;
+        let mut state_variables = Vec::new();
+
+        for base in target.bases.iter().rev().filter_map(|id| self.contracts.get(id)) {
+            let allowed = |check: UpgradeCheck, node: &Value| {
+                target.allows(check) || base.allows(check) || allowed_checks(node).contains(&check)
+            };
+            let mut error = |check: UpgradeCheck, node: &Value, details: String| {
+                errors.push(UpgradeError {
+                    contract: contract.clone(),
+                    check,
+                    location: self.location(&base.path, node),
+                    details,
+                });
+            };
+
+            for node in base.node["nodes"].as_array().into_iter().flatten() {
+                match node["nodeType"].as_str() {
+                    Some("VariableDeclaration") => {
+                        let name = node["name"].as_str().unwrap_or_default();
+                        match node["mutability"].as_str() {
+                            Some("immutable") => {
+                                if !allowed(UpgradeCheck::StateVariableImmutable, node) {
+                                    error(
+                                        UpgradeCheck::StateVariableImmutable,
+                                        node,
+                                        format!("`{}.{name}` is immutable", base.name),
+                                    );
+                                }
+                            }
+                            Some("constant") => {}
+                            _ => {
+                                state_variables.push(name.to_string());
+                                if !node["value"].is_null() &&
+                                    !allowed(UpgradeCheck::StateVariableAssignment, node)
+                                {
+                                    error(
+                                        UpgradeCheck::StateVariableAssignment,
+                                        node,
+                                        format!(
+                                            "`{}.{name}` is assigned an initial value",
+                                            base.name
+                                        ),
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    Some("FunctionDefinition" | "ModifierDefinition") => {
+                        let name = node["name"].as_str().unwrap_or_default();
+                        if node["kind"] == "constructor" {
+                            if !allowed(UpgradeCheck::Constructor, node) {
+                                error(
+                                    UpgradeCheck::Constructor,
+                                    node,
+                                    format!("`{}` has a constructor", base.name),
+                                );
+                            }
+                        } else if node["modifiers"].as_array().into_iter().flatten().any(|m| {
+                            matches!(
+                                m["modifierName"]["name"].as_str(),
+                                Some("initializer" | "reinitializer")
+                            )
+                        }) {
+                            has_initializer = true;
+                        }
+
+                        let mut calls = Vec::new();
+                        collect_unsafe_calls(&node["body"], &mut calls);
+                        for (check, call) in calls {
+                            if !allowed(check, node) {
+                                error(
+                                    check,
+                                    call,
+                                    format!("`{}.{name}` uses `{check}`", base.name),
+                                );
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if !has_initializer &&
+            !state_variables.is_empty() &&
+            !target.allows(UpgradeCheck::MissingInitializer)
+        {
+            errors.push(UpgradeError {
+                contract: contract.clone(),
+                check: UpgradeCheck::MissingInitializer,
+                location: self.location(&target.path, &target.node),
+                details: format!(
+                    "`{}` has state variables but no function with the `initializer` modifier",
+                    target.name
+                ),
+            });
+        }
+
+        errors
+    }
+
+    /// Returns the location of the node as `<path>:<line>`.
+    fn location(&self, path: &Path, node: &Value) -> String {
+        let start = node["src"]
+            .as_str()
+            .and_then(|src| src.split(':').next())
+            .and_then(|start| start.parse::<usize>().ok());
+        let line = start.zip(self.sources.get(path)).map(|(start, source)| {
+            source.as_bytes()[..start.min(source.len())].iter().filter(|b| **b == b'\n').count() + 1
+        });
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+        match line {
+            Some(line) => format!("{}:{line}", path.display()),
+            None => path.display().to_string(),
+        }
+    }
+}
+
+/// Returns the checks allowed by the `@custom:oz-upgrades-unsafe-allow` annotation of the node.
+fn allowed_checks(node: &Value) -> Vec<UpgradeCheck> {
+    let docs = match &node["documentation"] {
+        Value::String(text) => text.as_str(),
+        docs => docs["text"].as_str().unwrap_or_default(),
+    };
+    docs.lines()
+        .filter_map(|line| line.split_once(ALLOW_TAG))
+        .flat_map(|(_, checks)| checks.split_whitespace().filter_map(UpgradeCheck::from_name))
+        .collect()
+}
+
+/// Collects the `selfdestruct` and `delegatecall` expressions of a function body.
+fn collect_unsafe_calls<'a>(value: &'a Value, calls: &mut Vec<(UpgradeCheck, &'a Value)>) {
+    match value {
+        Value::Object(node) => {
+            match node.get("nodeType").and_then(Value::as_str) {
+                Some("Identifier")
+                    if matches!(
+                        node.get("name").and_then(Value::as_str),
+                        Some("selfdestruct" | "suicide")
+                    ) =>
+                {
+                    calls.push((UpgradeCheck::Selfdestruct, value));
+                }
+                Some("MemberAccess")
+                    if node.get("memberName").and_then(Value::as_str) == Some("delegatecall") =>
+                {
+                    calls.push((UpgradeCheck::Delegatecall, value));
+                }
+                _ => {}
+            }
+            node.values().for_each(|value| collect_unsafe_calls(value, calls));
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_unsafe_calls(value, calls)),
+        _ => {}
+    }
+}
+
+fn errors_table(errors: &[UpgradeError]) -> Table {
+    let mut table = Table::new();
+    table.apply_modifier(UTF8_ROUND_CORNERS);
+    table.set_header(vec!["Contract", "Check", "Location", "Details"]);
+    for error in errors {
+        table.add_row(vec![
+            Cell::new(&error.contract),
+            Cell::new(error.check).fg(Color::Red),
+            Cell::new(&error.location),
+            Cell::new(&error.details),
+        ]);
+    }
+    table
+}
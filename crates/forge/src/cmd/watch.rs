@@ -64,6 +64,17 @@ pub struct WatchArgs {
     /// overloading disk I/O.
     #[arg(long, value_name = "DELAY")]
     pub watch_delay: Option<String>,
+
+    /// Run a shell command after the command succeeded, on every change.
+    ///
+    /// Can be repeated to run several commands in order, each only if the previous one
+    /// succeeded.
+    #[arg(long, value_name = "COMMAND", requires = "watch")]
+    pub watch_exec: Vec<String>,
+
+    /// Do not clear the screen before re-running the command.
+    #[arg(long)]
+    pub no_clear: bool,
 }
 
 impl WatchArgs {
@@ -122,12 +133,20 @@ fn watchexec_config_inner(
         config.pathset(paths.iter().map(|p| p.as_path()));
 
         let n_path_args = self.watch.as_deref().unwrap_or_default().len();
-        let base_command = Arc::new(watch_command(cmd_args(n_path_args)));
+        let args = cmd_args(n_path_args);
+        let base_command = Arc::new(if self.watch_exec.is_empty() {
+            watch_command(args)
+        } else {
+            shell_command(&args, &self.watch_exec)
+        });
+        // the hook modifies the arguments of the command, which is wrapped in a shell script
+        let spawn_hook = spawn_hook.filter(|_| self.watch_exec.is_empty());
 
         let id = watchexec::Id::default();
         let quit_again = Arc::new(AtomicU8::new(0));
         let stop_timeout = Duration::from_secs(5);
         let no_restart = self.no_restart;
+        let no_clear = self.no_clear;
         let stop_signal = Signal::Terminate;
         config.on_action(move |mut action| {
             let base_command = base_command.clone();
@@ -147,8 +166,10 @@ fn watchexec_config_inner(
                 }
             });
 
-            let clear_screen = || {
-                let _ = clearscreen::clear();
+            let clear_screen = move || {
+                if !no_clear {
+                    let _ = clearscreen::clear();
+                }
             };
 
             let quit = |mut action: ActionHandler| {
@@ -355,6 +376,29 @@ fn watch_command(mut args: Vec<String>) -> Command {
     Command { program: Program::Exec { prog: prog.into(), args }, options: Default::default() }
 }
 
+/// Converts a list of arguments and follow-up shell commands to a `watchexec::Command` which runs
+/// them in order in a shell, stopping at the first failure.
+///
+/// The first index in `args` is the path to the executable.
+fn shell_command(args: &[String], follow_up: &[String]) -> Command {
+    let command = args.iter().map(|arg| shell_quote(arg)).collect::<Vec<_>>().join(" ");
+    let script = std::iter::once(command)
+        .chain(follow_up.iter().map(|cmd| format!("({cmd})")))
+        .collect::<Vec<_>>()
+        .join(" && ");
+    let (prog, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    watch_command(vec![prog.to_string(), flag.to_string(), script])
+}
+
+/// Quotes `arg` for the shell used by [`shell_command`].
+fn shell_quote(arg: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", arg.replace('"', "\"\""))
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
 /// Returns the env args without the `--watch` flag from the args for the Watchexec command
 fn cmd_args(num: usize) -> Vec<String> {
     clean_cmd_args(num, std::env::args().collect())
@@ -366,6 +410,12 @@ fn clean_cmd_args(num: usize, mut cmd_args: Vec<String>) -> Vec<String> {
         cmd_args.drain(pos..=(pos + num));
     }
 
+    // `--watch-exec` requires `--watch`, so it must be removed as well
+    while let Some(pos) = cmd_args.iter().position(|arg| arg.starts_with("--watch-exec")) {
+        let n = if cmd_args[pos] == "--watch-exec" { 2 } else { 1 };
+        cmd_args.drain(pos..(pos + n).min(cmd_args.len()));
+    }
+
     // There's another edge case where short flags are combined into one which is supported by clap,
     // like `-vw` for verbosity and watch
     // this removes any `w` from concatenated short flags
@@ -404,4 +454,21 @@ fn parse_cmd_args() {
         let cleaned = clean_cmd_args(0, args);
         assert_eq!(cleaned, vec!["-v".to_string()]);
     }
+
+    #[test]
+    fn parse_watch_exec_args() {
+        let args = ["forge", "build", "-w", "--watch-exec", "forge test", "--watch-exec=echo ok"];
+        let cleaned = clean_cmd_args(0, args.iter().map(|s| s.to_string()).collect());
+        assert_eq!(cleaned, vec!["forge".to_string(), "build".to_string()]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn shell_command_chains_follow_ups() {
+        let args = ["forge".to_string(), "build".to_string(), "it's".to_string()];
+        let command = shell_command(&args, &["forge test --match-contract Foo".to_string()]);
+        let Program::Exec { prog, args } = command.program else { panic!() };
+        assert_eq!(prog, PathBuf::from("sh"));
+        assert_eq!(args, ["-c", r"'forge' 'build' 'it'\''s' && (forge test --match-contract Foo)"]);
+    }
 }
@@ -1,12 +1,13 @@
 //! Coverage reports.
 
-use alloy_primitives::map::HashMap;
+use alloy_primitives::map::{HashMap, HashSet};
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Attribute, Cell, Color, Row, Table};
 use evm_disassembler::disassemble_bytes;
+use foundry_cli::utils::{CommandUtils, Git};
 use foundry_common::fs;
 use semver::Version;
 use std::{
-    collections::hash_map,
+    collections::{hash_map, BTreeMap},
     io::Write,
     path::{Path, PathBuf},
 };
@@ -25,6 +26,8 @@ fn needs_source_maps(&self) -> bool {
 }
 
 /// A simple summary reporter that prints the coverage results in a table.
+///
+/// The condition and modifier columns are only shown if the project has any.
 pub struct CoverageSummaryReporter {
     /// The summary table.
     table: Table,
@@ -37,34 +40,54 @@ fn default() -> Self {
         let mut table = Table::new();
         table.apply_modifier(UTF8_ROUND_CORNERS);
 
-        table.set_header(vec![
+        Self { table, total: CoverageSummary::default() }
+    }
+}
+
+impl CoverageSummaryReporter {
+    fn set_header(&mut self) {
+        let mut header = vec![
             Cell::new("File"),
             Cell::new("% Lines"),
             Cell::new("% Statements"),
             Cell::new("% Branches"),
-            Cell::new("% Funcs"),
-        ]);
-
-        Self { table, total: CoverageSummary::default() }
+        ];
+        if self.total.condition_count > 0 {
+            header.push(Cell::new("% Conditions"));
+        }
+        header.push(Cell::new("% Funcs"));
+        if self.total.modifier_count > 0 {
+            header.push(Cell::new("% Modifiers"));
+        }
+        self.table.set_header(header);
     }
-}
 
-impl CoverageSummaryReporter {
     fn add_row(&mut self, name: impl Into<Cell>, summary: CoverageSummary) {
         let mut row = Row::new();
         row.add_cell(name.into())
             .add_cell(format_cell(summary.line_hits, summary.line_count))
             .add_cell(format_cell(summary.statement_hits, summary.statement_count))
-            .add_cell(format_cell(summary.branch_hits, summary.branch_count))
-            .add_cell(format_cell(summary.function_hits, summary.function_count));
+            .add_cell(format_cell(summary.branch_hits, summary.branch_count));
+        if self.total.condition_count > 0 {
+            row.add_cell(format_cell(summary.condition_hits, summary.condition_count));
+        }
+        row.add_cell(format_cell(summary.function_hits, summary.function_count));
+        if self.total.modifier_count > 0 {
+            row.add_cell(format_cell(summary.modifier_hits, summary.modifier_count));
+        }
         self.table.add_row(row);
     }
 }
 
 impl CoverageReporter for CoverageSummaryReporter {
     fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
-        for (path, summary) in report.summary_by_file() {
-            self.total.merge(&summary);
+        let summaries = report.summary_by_file().collect::<Vec<_>>();
+        for (_, summary) in &summaries {
+            self.total.merge(summary);
+        }
+
+        self.set_header();
+        for (path, summary) in summaries {
             self.add_row(path.display(), summary);
         }
 
@@ -124,7 +147,10 @@ fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
                 let end_line = item.loc.lines.end - 1;
                 let hits = item.hits;
                 match item.kind {
-                    CoverageItemKind::Function { ref name } => {
+                    // LCOV has no notion of modifiers and conditions, they are reported as
+                    // functions and branches.
+                    CoverageItemKind::Function { ref name } |
+                    CoverageItemKind::Modifier { ref name } => {
                         let name = format!("{}.{name}", item.loc.contract_name);
                         if self.version >= Version::new(2, 2, 0) {
                             // v2.2 changed the FN format.
@@ -143,7 +169,8 @@ fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
                     CoverageItemKind::Line => {
                         writeln!(out, "DA:{line},{hits}")?;
                     }
-                    CoverageItemKind::Branch { branch_id, path_id, .. } => {
+                    CoverageItemKind::Branch { branch_id, path_id, .. } |
+                    CoverageItemKind::Condition { branch_id, path_id } => {
                         writeln!(
                             out,
                             "BRDA:{line},{branch_id},{path_id},{}",
@@ -157,16 +184,16 @@ fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
             }
 
             // Function summary
-            writeln!(out, "FNF:{}", summary.function_count)?;
-            writeln!(out, "FNH:{}", summary.function_hits)?;
+            writeln!(out, "FNF:{}", summary.function_count + summary.modifier_count)?;
+            writeln!(out, "FNH:{}", summary.function_hits + summary.modifier_hits)?;
 
             // Line summary
             writeln!(out, "LF:{}", summary.line_count)?;
             writeln!(out, "LH:{}", summary.line_hits)?;
 
             // Branch summary
-            writeln!(out, "BRF:{}", summary.branch_count)?;
-            writeln!(out, "BRH:{}", summary.branch_hits)?;
+            writeln!(out, "BRF:{}", summary.branch_count + summary.condition_count)?;
+            writeln!(out, "BRH:{}", summary.branch_hits + summary.condition_hits)?;
 
             writeln!(out, "end_of_record")?;
         }
@@ -178,6 +205,514 @@ fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
     }
 }
 
+/// Writes the coverage report in the [Cobertura] XML format.
+///
+/// Source files are reported as classes, grouped in packages by their parent directory.
+/// Conditions are reported as branches, and modifiers as methods.
+///
+/// [Cobertura]: https://cobertura.github.io/cobertura/
+pub struct CoberturaReporter {
+    root: PathBuf,
+    path: PathBuf,
+}
+
+impl CoberturaReporter {
+    /// Create a new Cobertura reporter.
+    pub fn new(root: PathBuf, path: PathBuf) -> Self {
+        Self { root, path }
+    }
+}
+
+impl CoverageReporter for CoberturaReporter {
+    fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
+        let mut total = CoverageSummary::default();
+        let mut packages: BTreeMap<&Path, Vec<(&Path, Vec<&CoverageItem>)>> = BTreeMap::new();
+        for (path, items) in report.items_by_file() {
+            total.add_items(items.iter().copied());
+            packages.entry(path.parent().unwrap_or(Path::new(""))).or_default().push((path, items));
+        }
+
+        let mut out = std::io::BufWriter::new(fs::create_file(&self.path)?);
+        writeln!(out, r#"<?xml version="1.0" ?>"#)?;
+        writeln!(
+            out,
+            r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+        )?;
+        writeln!(
+            out,
+            r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="{}" timestamp="{}">"#,
+            rate(total.line_hits, total.line_count),
+            rate(branch_hits(&total), branch_count(&total)),
+            total.line_hits,
+            total.line_count,
+            branch_hits(&total),
+            branch_count(&total),
+            env!("CARGO_PKG_VERSION"),
+            chrono::Utc::now().timestamp_millis(),
+        )?;
+        writeln!(out, "  <sources>")?;
+        writeln!(out, "    <source>{}</source>", escape_xml(&self.root.display().to_string()))?;
+        writeln!(out, "  </sources>")?;
+        writeln!(out, "  <packages>")?;
+        for (package, files) in packages {
+            let mut summary = CoverageSummary::default();
+            for (_, items) in &files {
+                summary.add_items(items.iter().copied());
+            }
+            writeln!(
+                out,
+                r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+                escape_xml(&package.display().to_string()),
+                rate(summary.line_hits, summary.line_count),
+                rate(branch_hits(&summary), branch_count(&summary)),
+            )?;
+            writeln!(out, "      <classes>")?;
+            for (path, items) in files {
+                let summary = CoverageSummary::from_items(items.iter().copied());
+                let path = escape_xml(&path.display().to_string());
+                writeln!(
+                    out,
+                    r#"        <class name="{path}" filename="{path}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+                    rate(summary.line_hits, summary.line_count),
+                    rate(branch_hits(&summary), branch_count(&summary)),
+                )?;
+
+                writeln!(out, "          <methods>")?;
+                for item in &items {
+                    let (CoverageItemKind::Function { name } | CoverageItemKind::Modifier { name }) =
+                        &item.kind
+                    else {
+                        continue;
+                    };
+                    let line = item.loc.lines.start;
+                    let hits = item.hits;
+                    writeln!(
+                        out,
+                        r#"            <method name="{}" signature="" line-rate="{}" branch-rate="1" complexity="0">"#,
+                        escape_xml(&format!("{}.{name}", item.loc.contract_name)),
+                        if hits > 0 { 1 } else { 0 },
+                    )?;
+                    writeln!(
+                        out,
+                        r#"              <lines><line number="{line}" hits="{hits}"/></lines>"#
+                    )?;
+                    writeln!(out, "            </method>")?;
+                }
+                writeln!(out, "          </methods>")?;
+
+                writeln!(out, "          <lines>")?;
+                for (line, coverage) in line_coverage(&items) {
+                    let Some(hits) = coverage.hits else { continue };
+                    if coverage.branches == 0 {
+                        writeln!(
+                            out,
+                            r#"            <line number="{line}" hits="{hits}" branch="false"/>"#
+                        )?;
+                    } else {
+                        writeln!(
+                            out,
+                            r#"            <line number="{line}" hits="{hits}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                            coverage.branch_hits * 100 / coverage.branches,
+                            coverage.branch_hits,
+                            coverage.branches,
+                        )?;
+                    }
+                }
+                writeln!(out, "          </lines>")?;
+                writeln!(out, "        </class>")?;
+            }
+            writeln!(out, "      </classes>")?;
+            writeln!(out, "    </package>")?;
+        }
+        writeln!(out, "  </packages>")?;
+        writeln!(out, "</coverage>")?;
+
+        out.flush()?;
+        sh_println!("Wrote Cobertura report.")?;
+
+        Ok(())
+    }
+}
+
+/// Writes a self-contained HTML coverage report.
+///
+/// The report consists of an `index.html` with a sortable summary table, and one page per source
+/// file with the annotated source code. Styles and scripts are inlined, so the report can be
+/// opened directly from the file system.
+pub struct HtmlReporter {
+    root: PathBuf,
+    destdir: PathBuf,
+}
+
+impl HtmlReporter {
+    /// Create a new HTML reporter.
+    pub fn new(root: PathBuf, destdir: PathBuf) -> Self {
+        Self { root, destdir }
+    }
+
+    /// Renders the annotated source of a single file.
+    fn file_page(&self, path: &Path, items: &[&CoverageItem]) -> eyre::Result<String> {
+        use std::fmt::Write;
+
+        let source = fs::read_to_string(self.root.join(path))?;
+        let lines = line_coverage(items);
+        let summary = CoverageSummary::from_items(items.iter().copied());
+
+        let mut body = String::new();
+        writeln!(body, "<h1>{}</h1>", escape_xml(&path.display().to_string()))?;
+        writeln!(
+            body,
+            "<p><a href=\"{}index.html\">Back to summary</a></p>",
+            "../".repeat(depth(path))
+        )?;
+        writeln!(body, "<table>")?;
+        writeln!(body, "{}", summary_header())?;
+        writeln!(body, "<tr><td>Total</td>{}</tr>", summary_cells(&summary))?;
+        writeln!(body, "</table>")?;
+        writeln!(body, "<pre class=\"source\">")?;
+        for (i, text) in source.lines().enumerate() {
+            let line = i as u32 + 1;
+            let (class, hits) = match lines.get(&line) {
+                Some(LineCoverage { hits: Some(0), .. }) => ("uncovered", "0".to_string()),
+                Some(LineCoverage { hits: Some(hits), branches, branch_hits })
+                    if branch_hits < branches =>
+                {
+                    ("partial", hits.to_string())
+                }
+                Some(LineCoverage { hits: Some(hits), .. }) => ("covered", hits.to_string()),
+                _ => ("", String::new()),
+            };
+            writeln!(
+                body,
+                "<span class=\"line {class}\"><span class=\"no\">{line}</span><span class=\"hits\">{hits}</span>{}</span>",
+                escape_xml(text)
+            )?;
+        }
+        writeln!(body, "</pre>")?;
+
+        Ok(html_page(&path.display().to_string(), &body))
+    }
+}
+
+impl CoverageReporter for HtmlReporter {
+    fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
+        use std::fmt::Write;
+
+        let mut total = CoverageSummary::default();
+        let mut rows = String::new();
+        for (path, items) in report.items_by_file() {
+            let summary = CoverageSummary::from_items(items.iter().copied());
+            total.merge(&summary);
+
+            let page = self.destdir.join(format!("{}.html", path.display()));
+            fs::create_dir_all(page.parent().unwrap_or(&self.destdir))?;
+            fs::write(&page, self.file_page(path, &items)?)?;
+
+            let path = path.display().to_string().replace('\\', "/");
+            writeln!(
+                rows,
+                "<tr><td><a href=\"{}.html\">{}</a></td>{}</tr>",
+                escape_xml(&path),
+                escape_xml(&path),
+                summary_cells(&summary)
+            )?;
+        }
+
+        let mut body = String::new();
+        writeln!(body, "<h1>Coverage report</h1>")?;
+        writeln!(body, "<table id=\"summary\">")?;
+        writeln!(body, "<thead>{}</thead>", summary_header())?;
+        writeln!(body, "<tbody>\n{rows}</tbody>")?;
+        writeln!(body, "<tfoot><tr><td>Total</td>{}</tr></tfoot>", summary_cells(&total))?;
+        writeln!(body, "</table>")?;
+        writeln!(body, "<script>{SORT_SCRIPT}</script>")?;
+
+        fs::create_dir_all(&self.destdir)?;
+        fs::write(self.destdir.join("index.html"), html_page("Coverage report", &body))?;
+        sh_println!("Wrote HTML report to {}.", self.destdir.display())?;
+
+        Ok(())
+    }
+}
+
+/// Style of the HTML report pages.
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: right; }
+th { cursor: pointer; background: #eee; }
+td:first-child, th:first-child { text-align: left; }
+td.high { background: #d4f4d4; }
+td.medium { background: #f8f0c8; }
+td.low { background: #f8d4d4; }
+pre.source { line-height: 1.3; }
+.line { display: block; }
+.line.covered { background: #d4f4d4; }
+.line.partial { background: #f8f0c8; }
+.line.uncovered { background: #f8d4d4; }
+.no, .hits { display: inline-block; width: 4em; padding-right: 1em; text-align: right; color: #888; user-select: none; }";
+
+/// Sorts the summary table when a header is clicked.
+const SORT_SCRIPT: &str = "\
+document.querySelectorAll('#summary th').forEach((th, col) => th.addEventListener('click', () => {
+  const body = document.querySelector('#summary tbody');
+  const asc = th.dataset.order !== 'asc';
+  th.dataset.order = asc ? 'asc' : 'desc';
+  const key = row => col === 0 ? row.cells[0].textContent : parseFloat(row.cells[col].dataset.value);
+  [...body.rows]
+    .sort((a, b) => (key(a) < key(b) ? -1 : key(a) > key(b) ? 1 : 0) * (asc ? 1 : -1))
+    .forEach(row => body.appendChild(row));
+}));";
+
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{HTML_STYLE}\n</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape_xml(title)
+    )
+}
+
+fn summary_header() -> &'static str {
+    "<tr><th>File</th><th>% Lines</th><th>% Statements</th><th>% Branches</th>\
+     <th>% Conditions</th><th>% Funcs</th><th>% Modifiers</th></tr>"
+}
+
+fn summary_cells(summary: &CoverageSummary) -> String {
+    [
+        (summary.line_hits, summary.line_count),
+        (summary.statement_hits, summary.statement_count),
+        (summary.branch_hits, summary.branch_count),
+        (summary.condition_hits, summary.condition_count),
+        (summary.function_hits, summary.function_count),
+        (summary.modifier_hits, summary.modifier_count),
+    ]
+    .into_iter()
+    .map(|(hits, total)| {
+        let percentage = if total == 0 { 1. } else { hits as f64 / total as f64 };
+        let class = match percentage {
+            _ if total == 0 => "",
+            _ if percentage < 0.5 => "low",
+            _ if percentage < 0.75 => "medium",
+            _ => "high",
+        };
+        format!(
+            "<td class=\"{class}\" data-value=\"{percentage}\">{:.2}% ({hits}/{total})</td>",
+            percentage * 100.
+        )
+    })
+    .collect()
+}
+
+/// Returns the number of directories in `path`.
+fn depth(path: &Path) -> usize {
+    path.components().count().saturating_sub(1)
+}
+
+/// Coverage of a single source line.
+#[derive(Default)]
+struct LineCoverage {
+    /// The line hits, if the line is executable.
+    hits: Option<u32>,
+    /// The number of branch and condition paths on this line.
+    branches: usize,
+    /// The number of branch and condition paths on this line that were hit.
+    branch_hits: usize,
+}
+
+/// Groups the line, branch and condition items of a source file by line.
+fn line_coverage(items: &[&CoverageItem]) -> BTreeMap<u32, LineCoverage> {
+    let mut lines = BTreeMap::<u32, LineCoverage>::new();
+    for item in items {
+        let line = lines.entry(item.loc.lines.start).or_default();
+        match item.kind {
+            CoverageItemKind::Line => {
+                line.hits = Some(line.hits.unwrap_or(0).max(item.hits));
+            }
+            CoverageItemKind::Branch { .. } | CoverageItemKind::Condition { .. } => {
+                line.branches += 1;
+                if item.hits > 0 {
+                    line.branch_hits += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// Returns the number of branch and condition paths.
+fn branch_count(summary: &CoverageSummary) -> usize {
+    summary.branch_count + summary.condition_count
+}
+
+/// Returns the number of branch and condition paths that were hit.
+fn branch_hits(summary: &CoverageSummary) -> usize {
+    summary.branch_hits + summary.condition_hits
+}
+
+/// Formats a coverage ratio as expected by Cobertura.
+fn rate(hits: usize, total: usize) -> String {
+    if total == 0 {
+        "1".to_string()
+    } else {
+        format!("{:.4}", hits as f64 / total as f64)
+    }
+}
+
+/// Escapes the XML special characters in `s`. Also valid for HTML text and attributes.
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reports the coverage of the lines changed relative to a git ref.
+///
+/// Fails if the percentage of covered changed lines is below the threshold.
+pub struct DiffReporter {
+    root: PathBuf,
+    base: String,
+    threshold: f64,
+}
+
+impl DiffReporter {
+    /// Create a new diff reporter.
+    pub fn new(root: PathBuf, base: String, threshold: f64) -> Self {
+        Self { root, base, threshold }
+    }
+}
+
+impl CoverageReporter for DiffReporter {
+    fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
+        let diff = Git::new(&self.root)
+            .cmd()
+            .args(["diff", "--unified=0", "--no-color", "--no-ext-diff", "--relative"])
+            .arg(&self.base)
+            .arg("--")
+            .get_stdout_lossy()?;
+        let mut changed = changed_lines(&diff);
+
+        // Untracked files are not part of the diff, but all of their lines are new.
+        let untracked = Git::new(&self.root)
+            .cmd()
+            .args(["ls-files", "--others", "--exclude-standard"])
+            .get_stdout_lossy()?;
+        for path in untracked.lines().map(PathBuf::from) {
+            let Ok(content) = fs::read_to_string(self.root.join(&path)) else { continue };
+            changed.entry(path).or_default().extend(1..=content.lines().count() as u32);
+        }
+
+        let mut table = Table::new();
+        table.apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_header(vec![
+            Cell::new("File"),
+            Cell::new("% Changed Lines"),
+            Cell::new("Uncovered Lines"),
+        ]);
+
+        let (mut total_hits, mut total_count) = (0, 0);
+        for (path, items) in report.items_by_file() {
+            let Some(changed) = changed.get(path) else { continue };
+            let mut uncovered = Vec::new();
+            let mut count = 0;
+            for (line, coverage) in line_coverage(&items) {
+                let Some(hits) = coverage.hits else { continue };
+                if !changed.contains(&line) {
+                    continue;
+                }
+                count += 1;
+                if hits == 0 {
+                    uncovered.push(line);
+                }
+            }
+            if count == 0 {
+                continue;
+            }
+
+            let hits = count - uncovered.len();
+            total_hits += hits;
+            total_count += count;
+            table.add_row(vec![
+                Cell::new(path.display()),
+                format_cell(hits, count),
+                Cell::new(format_line_ranges(&uncovered)),
+            ]);
+        }
+        table.add_row(vec![
+            Cell::new("Total"),
+            format_cell(total_hits, total_count),
+            Cell::new(""),
+        ]);
+        sh_println!("\nCoverage of lines changed since {}:\n{table}", self.base)?;
+
+        let percentage =
+            if total_count == 0 { 100. } else { total_hits as f64 / total_count as f64 * 100. };
+        if percentage < self.threshold {
+            eyre::bail!(
+                "coverage of changed lines is {percentage:.2}%, below the threshold of {:.2}%",
+                self.threshold
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the lines added or modified in each file from the output of `git diff --unified=0`.
+fn changed_lines(diff: &str) -> HashMap<PathBuf, HashSet<u32>> {
+    let mut changed = HashMap::<PathBuf, HashSet<u32>>::default();
+    let mut file = None;
+    let mut in_header = false;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            in_header = true;
+            file = None;
+        } else if in_header && line.starts_with("+++ ") {
+            // Deleted files are compared against `/dev/null`.
+            file = line.strip_prefix("+++ b/").map(PathBuf::from);
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            in_header = false;
+            let Some(file) = &file else { continue };
+            // `@@ -<start>[,<count>] +<start>[,<count>] @@`
+            let Some(range) = hunk.split(' ').nth(1).and_then(|range| range.strip_prefix('+'))
+            else {
+                continue;
+            };
+            let (start, count) = match range.split_once(',') {
+                Some((start, count)) => (start.parse::<u32>(), count.parse::<u32>()),
+                None => (range.parse::<u32>(), Ok(1)),
+            };
+            let (Ok(start), Ok(count)) = (start, count) else { continue };
+            changed.entry(file.clone()).or_default().extend(start..start + count);
+        }
+    }
+    changed
+}
+
+/// Formats sorted line numbers as comma-separated ranges, e.g. `3-5, 8`.
+fn format_line_ranges(lines: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for &line in lines {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(start, end)| if start == end { start.to_string() } else { format!("{start}-{end}") })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// A super verbose reporter for debugging coverage while it is still unstable.
 pub struct DebugReporter;
 
@@ -332,3 +867,46 @@ pub fn get_position(&mut self, path: &Path, offset: usize) -> eyre::Result<(usiz
         Ok((lo, pos))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_changed_lines() {
+        let diff = "\
+diff --git a/src/A.sol b/src/A.sol
+index 1111111..2222222 100644
+--- a/src/A.sol
++++ b/src/A.sol
+@@ -3,0 +4,3 @@ contract A {
++    function foo() public {
+++++ x;
++    }
+@@ -10 +13 @@ contract A {
+-    uint a;
++    uint b;
+@@ -20,2 +22,0 @@ contract A {
+-    uint c;
+-    uint d;
+diff --git a/src/B.sol b/src/B.sol
+deleted file mode 100644
+--- a/src/B.sol
++++ /dev/null
+@@ -1,2 +0,0 @@
+-contract B {
+-}
+";
+        let changed = changed_lines(diff);
+        assert_eq!(changed.len(), 1);
+        let mut lines = changed[Path::new("src/A.sol")].iter().copied().collect::<Vec<_>>();
+        lines.sort_unstable();
+        assert_eq!(lines, [4, 5, 6, 13]);
+    }
+
+    #[test]
+    fn line_ranges() {
+        assert_eq!(format_line_ranges(&[]), "");
+        assert_eq!(format_line_ranges(&[3, 4, 5, 8, 10, 11]), "3-5, 8, 10-11");
+    }
+}
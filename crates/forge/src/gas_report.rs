@@ -4,7 +4,7 @@
     constants::{CHEATCODE_ADDRESS, HARDHAT_CONSOLE_ADDRESS},
     traces::{CallTraceArena, CallTraceDecoder, CallTraceNode, DecodedCallData},
 };
-use alloy_primitives::map::HashSet;
+use alloy_primitives::{hex, map::HashSet};
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Cell, Color, Table};
 use foundry_common::{
     calc,
@@ -13,9 +13,28 @@
 };
 use foundry_evm::traces::CallKind;
 
+use eyre::Context;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::{collections::BTreeMap, fmt::Display};
+use std::{collections::BTreeMap, fmt::Display, path::Path};
+
+/// The format of the gas report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum GasReportFormat {
+    /// One table per contract.
+    Text,
+    /// A JSON array with one object per contract.
+    Json,
+}
+
+impl From<GasReportFormat> for ReportKind {
+    fn from(format: GasReportFormat) -> Self {
+        match format {
+            GasReportFormat::Text => Self::Text,
+            GasReportFormat::Json => Self::JSON,
+        }
+    }
+}
 
 /// Represents the gas report for a set of contracts.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -33,6 +52,11 @@ pub struct GasReport {
     /// All contracts that were analyzed grouped by their identifier
     /// ``test/Counter.t.sol:CounterTest
     pub contracts: BTreeMap<String, ContractInfo>,
+    /// The gas used by each call path, excluding the gas used by nested calls.
+    ///
+    /// Paths are `;`-separated `Contract::function` frames, starting from the test function.
+    #[serde(default)]
+    pub call_paths: BTreeMap<String, u64>,
 }
 
 impl GasReport {
@@ -54,6 +78,12 @@ pub fn new(
         }
     }
 
+    /// Sets the format of the report, overriding the one of the current shell.
+    pub fn with_format(mut self, format: GasReportFormat) -> Self {
+        self.report_kind = format.into();
+        self
+    }
+
     /// Whether the given contract should be reported.
     #[instrument(level = "trace", skip(self), ret)]
     fn should_report(&self, contract_name: &str) -> bool {
@@ -80,15 +110,54 @@ pub async fn analyze(
         arenas: impl IntoIterator<Item = &CallTraceArena>,
         decoder: &CallTraceDecoder,
     ) {
-        for node in arenas.into_iter().flat_map(|arena| arena.nodes()) {
-            self.analyze_node(node, decoder).await;
+        for arena in arenas {
+            let mut paths = Vec::with_capacity(arena.nodes().len());
+            for node in arena.nodes() {
+                self.analyze_node(node, decoder).await;
+                let path = self.analyze_call_path(arena, node, &paths, decoder).await;
+                paths.push(path);
+            }
+        }
+    }
+
+    /// Attributes the gas used by the given node, excluding nested calls, to its call path.
+    ///
+    /// `paths` holds the call paths of the previous nodes of the arena, parents always precede
+    /// their children. Returns the call path of the node, if it is not ignored.
+    async fn analyze_call_path(
+        &mut self,
+        arena: &CallTraceArena,
+        node: &CallTraceNode,
+        paths: &[Option<String>],
+        decoder: &CallTraceDecoder,
+    ) -> Option<String> {
+        if is_ignored_call(node) {
+            return None;
         }
+
+        let frame = call_frame(node, decoder).await;
+        let path = match node.parent.and_then(|parent| paths[parent].as_deref()) {
+            Some(parent) => format!("{parent};{frame}"),
+            None => frame,
+        };
+
+        let nested_gas: u64 = node
+            .children
+            .iter()
+            .map(|&child| &arena.nodes()[child])
+            .filter(|child| !is_ignored_call(child))
+            .map(|child| child.trace.gas_used)
+            .sum();
+        *self.call_paths.entry(path.clone()).or_default() +=
+            node.trace.gas_used.saturating_sub(nested_gas);
+
+        Some(path)
     }
 
     async fn analyze_node(&mut self, node: &CallTraceNode, decoder: &CallTraceDecoder) {
         let trace = &node.trace;
 
-        if trace.address == CHEATCODE_ADDRESS || trace.address == HARDHAT_CONSOLE_ADDRESS {
+        if is_ignored_call(node) {
             return;
         }
 
@@ -154,6 +223,63 @@ pub fn finalize(mut self) -> Self {
     }
 }
 
+impl GasReport {
+    /// Merges the finalized report of another project, e.g. of another workspace member, into
+    /// this one.
+    #[must_use]
+    pub fn merge(mut self, other: Self) -> Self {
+        for (name, other) in other.contracts {
+            let contract = self.contracts.entry(name).or_default();
+            if other.gas != 0 {
+                contract.gas = other.gas;
+                contract.size = other.size;
+            }
+            for (fname, sigs) in other.functions {
+                let functions = contract.functions.entry(fname).or_default();
+                for (sig, gas_info) in sigs {
+                    functions.entry(sig).or_default().frames.extend(gas_info.frames);
+                }
+            }
+        }
+        for (path, gas) in other.call_paths {
+            *self.call_paths.entry(path).or_default() += gas;
+        }
+        self.finalize()
+    }
+
+    /// Writes the gas used by each call path in the folded stack format, one `path gas` line per
+    /// call path.
+    ///
+    /// If the file has an `svg` extension, a flamegraph is rendered instead.
+    pub fn write_call_paths(&self, path: &Path) -> eyre::Result<()> {
+        let lines = self
+            .call_paths
+            .iter()
+            .filter(|(_, gas)| **gas > 0)
+            .map(|(path, gas)| format!("{path} {gas}"))
+            .collect::<Vec<_>>();
+
+        if path.extension().is_some_and(|ext| ext == "svg") {
+            let file = std::fs::File::create(path).wrap_err("failed to create file")?;
+            let mut options = inferno::flamegraph::Options::default();
+            options.title = "Gas report".to_string();
+            options.count_name = "gas".to_string();
+            inferno::flamegraph::from_lines(
+                &mut options,
+                lines.iter().map(String::as_str),
+                std::io::BufWriter::new(file),
+            )
+            .wrap_err("failed to write svg")?;
+        } else {
+            let mut folded = lines.join("\n");
+            folded.push('\n');
+            foundry_common::fs::write(path, folded)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Display for GasReport {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self.report_kind {
@@ -262,6 +388,33 @@ fn format_table_output(&self, contract: &ContractInfo, name: &str) -> Table {
     }
 }
 
+/// Returns whether the call is to the cheatcode or console address, which are not reported.
+fn is_ignored_call(node: &CallTraceNode) -> bool {
+    node.trace.address == CHEATCODE_ADDRESS || node.trace.address == HARDHAT_CONSOLE_ADDRESS
+}
+
+/// Returns the `Contract::function` frame of a call, falling back to the address and selector if
+/// they can't be decoded.
+async fn call_frame(node: &CallTraceNode, decoder: &CallTraceDecoder) -> String {
+    let trace = &node.trace;
+    let contract = match decoder.contracts.get(&trace.address) {
+        Some(name) => name.rsplit(':').next().unwrap_or(name).to_string(),
+        None => trace.address.to_string(),
+    };
+    let function = if trace.kind.is_any_create() {
+        "constructor".to_string()
+    } else if let Some(DecodedCallData { signature, .. }) =
+        decoder.decode_function(trace).await.call_data
+    {
+        signature.split('(').next().unwrap_or(&signature).to_string()
+    } else if trace.data.len() >= 4 {
+        hex::encode_prefixed(&trace.data[..4])
+    } else {
+        "fallback".to_string()
+    };
+    format!("{contract}::{function}")
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ContractInfo {
     pub gas: u64,
@@ -281,3 +434,37 @@ pub struct GasInfo {
     #[serde(skip)]
     pub frames: Vec<u64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(contract: &str, frames: &[u64]) -> GasReport {
+        let mut report = GasReport::default();
+        let info = report.contracts.entry(contract.to_string()).or_default();
+        info.gas = 100;
+        info.functions
+            .entry("increment".to_string())
+            .or_default()
+            .entry("increment()".to_string())
+            .or_default()
+            .frames
+            .extend(frames);
+        report.call_paths.insert("CounterTest::test;Counter::increment".to_string(), 10);
+        report.finalize()
+    }
+
+    #[test]
+    fn merges_reports() {
+        let merged = report("a/Counter.sol:Counter", &[1, 3])
+            .merge(report("b/Counter.sol:Counter", &[5]))
+            .merge(report("a/Counter.sol:Counter", &[2]));
+
+        assert_eq!(merged.contracts.len(), 2);
+        let a = &merged.contracts["a/Counter.sol:Counter"].functions["increment"]["increment()"];
+        assert_eq!((a.calls, a.min, a.median, a.max), (3, 1, 2, 3));
+        let b = &merged.contracts["b/Counter.sol:Counter"].functions["increment"]["increment()"];
+        assert_eq!(b.calls, 1);
+        assert_eq!(merged.call_paths["CounterTest::test;Counter::increment"], 30);
+    }
+}
@@ -16,6 +16,8 @@
 
 pub mod gas_report;
 
+pub mod mutation;
+
 pub mod multi_runner;
 pub use multi_runner::{MultiContractRunner, MultiContractRunnerBuilder};
 
@@ -23,8 +25,13 @@
 pub use runner::ContractRunner;
 
 mod progress;
+pub mod prove;
 pub mod result;
 
+pub mod sarif;
+
+pub mod yul;
+
 // TODO: remove
 pub use foundry_common::traits::TestFilter;
 pub use foundry_evm::*;
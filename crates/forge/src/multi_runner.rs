@@ -58,6 +58,9 @@ pub struct MultiContractRunner {
     pub libs_to_deploy: Vec<Bytes>,
     /// Library addresses used to link contracts.
     pub libraries: Libraries,
+    /// Names and addresses of the libraries pinned in `chain_libraries` for the chain the tests
+    /// run on, which are linked instead of being deployed.
+    pub pinned_libraries: Vec<(String, Address)>,
 
     /// The fork to use at launch
     pub fork: Option<CreateFork>,
@@ -303,9 +306,23 @@ pub fn reconfigure_with(&mut self, config: Arc<Config>) {
         self.odyssey = config.odyssey;
         self.isolation = config.isolate;
 
+        // Only override the environment values that were changed by the new config.
+        if config.chain != self.config.chain {
+            if let Some(chain) = config.chain {
+                self.evm_opts.env.chain_id = Some(chain.id());
+                self.env.cfg.chain_id = chain.id();
+            }
+        }
+        if config.block_base_fee_per_gas != self.config.block_base_fee_per_gas {
+            self.evm_opts.env.block_base_fee_per_gas = config.block_base_fee_per_gas;
+            self.env.block.basefee = U256::from(config.block_base_fee_per_gas);
+        }
+        if config.block_prevrandao != self.config.block_prevrandao {
+            self.evm_opts.env.block_prevrandao = config.block_prevrandao;
+            self.env.block.prevrandao = Some(config.block_prevrandao);
+        }
+
         // Specific to Forge, not present in config.
-        // TODO: self.evm_opts
-        // TODO: self.env
         // self.coverage = N/A;
         // self.debug = N/A;
         // self.decode_internal = N/A;
@@ -315,8 +332,6 @@ pub fn reconfigure_with(&mut self, config: Arc<Config>) {
 
     /// Configures the given executor with this configuration.
     pub fn configure_executor(&self, executor: &mut Executor) {
-        // TODO: See above
-
         let inspector = executor.inspector_mut();
         // inspector.set_env(&self.env);
         if let Some(cheatcodes) = inspector.cheatcodes.as_mut() {
@@ -335,6 +350,27 @@ pub fn configure_executor(&self, executor: &mut Executor) {
         executor.set_legacy_assertions(self.config.legacy_assertions);
     }
 
+    /// Applies the environment values that differ from `prev_env` to the given executor.
+    ///
+    /// Values that were not overridden are left untouched, so that changes made to the
+    /// environment in `setUp` are kept.
+    pub fn configure_executor_env(
+        &self,
+        prev_env: &revm::primitives::Env,
+        executor: &mut Executor,
+    ) {
+        let env = executor.env_mut();
+        if self.env.cfg.chain_id != prev_env.cfg.chain_id {
+            env.cfg.chain_id = self.env.cfg.chain_id;
+        }
+        if self.env.block.basefee != prev_env.block.basefee {
+            env.block.basefee = self.env.block.basefee;
+        }
+        if self.env.block.prevrandao != prev_env.block.prevrandao {
+            env.block.prevrandao = self.env.block.prevrandao;
+        }
+    }
+
     /// Creates a new executor with this configuration.
     pub fn executor(
         &self,
@@ -483,8 +519,18 @@ pub fn build<C: Compiler<CompilerContract = Contract>>(
             .filter_map(|(_, contract)| contract.abi.as_ref().map(|abi| abi.borrow()));
         let revert_decoder = RevertDecoder::new().with_abis(abis);
 
+        // Libraries pinned for the chain the tests run on are linked at their deployed address,
+        // all other external libraries are deployed by the library deployer.
+        let pinned = self.config.chain_libraries_with_remappings(env.cfg.chain_id)?;
+        let pinned_libraries = pinned
+            .libs
+            .values()
+            .flat_map(|libs| libs.iter())
+            .map(|(name, address)| Ok((name.clone(), address.parse::<Address>()?)))
+            .collect::<Result<Vec<_>>>()?;
+
         let LinkOutput { libraries, libs_to_deploy } = linker.link_with_nonce_or_address(
-            Default::default(),
+            pinned,
             LIBRARY_DEPLOYER,
             0,
             linker.contracts.keys(),
@@ -521,6 +567,7 @@ pub fn build<C: Compiler<CompilerContract = Contract>>(
             known_contracts,
             libs_to_deploy,
             libraries,
+            pinned_libraries,
 
             fork: self.fork,
 
@@ -0,0 +1,297 @@
+//! Mutation operators applied to Solidity sources by `forge mutate`.
+
+use similar::TextDiff;
+use solar_parse::{
+    ast::{self, visit::Visit, Arena},
+    interface::{Session, Span},
+    Parser as SolarParser,
+};
+use std::{
+    fmt,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
+
+/// The kind of change made by a mutant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MutationOperator {
+    /// Replaces a binary or compound assignment operator, e.g. `+` with `-`.
+    BinaryOperator,
+    /// Deletes a `require` or `assert` statement.
+    RequireDeletion,
+    /// Changes a number or boolean literal, e.g. `1` to `2`.
+    Constant,
+}
+
+impl fmt::Display for MutationOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::BinaryOperator => "binary operator",
+            Self::RequireDeletion => "require deletion",
+            Self::Constant => "constant",
+        })
+    }
+}
+
+/// A single change to a source file.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mutant {
+    pub path: PathBuf,
+    /// The byte range of the replaced code.
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+    pub operator: MutationOperator,
+}
+
+impl Mutant {
+    /// Returns `source` with the mutation applied.
+    pub fn apply(&self, source: &str) -> String {
+        let mut mutated = source.to_string();
+        mutated.replace_range(self.start..self.end, &self.replacement);
+        mutated
+    }
+
+    /// Returns the `line:column` of the mutation in `source`, both starting at 1.
+    pub fn location(&self, source: &str) -> (usize, usize) {
+        let before = &source[..self.start];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        (line, column)
+    }
+
+    /// Returns a one line description of the mutation, e.g. ``replaced `+` with `-` ``.
+    pub fn describe(&self, source: &str) -> String {
+        let original = &source[self.start..self.end];
+        match self.operator {
+            MutationOperator::RequireDeletion => format!("deleted `{original}`"),
+            _ => format!("replaced `{original}` with `{}`", self.replacement),
+        }
+    }
+
+    /// Returns the unified diff of the mutation.
+    pub fn diff(&self, source: &str) -> String {
+        let mutated = self.apply(source);
+        let path = self.path.display();
+        TextDiff::from_lines(source, &mutated)
+            .unified_diff()
+            .context_radius(2)
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string()
+    }
+}
+
+/// Returns the mutants of the Solidity file at `path` with content `source`.
+///
+/// Returns an empty list if the source can't be parsed.
+pub fn mutants(path: &Path, source: &str) -> Vec<Mutant> {
+    let sess = Session::builder().with_silent_emitter(None).build();
+    let mut visitor = MutantVisitor { source, base: None, mutants: Vec::new() };
+    let parsed = sess.enter(|| -> solar_parse::interface::Result<()> {
+        let arena = Arena::new();
+        let mut parser = SolarParser::from_source_code(
+            &sess,
+            &arena,
+            path.to_path_buf().into(),
+            source.to_string(),
+        )?;
+        let ast = parser.parse_file().map_err(|e| e.emit())?;
+        if let Some(item) = ast.items.first() {
+            visitor.base = Some(sess.source_map().lookup_source_file(item.span.lo()).start_pos.0);
+        }
+        let _ = visitor.visit_source_unit(&ast);
+        Ok(())
+    });
+    if parsed.is_err() {
+        return Vec::new();
+    }
+
+    let mut mutants = visitor
+        .mutants
+        .into_iter()
+        .filter(|(start, end, _, _)| start <= end && *end <= source.len())
+        .map(|(start, end, replacement, operator)| Mutant {
+            path: path.to_path_buf(),
+            start,
+            end,
+            replacement,
+            operator,
+        })
+        .collect::<Vec<_>>();
+    mutants.sort();
+    mutants.dedup();
+    mutants
+}
+
+/// Returns the replacements of a binary or compound assignment operator.
+fn swap_operator(op: &str) -> Option<&'static str> {
+    Some(match op {
+        "+" => "-",
+        "-" => "+",
+        "*" => "/",
+        "/" => "*",
+        "%" => "*",
+        "**" => "*",
+        "<" => "<=",
+        "<=" => "<",
+        ">" => ">=",
+        ">=" => ">",
+        "==" => "!=",
+        "!=" => "==",
+        "&&" => "||",
+        "||" => "&&",
+        "&" => "|",
+        "|" => "&",
+        "^" => "&",
+        "<<" => ">>",
+        ">>" => "<<",
+        "+=" => "-=",
+        "-=" => "+=",
+        "*=" => "/=",
+        "/=" => "*=",
+        _ => return None,
+    })
+}
+
+/// Returns the replacements of a number or boolean literal.
+fn tweak_constant(literal: &str) -> Vec<String> {
+    match literal {
+        "true" => return vec!["false".to_string()],
+        "false" => return vec!["true".to_string()],
+        _ => {}
+    }
+    let Ok(value) = literal.replace('_', "").parse::<u128>() else { return Vec::new() };
+    match value {
+        0 => vec!["1".to_string()],
+        u128::MAX => vec![(value - 1).to_string()],
+        _ => vec![(value + 1).to_string(), (value - 1).to_string()],
+    }
+}
+
+struct MutantVisitor<'a> {
+    source: &'a str,
+    /// The position of the file in the source map.
+    base: Option<u32>,
+    mutants: Vec<(usize, usize, String, MutationOperator)>,
+}
+
+impl MutantVisitor<'_> {
+    fn range(&self, span: Span) -> Option<(usize, usize)> {
+        let base = self.base?;
+        let start = span.lo().0.checked_sub(base)? as usize;
+        let end = span.hi().0.checked_sub(base)? as usize;
+        self.source.get(start..end).map(|_| (start, end))
+    }
+
+    fn push_binary_operator(&mut self, span: Span, assign: bool) {
+        let Some((start, mut end)) = self.range(span) else { return };
+        // compound assignments are replaced as a whole, e.g. `+=` with `-=`
+        if assign && !self.source[start..end].ends_with('=') && self.source[end..].starts_with('=')
+        {
+            end += 1;
+        }
+        if let Some(replacement) = swap_operator(&self.source[start..end]) {
+            self.mutants.push((
+                start,
+                end,
+                replacement.to_string(),
+                MutationOperator::BinaryOperator,
+            ));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for MutantVisitor<'_> {
+    type BreakValue = solar_parse::interface::data_structures::Never;
+
+    fn visit_stmt(&mut self, stmt: &'ast ast::Stmt<'ast>) -> ControlFlow<Self::BreakValue> {
+        if let ast::StmtKind::Expr(expr) = &stmt.kind {
+            if let ast::ExprKind::Call(callee, _) = &expr.kind {
+                if let ast::ExprKind::Ident(ident) = &callee.kind {
+                    if matches!(ident.as_str(), "require" | "assert") {
+                        if let Some((start, end)) = self.range(expr.span) {
+                            // replaced with an empty block, valid wherever a statement is
+                            if let Some(semicolon) = self.source[end..].find(';') {
+                                self.mutants.push((
+                                    start,
+                                    end + semicolon + 1,
+                                    "{}".to_string(),
+                                    MutationOperator::RequireDeletion,
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        self.walk_stmt(stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'ast ast::Expr<'ast>) -> ControlFlow<Self::BreakValue> {
+        match &expr.kind {
+            ast::ExprKind::Binary(_, op, _) => self.push_binary_operator(op.span, false),
+            ast::ExprKind::Assign(_, Some(op), _) => self.push_binary_operator(op.span, true),
+            ast::ExprKind::Lit(..) => {
+                if let Some((start, end)) = self.range(expr.span) {
+                    for replacement in tweak_constant(&self.source[start..end]) {
+                        self.mutants.push((start, end, replacement, MutationOperator::Constant));
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.walk_expr(expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+contract Counter {
+    uint256 public number;
+
+    function increment(uint256 by) public {
+        require(by > 0, "zero");
+        number += by + 1;
+    }
+}
+"#;
+
+    #[test]
+    fn generates_mutants() {
+        let path = Path::new("src/Counter.sol");
+        let mutants = mutants(path, SOURCE);
+        let described = mutants
+            .iter()
+            .map(|mutant| format!("{} {}", mutant.operator, mutant.describe(SOURCE)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            described,
+            [
+                "require deletion deleted `require(by > 0, \"zero\");`",
+                "binary operator replaced `>` with `>=`",
+                "constant replaced `0` with `1`",
+                "binary operator replaced `+=` with `-=`",
+                "binary operator replaced `+` with `-`",
+                "constant replaced `1` with `0`",
+                "constant replaced `1` with `2`",
+            ]
+        );
+
+        let mutant = &mutants[4];
+        assert_eq!(mutant.location(SOURCE), (7, 22));
+        assert!(mutant.apply(SOURCE).contains("number += by - 1;"));
+        assert!(mutant
+            .diff(SOURCE)
+            .contains("-        number += by + 1;\n+        number += by - 1;"));
+    }
+
+    #[test]
+    fn tweaks_constants() {
+        assert_eq!(tweak_constant("1_000"), ["1001", "999"]);
+        assert_eq!(tweak_constant("true"), ["false"]);
+        assert!(tweak_constant("0x10").is_empty());
+    }
+}
@@ -1,9 +1,10 @@
 use crate::cmd::{
-    bind::BindArgs, bind_json, build::BuildArgs, cache::CacheArgs, clone::CloneArgs,
-    compiler::CompilerArgs, config, coverage, create::CreateArgs, doc::DocArgs, eip712, flatten,
-    fmt::FmtArgs, geiger, generate, init::InitArgs, inspect, install::InstallArgs,
+    audit_deps::AuditDepsArgs, bind::BindArgs, bind_json, bind_ts::BindTsArgs, build::BuildArgs,
+    cache::CacheArgs, clone::CloneArgs, compiler::CompilerArgs, config, coverage,
+    create::CreateArgs, doc::DocArgs, eip712, flatten, fmt::FmtArgs, gas_check, geiger, generate,
+    init::InitArgs, inspect, install::InstallArgs, migrate::MigrateArgs, mutate, prove,
     remappings::RemappingArgs, remove::RemoveArgs, selectors::SelectorsSubcommands, snapshot,
-    soldeer, test, tree, update,
+    soldeer, storage_diff, test, tree, update, validate_upgrade,
 };
 use clap::{Parser, Subcommand, ValueHint};
 use forge_script::ScriptArgs;
@@ -42,15 +43,24 @@ pub enum ForgeSubcommand {
     /// Generate coverage reports.
     Coverage(coverage::CoverageArgs),
 
+    /// Prove tests with symbolic execution.
+    Prove(prove::ProveArgs),
+
+    /// Run the tests against mutated sources, reporting the mutations no test detects.
+    Mutate(mutate::MutateArgs),
+
     /// Generate Rust bindings for smart contracts.
     #[command(alias = "bi")]
     Bind(BindArgs),
 
+    /// Generate TypeScript bindings for smart contracts, compatible with viem and ethers.
+    BindTs(BindTsArgs),
+
     /// Build the project's smart contracts.
     #[command(visible_aliases = ["b", "compile"])]
     Build(BuildArgs),
 
-    /// Clone a contract from Etherscan.
+    /// Clone a verified contract from Etherscan or Sourcify.
     Clone(CloneArgs),
 
     /// Update one or multiple dependencies.
@@ -92,6 +102,9 @@ pub enum ForgeSubcommand {
     /// Create a new Forge project.
     Init(InitArgs),
 
+    /// Migrate a Hardhat or Truffle project to Foundry.
+    Migrate(MigrateArgs),
+
     /// Generate shell completions script.
     #[command(visible_alias = "com")]
     Completions {
@@ -112,6 +125,12 @@ pub enum ForgeSubcommand {
         /// or the current working directory.
         #[arg(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
         root: Option<PathBuf>,
+
+        /// Only remove the artifacts and cache entries of sources which no longer exist.
+        ///
+        /// Artifacts of the remaining sources are kept, so they are not recompiled.
+        #[arg(long)]
+        stale: bool,
     },
 
     /// Manage the Foundry cache.
@@ -121,6 +140,9 @@ pub enum ForgeSubcommand {
     #[command(visible_alias = "s")]
     Snapshot(snapshot::GasSnapshotArgs),
 
+    /// Compare the gas report of the tests against a baseline.
+    GasCheck(gas_check::GasCheckArgs),
+
     /// Display the current config.
     #[command(visible_alias = "co")]
     Config(config::ConfigArgs),
@@ -136,6 +158,12 @@ pub enum ForgeSubcommand {
     #[command(visible_alias = "in")]
     Inspect(inspect::InspectArgs),
 
+    /// Compare the storage layouts of two implementations of an upgradeable contract.
+    StorageDiff(storage_diff::StorageDiffArgs),
+
+    /// Check that contracts are safe to use as implementations of upgradeable proxies.
+    ValidateUpgrade(validate_upgrade::ValidateUpgradeArgs),
+
     /// Display a tree visualization of the project's dependency graph.
     #[command(visible_alias = "tr")]
     Tree(tree::TreeArgs),
@@ -143,6 +171,9 @@ pub enum ForgeSubcommand {
     /// Detects usage of unsafe cheat codes in a project and its dependencies.
     Geiger(geiger::GeigerArgs),
 
+    /// Checks the installed dependencies against known vulnerabilities.
+    AuditDeps(AuditDepsArgs),
+
     /// Generate documentation for the project.
     Doc(DocArgs),
 
@@ -0,0 +1,781 @@
+//! Symbolic execution of the runtime bytecode of a test contract.
+
+use super::{
+    solver::{SatResult, Solver},
+    term::{Byte, Constraint, Op, Term, Word},
+};
+use crate::revm::interpreter::{opcode as op, OpCode};
+use alloy_primitives::{
+    hex, keccak256,
+    map::{HashMap, HashSet},
+    Address, I256, U256,
+};
+use eyre::Result;
+use foundry_evm::{
+    constants::{CHEATCODE_ADDRESS, HARDHAT_CONSOLE_ADDRESS},
+    decode::RevertDecoder,
+};
+use std::sync::{Arc, LazyLock};
+
+/// The maximum size of the memory of a path.
+const MEMORY_LIMIT: usize = 1 << 20;
+
+/// The maximum size of the stack.
+const STACK_LIMIT: usize = 1024;
+
+/// The concrete environment of the symbolic execution.
+#[derive(Clone, Debug)]
+pub struct Env {
+    pub address: Address,
+    pub caller: Address,
+    pub timestamp: U256,
+    pub number: U256,
+    pub chain_id: U256,
+}
+
+/// Bounds of the symbolic execution.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// The maximum number of times a path branches on a symbolic condition at the same
+    /// instruction, bounding loops with symbolic conditions.
+    pub loop_bound: usize,
+    /// The maximum number of instructions executed by a path.
+    pub max_steps: usize,
+}
+
+/// The state of an execution path.
+#[derive(Clone, Debug)]
+pub struct State {
+    pc: usize,
+    stack: Vec<Word>,
+    memory: Vec<Byte>,
+    returndata: Vec<Byte>,
+    constraints: Vec<Constraint>,
+    /// Number of branches on a symbolic condition, by instruction.
+    forks: HashMap<usize, usize>,
+    steps: usize,
+}
+
+impl State {
+    /// Returns the initial state of a call constrained by `constraints`.
+    pub fn new(constraints: Vec<Constraint>) -> Self {
+        Self {
+            pc: 0,
+            stack: Vec::new(),
+            memory: Vec::new(),
+            returndata: Vec::new(),
+            constraints,
+            forks: HashMap::default(),
+            steps: 0,
+        }
+    }
+}
+
+/// How a test failed on a path.
+#[derive(Clone, Debug)]
+pub enum Failure {
+    /// The call reverted with the given data.
+    Revert(Vec<Byte>),
+    /// The call halted exceptionally.
+    Halt(&'static str),
+    /// An assertion cheatcode failed, with its custom message and failed comparison, if any.
+    Assertion { message: Option<String>, comparison: Option<Comparison> },
+}
+
+/// A comparison made by an assertion cheatcode.
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    left: Word,
+    right: Word,
+    /// The operator displayed when the comparison fails, e.g. `!=` for `assertEq`.
+    failed_op: &'static str,
+    kind: ValueKind,
+}
+
+impl Failure {
+    /// Returns the failure reason with the given values of the variables, formatted like the
+    /// reason of the failure of a concrete test.
+    pub fn reason(&self, model: &HashMap<Arc<str>, U256>) -> String {
+        match self {
+            Self::Revert(data) => {
+                let data = data.iter().map(|byte| byte.eval(model).unwrap_or_default());
+                RevertDecoder::new().decode(&data.collect::<Vec<_>>(), None)
+            }
+            Self::Halt(reason) => reason.to_string(),
+            Self::Assertion { message, comparison } => {
+                let message = message.as_deref().unwrap_or("assertion failed");
+                match comparison {
+                    Some(Comparison { left, right, failed_op, kind }) => {
+                        let format = |word: &Word| {
+                            word.eval(model).map_or_else(|| "?".to_string(), |v| kind.format(v))
+                        };
+                        format!("{message}: {} {failed_op} {}", format(left), format(right))
+                    }
+                    None => message.to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// How a path ended.
+#[derive(Debug)]
+enum PathEnd {
+    /// The call returned.
+    Return,
+    /// The path was rejected by `vm.assume`.
+    Rejected,
+    /// The test failed.
+    Fail(Failure),
+    /// The path reached an operation which isn't supported.
+    Unsupported(String),
+    /// The path reached the loop bound or the step limit.
+    Bounded,
+}
+
+/// The result of the exploration of the paths of a call.
+#[derive(Debug, Default)]
+pub struct Exploration {
+    /// The number of explored paths.
+    pub paths: usize,
+    /// The first failure found, with the values of the variables reaching it.
+    pub failure: Option<(Failure, HashMap<Arc<str>, U256>)>,
+    /// The first unsupported operation reached by a feasible path, if any.
+    pub unsupported: Option<String>,
+    /// Whether feasible paths were not fully explored because of the limits.
+    pub bounded: bool,
+}
+
+/// The paths left to explore.
+struct Worklist {
+    states: Vec<State>,
+    /// The paths which failed an assertion, with their constraints.
+    failures: Vec<(Failure, Vec<Constraint>)>,
+}
+
+/// A symbolic EVM executing the runtime bytecode of a contract with symbolic calldata.
+pub struct Machine<'a> {
+    code: &'a [u8],
+    jumpdests: HashSet<usize>,
+    calldata: Vec<Byte>,
+    env: &'a Env,
+    limits: Limits,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(code: &'a [u8], calldata: Vec<Byte>, env: &'a Env, limits: Limits) -> Self {
+        let mut jumpdests = HashSet::default();
+        let mut pc = 0;
+        while pc < code.len() {
+            match code[pc] {
+                op::JUMPDEST => {
+                    jumpdests.insert(pc);
+                }
+                opcode @ op::PUSH1..=op::PUSH32 => pc += (opcode - op::PUSH0) as usize,
+                _ => {}
+            }
+            pc += 1;
+        }
+        Self { code, jumpdests, calldata, env, limits }
+    }
+
+    /// Explores the paths of the call from `initial`, until a failure is found.
+    pub fn explore(&self, solver: &Solver, initial: State) -> Result<Exploration> {
+        let mut exploration = Exploration::default();
+        let mut worklist = Worklist { states: vec![initial], failures: vec![] };
+        while let Some(state) = worklist.states.pop() {
+            let (end, constraints) = self.run(state, &mut worklist);
+            let failures = std::mem::take(&mut worklist.failures);
+            let ends = failures
+                .into_iter()
+                .map(|(failure, constraints)| (PathEnd::Fail(failure), constraints))
+                .chain([(end, constraints)]);
+            for (end, constraints) in ends {
+                match end {
+                    PathEnd::Return => exploration.paths += 1,
+                    PathEnd::Rejected => {}
+                    PathEnd::Fail(failure) => match solver.check(&constraints)? {
+                        SatResult::Sat(model) => {
+                            exploration.paths += 1;
+                            exploration.failure = Some((failure, model));
+                            return Ok(exploration);
+                        }
+                        SatResult::Unsat => {}
+                        SatResult::Unknown => {
+                            exploration.unsupported.get_or_insert("solver returned unknown".into());
+                        }
+                    },
+                    PathEnd::Unsupported(reason) => {
+                        if solver.check(&constraints)? != SatResult::Unsat {
+                            exploration.paths += 1;
+                            exploration.unsupported.get_or_insert(reason);
+                        }
+                    }
+                    PathEnd::Bounded => {
+                        if solver.check(&constraints)? != SatResult::Unsat {
+                            exploration.paths += 1;
+                            exploration.bounded = true;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(exploration)
+    }
+
+    /// Runs a path until it ends, pushing the paths it branches to to `worklist`.
+    fn run(&self, mut state: State, worklist: &mut Worklist) -> (PathEnd, Vec<Constraint>) {
+        let end = loop {
+            match self.step(&mut state, worklist) {
+                Ok(()) => {}
+                Err(end) => break end,
+            }
+        };
+        (end, state.constraints)
+    }
+
+    /// Executes the instruction at the program counter, returning how the path ended if it did.
+    fn step(&self, state: &mut State, worklist: &mut Worklist) -> Result<(), PathEnd> {
+        state.steps += 1;
+        if state.steps > self.limits.max_steps {
+            return Err(PathEnd::Bounded);
+        }
+        let Some(&opcode) = self.code.get(state.pc) else { return Err(PathEnd::Return) };
+        let pc = state.pc;
+        state.pc += 1;
+
+        let constant = |value: U256| Term::constant(value);
+        let address = |address: Address| constant(U256::from_be_slice(address.as_slice()));
+        match opcode {
+            op::STOP => return Err(PathEnd::Return),
+            op::ADD |
+            op::SUB |
+            op::MUL |
+            op::DIV |
+            op::SDIV |
+            op::MOD |
+            op::SMOD |
+            op::LT |
+            op::GT |
+            op::SLT |
+            op::SGT |
+            op::EQ |
+            op::AND |
+            op::OR |
+            op::XOR |
+            op::BYTE |
+            op::SHL |
+            op::SHR |
+            op::SAR => {
+                let op = match opcode {
+                    op::ADD => Op::Add,
+                    op::SUB => Op::Sub,
+                    op::MUL => Op::Mul,
+                    op::DIV => Op::Div,
+                    op::SDIV => Op::SDiv,
+                    op::MOD => Op::Mod,
+                    op::SMOD => Op::SMod,
+                    op::LT => Op::Lt,
+                    op::GT => Op::Gt,
+                    op::SLT => Op::SLt,
+                    op::SGT => Op::SGt,
+                    op::EQ => Op::Eq,
+                    op::AND => Op::And,
+                    op::OR => Op::Or,
+                    op::XOR => Op::Xor,
+                    op::BYTE => Op::Byte,
+                    op::SHL => Op::Shl,
+                    op::SHR => Op::Shr,
+                    _ => Op::Sar,
+                };
+                let a = pop(state)?;
+                let b = pop(state)?;
+                push(state, Term::binary(op, a, b))?;
+            }
+            op::ADDMOD | op::MULMOD => {
+                let (a, b, n) = (pop(state)?, pop(state)?, pop(state)?);
+                let word = if opcode == op::ADDMOD {
+                    Term::add_mod(a, b, n)
+                } else {
+                    Term::mul_mod(a, b, n)
+                };
+                push(state, word)?;
+            }
+            op::EXP => {
+                let (base, exponent) = (pop(state)?, pop(state)?);
+                push(state, exp(base, exponent)?)?;
+            }
+            op::SIGNEXTEND => {
+                let (bytes, word) = (pop(state)?, pop(state)?);
+                let bytes = concrete(&bytes, "SIGNEXTEND of a symbolic size")?;
+                push(state, Term::sign_extend(bytes, word))?;
+            }
+            op::ISZERO => {
+                let a = pop(state)?;
+                push(state, Term::is_zero(a))?;
+            }
+            op::NOT => {
+                let a = pop(state)?;
+                push(state, Term::not(a))?;
+            }
+            op::KECCAK256 => {
+                let (offset, size) = (pop(state)?, pop(state)?);
+                let data = self.read_memory(state, &offset, &size)?;
+                let data = concrete_bytes(&data)
+                    .ok_or_else(|| unsupported("KECCAK256 of symbolic data"))?;
+                push(state, constant(keccak256(data).into()))?;
+            }
+            op::ADDRESS => push(state, address(self.env.address))?,
+            op::ORIGIN | op::CALLER => push(state, address(self.env.caller))?,
+            op::CALLVALUE => push(state, constant(U256::ZERO))?,
+            op::CALLDATALOAD => {
+                let offset = concrete_offset(&pop(state)?)?;
+                let bytes = (offset..offset + 32)
+                    .map(|i| self.calldata.get(i).cloned().unwrap_or(Byte::Const(0)))
+                    .collect();
+                push(state, Term::concat(bytes))?;
+            }
+            op::CALLDATASIZE => push(state, constant(U256::from(self.calldata.len())))?,
+            op::CALLDATACOPY | op::CODECOPY => {
+                let (dest, offset, size) = (pop(state)?, pop(state)?, pop(state)?);
+                let (offset, size) = (concrete_offset(&offset)?, concrete_offset(&size)?);
+                let bytes = (offset..offset + size)
+                    .map(|i| {
+                        if opcode == op::CALLDATACOPY {
+                            self.calldata.get(i).cloned().unwrap_or(Byte::Const(0))
+                        } else {
+                            Byte::Const(self.code.get(i).copied().unwrap_or_default())
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                self.write_memory(state, &dest, bytes)?;
+            }
+            op::CODESIZE => push(state, constant(U256::from(self.code.len())))?,
+            op::RETURNDATASIZE => push(state, constant(U256::from(state.returndata.len())))?,
+            op::RETURNDATACOPY => {
+                let (dest, offset, size) = (pop(state)?, pop(state)?, pop(state)?);
+                let (offset, size) = (concrete_offset(&offset)?, concrete_offset(&size)?);
+                let Some(bytes) = state.returndata.get(offset..offset + size) else {
+                    return Err(PathEnd::Fail(Failure::Halt("out of bounds return data copy")));
+                };
+                let bytes = bytes.to_vec();
+                self.write_memory(state, &dest, bytes)?;
+            }
+            op::TIMESTAMP => push(state, constant(self.env.timestamp))?,
+            op::NUMBER => push(state, constant(self.env.number))?,
+            op::CHAINID => push(state, constant(self.env.chain_id))?,
+            op::POP => {
+                pop(state)?;
+            }
+            op::MLOAD => {
+                let offset = pop(state)?;
+                let bytes = self.read_memory(state, &offset, &constant(U256::from(32)))?;
+                push(state, Term::concat(bytes))?;
+            }
+            op::MSTORE => {
+                let (offset, word) = (pop(state)?, pop(state)?);
+                self.write_memory(state, &offset, Term::bytes(&word))?;
+            }
+            op::MSTORE8 => {
+                let (offset, word) = (pop(state)?, pop(state)?);
+                let byte = Term::bytes(&word).pop().unwrap();
+                self.write_memory(state, &offset, vec![byte])?;
+            }
+            op::MCOPY => {
+                let (dest, offset, size) = (pop(state)?, pop(state)?, pop(state)?);
+                let bytes = self.read_memory(state, &offset, &size)?;
+                self.write_memory(state, &dest, bytes)?;
+            }
+            op::JUMP => {
+                let dest = concrete_offset(&pop(state)?)?;
+                state.pc = self.jump_dest(dest)?;
+            }
+            op::JUMPI => {
+                let (dest, cond) = (pop(state)?, pop(state)?);
+                match cond.as_const() {
+                    Some(cond) => {
+                        if !cond.is_zero() {
+                            state.pc = self.jump_dest(concrete_offset(&dest)?)?;
+                        }
+                    }
+                    None => {
+                        let forks = state.forks.entry(pc).or_default();
+                        if *forks >= self.limits.loop_bound {
+                            return Err(PathEnd::Bounded);
+                        }
+                        *forks += 1;
+
+                        let mut jumped = state.clone();
+                        jumped.constraints.push(Constraint { word: cond.clone(), nonzero: true });
+                        jumped.pc = self.jump_dest(concrete_offset(&dest)?)?;
+                        worklist.states.push(jumped);
+                        state.constraints.push(Constraint { word: cond, nonzero: false });
+                    }
+                }
+            }
+            op::PC => push(state, constant(U256::from(pc)))?,
+            op::MSIZE => push(state, constant(U256::from(state.memory.len())))?,
+            op::GAS => push(state, constant(U256::from(u64::MAX)))?,
+            op::JUMPDEST => {}
+            op::PUSH0..=op::PUSH32 => {
+                let size = (opcode - op::PUSH0) as usize;
+                let mut bytes = [0u8; 32];
+                for i in 0..size {
+                    bytes[32 - size + i] = self.code.get(state.pc + i).copied().unwrap_or_default();
+                }
+                state.pc += size;
+                push(state, constant(U256::from_be_bytes(bytes)))?;
+            }
+            op::DUP1..=op::DUP16 => {
+                let depth = (opcode - op::DUP1) as usize + 1;
+                let Some(word) =
+                    state.stack.len().checked_sub(depth).map(|i| state.stack[i].clone())
+                else {
+                    return Err(PathEnd::Fail(Failure::Halt("stack underflow")));
+                };
+                push(state, word)?;
+            }
+            op::SWAP1..=op::SWAP16 => {
+                let depth = (opcode - op::SWAP1) as usize + 1;
+                let len = state.stack.len();
+                if len <= depth {
+                    return Err(PathEnd::Fail(Failure::Halt("stack underflow")));
+                }
+                state.stack.swap(len - 1, len - 1 - depth);
+            }
+            op::LOG0..=op::LOG4 => {
+                for _ in 0..2 + (opcode - op::LOG0) {
+                    pop(state)?;
+                }
+            }
+            op::CALL | op::STATICCALL => {
+                let _gas = pop(state)?;
+                let target = pop(state)?;
+                if opcode == op::CALL {
+                    let value = pop(state)?;
+                    if value.as_const() != Some(U256::ZERO) {
+                        return Err(unsupported("call with value"));
+                    }
+                }
+                let (args_offset, args_size) = (pop(state)?, pop(state)?);
+                let (_ret_offset, _ret_size) = (pop(state)?, pop(state)?);
+                let target = concrete(&target, "call to a symbolic address")?;
+                let target = Address::from_word(target.into());
+                let args = self.read_memory(state, &args_offset, &args_size)?;
+                if target == CHEATCODE_ADDRESS {
+                    self.cheatcode(state, worklist, &args)?;
+                } else if target != HARDHAT_CONSOLE_ADDRESS {
+                    return Err(unsupported(&format!("call to {target}")));
+                }
+                state.returndata.clear();
+                push(state, constant(U256::from(1)))?;
+            }
+            op::RETURN => return Err(PathEnd::Return),
+            op::REVERT => {
+                let (offset, size) = (pop(state)?, pop(state)?);
+                let data = self.read_memory(state, &offset, &size)?;
+                return Err(PathEnd::Fail(Failure::Revert(data)));
+            }
+            op::INVALID => return Err(PathEnd::Fail(Failure::Halt("invalid opcode"))),
+            _ => {
+                let name = OpCode::new(opcode).map_or("UNKNOWN", |opcode| opcode.as_str());
+                return Err(unsupported(name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes a call to the cheatcode address. Only `vm.assume` and the assertion cheatcodes
+    /// are supported.
+    fn cheatcode(
+        &self,
+        state: &mut State,
+        worklist: &mut Worklist,
+        args: &[Byte],
+    ) -> Result<(), PathEnd> {
+        let selector = args.get(..4).and_then(concrete_bytes);
+        let Some(&(_, cheatcode)) =
+            CHEATCODES.iter().find(|(sel, _)| Some(sel.as_slice()) == selector.as_deref())
+        else {
+            let selector = selector.map_or_else(|| "symbolic".to_string(), hex::encode_prefixed);
+            return Err(unsupported(&format!("cheatcode with selector {selector}")));
+        };
+        let arg = |index: usize| {
+            let start = 4 + 32 * index;
+            args.get(start..start + 32).map(|bytes| Term::concat(bytes.to_vec()))
+        };
+        let missing = || PathEnd::Fail(Failure::Halt("invalid cheatcode arguments"));
+
+        let (holds, comparison, message_index) = match cheatcode {
+            Cheatcode::Assume => {
+                let cond = arg(0).ok_or_else(missing)?;
+                match cond.as_const() {
+                    Some(cond) if cond.is_zero() => return Err(PathEnd::Rejected),
+                    Some(_) => {}
+                    None => state.constraints.push(Constraint { word: cond, nonzero: true }),
+                }
+                return Ok(());
+            }
+            Cheatcode::Bool { expected, message } => {
+                let cond = arg(0).ok_or_else(missing)?;
+                let holds = if expected { cond } else { Term::is_zero(cond) };
+                (holds, None, message.then_some(1))
+            }
+            Cheatcode::Compare { cmp, kind, message } => {
+                let (left, right) = (arg(0).ok_or_else(missing)?, arg(1).ok_or_else(missing)?);
+                let signed = kind == ValueKind::Int;
+                let (lt, gt) = if signed { (Op::SLt, Op::SGt) } else { (Op::Lt, Op::Gt) };
+                let (holds, failed_op) = match cmp {
+                    Cmp::Eq => (Term::binary(Op::Eq, left.clone(), right.clone()), "!="),
+                    Cmp::NotEq => {
+                        (Term::is_zero(Term::binary(Op::Eq, left.clone(), right.clone())), "==")
+                    }
+                    Cmp::Lt => (Term::binary(lt, left.clone(), right.clone()), ">="),
+                    Cmp::Gt => (Term::binary(gt, left.clone(), right.clone()), "<="),
+                    Cmp::Le => (Term::is_zero(Term::binary(gt, left.clone(), right.clone())), ">"),
+                    Cmp::Ge => (Term::is_zero(Term::binary(lt, left.clone(), right.clone())), "<"),
+                };
+                (holds, Some(Comparison { left, right, failed_op, kind }), message.then_some(2))
+            }
+        };
+
+        let failure = || Failure::Assertion {
+            message: message_index
+                .map(|index| string_arg(args, index).unwrap_or_else(|| "assertion failed".into())),
+            comparison: comparison.clone(),
+        };
+        match holds.as_const() {
+            Some(holds) if holds.is_zero() => Err(PathEnd::Fail(failure())),
+            Some(_) => Ok(()),
+            None => {
+                let mut constraints = state.constraints.clone();
+                constraints.push(Constraint { word: holds.clone(), nonzero: false });
+                worklist.failures.push((failure(), constraints));
+                state.constraints.push(Constraint { word: holds, nonzero: true });
+                Ok(())
+            }
+        }
+    }
+
+    fn jump_dest(&self, dest: usize) -> Result<usize, PathEnd> {
+        if self.jumpdests.contains(&dest) {
+            Ok(dest)
+        } else {
+            Err(PathEnd::Fail(Failure::Halt("invalid jump destination")))
+        }
+    }
+
+    /// Reads `size` bytes of memory at `offset`, expanding it.
+    fn read_memory(
+        &self,
+        state: &mut State,
+        offset: &Word,
+        size: &Word,
+    ) -> Result<Vec<Byte>, PathEnd> {
+        let size = concrete_offset(size)?;
+        if size == 0 {
+            return Ok(vec![]);
+        }
+        let offset = concrete_offset(offset)?;
+        expand_memory(state, offset + size)?;
+        Ok(state.memory[offset..offset + size].to_vec())
+    }
+
+    /// Writes `bytes` to memory at `offset`, expanding it.
+    fn write_memory(
+        &self,
+        state: &mut State,
+        offset: &Word,
+        bytes: Vec<Byte>,
+    ) -> Result<(), PathEnd> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let offset = concrete_offset(offset)?;
+        expand_memory(state, offset + bytes.len())?;
+        state.memory[offset..offset + bytes.len()].clone_from_slice(&bytes);
+        Ok(())
+    }
+}
+
+fn pop(state: &mut State) -> Result<Word, PathEnd> {
+    state.stack.pop().ok_or(PathEnd::Fail(Failure::Halt("stack underflow")))
+}
+
+fn push(state: &mut State, word: Word) -> Result<(), PathEnd> {
+    if state.stack.len() == STACK_LIMIT {
+        return Err(PathEnd::Fail(Failure::Halt("stack overflow")));
+    }
+    state.stack.push(word);
+    Ok(())
+}
+
+fn unsupported(reason: &str) -> PathEnd {
+    PathEnd::Unsupported(reason.to_string())
+}
+
+/// Returns the concrete value of a word, or ends the path as unsupported.
+fn concrete(word: &Word, reason: &str) -> Result<U256, PathEnd> {
+    word.as_const().ok_or_else(|| unsupported(reason))
+}
+
+/// Returns the concrete value of a memory offset or size.
+fn concrete_offset(word: &Word) -> Result<usize, PathEnd> {
+    let value = concrete(word, "symbolic memory offset or size")?;
+    match usize::try_from(value) {
+        Ok(value) if value <= MEMORY_LIMIT => Ok(value),
+        _ => Err(unsupported("memory limit exceeded")),
+    }
+}
+
+/// Returns the concrete values of bytes, if all are concrete.
+fn concrete_bytes(bytes: &[Byte]) -> Option<Vec<u8>> {
+    bytes
+        .iter()
+        .map(|byte| match byte {
+            Byte::Const(byte) => Some(*byte),
+            Byte::Extract(word, index) => {
+                word.as_const().map(|word| word.byte(31 - *index as usize))
+            }
+        })
+        .collect()
+}
+
+fn expand_memory(state: &mut State, size: usize) -> Result<(), PathEnd> {
+    if size > MEMORY_LIMIT {
+        return Err(unsupported("memory limit exceeded"));
+    }
+    if size > state.memory.len() {
+        state.memory.resize(size.div_ceil(32) * 32, Byte::Const(0));
+    }
+    Ok(())
+}
+
+/// Returns `base ** exponent`, if the exponent is concrete or the base is a concrete value whose
+/// powers are shifts.
+fn exp(base: Word, exponent: Word) -> Result<Word, PathEnd> {
+    if let (Some(base), Some(exponent)) = (base.as_const(), exponent.as_const()) {
+        return Ok(Term::constant(base.wrapping_pow(exponent)));
+    }
+    match base.as_const() {
+        Some(base) if base.is_zero() => return Ok(Term::is_zero(exponent)),
+        Some(base) if base == U256::from(1) => return Ok(Term::constant(base)),
+        Some(base) if base == U256::from(2) => {
+            return Ok(Term::binary(Op::Shl, exponent, Term::constant(U256::from(1))))
+        }
+        _ => {}
+    }
+    let exponent = concrete(&exponent, "EXP with a symbolic exponent")?;
+    // Square and multiply, from the most significant bit of the exponent.
+    let mut result = Term::constant(U256::from(1));
+    for bit in (0..exponent.bit_len()).rev() {
+        result = Term::binary(Op::Mul, result.clone(), result);
+        if exponent.bit(bit) {
+            result = Term::binary(Op::Mul, result, base.clone());
+        }
+    }
+    Ok(result)
+}
+
+/// Returns the string argument at `index` of the ABI encoded `args` of a call, if concrete.
+fn string_arg(args: &[Byte], index: usize) -> Option<String> {
+    let args = args.get(4..)?;
+    let word = |offset: usize| -> Option<usize> {
+        let bytes = concrete_bytes(args.get(offset..offset + 32)?)?;
+        usize::try_from(U256::from_be_slice(&bytes)).ok()
+    };
+    let offset = word(32 * index)?;
+    let len = word(offset)?;
+    let bytes = concrete_bytes(args.get(offset + 32..offset.checked_add(32 + len)?)?)?;
+    String::from_utf8(bytes).ok()
+}
+
+/// The type of the values compared by an assertion cheatcode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ValueKind {
+    Uint,
+    Int,
+    Address,
+    Bool,
+    Bytes32,
+}
+
+impl ValueKind {
+    fn format(self, value: U256) -> String {
+        match self {
+            Self::Uint => value.to_string(),
+            Self::Int => I256::from_raw(value).to_string(),
+            Self::Address => Address::from_word(value.into()).to_checksum(None),
+            Self::Bool => (!value.is_zero()).to_string(),
+            Self::Bytes32 => hex::encode_prefixed(value.to_be_bytes::<32>()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Cmp {
+    Eq,
+    NotEq,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+}
+
+/// The supported cheatcodes.
+#[derive(Clone, Copy, Debug)]
+enum Cheatcode {
+    Assume,
+    /// `assertTrue` if `expected`, `assertFalse` otherwise.
+    Bool {
+        expected: bool,
+        message: bool,
+    },
+    Compare {
+        cmp: Cmp,
+        kind: ValueKind,
+        message: bool,
+    },
+}
+
+/// The supported cheatcodes, by selector.
+static CHEATCODES: LazyLock<Vec<([u8; 4], Cheatcode)>> = LazyLock::new(|| {
+    let mut cheatcodes = vec![("assume(bool)".to_string(), Cheatcode::Assume)];
+    for message in [false, true] {
+        let suffix = if message { ",string)" } else { ")" };
+        for (name, expected) in [("assertTrue", true), ("assertFalse", false)] {
+            cheatcodes
+                .push((format!("{name}(bool{suffix}"), Cheatcode::Bool { expected, message }));
+        }
+        let kinds = [
+            ("uint256", ValueKind::Uint),
+            ("int256", ValueKind::Int),
+            ("address", ValueKind::Address),
+            ("bool", ValueKind::Bool),
+            ("bytes32", ValueKind::Bytes32),
+        ];
+        for (name, cmp) in [
+            ("assertEq", Cmp::Eq),
+            ("assertNotEq", Cmp::NotEq),
+            ("assertLt", Cmp::Lt),
+            ("assertGt", Cmp::Gt),
+            ("assertLe", Cmp::Le),
+            ("assertGe", Cmp::Ge),
+        ] {
+            for (ty, kind) in kinds {
+                let ordered = matches!(kind, ValueKind::Uint | ValueKind::Int);
+                if !ordered && !matches!(cmp, Cmp::Eq | Cmp::NotEq) {
+                    continue;
+                }
+                cheatcodes.push((
+                    format!("{name}({ty},{ty}{suffix}"),
+                    Cheatcode::Compare { cmp, kind, message },
+                ));
+            }
+        }
+    }
+    cheatcodes
+        .into_iter()
+        .map(|(signature, cheatcode)| (keccak256(signature)[..4].try_into().unwrap(), cheatcode))
+        .collect()
+});
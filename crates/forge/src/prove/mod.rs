@@ -0,0 +1,169 @@
+//! Symbolic execution of tests, proving them for all their inputs.
+//!
+//! Supports the pure tests with static parameters: the runtime bytecode of the test contract is
+//! executed with symbolic arguments, and the feasibility of the paths reaching a failure is
+//! checked with an external SMT solver. Loops with symbolic conditions are bounded.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue, JsonAbiExt};
+use alloy_json_abi::Function;
+use alloy_primitives::{Address, Bytes, I256, U256};
+use eyre::Result;
+use foundry_common::fmt::format_tokens;
+use std::fmt;
+
+mod machine;
+pub use machine::{Env, Limits};
+use machine::{Machine, State};
+
+mod solver;
+pub use solver::Solver;
+
+mod term;
+use term::{Byte, Constraint, Op, Term};
+
+/// The result of proving a test.
+#[derive(Debug)]
+pub enum ProveResult {
+    /// The test passes for all inputs, or all inputs of the explored paths if `bounded`.
+    Pass { paths: usize, bounded: bool },
+    /// The test fails for the counterexample.
+    Fail { paths: usize, reason: String, calldata: Bytes, args: Vec<DynSolValue> },
+    /// The test could not be proven.
+    Skip(String),
+}
+
+impl fmt::Display for ProveResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass { .. } => write!(f, "[PASS]"),
+            Self::Fail { reason, calldata, args, .. } => write!(
+                f,
+                "[FAIL: {reason}; counterexample: calldata={calldata} args=[{}]]",
+                format_tokens(args).collect::<Vec<_>>().join(", ")
+            ),
+            Self::Skip(reason) => write!(f, "[SKIP: {reason}]"),
+        }
+    }
+}
+
+/// Proves the test `func` of the contract with runtime bytecode `code`.
+pub fn prove(
+    code: &[u8],
+    func: &Function,
+    env: &Env,
+    limits: Limits,
+    solver: &Solver,
+) -> Result<ProveResult> {
+    let mut types = Vec::with_capacity(func.inputs.len());
+    for param in &func.inputs {
+        let ty = param.resolve()?;
+        if ty.is_dynamic() ||
+            !matches!(
+                ty,
+                DynSolType::Uint(_) |
+                    DynSolType::Int(_) |
+                    DynSolType::Address |
+                    DynSolType::Bool |
+                    DynSolType::FixedBytes(_)
+            )
+        {
+            return Ok(ProveResult::Skip(format!("unsupported parameter type {ty}")));
+        }
+        types.push(ty);
+    }
+
+    // The calldata is the selector followed by a symbolic word per argument, constrained to the
+    // values of its type.
+    let mut calldata = func.selector().iter().map(|byte| Byte::Const(*byte)).collect::<Vec<_>>();
+    let mut constraints = Vec::new();
+    let mut args = Vec::new();
+    for (i, ty) in types.iter().enumerate() {
+        let arg = Term::var(format!("arg{i}"));
+        calldata.extend(Term::bytes(&arg));
+        if let Some(valid) = valid_value(ty, &arg) {
+            constraints.push(Constraint { word: valid, nonzero: true });
+        }
+        args.push(arg);
+    }
+
+    let machine = Machine::new(code, calldata, env, limits);
+    let exploration = machine.explore(solver, State::new(constraints))?;
+
+    if let Some((failure, model)) = exploration.failure {
+        let values = types
+            .iter()
+            .zip(&args)
+            .map(|(ty, arg)| value(ty, arg.eval(&model).unwrap_or_default()))
+            .collect::<Vec<_>>();
+        return Ok(ProveResult::Fail {
+            paths: exploration.paths,
+            reason: failure.reason(&model),
+            calldata: func.abi_encode_input(&values)?.into(),
+            args: values,
+        });
+    }
+    if let Some(reason) = exploration.unsupported {
+        return Ok(ProveResult::Skip(format!("unsupported: {reason}")));
+    }
+    Ok(ProveResult::Pass { paths: exploration.paths, bounded: exploration.bounded })
+}
+
+/// Returns the condition for a word to be a valid value of `ty`, if not all words are.
+fn valid_value(ty: &DynSolType, word: &term::Word) -> Option<term::Word> {
+    let constant = |value: U256| Term::constant(value);
+    match *ty {
+        DynSolType::Uint(256) | DynSolType::Int(256) | DynSolType::FixedBytes(32) => None,
+        DynSolType::Uint(bits) => {
+            Some(Term::binary(Op::Lt, word.clone(), constant(U256::from(1) << bits)))
+        }
+        DynSolType::Address => {
+            Some(Term::binary(Op::Lt, word.clone(), constant(U256::from(1) << 160)))
+        }
+        DynSolType::Bool => Some(Term::binary(Op::Lt, word.clone(), constant(U256::from(2)))),
+        DynSolType::Int(bits) => {
+            let sign_extended = Term::sign_extend(U256::from(bits / 8 - 1), word.clone());
+            Some(Term::binary(Op::Eq, sign_extended, word.clone()))
+        }
+        DynSolType::FixedBytes(size) => {
+            let low = Term::binary(Op::Shl, constant(U256::from(8 * size)), word.clone());
+            Some(Term::is_zero(low))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the value of type `ty` encoded as `word`.
+fn value(ty: &DynSolType, word: U256) -> DynSolValue {
+    match *ty {
+        DynSolType::Uint(bits) => DynSolValue::Uint(word, bits),
+        DynSolType::Int(bits) => DynSolValue::Int(I256::from_raw(word), bits),
+        DynSolType::Address => DynSolValue::Address(Address::from_word(word.into())),
+        DynSolType::Bool => DynSolValue::Bool(!word.is_zero()),
+        DynSolType::FixedBytes(size) => DynSolValue::FixedBytes(word.into(), size),
+        _ => unreachable!("unsupported parameter type"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::HashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn constrains_argument_types() {
+        let word = Term::var("arg0");
+        let valid = |ty: &DynSolType, value: U256| {
+            let model = HashMap::from_iter([(Arc::from("arg0"), value)]);
+            valid_value(ty, &word).map_or(true, |valid| !valid.eval(&model).unwrap().is_zero())
+        };
+        assert!(valid(&DynSolType::Uint(8), U256::from(255)));
+        assert!(!valid(&DynSolType::Uint(8), U256::from(256)));
+        assert!(valid(&DynSolType::Int(8), U256::MAX));
+        assert!(!valid(&DynSolType::Int(8), U256::from(128)));
+        assert!(!valid(&DynSolType::Bool, U256::from(2)));
+        assert!(valid(&DynSolType::FixedBytes(1), U256::from(0xff) << 248));
+        assert!(!valid(&DynSolType::FixedBytes(1), U256::from(1)));
+        assert!(valid(&DynSolType::Uint(256), U256::MAX));
+    }
+}
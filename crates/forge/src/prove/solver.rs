@@ -0,0 +1,135 @@
+//! Satisfiability queries to an external SMT-LIB solver.
+
+use super::term::{Constraint, SmtWriter};
+use alloy_primitives::{map::HashMap, U256};
+use eyre::{bail, Context, Result};
+use std::{
+    io::Write as _,
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+/// The result of a satisfiability query.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SatResult {
+    /// The constraints are satisfiable, with the values of the variables of a solution.
+    Sat(HashMap<Arc<str>, U256>),
+    Unsat,
+    /// The solver could not decide, e.g. on timeout.
+    Unknown,
+}
+
+/// An SMT-LIB solver run as a command reading the queries from its standard input, e.g.
+/// `z3 -in`.
+#[derive(Clone, Debug)]
+pub struct Solver {
+    command: Vec<String>,
+}
+
+impl Solver {
+    /// Creates a solver running `command`.
+    pub fn new(command: Vec<String>) -> Self {
+        Self { command }
+    }
+
+    /// Returns whether the constraints are satisfiable, with a solution if so.
+    pub fn check(&self, constraints: &[Constraint]) -> Result<SatResult> {
+        let mut writer = SmtWriter::default();
+        let asserts =
+            constraints.iter().map(|constraint| writer.constraint(constraint)).collect::<Vec<_>>();
+
+        let mut query = String::from("(set-logic QF_BV)\n");
+        for var in &writer.vars {
+            query.push_str(&format!("(declare-const {var} (_ BitVec 256))\n"));
+        }
+        query.push_str(&writer.definitions);
+        for assert in asserts {
+            query.push_str(&format!("(assert {assert})\n"));
+        }
+        query.push_str("(check-sat)\n");
+        if !writer.vars.is_empty() {
+            query.push_str(&format!("(get-value ({}))\n", writer.vars.join(" ")));
+        }
+        query.push_str("(exit)\n");
+
+        let output = self.run(&query)?;
+        let mut lines = output.lines();
+        match lines.next().map(str::trim) {
+            Some("sat") => Ok(SatResult::Sat(parse_model(
+                lines.collect::<Vec<_>>().join(" ").as_str(),
+                &writer.vars,
+            )?)),
+            Some("unsat") => Ok(SatResult::Unsat),
+            Some("unknown") => Ok(SatResult::Unknown),
+            _ => bail!("unexpected solver output: {output}"),
+        }
+    }
+
+    fn run(&self, query: &str) -> Result<String> {
+        let Some((program, args)) = self.command.split_first() else {
+            bail!("empty solver command")
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .wrap_err_with(|| format!("failed to run solver `{}`", self.command.join(" ")))?;
+        child.stdin.take().unwrap().write_all(query.as_bytes())?;
+        let output = child.wait_with_output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!(
+                "solver `{}` failed with {}:\n{}",
+                self.command.join(" "),
+                output.status,
+                if stderr.trim().is_empty() { stdout.trim() } else { stderr.trim() }
+            );
+        }
+        Ok(stdout)
+    }
+}
+
+/// Parses the values of `vars` from the output of `get-value`, e.g.
+/// `((arg0 #x00..01) (arg1 (_ bv2 256)))`.
+fn parse_model(output: &str, vars: &[Arc<str>]) -> Result<HashMap<Arc<str>, U256>> {
+    let output = output.replace(['(', ')'], " ");
+    let mut tokens = output.split_whitespace();
+    let mut model = HashMap::default();
+    while let Some(token) = tokens.next() {
+        let Some(var) = vars.iter().find(|var| ***var == *token) else { continue };
+        let value = match tokens.next() {
+            Some(hex) if hex.starts_with("#x") => U256::from_str_radix(&hex[2..], 16)?,
+            Some(bin) if bin.starts_with("#b") => U256::from_str_radix(&bin[2..], 2)?,
+            Some("_") => {
+                let Some(decimal) = tokens.next().and_then(|bv| bv.strip_prefix("bv")) else {
+                    bail!("invalid solver value for {var}")
+                };
+                U256::from_str_radix(decimal, 10)?
+            }
+            _ => bail!("invalid solver value for {var}"),
+        };
+        model.insert(var.clone(), value);
+    }
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_model() {
+        let vars = vec![Arc::from("arg0"), Arc::from("arg1"), Arc::from("arg2")];
+        let model = parse_model(
+            "((arg0 #x000000000000000000000000000000000000000000000000000000000000002a)\n (arg1 (_ bv7 256)) (arg2 #b101))",
+            &vars,
+        )
+        .unwrap();
+        assert_eq!(model[&vars[0]], U256::from(42));
+        assert_eq!(model[&vars[1]], U256::from(7));
+        assert_eq!(model[&vars[2]], U256::from(5));
+    }
+}
@@ -0,0 +1,503 @@
+//! Symbolic 256-bit words and their SMT-LIB encoding.
+
+use alloy_primitives::{hex, map::HashMap, U256};
+use std::{fmt::Write, sync::Arc};
+
+/// A symbolic 256-bit word.
+pub type Word = Arc<Term>;
+
+/// Sign bit of a 256-bit word.
+const SIGN_BIT: U256 = U256::from_limbs([0, 0, 0, 1 << 63]);
+
+/// Operations on 256-bit words, with the semantics of the EVM opcodes of the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    SDiv,
+    Mod,
+    SMod,
+    Lt,
+    Gt,
+    SLt,
+    SGt,
+    Eq,
+    And,
+    Or,
+    Xor,
+    Shl,
+    Shr,
+    Sar,
+    Byte,
+}
+
+/// A symbolic 256-bit word.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Term {
+    /// A concrete value.
+    Const(U256),
+    /// An unconstrained variable.
+    Var(Arc<str>),
+    /// `1` if the operand is zero, `0` otherwise.
+    IsZero(Word),
+    /// Bitwise negation.
+    Not(Word),
+    /// A binary operation. The shift operations take the shift as first operand, and `Byte` the
+    /// byte index.
+    Binary(Op, Word, Word),
+    /// `(a + b) % n` and `(a * b) % n` computed without overflow, `0` if `n` is zero.
+    AddMod(Word, Word, Word),
+    MulMod(Word, Word, Word),
+    /// Sign extension of the low `bytes + 1` bytes.
+    SignExtend(u8, Word),
+    /// A word built from 32 bytes, the most significant first.
+    Concat(Vec<Byte>),
+}
+
+/// A symbolic byte of memory, calldata or return data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Byte {
+    /// A concrete byte.
+    Const(u8),
+    /// Byte `index` of a word, the most significant being `0`.
+    Extract(Word, u8),
+}
+
+/// A constraint on the path to a state: the word is non-zero if `nonzero`, zero otherwise.
+#[derive(Clone, Debug)]
+pub struct Constraint {
+    pub word: Word,
+    pub nonzero: bool,
+}
+
+impl Term {
+    /// Returns a concrete word.
+    pub fn constant(value: U256) -> Word {
+        Arc::new(Self::Const(value))
+    }
+
+    /// Returns a variable.
+    pub fn var(name: impl Into<Arc<str>>) -> Word {
+        Arc::new(Self::Var(name.into()))
+    }
+
+    /// Returns the concrete value of the word, if any.
+    pub fn as_const(&self) -> Option<U256> {
+        match self {
+            Self::Const(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns `IsZero(word)`, simplified.
+    pub fn is_zero(word: Word) -> Word {
+        if let Some(value) = word.as_const() {
+            return Self::constant(U256::from(value.is_zero()));
+        }
+        // `ISZERO(ISZERO(ISZERO(x)))` is `ISZERO(x)`.
+        if let Self::IsZero(inner) = &*word {
+            if let Self::IsZero(_) = &**inner {
+                return inner.clone();
+            }
+        }
+        Arc::new(Self::IsZero(word))
+    }
+
+    /// Returns `Not(word)`, simplified.
+    pub fn not(word: Word) -> Word {
+        match &*word {
+            Self::Const(value) => Self::constant(!*value),
+            Self::Not(inner) => inner.clone(),
+            _ => Arc::new(Self::Not(word)),
+        }
+    }
+
+    /// Returns a binary operation, simplified.
+    pub fn binary(op: Op, a: Word, b: Word) -> Word {
+        if let (Some(a), Some(b)) = (a.as_const(), b.as_const()) {
+            return Self::constant(fold(op, a, b));
+        }
+        let (ca, cb) = (a.as_const(), b.as_const());
+        match op {
+            Op::Add | Op::Or | Op::Xor if ca == Some(U256::ZERO) => return b,
+            Op::Add | Op::Sub | Op::Or | Op::Xor if cb == Some(U256::ZERO) => return a,
+            Op::Mul if ca == Some(U256::from(1)) => return b,
+            Op::Mul | Op::Div | Op::SDiv if cb == Some(U256::from(1)) => return a,
+            Op::Mul | Op::And if ca == Some(U256::ZERO) || cb == Some(U256::ZERO) => {
+                return Self::constant(U256::ZERO)
+            }
+            Op::And if ca == Some(U256::MAX) => return b,
+            Op::And if cb == Some(U256::MAX) => return a,
+            Op::Shl | Op::Shr | Op::Sar if ca == Some(U256::ZERO) => return b,
+            Op::Sub | Op::Xor if a == b => return Self::constant(U256::ZERO),
+            Op::Eq if a == b => return Self::constant(U256::from(1)),
+            Op::And | Op::Or if a == b => return a,
+            _ => {}
+        }
+        Arc::new(Self::Binary(op, a, b))
+    }
+
+    /// Returns `AddMod(a, b, n)`, simplified.
+    pub fn add_mod(a: Word, b: Word, n: Word) -> Word {
+        match (a.as_const(), b.as_const(), n.as_const()) {
+            (Some(a), Some(b), Some(n)) => Self::constant(a.add_mod(b, n)),
+            _ => Arc::new(Self::AddMod(a, b, n)),
+        }
+    }
+
+    /// Returns `MulMod(a, b, n)`, simplified.
+    pub fn mul_mod(a: Word, b: Word, n: Word) -> Word {
+        match (a.as_const(), b.as_const(), n.as_const()) {
+            (Some(a), Some(b), Some(n)) => Self::constant(a.mul_mod(b, n)),
+            _ => Arc::new(Self::MulMod(a, b, n)),
+        }
+    }
+
+    /// Returns `SignExtend(bytes, word)`, simplified.
+    pub fn sign_extend(bytes: U256, word: Word) -> Word {
+        if bytes >= U256::from(31) {
+            return word;
+        }
+        let bytes = bytes.to::<u8>();
+        match word.as_const() {
+            Some(value) => Self::constant(sign_extend(bytes, value)),
+            None => Arc::new(Self::SignExtend(bytes, word)),
+        }
+    }
+
+    /// Returns the word made of `bytes`, simplified to the original word if the bytes are the ones
+    /// of a single word.
+    pub fn concat(bytes: Vec<Byte>) -> Word {
+        debug_assert_eq!(bytes.len(), 32);
+        if bytes.iter().all(|byte| matches!(byte, Byte::Const(_))) {
+            let bytes = bytes
+                .iter()
+                .map(|byte| match byte {
+                    Byte::Const(byte) => *byte,
+                    Byte::Extract(..) => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+            return Self::constant(U256::from_be_slice(&bytes));
+        }
+        if let Byte::Extract(word, 0) = &bytes[0] {
+            let same_word = bytes.iter().enumerate().all(|(i, byte)| {
+                matches!(byte, Byte::Extract(other, index) if Arc::ptr_eq(word, other) && *index as usize == i)
+            });
+            if same_word {
+                return word.clone();
+            }
+        }
+        Arc::new(Self::Concat(bytes))
+    }
+
+    /// Returns the bytes of a word, the most significant first.
+    pub fn bytes(word: &Word) -> Vec<Byte> {
+        match word.as_const() {
+            Some(value) => value.to_be_bytes::<32>().into_iter().map(Byte::Const).collect(),
+            None => (0..32).map(|i| Byte::Extract(word.clone(), i)).collect(),
+        }
+    }
+
+    /// Evaluates the word with the given values of its variables, `None` if a variable has no
+    /// value.
+    pub fn eval(&self, model: &HashMap<Arc<str>, U256>) -> Option<U256> {
+        Some(match self {
+            Self::Const(value) => *value,
+            Self::Var(name) => *model.get(name)?,
+            Self::IsZero(word) => U256::from(word.eval(model)?.is_zero()),
+            Self::Not(word) => !word.eval(model)?,
+            Self::Binary(op, a, b) => fold(*op, a.eval(model)?, b.eval(model)?),
+            Self::AddMod(a, b, n) => a.eval(model)?.add_mod(b.eval(model)?, n.eval(model)?),
+            Self::MulMod(a, b, n) => a.eval(model)?.mul_mod(b.eval(model)?, n.eval(model)?),
+            Self::SignExtend(bytes, word) => sign_extend(*bytes, word.eval(model)?),
+            Self::Concat(bytes) => {
+                let mut value = [0u8; 32];
+                for (i, byte) in bytes.iter().enumerate() {
+                    value[i] = byte.eval(model)?;
+                }
+                U256::from_be_bytes(value)
+            }
+        })
+    }
+}
+
+impl Byte {
+    /// Evaluates the byte with the given values of the variables.
+    pub fn eval(&self, model: &HashMap<Arc<str>, U256>) -> Option<u8> {
+        match self {
+            Self::Const(byte) => Some(*byte),
+            Self::Extract(word, index) => Some(word.eval(model)?.byte(31 - *index as usize)),
+        }
+    }
+}
+
+/// Applies an operation to concrete operands.
+fn fold(op: Op, a: U256, b: U256) -> U256 {
+    let bool_word = |b: bool| U256::from(b);
+    match op {
+        Op::Add => a.wrapping_add(b),
+        Op::Sub => a.wrapping_sub(b),
+        Op::Mul => a.wrapping_mul(b),
+        Op::Div => a.checked_div(b).unwrap_or_default(),
+        Op::SDiv => {
+            if b.is_zero() {
+                return U256::ZERO;
+            }
+            let quotient = abs(a) / abs(b);
+            if is_negative(a) != is_negative(b) {
+                negate(quotient)
+            } else {
+                quotient
+            }
+        }
+        Op::Mod => a.checked_rem(b).unwrap_or_default(),
+        Op::SMod => {
+            if b.is_zero() {
+                return U256::ZERO;
+            }
+            let remainder = abs(a) % abs(b);
+            if is_negative(a) {
+                negate(remainder)
+            } else {
+                remainder
+            }
+        }
+        Op::Lt => bool_word(a < b),
+        Op::Gt => bool_word(a > b),
+        Op::SLt => bool_word((a ^ SIGN_BIT) < (b ^ SIGN_BIT)),
+        Op::SGt => bool_word((a ^ SIGN_BIT) > (b ^ SIGN_BIT)),
+        Op::Eq => bool_word(a == b),
+        Op::And => a & b,
+        Op::Or => a | b,
+        Op::Xor => a ^ b,
+        Op::Shl => {
+            if a >= U256::from(256) {
+                U256::ZERO
+            } else {
+                b << a.to::<usize>()
+            }
+        }
+        Op::Shr => {
+            if a >= U256::from(256) {
+                U256::ZERO
+            } else {
+                b >> a.to::<usize>()
+            }
+        }
+        Op::Sar => {
+            let shift = if a >= U256::from(256) { 255 } else { a.to::<usize>() };
+            if is_negative(b) {
+                !((!b) >> shift)
+            } else {
+                b >> shift
+            }
+        }
+        Op::Byte => {
+            if a >= U256::from(32) {
+                U256::ZERO
+            } else {
+                U256::from(b.byte(31 - a.to::<usize>()))
+            }
+        }
+    }
+}
+
+fn is_negative(value: U256) -> bool {
+    value.bit(255)
+}
+
+fn negate(value: U256) -> U256 {
+    (!value).wrapping_add(U256::from(1))
+}
+
+fn abs(value: U256) -> U256 {
+    if is_negative(value) {
+        negate(value)
+    } else {
+        value
+    }
+}
+
+fn sign_extend(bytes: u8, value: U256) -> U256 {
+    let bit = bytes as usize * 8 + 7;
+    let mask = (U256::from(1) << (bit + 1)) - U256::from(1);
+    if value.bit(bit) {
+        value | !mask
+    } else {
+        value & mask
+    }
+}
+
+/// Writes SMT-LIB definitions of words, sharing the definitions of the words used several times.
+#[derive(Default)]
+pub struct SmtWriter {
+    /// The definitions written so far.
+    pub definitions: String,
+    /// The variables found so far.
+    pub vars: Vec<Arc<str>>,
+    /// The name of the definition of each word, by address.
+    names: HashMap<usize, String>,
+}
+
+impl SmtWriter {
+    /// Returns the SMT-LIB expression of a constraint, defining the words it uses.
+    pub fn constraint(&mut self, constraint: &Constraint) -> String {
+        let word = self.word(&constraint.word);
+        if constraint.nonzero {
+            format!("(not (= {word} {}))", hex_const(U256::ZERO))
+        } else {
+            format!("(= {word} {})", hex_const(U256::ZERO))
+        }
+    }
+
+    /// Returns the name of the definition of a word, defining it first if needed.
+    pub fn word(&mut self, word: &Word) -> String {
+        match &**word {
+            Term::Const(value) => return hex_const(*value),
+            Term::Var(name) => {
+                if !self.vars.contains(name) {
+                    self.vars.push(name.clone());
+                }
+                return name.to_string();
+            }
+            _ => {}
+        }
+        let key = Arc::as_ptr(word) as usize;
+        if let Some(name) = self.names.get(&key) {
+            return name.clone();
+        }
+
+        let zero = hex_const(U256::ZERO);
+        let one = hex_const(U256::from(1));
+        let bool_word = |cond: String| format!("(ite {cond} {one} {zero})");
+        let expr = match &**word {
+            Term::Const(_) | Term::Var(_) => unreachable!(),
+            Term::IsZero(a) => bool_word(format!("(= {} {zero})", self.word(a))),
+            Term::Not(a) => format!("(bvnot {})", self.word(a)),
+            Term::Binary(op, a, b) => {
+                let (a, b) = (self.word(a), self.word(b));
+                match op {
+                    Op::Add => format!("(bvadd {a} {b})"),
+                    Op::Sub => format!("(bvsub {a} {b})"),
+                    Op::Mul => format!("(bvmul {a} {b})"),
+                    Op::Div => format!("(ite (= {b} {zero}) {zero} (bvudiv {a} {b}))"),
+                    Op::SDiv => format!("(ite (= {b} {zero}) {zero} (bvsdiv {a} {b}))"),
+                    Op::Mod => format!("(ite (= {b} {zero}) {zero} (bvurem {a} {b}))"),
+                    Op::SMod => format!("(ite (= {b} {zero}) {zero} (bvsrem {a} {b}))"),
+                    Op::Lt => bool_word(format!("(bvult {a} {b})")),
+                    Op::Gt => bool_word(format!("(bvugt {a} {b})")),
+                    Op::SLt => bool_word(format!("(bvslt {a} {b})")),
+                    Op::SGt => bool_word(format!("(bvsgt {a} {b})")),
+                    Op::Eq => bool_word(format!("(= {a} {b})")),
+                    Op::And => format!("(bvand {a} {b})"),
+                    Op::Or => format!("(bvor {a} {b})"),
+                    Op::Xor => format!("(bvxor {a} {b})"),
+                    Op::Shl => format!("(bvshl {b} {a})"),
+                    Op::Shr => format!("(bvlshr {b} {a})"),
+                    Op::Sar => format!("(bvashr {b} {a})"),
+                    Op::Byte => format!(
+                        "(ite (bvult {a} {}) (bvand (bvlshr {b} (bvmul (bvsub {} {a}) {})) {}) {zero})",
+                        hex_const(U256::from(32)),
+                        hex_const(U256::from(31)),
+                        hex_const(U256::from(8)),
+                        hex_const(U256::from(0xff)),
+                    ),
+                }
+            }
+            Term::AddMod(a, b, n) | Term::MulMod(a, b, n) => {
+                let op = if matches!(&**word, Term::AddMod(..)) { "bvadd" } else { "bvmul" };
+                let (a, b, n) = (self.word(a), self.word(b), self.word(n));
+                let ext = |w: &str| format!("((_ zero_extend 256) {w})");
+                format!(
+                    "(ite (= {n} {zero}) {zero} ((_ extract 255 0) (bvurem ({op} {} {}) {})))",
+                    ext(&a),
+                    ext(&b),
+                    ext(&n)
+                )
+            }
+            Term::SignExtend(bytes, a) => {
+                let bits = (*bytes as usize + 1) * 8;
+                format!(
+                    "((_ sign_extend {}) ((_ extract {} 0) {}))",
+                    256 - bits,
+                    bits - 1,
+                    self.word(a)
+                )
+            }
+            Term::Concat(bytes) => {
+                let bytes = bytes
+                    .iter()
+                    .map(|byte| match byte {
+                        Byte::Const(byte) => format!("#x{byte:02x}"),
+                        Byte::Extract(word, index) => {
+                            let low = (31 - *index as usize) * 8;
+                            format!("((_ extract {} {low}) {})", low + 7, self.word(word))
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                format!("(concat {})", bytes.join(" "))
+            }
+        };
+
+        let name = format!("t{}", self.names.len());
+        writeln!(self.definitions, "(define-fun {name} () (_ BitVec 256) {expr})").unwrap();
+        self.names.insert(key, name.clone());
+        name
+    }
+}
+
+/// Returns the SMT-LIB literal of a 256-bit value.
+fn hex_const(value: U256) -> String {
+    format!("#x{}", hex::encode(value.to_be_bytes::<32>()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_signed_operations() {
+        let minus = |v: u64| negate(U256::from(v));
+        assert_eq!(fold(Op::SDiv, minus(7), U256::from(2)), minus(3));
+        assert_eq!(fold(Op::SMod, minus(7), U256::from(2)), minus(1));
+        assert_eq!(fold(Op::SLt, minus(1), U256::from(1)), U256::from(1));
+        assert_eq!(fold(Op::Sar, U256::from(1), minus(4)), minus(2));
+        assert_eq!(sign_extend(0, U256::from(0xff)), U256::MAX);
+        assert_eq!(fold(Op::Byte, U256::from(31), U256::from(0x1234)), U256::from(0x34));
+    }
+
+    #[test]
+    fn simplifies_words() {
+        let x = Term::var("x");
+        assert_eq!(Term::concat(Term::bytes(&x)), x);
+        assert_eq!(Term::binary(Op::Add, x.clone(), Term::constant(U256::ZERO)), x);
+        let bytes = Term::bytes(&Term::constant(U256::from(42)));
+        assert_eq!(Term::concat(bytes).as_const(), Some(U256::from(42)));
+
+        let is_zero = Term::is_zero(x.clone());
+        assert_eq!(Term::is_zero(Term::is_zero(is_zero.clone())), is_zero);
+    }
+
+    #[test]
+    fn evaluates_words() {
+        let x = Term::var("x");
+        let word = Term::binary(Op::Lt, x.clone(), Term::constant(U256::from(10)));
+        let model = HashMap::from_iter([(Arc::from("x"), U256::from(3))]);
+        assert_eq!(word.eval(&model), Some(U256::from(1)));
+        assert_eq!(Term::sign_extend(U256::ZERO, x).eval(&model), Some(U256::from(3)));
+    }
+
+    #[test]
+    fn writes_shared_definitions() {
+        let x = Term::var("x");
+        let sum = Term::binary(Op::Add, x.clone(), Term::constant(U256::from(1)));
+        let product = Term::binary(Op::Mul, sum.clone(), sum);
+        let mut writer = SmtWriter::default();
+        let constraint = writer.constraint(&Constraint { word: product, nonzero: true });
+        assert_eq!(writer.vars, vec![Arc::from("x")]);
+        assert_eq!(writer.definitions.lines().count(), 2);
+        assert_eq!(constraint, format!("(not (= t1 {}))", hex_const(U256::ZERO)));
+    }
+}
@@ -6,7 +6,7 @@
 };
 use alloy_primitives::{
     map::{AddressHashMap, HashMap},
-    Address, Log,
+    Address, Bytes, Log,
 };
 use eyre::Report;
 use foundry_common::{evm::Breakpoints, get_contract_name, get_file_name, shell};
@@ -151,9 +151,11 @@ pub fn summary(&self, wall_clock_time: Duration) -> String {
     }
 
     /// Checks if there are any failures and failures are disallowed.
+    ///
+    /// Failures of quarantined tests are ignored.
     pub fn ensure_ok(&self, silent: bool) -> eyre::Result<()> {
         let outcome = self;
-        let failures = outcome.failures().count();
+        let failures = outcome.failures().filter(|(_, t)| !t.quarantined).count();
         if outcome.allow_failure || failures == 0 {
             return Ok(());
         }
@@ -165,14 +167,14 @@ pub fn ensure_ok(&self, silent: bool) -> eyre::Result<()> {
 
         sh_println!("\nFailing tests:")?;
         for (suite_name, suite) in &outcome.results {
-            let failed = suite.failed();
+            let failed = suite.failures().filter(|(_, t)| !t.quarantined).count();
             if failed == 0 {
                 continue;
             }
 
             let term = if failed > 1 { "tests" } else { "test" };
             sh_println!("Encountered {failed} failing {term} in {suite_name}")?;
-            for (name, result) in suite.failures() {
+            for (name, result) in suite.failures().filter(|(_, t)| !t.quarantined) {
                 sh_println!("{}", result.short_result(name))?;
             }
             sh_println!()?;
@@ -381,6 +383,11 @@ pub struct TestResult {
     /// still be successful (i.e self.success == true) when it's expected to fail.
     pub reason: Option<String>,
 
+    /// The raw revert data of a failed test, used to decode errors which are not part of the
+    /// project after the run.
+    #[serde(skip)]
+    pub revert_data: Option<Bytes>,
+
     /// Minimal reproduction test case for failing test
     pub counterexample: Option<CounterExample>,
 
@@ -411,6 +418,10 @@ pub struct TestResult {
 
     pub duration: Duration,
 
+    /// The time spent executing the EVM, as part of [duration](Self::duration).
+    #[serde(default)]
+    pub evm_duration: Duration,
+
     /// pc breakpoint char map
     pub breakpoints: Breakpoints,
 
@@ -420,6 +431,15 @@ pub struct TestResult {
     /// Deprecated cheatcodes (mapped to their replacements, if any) used in current test.
     #[serde(skip)]
     pub deprecated_cheatcodes: HashMap<&'static str, Option<&'static str>>,
+
+    /// The number of passing re-runs and the total number of re-runs of a failing test, when
+    /// detecting flaky tests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reruns: Option<(u32, u32)>,
+
+    /// Whether the test failed and is part of the `quarantine` list, so it doesn't fail the run.
+    #[serde(default)]
+    pub quarantined: bool,
 }
 
 impl fmt::Display for TestResult {
@@ -535,6 +555,9 @@ pub fn single_result(
             false => TestStatus::Failure,
         };
         self.reason = reason;
+        self.revert_data =
+            (!success && raw_call_result.reverted && !raw_call_result.result.is_empty())
+                .then_some(raw_call_result.result);
         self.duration = Duration::default();
         self.gas_report_traces = Vec::new();
 
@@ -602,6 +625,12 @@ pub fn invariant_replay_fail(
         self.counterexample = Some(CounterExample::Sequence(call_sequence.len(), call_sequence));
     }
 
+    /// Returns the pass result for an invariant test replaying `calls` calls.
+    pub fn invariant_replay_pass(&mut self, calls: usize) {
+        self.kind = TestKind::Invariant { runs: 1, calls, reverts: 0, metrics: HashMap::default() };
+        self.status = TestStatus::Success;
+    }
+
     /// Returns the fail result for invariant test setup.
     pub fn invariant_setup_fail(&mut self, e: Report) {
         self.kind =
@@ -13,16 +13,17 @@
 use eyre::Result;
 use foundry_common::{contracts::ContractsByAddress, TestFunctionExt, TestFunctionKind};
 use foundry_compilers::utils::canonicalized;
-use foundry_config::{Config, InvariantConfig};
+use foundry_config::{Config, FuzzConfig, InvariantConfig};
 use foundry_evm::{
     constants::CALLER,
     decode::RevertDecoder,
     executors::{
-        fuzz::FuzzedExecutor,
+        fuzz::{DifferentialReference, FuzzedExecutor},
         invariant::{
-            check_sequence, replay_error, replay_run, InvariantExecutor, InvariantFuzzError,
+            check_sequence, replay_error, replay_run, replay_test_function, InvariantExecutor,
+            InvariantFuzzError,
         },
-        CallResult, EvmError, Executor, ITest, RawCallResult,
+        measure_evm_time, CallResult, EvmError, Executor, ITest, RawCallResult,
     },
     fuzz::{
         fixture_name,
@@ -153,6 +154,15 @@ fn _setup(&mut self, call_setup: bool) -> Result<TestSetup> {
             }
         }
 
+        // Pinned libraries are not deployed, they must already exist on the chain, e.g. a fork.
+        for (name, address) in &self.mcr.pinned_libraries {
+            if self.executor.is_empty_code(*address)? {
+                eyre::bail!(
+                    "library `{name}` pinned in `chain_libraries` has no code at {address}"
+                );
+            }
+        }
+
         let address = self.sender.create(self.executor.get_nonce(self.sender)?);
         result.address = address;
 
@@ -209,9 +219,11 @@ fn initial_balance(&self) -> U256 {
     fn apply_contract_inline_config(&mut self) -> Result<()> {
         if self.inline_config.contains_contract(self.name) {
             let new_config = Arc::new(self.inline_config(None)?);
+            let prev_env = self.tcfg.env.clone();
             self.tcfg.to_mut().reconfigure_with(new_config);
             let prev_tracer = self.executor.inspector_mut().tracer.take();
             self.tcfg.configure_executor(&mut self.executor);
+            self.tcfg.configure_executor_env(&prev_env, &mut self.executor);
             // Don't set tracer here.
             self.executor.inspector_mut().tracer = prev_tracer;
         }
@@ -405,7 +417,7 @@ pub fn run_tests(mut self, filter: &dyn TestFilter) -> SuiteResult {
 
         let test_results = functions
             .par_iter()
-            .map(|&func| {
+            .flat_map_iter(|&func| {
                 let start = Instant::now();
 
                 let _guard = self.tokio_handle.enter();
@@ -426,15 +438,39 @@ pub fn run_tests(mut self, filter: &dyn TestFilter) -> SuiteResult {
                 )
                 .entered();
 
-                let mut res = FunctionRunner::new(&self, &setup).run(
-                    func,
-                    kind,
-                    call_after_invariant,
-                    identified_contracts.as_ref(),
-                );
+                // Each case of a table test has its own result.
+                if kind.is_table_test() {
+                    let cases = match table_cases(func, &setup.fuzz_fixtures) {
+                        Ok(cases) => cases,
+                        Err(err) => return vec![(sig, TestResult::fail(err))],
+                    };
+                    return cases
+                        .iter()
+                        .enumerate()
+                        .map(|(i, args)| {
+                            let start = Instant::now();
+                            let (mut res, evm_duration) = measure_evm_time(|| {
+                                FunctionRunner::new(&self, &setup).run_table_case(func, args)
+                            });
+                            res.duration = start.elapsed();
+                            res.evm_duration = evm_duration;
+                            (format!("{sig}[{i}]"), res)
+                        })
+                        .collect();
+                }
+
+                let (mut res, evm_duration) = measure_evm_time(|| {
+                    FunctionRunner::new(&self, &setup).run(
+                        func,
+                        kind,
+                        call_after_invariant,
+                        identified_contracts.as_ref(),
+                    )
+                });
                 res.duration = start.elapsed();
+                res.evm_duration = evm_duration;
 
-                (sig, res)
+                vec![(sig, res)]
             })
             .collect::<BTreeMap<_, _>>();
 
@@ -491,8 +527,10 @@ fn revert_decoder(&self) -> &'a RevertDecoder {
     fn apply_function_inline_config(&mut self, func: &Function) -> Result<()> {
         if self.inline_config.contains_function(self.cr.name, &func.name) {
             let new_config = Arc::new(self.cr.inline_config(Some(func))?);
+            let prev_env = self.tcfg.env.clone();
             self.tcfg.to_mut().reconfigure_with(new_config);
             self.tcfg.configure_executor(self.executor.to_mut());
+            self.tcfg.configure_executor_env(&prev_env, self.executor.to_mut());
         }
         Ok(())
     }
@@ -510,7 +548,7 @@ fn run(
         }
 
         match kind {
-            TestFunctionKind::UnitTest { .. } => self.run_unit_test(func),
+            TestFunctionKind::UnitTest { .. } => self.run_unit_test(func, &[]),
             TestFunctionKind::FuzzTest { .. } => self.run_fuzz_test(func),
             TestFunctionKind::InvariantTest => {
                 let test_bytecode = &self.cr.contract.bytecode;
@@ -525,7 +563,16 @@ fn run(
         }
     }
 
-    /// Runs a single unit test.
+    /// Runs a single case of a table test, with the arguments of the case.
+    fn run_table_case(mut self, func: &Function, args: &[DynSolValue]) -> TestResult {
+        if let Err(e) = self.apply_function_inline_config(func) {
+            self.result.single_fail(Some(e.to_string()));
+            return self.result;
+        }
+        self.run_unit_test(func, args)
+    }
+
+    /// Runs a single unit test, or a case of a table test with `args`.
     ///
     /// Applies before test txes (if any), runs current test and returns the `TestResult`.
     ///
@@ -533,7 +580,7 @@ fn run(
     /// (therefore the unit test call will be made on modified state).
     /// State modifications of before test txes and unit test function call are discarded after
     /// test ends, similar to `eth_call`.
-    fn run_unit_test(mut self, func: &Function) -> TestResult {
+    fn run_unit_test(mut self, func: &Function, args: &[DynSolValue]) -> TestResult {
         // Prepare unit test execution.
         if self.prepare_test(func).is_err() {
             return self.result;
@@ -544,7 +591,7 @@ fn run_unit_test(mut self, func: &Function) -> TestResult {
             self.sender,
             self.address,
             func,
-            &[],
+            args,
             U256::ZERO,
             Some(self.revert_decoder()),
         ) {
@@ -593,6 +640,7 @@ fn run_invariant_test(
             self.clone_executor(),
             runner,
             invariant_config.clone(),
+            &self.config.fuzz,
             identified_contracts,
             &self.cr.mcr.known_contracts,
         );
@@ -609,11 +657,29 @@ fn run_invariant_test(
             &invariant_contract.invariant_function.name,
         );
         let show_solidity = invariant_config.show_solidity;
+        // A replay file replaces the campaign of the invariant it was recorded for.
+        let replay_file = invariant_config
+            .replay_file
+            .as_deref()
+            .filter(|file| file.file_name().is_some_and(|name| name == func.name.as_str()));
 
         // Try to replay recorded failure if any.
-        if let Some(mut call_sequence) =
-            persisted_call_sequence(failure_file.as_path(), test_bytecode)
-        {
+        let call_sequence = match replay_file {
+            Some(replay_file) => {
+                match foundry_common::fs::read_json_file::<InvariantPersistedFailure>(replay_file) {
+                    Ok(persisted_failure) => Some(persisted_failure.call_sequence),
+                    Err(err) => {
+                        self.result.invariant_setup_fail(eyre::eyre!(
+                            "failed to load replay file {}: {err}",
+                            replay_file.display()
+                        ));
+                        return self.result;
+                    }
+                }
+            }
+            None => persisted_call_sequence(failure_file.as_path(), test_bytecode),
+        };
+        if let Some(mut call_sequence) = call_sequence {
             // Create calls from failed sequence and check if invariant still broken.
             let txes = call_sequence
                 .iter_mut()
@@ -625,10 +691,12 @@ fn run_invariant_test(
                             target: seq.addr.unwrap_or_default(),
                             calldata: seq.calldata.clone(),
                         },
+                        warp: seq.warp,
+                        roll: seq.roll,
                     }
                 })
                 .collect::<Vec<BasicTxDetails>>();
-            if let Ok((success, replayed_entirely)) = check_sequence(
+            let checked_sequence = check_sequence(
                 self.clone_executor(),
                 &txes,
                 (0..min(txes.len(), invariant_config.depth as usize)).collect(),
@@ -636,13 +704,16 @@ fn run_invariant_test(
                 invariant_contract.invariant_function.selector().to_vec().into(),
                 invariant_config.fail_on_revert,
                 invariant_contract.call_after_invariant,
-            ) {
+            );
+            if let Ok((success, replayed_entirely)) = checked_sequence {
                 if !success {
-                    let _= sh_warn!("\
-                            Replayed invariant failure from {:?} file. \
-                            Run `forge clean` or remove file to ignore failure and to continue invariant test campaign.",
-                        failure_file.as_path()
-                    );
+                    if replay_file.is_none() {
+                        let _= sh_warn!("\
+                                Replayed invariant failure from {:?} file. \
+                                Run `forge clean` or remove file to ignore failure and to continue invariant test campaign.",
+                            failure_file.as_path()
+                        );
+                    }
                     // If sequence still fails then replay error to collect traces and
                     // exit without executing new runs.
                     let _ = replay_run(
@@ -665,6 +736,29 @@ fn run_invariant_test(
                     return self.result;
                 }
             }
+
+            if replay_file.is_some() {
+                match checked_sequence {
+                    Ok(_) => {
+                        // The invariant holds after the replayed sequence, collect its traces.
+                        let _ = replay_run(
+                            &invariant_contract,
+                            self.clone_executor(),
+                            &self.cr.mcr.known_contracts,
+                            identified_contracts.clone(),
+                            &mut self.result.logs,
+                            &mut self.result.traces,
+                            &mut self.result.coverage,
+                            &mut self.result.deprecated_cheatcodes,
+                            &txes,
+                            show_solidity,
+                        );
+                        self.result.invariant_replay_pass(txes.len());
+                    }
+                    Err(err) => self.result.invariant_setup_fail(err),
+                }
+                return self.result;
+            }
         }
 
         let progress =
@@ -721,6 +815,14 @@ fn run_invariant_test(
                                     },
                                 ) {
                                     error!(%err, "Failed to record call sequence");
+                                } else if let Err(err) = foundry_common::fs::write(
+                                    failure_file.with_extension("sol"),
+                                    replay_test_function(
+                                        &invariant_contract.invariant_function.name,
+                                        &call_sequence,
+                                    ),
+                                ) {
+                                    error!(%err, "Failed to record call sequence test function");
                                 }
 
                                 let original_seq_len =
@@ -790,14 +892,25 @@ fn run_fuzz_test(mut self, func: &Function) -> TestResult {
         }
 
         let runner = self.fuzz_runner();
-        let fuzz_config = self.config.fuzz.clone();
+        let mut fuzz_config = self.config.fuzz.clone();
+        fuzz_config.corpus_dir =
+            fuzz_config.corpus_dir.map(|dir| fuzz_corpus_dir(dir, self.cr.name, &func.name));
+
+        let differential = match self.differential_reference(&fuzz_config) {
+            Ok(differential) => differential,
+            Err(err) => {
+                self.result.single_fail(Some(err.to_string()));
+                return self.result;
+            }
+        };
 
         let progress =
             start_fuzz_progress(self.cr.progress, self.cr.name, &func.name, fuzz_config.runs);
 
         // Run fuzz test.
         let fuzzed_executor =
-            FuzzedExecutor::new(self.executor.into_owned(), runner, self.tcfg.sender, fuzz_config);
+            FuzzedExecutor::new(self.executor.into_owned(), runner, self.tcfg.sender, fuzz_config)
+                .with_differential(differential);
         let result = fuzzed_executor.fuzz(
             func,
             &self.setup.fuzz_fixtures,
@@ -810,6 +923,43 @@ fn run_fuzz_test(mut self, func: &Function) -> TestResult {
         self.result
     }
 
+    /// Returns the reference implementation of a differential fuzz test, if configured.
+    fn differential_reference(
+        &self,
+        fuzz_config: &FuzzConfig,
+    ) -> Result<Option<DifferentialReference>> {
+        let Some(differential) = &fuzz_config.differential else { return Ok(None) };
+        match (&differential.ffi, &differential.function) {
+            (Some(command), None) => {
+                if !self.config.ffi {
+                    eyre::bail!(
+                        "FFI is disabled; add the `--ffi` flag to allow differential tests to call external commands"
+                    );
+                }
+                if command.is_empty() {
+                    eyre::bail!("differential command is empty");
+                }
+                Ok(Some(DifferentialReference::Ffi {
+                    command: command.clone(),
+                    root: self.config.root.clone(),
+                }))
+            }
+            (None, Some(name)) => {
+                let func = self
+                    .cr
+                    .contract
+                    .abi
+                    .function(name)
+                    .and_then(|functions| functions.first())
+                    .ok_or_else(|| {
+                        eyre::eyre!("differential reference function `{name}` not found")
+                    })?;
+                Ok(Some(DifferentialReference::Function(func.clone())))
+            }
+            _ => eyre::bail!("differential test requires exactly one of `ffi` or `function`"),
+        }
+    }
+
     /// Prepares single unit test and fuzz test execution:
     /// - set up the test result and executor
     /// - check if before test txes are configured and apply them in order
@@ -891,6 +1041,34 @@ fn clone_executor(&self) -> Executor {
     }
 }
 
+/// Returns the arguments of the cases of a table test, from the fixtures of its parameters.
+///
+/// Every parameter must have a fixture, and all fixtures must have the same number of entries: the
+/// `i`-th case is called with the `i`-th entry of each fixture.
+fn table_cases(func: &Function, fixtures: &FuzzFixtures) -> Result<Vec<Vec<DynSolValue>>, String> {
+    if func.inputs.is_empty() {
+        return Err(format!("table test `{}` has no parameters", func.name));
+    }
+    let columns = func
+        .inputs
+        .iter()
+        .map(|param| {
+            fixtures.param_fixtures(&param.name).ok_or_else(|| {
+                format!("no fixture defined for parameter `{}` of table test", param.name)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let len = columns[0].len();
+    if columns.iter().any(|column| column.len() != len) {
+        return Err("fixtures of table test parameters must have the same length".to_string());
+    }
+    if len == 0 {
+        return Err("fixtures of table test parameters are empty".to_string());
+    }
+    Ok((0..len).map(|i| columns.iter().map(|column| column[i].clone()).collect()).collect())
+}
+
 fn fuzzer_with_cases(
     seed: Option<U256>,
     cases: u32,
@@ -947,7 +1125,13 @@ fn persisted_call_sequence(path: &Path, bytecode: &Bytes) -> Option<Vec<BaseCoun
     )
 }
 
+/// Returns the directory of the corpus of a fuzz test.
+fn fuzz_corpus_dir(corpus_dir: PathBuf, contract_name: &str, test_name: &str) -> PathBuf {
+    canonicalized(corpus_dir.join(contract_name.split(':').next_back().unwrap()).join(test_name))
+}
+
 /// Helper functions to return canonicalized invariant failure paths.
+
 fn invariant_failure_paths(
     config: &InvariantConfig,
     contract_name: &str,
@@ -0,0 +1,210 @@
+//! Conversion of compiler diagnostics to [SARIF](https://sarifweb.azurewebsites.net), the format
+//! read by GitHub code scanning and IDEs to annotate sources.
+
+use foundry_common::version::SEMVER_VERSION;
+use foundry_compilers::{
+    artifacts::{Severity, SourceLocation},
+    compilers::multi::MultiCompilerError,
+    ProjectCompileOutput,
+};
+use foundry_config::Config;
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A diagnostic of the compiler.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    /// The error code, or the error type if there is none, e.g. `2072` or `ParserError`.
+    pub rule: String,
+    /// The type of the error, e.g. `Warning` or `TypeError`.
+    pub kind: String,
+    pub severity: Severity,
+    pub message: String,
+    /// The source name and byte range of the diagnostic.
+    pub location: Option<(String, usize, usize)>,
+}
+
+impl Diagnostic {
+    fn new(error: &MultiCompilerError) -> Self {
+        match error {
+            MultiCompilerError::Solc(error) => {
+                let location = error.source_location.as_ref().and_then(
+                    |SourceLocation { file, start, end }| {
+                        Some((
+                            file.clone(),
+                            usize::try_from(*start).ok()?,
+                            usize::try_from(*end).ok()?,
+                        ))
+                    },
+                );
+                Self {
+                    rule: error
+                        .error_code
+                        .map_or_else(|| error.r#type.clone(), |code| code.to_string()),
+                    kind: error.r#type.clone(),
+                    severity: error.severity,
+                    message: error.message.clone(),
+                    location,
+                }
+            }
+            // Vyper only reports line numbers
+            MultiCompilerError::Vyper(error) => Self {
+                rule: "VyperError".to_string(),
+                kind: "VyperError".to_string(),
+                severity: error.severity,
+                message: error.message.clone(),
+                location: None,
+            },
+        }
+    }
+}
+
+/// Returns the SARIF log of the diagnostics of a compilation, excluding the error codes and files
+/// ignored in `config`.
+pub fn compiler_diagnostics(output: &ProjectCompileOutput, config: &Config) -> Value {
+    let ignored_codes = config.ignored_error_codes.iter().map(|code| u64::from(*code).to_string());
+    let ignored_codes = ignored_codes.collect::<Vec<_>>();
+    let diagnostics = output
+        .output()
+        .errors
+        .iter()
+        .map(Diagnostic::new)
+        .filter(|diagnostic| {
+            // errors can't be ignored
+            diagnostic.severity.is_error() ||
+                (!ignored_codes.contains(&diagnostic.rule) &&
+                    !diagnostic.location.as_ref().is_some_and(|(file, ..)| {
+                        let path = config.root.join(file);
+                        config
+                            .ignored_file_paths
+                            .iter()
+                            .any(|ignored| path.starts_with(config.root.join(ignored)))
+                    }))
+        })
+        .collect::<Vec<_>>();
+    to_sarif(&diagnostics, &config.root)
+}
+
+/// Returns the SARIF log of `diagnostics`, with the locations relative to `root`.
+pub fn to_sarif(diagnostics: &[Diagnostic], root: &Path) -> Value {
+    let mut sources = HashMap::new();
+    let mut rules = BTreeMap::new();
+    let mut results = Vec::with_capacity(diagnostics.len());
+    for diagnostic in diagnostics {
+        rules.entry(diagnostic.rule.clone()).or_insert_with(|| {
+            json!({
+                "id": diagnostic.rule,
+                "name": diagnostic.kind,
+                "shortDescription": { "text": diagnostic.kind },
+            })
+        });
+
+        let mut result = json!({
+            "ruleId": diagnostic.rule,
+            "level": level(diagnostic.severity),
+            "message": { "text": diagnostic.message },
+        });
+        if let Some((file, start, end)) = &diagnostic.location {
+            let path = Path::new(file);
+            let uri = path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+            let source = sources
+                .entry(file.clone())
+                .or_insert_with(|| std::fs::read_to_string(root.join(path)).ok());
+            let mut location = json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" },
+                },
+            });
+            if let Some(source) = source {
+                location["physicalLocation"]["region"] = region(source, *start, *end);
+            }
+            result["locations"] = json!([location]);
+        }
+        results.push(result);
+    }
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "forge",
+                    "version": SEMVER_VERSION,
+                    "informationUri": "https://book.getfoundry.sh",
+                    "rules": rules.into_values().collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// Returns the SARIF region of the byte range `start..end` of `source`, with 1-based lines and
+/// columns.
+fn region(source: &str, start: usize, end: usize) -> Value {
+    let position = |offset: usize| {
+        let offset = offset.min(source.len());
+        let before = source.get(..offset).unwrap_or(source);
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+        (line, column)
+    };
+    let (start_line, start_column) = position(start);
+    let (end_line, end_column) = position(end.max(start));
+    json!({
+        "startLine": start_line,
+        "startColumn": start_column,
+        "endLine": end_line,
+        "endColumn": end_column,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_byte_ranges_to_regions() {
+        let source = "contract A {\n    uint x;\n}\n";
+        let start = source.find("uint").unwrap();
+        assert_eq!(
+            region(source, start, start + 7),
+            json!({ "startLine": 2, "startColumn": 5, "endLine": 2, "endColumn": 12 })
+        );
+        assert_eq!(region(source, 0, 0)["startLine"], 1);
+    }
+
+    #[test]
+    fn groups_diagnostics_by_rule() {
+        let warning = Diagnostic {
+            rule: "2072".to_string(),
+            kind: "Warning".to_string(),
+            severity: Severity::Warning,
+            message: "Unused local variable.".to_string(),
+            location: Some(("src/A.sol".to_string(), 0, 1)),
+        };
+        let sarif = to_sarif(&[warning.clone(), warning], Path::new("/nonexistent"));
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+        assert_eq!(run["results"].as_array().unwrap().len(), 2);
+        assert_eq!(run["results"][0]["level"], "warning");
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/A.sol"
+        );
+    }
+}
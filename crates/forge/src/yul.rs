@@ -0,0 +1,164 @@
+//! Support for projects of pure Yul objects, see `language = "yul"`.
+
+use eyre::Result;
+use foundry_common::fs;
+use foundry_compilers::utils::source_files_iter;
+use foundry_config::Config;
+use regex::Regex;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+/// The path of the generated deployer library, relative to the test directory.
+pub const DEPLOYER_PATH: &str = "utils/YulDeployer.sol";
+
+static OBJECT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"object\s+"([^"]+)""#).unwrap());
+
+/// A top-level Yul object of the project sources.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct YulObject {
+    /// The name of the object, which is also the name of its artifact.
+    pub name: String,
+    /// The source file of the object.
+    pub path: PathBuf,
+}
+
+impl YulObject {
+    /// Returns the artifact identifier of the object, as accepted by `vm.getCode`.
+    pub fn artifact_id(&self) -> String {
+        let file = self.path.file_name().unwrap_or_default().to_string_lossy();
+        format!("{file}:{}", self.name)
+    }
+}
+
+/// Returns the name of the top-level object of a Yul source.
+pub fn object_name(source: &str) -> Option<&str> {
+    OBJECT.captures(source).and_then(|caps| caps.get(1)).map(|name| name.as_str())
+}
+
+/// Returns the top-level Yul objects of the files in `src`.
+pub fn objects(src: &Path) -> Result<Vec<YulObject>> {
+    let mut objects = Vec::new();
+    for path in source_files_iter(src, &["yul"]) {
+        let source = fs::read_to_string(&path)?;
+        if let Some(name) = object_name(&source) {
+            objects.push(YulObject { name: name.to_string(), path });
+        }
+    }
+    objects.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(objects)
+}
+
+/// Generates the `YulDeployer` library of a Yul project in the test directory, unless it is up to
+/// date.
+///
+/// Returns the path of the library, or `None` if the project is not a Yul project.
+pub fn write_deployer(config: &Config) -> Result<Option<PathBuf>> {
+    if !config.language.is_yul() {
+        return Ok(None);
+    }
+
+    let objects = objects(&config.src)?;
+    if objects.is_empty() {
+        let _ = sh_warn!("No Yul objects found in `{}`", config.src.display());
+    }
+    let source = deployer_source(&objects);
+
+    let path = config.test.join(DEPLOYER_PATH);
+    if fs::read_to_string(&path).ok().as_deref() != Some(source.as_str()) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, source)?;
+        trace!(path = %path.display(), objects = objects.len(), "generated Yul deployer");
+    }
+    Ok(Some(path))
+}
+
+/// Returns the source of the `YulDeployer` library, with a `deploy<Name>` function for each
+/// object.
+fn deployer_source(objects: &[YulObject]) -> String {
+    let mut source = String::from(
+        r#"// SPDX-License-Identifier: UNLICENSED
+// This file is generated by forge for `language = "yul"`, do not edit.
+pragma solidity >=0.8.12;
+
+interface YulDeployerVm {
+    function getCode(string calldata artifactPath) external view returns (bytes memory);
+}
+
+/// @notice Deploys the Yul objects of the project.
+library YulDeployer {
+    YulDeployerVm private constant VM =
+        YulDeployerVm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    /// @notice Deploys the Yul object of `artifactPath` with the ABI-encoded constructor `args`.
+    function deploy(string memory artifactPath, bytes memory args) internal returns (address deployed) {
+        bytes memory code = bytes.concat(VM.getCode(artifactPath), args);
+        assembly {
+            deployed := create(0, add(code, 0x20), mload(code))
+        }
+        require(deployed != address(0), string.concat("YulDeployer: failed to deploy ", artifactPath));
+    }
+"#,
+    );
+
+    // object names may not be valid identifiers, and must be unique
+    let mut functions = BTreeMap::new();
+    for object in objects {
+        let ident: String = object
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+            .collect();
+        functions.entry(ident).or_insert(object);
+    }
+    for (ident, object) in functions {
+        let id = object.artifact_id();
+        let _ = write!(
+            source,
+            r#"
+    /// @notice Deploys the Yul object `{name}`.
+    function deploy{ident}() internal returns (address) {{
+        return deploy("{id}", "");
+    }}
+
+    /// @notice Deploys the Yul object `{name}` with the ABI-encoded constructor `args`.
+    function deploy{ident}(bytes memory args) internal returns (address) {{
+        return deploy("{id}", args);
+    }}
+"#,
+            name = object.name,
+        );
+    }
+    source.push_str("}\n");
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_top_level_object_name() {
+        let source = r#"
+            object "Counter" {
+                code { datacopy(0, dataoffset("runtime"), datasize("runtime")) }
+                object "runtime" { code { } }
+            }
+        "#;
+        assert_eq!(object_name(source), Some("Counter"));
+        assert_eq!(object_name("{ sstore(0, 1) }"), None);
+    }
+
+    #[test]
+    fn generates_deploy_functions() {
+        let objects = [YulObject { name: "My-Token".into(), path: "src/Token.yul".into() }];
+        let source = deployer_source(&objects);
+        assert!(source.contains("function deployMy_Token() internal returns (address)"));
+        assert!(source.contains(r#"return deploy("Token.yul:My-Token", args);"#));
+    }
+}
@@ -0,0 +1,69 @@
+//! Contains tests for `forge audit-deps`.
+
+use std::fs;
+
+const ADVISORIES: &str = r#"[
+  {
+    "id": "GHSA-9vx6-7xxf-x967",
+    "summary": "Base64 encoding may read from potentially dirty memory",
+    "affected": [{
+      "package": { "name": "@openzeppelin/contracts", "ecosystem": "npm" },
+      "ranges": [{ "type": "SEMVER", "events": [{ "introduced": "4.5.0" }, { "fixed": "4.9.6" }] }]
+    }]
+  }
+]"#;
+
+forgetest!(reports_vulnerable_dependencies, |prj, cmd| {
+    let oz = prj.root().join("lib/openzeppelin-contracts");
+    fs::create_dir_all(oz.join("contracts/utils")).unwrap();
+    fs::write(
+        oz.join("contracts/package.json"),
+        r#"{ "name": "@openzeppelin/contracts", "version": "4.9.5" }"#,
+    )
+    .unwrap();
+    fs::write(oz.join("contracts/utils/Base64.sol"), "library Base64 {}").unwrap();
+    prj.add_source(
+        "Token.sol",
+        r#"
+import "../lib/openzeppelin-contracts/contracts/utils/Base64.sol";
+contract Token {}
+"#,
+    )
+    .unwrap();
+    prj.add_source("Other.sol", "contract Other {}").unwrap();
+
+    let advisories = prj.root().join("advisories.json");
+    fs::write(&advisories, ADVISORIES).unwrap();
+
+    let output = cmd
+        .args(["audit-deps", "--advisories"])
+        .arg(&advisories)
+        .assert_code(1)
+        .get_output()
+        .stdout_lossy();
+    assert!(output.contains("1 vulnerabilities found"), "{output}");
+    assert!(output.contains("GHSA-9vx6-7xxf-x967"), "{output}");
+    assert!(output.contains("4.9.6"), "{output}");
+    assert!(output.contains("src/Token.sol"), "{output}");
+    assert!(!output.contains("src/Other.sol"), "{output}");
+
+    let output = cmd.arg("--json").assert_code(1).get_output().stdout_lossy();
+    let findings: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(findings[0]["package"], "@openzeppelin/contracts");
+    assert_eq!(findings[0]["importedBy"][0], "src/Token.sol");
+
+    // the fixed release is not affected
+    fs::write(
+        oz.join("contracts/package.json"),
+        r#"{ "name": "@openzeppelin/contracts", "version": "4.9.6" }"#,
+    )
+    .unwrap();
+    cmd.forge_fuse()
+        .args(["audit-deps", "--advisories"])
+        .arg(&advisories)
+        .assert_success()
+        .stdout_eq(str![[r#"
+No known vulnerabilities found in [..] audited dependencies.
+
+"#]]);
+});
@@ -329,3 +329,108 @@
 
     cmd.args(["build"]).assert_success();
 });
+
+// tests that artifacts of previously compiled sources are restored from the content cache
+forgetest!(can_restore_artifacts_from_content_cache, |prj, cmd| {
+    prj.update_config(|config| {
+        config.content_cache = true;
+        config.content_cache_path = Some("artifact-store".into());
+    });
+
+    prj.add_source("Counter", "contract Counter { function a() public {} }").unwrap();
+    cmd.arg("build").assert_success();
+
+    prj.add_source("Counter", "contract Counter { function b() public {} }").unwrap();
+    cmd.forge_fuse().arg("build").assert_success();
+
+    // switching back to the previous content doesn't recompile
+    prj.add_source("Counter", "contract Counter { function a() public {} }").unwrap();
+    cmd.forge_fuse().arg("build").assert_success().stdout_eq(str![[r#"
+No files changed, compilation skipped
+
+"#]]);
+
+    let artifact =
+        std::fs::read_to_string(prj.root().join("out/Counter.sol/Counter.json")).unwrap();
+    assert!(artifact.contains("\"a()\""));
+    assert!(!artifact.contains("\"b()\""));
+});
+
+// tests that contract sizes can be compared against a saved size report
+forgetest!(build_sizes_compare_json, |prj, cmd| {
+    prj.add_source("Foo", "contract Foo {}").unwrap();
+    prj.add_source("Bar", "contract Bar {}").unwrap();
+    let baseline = prj.root().join("sizes.json");
+    std::fs::write(
+        &baseline,
+        r#"{"Foo":{"runtime_size":50,"init_size":80,"runtime_margin":24526,"init_margin":49072}}"#,
+    )
+    .unwrap();
+
+    cmd.args(["build", "--sizes", "--json", "--compare"])
+        .arg(&baseline)
+        .assert_success()
+        .stdout_eq(
+            str![[r#"
+{
+  "Bar": {
+    "runtime_size": 62,
+    "init_size": 88,
+    "runtime_margin": 24514,
+    "init_margin": 49064
+  },
+  "Foo": {
+    "runtime_size": 62,
+    "init_size": 88,
+    "runtime_margin": 24514,
+    "init_margin": 49064,
+    "runtime_diff": 12,
+    "init_diff": 8
+  }
+}
+"#]]
+            .is_json(),
+        );
+
+    cmd.forge_fuse()
+        .args(["build", "--sizes", "--compare"])
+        .arg(&baseline)
+        .args(["--max-size-increase", "10"])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: contract sizes increased by more than 10 bytes: Foo
+
+"#]]);
+});
+
+// tests that compiler diagnostics can be printed as SARIF
+forgetest!(build_diagnostics_sarif, |prj, cmd| {
+    prj.add_raw_source(
+        "Warn.sol",
+        r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Warn {
+    function f() public pure {
+        uint256 x;
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let output =
+        cmd.args(["build", "--format", "sarif"]).assert_success().get_output().stdout_lossy();
+    let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "forge");
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "2072");
+    assert_eq!(result["level"], "warning");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/Warn.sol");
+    assert_eq!(location["region"]["startLine"], 6);
+    assert_eq!(location["region"]["startColumn"], 9);
+});
@@ -3,7 +3,8 @@
 use crate::constants::*;
 use foundry_compilers::artifacts::{remappings::Remapping, ConfigurableContractArtifact, Metadata};
 use foundry_config::{
-    parse_with_profile, BasicConfig, Chain, Config, FuzzConfig, InvariantConfig, SolidityErrorCode,
+    parse_with_profile, BasicConfig, Chain, Config, FuzzConfig, GasSnapshotTolerance,
+    InvariantConfig, SolidityErrorCode,
 };
 use foundry_test_utils::{
     foundry_compilers::PathStyle,
@@ -81,6 +82,33 @@
     prj.assert_cleaned();
 });
 
+// checks that `clean --stale` only removes the artifacts of deleted sources
+forgetest!(can_clean_stale_artifacts, |prj, cmd| {
+    prj.add_source("Counter", "contract Counter {}").unwrap();
+    prj.add_source("Greeter", "contract Greeter {}").unwrap();
+    cmd.arg("build").assert_success();
+
+    cmd.forge_fuse().args(["clean", "--stale"]).assert_success().stdout_eq(str![[r#"
+No stale artifacts found.
+
+"#]]);
+
+    std::fs::remove_file(prj.root().join("src/Greeter.sol")).unwrap();
+    cmd.forge_fuse().args(["clean", "--stale"]).assert_success().stdout_eq(str![[r#"
+Removed 1 stale cache entries, 1 artifacts and 0 build info files, reclaimed [..].
+
+"#]]);
+
+    assert!(prj.root().join("out/Counter.sol/Counter.json").exists());
+    assert!(!prj.root().join("out/Greeter.sol").exists());
+
+    // the remaining sources are not recompiled
+    cmd.forge_fuse().arg("build").assert_success().stdout_eq(str![[r#"
+No files changed, compilation skipped
+
+"#]]);
+});
+
 // checks that `cache ls` can be invoked and displays the foundry cache
 forgetest!(
     #[ignore]
@@ -327,6 +355,125 @@
     assert!(!prj.root().join("lib/forge-std/.git").exists());
 });
 
+// Checks that a Huff project can be initialized
+forgetest!(can_init_huff, |prj, cmd| {
+    prj.wipe();
+
+    cmd.arg("init").arg(prj.root()).args(["--huff", "--offline", "--no-git"]).assert_success();
+    prj.assert_config_exists();
+
+    assert!(prj.root().join("src/Counter.huff").exists());
+    assert!(prj.root().join("src/utils/HuffDeployer.sol").exists());
+    assert!(prj.root().join("src/interface/ICounter.sol").exists());
+    assert!(prj.root().join("test/Counter.t.sol").exists());
+    assert!(prj.root().join("script/Counter.s.sol").exists());
+    assert!(!prj.root().join("src/Counter.sol").exists());
+
+    let config = std::fs::read_to_string(prj.root().join("foundry.toml")).unwrap();
+    assert!(config.contains("ffi = true"));
+});
+
+// Checks that the interactive wizard can scaffold a Vyper project
+forgetest!(can_init_interactive_vyper, |prj, cmd| {
+    prj.wipe();
+
+    cmd.arg("init")
+        .arg(prj.root())
+        .args(["--interactive", "--ci", "none", "--offline", "--no-git"])
+        .stdin(|mut stdin| {
+            use std::io::Write;
+            // Vyper, then the defaults for the solc version, license, fuzz runs and invariant depth
+            stdin.write_all(b"1\n\n\n\n\n").unwrap();
+        })
+        .assert_success();
+
+    assert!(prj.root().join("src/Counter.vy").exists());
+    assert!(prj.root().join("src/interface/ICounter.sol").exists());
+    assert!(prj.root().join("test/Counter.t.sol").exists());
+    assert!(prj.root().join("script/Counter.s.sol").exists());
+    assert!(!prj.root().join("src/Counter.sol").exists());
+
+    let config = std::fs::read_to_string(prj.root().join("foundry.toml")).unwrap();
+    assert!(!config.contains("ffi"));
+});
+
+// Checks that `forge init --ci` generates the pipeline of the chosen provider
+forgetest!(can_init_with_ci_provider, |prj, cmd| {
+    prj.wipe();
+    cmd.arg("init").arg(prj.root()).args(["--offline", "--ci", "gitlab"]).assert_success();
+    assert!(prj.root().join(".gitlab-ci.yml").exists());
+    assert!(!prj.root().join(".github").exists());
+
+    prj.wipe();
+    cmd.forge_fuse().arg("init").arg(prj.root()).args(["--offline", "--ci", "none"]);
+    cmd.assert_success();
+    assert!(!prj.root().join(".gitlab-ci.yml").exists());
+    assert!(!prj.root().join(".github").exists());
+    assert!(!prj.root().join(".circleci").exists());
+});
+
+// Checks that a project can be scaffolded from an ABI
+forgetest!(can_init_from_abi, |prj, cmd| {
+    prj.wipe();
+    let abi = prj.root().parent().unwrap().join("Vault.json");
+    std::fs::write(
+        &abi,
+        r#"[
+            {"type":"function","name":"deposit","inputs":[{"name":"amount","type":"uint256"}],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"nonpayable"},
+            {"type":"function","name":"owner","inputs":[],"outputs":[{"name":"","type":"address"}],"stateMutability":"view"},
+            {"type":"function","name":"quote","inputs":[{"name":"symbol","type":"string"}],"outputs":[{"name":"","type":"string"}],"stateMutability":"pure"}
+        ]"#,
+    )
+    .unwrap();
+
+    cmd.arg("init").arg(prj.root()).arg("--from-abi").arg(&abi).assert_success();
+
+    assert!(prj.root().join("src/interface/IVault.sol").exists());
+    assert!(prj.root().join("src/mocks/MockVault.sol").exists());
+    assert!(prj.root().join("test/Vault.t.sol").exists());
+    assert!(!prj.root().join("src/Counter.sol").exists());
+
+    cmd.forge_fuse().arg("test").assert_success().stdout_eq(str![[r#"
+...
+Ran 3 tests for test/Vault.t.sol:VaultTest
+[PASS] test_deposit_0() ([GAS])
+[PASS] test_owner_1() ([GAS])
+[PASS] test_quote_2() ([GAS])
+...
+"#]]);
+});
+
+// Checks that a workspace can be initialized, built and tested from the root
+forgetest!(can_init_workspace, |prj, cmd| {
+    prj.wipe();
+
+    cmd.arg("init").arg(prj.root()).arg("--workspace").assert_success();
+    assert!(prj.root().join("packages/counter/src/Counter.sol").exists());
+    assert!(prj.root().join("packages/counter/test/Counter.t.sol").exists());
+    assert!(prj.root().join("lib/forge-std").exists());
+    assert!(!prj.root().join("src").exists());
+
+    let config = std::fs::read_to_string(prj.root().join("foundry.toml")).unwrap();
+    assert!(config.contains("[profile.counter]"));
+    assert!(config.contains("members = [\"packages/*\"]"));
+
+    cmd.forge_fuse().arg("build").assert_success().stdout_eq(str![[r#"
+Compiling workspace member counter...
+[COMPILING_FILES] with [SOLC_VERSION]
+[SOLC_VERSION] [ELAPSED]
+Compiler run successful!
+
+"#]]);
+    assert!(prj.root().join("out/counter/Counter.sol").exists());
+
+    cmd.forge_fuse().arg("test").assert_success().stdout_eq(str![[r#"
+Testing workspace member counter...
+...
+Ran 2 tests for packages/counter/test/Counter.t.sol:CounterTest
+...
+"#]]);
+});
+
 // Checks that quiet mode does not print anything
 forgetest!(can_init_quiet, |prj, cmd| {
     prj.wipe();
@@ -573,14 +720,151 @@
     assert!(prj.root().join("scripts").exists());
 });
 
+// checks that forge can init from a subdirectory of a template
+forgetest!(can_init_template_with_template_path, |prj, cmd| {
+    prj.wipe();
+    cmd.args(["init", "--template", "foundry-rs/forge-template", "--template-path", "src"])
+        .arg(prj.root())
+        .assert_success()
+        .stdout_eq(str![[r#"
+Initializing [..] from https://github.com/foundry-rs/forge-template...
+    Initialized forge project
+
+"#]]);
+
+    assert!(prj.root().join(".git").exists());
+    assert!(!prj.root().join("src").exists());
+    assert!(!prj.root().join("foundry.toml").exists());
+});
+
+// checks that `forge init --template <DIR>` copies a local template
+forgetest!(can_init_local_template, |prj, cmd| {
+    let template = tempfile::tempdir().unwrap();
+    for file in ["src/Token.sol", "test/Token.t.sol", "node_modules/pkg/index.js", "notes.md"] {
+        let path = template.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+    fs::write(template.path().join(".forgeignore"), "node_modules/\n").unwrap();
+    fs::write(template.path().join(".forgeinclude"), "*.sol\n").unwrap();
+
+    prj.wipe();
+    cmd.args(["init", "--template"]).arg(template.path()).arg(prj.root()).assert_success();
+
+    assert!(prj.root().join(".git").exists());
+    assert!(prj.root().join("src/Token.sol").exists());
+    assert!(prj.root().join("test/Token.t.sol").exists());
+    assert!(!prj.root().join("node_modules").exists());
+    assert!(!prj.root().join("notes.md").exists());
+    assert!(!prj.root().join(".forgeignore").exists());
+});
+
+// checks that `forge init --template <DIR> --branch` copies a branch of a local template repository
+forgetest!(can_init_local_template_branch, |prj, cmd| {
+    let template = tempfile::tempdir().unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .current_dir(template.path())
+            .args(["-c", "user.name=foundry", "-c", "user.email=foundry@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    fs::create_dir_all(template.path().join("src")).unwrap();
+    fs::write(template.path().join("src/Token.sol"), "").unwrap();
+    git(&["init"]);
+    git(&["add", "--all"]);
+    git(&["commit", "-m", "token"]);
+    git(&["checkout", "-b", "vault"]);
+    fs::write(template.path().join("src/Vault.sol"), "").unwrap();
+    git(&["add", "--all"]);
+    git(&["commit", "-m", "vault"]);
+    git(&["checkout", "-"]);
+
+    prj.wipe();
+    cmd.args(["init", "--template"])
+        .arg(template.path())
+        .args(["--branch", "vault"])
+        .arg(prj.root())
+        .assert_success();
+    assert!(prj.root().join("src/Token.sol").exists());
+    assert!(prj.root().join("src/Vault.sol").exists());
+
+    prj.wipe();
+    cmd.forge_fuse().args(["init", "--template"]).arg(template.path()).arg(prj.root());
+    cmd.assert_success();
+    assert!(prj.root().join("src/Token.sol").exists());
+    assert!(!prj.root().join("src/Vault.sol").exists());
+
+    prj.wipe();
+    cmd.forge_fuse()
+        .args(["init", "--template"])
+        .arg(template.path())
+        .args(["--branch", "missing"])
+        .arg(prj.root())
+        .assert_failure();
+});
+
+// checks that `forge init --template` substitutes the variables declared in `template.toml`
+forgetest!(can_init_template_with_variables, |prj, cmd| {
+    let template = tempfile::tempdir().unwrap();
+    fs::create_dir_all(template.path().join("src")).unwrap();
+    fs::write(
+        template.path().join("template.toml"),
+        "[variables]\nproject_name = {}\ntoken = {}\nsolc_version = { default = \"0.8.28\" }\n",
+    )
+    .unwrap();
+    fs::write(
+        template.path().join("src/{{ token }}.sol"),
+        "pragma solidity {{solc_version}};\ncontract {{ token }} {}\n// ${{ matrix.os }}\n",
+    )
+    .unwrap();
+
+    prj.wipe();
+    cmd.args(["init", "--template"])
+        .arg(template.path())
+        .args(["--var", "token=Vault"])
+        .arg(prj.root())
+        .assert_success();
+
+    assert!(!prj.root().join("template.toml").exists());
+    assert_eq!(
+        fs::read_to_string(prj.root().join("src/Vault.sol")).unwrap(),
+        "pragma solidity 0.8.28;\ncontract Vault {}\n// ${{ matrix.os }}\n"
+    );
+
+    // fails if a variable has no value
+    prj.wipe();
+    cmd.forge_fuse().args(["init", "--template"]).arg(template.path()).arg(prj.root());
+    cmd.assert_failure();
+});
+
 // checks that init fails when the provided template doesn't exist
 forgetest!(fail_init_nonexistent_template, |prj, cmd| {
     prj.wipe();
-    cmd.args(["init", "--template", "a"]).arg(prj.root()).assert_failure().stderr_eq(str![[r#"
+    cmd.args(["init", "--template", "github.com/a"]).arg(prj.root()).assert_failure().stderr_eq(
+        str![[r#"
 remote: Not Found
 fatal: repository 'https://github.com/a/' not found
 Error: git fetch exited with code 128
 
+"#]],
+    );
+});
+
+// checks that init fails when a template short name can't be resolved from the registry
+forgetest!(fail_init_unresolved_template_short_name, |prj, cmd| {
+    prj.wipe();
+    cmd.args(["init", "--template", "a", "--registry-url", "http://127.0.0.1:1/registry.json"])
+        .arg(prj.root())
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: could not resolve template `a` from the template registry
+
+Context:
+- Error #0: failed to fetch template registry from http://127.0.0.1:1/registry.json
+...
 "#]]);
 });
 
@@ -1065,6 +1349,62 @@ struct Bar {
 "#]]);
 });
 
+// test that `forge snapshot --check` tolerates deviations configured per test
+forgetest!(can_check_snapshot_with_tolerances, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "ATest.t.sol",
+        r#"
+import "./test.sol";
+contract ATest is DSTest {
+    function testExample() public {
+        assertTrue(true);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["snapshot"]).assert_success();
+
+    // Increase the snapshotted gas by 50.
+    let snap = prj.root().join(".gas-snapshot");
+    let content = std::fs::read_to_string(&snap).unwrap();
+    let gas = content.split("(gas: ").nth(1).unwrap().trim_end_matches(')');
+    let bumped = gas.parse::<u64>().unwrap() + 50;
+    std::fs::write(&snap, content.replace(gas, &bumped.to_string())).unwrap();
+
+    prj.update_config(|config| {
+        config.gas_snapshot_tolerances = vec![
+            GasSnapshotTolerance { absolute: Some(10), ..Default::default() },
+            GasSnapshotTolerance {
+                contract: Some("ATest".to_string()),
+                test: Some("testExample".to_string()),
+                absolute: Some(100),
+                ..Default::default()
+            },
+        ];
+    });
+    cmd.forge_fuse().args(["snapshot", "--check"]).assert_success().stdout_eq(str![[r#"
+...
+ATest:
+  Tolerated diff in "testExample()": consumed "(gas: [..])" gas, expected "(gas: [..])" gas
+
+"#]]);
+
+    prj.update_config(|config| {
+        config.gas_snapshot_tolerances =
+            vec![GasSnapshotTolerance { absolute: Some(10), ..Default::default() }];
+    });
+    cmd.forge_fuse().args(["snapshot", "--check"]).assert_failure().stdout_eq(str![[r#"
+...
+ATest:
+  Diff in "testExample()": consumed "(gas: [..])" gas, expected "(gas: [..])" gas
+
+"#]]);
+});
+
 // test that `forge build` does not print `(with warnings)` if file path is ignored
 forgetest!(can_compile_without_warnings_ignored_file_paths, |prj, cmd| {
     // Ignoring path and setting empty error_codes as default would set would set some error codes
@@ -1376,6 +1716,41 @@ struct Bar {
     remove(&mut cmd, "lib/forge-std");
 });
 
+// test that `forge remove` cleans up the remappings of the removed dependency
+forgetest!(can_remove_dependency_remappings, |prj, cmd| {
+    cmd.git_init();
+    cmd.forge_fuse().args(["install", "foundry-rs/forge-std"]).assert_success();
+
+    let remappings_txt = prj.root().join("remappings.txt");
+    fs::write(&remappings_txt, "forge-std/=lib/forge-std/src/\nsolady/=lib/solady/src/\n").unwrap();
+    prj.update_config(|config| {
+        config.remappings = vec![
+            Remapping::from_str("forge-std-test/=lib/forge-std/test/").unwrap().into(),
+            Remapping::from_str("solmate/=lib/solmate/src/").unwrap().into(),
+        ];
+    });
+
+    cmd.forge_fuse()
+        .args(["remove", "--force", "--dry-run", "forge-std"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Would remove 'forge-std' in lib/forge-std
+Would remove remapping 'forge-std/=lib/forge-std/src/' from remappings.txt
+Would remove remapping 'forge-std-test/=lib/forge-std/test/' from foundry.toml
+Would remove submodule 'lib/forge-std' from .gitmodules
+
+"#]]);
+    assert!(prj.root().join("lib/forge-std").exists());
+
+    cmd.forge_fuse().args(["remove", "--force", "forge-std"]).assert_success();
+    assert!(!prj.root().join("lib/forge-std").exists());
+    assert_eq!(read_string(&remappings_txt), "solady/=lib/solady/src/\n");
+    let config = read_string(prj.root().join("foundry.toml"));
+    assert!(!config.contains("forge-std"), "{config}");
+    assert!(config.contains("solmate/=lib/solmate/src/"), "{config}");
+    assert!(!read_string(prj.root().join(".gitmodules")).contains("forge-std"));
+});
+
 // test to check we can run `forge install` in an empty dir <https://github.com/foundry-rs/foundry/issues/6519>
 forgetest!(can_install_empty, |prj, cmd| {
     // create
@@ -1456,6 +1831,135 @@ struct Bar {
     assert!(current >= version);
 });
 
+// test that installed dependencies are pinned in `foundry.lock` and can be reinstalled from it
+forgetest!(can_install_from_lockfile, |prj, cmd| {
+    cmd.git_init();
+
+    // fails without a lockfile
+    cmd.forge_fuse().args(["install", "--frozen"]).assert_failure();
+
+    cmd.forge_fuse().args(["install", "--no-git", "foundry-rs/forge-std@v1.9.6"]).assert_success();
+    let lockfile = read_string(prj.root().join("foundry.lock"));
+    assert!(lockfile.contains(r#"[dependencies."lib/forge-std"]"#), "{lockfile}");
+    assert!(lockfile.contains(r#"git = "https://github.com/foundry-rs/forge-std""#), "{lockfile}");
+    assert!(lockfile.contains(r#"tag = "v1.9.6""#), "{lockfile}");
+
+    // reinstalls the pinned dependency
+    let forge_std = prj.root().join("lib/forge-std");
+    fs::remove_dir_all(&forge_std).unwrap();
+    cmd.forge_fuse().args(["install", "--no-git"]).assert_success();
+    assert!(forge_std.join("src/Test.sol").exists());
+    cmd.forge_fuse().args(["install", "--no-git", "--frozen"]).assert_success();
+
+    // build artifacts inside the dependency are ignored
+    fs::create_dir_all(forge_std.join("out")).unwrap();
+    fs::write(forge_std.join("out/Test.json"), "{}").unwrap();
+    cmd.forge_fuse().args(["install", "--no-git", "--frozen"]).assert_success();
+
+    // fails if the installed dependency was modified
+    fs::write(forge_std.join("src/Test.sol"), "").unwrap();
+    cmd.forge_fuse().args(["install", "--no-git", "--frozen"]).assert_failure();
+});
+
+// test that vendored dependencies contain no git metadata and are reinstalled from the lockfile
+forgetest!(can_install_vendored_dependency, |prj, cmd| {
+    cmd.git_init();
+    cmd.forge_fuse().args(["install", "--vendor", "evalir/forge-5980-test"]).assert_success();
+
+    let dep = prj.root().join("lib/forge-5980-test");
+    assert!(dep.join("lib/forge-5980-test-dep/src").exists());
+    assert!(!dep.join(".git").exists());
+    assert!(!dep.join("lib/forge-5980-test-dep/.git").exists());
+    assert!(!prj.root().join(".gitmodules").exists());
+
+    let lockfile = read_string(prj.root().join("foundry.lock"));
+    assert!(lockfile.contains("vendored = true"), "{lockfile}");
+    assert!(lockfile.contains(r#""lib/forge-5980-test-dep" = "#), "{lockfile}");
+
+    fs::remove_dir_all(&dep).unwrap();
+    cmd.forge_fuse().args(["install", "--frozen"]).assert_success();
+    assert!(dep.join("lib/forge-5980-test-dep/src").exists());
+    assert!(!dep.join("lib/forge-5980-test-dep/.git").exists());
+});
+
+// test that npm packages are installed from the registry with a remapping
+forgetest!(can_install_npm_package, |prj, cmd| {
+    cmd.git_init();
+    cmd.forge_fuse().args(["install", "npm:@openzeppelin/contracts@5.0.2"]);
+    cmd.assert_success();
+
+    let dep = prj.root().join("lib/openzeppelin-contracts");
+    assert!(dep.join("token/ERC20/ERC20.sol").exists());
+    assert!(dep.join("package.json").exists());
+
+    let remappings = read_string(prj.root().join("remappings.txt"));
+    assert!(
+        remappings.contains("@openzeppelin/contracts/=lib/openzeppelin-contracts/"),
+        "{remappings}"
+    );
+
+    // refuses to overwrite an installed package
+    cmd.assert_failure();
+});
+
+// test that `forge update --minor` only moves tagged dependencies to compatible releases
+forgetest!(can_update_dependency_to_compatible_release, |prj, cmd| {
+    cmd.git_init();
+    cmd.forge_fuse().args(["install", "foundry-rs/forge-std@v1.9.4"]).assert_success();
+
+    let forge_std = prj.root().join("lib/forge-std");
+    let git = || {
+        let mut git = std::process::Command::new("git");
+        git.current_dir(&forge_std);
+        git
+    };
+    let head = || String::from_utf8(git().args(["rev-parse", "HEAD"]).output().unwrap().stdout);
+    let installed = head().unwrap();
+
+    // does not touch the dependency
+    let output = cmd
+        .forge_fuse()
+        .args(["update", "lib/forge-std", "--minor", "--dry-run"])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    assert!(output.contains("lib/forge-std: v1.9.4 ("), "{output}");
+    assert!(output.contains(") -> v1."), "{output}");
+    assert_eq!(head().unwrap(), installed);
+
+    cmd.forge_fuse().args(["update", "lib/forge-std", "--minor"]).assert_success();
+    assert_ne!(head().unwrap(), installed);
+    let tags = git().args(["tag", "--points-at", "HEAD"]).output().unwrap().stdout;
+    let tags = String::from_utf8_lossy(&tags);
+    assert!(tags.lines().any(|tag| tag.starts_with("v1.")));
+
+    // the lockfile records the new release
+    let lockfile = read_string(prj.root().join("foundry.lock"));
+    assert!(!lockfile.contains(r#"tag = "v1.9.4""#), "{lockfile}");
+    assert!(tags.lines().any(|tag| lockfile.contains(&format!(r#"tag = "{tag}""#))), "{lockfile}");
+});
+
+// test that the npm packages required by an installed package are installed as well
+forgetest!(can_install_transitive_npm_packages, |prj, cmd| {
+    cmd.git_init();
+    cmd.forge_fuse()
+        .args(["install", "npm:@openzeppelin/contracts-upgradeable@5.0.2"])
+        .assert_success();
+
+    // `@openzeppelin/contracts` is a peer dependency of the upgradeable contracts
+    let peer = prj.root().join("lib/openzeppelin-contracts");
+    assert!(peer.join("token/ERC20/ERC20.sol").exists());
+    let lockfile = read_string(prj.root().join("foundry.lock"));
+    assert!(lockfile.contains(r#"npm = "@openzeppelin/contracts""#), "{lockfile}");
+    assert!(lockfile.contains(r#"version = "5.0.2""#), "{lockfile}");
+
+    let remappings = read_string(prj.root().join("remappings.txt"));
+    assert!(
+        remappings.contains("@openzeppelin/contracts/=lib/openzeppelin-contracts/"),
+        "{remappings}"
+    );
+});
+
 // Tests that forge update doesn't break a working dependency by recursively updating nested
 // dependencies
 forgetest!(
@@ -1479,7 +1983,10 @@ struct Bar {
             .stdout_eq(str![[r#"
 Installing forge-5980-test in [..] (url: Some("https://github.com/evalir/forge-5980-test"), tag: None)
     Installed forge-5980-test
-
+Resolved [..] transitive [..]:
+...
+    forge-5980-test-dep [..] in lib/forge-5980-test/lib/forge-5980-test-dep, required by lib/forge-5980-test
+...
 "#]]);
 
         // assert paths exist
@@ -3153,6 +3660,115 @@ struct Bar {
 "#]]);
 });
 
+// checks forge bind generates deploy, error and event helpers in a module per contract
+forgetest!(can_bind_module_per_contract, |prj, cmd| {
+    prj.add_source(
+        "Vault.sol",
+        r#"
+contract Vault {
+    error InsufficientBalance(uint256 balance);
+    event Deposited(address indexed from, uint256 amount);
+
+    function deposit() external payable {
+        emit Deposited(msg.sender, msg.value);
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["bind", "--select", "^Vault$", "--module-per-contract"]).assert_success().stdout_eq(
+        str![[r#"
+[COMPILING_FILES] with [SOLC_VERSION]
+[SOLC_VERSION] [ELAPSED]
+Compiler run successful!
+Generating bindings for 1 contracts
+Bindings have been generated to [..]
+
+"#]],
+    );
+
+    let module = prj.root().join("out/bindings/src/vault");
+    let binding = fs::read_to_string(module.join("mod.rs")).unwrap();
+    assert!(binding.contains("pub fn deploy<"));
+    assert!(binding.contains("mod helpers;"));
+    let helpers = fs::read_to_string(module.join("helpers.rs")).unwrap();
+    assert!(helpers.contains("impl super::Vault::VaultErrors"));
+    assert!(helpers.contains("pub fn decode_error("));
+    assert!(helpers.contains("pub async fn Deposited_stream("));
+
+    // the generated bindings are consistent
+    cmd.forge_fuse()
+        .args(["bind", "--select", "^Vault$", "--module-per-contract", "--skip-build"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Bindings found. Checking for consistency.
+Checking bindings for 1 contracts
+OK.
+
+"#]]);
+});
+
+// checks forge bind-ts generates TypeScript modules and deployed addresses
+forgetest_init!(can_bind_ts, |prj, cmd| {
+    prj.clear();
+
+    // a broadcasted deployment of `Counter` on chain 31337
+    let broadcast = prj.root().join("broadcast/Counter.s.sol/31337");
+    fs::create_dir_all(&broadcast).unwrap();
+    fs::write(
+        broadcast.join("run-latest.json"),
+        r#"{
+  "transactions": [
+    {
+      "transactionType": "CREATE",
+      "contractName": "Counter",
+      "contractAddress": "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+    }
+  ],
+  "timestamp": 1,
+  "chain": 31337
+}"#,
+    )
+    .unwrap();
+
+    cmd.args(["bind-ts", "--select", "^Counter$"]).assert_success().stdout_eq(str![[r#"
+[COMPILING_FILES] with [SOLC_VERSION]
+[SOLC_VERSION] [ELAPSED]
+Compiler run successful!
+Generating bindings for 1 contracts
+Bindings have been generated to [..]
+
+"#]]);
+
+    let bindings = prj.root().join("out/bindings-ts");
+    let counter = fs::read_to_string(bindings.join("Counter.ts")).unwrap();
+    assert!(counter.contains("export const counterAbi = ["));
+    assert!(counter.contains("export const counterBytecode = \"0x"));
+    assert!(counter.contains("export const counterFactory = {"));
+    assert_eq!(
+        fs::read_to_string(bindings.join("addresses.ts")).unwrap(),
+        r#"// This file is generated by `forge bind-ts`, do not edit.
+
+export const addresses = {
+  31337: {
+    Counter: "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+  },
+} as const;
+"#
+    );
+
+    // existing bindings are checked for consistency
+    cmd.forge_fuse()
+        .args(["bind-ts", "--select", "^Counter$", "--skip-build"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Bindings found. Checking for consistency.
+OK.
+
+"#]]);
+});
+
 // checks missing dependencies are auto installed
 forgetest_init!(can_install_missing_deps_test, |prj, cmd| {
     prj.clear();
@@ -3599,6 +4215,42 @@ struct ErrWithMsg {
 "#]]);
 });
 
+forgetest!(inspect_full_report, |prj, cmd| {
+    prj.add_source(
+        "Vault.sol",
+        r#"
+contract Vault {
+    uint256 public totalSupply;
+    address public immutable owner;
+
+    constructor() {
+        owner = msg.sender;
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let output =
+        cmd.args(["inspect", "Vault", "full-report"]).assert_success().get_output().stdout_lossy();
+    let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        report["methodIdentifiers"],
+        serde_json::json!({ "owner()": "8da5cb5b", "totalSupply()": "18160ddd" })
+    );
+    assert_eq!(report["storageLayout"]["storage"][0]["label"], "totalSupply");
+    assert_eq!(report["immutableReferences"].as_object().unwrap().len(), 1);
+    assert!(report["deployedSize"].as_u64().unwrap() > 0);
+    assert!(report["compilerVersion"].is_string());
+    assert!(report["settings"]["optimizer"].is_object());
+
+    // reports of all contracts, keyed by identifier
+    let output =
+        cmd.forge_fuse().args(["inspect", "--all"]).assert_success().get_output().stdout_lossy();
+    let reports: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(reports["src/Vault.sol:Vault"], report);
+});
+
 // checks that `clean` also works with the "out" value set in Config
 forgetest_init!(gas_report_include_tests, |prj, cmd| {
     prj.update_config(|config| {
@@ -3714,6 +4366,67 @@ struct ErrWithMsg {
         );
 });
 
+forgetest_init!(gas_report_format_and_call_paths, |prj, cmd| {
+    prj.update_config(|config| config.fuzz.runs = 1);
+
+    let call_paths = prj.root().join("gas.folded");
+    cmd.args(["test", "--mt", "test_Increment", "--gas-report", "--gas-report-format", "json"])
+        .arg("--gas-report-call-paths")
+        .arg(&call_paths)
+        .assert_success()
+        .stdout_eq(
+            str![[r#"
+[
+  {
+    "contract": "src/Counter.sol:Counter",
+    "deployment": {
+      "gas": 156813,
+      "size": 509
+    },
+    "functions": {
+      "increment()": {
+        "calls": 1,
+        "min": 43482,
+        "mean": 43482,
+        "median": 43482,
+        "max": 43482
+      },
+      "number()": {
+        "calls": 1,
+        "min": 2424,
+        "mean": 2424,
+        "median": 2424,
+        "max": 2424
+      },
+      "setNumber(uint256)": {
+        "calls": 1,
+        "min": 23784,
+        "mean": 23784,
+        "median": 23784,
+        "max": 23784
+      }
+    }
+  }
+]
+"#]]
+            .is_json(),
+        );
+
+    // Gas is attributed to the full call path, excluding nested calls.
+    let folded = std::fs::read_to_string(call_paths).unwrap();
+    let frames = folded.lines().filter_map(|line| line.rsplit_once(' ')).collect::<Vec<_>>();
+    for path in [
+        "CounterTest::setUp",
+        "CounterTest::setUp;Counter::constructor",
+        "CounterTest::setUp;Counter::setNumber",
+        "CounterTest::test_Increment",
+        "CounterTest::test_Increment;Counter::increment",
+        "CounterTest::test_Increment;Counter::number",
+    ] {
+        assert!(frames.iter().any(|(p, gas)| *p == path && gas.parse::<u64>().is_ok()), "{path}");
+    }
+});
+
 forgetest_async!(gas_report_fuzz_invariant, |prj, cmd| {
     // speed up test by running with depth of 15
     prj.update_config(|config| config.invariant.depth = 15);
@@ -3786,3 +4499,102 @@ enum MyEnum { A, B, C }
 Bindings have been generated to [..]"#
     ]]);
 });
+
+// checks that flattened contracts have a single license and pragma, and compile to the same
+// bytecode
+forgetest!(can_flatten_with_unified_pragma, |prj, cmd| {
+    prj.add_raw_source(
+        "A.sol",
+        r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract A {}
+"#,
+    )
+    .unwrap();
+    let target = prj
+        .add_raw_source(
+            "B.sol",
+            r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.4;
+
+import "./A.sol";
+
+/// @notice Inherits from A.
+contract B is A {}
+"#,
+        )
+        .unwrap();
+
+    cmd.arg("flatten").arg(&target).assert_success().stdout_eq(str![[r#"
+// SPDX-License-Identifier: [..]
+pragma solidity ^0.8.4;
+
+...
+contract A {}
+...
+/// @notice Inherits from A.
+contract B is A {}
+
+
+"#]]);
+
+    let output = prj.root().join("Flattened.sol");
+    cmd.forge_fuse()
+        .args(["flatten", "--strip-comments", "--check", "-o"])
+        .arg(&output)
+        .arg(&target)
+        .assert_success()
+        .stdout_eq(str![[r#"
+Flattened src/B.sol compiles to identical bytecode
+Flattened file written at [..]
+
+"#]]);
+    let flattened = std::fs::read_to_string(output).unwrap();
+    assert_eq!(flattened.matches("SPDX-License-Identifier").count(), 1);
+    assert!(!flattened.contains("@notice"));
+});
+
+// checks that `forge generate invariant-handler` scaffolds a handler usable in invariant tests
+forgetest_init!(can_generate_invariant_handler, |prj, cmd| {
+    cmd.args(["generate", "invariant-handler", "--contract", "Counter"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Generated invariant handler: test/handlers/CounterHandler.sol
+
+"#]]);
+
+    let handler = fs::read_to_string(prj.root().join("test/handlers/CounterHandler.sol")).unwrap();
+    assert!(handler.contains("import \"src/Counter.sol\";"));
+    assert!(handler.contains(
+        "    function setNumber(uint256 actorSeed, uint256 newNumber) public useActor(actorSeed) countCall {
+        newNumber = bound(newNumber, 0, type(uint256).max);
+        counter.setNumber(newNumber);"
+    ));
+    assert!(handler.contains("function increment(uint256 actorSeed) public"));
+    assert!(!handler.contains("function number("));
+
+    prj.add_test(
+        "CounterInvariant.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+import {Counter} from "../src/Counter.sol";
+import {CounterHandler} from "./handlers/CounterHandler.sol";
+
+contract CounterInvariantTest is Test {
+    CounterHandler handler;
+
+    function setUp() public {
+        handler = new CounterHandler(new Counter());
+        targetContract(address(handler));
+    }
+
+    function invariant_actors() public view {
+        assertTrue(handler.actors(0) != address(0));
+    }
+}
+"#,
+    )
+    .unwrap();
+    cmd.forge_fuse().args(["test", "--mc", "CounterInvariantTest"]).assert_success();
+});
@@ -46,7 +46,10 @@
         snapshots: "snapshots".into(),
         gas_snapshot_check: false,
         gas_snapshot_emit: true,
+        gas_snapshot_tolerances: vec![],
+        update_snapshots: false,
         broadcast: "broadcast".into(),
+        deployments: "deployments".into(),
         force: true,
         evm_version: EvmVersion::Byzantium,
         gas_reports: vec!["Contract".to_string()],
@@ -76,6 +79,8 @@
         path_pattern_inverse: None,
         coverage_pattern_inverse: None,
         test_failures_file: "test-cache/test-failures".into(),
+        test_timings_file: "test-cache/test-timings.json".into(),
+        quarantine: vec!["CounterTest::test_flaky".to_string()],
         threads: None,
         show_progress: false,
         fuzz: FuzzConfig {
@@ -119,11 +124,13 @@
         eth_rpc_headers: None,
         etherscan_api_key: None,
         etherscan: Default::default(),
+        verifiers: Default::default(),
         verbosity: 4,
         remappings: vec![Remapping::from_str("forge-std/=lib/forge-std/").unwrap().into()],
         libraries: vec![
             "src/DssSpell.sol:DssExecLib:0x8De6DDbCd5053d32292AAA0D2105A32d108484a6".to_string()
         ],
+        chain_libraries: Default::default(),
         ignored_error_codes: vec![],
         ignored_file_paths: vec![],
         deny_warnings: false,
@@ -139,17 +146,22 @@
         bytecode_hash: Default::default(),
         cbor_metadata: true,
         revert_strings: Some(RevertStrings::Strip),
+        language: Default::default(),
         sparse_mode: true,
         allow_paths: vec![],
         include_paths: vec![],
         rpc_endpoints: Default::default(),
         build_info: false,
         build_info_path: None,
+        content_cache: false,
+        content_cache_path: None,
         fmt: Default::default(),
         doc: Default::default(),
         bind_json: Default::default(),
+        workspace: Default::default(),
         fs_permissions: Default::default(),
         labels: Default::default(),
+        wallets: Default::default(),
         isolate: true,
         unchecked_cheatcode_artifacts: false,
         create2_library_salt: Config::DEFAULT_CREATE2_LIBRARY_SALT,
@@ -158,6 +170,7 @@
         skip: vec![],
         dependencies: Default::default(),
         soldeer: Default::default(),
+        remote_cache: None,
         warnings: vec![],
         assertions_revert: true,
         legacy_assertions: false,
@@ -165,6 +178,7 @@
         eof_version: None,
         odyssey: false,
         transaction_timeout: 120,
+        priority_fee_caps: Default::default(),
         additional_compiler_profiles: Default::default(),
         compilation_restrictions: Default::default(),
         eof: false,
@@ -975,7 +989,9 @@
 snapshots = "snapshots"
 gas_snapshot_check = false
 gas_snapshot_emit = true
+update_snapshots = false
 broadcast = "broadcast"
+deployments = "deployments"
 allow_paths = []
 include_paths = []
 skip = []
@@ -998,6 +1014,8 @@
 ignored_warnings_from = []
 deny_warnings = false
 test_failures_file = "cache/test-failures"
+test_timings_file = "test-timings.json"
+quarantine = []
 show_progress = false
 ffi = false
 allow_internal_expect_revert = false
@@ -1025,8 +1043,10 @@
 use_literal_content = false
 bytecode_hash = "ipfs"
 cbor_metadata = true
+language = "solidity"
 sparse_mode = false
 build_info = false
+content_cache = false
 isolate = false
 disable_block_gas_limit = false
 unchecked_cheatcode_artifacts = false
@@ -1079,6 +1099,7 @@
 include_push_bytes = true
 max_fuzz_dictionary_addresses = 15728640
 max_fuzz_dictionary_values = 6553600
+fork_log_blocks = 16
 gas_report_samples = 256
 failure_persist_dir = "cache/fuzz"
 failure_persist_file = "failures"
@@ -1094,6 +1115,7 @@
 include_push_bytes = true
 max_fuzz_dictionary_addresses = 15728640
 max_fuzz_dictionary_values = 6553600
+fork_log_blocks = 16
 shrink_run_limit = 5000
 max_assume_rejects = 65536
 gas_report_samples = 256
@@ -1103,6 +1125,8 @@
 
 [labels]
 
+[wallets]
+
 [vyper]
 
 [bind_json]
@@ -1110,6 +1134,9 @@
 include = []
 exclude = []
 
+[workspace]
+members = []
+
 
 "#]]);
 
@@ -1132,7 +1159,9 @@
   "snapshots": "snapshots",
   "gas_snapshot_check": false,
   "gas_snapshot_emit": true,
+  "update_snapshots": false,
   "broadcast": "broadcast",
+  "deployments": "deployments",
   "allow_paths": [],
   "include_paths": [],
   "skip": [],
@@ -1172,6 +1201,8 @@
   "no_match_path": null,
   "no_match_coverage": null,
   "test_failures_file": "cache/test-failures",
+  "test_timings_file": "test-timings.json",
+  "quarantine": [],
   "threads": null,
   "show_progress": false,
   "fuzz": {
@@ -1183,6 +1214,7 @@
     "include_push_bytes": true,
     "max_fuzz_dictionary_addresses": 15728640,
     "max_fuzz_dictionary_values": 6553600,
+    "fork_log_blocks": 16,
     "gas_report_samples": 256,
     "failure_persist_dir": "cache/fuzz",
     "failure_persist_file": "failures",
@@ -1199,6 +1231,7 @@
     "include_push_bytes": true,
     "max_fuzz_dictionary_addresses": 15728640,
     "max_fuzz_dictionary_values": 6553600,
+    "fork_log_blocks": 16,
     "shrink_run_limit": 5000,
     "max_assume_rejects": 65536,
     "gas_report_samples": 256,
@@ -1243,9 +1276,12 @@
   "bytecode_hash": "ipfs",
   "cbor_metadata": true,
   "revert_strings": null,
+  "language": "solidity",
   "sparse_mode": false,
   "build_info": false,
   "build_info_path": null,
+  "content_cache": false,
+  "content_cache_path": null,
   "fmt": {
     "line_length": 120,
     "tab_width": 4,
@@ -1274,6 +1310,9 @@
     "include": [],
     "exclude": []
   },
+  "workspace": {
+    "members": []
+  },
   "fs_permissions": [
     {
       "access": "read",
@@ -1283,12 +1322,14 @@
   "isolate": false,
   "disable_block_gas_limit": false,
   "labels": {},
+  "wallets": {},
   "unchecked_cheatcode_artifacts": false,
   "create2_library_salt": "0x0000000000000000000000000000000000000000000000000000000000000000",
   "create2_deployer": "0x4e59b44847b379578588920ca78fbf26c0b4956c",
   "vyper": {},
   "dependencies": null,
   "soldeer": null,
+  "remote_cache": null,
   "assertions_revert": true,
   "legacy_assertions": false,
   "odyssey": false,
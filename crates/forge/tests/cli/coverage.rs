@@ -182,6 +182,178 @@ fn basic_base(prj: TestProject, mut cmd: TestCommand) {
     basic_base(prj, cmd);
 });
 
+forgetest_init!(cobertura_and_html, |prj, cmd| {
+    cmd.args(["coverage", "--report=cobertura", "--report=html"]).assert_success().stdout_eq(str![
+        [r#"
+...
+Wrote Cobertura report.
+Wrote HTML report to [..].
+
+"#]
+    ]);
+
+    assert_data_eq!(
+        Data::read_from(&prj.root().join("cobertura.xml"), None),
+        str![[r#"
+<?xml version="1.0" ?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage line-rate="0.4444" branch-rate="1" lines-covered="4" lines-valid="9" branches-covered="0" branches-valid="0" complexity="0" version="[..]" timestamp="[..]">
+  <sources>
+    <source>[..]</source>
+  </sources>
+  <packages>
+    <package name="script" line-rate="0.0000" branch-rate="1" complexity="0">
+      <classes>
+        <class name="script/Counter.s.sol" filename="script/Counter.s.sol" line-rate="0.0000" branch-rate="1" complexity="0">
+          <methods>
+            <method name="CounterScript.setUp" signature="" line-rate="0" branch-rate="1" complexity="0">
+              <lines><line number="10" hits="0"/></lines>
+            </method>
+            <method name="CounterScript.run" signature="" line-rate="0" branch-rate="1" complexity="0">
+              <lines><line number="12" hits="0"/></lines>
+            </method>
+          </methods>
+          <lines>
+            <line number="10" hits="0" branch="false"/>
+            <line number="12" hits="0" branch="false"/>
+            <line number="13" hits="0" branch="false"/>
+            <line number="15" hits="0" branch="false"/>
+            <line number="17" hits="0" branch="false"/>
+          </lines>
+        </class>
+      </classes>
+    </package>
+    <package name="src" line-rate="1.0000" branch-rate="1" complexity="0">
+      <classes>
+        <class name="src/Counter.sol" filename="src/Counter.sol" line-rate="1.0000" branch-rate="1" complexity="0">
+          <methods>
+            <method name="Counter.setNumber" signature="" line-rate="1" branch-rate="1" complexity="0">
+              <lines><line number="7" hits="258"/></lines>
+            </method>
+            <method name="Counter.increment" signature="" line-rate="1" branch-rate="1" complexity="0">
+              <lines><line number="11" hits="1"/></lines>
+            </method>
+          </methods>
+          <lines>
+            <line number="7" hits="258" branch="false"/>
+            <line number="8" hits="258" branch="false"/>
+            <line number="11" hits="1" branch="false"/>
+            <line number="12" hits="1" branch="false"/>
+          </lines>
+        </class>
+      </classes>
+    </package>
+  </packages>
+</coverage>
+
+"#]]
+    );
+
+    let html = prj.root().join("coverage-report");
+    let index = fs::read_to_string(html.join("index.html")).unwrap();
+    assert!(index.contains(r#"<a href="src/Counter.sol.html">src/Counter.sol</a>"#));
+    let page = fs::read_to_string(html.join("src/Counter.sol.html")).unwrap();
+    assert!(page.contains(r#"<a href="../index.html">Back to summary</a>"#));
+    assert!(page.contains(
+        r#"<span class="line covered"><span class="no">12</span><span class="hits">1</span>        number++;</span>"#
+    ));
+});
+
+forgetest!(diff, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo() public {
+        i = 1;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+
+contract AContractTest is DSTest {
+    function testFoo() public {
+        AContract a = new AContract();
+        a.foo();
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.git_init();
+    cmd.git_add();
+    cmd.git_commit("Initial commit");
+
+    // Add an untested function.
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo() public {
+        i = 1;
+    }
+
+    function bar() public {
+        i = 2;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    // Add an untracked, untested contract.
+    prj.add_source(
+        "BContract.sol",
+        r#"
+contract BContract {
+    int public i;
+
+    function baz() public {
+        i = 3;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.forge_fuse().args(["coverage", "--diff", "HEAD"]).assert_success().stdout_eq(str![[r#"
+...
+Coverage of lines changed since HEAD:
+╭-------------------+-----------------+-----------------╮
+| File              | % Changed Lines | Uncovered Lines |
++=======================================================+
+| src/AContract.sol | 0.00% (0/2)     | 11-12           |
+|-------------------+-----------------+-----------------|
+| src/BContract.sol | 0.00% (0/2)     | 7-8             |
+|-------------------+-----------------+-----------------|
+| Total             | 0.00% (0/4)     |                 |
+╰-------------------+-----------------+-----------------╯
+
+"#]]);
+
+    cmd.forge_fuse()
+        .args(["coverage", "--diff", "HEAD", "--diff-threshold", "50"])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+...
+Error: coverage of changed lines is 0.00%, below the threshold of 50.00%
+
+"#]]);
+});
+
 forgetest!(setup, |prj, cmd| {
     prj.insert_ds_test();
     prj.add_source(
@@ -383,13 +555,13 @@ fn basic_base(prj: TestProject, mut cmd: TestCommand) {
     cmd.arg("coverage").args(["--mt", "testAssertRevertBranch"]).assert_success().stdout_eq(str![
         [r#"
 ...
-╭-------------------+--------------+--------------+---------------+---------------╮
-| File              | % Lines      | % Statements | % Branches    | % Funcs       |
-+=================================================================================+
-| src/AContract.sol | 66.67% (2/3) | 50.00% (1/2) | 100.00% (0/0) | 100.00% (1/1) |
-|-------------------+--------------+--------------+---------------+---------------|
-| Total             | 66.67% (2/3) | 50.00% (1/2) | 100.00% (0/0) | 100.00% (1/1) |
-╰-------------------+--------------+--------------+---------------+---------------╯
+╭-------------------+--------------+--------------+---------------+--------------+---------------╮
+| File              | % Lines      | % Statements | % Branches    | % Conditions | % Funcs       |
++================================================================================================+
+| src/AContract.sol | 66.67% (2/3) | 50.00% (1/2) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) |
+|-------------------+--------------+--------------+---------------+--------------+---------------|
+| Total             | 66.67% (2/3) | 50.00% (1/2) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) |
+╰-------------------+--------------+--------------+---------------+--------------+---------------╯
 
 "#]
     ]);
@@ -398,13 +570,13 @@ fn basic_base(prj: TestProject, mut cmd: TestCommand) {
     cmd.forge_fuse().arg("coverage").args(["--mt", "testAssertBranch"]).assert_success().stdout_eq(
         str![[r#"
 ...
-╭-------------------+---------------+---------------+---------------+---------------╮
-| File              | % Lines       | % Statements  | % Branches    | % Funcs       |
-+===================================================================================+
-| src/AContract.sol | 100.00% (3/3) | 100.00% (2/2) | 100.00% (0/0) | 100.00% (1/1) |
-|-------------------+---------------+---------------+---------------+---------------|
-| Total             | 100.00% (3/3) | 100.00% (2/2) | 100.00% (0/0) | 100.00% (1/1) |
-╰-------------------+---------------+---------------+---------------+---------------╯
+╭-------------------+---------------+---------------+---------------+--------------+---------------╮
+| File              | % Lines       | % Statements  | % Branches    | % Conditions | % Funcs       |
++==================================================================================================+
+| src/AContract.sol | 100.00% (3/3) | 100.00% (2/2) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) |
+|-------------------+---------------+---------------+---------------+--------------+---------------|
+| Total             | 100.00% (3/3) | 100.00% (2/2) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) |
+╰-------------------+---------------+---------------+---------------+--------------+---------------╯
 
 "#]],
     );
@@ -454,13 +626,13 @@ fn basic_base(prj: TestProject, mut cmd: TestCommand) {
     // Assert 50% branch coverage if only revert tested.
     cmd.arg("coverage").args(["--mt", "testRequireRevert"]).assert_success().stdout_eq(str![[r#"
 ...
-╭-------------------+---------------+---------------+--------------+---------------╮
-| File              | % Lines       | % Statements  | % Branches   | % Funcs       |
-+==================================================================================+
-| src/AContract.sol | 100.00% (2/2) | 100.00% (1/1) | 50.00% (1/2) | 100.00% (1/1) |
-|-------------------+---------------+---------------+--------------+---------------|
-| Total             | 100.00% (2/2) | 100.00% (1/1) | 50.00% (1/2) | 100.00% (1/1) |
-╰-------------------+---------------+---------------+--------------+---------------╯
+╭-------------------+---------------+---------------+---------------+--------------+---------------╮
+| File              | % Lines       | % Statements  | % Branches    | % Conditions | % Funcs       |
++==================================================================================================+
+| src/AContract.sol | 100.00% (2/2) | 100.00% (1/1) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) |
+|-------------------+---------------+---------------+---------------+--------------+---------------|
+| Total             | 100.00% (2/2) | 100.00% (1/1) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) |
+╰-------------------+---------------+---------------+---------------+--------------+---------------╯
 
 "#]]);
 
@@ -471,26 +643,26 @@ fn basic_base(prj: TestProject, mut cmd: TestCommand) {
         .assert_success()
         .stdout_eq(str![[r#"
 ...
-╭-------------------+---------------+---------------+--------------+---------------╮
-| File              | % Lines       | % Statements  | % Branches   | % Funcs       |
-+==================================================================================+
-| src/AContract.sol | 100.00% (2/2) | 100.00% (1/1) | 50.00% (1/2) | 100.00% (1/1) |
-|-------------------+---------------+---------------+--------------+---------------|
-| Total             | 100.00% (2/2) | 100.00% (1/1) | 50.00% (1/2) | 100.00% (1/1) |
-╰-------------------+---------------+---------------+--------------+---------------╯
+╭-------------------+---------------+---------------+---------------+--------------+---------------╮
+| File              | % Lines       | % Statements  | % Branches    | % Conditions | % Funcs       |
++==================================================================================================+
+| src/AContract.sol | 100.00% (2/2) | 100.00% (1/1) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) |
+|-------------------+---------------+---------------+---------------+--------------+---------------|
+| Total             | 100.00% (2/2) | 100.00% (1/1) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) |
+╰-------------------+---------------+---------------+---------------+--------------+---------------╯
 
 "#]]);
 
     // Assert 100% branch coverage.
     cmd.forge_fuse().arg("coverage").assert_success().stdout_eq(str![[r#"
 ...
-╭-------------------+---------------+---------------+---------------+---------------╮
-| File              | % Lines       | % Statements  | % Branches    | % Funcs       |
-+===================================================================================+
-| src/AContract.sol | 100.00% (2/2) | 100.00% (1/1) | 100.00% (2/2) | 100.00% (1/1) |
-|-------------------+---------------+---------------+---------------+---------------|
-| Total             | 100.00% (2/2) | 100.00% (1/1) | 100.00% (2/2) | 100.00% (1/1) |
-╰-------------------+---------------+---------------+---------------+---------------╯
+╭-------------------+---------------+---------------+---------------+---------------+---------------╮
+| File              | % Lines       | % Statements  | % Branches    | % Conditions  | % Funcs       |
++===================================================================================================+
+| src/AContract.sol | 100.00% (2/2) | 100.00% (1/1) | 100.00% (0/0) | 100.00% (2/2) | 100.00% (1/1) |
+|-------------------+---------------+---------------+---------------+---------------+---------------|
+| Total             | 100.00% (2/2) | 100.00% (1/1) | 100.00% (0/0) | 100.00% (2/2) | 100.00% (1/1) |
+╰-------------------+---------------+---------------+---------------+---------------+---------------╯
 
 "#]]);
 });
@@ -769,13 +941,13 @@ struct Item {
         .assert_success()
         .stdout_eq(str![[r#"
 ...
-╭-------------+----------------+----------------+---------------+---------------╮
-| File        | % Lines        | % Statements   | % Branches    | % Funcs       |
-+===============================================================================+
-| src/Foo.sol | 91.67% (33/36) | 90.00% (27/30) | 80.00% (8/10) | 100.00% (9/9) |
-|-------------+----------------+----------------+---------------+---------------|
-| Total       | 91.67% (33/36) | 90.00% (27/30) | 80.00% (8/10) | 100.00% (9/9) |
-╰-------------+----------------+----------------+---------------+---------------╯
+╭-------------+----------------+----------------+---------------+---------------+---------------╮
+| File        | % Lines        | % Statements   | % Branches    | % Conditions  | % Funcs       |
++===============================================================================================+
+| src/Foo.sol | 91.67% (33/36) | 90.00% (27/30) | 80.00% (8/10) | 100.00% (6/6) | 100.00% (9/9) |
+|-------------+----------------+----------------+---------------+---------------+---------------|
+| Total       | 91.67% (33/36) | 90.00% (27/30) | 80.00% (8/10) | 100.00% (6/6) | 100.00% (9/9) |
+╰-------------+----------------+----------------+---------------+---------------+---------------╯
 
 "#]]);
 
@@ -786,26 +958,26 @@ struct Item {
         .assert_success()
         .stdout_eq(str![[r#"
 ...
-╭-------------+----------------+----------------+---------------+---------------╮
-| File        | % Lines        | % Statements   | % Branches    | % Funcs       |
-+===============================================================================+
-| src/Foo.sol | 97.22% (35/36) | 96.67% (29/30) | 90.00% (9/10) | 100.00% (9/9) |
-|-------------+----------------+----------------+---------------+---------------|
-| Total       | 97.22% (35/36) | 96.67% (29/30) | 90.00% (9/10) | 100.00% (9/9) |
-╰-------------+----------------+----------------+---------------+---------------╯
+╭-------------+----------------+----------------+---------------+---------------+---------------╮
+| File        | % Lines        | % Statements   | % Branches    | % Conditions  | % Funcs       |
++===============================================================================================+
+| src/Foo.sol | 97.22% (35/36) | 96.67% (29/30) | 90.00% (9/10) | 100.00% (6/6) | 100.00% (9/9) |
+|-------------+----------------+----------------+---------------+---------------+---------------|
+| Total       | 97.22% (35/36) | 96.67% (29/30) | 90.00% (9/10) | 100.00% (6/6) | 100.00% (9/9) |
+╰-------------+----------------+----------------+---------------+---------------+---------------╯
 
 "#]]);
 
     // Assert 100% coverage.
     cmd.forge_fuse().arg("coverage").assert_success().stdout_eq(str![[r#"
 ...
-╭-------------+-----------------+-----------------+-----------------+---------------╮
-| File        | % Lines         | % Statements    | % Branches      | % Funcs       |
-+===================================================================================+
-| src/Foo.sol | 100.00% (36/36) | 100.00% (30/30) | 100.00% (10/10) | 100.00% (9/9) |
-|-------------+-----------------+-----------------+-----------------+---------------|
-| Total       | 100.00% (36/36) | 100.00% (30/30) | 100.00% (10/10) | 100.00% (9/9) |
-╰-------------+-----------------+-----------------+-----------------+---------------╯
+╭-------------+-----------------+-----------------+-----------------+---------------+---------------╮
+| File        | % Lines         | % Statements    | % Branches      | % Conditions  | % Funcs       |
++===================================================================================================+
+| src/Foo.sol | 100.00% (36/36) | 100.00% (30/30) | 100.00% (10/10) | 100.00% (6/6) | 100.00% (9/9) |
+|-------------+-----------------+-----------------+-----------------+---------------+---------------|
+| Total       | 100.00% (36/36) | 100.00% (30/30) | 100.00% (10/10) | 100.00% (6/6) | 100.00% (9/9) |
+╰-------------+-----------------+-----------------+-----------------+---------------+---------------╯
 
 "#]]);
 });
@@ -981,26 +1153,26 @@ struct Custom {
     // Assert coverage not 100% for happy paths only.
     cmd.arg("coverage").args(["--mt", "happy"]).assert_success().stdout_eq(str![[r#"
 ...
-╭-------------+----------------+----------------+--------------+---------------╮
-| File        | % Lines        | % Statements   | % Branches   | % Funcs       |
-+==============================================================================+
-| src/Foo.sol | 77.27% (17/22) | 78.57% (11/14) | 66.67% (6/9) | 100.00% (6/6) |
-|-------------+----------------+----------------+--------------+---------------|
-| Total       | 77.27% (17/22) | 78.57% (11/14) | 66.67% (6/9) | 100.00% (6/6) |
-╰-------------+----------------+----------------+--------------+---------------╯
+╭-------------+----------------+----------------+--------------+--------------+---------------╮
+| File        | % Lines        | % Statements   | % Branches   | % Conditions | % Funcs       |
++=============================================================================================+
+| src/Foo.sol | 77.27% (17/22) | 78.57% (11/14) | 60.00% (3/5) | 83.33% (5/6) | 100.00% (6/6) |
+|-------------+----------------+----------------+--------------+--------------+---------------|
+| Total       | 77.27% (17/22) | 78.57% (11/14) | 60.00% (3/5) | 83.33% (5/6) | 100.00% (6/6) |
+╰-------------+----------------+----------------+--------------+--------------+---------------╯
 
 "#]]);
 
     // Assert 100% branch coverage (including clauses without body).
     cmd.forge_fuse().arg("coverage").assert_success().stdout_eq(str![[r#"
 ...
-╭-------------+-----------------+-----------------+---------------+---------------╮
-| File        | % Lines         | % Statements    | % Branches    | % Funcs       |
-+=================================================================================+
-| src/Foo.sol | 100.00% (22/22) | 100.00% (14/14) | 100.00% (9/9) | 100.00% (6/6) |
-|-------------+-----------------+-----------------+---------------+---------------|
-| Total       | 100.00% (22/22) | 100.00% (14/14) | 100.00% (9/9) | 100.00% (6/6) |
-╰-------------+-----------------+-----------------+---------------+---------------╯
+╭-------------+-----------------+-----------------+---------------+---------------+---------------╮
+| File        | % Lines         | % Statements    | % Branches    | % Conditions  | % Funcs       |
++=================================================================================================+
+| src/Foo.sol | 100.00% (22/22) | 100.00% (14/14) | 100.00% (5/5) | 100.00% (6/6) | 100.00% (6/6) |
+|-------------+-----------------+-----------------+---------------+---------------+---------------|
+| Total       | 100.00% (22/22) | 100.00% (14/14) | 100.00% (5/5) | 100.00% (6/6) | 100.00% (6/6) |
+╰-------------+-----------------+-----------------+---------------+---------------+---------------╯
 
 "#]]);
 });
@@ -1753,6 +1925,63 @@ struct Custom {
     assert!(files.is_empty());
 });
 
+// Assert that `viaIR` projects are covered with minimum optimization, and that modifiers and
+// `require` conditions are reported separately.
+forgetest!(via_ir_modifier_and_condition, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.update_config(|config| config.via_ir = true);
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    uint256 public value;
+
+    modifier onlyIf(bool ok) {
+        require(ok, "not allowed");
+        _;
+    }
+
+    function set(bool ok, uint256 x) public onlyIf(ok) {
+        value = x;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+
+contract AContractTest is DSTest {
+    function testSet() public {
+        AContract a = new AContract();
+        a.set(true, 1);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    // Assert only the passing path of the condition is covered.
+    cmd.arg("coverage").assert_success().stdout_eq(str![[r#"
+...
+╭-------------------+---------------+---------------+---------------+--------------+---------------+---------------╮
+| File              | % Lines       | % Statements  | % Branches    | % Conditions | % Funcs       | % Modifiers   |
++==================================================================================================================+
+| src/AContract.sol | 100.00% (4/4) | 100.00% (2/2) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) | 100.00% (1/1) |
+|-------------------+---------------+---------------+---------------+--------------+---------------+---------------|
+| Total             | 100.00% (4/4) | 100.00% (2/2) | 100.00% (0/0) | 50.00% (1/2) | 100.00% (1/1) | 100.00% (1/1) |
+╰-------------------+---------------+---------------+---------------+--------------+---------------+---------------╯
+
+"#]]).stderr_eq(str![[r#"
+Warning: `viaIR` is enabled, coverage is collected with minimum optimization, which can result in inaccurate source mappings.
+...
+"#]]);
+});
+
 #[track_caller]
 fn assert_lcov(cmd: &mut TestCommand, data: impl IntoData) {
     cmd.args(["--report=lcov", "--report-file"]).assert_file(data.into_data());
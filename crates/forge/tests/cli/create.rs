@@ -502,3 +502,43 @@ struct Point {
 
 "#]]);
 });
+
+// tests that we can deploy with CREATE2 and that a second deployment with the same salt is refused
+forgetest_async!(can_create2_with_salt, |prj, cmd| {
+    foundry_test_utils::util::initialize(prj.root());
+
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+
+    // explicitly byte code hash for consistent checks
+    prj.update_config(|c| c.bytecode_hash = BytecodeHash::None);
+
+    let args = [
+        "create",
+        format!("./src/{TEMPLATE_CONTRACT}.sol:{TEMPLATE_CONTRACT}").as_str(),
+        "--rpc-url",
+        rpc.as_str(),
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--create2",
+        "--salt",
+        "0x0000000000000000000000000000000000000000000000000000000000000001",
+        "--broadcast",
+    ]
+    .map(String::from);
+
+    cmd.forge_fuse().args(&args).assert_success().stdout_eq(str![[r#"
+...
+Predicted address: [..]
+Salt: 0x0000000000000000000000000000000000000000000000000000000000000001
+Deployer: 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+Deployed to: [..]
+[TX_HASH]
+
+"#]]);
+
+    cmd.forge_fuse().args(&args).assert_failure().stderr_eq(str![[r#"
+Error: a contract is already deployed at [..], the address of the deployment with salt 0x0000000000000000000000000000000000000000000000000000000000000001
+
+"#]]);
+});
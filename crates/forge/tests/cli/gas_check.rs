@@ -0,0 +1,56 @@
+//! Contains tests for `forge gas-check`.
+
+use serde_json::Value;
+use std::fs;
+
+forgetest_init!(checks_gas_against_baseline, |prj, cmd| {
+    prj.update_config(|config| config.fuzz.runs = 1);
+
+    cmd.args(["gas-check", "--mt", "test_Increment", "--update"]).assert_success().stdout_eq(str![
+        [r#"
+...
+Wrote gas baseline to gas-baseline.json.
+
+"#]
+    ]);
+
+    let baseline = prj.root().join("gas-baseline.json");
+    let mut report: Value = serde_json::from_str(&fs::read_to_string(&baseline).unwrap()).unwrap();
+    assert_eq!(report[0]["contract"], "src/Counter.sol:Counter");
+
+    cmd.forge_fuse().args(["gas-check", "--mt", "test_Increment"]).assert_success().stdout_eq(
+        str![[r#"
+...
+No gas changes compared to gas-baseline.json.
+
+"#]],
+    );
+
+    // Lower the baseline of `increment()` by 2%.
+    let increment = &mut report[0]["functions"]["increment()"]["mean"];
+    let mean = increment.as_u64().unwrap();
+    *increment = (mean * 98 / 100).into();
+    fs::write(&baseline, serde_json::to_string_pretty(&report).unwrap()).unwrap();
+
+    cmd.forge_fuse()
+        .args(["gas-check", "--mt", "test_Increment", "--max-increase", "5%"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+Gas changes compared to gas-baseline.json:
+╭-------------------------+-------------+----------+---------+---------------[..]
+| Contract                | Function    | Baseline | Current | Change[..]
++==========================================================================[..]
+| src/Counter.sol:Counter | increment() | [..]
+╰-------------------------+-------------+----------+---------+---------------[..]
+
+"#]]);
+
+    cmd.forge_fuse()
+        .args(["gas-check", "--mt", "test_Increment", "--max-increase", "1%"])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: gas increased by more than 1% for 1 of 1 entries
+
+"#]]);
+});
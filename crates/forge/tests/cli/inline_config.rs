@@ -401,3 +401,73 @@ fn read_snapshot<T: for<'de> Deserialize<'de>>(path: &Path) -> T {
 
 "#]]);
 });
+
+forgetest_init!(config_inline_env, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "inline.sol",
+        r#"
+        import {Test} from "forge-std/Test.sol";
+
+        contract FunctionConfig is Test {
+            /// forge-config: default.chain_id = 10
+            /// forge-config: default.block_base_fee_per_gas = 100
+            /// forge-config: default.block_prevrandao = "0x0000000000000000000000000000000000000000000000000000000000000042"
+            function test_env() public {
+                assertEq(block.chainid, 10);
+                assertEq(block.basefee, 100);
+                assertEq(block.prevrandao, 0x42);
+            }
+
+            function test_default_env() public {
+                assertEq(block.chainid, 31337);
+                assertEq(block.basefee, 0);
+            }
+        }
+
+        /// forge-config: default.chain_id = 10
+        contract ContractConfig is Test {
+            function test_env() public {
+                assertEq(block.chainid, 10);
+            }
+
+            /// forge-config: default.chain_id = 137
+            function test_function_env() public {
+                assertEq(block.chainid, 137);
+            }
+        }
+
+        contract SetUpConfig is Test {
+            function setUp() public {
+                vm.chainId(5);
+            }
+
+            /// forge-config: default.fuzz.runs = 2
+            function test_keeps_setup_env(bool) public {
+                assertEq(block.chainid, 5);
+            }
+        }
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "-j1"]).assert_success().stdout_eq(str![[r#"
+...
+Ran 2 tests for test/inline.sol:ContractConfig
+[PASS] test_env() ([GAS])
+[PASS] test_function_env() ([GAS])
+Suite result: ok. 2 passed; 0 failed; 0 skipped; [ELAPSED]
+
+Ran 2 tests for test/inline.sol:FunctionConfig
+[PASS] test_default_env() ([GAS])
+[PASS] test_env() ([GAS])
+Suite result: ok. 2 passed; 0 failed; 0 skipped; [ELAPSED]
+
+Ran 1 test for test/inline.sol:SetUpConfig
+[PASS] test_keeps_setup_env(bool) (runs: 2, [AVG_GAS])
+Suite result: ok. 1 passed; 0 failed; 0 skipped; [ELAPSED]
+
+Ran 3 test suites [ELAPSED]: 5 tests passed, 0 failed, 0 skipped (5 total tests)
+
+"#]]);
+});
@@ -4,6 +4,7 @@
 pub mod constants;
 pub mod utils;
 
+mod audit_deps;
 mod bind_json;
 mod build;
 mod cache;
@@ -18,13 +19,19 @@
 mod eip712;
 mod eof;
 mod failure_assertions;
+mod gas_check;
 mod geiger;
 mod inline_config;
+mod migrate;
 mod multi_script;
+mod mutate;
+mod prove;
 mod script;
 mod soldeer;
+mod storage_diff;
 mod svm;
 mod test_cmd;
+mod validate_upgrade;
 mod verify;
 mod verify_bytecode;
 mod version;
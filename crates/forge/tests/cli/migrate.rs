@@ -0,0 +1,137 @@
+//! Contains tests for `forge migrate`.
+
+use foundry_test_utils::str;
+use std::fs;
+
+forgetest!(can_migrate_hardhat_project, |prj, cmd| {
+    prj.wipe();
+    let root = prj.root();
+    fs::create_dir_all(root.join("contracts")).unwrap();
+    fs::create_dir_all(root.join("test")).unwrap();
+    fs::create_dir_all(root.join("node_modules/@openzeppelin/contracts/token")).unwrap();
+    fs::write(
+        root.join("hardhat.config.js"),
+        r#"module.exports = {
+  solidity: { version: "0.8.20", settings: { optimizer: { enabled: true, runs: 1000 } } },
+  networks: { sepolia: { url: process.env.SEPOLIA_RPC_URL } },
+};
+"#,
+    )
+    .unwrap();
+    fs::write(
+        root.join("package.json"),
+        r#"{ "devDependencies": { "hardhat": "^2.22.0", "@openzeppelin/contracts": "5.0.2" } }"#,
+    )
+    .unwrap();
+    fs::write(root.join("node_modules/@openzeppelin/contracts/token/ERC20.sol"), "").unwrap();
+    fs::write(
+        root.join("contracts/Token.sol"),
+        "import \"hardhat/console.sol\";\ncontract Token {}\n",
+    )
+    .unwrap();
+    fs::write(root.join("test/Token.t.sol"), "import {Token} from \"../contracts/Token.sol\";\n")
+        .unwrap();
+
+    cmd.arg("migrate").arg(root).assert_success();
+
+    assert!(!root.join("contracts").exists());
+    assert_eq!(
+        fs::read_to_string(root.join("src/Token.sol")).unwrap(),
+        "import \"forge-std/console.sol\";\ncontract Token {}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("test/Token.t.sol")).unwrap(),
+        "import {Token} from \"../src/Token.sol\";\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("remappings.txt")).unwrap(),
+        "@openzeppelin/contracts/=node_modules/@openzeppelin/contracts/\n"
+    );
+
+    let config = fs::read_to_string(root.join("foundry.toml")).unwrap();
+    assert!(config.contains("solc = \"0.8.20\""), "{config}");
+    assert!(config.contains("optimizer_runs = 1000"), "{config}");
+    assert!(config.contains("sepolia = \"${SEPOLIA_RPC_URL}\""), "{config}");
+
+    // refuses to overwrite the generated config
+    cmd.assert_failure();
+});
+
+forgetest!(can_migrate_hardhat_tests, |prj, cmd| {
+    prj.wipe();
+    let root = prj.root();
+    fs::create_dir_all(root.join("contracts")).unwrap();
+    fs::create_dir_all(root.join("tests/unit")).unwrap();
+    fs::write(
+        root.join("hardhat.config.ts"),
+        r#"export default { solidity: "0.8.24", paths: { tests: "./tests" } };"#,
+    )
+    .unwrap();
+    fs::write(root.join("contracts/Token.sol"), "contract Token {}\n").unwrap();
+    fs::write(
+        root.join("tests/unit/Token.t.sol"),
+        "import {Token} from \"../../contracts/Token.sol\";\nimport \"../Base.sol\";\n",
+    )
+    .unwrap();
+    fs::write(root.join("tests/Base.sol"), "import \"hardhat/console.sol\";\n").unwrap();
+    fs::write(root.join("tests/token.ts"), "describe(\"Token\", () => {});\n").unwrap();
+
+    cmd.arg("migrate").arg(root).assert_success();
+
+    assert_eq!(
+        fs::read_to_string(root.join("test/unit/Token.t.sol")).unwrap(),
+        "import {Token} from \"../../src/Token.sol\";\nimport \"../Base.sol\";\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("test/Base.sol")).unwrap(),
+        "import \"forge-std/console.sol\";\n"
+    );
+    assert!(!root.join("tests/unit/Token.t.sol").exists());
+    assert!(!root.join("tests/Base.sol").exists());
+    // JavaScript tests are left in place
+    assert!(root.join("tests/token.ts").exists());
+});
+
+forgetest!(can_migrate_truffle_project, |prj, cmd| {
+    prj.wipe();
+    let root = prj.root();
+    fs::create_dir_all(root.join("contracts")).unwrap();
+    fs::create_dir_all(root.join("test")).unwrap();
+    fs::write(
+        root.join("truffle-config.js"),
+        r#"module.exports = {
+  networks: { development: { host: "127.0.0.1", port: 8545, network_id: "*" } },
+  compilers: { solc: { version: "0.8.13", settings: { optimizer: { enabled: true, runs: 200 } } } },
+};
+"#,
+    )
+    .unwrap();
+    fs::write(root.join("contracts/Token.sol"), "contract Token {}\n").unwrap();
+    fs::write(
+        root.join("test/TestToken.sol"),
+        "import \"truffle/Assert.sol\";\nimport \"../contracts/Token.sol\";\n",
+    )
+    .unwrap();
+
+    cmd.arg("migrate").arg(root).assert_success().stderr_eq(str![[r#"
+Warning: test/TestToken.sol uses Truffle assertions, which must be ported to forge-std manually
+...
+"#]]);
+
+    assert!(root.join("src/Token.sol").exists());
+    assert_eq!(
+        fs::read_to_string(root.join("test/TestToken.sol")).unwrap(),
+        "import \"truffle/Assert.sol\";\nimport \"../src/Token.sol\";\n"
+    );
+
+    let config = fs::read_to_string(root.join("foundry.toml")).unwrap();
+    assert!(config.contains("solc = \"0.8.13\""), "{config}");
+    assert!(config.contains("optimizer = true"), "{config}");
+    assert!(config.contains("development = \"http://127.0.0.1:8545\""), "{config}");
+});
+
+forgetest!(fails_migrate_without_project, |prj, cmd| {
+    prj.wipe();
+    fs::create_dir_all(prj.root()).unwrap();
+    cmd.arg("migrate").arg(prj.root()).assert_failure();
+});
@@ -2,6 +2,7 @@
 use anvil::{spawn, NodeConfig};
 
 use foundry_test_utils::{ScriptOutcome, ScriptTester};
+use serde_json::Value;
 
 forgetest_async!(can_deploy_multi_chain_script_without_lib, |prj, cmd| {
     let (api1, handle1) = spawn(NodeConfig::test()).await;
@@ -64,3 +65,38 @@
         .arg("--multi")
         .resume(ScriptOutcome::OkBroadcast);
 });
+
+// Tests that the transactions of each chain are saved separately and broadcast in script order, so
+// that a chain can use the contracts deployed on another one.
+forgetest_async!(can_deploy_multi_chain_script_with_dependencies, |prj, cmd| {
+    let (api1, handle1) = spawn(NodeConfig::test()).await;
+    let (api2, handle2) = spawn(NodeConfig::test().with_chain_id(Some(31338u64))).await;
+    let mut tester = ScriptTester::new_broadcast_without_endpoint(cmd, prj.root());
+
+    tester
+        .load_private_keys(&[0])
+        .await
+        .add_sig("MultiChainBroadcastNoLink", "deployAndRegister(string memory,string memory)")
+        .args(&[&handle1.http_endpoint(), &handle2.http_endpoint()])
+        .broadcast(ScriptOutcome::OkBroadcast);
+
+    assert_eq!(api1.transaction_count(tester.accounts_pub[0], None).await.unwrap().to::<u32>(), 1);
+    assert_eq!(api2.transaction_count(tester.accounts_pub[0], None).await.unwrap().to::<u32>(), 2);
+
+    let read_sequence = |chain: u64| -> Value {
+        let path = prj
+            .root()
+            .join(format!("broadcast/Broadcast.t.sol/{chain}/deployAndRegister-latest.json"));
+        foundry_common::fs::read_json_file(&path).unwrap()
+    };
+    let sequence_a = read_sequence(31337);
+    let sequence_b = read_sequence(31338);
+    assert_eq!(sequence_a["transactions"].as_array().unwrap().len(), 1);
+    assert_eq!(sequence_a["receipts"].as_array().unwrap().len(), 1);
+    assert_eq!(sequence_b["transactions"].as_array().unwrap().len(), 2);
+    assert_eq!(sequence_b["receipts"].as_array().unwrap().len(), 2);
+
+    let deployed = sequence_a["transactions"][0]["contractAddress"].as_str().unwrap();
+    let registered = sequence_b["transactions"][1]["arguments"][0].as_str().unwrap();
+    assert_eq!(deployed.to_lowercase(), registered.to_lowercase());
+});
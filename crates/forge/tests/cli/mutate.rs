@@ -0,0 +1,127 @@
+// Tests for the `forge mutate` command.
+
+forgetest_init!(can_report_surviving_mutants, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_source(
+        "Math.sol",
+        r#"
+contract Math {
+    function max(uint256 a, uint256 b) public pure returns (uint256) {
+        return a > b ? a : b;
+    }
+
+    function double(uint256 a) public pure returns (uint256) {
+        return a * 2;
+    }
+}
+    "#,
+    )
+    .unwrap();
+    prj.add_test(
+        "Math.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+import {Math} from "../src/Math.sol";
+
+contract MathTest is Test {
+    Math math = new Math();
+
+    function test_max() public view {
+        assertEq(math.max(1, 2), 2);
+        assertEq(math.max(3, 2), 3);
+    }
+
+    function test_double() public view {
+        assertEq(math.double(3), 6);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["mutate", "--jobs", "1"]).assert_success().stdout_eq(str![[r#"
+Generated 4 mutants from 1 files
+[SURVIVED] src/Math.sol:6:18 replaced `>` with `>=`
+[KILLED] src/Math.sol:10:18 replaced `*` with `/`
+[KILLED] src/Math.sol:10:20 replaced `2` with `1`
+[KILLED] src/Math.sol:10:20 replaced `2` with `3`
+
+Surviving mutants:
+
+src/Math.sol:6:18 replaced `>` with `>=`
+--- a/src/Math.sol
++++ b/src/Math.sol
+@@ -4,5 +4,5 @@
+ contract Math {
+     function max(uint256 a, uint256 b) public pure returns (uint256) {
+-        return a > b ? a : b;
++        return a >= b ? a : b;
+     }
+ 
+[..]
+Mutation score: 75.00% (3 killed, 1 survived, 0 invalid)
+
+"#]]);
+
+    // results are cached until the sources change
+    cmd.forge_fuse().args(["mutate", "--jobs", "1"]).assert_success().stdout_eq(str![[r#"
+Generated 4 mutants from 1 files
+[SURVIVED] src/Math.sol:6:18 replaced `>` with `>=` (cached)
+[KILLED] src/Math.sol:10:18 replaced `*` with `/` (cached)
+[KILLED] src/Math.sol:10:20 replaced `2` with `1` (cached)
+[KILLED] src/Math.sol:10:20 replaced `2` with `3` (cached)
+...
+"#]]);
+});
+
+// tests that mutants never modify the sources of the project or its dependencies
+forgetest_init!(keeps_original_sources, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_source(
+        "Math.sol",
+        r#"
+contract Math {
+    function double(uint256 a) public pure returns (uint256) {
+        return a * 2;
+    }
+}
+    "#,
+    )
+    .unwrap();
+    prj.add_test(
+        "Math.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+import {Math} from "../src/Math.sol";
+
+contract MathTest is Test {
+    function test_double() public {
+        assertEq(new Math().double(3), 6);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    let source = prj.root().join("src/Math.sol");
+    let dependency = prj.root().join("lib/forge-std/src/StdMath.sol");
+    let outside = prj.root().parent().unwrap().join("Outside.sol");
+    std::fs::copy(&source, &outside).unwrap();
+    let read = |path: &std::path::Path| std::fs::read(path).unwrap();
+    let originals = [read(&source), read(&dependency), read(&outside)];
+
+    cmd.args(["mutate", "--jobs", "1"]).assert_success();
+
+    cmd.forge_fuse().arg("mutate").arg(&outside).assert_failure().stderr_eq(str![[r#"
+Error: [..]Outside.sol is outside of the project root [..]
+
+"#]]);
+
+    cmd.forge_fuse().arg("mutate").arg(&dependency).assert_failure().stderr_eq(str![[r#"
+Error: [..]StdMath.sol is in a dependency, only the sources of the project can be mutated
+
+"#]]);
+
+    assert_eq!([read(&source), read(&dependency), read(&outside)], originals);
+    std::fs::remove_file(&outside).unwrap();
+});
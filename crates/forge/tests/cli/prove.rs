@@ -0,0 +1,42 @@
+// Tests for the `forge prove` command.
+
+// Requires the `z3` solver, which is installed in CI.
+forgetest_init!(
+    #[cfg_attr(windows, ignore = "Windows cannot find installed programs")]
+    can_prove_pure_tests,
+    |prj, cmd| {
+        prj.wipe_contracts();
+        prj.add_test(
+            "Prove.t.sol",
+            r#"
+import {Test} from "forge-std/Test.sol";
+
+contract ProveTest is Test {
+    function testFuzzDouble(uint128 x) public pure {
+        assertEq(uint256(x) * 2 / 2, x);
+    }
+
+    function testFuzzNotMagic(uint256 x) public pure {
+        assertTrue(x != 1337);
+    }
+}
+    "#,
+        )
+        .unwrap();
+
+        cmd.args(["prove", "--mt", "testFuzzDouble"]).assert_success().stdout_eq(str![[r#"
+...
+Proving 1 tests in test/Prove.t.sol:ProveTest
+[PASS] testFuzzDouble(uint128) (paths: [..])
+
+"#]]);
+
+        cmd.forge_fuse().args(["prove"]).assert_failure().stdout_eq(str![[r#"
+...
+Proving 2 tests in test/Prove.t.sol:ProveTest
+[PASS] testFuzzDouble(uint128) (paths: [..])
+[FAIL: assertion failed; counterexample: calldata=0x[..] args=[1337]] testFuzzNotMagic(uint256) (paths: [..])
+
+"#]]);
+    }
+);
@@ -1,8 +1,9 @@
 //! Contains various tests related to `forge script`.
 
 use crate::constants::TEMPLATE_CONTRACT;
-use alloy_primitives::{address, hex, Address, Bytes};
+use alloy_primitives::{address, hex, Address, Bytes, U256};
 use anvil::{spawn, NodeConfig};
+use axum::Json;
 use forge_script_sequence::ScriptSequence;
 use foundry_test_utils::{
     rpc::{self, next_http_archive_rpc_url},
@@ -11,8 +12,13 @@
     ScriptOutcome, ScriptTester,
 };
 use regex::Regex;
-use serde_json::Value;
-use std::{env, fs, path::PathBuf, str::FromStr};
+use serde_json::{json, Value};
+use std::{
+    env, fs,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
 // Tests that fork cheat codes can be used in script
 forgetest_init!(
@@ -785,6 +791,35 @@
         .await;
 });
 
+// Tests that resuming doesn't send again transactions that were mined without their receipts
+// being saved.
+forgetest_async!(can_resume_script_with_lost_receipts, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let mut tester = ScriptTester::new_broadcast(cmd, &handle.http_endpoint(), prj.root());
+
+    tester
+        .load_private_keys(&[0, 1])
+        .await
+        .add_sig("BroadcastTest", "deploy()")
+        .broadcast(ScriptOutcome::OkBroadcast);
+
+    let broadcast = foundry_common::fs::json_files(&prj.root().join("broadcast"))
+        .find(|file| file.ends_with("deploy-latest.json"))
+        .expect("No broadcast artifacts");
+    let mut sequence: Value = foundry_common::fs::read_json_file(&broadcast).unwrap();
+    for tx in sequence["transactions"].as_array_mut().unwrap() {
+        assert_eq!(tx["status"], "mined");
+        tx.as_object_mut().unwrap().remove("status");
+    }
+    sequence["receipts"] = Value::Array(vec![]);
+    foundry_common::fs::write_json_file(&broadcast, &sequence).unwrap();
+
+    tester.resume(ScriptOutcome::OkBroadcast).assert_nonce_increment(&[(0, 2), (1, 1)]).await;
+
+    let sequence: Value = foundry_common::fs::read_json_file(&broadcast).unwrap();
+    assert_eq!(sequence["receipts"].as_array().unwrap().len(), 3);
+});
+
 forgetest_async!(can_deploy_broadcast_wrap, |prj, cmd| {
     let (_api, handle) = spawn(NodeConfig::test()).await;
     let mut tester = ScriptTester::new_broadcast(cmd, &handle.http_endpoint(), prj.root());
@@ -2645,3 +2680,528 @@ struct Point {
 
 "#]]);
 });
+
+// Tests that `--safe` saves the transactions as a Safe Transaction Builder batch.
+forgetest_async!(can_save_safe_batch, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+
+    foundry_test_utils::util::initialize(prj.root());
+    prj.add_source(
+        "Foo",
+        r#"
+import "forge-std/Script.sol";
+
+contract Simple {}
+
+contract SafeScript is Script {
+    address constant SAFE = 0x70997970C51812dc3A010C7d01b50e0d17dc79C8;
+
+    function run() public {
+        vm.startBroadcast(SAFE);
+        (bool success,) = address(0xbeef).call{value: 1}(abi.encodeWithSignature("setNumber(uint256)", 1));
+        require(success);
+    }
+
+    function deploy() public {
+        vm.startBroadcast(SAFE);
+        new Simple();
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args([
+        "script",
+        "SafeScript",
+        "--rpc-url",
+        &handle.http_endpoint(),
+        "--safe",
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+    ])
+    .assert_success()
+    .stdout_eq(str![[r#"
+...
+Safe batch of 1 transactions for chain 31337 saved to: [..]
+
+Import the batch in the Safe Transaction Builder app of 0x70997970C51812dc3A010C7d01b50e0d17dc79C8 to propose it, or propose it with `--safe-tx-service`.
+
+"#]]);
+
+    let batch: Value = foundry_common::fs::read_json_file(
+        &prj.root().join("broadcast/Foo.sol/31337/run-safe.json"),
+    )
+    .unwrap();
+    assert_eq!(batch["chainId"], "31337");
+    let tx = &batch["transactions"][0];
+    assert_eq!(tx["to"].as_str().unwrap().to_lowercase(), format!("0x{:0>40}", "beef"));
+    assert_eq!(tx["value"], "1");
+    assert_eq!(
+        tx["data"],
+        "0x3fb5c1cb0000000000000000000000000000000000000000000000000000000000000001"
+    );
+
+    cmd.forge_fuse()
+        .args([
+            "script",
+            "SafeScript",
+            "--sig",
+            "deploy()",
+            "--rpc-url",
+            &handle.http_endpoint(),
+            "--safe",
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+        ])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: transaction #1 on chain 31337 deploys a contract with CREATE, which a Safe can't do; deploy it with CREATE2 instead
+
+"#]]);
+});
+
+/// Spawns a mock Safe Transaction Service for a Safe at nonce 3, returning its URL and the
+/// transactions proposed to it.
+async fn spawn_mock_safe_tx_service() -> (String, Arc<Mutex<Vec<Value>>>) {
+    let proposed = Arc::<Mutex<Vec<Value>>>::default();
+    let txs = proposed.clone();
+    let app = axum::Router::new()
+        .route(
+            "/api/v1/safes/:safe/",
+            axum::routing::get(|| async { Json(json!({ "nonce": 3, "threshold": 2 })) }),
+        )
+        .route(
+            "/api/v1/safes/:safe/multisig-transactions/",
+            axum::routing::post(move |Json(tx): Json<Value>| {
+                txs.lock().unwrap().push(tx);
+                async { axum::http::StatusCode::CREATED }
+            }),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (url, proposed)
+}
+
+// Tests that `--safe-tx-service` proposes the transactions as a signed Safe transaction.
+forgetest_async!(can_propose_safe_transaction, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let (service_url, proposed) = spawn_mock_safe_tx_service().await;
+
+    foundry_test_utils::util::initialize(prj.root());
+    prj.add_source(
+        "Foo",
+        r#"
+import "forge-std/Script.sol";
+
+contract SafeScript is Script {
+    address constant SAFE = 0x70997970C51812dc3A010C7d01b50e0d17dc79C8;
+
+    function run() public {
+        vm.startBroadcast(SAFE);
+        (bool success,) = address(0xbeef).call{value: 1}(abi.encodeWithSignature("setNumber(uint256)", 1));
+        require(success);
+        (success,) = address(0xcafe).call(abi.encodeWithSignature("setNumber(uint256)", 2));
+        require(success);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args([
+        "script",
+        "SafeScript",
+        "--rpc-url",
+        &handle.http_endpoint(),
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--safe",
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+        "--safe-tx-service",
+        &service_url,
+    ])
+    .assert_success()
+    .stdout_eq(str![[r#"
+...
+Proposed Safe transaction 0x92b3a570fabbedefce971d79ccac3c2f2db108102c2bdd44bd8f0bc372035859 with 2 transactions for chain 31337 at nonce 3
+
+The other owners of 0x70997970C51812dc3A010C7d01b50e0d17dc79C8 can confirm it in the Safe app.
+
+"#]]);
+
+    let proposed = proposed.lock().unwrap();
+    assert_eq!(proposed.len(), 1);
+    let tx = &proposed[0];
+    // the calls are batched with MultiSendCallOnly
+    assert_eq!(tx["to"], "0x9641d764fc13c8B624c04430C7356C1C7C8102e2");
+    assert_eq!(tx["operation"], 1);
+    assert_eq!(tx["value"], "0");
+    assert!(tx["data"].as_str().unwrap().starts_with("0x8d80ff0a"), "{tx}");
+    assert_eq!(tx["nonce"], 3);
+    assert_eq!(
+        tx["contractTransactionHash"],
+        "0x92b3a570fabbedefce971d79ccac3c2f2db108102c2bdd44bd8f0bc372035859"
+    );
+    assert_eq!(tx["sender"], "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+    assert_eq!(tx["signature"].as_str().unwrap().len(), 2 + 65 * 2);
+});
+
+// Tests that the wallets configured in `foundry.toml` sign the transactions of their addresses.
+forgetest_async!(can_broadcast_with_configured_wallets, |prj, cmd| {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let sender = address!("ec554aeafe75601aaab43bd4621a22284db566c2");
+    api.anvil_set_balance(sender, U256::from(10u64.pow(18))).await.unwrap();
+
+    foundry_test_utils::util::initialize(prj.root());
+    let keystore_dir =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../cast/tests/fixtures/keystore");
+    prj.update_config(|config| {
+        config.wallets.insert(
+            sender,
+            foundry_config::WalletConfig {
+                keystore: Some(
+                    keystore_dir
+                        .join("UTC--2022-12-20T10-30-43.591916000Z--ec554aeafe75601aaab43bd4621a22284db566c2")
+                        .to_string_lossy()
+                        .into_owned(),
+                ),
+                password_file: Some(
+                    keystore_dir.join("password-ec554").to_string_lossy().into_owned(),
+                ),
+                ..Default::default()
+            },
+        );
+    });
+    prj.add_source(
+        "Foo",
+        r#"
+import "forge-std/Script.sol";
+
+contract WalletScript is Script {
+    function run() public {
+        vm.broadcast(0xec554aeafe75601aaab43bd4621a22284db566c2);
+        payable(address(0xbeef)).transfer(1);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["script", "WalletScript", "--rpc-url", &handle.http_endpoint(), "--broadcast"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+ONCHAIN EXECUTION COMPLETE & SUCCESSFUL.
+...
+"#]]);
+
+    assert_eq!(api.transaction_count(sender, None).await.unwrap().to::<u32>(), 1);
+});
+
+// Tests that the results of verifying the deployments of a script are written to their manifests.
+forgetest_async!(writes_verification_status_to_deployments, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+
+    foundry_test_utils::util::initialize(prj.root());
+    prj.add_source(
+        "Foo",
+        r#"
+import "forge-std/Script.sol";
+
+contract Impl {}
+
+contract Proxy {
+    constructor(address implementation) {
+        assembly {
+            sstore(0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc, implementation)
+        }
+    }
+}
+
+contract VerifyScript is Script {
+    function run() public {
+        vm.startBroadcast();
+        Impl impl = new Impl();
+        new Proxy(address(impl));
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    // The verifiers are unreachable, so the verification fails with both of them.
+    cmd.args([
+        "script",
+        "VerifyScript",
+        "--rpc-url",
+        &handle.http_endpoint(),
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--broadcast",
+        "--verify",
+        "--verifier",
+        "sourcify",
+        "--verifier-url",
+        "http://127.0.0.1:1",
+        "--verifier-fallback",
+        "blockscout:http://127.0.0.1:1",
+        "--retries",
+        "1",
+        "--delay",
+        "0",
+    ])
+    .assert_failure()
+    .stderr_eq(str![[r#"
+...
+Error: Not all (0 / 2) contracts were verified!
+...
+"#]]);
+
+    let implementation: Value =
+        foundry_common::fs::read_json_file(&prj.root().join("deployments/31337/Impl.json"))
+            .unwrap();
+    assert_eq!(implementation["verification"]["status"], "failed");
+    assert_eq!(implementation["verification"]["verifier"], "blockscout");
+    assert!(implementation.get("implementation").is_none());
+
+    let proxy: Value =
+        foundry_common::fs::read_json_file(&prj.root().join("deployments/31337/Proxy.json"))
+            .unwrap();
+    assert_eq!(proxy["verification"]["status"], "failed");
+    assert_eq!(proxy["implementation"], implementation["address"]);
+});
+
+// Tests that the broadcast is aborted before the fees could exceed `--max-total-fee`.
+forgetest_async!(aborts_broadcast_over_max_total_fee, |prj, cmd| {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+
+    foundry_test_utils::util::initialize(prj.root());
+    prj.add_source(
+        "Foo",
+        r#"
+import "forge-std/Script.sol";
+
+contract Simple {}
+
+contract FeeScript is Script {
+    function run() public {
+        vm.startBroadcast();
+        new Simple();
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args([
+        "script",
+        "FeeScript",
+        "--rpc-url",
+        &handle.http_endpoint(),
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--broadcast",
+        "--fee-strategy",
+        "fast",
+        "--max-total-fee",
+        "1",
+    ])
+    .assert_failure()
+    .stderr_eq(str![[r#"
+Error: The fees paid on chain 31337 could reach [..] ETH, over the maximum total fee of 0.000000000000000001 ETH.
+
+Raise `--max-total-fee` and add `--resume` to your command to continue broadcasting the transactions.
+
+"#]]);
+
+    let sender = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+    assert_eq!(api.transaction_count(sender, None).await.unwrap().to::<u32>(), 0);
+});
+
+// Tests that the on-chain simulation uses the environment of the block passed with
+// `--simulate-block`.
+forgetest_async!(can_simulate_against_future_block, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+
+    foundry_test_utils::util::initialize(prj.root());
+    prj.add_source(
+        "Foo",
+        r#"
+import "forge-std/Script.sol";
+
+contract Deadline {
+    constructor(uint256 number) {
+        require(block.number >= number, "too early");
+    }
+}
+
+contract DeadlineScript is Script {
+    function run() public {
+        vm.roll(5);
+        vm.startBroadcast();
+        new Deadline(5);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    // The latest block doesn't pass the check.
+    cmd.args([
+        "script",
+        "DeadlineScript",
+        "--rpc-url",
+        &handle.http_endpoint(),
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    ])
+    .assert_failure()
+    .stderr_eq(str![[r#"
+...
+Error: Simulated execution failed.
+...
+"#]]);
+
+    cmd.forge_fuse()
+        .args([
+            "script",
+            "DeadlineScript",
+            "--rpc-url",
+            &handle.http_endpoint(),
+            "--private-key",
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            "--simulate-block",
+            "5",
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+Simulating against block 5 (base fee: [..] gwei, timestamp: [..]) on [..].
+...
+"#]]);
+});
+
+/// Spawns a mock ERC-4337 bundler, returning its URL and the params of the
+/// `eth_sendUserOperation` requests it received.
+async fn spawn_mock_bundler() -> (String, Arc<Mutex<Vec<Value>>>) {
+    let sent = Arc::<Mutex<Vec<Value>>>::default();
+    let ops = sent.clone();
+    let handler = axum::routing::post(move |Json(request): Json<Value>| {
+        let result = match request["method"].as_str().unwrap() {
+            "eth_estimateUserOperationGas" => json!({
+                "preVerificationGas": "0xc350",
+                "verificationGasLimit": "0x30d40",
+                "callGasLimit": "0x186a0",
+                "paymasterVerificationGasLimit": "0x7530",
+                "paymasterPostOpGasLimit": "0x9c40",
+            }),
+            "eth_sendUserOperation" => {
+                ops.lock().unwrap().push(request["params"].clone());
+                json!(format!("0x{}", "ab".repeat(32)))
+            }
+            "eth_getUserOperationReceipt" => json!({
+                "success": true,
+                "receipt": {
+                    "transactionHash": format!("0x{}", "11".repeat(32)),
+                    "transactionIndex": "0x0",
+                    "blockHash": format!("0x{}", "22".repeat(32)),
+                    "blockNumber": "0x1",
+                    "from": "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+                    "to": "0x0000000000000000000000000000000000004337",
+                    "cumulativeGasUsed": "0x30d40",
+                    "gasUsed": "0x30d40",
+                    "effectiveGasPrice": "0x3b9aca00",
+                    "contractAddress": null,
+                    "logs": [],
+                    "logsBloom": format!("0x{}", "00".repeat(256)),
+                    "status": "0x1",
+                    "type": "0x2",
+                },
+            }),
+            method => panic!("unexpected bundler request {method}"),
+        };
+        async move { Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })) }
+    });
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, handler.into_make_service()).await.unwrap() });
+    (url, sent)
+}
+
+// Tests that `--bundler-url` broadcasts the transactions as UserOperations signed by the owner of
+// the smart account.
+forgetest_async!(can_broadcast_user_operations, |prj, cmd| {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let account = address!("00000000000000000000000000000000000acc01");
+    api.anvil_set_balance(account, U256::from(10u64.pow(18))).await.unwrap();
+    let (bundler_url, sent) = spawn_mock_bundler().await;
+
+    foundry_test_utils::util::initialize(prj.root());
+    prj.add_source(
+        "Foo",
+        r#"
+import "forge-std/Script.sol";
+
+contract UserOperationScript is Script {
+    function run() public {
+        vm.startBroadcast(address(0xacc01));
+        (bool success,) = address(0xbeef).call(abi.encodeWithSignature("setNumber(uint256)", 1));
+        require(success);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args([
+        "script",
+        "UserOperationScript",
+        "--rpc-url",
+        &handle.http_endpoint(),
+        "--broadcast",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--bundler-url",
+        &bundler_url,
+        "--entry-point",
+        "0x0000000000000000000000000000000000004337",
+        "--paymaster",
+        "0x0000000000000000000000000000000000009a9a",
+        "--paymaster-data",
+        "0xcafe",
+    ])
+    .assert_success()
+    .stdout_eq(str![[r#"
+...
+UserOperation 0xabababababababababababababababababababababababababababababababab sent from 0x00000000000000000000000000000000000aCC01
+...
+ONCHAIN EXECUTION COMPLETE & SUCCESSFUL.
+...
+"#]]);
+
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    let (op, entry_point) = (&sent[0][0], &sent[0][1]);
+    assert_eq!(entry_point, "0x0000000000000000000000000000000000004337");
+    assert_eq!(op["sender"].as_str().unwrap().to_lowercase(), account.to_string().to_lowercase());
+    // `execute(0xbeef, 0, setNumber(1))` on the smart account
+    let call_data = op["callData"].as_str().unwrap();
+    assert!(call_data.starts_with("0xb61d27f6"), "{call_data}");
+    assert!(call_data.contains("3fb5c1cb"), "{call_data}");
+    assert_eq!(op["paymaster"].as_str().unwrap().to_lowercase(), format!("0x{:0>40}", "9a9a"));
+    assert_eq!(op["paymasterData"], "0xcafe");
+    assert_eq!(op["paymasterVerificationGasLimit"], "0x7530");
+    assert_eq!(op["paymasterPostOpGasLimit"], "0x9c40");
+    // signed by the owner instead of the dummy signature used for the gas estimation
+    let signature = op["signature"].as_str().unwrap();
+    assert_eq!(signature.len(), 2 + 65 * 2);
+    assert!(!signature.starts_with("0xfffffff"), "{signature}");
+
+    let sequence: Value = foundry_common::fs::read_json_file(
+        &prj.root().join("broadcast/Foo.sol/31337/run-latest.json"),
+    )
+    .unwrap();
+    assert_eq!(sequence["transactions"][0]["hash"], format!("0x{}", "11".repeat(32)));
+});
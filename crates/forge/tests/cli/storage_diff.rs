@@ -0,0 +1,91 @@
+//! Contains tests for `forge storage-diff`.
+
+forgetest!(compares_storage_layouts, |prj, cmd| {
+    prj.add_source(
+        "Vault.sol",
+        r#"
+contract VaultV1 {
+    address owner;
+    uint256 total;
+    uint256[50] __gap;
+}
+
+contract VaultV2 {
+    address owner;
+    uint256 total;
+    mapping(address => uint256) balances;
+    uint256[49] __gap;
+}
+
+contract VaultV3 {
+    address owner;
+    uint128 total;
+    uint256[50] __gap;
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["storage-diff", "VaultV1", "VaultV2"]).assert_success().stdout_eq(str![[r#"
+...
+No storage layout changes between VaultV1 and VaultV2.
+
+"#]]);
+
+    cmd.forge_fuse().args(["storage-diff", "VaultV1", "VaultV3"]).assert_failure().stdout_eq(str![
+        [r#"
+...
+Storage layout changes between VaultV1 and VaultV3:
+╭--------------+----------+---------+---------╮
+| Change       | Variable | Old     | New     |
++=============================================+
+| type changed | total    | uint256 | uint128 |
+╰--------------+----------+---------+---------╯
+
+"#]
+    ]);
+});
+
+forgetest!(checks_erc7201_namespaces, |prj, cmd| {
+    prj.add_source(
+        "Token.sol",
+        r#"
+contract TokenV1 {
+    /// @custom:storage-location erc7201:example.token
+    struct TokenStorage {
+        uint256 supply;
+        address minter;
+    }
+}
+
+contract TokenV2 {
+    /// @custom:storage-location erc7201:example.token
+    struct TokenStorage {
+        address minter;
+        uint256 supply;
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["storage-diff", "TokenV1", "TokenV2", "--json"]).assert_failure().stdout_eq(str![[
+        r#"
+[
+  {
+    "kind": "moved",
+    "variable": "erc7201:example.token.supply",
+    "old": "member 0",
+    "new": "member 1"
+  },
+  {
+    "kind": "moved",
+    "variable": "erc7201:example.token.minter",
+    "old": "member 1",
+    "new": "member 0"
+  }
+]
+
+"#
+    ]]);
+});
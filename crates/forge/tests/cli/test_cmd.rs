@@ -7,7 +7,11 @@
     util::{OutputExt, OTHER_SOLC_VERSION, SOLC_VERSION},
 };
 use similar_asserts::assert_eq;
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    str::FromStr,
+};
 
 // tests that test filters are handled correctly
 forgetest!(can_set_filter_values, |prj, cmd| {
@@ -588,6 +592,51 @@
 "#]]);
 });
 
+forgetest_init!(fork_record_replay, |prj, cmd| {
+    prj.wipe_contracts();
+
+    let endpoint = rpc::next_http_archive_rpc_url();
+
+    prj.add_test(
+        "Contract.t.sol",
+        &r#"
+import {Test} from "forge-std/Test.sol";
+
+interface IERC20 {
+    function totalSupply() external view returns (uint256);
+}
+
+contract ForkSnapshotTest is Test {
+    function test() public {
+        vm.createSelectFork("<url>", 21_000_000);
+        assertEq(block.number, 21_000_000);
+        assertGt(IERC20(0xdAC17F958D2ee523a2206206994597C13D831ec7).totalSupply(), 0);
+    }
+}
+   "#
+        .replace("<url>", &endpoint),
+    )
+    .unwrap();
+
+    cmd.args(["test", "--fork-record"]).assert_success();
+    let snapshots = std::fs::read_dir(prj.root().join("cache/fork")).unwrap().collect::<Vec<_>>();
+    assert_eq!(snapshots.len(), 1);
+
+    cmd.forge_fuse().args(["test", "--fork-replay"]).assert_success().stdout_eq(str![[r#"
+No files changed, compilation skipped
+
+Ran 1 test for test/Contract.t.sol:ForkSnapshotTest
+[PASS] test() ([GAS])
+Suite result: ok. 1 passed; 0 failed; 0 skipped; [ELAPSED]
+
+Ran 1 test suite [ELAPSED]: 1 tests passed, 0 failed, 0 skipped (1 total tests)
+
+"#]]);
+
+    // Replaying without a recorded snapshot fails.
+    cmd.forge_fuse().args(["test", "--fork-replay", "missing"]).assert_failure();
+});
+
 // https://github.com/foundry-rs/foundry/issues/6579
 forgetest_init!(include_custom_types_in_traces, |prj, cmd| {
     prj.wipe_contracts();
@@ -2151,6 +2200,133 @@ fn extract_test_result(out: &str) -> &str {
 "#]]);
 });
 
+forgetest_init!(should_print_github_annotations, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.insert_ds_test();
+    prj.clear();
+
+    prj.add_source(
+        "GithubAnnotations.t.sol",
+        r#"
+        import {DSTest} from "./test.sol";
+
+        contract GithubAnnotationsTest is DSTest {
+            function test_pass() public {}
+
+            function test_revert_fail() public {
+                require(1 > 2, "Revert, with comma");
+            }
+        }
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--format", "github"]).assert_failure().stdout_eq(str![[r#"
+...
+::error file=src/GithubAnnotations.t.sol,line=[..],title=GithubAnnotationsTest%3A%3Atest_revert_fail()::GithubAnnotationsTest::test_revert_fail() failed: revert: Revert, with comma
+
+"#]]);
+});
+
+forgetest_init!(detects_flaky_and_quarantined_tests, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.insert_ds_test();
+    prj.insert_vm();
+    prj.update_config(|config| {
+        config.fs_permissions = foundry_config::FsPermissions::new(vec![
+            foundry_config::fs_permissions::PathPermission::read_write("./"),
+        ]);
+        config.quarantine = vec!["FlakyTest::test_quarantined".to_string()];
+    });
+
+    prj.add_source(
+        "Flaky.t.sol",
+        r#"
+        import {Vm} from "./Vm.sol";
+        import {DSTest} from "./test.sol";
+
+        contract FlakyTest is DSTest {
+            Vm constant vm = Vm(HEVM_ADDRESS);
+
+            function test_flaky() public {
+                if (!vm.exists("flaky-marker")) {
+                    vm.writeFile("flaky-marker", "");
+                    fail();
+                }
+            }
+
+            function test_fail() public {
+                fail();
+            }
+
+            function test_quarantined() public {
+                fail();
+            }
+        }
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--detect-flaky", "2"]).assert_failure().stdout_eq(str![[r#"
+...
+Flaky tests:
+  FlakyTest::test_flaky() (passed 2/2 re-runs)
+
+Consistently failing tests:
+  FlakyTest::test_fail() (failed 2/2 re-runs)
+  FlakyTest::test_quarantined() (failed 2/2 re-runs)
+
+Quarantined failing tests (ignored):
+  FlakyTest::test_quarantined()
+
+...
+Encountered 2 failing tests in src/Flaky.t.sol:FlakyTest
+...
+
+"#]]);
+
+    // Failures of quarantined tests don't fail the run.
+    cmd.forge_fuse().args(["test", "--mt", "test_quarantined"]).assert_success();
+});
+
+// Custom errors of contracts which are not part of the project are looked up by selector.
+forgetest_init!(decodes_unknown_custom_errors, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.insert_ds_test();
+    prj.insert_vm();
+
+    prj.add_source(
+        "UnknownError.t.sol",
+        r#"
+        import {Vm} from "./Vm.sol";
+        import {DSTest} from "./test.sol";
+
+        contract UnknownErrorTest is DSTest {
+            Vm constant vm = Vm(HEVM_ADDRESS);
+
+            function test_unknown_error() public {
+                address target = address(0x1234);
+                // reverts with `Unauthorized()`
+                vm.etch(target, hex"6382b4290060e01b60005260046000fd");
+                (bool success, bytes memory data) = target.call("");
+                if (!success) {
+                    assembly {
+                        revert(add(data, 32), mload(data))
+                    }
+                }
+            }
+        }
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: Unauthorized()] test_unknown_error() ([GAS])
+...
+"#]]);
+});
+
 forgetest_init!(should_generate_junit_xml_report_with_logs, |prj, cmd| {
     prj.wipe_contracts();
     prj.add_source(
@@ -2840,6 +3016,20 @@ struct PotentialRevert {
     // Check if the broadcast folder exists
     assert!(broadcast_path.exists() && broadcast_path.is_dir());
 
+    // The deployment manifest holds the latest deployment.
+    let deployment: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(prj.root().join("deployments/31337/Counter.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(deployment["contractName"], "Counter");
+    assert_eq!(
+        deployment["address"].as_str().unwrap().to_lowercase(),
+        "0x90d4e26f2e78fedf488c7f3c46b8053a0515c71f"
+    );
+    assert_eq!(deployment["chainId"], 31337);
+    assert!(deployment["abi"].is_array());
+    assert!(deployment["bytecodeHash"].is_string());
+
     cmd.forge_fuse().args(["test", "--mc", "GetBroadcastTest", "-vvv"]).assert_success();
 });
 
@@ -3411,3 +3601,534 @@ struct PotentialRevert {
 
 "#]]);
 });
+
+forgetest!(can_find_selector_collisions_across_project, |prj, cmd| {
+    prj.add_source(
+        "Token.sol",
+        r#"
+contract Token {
+    function burn(uint256) external {}
+}
+    "#,
+    )
+    .unwrap();
+
+    prj.add_source(
+        "Proxy.sol",
+        r#"
+contract Proxy {
+    function collate_propagate_storage(bytes16) external {}
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["selectors", "collisions"]).assert_success().stdout_eq(str![[r#"
+1 colliding selectors found:
+...
+| 0x42966c68 | burn(uint256) [..]
+...
+
+"#]]);
+
+    cmd.forge_fuse().args(["selectors", "collisions", "--json"]).assert_success().stdout_eq(
+        str![[r#"
+{
+  "0x42966c68": {
+    "burn(uint256)": [
+      "src/Token.sol:Token"
+    ],
+    "collate_propagate_storage(bytes16)": [
+      "src/Proxy.sol:Proxy"
+    ]
+  }
+}
+"#]]
+        .is_json(),
+    );
+
+    cmd.forge_fuse()
+        .args(["selectors", "collisions", "--fail-on-collision"])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: found 1 colliding function selectors
+
+"#]]);
+});
+
+// tests that Yul objects can be deployed from tests with the generated deployer
+forgetest!(can_deploy_yul_objects, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.update_config(|config| config.language = foundry_config::ProjectLanguage::Yul);
+
+    prj.add_raw_source(
+        "Answer.yul",
+        r#"
+object "Answer" {
+    code {
+        datacopy(0, dataoffset("runtime"), datasize("runtime"))
+        return(0, datasize("runtime"))
+    }
+    object "runtime" {
+        code {
+            mstore(0, 42)
+            return(0, 32)
+        }
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    prj.add_test(
+        "Answer.t.sol",
+        r#"
+import "../src/test.sol";
+import {YulDeployer} from "./utils/YulDeployer.sol";
+
+contract AnswerTest is DSTest {
+    function testAnswer() public {
+        address answer = YulDeployer.deployAnswer();
+        (bool success, bytes memory data) = answer.call("");
+        assertTrue(success);
+        assertEq(abi.decode(data, (uint256)), 42);
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--mt", "testAnswer"]).assert_success().stdout_eq(str![[r#"
+...
+[PASS] testAnswer() ([GAS])
+...
+"#]]);
+    assert!(prj.root().join("test/utils/YulDeployer.sol").exists());
+});
+
+// tests that external libraries are deployed automatically unless pinned for the test chain
+forgetest_init!(links_pinned_chain_libraries, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_source(
+        "Math.sol",
+        r#"
+library Math {
+    function double(uint256 x) external pure returns (uint256) {
+        return x * 2;
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    prj.add_test(
+        "Math.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+import {Math} from "../src/Math.sol";
+
+contract MathTest is Test {
+    function testDouble() public pure {
+        assertEq(Math.double(21), 42);
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_success().stdout_eq(str![[r#"
+...
+[PASS] testDouble() ([GAS])
+...
+"#]]);
+
+    prj.update_config(|config| {
+        config.chain_libraries = [(
+            "31337".to_string(),
+            vec!["src/Math.sol:Math:0x000000000000000000000000000000000000dEaD".to_string()],
+        )]
+        .into();
+    });
+    cmd.assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: library `Math` pinned in `chain_libraries` has no code at 0x000000000000000000000000000000000000dEaD] setUp() ([GAS])
+...
+"#]]);
+});
+
+// tests that `--shard` partitions the tests across shards, and that `--write-timings` records test
+// timings
+forgetest_init!(can_shard_tests, |prj, cmd| {
+    prj.wipe_contracts();
+    for name in ["A", "B", "C", "D"] {
+        prj.add_test(
+            &format!("{name}.t.sol"),
+            &format!(
+                r#"
+import {{Test}} from "forge-std/Test.sol";
+
+contract {name}Test is Test {{
+    function testOne() public pure {{}}
+    function testTwo() public pure {{}}
+}}
+"#
+            ),
+        )
+        .unwrap();
+    }
+
+    let mut list = |shard: &str, by: &str| {
+        let output = cmd
+            .forge_fuse()
+            .args(["test", "--list", "--json", "--shard", shard, "--shard-by", by])
+            .assert_success()
+            .get_output()
+            .stdout_lossy();
+        let files: BTreeMap<String, BTreeMap<String, Vec<String>>> =
+            serde_json::from_str(&output).unwrap();
+        files
+            .into_values()
+            .flat_map(|contracts| contracts.into_iter())
+            .flat_map(|(contract, tests)| {
+                tests.into_iter().map(move |test| (contract.clone(), test))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for by in ["contract", "test"] {
+        let first = list("1/2", by);
+        let second = list("2/2", by);
+        assert_eq!(list("1/2", by), first);
+        assert_eq!(first.len(), 4);
+        assert_eq!(second.len(), 4);
+        let all = first.iter().chain(&second).collect::<BTreeSet<_>>();
+        assert_eq!(all.len(), 8);
+        if by == "contract" {
+            let contracts = |tests: &[(String, String)]| {
+                tests.iter().map(|(contract, _)| contract.clone()).collect::<BTreeSet<_>>()
+            };
+            assert!(contracts(&first).is_disjoint(&contracts(&second)));
+        }
+    }
+
+    let timings_file = prj.root().join("test-timings.json");
+    cmd.forge_fuse().args(["test", "--shard", "2/2"]).assert_success();
+    assert!(!timings_file.exists());
+
+    cmd.forge_fuse().args(["test", "--shard", "2/2", "--write-timings"]).assert_success();
+    let timings: BTreeMap<String, BTreeMap<String, u64>> =
+        serde_json::from_str(&std::fs::read_to_string(&timings_file).unwrap()).unwrap();
+    assert_eq!(timings.len(), 2);
+    assert!(timings.values().all(|tests| tests.len() == 2));
+
+    cmd.forge_fuse().args(["test", "--shard", "3/2"]).assert_failure().stderr_eq(str![[r#"
+error: invalid value '3/2' for '--shard <INDEX/COUNT>': shard index must be between 1 and the shard count, got `3/2`
+
+For more information, try '--help'.
+
+"#]]);
+});
+
+// tests that table tests run once per fixture entry and report each case
+forgetest_init!(can_run_table_tests, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "Table.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract TableTest is Test {
+    uint256[] public fixtureAmount = [1, 2, 3];
+    bool[] public fixtureDouble = [true, false, true];
+
+    function fixtureExpected() public pure returns (uint256[] memory expected) {
+        expected = new uint256[](3);
+        expected[0] = 2;
+        expected[1] = 2;
+        expected[2] = 7;
+    }
+
+    function tableMultiply(uint256 amount, bool double, uint256 expected) public pure {
+        assertEq(double ? amount * 2 : amount, expected);
+    }
+
+    function tableMissingFixture(uint256 amount, uint256 other) public pure {}
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_failure().stdout_eq(str![[r#"
+...
+Ran 4 tests for test/Table.t.sol:TableTest
+[FAIL: no fixture defined for parameter `other` of table test] tableMissingFixture(uint256,uint256) ([GAS])
+[PASS] tableMultiply(uint256,bool,uint256)[0] ([GAS])
+[PASS] tableMultiply(uint256,bool,uint256)[1] ([GAS])
+[FAIL: assertion failed: 6 != 7] tableMultiply(uint256,bool,uint256)[2] ([GAS])
+Suite result: FAILED. 2 passed; 2 failed; 0 skipped; [ELAPSED]
+...
+"#]]);
+});
+
+// tests that `vm.assertMatchesSnapshot` creates, checks and updates golden files
+forgetest!(can_assert_matches_snapshot, |prj, cmd| {
+    let test = |value: &str| {
+        format!(
+            r#"
+interface Vm {{
+    function assertMatchesSnapshot(string calldata name, bytes calldata value) external;
+    function assertMatchesSnapshot(string calldata name, string calldata value) external;
+}}
+
+contract GoldenTest {{
+    Vm constant vm = Vm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    function testGolden() public {{
+        vm.assertMatchesSnapshot("calldata/transfer", abi.encode(uint256({value})));
+        vm.assertMatchesSnapshot("greeting.txt", "hello");
+    }}
+}}
+"#
+        )
+    };
+    prj.add_test("Golden.t.sol", &test("1")).unwrap();
+
+    // golden files are subject to the fs permissions of the project
+    cmd.args(["test"]).assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: the path snapshots/golden/calldata/transfer is not allowed to be accessed for read operations] testGolden() ([GAS])
+...
+"#]]);
+    assert!(!prj.root().join("snapshots/golden").exists());
+
+    prj.update_config(|config| {
+        config.fs_permissions = foundry_config::FsPermissions::new(vec![
+            foundry_config::fs_permissions::PathPermission::read_write("./snapshots/golden"),
+        ]);
+    });
+
+    // golden files are created on the first run, apart from the gas snapshots
+    cmd.assert_success();
+    let golden = prj.root().join("snapshots/golden/calldata/transfer");
+    assert_eq!(
+        std::fs::read_to_string(&golden).unwrap(),
+        "0x0000000000000000000000000000000000000000000000000000000000000001"
+    );
+    assert_eq!(
+        std::fs::read_to_string(prj.root().join("snapshots/golden/greeting.txt")).unwrap(),
+        "hello"
+    );
+    cmd.assert_success();
+
+    prj.add_test("Golden.t.sol", &test("2")).unwrap();
+    cmd.assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: assertion failed: snapshot `calldata/transfer` does not match: 0x0000000000000000000000000000000000000000000000000000000000000002 != 0x0000000000000000000000000000000000000000000000000000000000000001] testGolden() ([GAS])
+...
+"#]]);
+
+    cmd.forge_fuse().args(["test", "--update-snapshots"]).assert_success();
+    assert_eq!(
+        std::fs::read_to_string(&golden).unwrap(),
+        "0x0000000000000000000000000000000000000000000000000000000000000002"
+    );
+    cmd.forge_fuse().args(["test"]).assert_success();
+});
+
+// tests that `fuzz.timestamp_range` and `fuzz.block_number_range` advance the block before fuzzed
+// calls
+forgetest_init!(should_advance_block_in_fuzz_campaigns, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "TimeTravel.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract Vault {
+    uint256 public immutable unlockTime = block.timestamp + 1 days;
+    uint256 public withdrawn;
+
+    function withdraw() public {
+        if (block.timestamp >= unlockTime) withdrawn++;
+    }
+}
+
+contract TimeTravelTest is Test {
+    Vault vault;
+    uint256 start;
+
+    function setUp() public {
+        vault = new Vault();
+        start = block.number;
+        targetContract(address(vault));
+    }
+
+    function testFuzzLocked(uint256) public view {
+        assertLt(block.timestamp, vault.unlockTime());
+    }
+
+    function invariantNeverWithdrawn() public view {
+        assertEq(vault.withdrawn(), 0);
+    }
+
+    function invariantSameBlock() public view {
+        assertEq(block.number, start);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    // without time travel the vault is never unlocked
+    cmd.args(["test"]).assert_success();
+
+    prj.update_config(|config| {
+        config.fuzz.timestamp_range = Some([3600, 2 * 86400]);
+        config.fuzz.block_number_range = Some([1, 10]);
+    });
+    cmd.assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: assertion failed: [..]]
+	[Sequence] (original: [..], shrunk: [..])
+...
+ invariantNeverWithdrawn() (runs: [..], calls: [..], reverts: [..])
+[FAIL: assertion failed: [..]]
+	[Sequence] (original: [..], shrunk: 1)
+		sender=[..] addr=[test/TimeTravel.t.sol:Vault][..] calldata=[..] args=[] warp=[..] roll=[..]
+ invariantSameBlock() (runs: [..], calls: [..], reverts: [..])
+[FAIL: assertion failed: [..]; counterexample: calldata=[..] args=[..] warp=[..] roll=[..]] testFuzzLocked(uint256) (runs: [..], [AVG_GAS])
+...
+"#]]);
+});
+
+// tests that `--fuzz-corpus` records the inputs which increased coverage and reuses them
+forgetest_init!(should_record_fuzz_corpus, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "Corpus.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract CorpusTest is Test {
+    function testFuzzBranches(uint256 a, uint256 b) public pure {
+        if (a % 2 == 0) {
+            if (b % 3 == 0) return;
+            return;
+        }
+        if (b > a) return;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--fuzz-corpus"]).assert_success();
+    let corpus = prj.root().join("cache/fuzz/corpus/CorpusTest/testFuzzBranches");
+    let entries = std::fs::read_dir(&corpus).unwrap().count();
+    assert!(entries > 0);
+
+    // the recorded entries are replayed and only new coverage is recorded
+    cmd.assert_success();
+    assert!(std::fs::read_dir(&corpus).unwrap().count() >= entries);
+
+    // the corpus can be shared through another directory
+    cmd.forge_fuse().args(["test", "--fuzz-corpus", "shared-corpus"]).assert_success();
+    assert!(prj.root().join("shared-corpus/CorpusTest/testFuzzBranches").exists());
+});
+
+// tests that invariant failures are recorded as a Solidity test and can be replayed with `--replay`
+forgetest_init!(should_replay_invariant_failure, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "Replay.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number++;
+    }
+}
+
+contract ReplayTest is Test {
+    Counter counter;
+
+    function setUp() public {
+        counter = new Counter();
+        targetContract(address(counter));
+    }
+
+    function invariant_zero() public view {
+        assertEq(counter.number(), 0);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_failure();
+    let failure = prj.root().join("cache/invariant/failures/ReplayTest/invariant_zero");
+    assert!(failure.exists());
+    let function = std::fs::read_to_string(failure.with_extension("sol")).unwrap();
+    assert!(function.starts_with("function test_replay_invariant_zero() public {"));
+    assert!(function.contains(").increment();"));
+    assert!(function.contains("invariant_zero();"));
+
+    cmd.forge_fuse()
+        .args(["test", "--replay", failure.to_str().unwrap()])
+        .assert_failure()
+        .stdout_eq(str![[r#"
+...
+[FAIL: invariant_zero replay failure]
+	[Sequence] (original: [..], shrunk: [..])
+		sender=[..] addr=[test/Replay.t.sol:Counter][..] calldata=increment() args=[]
+ invariant_zero() (runs: 1, calls: [..], reverts: 1)
+...
+"#]]);
+});
+
+// tests that differential fuzz tests compare their return data to a reference implementation
+forgetest_init!(should_run_differential_fuzz_tests, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "Differential.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract DifferentialTest is Test {
+    /// forge-config: default.fuzz.differential.function = "referenceMax"
+    function testFuzzMax(uint8 a, uint8 b) public pure returns (uint8) {
+        return a > b ? a : a;
+    }
+
+    function referenceMax(uint8 a, uint8 b) public pure returns (uint8) {
+        return a > b ? a : b;
+    }
+
+    /// forge-config: default.fuzz.differential.ffi = ["sh", "-c", "echo $0"]
+    function testFuzzIdentity(uint256 x) public pure returns (uint256) {
+        return x;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--mt", "testFuzzMax"]).assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: differential mismatch: returned ([..]) but reference `referenceMax(uint8,uint8)` returned ([..]); counterexample: calldata=[..] args=[..]] testFuzzMax(uint8,uint8) (runs: [..], [AVG_GAS])
+...
+"#]]);
+
+    cmd.forge_fuse().args(["test", "--mt", "testFuzzIdentity"]).assert_failure().stdout_eq(str![[
+        r#"
+...
+[FAIL: FFI is disabled; add the `--ffi` flag to allow differential tests to call external commands] testFuzzIdentity(uint256) ([GAS])
+...
+"#
+    ]]);
+
+    cmd.forge_fuse().args(["test", "--mt", "testFuzzIdentity", "--ffi"]).assert_success();
+});
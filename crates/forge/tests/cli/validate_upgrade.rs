@@ -0,0 +1,96 @@
+//! Contains tests for `forge validate-upgrade`.
+
+const INITIALIZABLE: &str = r#"
+abstract contract Initializable {
+    bool private _initialized;
+
+    modifier initializer() {
+        require(!_initialized);
+        _initialized = true;
+        _;
+    }
+
+    function _disableInitializers() internal {
+        _initialized = true;
+    }
+}
+"#;
+
+forgetest!(validates_implementations, |prj, cmd| {
+    prj.add_source("Initializable.sol", INITIALIZABLE).unwrap();
+    prj.add_source(
+        "Vault.sol",
+        r#"
+import "./Initializable.sol";
+
+contract SafeVault is Initializable {
+    address public owner;
+
+    /// @custom:oz-upgrades-unsafe-allow constructor
+    constructor() {
+        _disableInitializers();
+    }
+
+    function initialize(address owner_) external initializer {
+        owner = owner_;
+    }
+}
+
+contract UnsafeVault is Initializable {
+    uint256 public fee = 100;
+    address public immutable token;
+
+    constructor(address token_) {
+        token = token_;
+    }
+
+    function destroy() external {
+        selfdestruct(payable(msg.sender));
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["validate-upgrade", "SafeVault"]).assert_success().stdout_eq(str![[r#"
+...
+Validated 1 contract(s), no upgrade safety errors found.
+
+"#]]);
+
+    cmd.forge_fuse().args(["validate-upgrade", "--json"]).assert_failure().stdout_eq(str![[r#"
+[
+  {
+    "contract": "src/Vault.sol:UnsafeVault",
+    "check": "state-variable-assignment",
+    "location": "src/Vault.sol:20",
+    "details": "`UnsafeVault.fee` is assigned an initial value"
+  },
+  {
+    "contract": "src/Vault.sol:UnsafeVault",
+    "check": "state-variable-immutable",
+    "location": "src/Vault.sol:21",
+    "details": "`UnsafeVault.token` is immutable"
+  },
+  {
+    "contract": "src/Vault.sol:UnsafeVault",
+    "check": "constructor",
+    "location": "src/Vault.sol:23",
+    "details": "`UnsafeVault` has a constructor"
+  },
+  {
+    "contract": "src/Vault.sol:UnsafeVault",
+    "check": "selfdestruct",
+    "location": "src/Vault.sol:28",
+    "details": "`UnsafeVault.destroy` uses `selfdestruct`"
+  },
+  {
+    "contract": "src/Vault.sol:UnsafeVault",
+    "check": "missing-initializer",
+    "location": "src/Vault.sol:19",
+    "details": "`UnsafeVault` has state variables but no function with the `initializer` modifier"
+  }
+]
+
+"#]]);
+});
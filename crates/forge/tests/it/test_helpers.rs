@@ -121,12 +121,17 @@ pub fn config(&self) -> Config {
                 dictionary_weight: 40,
                 max_fuzz_dictionary_addresses: 10_000,
                 max_fuzz_dictionary_values: 10_000,
+                fork_log_blocks: 0,
             },
             gas_report_samples: 256,
             failure_persist_dir: Some(tempfile::tempdir().unwrap().into_path()),
             failure_persist_file: Some("testfailure".to_string()),
             show_logs: false,
             timeout: None,
+            timestamp_range: None,
+            block_number_range: None,
+            corpus_dir: None,
+            differential: None,
         };
         config.invariant = InvariantConfig {
             runs: 256,
@@ -139,6 +144,7 @@ pub fn config(&self) -> Config {
                 include_push_bytes: true,
                 max_fuzz_dictionary_addresses: 10_000,
                 max_fuzz_dictionary_values: 10_000,
+                fork_log_blocks: 0,
             },
             shrink_run_limit: 5000,
             max_assume_rejects: 65536,
@@ -153,6 +159,7 @@ pub fn config(&self) -> Config {
             show_metrics: false,
             timeout: None,
             show_solidity: false,
+            replay_file: None,
         };
 
         config.sanitized()
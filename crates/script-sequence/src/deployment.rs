@@ -0,0 +1,135 @@
+use crate::ScriptSequence;
+use alloy_json_abi::JsonAbi;
+use alloy_network::ReceiptResponse;
+use alloy_primitives::{Address, TxHash, B256};
+use eyre::{Result, WrapErr};
+use foundry_common::fs;
+use revm_inspectors::tracing::types::CallKind;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A contract deployed by a broadcasted script.
+///
+/// Deployments are saved to `project_root/deployments/{chain_id}/{contract_name}.json` and
+/// overwritten by later deployments of a contract with the same name, so that they can be looked up
+/// with `vm.getDeployment`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deployment {
+    pub contract_name: String,
+    pub address: Address,
+    /// The constructor arguments.
+    pub arguments: Vec<String>,
+    pub transaction_hash: TxHash,
+    pub block_number: u64,
+    pub chain_id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abi: Option<JsonAbi>,
+    /// The keccak256 hash of the contract's runtime bytecode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytecode_hash: Option<B256>,
+    pub timestamp: u64,
+    /// The implementation of the contract, if it's an ERC-1967 proxy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implementation: Option<Address>,
+    /// The result of the latest verification of the contract, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification: Option<DeploymentVerification>,
+}
+
+/// The verification of a deployment by `forge script --verify`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentVerification {
+    pub status: VerificationStatus,
+    /// The provider that verified the contract, or the last one that was tried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verifier: Option<String>,
+    pub timestamp: u64,
+}
+
+/// The status of a [`DeploymentVerification`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationStatus {
+    Verified,
+    Failed,
+    /// No artifact matches the creation code of the contract.
+    Unverifiable,
+}
+
+impl Deployment {
+    /// Returns the deployments of the sequence, i.e. its successful CREATE and CREATE2
+    /// transactions of named contracts, in the order they were mined.
+    ///
+    /// The ABI and bytecode hash are left empty.
+    pub fn from_sequence(sequence: &ScriptSequence) -> Vec<Self> {
+        sequence
+            .receipts
+            .iter()
+            .filter(|receipt| receipt.status())
+            .filter_map(|receipt| {
+                let tx = sequence.transactions.iter().find(|tx| {
+                    tx.hash == Some(receipt.transaction_hash) &&
+                        matches!(tx.opcode, CallKind::Create | CallKind::Create2)
+                })?;
+                let contract_name = tx.contract_name.clone().filter(|name| !name.is_empty())?;
+                Some(Self {
+                    contract_name,
+                    address: tx.contract_address?,
+                    arguments: tx.arguments.clone().unwrap_or_default(),
+                    transaction_hash: receipt.transaction_hash,
+                    block_number: receipt.block_number.unwrap_or_default(),
+                    chain_id: sequence.chain,
+                    abi: None,
+                    bytecode_hash: None,
+                    timestamp: sequence.timestamp,
+                    implementation: None,
+                    verification: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the path of the deployment of `contract_name` on `chain_id`.
+    pub fn path(deployments_path: &Path, chain_id: u64, contract_name: &str) -> PathBuf {
+        deployments_path.join(chain_id.to_string()).join(format!("{contract_name}.json"))
+    }
+
+    /// Loads the latest deployment of `contract_name` on `chain_id`.
+    pub fn load(deployments_path: &Path, chain_id: u64, contract_name: &str) -> Result<Self> {
+        let path = Self::path(deployments_path, chain_id, contract_name);
+        fs::read_json_file(&path)
+            .wrap_err(format!("no deployment found for {contract_name} on chain {chain_id}"))
+    }
+
+    /// Updates the saved deployment of `contract_name` with the verification result, if it's still
+    /// the deployment at `address`.
+    pub fn update_verification(
+        deployments_path: &Path,
+        chain_id: u64,
+        contract_name: &str,
+        address: Address,
+        implementation: Option<Address>,
+        verification: DeploymentVerification,
+    ) -> Result<()> {
+        let Ok(mut deployment) = Self::load(deployments_path, chain_id, contract_name) else {
+            return Ok(());
+        };
+        if deployment.address != address {
+            return Ok(());
+        }
+        deployment.implementation = implementation;
+        deployment.verification = Some(verification);
+        deployment.save(deployments_path)?;
+        Ok(())
+    }
+
+    /// Saves the deployment, replacing any previous deployment of the contract on the same chain.
+    pub fn save(&self, deployments_path: &Path) -> Result<PathBuf> {
+        let path = Self::path(deployments_path, self.chain_id, &self.contract_name);
+        fs::create_dir_all(path.parent().expect("has parent"))?;
+        fs::write_pretty_json_file(&path, self)?;
+        Ok(path)
+    }
+}
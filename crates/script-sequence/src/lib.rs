@@ -3,10 +3,12 @@
 #[macro_use]
 extern crate foundry_common;
 
+pub mod deployment;
 pub mod reader;
 pub mod sequence;
 pub mod transaction;
 
+pub use deployment::*;
 pub use reader::*;
 pub use sequence::*;
 pub use transaction::*;
@@ -1,5 +1,5 @@
-use crate::transaction::TransactionWithMetadata;
-use alloy_network::AnyTransactionReceipt;
+use crate::transaction::{TransactionStatus, TransactionWithMetadata};
+use alloy_network::{AnyTransactionReceipt, ReceiptResponse};
 use alloy_primitives::{hex, map::HashMap, TxHash};
 use eyre::{ContextCompat, Result, WrapErr};
 use foundry_common::{fs, shell, TransactionMaybeSigned, SELECTOR_LEN};
@@ -31,7 +31,7 @@ pub struct ScriptSequence {
     pub pending: Vec<TxHash>,
     #[serde(skip)]
     /// Contains paths to the sequence files
-    /// None if sequence should not be saved to disk (e.g. part of a multi-chain sequence)
+    /// None if sequence should not be saved to disk
     pub paths: Option<(PathBuf, PathBuf)>,
     pub returns: HashMap<String, NestedValue>,
     pub timestamp: u64,
@@ -83,6 +83,7 @@ pub fn load(
         .wrap_err(format!("Deployment's sensitive details not found for chain `{chain_id}`."))?;
 
         script_sequence.fill_sensitive(&sensitive_script_sequence);
+        script_sequence.fill_status();
 
         script_sequence.paths = Some((path, sensitive_path));
 
@@ -146,9 +147,39 @@ pub fn save(&mut self, silent: bool, save_ts: bool) -> Result<()> {
     }
 
     pub fn add_receipt(&mut self, receipt: AnyTransactionReceipt) {
+        let status =
+            if receipt.status() { TransactionStatus::Mined } else { TransactionStatus::Reverted };
+        self.set_status(receipt.transaction_hash, status);
         self.receipts.push(receipt);
     }
 
+    /// Sets the status of the transaction with the given hash.
+    pub fn set_status(&mut self, tx_hash: TxHash, status: TransactionStatus) {
+        if let Some(tx) = self.transactions.iter_mut().find(|tx| tx.hash == Some(tx_hash)) {
+            tx.status = status;
+        }
+    }
+
+    /// Infers the status of the transactions from the receipts and pending hashes, for sequences
+    /// saved before the status was tracked.
+    fn fill_status(&mut self) {
+        if self.transactions.iter().any(|tx| tx.status != TransactionStatus::Unsent) {
+            return;
+        }
+        for tx in &mut self.transactions {
+            let Some(hash) = tx.hash else { continue };
+            if let Some(receipt) = self.receipts.iter().find(|r| r.transaction_hash == hash) {
+                tx.status = if receipt.status() {
+                    TransactionStatus::Mined
+                } else {
+                    TransactionStatus::Reverted
+                };
+            } else if self.pending.contains(&hash) {
+                tx.status = TransactionStatus::Pending;
+            }
+        }
+    }
+
     /// Sorts all receipts with ascending transaction index
     pub fn sort_receipts(&mut self) {
         self.receipts.sort_by_key(|r| (r.block_number, r.transaction_index));
@@ -157,6 +188,7 @@ pub fn sort_receipts(&mut self) {
     pub fn add_pending(&mut self, index: usize, tx_hash: TxHash) {
         if !self.pending.contains(&tx_hash) {
             self.transactions[index].hash = Some(tx_hash);
+            self.transactions[index].status = TransactionStatus::Pending;
             self.pending.push(tx_hash);
         }
     }
@@ -12,10 +12,36 @@ pub struct AdditionalContract {
     pub init_code: Bytes,
 }
 
+/// The broadcast status of a [`TransactionWithMetadata`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionStatus {
+    /// Not sent yet, or dropped and to be sent again.
+    #[default]
+    Unsent,
+    /// Sent and waiting to be mined.
+    Pending,
+    /// Mined successfully.
+    Mined,
+    /// Mined but reverted.
+    Reverted,
+    /// Not sent because its nonce was used by another transaction.
+    Skipped,
+}
+
+impl TransactionStatus {
+    /// Returns whether the transaction won't be sent again.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Mined | Self::Reverted | Self::Skipped)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionWithMetadata {
     pub hash: Option<B256>,
+    #[serde(default)]
+    pub status: TransactionStatus,
     #[serde(rename = "transactionType")]
     pub opcode: CallKind,
     #[serde(default = "default_string")]
@@ -50,6 +76,7 @@ pub fn from_tx_request(transaction: TransactionMaybeSigned) -> Self {
         Self {
             transaction,
             hash: Default::default(),
+            status: Default::default(),
             opcode: Default::default(),
             contract_name: Default::default(),
             contract_address: Default::default(),
@@ -1,20 +1,26 @@
 use crate::{
-    build::LinkedBuildData, progress::ScriptProgress, sequence::ScriptSequenceKind,
-    verify::BroadcastedState, ScriptArgs, ScriptConfig,
+    build::LinkedBuildData,
+    fees::{check_max_total_fee, estimate_fees},
+    progress::ScriptProgress,
+    sequence::ScriptSequenceKind,
+    verify::BroadcastedState,
+    ScriptArgs, ScriptConfig,
 };
-use alloy_chains::Chain;
-use alloy_consensus::TxEnvelope;
+use alloy_consensus::{Transaction, TxEnvelope};
 use alloy_eips::{eip2718::Encodable2718, BlockId};
 use alloy_network::{AnyNetwork, EthereumWallet, TransactionBuilder};
 use alloy_primitives::{
+    keccak256,
     map::{AddressHashMap, AddressHashSet},
     utils::format_units,
     Address, TxHash,
 };
-use alloy_provider::{utils::Eip1559Estimation, Provider};
+use alloy_provider::Provider;
 use alloy_rpc_types::TransactionRequest;
 use alloy_serde::WithOtherFields;
+use alloy_signer::Signer;
 use eyre::{bail, Context, Result};
+use forge_script_sequence::{Deployment, ScriptSequence, TransactionStatus};
 use forge_verify::provider::VerificationProviderType;
 use foundry_cheatcodes::Wallets;
 use foundry_cli::utils::{has_batch_support, has_different_gas_calc};
@@ -23,6 +29,7 @@
     shell, TransactionMaybeSigned,
 };
 use foundry_config::Config;
+use foundry_wallets::utils::create_config_signer;
 use futures::{future::join_all, StreamExt};
 use itertools::Itertools;
 use std::{cmp::Ordering, sync::Arc};
@@ -128,6 +135,19 @@ pub enum SendTransactionKind<'a> {
     Signed(TxEnvelope),
 }
 
+impl SendTransactionKind<'_> {
+    /// Returns the maximum fee the transaction can pay, if its gas limit is known.
+    pub fn max_fee(&self) -> u128 {
+        match self {
+            Self::Unlocked(tx) | Self::Raw(tx, _) => {
+                tx.gas.unwrap_or_default() as u128 *
+                    tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default()
+            }
+            Self::Signed(tx) => tx.gas_limit() as u128 * tx.max_fee_per_gas(),
+        }
+    }
+}
+
 /// Represents how to send _all_ transactions
 pub enum SendTransactionsKind {
     /// Send via `eth_sendTransaction` and rely on the  `from` address being unlocked.
@@ -163,6 +183,60 @@ pub fn for_sender(
     }
 }
 
+/// Updates the status of the previously sent transactions of the sequence from the chain, so that
+/// resuming doesn't send them again.
+///
+/// Transactions with a receipt are marked as mined or reverted, and unmined transactions whose
+/// nonce was already used on chain are skipped.
+async fn sync_with_chain(sequence: &mut ScriptSequence, provider: &RetryProvider) -> Result<()> {
+    let mut nonces = AddressHashMap::default();
+    for index in 0..sequence.transactions.len() {
+        let tx = &sequence.transactions[index];
+        if tx.status.is_final() {
+            continue;
+        }
+        let hash = tx.hash;
+
+        if let Some(hash) = hash {
+            if let Some(receipt) = provider.get_transaction_receipt(hash).await? {
+                sequence.remove_pending(hash);
+                sequence.add_receipt(receipt);
+                continue;
+            }
+        }
+
+        let (Some(from), Some(nonce)) = (tx.tx().from(), tx.tx().nonce()) else { continue };
+        let onchain_nonce = match nonces.get(&from) {
+            Some(nonce) => *nonce,
+            None => {
+                let nonce = provider.get_transaction_count(from).await?;
+                nonces.insert(from, nonce);
+                nonce
+            }
+        };
+        if nonce < onchain_nonce {
+            sh_warn!(
+                "Skipping transaction #{index} from {from}: nonce {nonce} was used by another transaction."
+            )?;
+            if let Some(hash) = hash {
+                sequence.remove_pending(hash);
+            }
+            sequence.transactions[index].status = TransactionStatus::Skipped;
+        }
+    }
+    sequence.sort_receipts();
+    Ok(())
+}
+
+/// Returns the fee to use for a transaction replacing one that paid `previous`, which must be at
+/// least `percent` higher.
+fn bump_fee(current: u128, previous: Option<u128>, percent: u64) -> u128 {
+    match previous {
+        Some(previous) => current.max(previous * (100 + percent as u128) / 100),
+        None => current,
+    }
+}
+
 /// State after we have bundled all
 /// [`TransactionWithMetadata`](forge_script_sequence::TransactionWithMetadata) objects into a
 /// single [`ScriptSequenceKind`] object containing one or more script sequences.
@@ -186,12 +260,23 @@ pub async fn wait_for_pending(mut self) -> Result<Self> {
             .map(|(sequence_idx, sequence)| async move {
                 let rpc_url = sequence.rpc_url();
                 let provider = Arc::new(get_http_provider(rpc_url));
+                if self.args.resume {
+                    sync_with_chain(sequence, &provider).await?;
+                }
+                if self.args.bump_fees.is_some() {
+                    // Pending transactions are replaced instead of waited for.
+                    for hash in std::mem::take(&mut sequence.pending) {
+                        sequence.set_status(hash, TransactionStatus::Unsent);
+                    }
+                    return Ok(());
+                }
                 progress_ref
                     .wait_for_pending(
                         sequence_idx,
                         sequence,
                         &provider,
                         self.script_config.config.transaction_timeout,
+                        self.args.bump_on_timeout.is_some(),
                     )
                     .await
             })
@@ -231,11 +316,26 @@ pub async fn broadcast(mut self) -> Result<BroadcastedState> {
         let send_kind = if self.args.unlocked {
             SendTransactionsKind::Unlocked(required_addresses.clone())
         } else {
-            let signers = self.script_wallets.into_multi_wallet().into_signers()?;
+            let mut signers = self.script_wallets.into_multi_wallet().into_signers()?;
             let mut missing_addresses = Vec::new();
 
             for addr in &required_addresses {
-                if !signers.contains_key(addr) {
+                if signers.contains_key(addr) {
+                    continue;
+                }
+                // Fall back to the wallet configured for the address in `foundry.toml`.
+                if let Some(wallet) = self.script_config.config.wallets.get(addr) {
+                    let signer = create_config_signer(wallet).await.wrap_err_with(|| {
+                        format!("Failed to load the wallet configured for {addr}")
+                    })?;
+                    if signer.address() != *addr {
+                        bail!(
+                            "The wallet configured for {addr} is for a different address: {}",
+                            signer.address()
+                        );
+                    }
+                    signers.insert(*addr, signer);
+                } else {
                     missing_addresses.push(addr);
                 }
             }
@@ -262,83 +362,94 @@ pub async fn broadcast(mut self) -> Result<BroadcastedState> {
             let mut sequence = self.sequence.sequences_mut().get_mut(i).unwrap();
 
             let provider = Arc::new(try_get_http_provider(sequence.rpc_url())?);
-            let already_broadcasted = sequence.receipts.len();
-
             let seq_progress = progress.get_sequence_progress(i, sequence);
 
-            if already_broadcasted < sequence.transactions.len() {
-                let is_legacy = Chain::from(sequence.chain).is_legacy() || self.args.legacy;
-                // Make a one-time gas price estimation
-                let (gas_price, eip1559_fees) = match (
-                    is_legacy,
-                    self.args.with_gas_price,
-                    self.args.priority_gas_price,
-                ) {
-                    (true, Some(gas_price), _) => (Some(gas_price.to()), None),
-                    (true, None, _) => (Some(provider.get_gas_price().await?), None),
-                    (false, Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => (
-                        None,
-                        Some(Eip1559Estimation {
-                            max_fee_per_gas: max_fee_per_gas.to(),
-                            max_priority_fee_per_gas: max_priority_fee_per_gas.to(),
-                        }),
-                    ),
-                    (false, _, _) => {
-                        let mut fees = provider.estimate_eip1559_fees().await.wrap_err("Failed to estimate EIP1559 fees. This chain might not support EIP1559, try adding --legacy to your command.")?;
-
-                        if let Some(gas_price) = self.args.with_gas_price {
-                            fees.max_fee_per_gas = gas_price.to();
-                        }
-
-                        if let Some(priority_gas_price) = self.args.priority_gas_price {
-                            fees.max_priority_fee_per_gas = priority_gas_price.to();
-                        }
+            // Transactions replaced after timing out are sent again, until none is left unsent.
+            loop {
+                let unsent = sequence
+                    .transactions
+                    .iter()
+                    .positions(|tx| tx.status == TransactionStatus::Unsent)
+                    .collect::<Vec<_>>();
+                if unsent.is_empty() {
+                    break;
+                }
 
-                        (None, Some(fees))
-                    }
-                };
+                // Make a gas price estimation for the unsent transactions
+                let (gas_price, eip1559_fees) = estimate_fees(
+                    &self.args,
+                    &self.script_config.config,
+                    &provider,
+                    sequence.chain,
+                )
+                .await?;
 
                 // Iterate through transactions, matching the `from` field with the associated
                 // wallet. Then send the transaction. Panics if we find a unknown `from`
-                let transactions = sequence
-                    .transactions
-                    .iter()
-                    .skip(already_broadcasted)
-                    .map(|tx_with_metadata| {
-                        let is_fixed_gas_limit = tx_with_metadata.is_fixed_gas_limit;
+                let mut transactions = Vec::with_capacity(unsent.len());
+                for &index in &unsent {
+                    let tx_with_metadata = &sequence.transactions[index];
+                    let is_fixed_gas_limit = tx_with_metadata.is_fixed_gas_limit;
+
+                    // A transaction that was sent before and is still known to the node is
+                    // replaced, which requires paying higher fees.
+                    let (bump_percent, replaced) = match (
+                        self.args.bump_fees.or(self.args.bump_on_timeout),
+                        tx_with_metadata.hash,
+                    ) {
+                        (Some(percent), Some(hash)) => {
+                            (percent, provider.get_transaction_by_hash(hash).await?)
+                        }
+                        _ => (0, None),
+                    };
+
+                    let kind = match tx_with_metadata.tx().clone() {
+                        TransactionMaybeSigned::Signed { tx, .. } => {
+                            if replaced.is_some() {
+                                sh_warn!(
+                                    "Cannot bump the fees of pre-signed transaction {:?}, sending it again as is.",
+                                    tx_with_metadata.hash.unwrap_or_default()
+                                )?;
+                            }
+                            SendTransactionKind::Signed(tx)
+                        }
+                        TransactionMaybeSigned::Unsigned(mut tx) => {
+                            let from = tx.from.expect("No sender for onchain transaction!");
 
-                        let kind = match tx_with_metadata.tx().clone() {
-                            TransactionMaybeSigned::Signed { tx, .. } => {
-                                SendTransactionKind::Signed(tx)
+                            tx.set_chain_id(sequence.chain);
+
+                            // Set TxKind::Create explicitly to satisfy `check_reqd_fields` in
+                            // alloy
+                            if tx.to.is_none() {
+                                tx.set_create();
                             }
-                            TransactionMaybeSigned::Unsigned(mut tx) => {
-                                let from = tx.from.expect("No sender for onchain transaction!");
-
-                                tx.set_chain_id(sequence.chain);
-
-                                // Set TxKind::Create explicitly to satisfy `check_reqd_fields` in
-                                // alloy
-                                if tx.to.is_none() {
-                                    tx.set_create();
-                                }
-
-                                if let Some(gas_price) = gas_price {
-                                    tx.set_gas_price(gas_price);
-                                } else {
-                                    let eip1559_fees = eip1559_fees.expect("was set above");
-                                    tx.set_max_priority_fee_per_gas(
-                                        eip1559_fees.max_priority_fee_per_gas,
-                                    );
-                                    tx.set_max_fee_per_gas(eip1559_fees.max_fee_per_gas);
-                                }
-
-                                send_kind.for_sender(&from, tx)?
+
+                            if let Some(gas_price) = gas_price {
+                                let previous = replaced.as_ref().and_then(|tx| tx.gas_price());
+                                tx.set_gas_price(bump_fee(gas_price, previous, bump_percent));
+                            } else {
+                                let eip1559_fees = eip1559_fees.expect("was set above");
+                                let previous =
+                                    replaced.as_ref().and_then(|tx| tx.max_priority_fee_per_gas());
+                                tx.set_max_priority_fee_per_gas(bump_fee(
+                                    eip1559_fees.max_priority_fee_per_gas,
+                                    previous,
+                                    bump_percent,
+                                ));
+                                let previous = replaced.as_ref().map(|tx| tx.max_fee_per_gas());
+                                tx.set_max_fee_per_gas(bump_fee(
+                                    eip1559_fees.max_fee_per_gas,
+                                    previous,
+                                    bump_percent,
+                                ));
                             }
-                        };
 
-                        Ok((kind, is_fixed_gas_limit))
-                    })
-                    .collect::<Result<Vec<_>>>()?;
+                            send_kind.for_sender(&from, tx)?
+                        }
+                    };
+
+                    transactions.push((kind, is_fixed_gas_limit));
+                }
 
                 let estimate_via_rpc =
                     has_different_gas_calc(sequence.chain) || self.args.skip_simulation;
@@ -355,9 +466,15 @@ pub async fn broadcast(mut self) -> Result<BroadcastedState> {
 
                 // We send transactions and wait for receipts in batches.
                 let batch_size = if sequential_broadcast { 1 } else { self.args.batch_size };
-                let mut index = already_broadcasted;
+                let mut indices = unsent.iter();
 
                 for (batch_number, batch) in transactions.chunks(batch_size).enumerate() {
+                    check_max_total_fee(
+                        sequence,
+                        batch.iter().map(|(kind, _)| kind.max_fee()).sum(),
+                        self.args.max_total_fee,
+                    )?;
+
                     let mut pending_transactions = vec![];
 
                     seq_progress.inner.write().set_status(&format!(
@@ -382,6 +499,7 @@ pub async fn broadcast(mut self) -> Result<BroadcastedState> {
 
                         while let Some(tx_hash) = buffer.next().await {
                             let tx_hash = tx_hash.wrap_err("Failed to send transaction")?;
+                            let index = *indices.next().expect("one index per transaction");
                             sequence.add_pending(index, tx_hash);
 
                             // Checkpoint save
@@ -389,7 +507,6 @@ pub async fn broadcast(mut self) -> Result<BroadcastedState> {
                             sequence = self.sequence.sequences_mut().get_mut(i).unwrap();
 
                             seq_progress.inner.write().tx_sent(tx_hash);
-                            index += 1;
                         }
 
                         // Checkpoint save
@@ -402,8 +519,12 @@ pub async fn broadcast(mut self) -> Result<BroadcastedState> {
                                 sequence,
                                 &provider,
                                 self.script_config.config.transaction_timeout,
+                                self.args.bump_on_timeout.is_some(),
                             )
-                            .await?
+                            .await?;
+
+                        // Gas limits estimated when sending are only accounted for once mined.
+                        check_max_total_fee(sequence, 0, self.args.max_total_fee)?;
                     }
                     // Checkpoint save
                     self.sequence.save(true, false)?;
@@ -418,8 +539,9 @@ pub async fn broadcast(mut self) -> Result<BroadcastedState> {
                     (acc.0 + gas_used, acc.1 + gas_price, acc.2 + gas_used * gas_price)
                 });
             let paid = format_units(total_paid, 18).unwrap_or_else(|_| "N/A".to_string());
-            let avg_gas_price = format_units(total_gas_price / sequence.receipts.len() as u64, 9)
-                .unwrap_or_else(|_| "N/A".to_string());
+            let avg_gas_price =
+                format_units(total_gas_price / sequence.receipts.len().max(1) as u64, 9)
+                    .unwrap_or_else(|_| "N/A".to_string());
 
             seq_progress.inner.write().set_status(&format!(
                 "Total Paid: {} ETH ({} gas * avg {} gwei)\n",
@@ -430,17 +552,20 @@ pub async fn broadcast(mut self) -> Result<BroadcastedState> {
             seq_progress.inner.write().finish();
         }
 
+        let broadcasted = BroadcastedState {
+            args: self.args,
+            script_config: self.script_config,
+            build_data: self.build_data,
+            sequence: self.sequence,
+        };
+        broadcasted.save_deployments()?;
+
         if !shell::is_json() {
             sh_println!("\n\n==========================")?;
             sh_println!("\nONCHAIN EXECUTION COMPLETE & SUCCESSFUL.")?;
         }
 
-        Ok(BroadcastedState {
-            args: self.args,
-            script_config: self.script_config,
-            build_data: self.build_data,
-            sequence: self.sequence,
-        })
+        Ok(broadcasted)
     }
 
     pub fn verify_preflight_check(&self) -> Result<()> {
@@ -458,3 +583,27 @@ pub fn verify_preflight_check(&self) -> Result<()> {
         Ok(())
     }
 }
+
+impl BroadcastedState {
+    /// Saves the contracts deployed by the sequences to the deployments directory.
+    pub(crate) fn save_deployments(&self) -> Result<()> {
+        let deployments_path = &self.script_config.config.deployments;
+        for sequence in self.sequence.sequences() {
+            for mut deployment in Deployment::from_sequence(sequence) {
+                let contract = self
+                    .build_data
+                    .known_contracts
+                    .iter()
+                    .find(|(id, _)| id.name == deployment.contract_name)
+                    .map(|(_, contract)| contract);
+                if let Some(contract) = contract {
+                    deployment.abi = Some(contract.abi.clone());
+                    deployment.bytecode_hash = contract.deployed_bytecode().map(keccak256);
+                }
+                deployment.save(deployments_path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
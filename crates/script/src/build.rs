@@ -8,7 +8,8 @@
 use forge_script_sequence::ScriptSequence;
 use foundry_cheatcodes::Wallets;
 use foundry_common::{
-    compile::ProjectCompiler, provider::try_get_http_provider, ContractData, ContractsByArtifact,
+    artifact_store::ArtifactStore, compile::ProjectCompiler, provider::try_get_http_provider,
+    ContractData, ContractsByArtifact,
 };
 use foundry_compilers::{
     artifacts::{BytecodeObject, Libraries},
@@ -189,7 +190,10 @@ pub fn compile(self) -> Result<CompiledState> {
         )
         .chain([target_path.to_path_buf()]);
 
-        let output = ProjectCompiler::new().files(sources_to_compile).compile(&project)?;
+        let output = ProjectCompiler::new()
+            .files(sources_to_compile)
+            .artifact_store(ArtifactStore::from_config(&script_config.config)?)
+            .compile(&project)?;
 
         let mut target_id: Option<ArtifactId> = None;
 
@@ -295,7 +299,10 @@ pub async fn resume(self) -> Result<BundledState> {
                 .signers()
                 .map_err(|e| eyre::eyre!("Failed to get available signers: {}", e))?;
 
-            if !froms.all(|from| available_signers.contains(&from)) {
+            let configured_wallets = &self.script_config.config.wallets;
+            if !froms.all(|from| {
+                available_signers.contains(&from) || configured_wallets.contains_key(&from)
+            }) {
                 // IF we are missing required signers, execute script as we might need to collect
                 // private keys from the execution.
                 let executed = self.link().await?.prepare_execution().await?.execute().await?;
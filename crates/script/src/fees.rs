@@ -0,0 +1,155 @@
+use crate::ScriptArgs;
+use alloy_chains::Chain;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{utils::format_units, U256};
+use alloy_provider::{
+    utils::{eip1559_default_estimator, Eip1559Estimation},
+    Provider,
+};
+use clap::ValueEnum;
+use eyre::{bail, Result, WrapErr};
+use forge_script_sequence::ScriptSequence;
+use foundry_common::provider::RetryProvider;
+use foundry_config::Config;
+
+/// The number of recent blocks whose priority fees are sampled by the slow and fast strategies.
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// How the fees of broadcasted transactions are chosen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum FeeStrategy {
+    /// Pay the 10th percentile of the priority fees of recent blocks, or 90% of the gas price of
+    /// legacy transactions.
+    Slow,
+    /// Pay the fees estimated by the node.
+    #[default]
+    Standard,
+    /// Pay the 90th percentile of the priority fees of recent blocks, or 125% of the gas price of
+    /// legacy transactions.
+    Fast,
+    /// Pay the fees given with `--with-gas-price` and `--priority-gas-price`.
+    Custom,
+}
+
+impl FeeStrategy {
+    /// Returns the percentile of the priority fees of recent blocks to pay, or `None` to use the
+    /// node's estimation.
+    fn reward_percentile(self) -> Option<f64> {
+        match self {
+            Self::Slow => Some(10.0),
+            Self::Fast => Some(90.0),
+            Self::Standard | Self::Custom => None,
+        }
+    }
+
+    /// Returns the percentage of the node's gas price to pay for legacy transactions.
+    fn gas_price_percent(self) -> u128 {
+        match self {
+            Self::Slow => 90,
+            Self::Fast => 125,
+            Self::Standard | Self::Custom => 100,
+        }
+    }
+}
+
+/// Returns the fees to pay for the transactions sent to `chain`: a gas price for legacy
+/// transactions, and EIP-1559 fees otherwise.
+///
+/// Estimated priority fees are capped by the `priority_fee_caps` of the config.
+pub async fn estimate_fees(
+    args: &ScriptArgs,
+    config: &Config,
+    provider: &RetryProvider,
+    chain: u64,
+) -> Result<(Option<u128>, Option<Eip1559Estimation>)> {
+    let is_legacy = Chain::from(chain).is_legacy() || args.legacy;
+    let strategy = args.fee_strategy;
+
+    if strategy == FeeStrategy::Custom &&
+        (args.with_gas_price.is_none() || (!is_legacy && args.priority_gas_price.is_none()))
+    {
+        bail!(
+            "`--fee-strategy custom` requires `--with-gas-price`, and `--priority-gas-price` for EIP-1559 transactions"
+        );
+    }
+
+    if is_legacy {
+        let gas_price = match args.with_gas_price {
+            Some(gas_price) => gas_price.to(),
+            None => provider.get_gas_price().await? * strategy.gas_price_percent() / 100,
+        };
+        return Ok((Some(gas_price), None));
+    }
+
+    if let (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) =
+        (args.with_gas_price, args.priority_gas_price)
+    {
+        return Ok((
+            None,
+            Some(Eip1559Estimation {
+                max_fee_per_gas: max_fee_per_gas.to(),
+                max_priority_fee_per_gas: max_priority_fee_per_gas.to(),
+            }),
+        ));
+    }
+
+    let mut fees = match strategy.reward_percentile() {
+        Some(percentile) => {
+            let history = provider
+                .get_fee_history(FEE_HISTORY_BLOCKS, BlockNumberOrTag::Latest, &[percentile])
+                .await
+                .wrap_err("Failed to fetch the fee history. This chain might not support EIP1559, try adding --legacy to your command.")?;
+            // The last base fee is the one of the next block.
+            let base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default();
+            eip1559_default_estimator(base_fee, history.reward.as_deref().unwrap_or_default())
+        }
+        None => provider.estimate_eip1559_fees().await.wrap_err("Failed to estimate EIP1559 fees. This chain might not support EIP1559, try adding --legacy to your command.")?,
+    };
+
+    if let Some(cap) = config.priority_fee_cap(chain.into())? {
+        let cap = cap as u128;
+        if fees.max_priority_fee_per_gas > cap {
+            fees.max_fee_per_gas =
+                fees.max_fee_per_gas.saturating_sub(fees.max_priority_fee_per_gas - cap);
+            fees.max_priority_fee_per_gas = cap;
+        }
+    }
+
+    if let Some(gas_price) = args.with_gas_price {
+        fees.max_fee_per_gas = gas_price.to();
+    }
+
+    if let Some(priority_gas_price) = args.priority_gas_price {
+        fees.max_priority_fee_per_gas = priority_gas_price.to();
+    }
+
+    Ok((None, Some(fees)))
+}
+
+/// Fails if sending transactions paying up to `max_fees` could bring the fees paid for the
+/// transactions of the sequence over `max_total_fee`.
+pub fn check_max_total_fee(
+    sequence: &ScriptSequence,
+    max_fees: u128,
+    max_total_fee: Option<U256>,
+) -> Result<()> {
+    let Some(max_total_fee) = max_total_fee else { return Ok(()) };
+
+    let paid = sequence
+        .receipts
+        .iter()
+        .map(|receipt| receipt.gas_used as u128 * receipt.effective_gas_price)
+        .sum::<u128>();
+    let total = U256::from(paid) + U256::from(max_fees);
+    if total > max_total_fee {
+        bail!(
+            "The fees paid on chain {} could reach {} ETH, over the maximum total fee of {} ETH.\n\n\
+             Raise `--max-total-fee` and add `--resume` to your command to continue broadcasting the transactions.",
+            sequence.chain,
+            format_units(total, 18).unwrap_or_else(|_| "N/A".to_string()),
+            format_units(max_total_fee, 18).unwrap_or_else(|_| "N/A".to_string()),
+        );
+    }
+
+    Ok(())
+}
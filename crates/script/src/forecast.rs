@@ -0,0 +1,165 @@
+use alloy_eips::{
+    eip1559::{calc_next_block_base_fee, BaseFeeParams},
+    eip4844::calc_excess_blob_gas,
+    BlockNumberOrTag,
+};
+use alloy_network::AnyRpcBlock;
+use alloy_primitives::U256;
+use alloy_provider::Provider;
+use eyre::{Result, WrapErr};
+use foundry_common::provider::RetryProvider;
+use foundry_evm::revm::primitives::BlockEnv;
+use std::str::FromStr;
+
+/// The number of recent blocks used to forecast the base fee and the block time.
+const HISTORY_BLOCKS: u64 = 10;
+
+/// The block time assumed when it can't be measured, in seconds.
+const DEFAULT_BLOCK_TIME: u64 = 12;
+
+/// The block whose environment the on-chain simulation of a script uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimulateBlock {
+    Number(u64),
+    /// The pending block of the node, or the next block if the node doesn't expose it.
+    Pending,
+    /// The block after the latest one.
+    Next,
+}
+
+impl FromStr for SimulateBlock {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "next" => Ok(Self::Next),
+            _ => s.parse().map(Self::Number).map_err(|_| {
+                format!("invalid block `{s}`, expected a block number, `pending` or `next`")
+            }),
+        }
+    }
+}
+
+/// The environment of the block a script is simulated against, forecasted from the recent blocks
+/// if it's not mined yet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockForecast {
+    pub number: u64,
+    pub timestamp: u64,
+    pub base_fee: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+}
+
+impl BlockForecast {
+    /// Returns the environment of `block` on the chain of `provider`.
+    ///
+    /// The base fee of a future block is forecasted assuming the blocks until it use as much gas
+    /// as the recent ones, its blob base fee assuming they use the target blob gas, and its
+    /// timestamp assuming the recent block time.
+    pub async fn fetch(provider: &RetryProvider, block: SimulateBlock) -> Result<Self> {
+        let latest = get_block(provider, BlockNumberOrTag::Latest).await?;
+        let number = match block {
+            SimulateBlock::Number(number) => number,
+            SimulateBlock::Next => latest.header.number + 1,
+            SimulateBlock::Pending => {
+                match provider.get_block_by_number(BlockNumberOrTag::Pending).await {
+                    Ok(Some(pending)) => return Ok(Self::from_block(&pending)),
+                    _ => latest.header.number + 1,
+                }
+            }
+        };
+
+        if number <= latest.header.number {
+            let block = get_block(provider, BlockNumberOrTag::Number(number)).await?;
+            return Ok(Self::from_block(&block));
+        }
+
+        let blocks = number - latest.header.number;
+        let history = provider
+            .get_fee_history(HISTORY_BLOCKS, BlockNumberOrTag::Latest, &[])
+            .await
+            .wrap_err("failed to fetch the fee history")?;
+
+        // The last base fee of the history is the one of the next block.
+        let mut base_fee = history.base_fee_per_gas.last().map(|fee| *fee as u64);
+        let gas_used_ratio = if history.gas_used_ratio.is_empty() {
+            0.5
+        } else {
+            history.gas_used_ratio.iter().sum::<f64>() / history.gas_used_ratio.len() as f64
+        };
+        let gas_limit = latest.header.gas_limit;
+        for _ in 1..blocks {
+            base_fee = base_fee.map(|base_fee| {
+                calc_next_block_base_fee(
+                    (gas_limit as f64 * gas_used_ratio) as u64,
+                    gas_limit,
+                    base_fee,
+                    BaseFeeParams::ethereum(),
+                )
+            });
+        }
+
+        let excess_blob_gas = latest.header.excess_blob_gas.map(|excess_blob_gas| {
+            calc_excess_blob_gas(excess_blob_gas, latest.header.blob_gas_used.unwrap_or_default())
+        });
+
+        let block_time = match latest.header.number.checked_sub(HISTORY_BLOCKS) {
+            Some(old) => {
+                let old = get_block(provider, BlockNumberOrTag::Number(old)).await?;
+                (latest.header.timestamp.saturating_sub(old.header.timestamp) / HISTORY_BLOCKS)
+                    .max(1)
+            }
+            None => DEFAULT_BLOCK_TIME,
+        };
+
+        Ok(Self {
+            number,
+            timestamp: latest.header.timestamp + blocks * block_time,
+            base_fee,
+            excess_blob_gas,
+        })
+    }
+
+    fn from_block(block: &AnyRpcBlock) -> Self {
+        let header = &block.header;
+        Self {
+            number: header.number,
+            timestamp: header.timestamp,
+            base_fee: header.base_fee_per_gas,
+            excess_blob_gas: header.excess_blob_gas,
+        }
+    }
+
+    /// Sets the forecasted values in the block environment.
+    pub fn apply(&self, env: &mut BlockEnv, is_prague: bool) {
+        env.number = U256::from(self.number);
+        env.timestamp = U256::from(self.timestamp);
+        if let Some(base_fee) = self.base_fee {
+            env.basefee = U256::from(base_fee);
+        }
+        if let Some(excess_blob_gas) = self.excess_blob_gas {
+            env.set_blob_excess_gas_and_price(excess_blob_gas, is_prague);
+        }
+    }
+}
+
+async fn get_block(provider: &RetryProvider, block: BlockNumberOrTag) -> Result<AnyRpcBlock> {
+    provider
+        .get_block_by_number(block)
+        .await?
+        .ok_or_else(|| eyre::eyre!("failed to get block {block}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_simulate_block() {
+        assert_eq!("pending".parse(), Ok(SimulateBlock::Pending));
+        assert_eq!("next".parse(), Ok(SimulateBlock::Next));
+        assert_eq!("123".parse(), Ok(SimulateBlock::Number(123)));
+        assert!("latest".parse::<SimulateBlock>().is_err());
+    }
+}
@@ -24,6 +24,8 @@
 use clap::{Parser, ValueHint};
 use dialoguer::Confirm;
 use eyre::{ContextCompat, Result};
+use fees::FeeStrategy;
+use forecast::SimulateBlock;
 use forge_script_sequence::{AdditionalContract, NestedValue};
 use forge_verify::{RetryArgs, VerifierArgs};
 use foundry_cli::{
@@ -57,18 +59,24 @@
 use foundry_wallets::MultiWalletOpts;
 use serde::Serialize;
 use std::path::PathBuf;
+use user_operation::UserOperationArgs;
+use verify::parse_verifier_fallback;
 
 mod broadcast;
 mod build;
 mod execute;
+mod fees;
+mod forecast;
 mod multi_sequence;
 mod progress;
 mod providers;
 mod receipts;
 mod runner;
+mod safe;
 mod sequence;
 mod simulate;
 mod transaction;
+mod user_operation;
 mod verify;
 
 // Loads project's figment and merges the build cli arguments into it
@@ -108,6 +116,10 @@ pub struct ScriptArgs {
     )]
     pub priority_gas_price: Option<U256>,
 
+    /// The strategy choosing the fees of the broadcasted transactions.
+    #[arg(long, value_enum, default_value_t = FeeStrategy::Standard)]
+    pub fee_strategy: FeeStrategy,
+
     /// Use legacy transactions instead of EIP1559 ones.
     ///
     /// This is auto-enabled for common networks without EIP1559.
@@ -118,6 +130,23 @@ pub struct ScriptArgs {
     #[arg(long)]
     pub broadcast: bool,
 
+    /// Saves the transactions as a Safe Transaction Builder batch for the given Safe, instead of
+    /// broadcasting them.
+    ///
+    /// The transactions must be sent from the Safe, e.g. with `vm.startBroadcast(safe)`, and
+    /// contracts must be deployed with CREATE2.
+    #[arg(long, value_name = "ADDRESS", conflicts_with_all = &["broadcast", "resume"])]
+    pub safe: Option<Address>,
+
+    /// Proposes the transactions to the Safe Transaction Service at the given URL, e.g.
+    /// `https://safe-transaction-sepolia.safe.global`, instead of saving them, when used with
+    /// `--safe`.
+    ///
+    /// The transactions are batched with MultiSendCallOnly into a single Safe transaction at the
+    /// current nonce of the Safe, signed by the loaded wallet, which must be an owner of the Safe.
+    #[arg(long, value_name = "URL", requires = "safe")]
+    pub safe_tx_service: Option<String>,
+
     /// Batch size of transactions.
     ///
     /// This is ignored and set to 1 if batching is not available or `--slow` is enabled.
@@ -128,6 +157,14 @@ pub struct ScriptArgs {
     #[arg(long)]
     pub skip_simulation: bool,
 
+    /// Simulates the transactions against the environment of the given block instead of the
+    /// latest one: a block number, `pending` or `next`.
+    ///
+    /// The base fee, blob base fee and timestamp of a block that isn't mined yet are forecasted
+    /// from the recent blocks.
+    #[arg(long, value_name = "BLOCK", conflicts_with = "skip_simulation")]
+    pub simulate_block: Option<SimulateBlock>,
+
     /// Relative percentage to multiply gas estimates by.
     #[arg(long, short, default_value = "130")]
     pub gas_estimate_multiplier: u64,
@@ -141,13 +178,34 @@ pub struct ScriptArgs {
 
     /// Resumes submitting transactions that failed or timed-out previously.
     ///
-    /// It DOES NOT simulate the script again and it expects nonces to have remained the same.
+    /// It DOES NOT simulate the script again. Transactions that were mined in the meantime are
+    /// not sent again, and transactions whose nonce was used by another transaction are skipped.
     ///
     /// Example: If transaction N has a nonce of 22, then the account should have a nonce of 22,
     /// otherwise it fails.
     #[arg(long)]
     pub resume: bool,
 
+    /// Replaces the pending transactions of a resumed broadcast with ones paying higher fees,
+    /// instead of waiting for them.
+    ///
+    /// The fees of the replacements are increased by the given percentage, 10 by default.
+    #[arg(
+        long,
+        requires = "resume",
+        num_args = 0..=1,
+        default_missing_value = "10",
+        value_name = "PERCENT"
+    )]
+    pub bump_fees: Option<u64>,
+
+    /// Replaces the transactions still pending after the transaction timeout with ones paying
+    /// higher fees, instead of waiting for them.
+    ///
+    /// The fees of the replacements are increased by the given percentage, 10 by default.
+    #[arg(long, num_args = 0..=1, default_missing_value = "10", value_name = "PERCENT")]
+    pub bump_on_timeout: Option<u64>,
+
     /// If present, --resume or --verify will be assumed to be a multi chain deployment.
     #[arg(long)]
     pub multi: bool,
@@ -186,6 +244,21 @@ pub struct ScriptArgs {
     #[arg(long)]
     pub verify: bool,
 
+    /// Verification providers to fall back to, in order, if verifying a contract with
+    /// `--verifier` fails.
+    ///
+    /// Each provider is given as `<PROVIDER>` or `<PROVIDER>:<URL>`, e.g.
+    /// `sourcify,blockscout:https://eth.blockscout.com/api/`.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = parse_verifier_fallback,
+        requires = "verify",
+        help_heading = "Verifier options",
+        value_name = "VERIFIERS"
+    )]
+    pub verifier_fallback: Vec<VerifierArgs>,
+
     /// Gas price for legacy transactions, or max fee per gas for EIP1559 transactions, either
     /// specified in wei, or as a string with a unit type.
     ///
@@ -198,6 +271,17 @@ pub struct ScriptArgs {
     )]
     pub with_gas_price: Option<U256>,
 
+    /// Aborts the broadcast before the fees paid for the transactions sent to a chain could
+    /// exceed this amount, either specified in wei, or as a string with a unit type.
+    ///
+    /// Examples: 1ether, 0.05ether
+    #[arg(
+        long,
+        value_parser = foundry_cli::utils::parse_ether_value,
+        value_name = "VALUE"
+    )]
+    pub max_total_fee: Option<U256>,
+
     /// Timeout to use for broadcasting transactions.
     #[arg(long, env = "ETH_TIMEOUT")]
     pub timeout: Option<u64>,
@@ -216,6 +300,9 @@ pub struct ScriptArgs {
 
     #[command(flatten)]
     pub retry: RetryArgs,
+
+    #[command(flatten)]
+    pub user_operation: UserOperationArgs,
 }
 
 impl ScriptArgs {
@@ -300,6 +387,13 @@ pub async fn run_script(self) -> Result<()> {
             pre_simulation.fill_metadata().await?.bundle().await?
         };
 
+        if let Some(safe) = bundled.args.safe {
+            return match bundled.args.safe_tx_service.clone() {
+                Some(url) => bundled.propose_safe_transaction(safe, &url).await,
+                None => bundled.save_safe_batches(safe),
+            };
+        }
+
         // Exit early in case user didn't provide any broadcast/verify related flags.
         if !bundled.args.should_broadcast() {
             if !shell::is_json() {
@@ -319,7 +413,11 @@ pub async fn run_script(self) -> Result<()> {
         }
 
         // Wait for pending txes and broadcast others.
-        let broadcasted = bundled.wait_for_pending().await?.broadcast().await?;
+        let broadcasted = if bundled.args.user_operation.bundler_url.is_some() {
+            bundled.broadcast_user_operations().await?
+        } else {
+            bundled.wait_for_pending().await?.broadcast().await?
+        };
 
         if broadcasted.args.verify {
             broadcasted.verify().await?;
@@ -650,6 +748,7 @@ async fn _get_runner(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use forge_verify::provider::VerificationProviderType;
     use foundry_config::{NamedChain, UnresolvedEnvVarError};
     use std::fs;
     use tempfile::tempdir;
@@ -719,6 +818,52 @@ fn can_parse_verifier_url() {
         );
     }
 
+    #[test]
+    fn can_parse_fee_options() {
+        let args = ScriptArgs::parse_from([
+            "foundry-cli",
+            "script",
+            "script/Test.s.sol:TestScript",
+            "--broadcast",
+            "--fee-strategy",
+            "fast",
+            "--bump-on-timeout",
+            "--max-total-fee",
+            "0.5ether",
+        ]);
+        assert_eq!(args.fee_strategy, FeeStrategy::Fast);
+        assert_eq!(args.bump_on_timeout, Some(10));
+        assert_eq!(args.max_total_fee, Some(U256::from(5u64 * 10u64.pow(17))));
+
+        let args = ScriptArgs::parse_from(["foundry-cli", "script/Test.s.sol:TestScript"]);
+        assert_eq!(args.fee_strategy, FeeStrategy::Standard);
+        assert_eq!(args.bump_on_timeout, None);
+        assert_eq!(args.max_total_fee, None);
+    }
+
+    #[test]
+    fn can_parse_verifier_fallback() {
+        let args = ScriptArgs::parse_from([
+            "foundry-cli",
+            "script",
+            "script/Test.s.sol:TestScript",
+            "--broadcast",
+            "--verify",
+            "--verifier",
+            "etherscan",
+            "--verifier-fallback",
+            "sourcify,blockscout:https://eth.blockscout.com/api/",
+        ]);
+        assert_eq!(args.verifier_fallback.len(), 2);
+        assert_eq!(args.verifier_fallback[0].verifier, VerificationProviderType::Sourcify);
+        assert_eq!(args.verifier_fallback[0].verifier_url, None);
+        assert_eq!(args.verifier_fallback[1].verifier, VerificationProviderType::Blockscout);
+        assert_eq!(
+            args.verifier_fallback[1].verifier_url.as_deref(),
+            Some("https://eth.blockscout.com/api/")
+        );
+    }
+
     #[test]
     fn can_extract_code_size_limit() {
         let args = ScriptArgs::parse_from([
@@ -46,7 +46,42 @@ pub fn new(
     ) -> Result<Self> {
         let (path, sensitive_path) = Self::get_paths(config, sig, target, dry_run)?;
 
-        Ok(Self { deployments, path, sensitive_path, timestamp: now().as_secs() })
+        let mut sequence = Self { deployments, path, sensitive_path, timestamp: now().as_secs() };
+        sequence.set_chain_paths(config, sig, target, dry_run)?;
+        Ok(sequence)
+    }
+
+    /// Sets the paths of the deployments to the ones of single chain deployments, so that the
+    /// transactions of each chain are also saved separately.
+    pub fn set_chain_paths(
+        &mut self,
+        config: &Config,
+        sig: &str,
+        target: &ArtifactId,
+        dry_run: bool,
+    ) -> Result<()> {
+        for sequence in &mut self.deployments {
+            sequence.paths =
+                Some(ScriptSequence::get_paths(config, sig, target, sequence.chain, dry_run)?);
+        }
+        Ok(())
+    }
+
+    /// Returns the sequences of each chain, merging the deployments of chains that the script
+    /// switched back to after broadcasting on another chain.
+    fn chain_sequences(&self) -> Vec<ScriptSequence> {
+        let mut sequences: Vec<ScriptSequence> = Vec::new();
+        for deployment in &self.deployments {
+            match sequences.iter_mut().find(|sequence| sequence.chain == deployment.chain) {
+                Some(sequence) => {
+                    sequence.transactions.extend(deployment.transactions.iter().cloned());
+                    sequence.receipts.extend(deployment.receipts.iter().cloned());
+                    sequence.pending.extend(deployment.pending.iter().copied());
+                }
+                None => sequences.push(deployment.clone()),
+            }
+        }
+        sequences
     }
 
     /// Gets paths in the formats
@@ -105,6 +140,7 @@ pub fn load(config: &Config, sig: &str, target: &ArtifactId, dry_run: bool) -> R
 
         sequence.path = path;
         sequence.sensitive_path = sensitive_path;
+        sequence.set_chain_paths(config, sig, target, dry_run)?;
 
         Ok(sequence)
     }
@@ -145,6 +181,10 @@ pub fn save(&mut self, silent: bool, save_ts: bool) -> Result<()> {
             fs::copy(&self.sensitive_path, &file)?;
         }
 
+        for mut sequence in self.chain_sequences() {
+            sequence.save(true, save_ts)?;
+        }
+
         if !silent {
             if shell::is_json() {
                 sh_println!(
@@ -5,7 +5,7 @@
     B256,
 };
 use eyre::Result;
-use forge_script_sequence::ScriptSequence;
+use forge_script_sequence::{ScriptSequence, TransactionStatus};
 use foundry_cli::utils::init_progress;
 use foundry_common::{provider::RetryProvider, shell};
 use futures::StreamExt;
@@ -187,6 +187,7 @@ pub async fn wait_for_pending(
         deployment_sequence: &mut ScriptSequence,
         provider: &RetryProvider,
         timeout: u64,
+        replace_on_timeout: bool,
     ) -> Result<()> {
         if deployment_sequence.pending.is_empty() {
             return Ok(());
@@ -203,7 +204,7 @@ pub async fn wait_for_pending(
             .pending
             .clone()
             .into_iter()
-            .map(|tx| check_tx_status(provider, tx, timeout));
+            .map(|tx| check_tx_status(provider, tx, timeout, replace_on_timeout));
         let mut tasks = futures::stream::iter(futs).buffer_unordered(10);
 
         let mut errors: Vec<String> = vec![];
@@ -218,10 +219,21 @@ pub async fn wait_for_pending(
                 Ok(TxStatus::Dropped) => {
                     // We want to remove it from pending so it will be re-broadcast.
                     deployment_sequence.remove_pending(tx_hash);
+                    deployment_sequence.set_status(tx_hash, TransactionStatus::Unsent);
                     errors.push(format!("Transaction dropped from the mempool: {tx_hash:?}"));
 
                     seq_progress.inner.write().finish_tx_spinner(tx_hash);
                 }
+                Ok(TxStatus::TimedOut) => {
+                    // Sent again with higher fees.
+                    deployment_sequence.remove_pending(tx_hash);
+                    deployment_sequence.set_status(tx_hash, TransactionStatus::Unsent);
+                    sh_warn!(
+                        "Transaction {tx_hash:?} is still pending after {timeout} seconds, replacing it with higher fees."
+                    )?;
+
+                    seq_progress.inner.write().finish_tx_spinner(tx_hash);
+                }
                 Ok(TxStatus::Success(receipt)) => {
                     trace!(tx_hash=?tx_hash, "received tx receipt");
 
@@ -237,6 +249,8 @@ pub async fn wait_for_pending(
                     // un-resumable. Is this desirable on reverts?
                     warn!(tx_hash=?tx_hash, "Transaction Failure");
                     deployment_sequence.remove_pending(receipt.transaction_hash);
+                    deployment_sequence
+                        .set_status(receipt.transaction_hash, TransactionStatus::Reverted);
 
                     let msg = format_receipt(deployment_sequence.chain.into(), &receipt);
                     seq_progress.inner.write().finish_tx_spinner_with_msg(tx_hash, &msg)?;
@@ -9,6 +9,8 @@
 /// Convenience enum for internal signalling of transaction status
 pub enum TxStatus {
     Dropped,
+    /// Still pending after the timeout, to be replaced.
+    TimedOut,
     Success(AnyTransactionReceipt),
     Revert(AnyTransactionReceipt),
 }
@@ -25,10 +27,14 @@ fn from(receipt: AnyTransactionReceipt) -> Self {
 
 /// Checks the status of a txhash by first polling for a receipt, then for
 /// mempool inclusion. Returns the tx hash, and a status
+///
+/// A transaction still pending after the timeout is waited for again, unless `replace_on_timeout`
+/// is set.
 pub async fn check_tx_status(
     provider: &RetryProvider,
     hash: TxHash,
     timeout: u64,
+    replace_on_timeout: bool,
 ) -> (TxHash, Result<TxStatus, eyre::Report>) {
     let result = retry::Retry::new_no_delay(3)
         .run_async_until_break(|| async {
@@ -40,6 +46,11 @@ pub async fn check_tx_status(
                 Ok(receipt) => Ok(receipt.into()),
                 Err(e) => match provider.get_transaction_by_hash(hash).await {
                     Ok(_) => match e {
+                        PendingTransactionError::TxWatcher(WatchTxError::Timeout)
+                            if replace_on_timeout =>
+                        {
+                            Ok(TxStatus::TimedOut)
+                        }
                         PendingTransactionError::TxWatcher(WatchTxError::Timeout) => {
                             Err(RetryError::Continue(eyre!(
                                 "tx is still known to the node, waiting for receipt"
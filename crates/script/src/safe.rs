@@ -0,0 +1,403 @@
+use crate::broadcast::BundledState;
+use alloy_dyn_abi::DynSolValue;
+use alloy_primitives::{address, keccak256, Address, Bytes, TxKind, B256, U256};
+use alloy_signer::Signer;
+use eyre::{Context, Result};
+use forge_script_sequence::{now, sig_to_file_name, ScriptSequence};
+use foundry_common::{fs, shell};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// The MultiSendCallOnly v1.4.1 contract, which executes a batch of calls from a Safe.
+pub const MULTI_SEND_CALL_ONLY: Address = address!("9641d764fc13c8B624c04430C7356C1C7C8102e2");
+
+/// A batch of transactions in the format of the Safe Transaction Builder.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatch {
+    pub version: String,
+    pub chain_id: String,
+    /// Creation time in milliseconds.
+    pub created_at: u64,
+    pub meta: SafeBatchMeta,
+    pub transactions: Vec<SafeBatchTransaction>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatchMeta {
+    pub name: String,
+    pub description: String,
+    pub created_from_safe_address: Address,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatchTransaction {
+    pub to: Address,
+    /// The value in wei, as a decimal string.
+    pub value: String,
+    pub data: Bytes,
+    /// Always empty, as `data` holds the encoded call.
+    pub contract_method: Option<Value>,
+    pub contract_inputs_values: Option<Value>,
+}
+
+impl SafeBatch {
+    /// Creates the batch of the transactions of `sequence`, which must all be calls from `safe`.
+    pub fn from_sequence(sequence: &ScriptSequence, safe: Address, name: String) -> Result<Self> {
+        let transactions = sequence
+            .transactions
+            .iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                let tx = tx.tx();
+                let from = tx.from().unwrap_or_default();
+                if from != safe {
+                    eyre::bail!(
+                        "transaction #{} on chain {} is sent from {from} instead of the Safe {safe}; \
+                         broadcast it with `vm.startBroadcast({safe})`",
+                        i + 1,
+                        sequence.chain
+                    );
+                }
+                let Some(TxKind::Call(to)) = tx.to() else {
+                    eyre::bail!(
+                        "transaction #{} on chain {} deploys a contract with CREATE, which a Safe \
+                         can't do; deploy it with CREATE2 instead",
+                        i + 1,
+                        sequence.chain
+                    );
+                };
+                Ok(SafeBatchTransaction {
+                    to,
+                    value: tx.value().unwrap_or_default().to_string(),
+                    data: Bytes::copy_from_slice(tx.input().unwrap_or_default()),
+                    contract_method: None,
+                    contract_inputs_values: None,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            version: "1.0".to_string(),
+            chain_id: sequence.chain.to_string(),
+            created_at: now().as_millis() as u64,
+            meta: SafeBatchMeta {
+                name,
+                description: String::new(),
+                created_from_safe_address: safe,
+            },
+            transactions,
+        })
+    }
+}
+
+/// A transaction of a Safe, as signed by its owners.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SafeTransaction {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    /// `0` for a call, `1` for a delegatecall.
+    pub operation: u8,
+    pub nonce: u64,
+}
+
+impl SafeTransaction {
+    /// Returns the transaction executing the calls of `batch`, batched with
+    /// [`MULTI_SEND_CALL_ONLY`] if there are several.
+    pub fn from_batch(batch: &SafeBatch, nonce: u64) -> Result<Self> {
+        if let [tx] = batch.transactions.as_slice() {
+            return Ok(Self {
+                to: tx.to,
+                value: tx.value.parse()?,
+                data: tx.data.clone(),
+                operation: 0,
+                nonce,
+            });
+        }
+
+        let mut transactions = Vec::new();
+        for tx in &batch.transactions {
+            let value: U256 = tx.value.parse()?;
+            transactions.push(0u8);
+            transactions.extend_from_slice(tx.to.as_slice());
+            transactions.extend_from_slice(&value.to_be_bytes::<32>());
+            transactions.extend_from_slice(&U256::from(tx.data.len()).to_be_bytes::<32>());
+            transactions.extend_from_slice(&tx.data);
+        }
+        let mut data = keccak256("multiSend(bytes)")[..4].to_vec();
+        data.extend(DynSolValue::Tuple(vec![DynSolValue::Bytes(transactions)]).abi_encode_params());
+        Ok(Self {
+            to: MULTI_SEND_CALL_ONLY,
+            value: U256::ZERO,
+            data: data.into(),
+            operation: 1,
+            nonce,
+        })
+    }
+
+    /// Returns the EIP-712 hash of the transaction, as computed by `Safe.getTransactionHash`.
+    ///
+    /// The gas refund parameters are not used, so they are all zero.
+    pub fn hash(&self, safe: Address, chain_id: u64) -> B256 {
+        let domain_separator = keccak256(
+            DynSolValue::Tuple(vec![
+                DynSolValue::FixedBytes(
+                    keccak256("EIP712Domain(uint256 chainId,address verifyingContract)"),
+                    32,
+                ),
+                DynSolValue::Uint(U256::from(chain_id), 256),
+                DynSolValue::Address(safe),
+            ])
+            .abi_encode_params(),
+        );
+        let struct_hash = keccak256(
+            DynSolValue::Tuple(vec![
+                DynSolValue::FixedBytes(
+                    keccak256(
+                        "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)",
+                    ),
+                    32,
+                ),
+                DynSolValue::Address(self.to),
+                DynSolValue::Uint(self.value, 256),
+                DynSolValue::FixedBytes(keccak256(&self.data), 32),
+                DynSolValue::Uint(U256::from(self.operation), 8),
+                DynSolValue::Uint(U256::ZERO, 256),
+                DynSolValue::Uint(U256::ZERO, 256),
+                DynSolValue::Uint(U256::ZERO, 256),
+                DynSolValue::Address(Address::ZERO),
+                DynSolValue::Address(Address::ZERO),
+                DynSolValue::Uint(U256::from(self.nonce), 256),
+            ])
+            .abi_encode_params(),
+        );
+
+        let mut digest = [0x19, 0x01].to_vec();
+        digest.extend_from_slice(domain_separator.as_slice());
+        digest.extend_from_slice(struct_hash.as_slice());
+        keccak256(digest)
+    }
+}
+
+impl BundledState {
+    /// Saves the transactions of each chain as a Safe Transaction Builder batch next to the
+    /// broadcast files, instead of broadcasting them.
+    pub fn save_safe_batches(&self, safe: Address) -> Result<()> {
+        let config = &self.script_config.config;
+        let target = &self.build_data.build_data.target;
+        let batches = self.safe_batches(safe)?;
+
+        let mut paths = Vec::with_capacity(batches.len());
+        for (chain, batch) in &batches {
+            let (broadcast_path, _) =
+                ScriptSequence::get_paths(config, &self.args.sig, target, *chain, false)?;
+            let path = broadcast_path
+                .with_file_name(format!("{}-safe.json", sig_to_file_name(&self.args.sig)));
+            fs::write_pretty_json_file(&path, batch)?;
+            paths.push(path);
+        }
+
+        if shell::is_json() {
+            sh_println!(
+                "{}",
+                json!({
+                    "status": "success",
+                    "safe": safe,
+                    "batches": paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>(),
+                })
+            )?;
+        } else {
+            for ((chain, batch), path) in batches.iter().zip(&paths) {
+                sh_println!(
+                    "\nSafe batch of {} transactions for chain {chain} saved to: {}",
+                    batch.transactions.len(),
+                    path.display()
+                )?;
+            }
+            sh_println!(
+                "\nImport the batch in the Safe Transaction Builder app of {safe} to propose it, \
+                 or propose it with `--safe-tx-service`."
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Proposes the transactions as a single Safe transaction to the Safe Transaction Service at
+    /// `url`, signed by the loaded wallet, instead of broadcasting them.
+    pub async fn propose_safe_transaction(self, safe: Address, url: &str) -> Result<()> {
+        let mut batches = self.safe_batches(safe)?;
+        if batches.len() != 1 {
+            eyre::bail!(
+                "the Safe Transaction Service proposes transactions of a single chain, but the script sends transactions on {} chains",
+                batches.len()
+            );
+        }
+        let (chain, batch) = batches.remove(0);
+
+        let signers = self.script_wallets.into_multi_wallet().into_signers()?;
+        if signers.len() != 1 {
+            eyre::bail!(
+                "Safe transactions are signed by an owner of the Safe, which must be the only loaded wallet; got {} wallets",
+                signers.len()
+            );
+        }
+        let (sender, signer) = signers.into_iter().next().expect("one signer");
+
+        let url = url.trim_end_matches('/');
+        let client = reqwest::Client::new();
+        let info: Value = client
+            .get(format!("{url}/api/v1/safes/{safe}/"))
+            .send()
+            .await?
+            .error_for_status()
+            .wrap_err_with(|| format!("Failed to get the Safe {safe} from {url}"))?
+            .json()
+            .await?;
+        let nonce = match &info["nonce"] {
+            Value::Number(nonce) => nonce.as_u64(),
+            Value::String(nonce) => nonce.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| eyre::eyre!("invalid nonce of the Safe {safe}: {}", info["nonce"]))?;
+
+        let tx = SafeTransaction::from_batch(&batch, nonce)?;
+        let hash = tx.hash(safe, chain);
+        let signature = signer.sign_hash(&hash).await?;
+
+        let proposal = json!({
+            "to": tx.to,
+            "value": tx.value.to_string(),
+            "data": (!tx.data.is_empty()).then_some(&tx.data),
+            "operation": tx.operation,
+            "safeTxGas": "0",
+            "baseGas": "0",
+            "gasPrice": "0",
+            "gasToken": Address::ZERO,
+            "refundReceiver": Address::ZERO,
+            "nonce": tx.nonce,
+            "contractTransactionHash": hash,
+            "sender": sender,
+            "signature": Bytes::from(signature.as_bytes()),
+            "origin": "forge script",
+        });
+        let response = client
+            .post(format!("{url}/api/v1/safes/{safe}/multisig-transactions/"))
+            .json(&proposal)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            eyre::bail!("Failed to propose the Safe transaction {hash} ({status}): {body}");
+        }
+
+        if shell::is_json() {
+            sh_println!(
+                "{}",
+                json!({
+                    "status": "success",
+                    "safe": safe,
+                    "chain": chain,
+                    "safeTxHash": hash,
+                    "nonce": nonce,
+                })
+            )?;
+        } else {
+            sh_println!(
+                "\nProposed Safe transaction {hash} with {} transactions for chain {chain} at nonce {nonce}",
+                batch.transactions.len()
+            )?;
+            sh_println!("\nThe other owners of {safe} can confirm it in the Safe app.")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the transactions of each chain as a Safe Transaction Builder batch.
+    ///
+    /// Sequences of the same chain are merged, as a batch is proposed to a single Safe.
+    fn safe_batches(&self, safe: Address) -> Result<Vec<(u64, SafeBatch)>> {
+        let target = &self.build_data.build_data.target;
+        let name = format!("{} {}", target.name, self.args.sig);
+
+        let mut batches: Vec<(u64, SafeBatch)> = Vec::new();
+        for sequence in self.sequence.sequences() {
+            let batch = SafeBatch::from_sequence(sequence, safe, name.clone())?;
+            match batches.iter_mut().find(|(chain, _)| *chain == sequence.chain) {
+                Some((_, existing)) => existing.transactions.extend(batch.transactions),
+                None => batches.push((sequence.chain, batch)),
+            }
+        }
+        Ok(batches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, hex};
+
+    fn batch(transactions: Vec<(Address, u64, Bytes)>) -> SafeBatch {
+        SafeBatch {
+            version: "1.0".to_string(),
+            chain_id: "1".to_string(),
+            created_at: 0,
+            meta: SafeBatchMeta {
+                name: String::new(),
+                description: String::new(),
+                created_from_safe_address: Address::ZERO,
+            },
+            transactions: transactions
+                .into_iter()
+                .map(|(to, value, data)| SafeBatchTransaction {
+                    to,
+                    value: value.to_string(),
+                    data,
+                    contract_method: None,
+                    contract_inputs_values: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn hashes_safe_transaction() {
+        // computed with the EIP-712 encoding of `Safe.getTransactionHash`
+        let safe = Address::repeat_byte(0x11);
+        let call = (Address::repeat_byte(0x22), 1, Bytes::from_static(&hex!("deadbeef")));
+        let tx = SafeTransaction::from_batch(&batch(vec![call]), 7).unwrap();
+        assert_eq!(tx.operation, 0);
+        assert_eq!(
+            tx.hash(safe, 1),
+            b256!("7dfeb383629eefa0dabf8e2506d1e90fc432c9c0bc378addd417833edabced45")
+        );
+    }
+
+    #[test]
+    fn batches_calls_with_multi_send() {
+        let set_number = |n: u8| {
+            let mut data = hex!("3fb5c1cb").to_vec();
+            data.extend_from_slice(&U256::from(n).to_be_bytes::<32>());
+            Bytes::from(data)
+        };
+        let calls = vec![
+            (address!("000000000000000000000000000000000000beef"), 1, set_number(1)),
+            (address!("000000000000000000000000000000000000cafe"), 0, set_number(2)),
+        ];
+        let tx = SafeTransaction::from_batch(&batch(calls), 7).unwrap();
+        assert_eq!(tx.to, MULTI_SEND_CALL_ONLY);
+        assert_eq!(tx.operation, 1);
+        assert_eq!(tx.value, U256::ZERO);
+        assert_eq!(tx.data[..4], hex!("8d80ff0a"));
+        // selector, offset, length and two calls of 1 + 20 + 32 + 32 + 36 bytes, padded
+        assert_eq!(tx.data.len(), 4 + 32 + 32 + 256);
+        assert_eq!(
+            tx.hash(Address::repeat_byte(0x11), 1),
+            b256!("44d5edd748ab654826ab6e40ffa64f22fab1132c41808e9a6ded84a98cba6478")
+        );
+    }
+}
@@ -86,6 +86,7 @@ pub fn update_paths_to_broadcasted(
             Self::Multi(sequence) => {
                 (sequence.path, sequence.sensitive_path) =
                     MultiChainSequence::get_paths(config, sig, target, false)?;
+                sequence.set_chain_paths(config, sig, target, false)?;
             }
         };
 
@@ -6,19 +6,24 @@
     broadcast::{estimate_gas, BundledState},
     build::LinkedBuildData,
     execute::{ExecutionArtifacts, ExecutionData},
+    forecast::BlockForecast,
     sequence::get_commit_hash,
     ScriptArgs, ScriptConfig, ScriptResult,
 };
 use alloy_chains::NamedChain;
 use alloy_network::TransactionBuilder;
 use alloy_primitives::{map::HashMap, utils::format_units, Address, Bytes, TxKind, U256};
+use alloy_provider::Provider;
 use dialoguer::Confirm;
 use eyre::{Context, Result};
 use forge_script_sequence::{ScriptSequence, TransactionWithMetadata};
 use foundry_cheatcodes::Wallets;
 use foundry_cli::utils::{has_different_gas_calc, now};
-use foundry_common::{shell, ContractData};
-use foundry_evm::traces::{decode_trace_arena, render_trace_arena};
+use foundry_common::{provider::try_get_http_provider, shell, ContractData};
+use foundry_evm::{
+    revm::primitives::SpecId,
+    traces::{decode_trace_arena, render_trace_arena},
+};
 use futures::future::{join_all, try_join_all};
 use parking_lot::RwLock;
 use std::{
@@ -26,6 +31,39 @@
     sync::Arc,
 };
 
+/// Prints the predicted addresses of the contracts deployed with CREATE2, and fails if one of them
+/// already holds code, in which case its deployment would revert.
+async fn check_create2_deployments(transactions: &VecDeque<TransactionWithMetadata>) -> Result<()> {
+    let deployments = transactions
+        .iter()
+        .filter(|tx| tx.is_create2())
+        .filter_map(|tx| Some((tx, tx.contract_address?)))
+        .collect::<Vec<_>>();
+    if deployments.is_empty() {
+        return Ok(());
+    }
+
+    if !shell::is_json() {
+        sh_println!("\n## Predicted CREATE2 addresses")?;
+    }
+    for (tx, address) in deployments {
+        let name = tx.contract_name.as_deref().unwrap_or("<unknown>");
+        if !shell::is_json() {
+            sh_println!("{name}: {address}")?;
+        }
+
+        let provider = try_get_http_provider(&tx.rpc)?;
+        if !provider.get_code_at(address).await?.is_empty() {
+            eyre::bail!(
+                "{name} is already deployed at {address} on {}. Change the salt of its CREATE2 deployment, or skip it when the address has code.",
+                tx.rpc
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Same as [ExecutedState](crate::execute::ExecutedState), but also contains [ExecutionArtifacts]
 /// which are obtained from [ScriptResult].
 ///
@@ -78,6 +116,8 @@ pub async fn fill_metadata(self) -> Result<FilledTransactionsState> {
             })
             .collect::<Result<VecDeque<_>>>()?;
 
+        check_create2_deployments(&transactions).await?;
+
         if self.args.skip_simulation {
             sh_println!("\nSKIPPING ON CHAIN SIMULATION.")?;
         } else {
@@ -234,7 +274,31 @@ async fn build_runners(&self) -> Result<Vec<(String, ScriptRunner)>> {
         let futs = rpcs.into_iter().map(|rpc| async move {
             let mut script_config = self.script_config.clone();
             script_config.evm_opts.fork_url = Some(rpc.clone());
-            let runner = script_config.get_runner().await?;
+            let mut runner = script_config.get_runner().await?;
+
+            // Simulate against the environment of the target block instead of the latest one.
+            if let Some(block) = self.args.simulate_block {
+                let provider = try_get_http_provider(&rpc)?;
+                let forecast = BlockForecast::fetch(&provider, block).await?;
+                if !shell::is_json() {
+                    sh_println!(
+                        "Simulating against block {} (base fee: {} gwei, timestamp: {}) on {rpc}.",
+                        forecast.number,
+                        forecast
+                            .base_fee
+                            .and_then(|fee| format_units(fee, 9).ok())
+                            .unwrap_or_else(|| "N/A".to_string())
+                            .trim_end_matches('0')
+                            .trim_end_matches('.'),
+                        forecast.timestamp,
+                    )?;
+                }
+                forecast.apply(
+                    &mut runner.executor.env_mut().block,
+                    script_config.config.evm_spec_id() >= SpecId::PRAGUE,
+                );
+            }
+
             Ok((rpc.clone(), runner))
         });
         try_join_all(futs).await
@@ -421,8 +485,8 @@ fn create_sequence(
         chain: u64,
         transactions: VecDeque<TransactionWithMetadata>,
     ) -> Result<ScriptSequence> {
-        // Paths are set to None for multi-chain sequences parts, because they don't need to be
-        // saved to a separate file.
+        // Paths of multi-chain sequences parts are set by [`MultiChainSequence`], which saves the
+        // parts of the same chain together.
         let paths = if multi {
             None
         } else {
@@ -0,0 +1,392 @@
+//! Broadcasting of script transactions as ERC-4337 UserOperations.
+
+use crate::{broadcast::BundledState, verify::BroadcastedState};
+use alloy_dyn_abi::DynSolValue;
+use alloy_network::AnyTransactionReceipt;
+use alloy_primitives::{address, hex, keccak256, Address, Bytes, TxKind, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::TransactionRequest;
+use alloy_serde::WithOtherFields;
+use alloy_signer::Signer;
+use clap::Parser;
+use eyre::{Context, Result};
+use forge_script_sequence::TransactionStatus;
+use foundry_common::{
+    provider::{try_get_http_provider, RetryProvider},
+    shell,
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// The address of the v0.7 EntryPoint.
+pub const ENTRY_POINT_V07: Address = address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// A signature of the right length for estimating the gas of a UserOperation before signing it.
+const DUMMY_SIGNATURE: [u8; 65] = hex!("fffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c");
+
+/// CLI arguments for broadcasting script transactions as ERC-4337 UserOperations.
+#[derive(Clone, Debug, Default, Parser)]
+#[command(next_help_heading = "ERC-4337 options")]
+pub struct UserOperationArgs {
+    /// Broadcasts the transactions as ERC-4337 UserOperations through the given bundler RPC, when
+    /// used with `--broadcast`.
+    ///
+    /// The transactions must be sent from the smart account, e.g. with
+    /// `vm.startBroadcast(account)`. The account must implement `execute(address,uint256,bytes)`
+    /// and accept signatures of its owner, which must be the only loaded wallet.
+    #[arg(long, value_name = "URL")]
+    pub bundler_url: Option<String>,
+
+    /// The EntryPoint to send the UserOperations to.
+    ///
+    /// Defaults to the v0.7 EntryPoint.
+    #[arg(long, value_name = "ADDRESS", requires = "bundler_url")]
+    pub entry_point: Option<Address>,
+
+    /// The paymaster sponsoring the UserOperations.
+    #[arg(long, value_name = "ADDRESS", requires = "bundler_url")]
+    pub paymaster: Option<Address>,
+
+    /// The data passed to the paymaster.
+    #[arg(long, value_name = "HEX", requires = "paymaster")]
+    pub paymaster_data: Option<Bytes>,
+}
+
+/// A v0.7 UserOperation, in the format of the bundler RPC.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// Returns the hash of the UserOperation signed by the owner of the account, as computed by
+    /// `EntryPoint.getUserOpHash`.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let paymaster_and_data = self
+            .paymaster
+            .map(|paymaster| {
+                let mut data = paymaster.to_vec();
+                data.extend_from_slice(&u128_bytes(self.paymaster_verification_gas_limit));
+                data.extend_from_slice(&u128_bytes(self.paymaster_post_op_gas_limit));
+                if let Some(paymaster_data) = &self.paymaster_data {
+                    data.extend_from_slice(paymaster_data);
+                }
+                data
+            })
+            .unwrap_or_default();
+
+        let packed = DynSolValue::Tuple(vec![
+            DynSolValue::Address(self.sender),
+            DynSolValue::Uint(self.nonce, 256),
+            // The account is expected to be deployed, so the init code is empty.
+            DynSolValue::FixedBytes(keccak256(b""), 32),
+            DynSolValue::FixedBytes(keccak256(&self.call_data), 32),
+            DynSolValue::FixedBytes(
+                pack_u128(self.verification_gas_limit, self.call_gas_limit),
+                32,
+            ),
+            DynSolValue::Uint(self.pre_verification_gas, 256),
+            DynSolValue::FixedBytes(
+                pack_u128(self.max_priority_fee_per_gas, self.max_fee_per_gas),
+                32,
+            ),
+            DynSolValue::FixedBytes(keccak256(paymaster_and_data), 32),
+        ])
+        .abi_encode_params();
+
+        keccak256(
+            DynSolValue::Tuple(vec![
+                DynSolValue::FixedBytes(keccak256(packed), 32),
+                DynSolValue::Address(entry_point),
+                DynSolValue::Uint(U256::from(chain_id), 256),
+            ])
+            .abi_encode_params(),
+        )
+    }
+}
+
+/// Gas limits returned by `eth_estimateUserOperationGas`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserOperationGas {
+    pre_verification_gas: U256,
+    verification_gas_limit: U256,
+    call_gas_limit: U256,
+    #[serde(default)]
+    paymaster_verification_gas_limit: Option<U256>,
+    #[serde(default)]
+    paymaster_post_op_gas_limit: Option<U256>,
+}
+
+/// Receipt returned by `eth_getUserOperationReceipt`.
+#[derive(Clone, Debug, Deserialize)]
+struct UserOperationReceipt {
+    success: bool,
+    /// The receipt of the transaction that included the UserOperation.
+    receipt: AnyTransactionReceipt,
+}
+
+/// Returns the low 128 bits of `value` as big-endian bytes.
+fn u128_bytes(value: Option<U256>) -> [u8; 16] {
+    let bytes = value.unwrap_or_default().to_be_bytes::<32>();
+    bytes[16..].try_into().expect("16 bytes")
+}
+
+/// Packs two 128 bit values into a word, as in the gas fields of a packed UserOperation.
+fn pack_u128(high: U256, low: U256) -> B256 {
+    let mut word = [0; 32];
+    word[..16].copy_from_slice(&u128_bytes(Some(high)));
+    word[16..].copy_from_slice(&u128_bytes(Some(low)));
+    B256::from(word)
+}
+
+/// Encodes a call of `execute(address,uint256,bytes)` on the smart account.
+fn execute_call_data(to: Address, value: U256, data: &[u8]) -> Bytes {
+    let mut call_data = keccak256("execute(address,uint256,bytes)")[..4].to_vec();
+    call_data.extend(
+        DynSolValue::Tuple(vec![
+            DynSolValue::Address(to),
+            DynSolValue::Uint(value, 256),
+            DynSolValue::Bytes(data.to_vec()),
+        ])
+        .abi_encode_params(),
+    );
+    call_data.into()
+}
+
+/// Returns the next nonce of `sender` from `EntryPoint.getNonce(sender, 0)`.
+async fn entry_point_nonce(
+    provider: &RetryProvider,
+    entry_point: Address,
+    sender: Address,
+) -> Result<U256> {
+    let mut input = keccak256("getNonce(address,uint192)")[..4].to_vec();
+    input.extend(
+        DynSolValue::Tuple(vec![DynSolValue::Address(sender), DynSolValue::Uint(U256::ZERO, 192)])
+            .abi_encode_params(),
+    );
+    let tx = WithOtherFields::new(
+        TransactionRequest::default().to(entry_point).input(Bytes::from(input).into()),
+    );
+    let output = provider.call(tx).await.wrap_err("Failed to get the EntryPoint nonce")?;
+    U256::try_from_be_slice(&output).ok_or_else(|| eyre::eyre!("invalid EntryPoint nonce {output}"))
+}
+
+/// Polls the bundler for the receipt of the UserOperation `hash`.
+async fn wait_for_receipt(
+    bundler: &RetryProvider,
+    hash: B256,
+    timeout: u64,
+) -> Result<UserOperationReceipt> {
+    let start = Instant::now();
+    loop {
+        let receipt: Option<UserOperationReceipt> =
+            bundler.raw_request("eth_getUserOperationReceipt".into(), (hash,)).await?;
+        if let Some(receipt) = receipt {
+            return Ok(receipt);
+        }
+        if start.elapsed() > Duration::from_secs(timeout) {
+            eyre::bail!("Timed out waiting for the receipt of UserOperation {hash}");
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+impl BundledState {
+    /// Broadcasts the transactions of all sequences as UserOperations, one at a time.
+    pub async fn broadcast_user_operations(mut self) -> Result<BroadcastedState> {
+        let args = self.args.user_operation.clone();
+        let bundler_url = args.bundler_url.as_deref().expect("bundler url is set");
+        let bundler = try_get_http_provider(bundler_url)?;
+        let entry_point = args.entry_point.unwrap_or(ENTRY_POINT_V07);
+        let timeout = self.script_config.config.transaction_timeout;
+
+        let signers = self.script_wallets.into_multi_wallet().into_signers()?;
+        if signers.len() != 1 {
+            eyre::bail!(
+                "UserOperations are signed by the owner of the smart account, which must be the only loaded wallet; got {} wallets",
+                signers.len()
+            );
+        }
+        let signer = signers.into_values().next().expect("one signer");
+
+        for i in 0..self.sequence.sequences().len() {
+            let sequence = &self.sequence.sequences()[i];
+            let provider = try_get_http_provider(sequence.rpc_url())?;
+            let chain_id = sequence.chain;
+            let unsent = sequence
+                .transactions
+                .iter()
+                .positions(|tx| tx.status == TransactionStatus::Unsent)
+                .collect::<Vec<_>>();
+
+            for index in unsent {
+                let tx = self.sequence.sequences()[i].transactions[index].tx().clone();
+                let sender = tx.from().expect("no sender");
+                let Some(TxKind::Call(to)) = tx.to() else {
+                    eyre::bail!(
+                        "Transaction #{} on chain {chain_id} deploys a contract with CREATE, which a smart account can't do; deploy it with CREATE2 instead",
+                        index + 1
+                    );
+                };
+
+                let fees = provider
+                    .estimate_eip1559_fees()
+                    .await
+                    .wrap_err("Failed to estimate EIP1559 fees")?;
+                let mut op = UserOperation {
+                    sender,
+                    nonce: entry_point_nonce(&provider, entry_point, sender).await?,
+                    call_data: execute_call_data(
+                        to,
+                        tx.value().unwrap_or_default(),
+                        tx.input().unwrap_or_default(),
+                    ),
+                    max_fee_per_gas: self
+                        .args
+                        .with_gas_price
+                        .unwrap_or(U256::from(fees.max_fee_per_gas)),
+                    max_priority_fee_per_gas: self
+                        .args
+                        .priority_gas_price
+                        .unwrap_or(U256::from(fees.max_priority_fee_per_gas)),
+                    paymaster: args.paymaster,
+                    paymaster_data: args
+                        .paymaster
+                        .map(|_| args.paymaster_data.clone().unwrap_or_default()),
+                    signature: DUMMY_SIGNATURE.into(),
+                    ..Default::default()
+                };
+
+                let gas: UserOperationGas = bundler
+                    .raw_request("eth_estimateUserOperationGas".into(), (&op, entry_point))
+                    .await
+                    .wrap_err("Failed to estimate UserOperation gas")?;
+                op.pre_verification_gas = gas.pre_verification_gas;
+                op.verification_gas_limit = gas.verification_gas_limit;
+                op.call_gas_limit = gas.call_gas_limit *
+                    U256::from(self.args.gas_estimate_multiplier) /
+                    U256::from(100);
+                if op.paymaster.is_some() {
+                    op.paymaster_verification_gas_limit = gas.paymaster_verification_gas_limit;
+                    op.paymaster_post_op_gas_limit = gas.paymaster_post_op_gas_limit;
+                }
+
+                let hash = op.hash(entry_point, chain_id);
+                op.signature = signer.sign_message(hash.as_slice()).await?.as_bytes().into();
+
+                let op_hash: B256 = bundler
+                    .raw_request("eth_sendUserOperation".into(), (&op, entry_point))
+                    .await
+                    .wrap_err("Failed to send UserOperation")?;
+                if !shell::is_json() {
+                    sh_println!("UserOperation {op_hash} sent from {sender}")?;
+                }
+
+                let receipt = wait_for_receipt(&bundler, op_hash, timeout).await?;
+                let sequence = &mut self.sequence.sequences_mut()[i];
+                let tx_hash = receipt.receipt.transaction_hash;
+                // UserOperations can share the transaction of a bundle.
+                if !sequence.receipts.iter().any(|r| r.transaction_hash == tx_hash) {
+                    sequence.receipts.push(receipt.receipt);
+                }
+                let tx = &mut sequence.transactions[index];
+                tx.hash = Some(tx_hash);
+                tx.status = if receipt.success {
+                    TransactionStatus::Mined
+                } else {
+                    TransactionStatus::Reverted
+                };
+
+                // Checkpoint save
+                self.sequence.save(true, false)?;
+
+                if !receipt.success {
+                    eyre::bail!("UserOperation {op_hash} reverted in transaction {tx_hash}");
+                }
+            }
+        }
+
+        let broadcasted = BroadcastedState {
+            args: self.args,
+            script_config: self.script_config,
+            build_data: self.build_data,
+            sequence: self.sequence,
+        };
+        broadcasted.save_deployments()?;
+
+        if !shell::is_json() {
+            sh_println!("\n\n==========================")?;
+            sh_println!("\nONCHAIN EXECUTION COMPLETE & SUCCESSFUL.")?;
+        }
+
+        Ok(broadcasted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn encodes_execute_call() {
+        let call_data =
+            execute_call_data(Address::repeat_byte(0x11), U256::from(1), &hex!("deadbeef"));
+        assert_eq!(call_data[..4], hex!("b61d27f6"));
+        assert_eq!(call_data.len(), 4 + 32 * 5);
+    }
+
+    #[test]
+    fn packs_gas_fields() {
+        let word = pack_u128(U256::from(1), U256::from(2));
+        assert_eq!(U256::from_be_bytes(word.0), U256::from(1) << 128 | U256::from(2));
+    }
+
+    #[test]
+    fn hashes_user_operation() {
+        // computed with the encoding of `EntryPoint.getUserOpHash` of the v0.7 EntryPoint
+        let mut op = UserOperation {
+            sender: Address::repeat_byte(0x11),
+            nonce: U256::from(1),
+            call_data: hex!("deadbeef").into(),
+            call_gas_limit: U256::from(100_000),
+            verification_gas_limit: U256::from(200_000),
+            pre_verification_gas: U256::from(50_000),
+            max_fee_per_gas: U256::from(2_000_000_000u64),
+            max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+            ..Default::default()
+        };
+        assert_eq!(
+            op.hash(ENTRY_POINT_V07, 1),
+            b256!("a6aa0e5796a65324090328b07a8514cd2a922d18a29bccd3ba781786686a9c7f")
+        );
+
+        op.paymaster = Some(Address::repeat_byte(0x22));
+        op.paymaster_verification_gas_limit = Some(U256::from(30_000));
+        op.paymaster_post_op_gas_limit = Some(U256::from(40_000));
+        op.paymaster_data = Some(hex!("cafe").into());
+        assert_eq!(
+            op.hash(ENTRY_POINT_V07, 1),
+            b256!("46cb1bfca7f0be9432bb819e3264abcc56c54971e37bbeaf1728a8ddfca06201")
+        );
+    }
+}
@@ -3,15 +3,28 @@
     sequence::{get_commit_hash, ScriptSequenceKind},
     ScriptArgs, ScriptConfig,
 };
-use alloy_primitives::{hex, Address};
+use alloy_dyn_abi::JsonAbiExt;
+use alloy_network::ReceiptResponse;
+use alloy_primitives::{b256, hex, Address, B256, U256};
+use alloy_provider::Provider;
+use clap::ValueEnum;
 use eyre::{eyre, Result};
-use forge_script_sequence::{AdditionalContract, ScriptSequence};
+use forge_script_sequence::{
+    now, AdditionalContract, Deployment, DeploymentVerification, ScriptSequence, TransactionStatus,
+    VerificationStatus,
+};
 use forge_verify::{provider::VerificationProviderType, RetryArgs, VerifierArgs, VerifyArgs};
 use foundry_cli::opts::{EtherscanOpts, ProjectPathOpts};
-use foundry_common::ContractsByArtifact;
-use foundry_compilers::{artifacts::EvmVersion, info::ContractInfo, Project};
+use foundry_common::{
+    abi::encode_args,
+    provider::{get_http_provider, RetryProvider},
+    ContractsByArtifact,
+};
+use foundry_compilers::{artifacts::EvmVersion, info::ContractInfo, ArtifactId, Project};
 use foundry_config::{Chain, Config};
 use semver::Version;
+use serde::Deserialize;
+use std::time::Duration;
 
 /// State after we have broadcasted the script.
 /// It is assumed that at this point [BroadcastedState::sequence] contains receipts for all
@@ -36,7 +49,13 @@ pub async fn verify(self) -> Result<()> {
         );
 
         for sequence in sequence.sequences_mut() {
-            verify_contracts(sequence, &script_config.config, verify.clone()).await?;
+            verify_contracts(
+                sequence,
+                &script_config.config,
+                verify.clone(),
+                &args.verifier_fallback,
+            )
+            .await?;
         }
 
         Ok(())
@@ -116,83 +135,148 @@ pub fn get_verify_args(
             // of the transaction
             if data.split_at(create2_offset).1.starts_with(bytecode) {
                 let constructor_args = data.split_at(create2_offset + bytecode.len()).1.to_vec();
+                return Some(self.verify_args(
+                    contract_address,
+                    artifact,
+                    constructor_args,
+                    libraries,
+                    evm_version,
+                ))
+            }
+        }
+        None
+    }
 
-                if artifact.source.extension().is_some_and(|e| e.to_str() == Some("vy")) {
-                    warn!("Skipping verification of Vyper contract: {}", artifact.name);
-                }
+    /// Generates the `VerifyArgs` of a contract whose creation code doesn't match any artifact,
+    /// from its name and the constructor arguments recorded in the broadcast.
+    pub fn get_verify_args_from_broadcast(
+        &self,
+        contract_address: Address,
+        contract_name: &str,
+        arguments: &[String],
+        libraries: &[String],
+        evm_version: EvmVersion,
+    ) -> Option<VerifyArgs> {
+        let (artifact, contract) = self
+            .known_contracts
+            .iter()
+            .find(|(artifact, _)| strip_profile(artifact) == contract_name)?;
+        let constructor_args = match contract.abi.constructor() {
+            Some(constructor) => constructor
+                .abi_encode_input(&encode_args(&constructor.inputs, arguments).ok()?)
+                .ok()?,
+            None => vec![],
+        };
+        Some(self.verify_args(contract_address, artifact, constructor_args, libraries, evm_version))
+    }
 
-                // Strip artifact profile from contract name when creating contract info.
-                let contract = ContractInfo {
-                    path: Some(artifact.source.to_string_lossy().to_string()),
-                    name: artifact
-                        .name
-                        .strip_suffix(&format!(".{}", &artifact.profile))
-                        .unwrap_or_else(|| &artifact.name)
-                        .to_string(),
-                };
-
-                // We strip the build metadadata information, since it can lead to
-                // etherscan not identifying it correctly. eg:
-                // `v0.8.10+commit.fc410830.Linux.gcc` != `v0.8.10+commit.fc410830`
-                let version = Version::new(
-                    artifact.version.major,
-                    artifact.version.minor,
-                    artifact.version.patch,
-                );
+    fn verify_args(
+        &self,
+        contract_address: Address,
+        artifact: &ArtifactId,
+        constructor_args: Vec<u8>,
+        libraries: &[String],
+        evm_version: EvmVersion,
+    ) -> VerifyArgs {
+        if artifact.source.extension().is_some_and(|e| e.to_str() == Some("vy")) {
+            warn!("Skipping verification of Vyper contract: {}", artifact.name);
+        }
 
-                let verify = VerifyArgs {
-                    address: contract_address,
-                    contract: Some(contract),
-                    compiler_version: Some(version.to_string()),
-                    constructor_args: Some(hex::encode(constructor_args)),
-                    constructor_args_path: None,
-                    num_of_optimizations: self.num_of_optimizations,
-                    etherscan: self.etherscan.clone(),
-                    rpc: Default::default(),
-                    flatten: false,
-                    force: false,
-                    skip_is_verified_check: true,
-                    watch: true,
-                    retry: self.retry,
-                    libraries: libraries.to_vec(),
-                    root: None,
-                    verifier: self.verifier.clone(),
-                    via_ir: self.via_ir,
-                    evm_version: Some(evm_version),
-                    show_standard_json_input: false,
-                    guess_constructor_args: false,
-                    compilation_profile: Some(artifact.profile.to_string()),
-                };
-
-                return Some(verify)
-            }
+        // Strip artifact profile from contract name when creating contract info.
+        let contract = ContractInfo {
+            path: Some(artifact.source.to_string_lossy().to_string()),
+            name: strip_profile(artifact).to_string(),
+        };
+
+        // We strip the build metadadata information, since it can lead to
+        // etherscan not identifying it correctly. eg:
+        // `v0.8.10+commit.fc410830.Linux.gcc` != `v0.8.10+commit.fc410830`
+        let version =
+            Version::new(artifact.version.major, artifact.version.minor, artifact.version.patch);
+
+        VerifyArgs {
+            address: contract_address,
+            contract: Some(contract),
+            compiler_version: Some(version.to_string()),
+            constructor_args: Some(hex::encode(constructor_args)),
+            constructor_args_path: None,
+            num_of_optimizations: self.num_of_optimizations,
+            etherscan: self.etherscan.clone(),
+            rpc: Default::default(),
+            flatten: false,
+            force: false,
+            skip_is_verified_check: true,
+            watch: true,
+            retry: self.retry,
+            libraries: libraries.to_vec(),
+            root: None,
+            verifier: self.verifier.clone(),
+            via_ir: self.via_ir,
+            evm_version: Some(evm_version),
+            show_standard_json_input: false,
+            guess_constructor_args: false,
+            compilation_profile: Some(artifact.profile.to_string()),
         }
-        None
     }
 }
 
+/// Returns the name of the artifact's contract, without the compilation profile suffix.
+fn strip_profile(artifact: &ArtifactId) -> &str {
+    artifact.name.strip_suffix(&format!(".{}", &artifact.profile)).unwrap_or(&artifact.name)
+}
+
+/// The storage slot holding the implementation of an ERC-1967 proxy.
+const IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// A contract created by the sequence, to verify.
+struct VerificationJob {
+    address: Address,
+    contract_name: Option<String>,
+    /// `None` if no artifact matches the contract.
+    args: Option<VerifyArgs>,
+    /// The implementation, if the contract is an ERC-1967 proxy.
+    implementation: Option<Address>,
+}
+
 /// Given the broadcast log, it matches transactions with receipts, and tries to verify any
 /// created contract on etherscan.
+///
+/// Proxies are verified after the other contracts, so that their implementation is verified
+/// first, and the results are written to the deployments of the sequence.
 async fn verify_contracts(
     sequence: &mut ScriptSequence,
     config: &Config,
     mut verify: VerifyBundle,
+    fallbacks: &[VerifierArgs],
 ) -> Result<()> {
     trace!(target: "script", "verifying {} contracts [{}]", verify.known_contracts.len(), sequence.chain);
 
     verify.set_chain(config, sequence.chain.into());
 
+    if sequence.transactions.is_empty() {
+        return Ok(())
+    }
+
     if verify.etherscan.has_key() || verify.verifier.verifier != VerificationProviderType::Etherscan
     {
+        let provider = get_http_provider(sequence.rpc_url());
+        wait_for_receipts(sequence, &provider, verify.retry).await?;
+
         trace!(target: "script", "prepare future verifications");
 
-        let mut future_verifications = Vec::with_capacity(sequence.receipts.len());
-        let mut unverifiable_contracts = vec![];
+        let mut jobs = Vec::with_capacity(sequence.receipts.len());
 
-        // Make sure the receipts have the right order first.
-        sequence.sort_receipts();
+        for tx in &sequence.transactions {
+            let Some(receipt) = tx.hash.and_then(|hash| {
+                sequence.receipts.iter_mut().find(|receipt| receipt.transaction_hash == hash)
+            }) else {
+                continue
+            };
+            if !receipt.status() {
+                continue
+            }
 
-        for (receipt, tx) in sequence.receipts.iter_mut().zip(sequence.transactions.iter()) {
             // create2 hash offset
             let mut offset = 0;
 
@@ -203,51 +287,116 @@ async fn verify_contracts(
 
             // Verify contract created directly from the transaction
             if let (Some(address), Some(data)) = (receipt.contract_address, tx.tx().input()) {
-                match verify.get_verify_args(
+                let args = verify
+                    .get_verify_args(address, offset, data, &sequence.libraries, config.evm_version)
+                    .or_else(|| {
+                        // Fall back to the constructor arguments recorded in the broadcast.
+                        verify.get_verify_args_from_broadcast(
+                            address,
+                            tx.contract_name.as_deref()?,
+                            tx.arguments.as_deref().unwrap_or_default(),
+                            &sequence.libraries,
+                            config.evm_version,
+                        )
+                    });
+                jobs.push(VerificationJob {
                     address,
-                    offset,
-                    data,
-                    &sequence.libraries,
-                    config.evm_version,
-                ) {
-                    Some(verify) => future_verifications.push(verify.run()),
-                    None => unverifiable_contracts.push(address),
-                };
+                    contract_name: tx.contract_name.clone(),
+                    args,
+                    implementation: None,
+                });
             }
 
             // Verify potential contracts created during the transaction execution
             for AdditionalContract { address, init_code, .. } in &tx.additional_contracts {
-                match verify.get_verify_args(
+                let args = verify.get_verify_args(
                     *address,
                     0,
                     init_code.as_ref(),
                     &sequence.libraries,
                     config.evm_version,
-                ) {
-                    Some(verify) => future_verifications.push(verify.run()),
-                    None => unverifiable_contracts.push(*address),
-                };
+                );
+                jobs.push(VerificationJob {
+                    address: *address,
+                    contract_name: None,
+                    args,
+                    implementation: None,
+                });
+            }
+        }
+
+        for job in &mut jobs {
+            let slot = provider
+                .get_storage_at(job.address, U256::from_be_bytes(IMPLEMENTATION_SLOT.0))
+                .await?;
+            let implementation = Address::from_word(B256::from(slot.to_be_bytes::<32>()));
+            if !implementation.is_zero() {
+                job.implementation = Some(implementation);
             }
         }
+        jobs.sort_by_key(|job| job.implementation.is_some());
+
+        let unverifiable_contracts =
+            jobs.iter().filter(|job| job.args.is_none()).map(|job| job.address).collect();
 
-        trace!(target: "script", "collected {} verification jobs and {} unverifiable contracts", future_verifications.len(), unverifiable_contracts.len());
+        trace!(target: "script", "collected {} verification jobs", jobs.len());
 
-        check_unverified(sequence, unverifiable_contracts, verify);
+        check_unverified(sequence, unverifiable_contracts, verify.clone());
 
-        let num_verifications = future_verifications.len();
+        let num_verifications = jobs.iter().filter(|job| job.args.is_some()).count();
         let mut num_of_successful_verifications = 0;
+        let mut results = Vec::with_capacity(jobs.len());
         sh_println!("##\nStart verification for ({num_verifications}) contracts")?;
-        for verification in future_verifications {
-            match verification.await {
-                Ok(_) => {
+        for job in &jobs {
+            let Some(args) = &job.args else {
+                results.push((job, VerificationStatus::Unverifiable, None));
+                continue
+            };
+            match verify_with_fallbacks(args, fallbacks, verify.retry).await {
+                Ok(verifier) => {
                     num_of_successful_verifications += 1;
+                    if let Some(implementation) = job.implementation {
+                        if verifier.verifier.is_etherscan() {
+                            if let Err(err) = mark_proxy(
+                                config,
+                                sequence.chain.into(),
+                                &verifier,
+                                job.address,
+                                implementation,
+                                verify.retry,
+                            )
+                            .await
+                            {
+                                sh_warn!("Failed to mark {} as a proxy: {err:#}", job.address)?;
+                            }
+                        }
+                    }
+                    results.push((job, VerificationStatus::Verified, Some(verifier)));
                 }
-                Err(err) => {
+                Err((err, verifier)) => {
                     sh_err!("Failed to verify contract: {err:#}")?;
+                    results.push((job, VerificationStatus::Failed, Some(verifier)));
                 }
             }
         }
 
+        let timestamp = now().as_secs();
+        for (job, status, verifier) in results {
+            let Some(contract_name) = &job.contract_name else { continue };
+            Deployment::update_verification(
+                &config.deployments,
+                sequence.chain,
+                contract_name,
+                job.address,
+                job.implementation,
+                DeploymentVerification {
+                    status,
+                    verifier: verifier.map(|verifier| verifier.verifier.to_string()),
+                    timestamp,
+                },
+            )?;
+        }
+
         if num_of_successful_verifications < num_verifications {
             return Err(eyre!("Not all ({num_of_successful_verifications} / {num_verifications}) contracts were verified!"))
         }
@@ -258,6 +407,158 @@ async fn verify_contracts(
     Ok(())
 }
 
+/// Polls the receipts of the broadcasted transactions that don't have one yet.
+async fn wait_for_receipts(
+    sequence: &mut ScriptSequence,
+    provider: &RetryProvider,
+    retry: RetryArgs,
+) -> Result<()> {
+    let hashes = sequence
+        .transactions
+        .iter()
+        .filter(|tx| tx.status != TransactionStatus::Skipped)
+        .filter_map(|tx| tx.hash)
+        .filter(|hash| sequence.receipts.iter().all(|receipt| receipt.transaction_hash != *hash))
+        .collect::<Vec<_>>();
+
+    for hash in hashes {
+        let receipt = retry
+            .into_retry()
+            .run_async(|| async {
+                provider
+                    .get_transaction_receipt(hash)
+                    .await?
+                    .ok_or_else(|| eyre!("transaction {hash} is not mined yet"))
+            })
+            .await?;
+        sequence.remove_pending(hash);
+        sequence.add_receipt(receipt);
+    }
+    sequence.sort_receipts();
+
+    Ok(())
+}
+
+/// Verifies the contract with the configured verifier, then with each fallback verifier with an
+/// exponential backoff, until one of them succeeds.
+///
+/// Returns the verifier that verified the contract, or the last error with the last verifier.
+async fn verify_with_fallbacks(
+    args: &VerifyArgs,
+    fallbacks: &[VerifierArgs],
+    retry: RetryArgs,
+) -> Result<VerifierArgs, (eyre::Report, VerifierArgs)> {
+    let mut delay = Duration::from_secs(retry.delay as u64);
+    let mut error = None;
+    for verifier in std::iter::once(&args.verifier).chain(fallbacks) {
+        if let Some(err) = &error {
+            let _ = sh_warn!(
+                "Failed to verify contract: {err:#}; retrying with {} in {} seconds",
+                verifier.verifier,
+                delay.as_secs()
+            );
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+        let mut args = args.clone();
+        args.verifier = verifier.clone();
+        match args.run().await {
+            Ok(()) => return Ok(verifier.clone()),
+            Err(err) => error = Some(err),
+        }
+    }
+    let last = fallbacks.last().unwrap_or(&args.verifier).clone();
+    Err((error.expect("at least one verification attempt"), last))
+}
+
+/// The response of the Etherscan API.
+#[derive(Deserialize)]
+struct EtherscanResponse {
+    status: String,
+    result: String,
+}
+
+/// Marks the verified `proxy` as a proxy of `implementation` on Etherscan, so that its page exposes
+/// the implementation's functions.
+async fn mark_proxy(
+    config: &Config,
+    chain: Chain,
+    verifier: &VerifierArgs,
+    proxy: Address,
+    implementation: Address,
+    retry: RetryArgs,
+) -> Result<()> {
+    let etherscan = config.get_etherscan_config_with_chain(Some(chain))?;
+    let api_url = verifier
+        .verifier_url
+        .as_deref()
+        .or_else(|| etherscan.as_ref().map(|c| c.api_url.as_str()))
+        .ok_or_else(|| eyre!("no Etherscan API URL configured for chain {chain}"))?
+        .trim_end_matches('/');
+    let key = verifier
+        .verifier_api_key
+        .as_deref()
+        .or_else(|| etherscan.as_ref().map(|c| c.key.as_str()))
+        .unwrap_or_default();
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(api_url)
+        .query(&[("module", "contract"), ("action", "verifyproxycontract"), ("apikey", key)])
+        .form(&[
+            ("address", proxy.to_string()),
+            ("expectedimplementation", implementation.to_string()),
+        ])
+        .send()
+        .await?
+        .text()
+        .await?;
+    let response: EtherscanResponse = serde_json::from_str(&response)?;
+    if response.status != "1" {
+        eyre::bail!("{}", response.result);
+    }
+    let guid = response.result;
+
+    retry
+        .into_retry()
+        .run_async(|| async {
+            let response = client
+                .get(api_url)
+                .query(&[
+                    ("module", "contract"),
+                    ("action", "checkproxyverification"),
+                    ("guid", guid.as_str()),
+                    ("apikey", key),
+                ])
+                .send()
+                .await?
+                .text()
+                .await?;
+            let response: EtherscanResponse = serde_json::from_str(&response)?;
+            if response.status != "1" {
+                eyre::bail!("{}", response.result);
+            }
+            Ok(())
+        })
+        .await?;
+
+    sh_println!("Marked {proxy} as a proxy of {implementation}")?;
+    Ok(())
+}
+
+/// Parses a `--verifier-fallback` value, given as `<PROVIDER>` or `<PROVIDER>:<URL>`.
+pub fn parse_verifier_fallback(s: &str) -> Result<VerifierArgs, String> {
+    let (verifier, verifier_url) = match s.split_once(':') {
+        Some((verifier, url)) => (verifier, Some(url.to_string())),
+        None => (s, None),
+    };
+    Ok(VerifierArgs {
+        verifier: VerificationProviderType::from_str(verifier, true)?,
+        verifier_api_key: None,
+        verifier_url,
+    })
+}
+
 fn check_unverified(
     sequence: &ScriptSequence,
     unverifiable_contracts: Vec<Address>,
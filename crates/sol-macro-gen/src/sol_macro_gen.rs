@@ -8,12 +8,17 @@
 //!
 //! It contains methods to read the json abi, generate rust bindings from the abi and ultimately
 //! write the bindings to a crate or modules.
+//!
+//! On top of the `sol!` expansion, the bindings include helpers to decode the custom errors of
+//! failed calls and to watch the events of a contract, see [`SolMacroGen::helpers`].
 
 use alloy_sol_macro_expander::expand::expand;
 use alloy_sol_macro_input::{SolInput, SolInputKind};
 use eyre::{Context, OptionExt, Result};
 use foundry_common::fs;
 use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use serde_json::Value;
 use std::{
     fmt::Write,
     path::{Path, PathBuf},
@@ -43,21 +48,124 @@ pub fn get_sol_input(&self) -> Result<SolInput> {
 
         Ok(sol_input)
     }
+
+    /// Returns the linked creation bytecode of the artifact, if any.
+    pub fn bytecode(&self) -> Result<Option<String>> {
+        let artifact: Value = fs::read_json_file(&self.path)?;
+        let bytecode = &artifact["bytecode"];
+        let bytecode =
+            bytecode["object"].as_str().or_else(|| bytecode.as_str()).unwrap_or_default();
+        let bytecode = bytecode.trim_start_matches("0x");
+        // unlinked bytecode can't be deployed without its libraries
+        if bytecode.is_empty() || bytecode.contains("__") {
+            return Ok(None);
+        }
+        Ok(Some(format!("0x{bytecode}")))
+    }
+
+    /// Returns the helpers generated for the contract on top of the `sol!` expansion, if any:
+    /// - `<Name>Errors::decode_error`, to decode the custom error of a failed contract call
+    /// - `<Name>Instance::<Event>_stream`, to watch the events of a deployed contract
+    ///
+    /// `module` is the path of the contract module generated by `sol!`.
+    pub fn helpers(&self, module: &TokenStream) -> Option<TokenStream> {
+        let expansion = self.expansion.as_ref()?.to_string();
+        let name = &self.name;
+        let mut helpers = TokenStream::new();
+
+        if expansion.contains(&format!("enum {name}Errors ")) {
+            let errors = format_ident!("{name}Errors");
+            helpers.extend(quote! {
+                impl #module::#errors {
+                    /// Decodes the revert data of a failed contract call into one of the custom
+                    /// errors of the contract.
+                    pub fn decode_error(error: &alloy::contract::Error) -> Option<Self> {
+                        error.as_decoded_interface_error::<Self>()
+                    }
+                }
+            });
+        }
+
+        let instance_start = expansion.find(&format!("struct {name}Instance "));
+        let events = instance_start.map(|i| event_filters(&expansion[i..])).unwrap_or_default();
+        if !events.is_empty() {
+            let instance = format_ident!("{name}Instance");
+            let streams = events.iter().map(|event| {
+                let ty = format_ident!("{event}");
+                let filter = format_ident!("{event}_filter");
+                let stream = format_ident!("{event}_stream");
+                let doc = format!(
+                    " Watches the `{event}` events emitted by the contract from now on.\n\n \
+                     Use [`alloy::contract::EventPoller::into_stream`] to get a stream of the \
+                     decoded events."
+                );
+                quote! {
+                    #[doc = #doc]
+                    pub async fn #stream(
+                        &self,
+                    ) -> alloy::transports::TransportResult<alloy::contract::EventPoller<#module::#ty>> {
+                        self.#filter().watch().await
+                    }
+                }
+            });
+            helpers.extend(quote! {
+                #[allow(non_snake_case)]
+                impl<P: alloy::providers::Provider<N>, N: alloy::network::Network>
+                    #module::#instance<P, N>
+                {
+                    #(#streams)*
+                }
+            });
+        }
+
+        (!helpers.is_empty()).then_some(helpers)
+    }
+}
+
+/// Returns the names of the events with a `<Event>_filter` method in a `sol!` expansion.
+fn event_filters(expansion: &str) -> Vec<String> {
+    let tokens = expansion.split_whitespace().collect::<Vec<_>>();
+    let mut events = Vec::new();
+    for window in tokens.windows(3) {
+        let [kw, ident, next] = window else { continue };
+        if *kw == "fn" && next.starts_with('(') {
+            if let Some(event) = ident.strip_suffix("_filter") {
+                if !event.is_empty() && !events.iter().any(|e| e == event) {
+                    events.push(event.to_string());
+                }
+            }
+        }
+    }
+    events
 }
 
 pub struct MultiSolMacroGen {
     pub artifacts_path: PathBuf,
     pub instances: Vec<SolMacroGen>,
+    /// Whether each contract is generated as a directory module, with the `sol!` expansion in
+    /// `<name>/mod.rs` and the helpers in `<name>/helpers.rs`.
+    pub module_per_contract: bool,
 }
 
 impl MultiSolMacroGen {
     pub fn new(artifacts_path: &Path, instances: Vec<SolMacroGen>) -> Self {
-        Self { artifacts_path: artifacts_path.to_path_buf(), instances }
+        Self { artifacts_path: artifacts_path.to_path_buf(), instances, module_per_contract: false }
+    }
+
+    /// Sets whether each contract is generated as a directory module.
+    pub fn with_module_per_contract(mut self, module_per_contract: bool) -> Self {
+        self.module_per_contract = module_per_contract;
+        self
     }
 
     pub fn populate_expansion(&mut self, bindings_path: &Path) -> Result<()> {
         for instance in &mut self.instances {
-            let path = bindings_path.join(format!("{}.rs", instance.name.to_lowercase()));
+            let name = instance.name.to_lowercase();
+            let path = if self.module_per_contract {
+                bindings_path.join(name).join("mod.rs")
+            } else {
+                bindings_path.join(format!("{name}.rs"))
+            };
             let expansion = fs::read_to_string(path).wrap_err("Failed to read file")?;
 
             let tokens = TokenStream::from_str(&expansion)
@@ -92,7 +200,16 @@ fn generate_binding(instance: &mut SolMacroGen, all_derives: bool) -> Result<()>
                     #[sol(rpc, alloy_sol_types = alloy::sol_types, alloy_contract = alloy::contract, all_derives = #all_derives)]
                 };
                 file.attrs.push(sol_attr);
-                expand(file).wrap_err("failed to expand")?
+                let tokens = expand(file.clone()).wrap_err("failed to expand")?;
+
+                // `deploy` helpers are only generated if the bytecode was read from the artifact
+                match instance.bytecode()? {
+                    Some(bytecode) if !tokens.to_string().contains("fn deploy_builder") => {
+                        file.attrs.push(syn::parse_quote!(#[sol(bytecode = #bytecode)]));
+                        expand(file).wrap_err("failed to expand")?
+                    }
+                    _ => tokens,
+                }
             }
             _ => unreachable!(),
         };
@@ -101,6 +218,52 @@ fn generate_binding(instance: &mut SolMacroGen, all_derives: bool) -> Result<()>
         Ok(())
     }
 
+    /// Returns the files of the bindings of a contract, relative to the bindings source
+    /// directory, with their unformatted contents.
+    fn instance_files(&self, instance: &SolMacroGen) -> Result<Vec<(PathBuf, String)>> {
+        let name = instance.name.to_lowercase();
+        let expansion = instance
+            .expansion
+            .as_ref()
+            .ok_or_eyre(format!("TokenStream for {name} does not exist"))?;
+        let ident = proc_macro2::Ident::new(&instance.name, Span::call_site());
+
+        if !self.module_per_contract {
+            let helpers = instance.helpers(&quote!(#ident));
+            return Ok(vec![(
+                PathBuf::from(format!("{name}.rs")),
+                quote!(#expansion #helpers).to_string(),
+            )])
+        }
+
+        let dir = PathBuf::from(&name);
+        match instance.helpers(&quote!(super::#ident)) {
+            Some(helpers) => Ok(vec![
+                (dir.join("mod.rs"), quote!(#expansion mod helpers;).to_string()),
+                (dir.join("helpers.rs"), helpers.to_string()),
+            ]),
+            None => Ok(vec![(dir.join("mod.rs"), expansion.to_string())]),
+        }
+    }
+
+    /// Writes the formatted bindings of a contract to `dir`.
+    fn write_instance(&self, instance: &SolMacroGen, dir: &Path) -> Result<()> {
+        for (path, contents) in self.instance_files(instance)? {
+            let path = dir.join(path);
+            let file = syn::parse_file(&contents).wrap_err_with(|| {
+                format!(
+                    "failed to parse generated tokens as an AST for {};\nthis is likely a bug",
+                    path.display()
+                )
+            })?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, prettyplease::unparse(&file)).wrap_err("Failed to write file")?;
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn write_to_crate(
         &mut self,
@@ -151,17 +314,13 @@ pub fn write_to_crate(
             format!("failed to parse generated tokens as an AST for {name};\nthis is likely a bug")
         };
         for instance in &self.instances {
-            let contents = instance.expansion.as_ref().unwrap();
-
             let name = instance.name.to_lowercase();
-            let path = src.join(format!("{name}.rs"));
-            let file = syn::parse2(contents.clone())
-                .wrap_err_with(|| parse_error(&format!("{}:{}", path.display(), name)))?;
-            let contents = prettyplease::unparse(&file);
             if single_file {
-                write!(&mut lib_contents, "{contents}")?;
+                for (_, contents) in self.instance_files(instance)? {
+                    write!(&mut lib_contents, "{contents}")?;
+                }
             } else {
-                fs::write(path, contents).wrap_err("failed to write to file")?;
+                self.write_instance(instance, &src)?;
                 write_mod_name(&mut lib_contents, &name)?;
             }
         }
@@ -197,19 +356,12 @@ pub fn write_to_module(
             if !single_file {
                 // Module
                 write_mod_name(&mut mod_contents, &name)?;
-                let mut contents = String::new();
-
-                write!(contents, "{}", instance.expansion.as_ref().unwrap())?;
-                let file = syn::parse_file(&contents)?;
-
-                let contents = prettyplease::unparse(&file);
-                fs::write(bindings_path.join(format!("{name}.rs")), contents)
-                    .wrap_err("Failed to write file")?;
+                self.write_instance(instance, bindings_path)?;
             } else {
                 // Single File
-                let mut contents = String::new();
-                write!(contents, "{}\n\n", instance.expansion.as_ref().unwrap())?;
-                write!(mod_contents, "{contents}")?;
+                for (_, contents) in self.instance_files(instance)? {
+                    write!(mod_contents, "{contents}\n\n")?;
+                }
             }
         }
 
@@ -254,20 +406,12 @@ pub fn check_consistency(
             "#
         )?;
         if !single_file {
+            let src = if is_mod { crate_path.to_path_buf() } else { crate_path.join("src") };
             for instance in &self.instances {
                 let name = instance.name.to_lowercase();
-                let path = if is_mod {
-                    crate_path.join(format!("{name}.rs"))
-                } else {
-                    crate_path.join(format!("src/{name}.rs"))
-                };
-                let tokens = instance
-                    .expansion
-                    .as_ref()
-                    .ok_or_eyre(format!("TokenStream for {path:?} does not exist"))?
-                    .to_string();
-
-                self.check_file_contents(&path, &tokens)?;
+                for (path, tokens) in self.instance_files(instance)? {
+                    self.check_file_contents(&src.join(path), &tokens)?;
+                }
                 write_mod_name(&mut super_contents, &name)?;
             }
 
@@ -0,0 +1,95 @@
+use crate::{
+    etherscan::EtherscanVerificationProvider,
+    provider::{VerificationContext, VerificationProvider},
+    verify::{VerifyArgs, VerifyCheckArgs},
+};
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use eyre::Result;
+use reqwest::Url;
+use serde::Deserialize;
+
+/// The type that can verify a contract on Blockscout.
+///
+/// Verification requests are sent to the Etherscan compatible API of the explorer, which is
+/// derived from its URL if `--verifier-url` doesn't point to it, while verified contracts are
+/// looked up with the Blockscout API.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct BlockscoutVerificationProvider;
+
+#[async_trait]
+impl VerificationProvider for BlockscoutVerificationProvider {
+    async fn preflight_verify_check(
+        &mut self,
+        mut args: VerifyArgs,
+        context: VerificationContext,
+    ) -> Result<()> {
+        args.verifier.verifier_url = args.verifier.verifier_url.as_deref().map(api_url);
+        EtherscanVerificationProvider::default().preflight_verify_check(args, context).await
+    }
+
+    async fn verify(&mut self, mut args: VerifyArgs, context: VerificationContext) -> Result<()> {
+        args.verifier.verifier_url = args.verifier.verifier_url.as_deref().map(api_url);
+
+        if let Some(url) = &args.verifier.verifier_url {
+            if !args.skip_is_verified_check && is_verified(url, args.address).await? {
+                sh_println!(
+                    "\nContract [{}] {:?} is already verified. Skipping verification.",
+                    context.target_name,
+                    args.address.to_checksum(None)
+                )?;
+                return Ok(());
+            }
+            args.skip_is_verified_check = true;
+        }
+
+        EtherscanVerificationProvider::default().verify(args, context).await
+    }
+
+    async fn check(&self, mut args: VerifyCheckArgs) -> Result<()> {
+        args.verifier.verifier_url = args.verifier.verifier_url.as_deref().map(api_url);
+        EtherscanVerificationProvider::default().check(args).await
+    }
+}
+
+/// Returns the URL of the Etherscan compatible API of the explorer at `url`, which is served under
+/// `/api`.
+fn api_url(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(parsed) if parsed.path().trim_end_matches('/').is_empty() => {
+            format!("{}/api", url.trim_end_matches('/'))
+        }
+        _ => url.to_string(),
+    }
+}
+
+/// Returns whether the contract at `address` is verified on the explorer whose API is at `api_url`.
+async fn is_verified(api_url: &str, address: Address) -> Result<bool> {
+    let url = format!("{}/v2/smart-contracts/{address}", api_url.trim_end_matches('/'));
+    let response = reqwest::get(url).await?;
+    // Unknown contracts and explorers without the v2 API are treated as unverified.
+    if !response.status().is_success() {
+        return Ok(false);
+    }
+    let contract = response.json::<BlockscoutSmartContract>().await.unwrap_or_default();
+    Ok(contract.is_verified)
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BlockscoutSmartContract {
+    #[serde(default)]
+    is_verified: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_url() {
+        assert_eq!(api_url("https://eth.blockscout.com"), "https://eth.blockscout.com/api");
+        assert_eq!(api_url("https://eth.blockscout.com/"), "https://eth.blockscout.com/api");
+        assert_eq!(api_url("https://eth.blockscout.com/api"), "https://eth.blockscout.com/api");
+    }
+}
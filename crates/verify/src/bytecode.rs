@@ -1,9 +1,11 @@
 //! The `forge verify-bytecode` command.
 use crate::{
     etherscan::EtherscanVerificationProvider,
+    types::VerificationType,
     utils::{
-        check_and_encode_args, check_explorer_args, configure_env_block, maybe_predeploy_contract,
-        BytecodeType, JsonResult,
+        check_and_encode_args, check_explorer_args, configure_env_block, decode_constructor_args,
+        find_mismatches, immutable_offsets, link_onchain_libraries, maybe_predeploy_contract,
+        BytecodeType, JsonResult, SourceLocator,
     },
     verify::VerifierArgs,
 };
@@ -14,7 +16,7 @@
 };
 use alloy_rpc_types::{BlockId, BlockNumberOrTag, TransactionInput, TransactionRequest};
 use clap::{Parser, ValueHint};
-use eyre::{Context, OptionExt, Result};
+use eyre::{Context, Result};
 use foundry_cli::{
     opts::EtherscanOpts,
     utils::{self, read_constructor_args_file, LoadConfig},
@@ -89,6 +91,12 @@ pub struct VerifyBytecodeArgs {
     /// Ignore verification for creation or runtime bytecode.
     #[arg(long, value_name = "BYTECODE_TYPE")]
     pub ignore: Option<BytecodeType>,
+
+    /// The match required for the bytecode to be verified.
+    ///
+    /// A `full` match requires the metadata hash to match too, while a `partial` match ignores it.
+    #[arg(long, value_name = "MODE", default_value = "partial")]
+    pub match_mode: VerificationType,
 }
 
 impl figment::Provider for VerifyBytecodeArgs {
@@ -181,21 +189,16 @@ pub async fn run(mut self) -> Result<()> {
         let etherscan_metadata = source_code.items.first().unwrap();
 
         // Obtain local artifact
-        let artifact = if let Ok(local_bytecode) =
+        let (artifact, sources) = if let Ok(local_bytecode) =
             crate::utils::build_using_cache(&self, etherscan_metadata, &config)
         {
             trace!("using cache");
-            local_bytecode
+            (local_bytecode, Default::default())
         } else {
             crate::utils::build_project(&self, &config)?
         };
-
-        // Get local bytecode (creation code)
-        let local_bytecode = artifact
-            .bytecode
-            .as_ref()
-            .and_then(|b| b.to_owned().into_bytes())
-            .ok_or_eyre("Unlinked bytecode is not supported for verification")?;
+        let locator = SourceLocator::new(config.root.clone(), &artifact, sources);
+        let immutables = immutable_offsets(&artifact);
 
         // Get and encode user provided constructor args
         let provided_constructor_args = if let Some(path) = self.constructor_args_path.to_owned() {
@@ -208,6 +211,7 @@ pub async fn run(mut self) -> Result<()> {
         .transpose()?
         .or(self.encoded_constructor_args.to_owned().map(hex::decode).transpose()?);
 
+        let is_provided = provided_constructor_args.is_some();
         let mut constructor_args = if let Some(provided) = provided_constructor_args {
             provided.into()
         } else {
@@ -227,6 +231,9 @@ pub async fn run(mut self) -> Result<()> {
                 )?;
             }
 
+            // Get local bytecode (creation code), linked to the libraries of the on-chain code.
+            let local_bytecode = link_onchain_libraries(&artifact, &code, true)?;
+
             // Append constructor args to the local_bytecode.
             trace!(%constructor_args);
             let mut local_bytecode_vec = local_bytecode.to_vec();
@@ -290,13 +297,21 @@ pub async fn run(mut self) -> Result<()> {
             )
             .await?;
 
+            let deployed_bytecode = deployed_bytecode.original_bytes();
             let match_type = crate::utils::match_bytecodes(
-                &deployed_bytecode.original_bytes(),
+                &deployed_bytecode,
                 &onchain_runtime_code,
                 &constructor_args,
                 true,
                 config.bytecode_hash,
-            );
+                &immutables,
+            )
+            .filter(|match_type| match_type.satisfies(self.match_mode));
+            let mismatches = if match_type.is_none() {
+                find_mismatches(&deployed_bytecode, &onchain_runtime_code, true, &locator)
+            } else {
+                vec![]
+            };
 
             crate::utils::print_result(
                 match_type,
@@ -304,6 +319,7 @@ pub async fn run(mut self) -> Result<()> {
                 &mut json_results,
                 etherscan_metadata,
                 &config,
+                mismatches,
             );
 
             if shell::is_json() {
@@ -361,6 +377,9 @@ pub async fn run(mut self) -> Result<()> {
                 );
             };
 
+        // Get local bytecode (creation code), linked to the libraries of the on-chain code.
+        let local_bytecode = link_onchain_libraries(&artifact, maybe_creation_code, false)?;
+
         // In some cases, Etherscan will return incorrect constructor arguments. If this
         // happens, try extracting arguments ourselves.
         if !maybe_creation_code.ends_with(&constructor_args) {
@@ -374,6 +393,12 @@ pub async fn run(mut self) -> Result<()> {
                     "setting constructor args to latest {} bytes of bytecode",
                     constructor_args.len()
                 );
+                if !is_provided && !shell::is_json() {
+                    sh_println!(
+                        "Using the constructor arguments of the creation transaction: {}",
+                        decode_constructor_args(&artifact, &constructor_args)
+                    )?;
+                }
             }
         }
 
@@ -392,7 +417,14 @@ pub async fn run(mut self) -> Result<()> {
                 &constructor_args,
                 false,
                 config.bytecode_hash,
-            );
+                &[],
+            )
+            .filter(|match_type| match_type.satisfies(self.match_mode));
+            let mismatches = if match_type.is_none() {
+                find_mismatches(&local_bytecode_vec, maybe_creation_code, false, &locator)
+            } else {
+                vec![]
+            };
 
             crate::utils::print_result(
                 match_type,
@@ -400,6 +432,7 @@ pub async fn run(mut self) -> Result<()> {
                 &mut json_results,
                 etherscan_metadata,
                 &config,
+                mismatches,
             );
 
             // If the creation code does not match, the runtime also won't match. Hence return.
@@ -410,6 +443,7 @@ pub async fn run(mut self) -> Result<()> {
                     &mut json_results,
                     etherscan_metadata,
                     &config,
+                    vec![],
                 );
                 if shell::is_json() {
                     sh_println!("{}", serde_json::to_string(&json_results)?)?;
@@ -500,13 +534,21 @@ pub async fn run(mut self) -> Result<()> {
             .await?;
 
             // Compare the onchain runtime bytecode with the runtime code from the fork.
+            let fork_runtime_code = fork_runtime_code.original_bytes();
             let match_type = crate::utils::match_bytecodes(
-                &fork_runtime_code.original_bytes(),
+                &fork_runtime_code,
                 &onchain_runtime_code,
                 &constructor_args,
                 true,
                 config.bytecode_hash,
-            );
+                &immutables,
+            )
+            .filter(|match_type| match_type.satisfies(self.match_mode));
+            let mismatches = if match_type.is_none() {
+                find_mismatches(&fork_runtime_code, &onchain_runtime_code, true, &locator)
+            } else {
+                vec![]
+            };
 
             crate::utils::print_result(
                 match_type,
@@ -514,6 +556,7 @@ pub async fn run(mut self) -> Result<()> {
                 &mut json_results,
                 etherscan_metadata,
                 &config,
+                mismatches,
             );
         }
 
@@ -30,6 +30,27 @@
 pub static RE_BUILD_COMMIT: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?P<commit>commit\.[0-9,a-f]{8})").unwrap());
 
+/// The Etherscan multichain API, verifying contracts on all the chains explored by Etherscan with a
+/// single API key.
+pub const ETHERSCAN_V2_API_URL: &str = "https://api.etherscan.io/v2/api";
+
+/// The hosts of the explorers operated by Etherscan, whose chains are supported by the multichain
+/// API.
+const ETHERSCAN_V2_HOSTS: &[&str] = &[
+    "etherscan.io",
+    "arbiscan.io",
+    "basescan.org",
+    "blastscan.io",
+    "bscscan.com",
+    "celoscan.io",
+    "gnosisscan.io",
+    "lineascan.build",
+    "moonscan.io",
+    "polygonscan.com",
+    "scrollscan.com",
+    "snowscan.xyz",
+];
+
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub struct EtherscanVerificationProvider;
@@ -260,19 +281,31 @@ pub(crate) fn client(
     ) -> Result<Client> {
         let etherscan_config = config.get_etherscan_config_with_chain(Some(chain))?;
 
-        let etherscan_api_url = verifier_url
+        let etherscan_key =
+            etherscan_key.or_else(|| etherscan_config.as_ref().map(|c| c.key.as_str()));
+
+        let mut etherscan_api_url = verifier_url
             .or_else(|| etherscan_config.as_ref().map(|c| c.api_url.as_str()))
             .map(str::to_owned);
 
+        // Chains explored by Etherscan are verified through its multichain API, unless another
+        // API URL is set.
+        let is_etherscan = verifier_type.is_etherscan() ||
+            (verifier_type.is_sourcify() && etherscan_key.is_some());
+        if is_etherscan && verifier_url.is_none() {
+            if let Some(v2_url) =
+                etherscan_api_url.as_deref().and_then(|url| v2_api_url(chain, url))
+            {
+                etherscan_api_url = Some(v2_url);
+            }
+        }
+
         let api_url = etherscan_api_url.as_deref();
         let base_url = etherscan_config
             .as_ref()
             .and_then(|c| c.browser_url.as_deref())
             .or_else(|| chain.etherscan_urls().map(|(_, url)| url));
 
-        let etherscan_key =
-            etherscan_key.or_else(|| etherscan_config.as_ref().map(|c| c.key.as_str()));
-
         let mut builder = Client::builder();
 
         builder = if let Some(api_url) = api_url {
@@ -281,8 +314,6 @@ pub(crate) fn client(
 
             // Verifier is etherscan if explicitly set or if no verifier set (default sourcify) but
             // API key passed.
-            let is_etherscan = verifier_type.is_etherscan() ||
-                (verifier_type.is_sourcify() && etherscan_key.is_some());
             let base_url = if !is_etherscan {
                 // If verifier is not Etherscan then set base url as api url without /api suffix.
                 api_url.strip_prefix("/api").unwrap_or(api_url)
@@ -444,6 +475,20 @@ async fn guess_constructor_args(
     }
 }
 
+/// Returns the URL of the Etherscan multichain API for `chain`, if `api_url` is the default API URL
+/// of a chain explored by Etherscan.
+fn v2_api_url(chain: Chain, api_url: &str) -> Option<String> {
+    let (default_url, _) = chain.etherscan_urls()?;
+    if api_url.trim_end_matches('/') != default_url.trim_end_matches('/') {
+        return None;
+    }
+    let host = reqwest::Url::parse(api_url).ok()?.host_str()?.to_string();
+    ETHERSCAN_V2_HOSTS
+        .iter()
+        .any(|h| host == *h || host.ends_with(&format!(".{h}")))
+        .then(|| format!("{ETHERSCAN_V2_API_URL}?chainid={}", chain.id()))
+}
+
 /// Given any solc [Version] return a [Version] with build metadata
 ///
 /// # Example
@@ -539,6 +584,21 @@ fn can_extract_etherscan_verify_config() {
         assert!(format!("{client:?}").contains("dummykey"));
     }
 
+    #[test]
+    fn uses_etherscan_v2_api() {
+        let (mainnet_url, _) = Chain::mainnet().etherscan_urls().unwrap();
+        assert_eq!(
+            v2_api_url(Chain::mainnet(), mainnet_url).as_deref(),
+            Some("https://api.etherscan.io/v2/api?chainid=1")
+        );
+        let (base_url, _) = Chain::base_mainnet().etherscan_urls().unwrap();
+        assert_eq!(
+            v2_api_url(Chain::base_mainnet(), base_url).as_deref(),
+            Some("https://api.etherscan.io/v2/api?chainid=8453")
+        );
+        assert_eq!(v2_api_url(Chain::mainnet(), "https://verifier-url.com/"), None);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn fails_on_disabled_cache_and_missing_info() {
         let temp = tempdir().unwrap();
@@ -9,6 +9,8 @@
 #[macro_use]
 extern crate tracing;
 
+mod blockscout;
+
 mod etherscan;
 
 pub mod provider;
@@ -21,6 +23,8 @@
 
 mod sourcify;
 
+mod standard_json;
+
 pub mod verify;
 pub use verify::{VerifierArgs, VerifyArgs, VerifyCheckArgs};
 
@@ -1,6 +1,8 @@
 use crate::{
+    blockscout::BlockscoutVerificationProvider,
     etherscan::EtherscanVerificationProvider,
     sourcify::SourcifyVerificationProvider,
+    standard_json::StandardJsonVerificationProvider,
     verify::{VerifyArgs, VerifyCheckArgs},
 };
 use alloy_json_abi::JsonAbi;
@@ -128,6 +130,7 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
             "b" | "blockscout" => Ok(Self::Blockscout),
             "o" | "oklink" => Ok(Self::Oklink),
             "c" | "custom" => Ok(Self::Custom),
+            "j" | "standard-json" => Ok(Self::StandardJson),
             _ => Err(format!("Unknown provider: {s}")),
         }
     }
@@ -151,6 +154,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             Self::Custom => {
                 write!(f, "custom")?;
             }
+            Self::StandardJson => {
+                write!(f, "standard-json")?;
+            }
         };
         Ok(())
     }
@@ -165,6 +171,9 @@ pub enum VerificationProviderType {
     Oklink,
     /// Custom verification provider, requires compatibility with the Etherscan API.
     Custom,
+    /// Verifier accepting the standard JSON input of contracts, configured with `--verifier-url`
+    /// or in the `[verifiers]` table of the config.
+    StandardJson,
 }
 
 impl VerificationProviderType {
@@ -188,10 +197,15 @@ pub fn client(&self, key: Option<&str>) -> Result<Box<dyn VerificationProvider>>
             return Ok(Box::<EtherscanVerificationProvider>::default());
         }
 
-        // 3. If `--verifier blockscout | oklink | custom` is explicitly set, use the chosen
-        //    verifier.
-        if matches!(self, Self::Blockscout | Self::Oklink | Self::Custom) {
-            return Ok(Box::<EtherscanVerificationProvider>::default());
+        // 3. If `--verifier blockscout | oklink | custom | standard-json` is explicitly set, use
+        //    the chosen verifier.
+        match self {
+            Self::Blockscout => return Ok(Box::<BlockscoutVerificationProvider>::default()),
+            Self::Oklink | Self::Custom => {
+                return Ok(Box::<EtherscanVerificationProvider>::default())
+            }
+            Self::StandardJson => return Ok(Box::<StandardJsonVerificationProvider>::default()),
+            Self::Etherscan | Self::Sourcify => {}
         }
 
         // 4. If no `--verifier` is specified but `ETHERSCAN_API_KEY` is set, default to Etherscan.
@@ -0,0 +1,209 @@
+use crate::{
+    etherscan::EtherscanVerificationProvider,
+    provider::{VerificationContext, VerificationProvider},
+    verify::{VerifierArgs, VerifyArgs, VerifyCheckArgs},
+};
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use eyre::{Context, Result};
+use foundry_cli::utils::LoadConfig;
+use foundry_config::{Chain, Config};
+use serde::{Deserialize, Serialize};
+
+/// The type that can verify a contract by posting its standard JSON input to a verifier, set with
+/// `--verifier-url` or in the `[verifiers]` table of the config.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct StandardJsonVerificationProvider;
+
+#[async_trait]
+impl VerificationProvider for StandardJsonVerificationProvider {
+    async fn preflight_verify_check(
+        &mut self,
+        args: VerifyArgs,
+        context: VerificationContext,
+    ) -> Result<()> {
+        let chain = args.etherscan.chain.unwrap_or_default();
+        let _ = endpoint(&args.verifier, chain, &context.config)?;
+        let _ = self.prepare_request(&args, &context).await?;
+        Ok(())
+    }
+
+    async fn verify(&mut self, args: VerifyArgs, context: VerificationContext) -> Result<()> {
+        let chain = args.etherscan.chain.unwrap_or_default();
+        let (url, key) = endpoint(&args.verifier, chain, &context.config)?;
+        let body = self.prepare_request(&args, &context).await?;
+
+        trace!(url, contract = body.contract_name, "submitting verification request");
+
+        let client = reqwest::Client::new();
+        args.retry
+            .into_retry()
+            .run_async(|| async {
+                sh_println!(
+                    "\nSubmitting verification for [{}] {}.",
+                    body.contract_name,
+                    body.address
+                )?;
+                let mut request = client.post(&url).json(&body);
+                if let Some(key) = &key {
+                    request = request.bearer_auth(key);
+                }
+                let response = request.send().await?;
+
+                let status = response.status();
+                if !status.is_success() {
+                    let details = response.text().await.unwrap_or_default();
+                    eyre::bail!(
+                        "Verification request for address ({}) failed with status code {status}\n\
+                         Details: {details}",
+                        body.address,
+                    );
+                }
+                Ok(())
+            })
+            .await?;
+
+        sh_println!("Contract successfully verified")?;
+        Ok(())
+    }
+
+    async fn check(&self, args: VerifyCheckArgs) -> Result<()> {
+        let config = args.load_config()?;
+        let chain = args.etherscan.chain.unwrap_or_default();
+        let (url, key) = endpoint(&args.verifier, chain, &config)?;
+
+        let client = reqwest::Client::new();
+        let response = args
+            .retry
+            .into_retry()
+            .run_async(|| async {
+                let mut request = client
+                    .get(&url)
+                    .query(&[("chainId", chain.id().to_string()), ("address", args.id.clone())]);
+                if let Some(key) = &key {
+                    request = request.bearer_auth(key);
+                }
+                let response = request.send().await?;
+                if !response.status().is_success() {
+                    eyre::bail!(
+                        "Failed to request verification status with status code {}",
+                        response.status()
+                    );
+                }
+                Ok(response.json::<StandardJsonCheckResponse>().await?)
+            })
+            .await?;
+
+        if response.verified {
+            sh_println!("Contract successfully verified")?;
+        } else {
+            sh_println!("Contract source code is not verified")?;
+        }
+        Ok(())
+    }
+}
+
+impl StandardJsonVerificationProvider {
+    /// Configures the request to the verifier using the given [`VerifyArgs`].
+    async fn prepare_request(
+        &self,
+        args: &VerifyArgs,
+        context: &VerificationContext,
+    ) -> Result<StandardJsonVerifyRequest> {
+        if args.flatten {
+            eyre::bail!("Standard JSON verifiers don't support flattened sources");
+        }
+
+        let verify_args =
+            EtherscanVerificationProvider::default().create_verify_request(args, context).await?;
+
+        Ok(StandardJsonVerifyRequest {
+            chain_id: args.etherscan.chain.unwrap_or_default().id(),
+            address: verify_args.address,
+            contract_name: verify_args.contract_name,
+            compiler_version: verify_args.compiler_version,
+            standard_json_input: serde_json::from_str(&verify_args.source)
+                .wrap_err("Failed to parse standard json input")?,
+            constructor_arguments: verify_args
+                .constructor_arguments
+                .map(|args| format!("0x{}", args.trim_start_matches("0x"))),
+        })
+    }
+}
+
+/// Returns the URL of the verifier of `chain` and the key to authenticate with, from the command
+/// line or the `[verifiers]` table of the config.
+fn endpoint(
+    verifier: &VerifierArgs,
+    chain: Chain,
+    config: &Config,
+) -> Result<(String, Option<String>)> {
+    let configured = config.verifier(chain)?;
+
+    let url = verifier
+        .verifier_url
+        .clone()
+        .or_else(|| configured.map(|verifier| verifier.url.clone()))
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "No verifier configured for chain {chain}. Pass --verifier-url or add one to the `[verifiers]` table of foundry.toml"
+            )
+        })?;
+
+    let key = match &verifier.verifier_api_key {
+        Some(key) => Some(key.clone()),
+        None => configured.map(|verifier| verifier.resolved_key()).transpose()?.flatten(),
+    };
+
+    Ok((url, key))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardJsonVerifyRequest {
+    chain_id: u64,
+    address: Address,
+    contract_name: String,
+    compiler_version: String,
+    standard_json_input: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constructor_arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StandardJsonCheckResponse {
+    verified: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use foundry_config::VerifierConfig;
+
+    #[test]
+    fn resolves_endpoint() {
+        let mut config = Config::default();
+        config.verifiers.insert(
+            "12345".to_string(),
+            VerifierConfig {
+                url: "https://explorer.example.com/api/verify".to_string(),
+                key: Some("secret".to_string()),
+            },
+        );
+
+        let (url, key) =
+            endpoint(&VerifierArgs::default(), Chain::from_id(12345), &config).unwrap();
+        assert_eq!(url, "https://explorer.example.com/api/verify");
+        assert_eq!(key.as_deref(), Some("secret"));
+
+        let verifier = VerifierArgs {
+            verifier_url: Some("https://verifier.example.com".to_string()),
+            ..Default::default()
+        };
+        let (url, _) = endpoint(&verifier, Chain::from_id(12345), &config).unwrap();
+        assert_eq!(url, "https://verifier.example.com");
+
+        assert!(endpoint(&VerifierArgs::default(), Chain::mainnet(), &config).is_err());
+    }
+}
@@ -13,6 +13,13 @@ pub enum VerificationType {
     Partial,
 }
 
+impl VerificationType {
+    /// Returns whether a match of this type satisfies the `required` match.
+    pub fn satisfies(self, required: Self) -> bool {
+        self == Self::Full || required == Self::Partial
+    }
+}
+
 impl FromStr for VerificationType {
     type Err = eyre::Error;
 
@@ -1,6 +1,6 @@
 use crate::{bytecode::VerifyBytecodeArgs, types::VerificationType};
-use alloy_dyn_abi::DynSolValue;
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_dyn_abi::{DynSolType, DynSolValue, Specifier};
+use alloy_primitives::{hex, Address, Bytes, U256};
 use alloy_provider::{network::AnyRpcBlock, Provider};
 use alloy_rpc_types::BlockId;
 use clap::ValueEnum;
@@ -9,8 +9,13 @@
     contract::{ContractCreationData, ContractMetadata, Metadata},
     errors::EtherscanError,
 };
-use foundry_common::{abi::encode_args, compile::ProjectCompiler, provider::RetryProvider, shell};
-use foundry_compilers::artifacts::{BytecodeHash, CompactContractBytecode, EvmVersion};
+use foundry_common::{
+    abi::encode_args, compile::ProjectCompiler, fmt::format_token, provider::RetryProvider, shell,
+};
+use foundry_compilers::artifacts::{
+    sourcemap::{self, SourceMap},
+    BytecodeHash, CompactContractBytecode, EvmVersion, Offsets,
+};
 use foundry_config::Config;
 use foundry_evm::{
     constants::DEFAULT_CREATE2_DEPLOYER, executors::TracingExecutor, opts::EvmOpts,
@@ -24,6 +29,11 @@
 };
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
 use yansi::Paint;
 
 /// Enum to represent the type of bytecode being verified
@@ -53,6 +63,214 @@ pub struct JsonResult {
     pub match_type: Option<VerificationType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mismatches: Vec<BytecodeMismatch>,
+}
+
+/// The maximum number of differing byte ranges reported for a bytecode mismatch.
+const MAX_REPORTED_MISMATCHES: usize = 10;
+
+/// The maximum number of bytes shown for a differing byte range.
+const MAX_REPORTED_BYTES: usize = 32;
+
+/// A range of bytes that differ between the local and the on-chain bytecode.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BytecodeMismatch {
+    /// The offset of the range in the bytecode.
+    pub offset: usize,
+    /// The length of the range.
+    pub length: usize,
+    /// The local bytes, truncated to 32 bytes.
+    pub local: Bytes,
+    /// The on-chain bytes, truncated to 32 bytes.
+    pub onchain: Bytes,
+    /// The source code the local bytes were compiled from, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl fmt::Display for BytecodeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bytes {}..{}: local {} != on-chain {}",
+            self.offset,
+            self.offset + self.length,
+            self.local,
+            self.onchain
+        )?;
+        if let Some(source) = &self.source {
+            write!(f, " ({source})")?;
+        }
+        Ok(())
+    }
+}
+
+/// The source maps of a contract and the paths of the sources they refer to, to locate the
+/// source code of bytecode mismatches.
+#[derive(Debug, Default)]
+pub struct SourceLocator {
+    pub root: PathBuf,
+    pub source_map: Option<SourceMap>,
+    pub source_map_runtime: Option<SourceMap>,
+    pub sources: BTreeMap<u32, PathBuf>,
+}
+
+impl SourceLocator {
+    pub fn new(
+        root: PathBuf,
+        artifact: &CompactContractBytecode,
+        sources: BTreeMap<u32, PathBuf>,
+    ) -> Self {
+        let parse = |map: Option<&String>| sourcemap::parse(map?).ok();
+        let source_map = parse(artifact.bytecode.as_ref().and_then(|b| b.source_map.as_ref()));
+        let source_map_runtime = parse(
+            artifact
+                .deployed_bytecode
+                .as_ref()
+                .and_then(|d| d.bytecode.as_ref()?.source_map.as_ref()),
+        );
+        Self { root, source_map, source_map_runtime, sources }
+    }
+
+    /// Returns the location of the source code the byte at `offset` of `bytecode` was compiled
+    /// from.
+    fn locate(&self, bytecode: &[u8], offset: usize, is_runtime: bool) -> Option<String> {
+        if offset >= bytecode.len() {
+            return None;
+        }
+        if bytecode.len() >= 2 && offset >= extract_metadata_hash(bytecode).len() {
+            return Some("metadata".to_string());
+        }
+
+        let source_map =
+            if is_runtime { self.source_map_runtime.as_ref() } else { self.source_map.as_ref() }?;
+        let element = source_map.get(instruction_index(bytecode, offset))?;
+        let index = element.index()?;
+        let Some(path) = self.sources.get(&index) else {
+            return Some(format!(
+                "source {index}, offset {}, length {}",
+                element.offset(),
+                element.length()
+            ));
+        };
+
+        let source = std::fs::read_to_string(self.root.join(path)).ok()?;
+        let line = source.get(..element.offset() as usize)?.matches('\n').count() + 1;
+        Some(format!("{}:{line}", path.display()))
+    }
+}
+
+/// Returns the index of the instruction containing the byte at `offset` of `bytecode`.
+fn instruction_index(bytecode: &[u8], offset: usize) -> usize {
+    let (mut pc, mut ic) = (0, 0);
+    while pc < bytecode.len() {
+        // PUSH1 to PUSH32 are followed by their immediate.
+        let immediate = match bytecode[pc] {
+            op @ 0x60..=0x7f => (op - 0x5f) as usize,
+            _ => 0,
+        };
+        if offset <= pc + immediate {
+            break;
+        }
+        pc += 1 + immediate;
+        ic += 1;
+    }
+    ic
+}
+
+/// Returns the ranges of bytes that differ between `local_bytecode` and `bytecode`.
+pub fn find_mismatches(
+    local_bytecode: &[u8],
+    bytecode: &[u8],
+    is_runtime: bool,
+    locator: &SourceLocator,
+) -> Vec<BytecodeMismatch> {
+    let len = local_bytecode.len().max(bytecode.len());
+    let mut mismatches = Vec::new();
+    let mut offset = 0;
+    while offset < len && mismatches.len() < MAX_REPORTED_MISMATCHES {
+        if local_bytecode.get(offset) == bytecode.get(offset) {
+            offset += 1;
+            continue;
+        }
+
+        let start = offset;
+        while offset < len && local_bytecode.get(offset) != bytecode.get(offset) {
+            offset += 1;
+        }
+        let slice = |code: &[u8]| {
+            let end = offset.min(code.len()).min(start + MAX_REPORTED_BYTES);
+            Bytes::copy_from_slice(code.get(start..end).unwrap_or_default())
+        };
+        mismatches.push(BytecodeMismatch {
+            offset: start,
+            length: offset - start,
+            local: slice(local_bytecode),
+            onchain: slice(bytecode),
+            source: locator.locate(local_bytecode, start, is_runtime),
+        });
+    }
+    mismatches
+}
+
+/// Sets the bytes of the immutables at `immutables` to zero, as their values depend on the
+/// deployment.
+fn mask_immutables(bytecode: &[u8], immutables: &[Offsets]) -> Vec<u8> {
+    let mut bytecode = bytecode.to_vec();
+    for offsets in immutables {
+        let start = offsets.start as usize;
+        let end = (start + offsets.length as usize).min(bytecode.len());
+        if start < end {
+            bytecode[start..end].fill(0);
+        }
+    }
+    bytecode
+}
+
+/// Returns the offsets of the immutables in the runtime bytecode of `artifact`.
+pub fn immutable_offsets(artifact: &CompactContractBytecode) -> Vec<Offsets> {
+    artifact
+        .deployed_bytecode
+        .as_ref()
+        .map(|d| d.immutable_references.values().flatten().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Returns the creation code of `artifact` with its libraries linked to the addresses found at
+/// their link references in `onchain_code`, which is the deployed runtime code if `is_runtime`,
+/// and the creation code otherwise.
+pub fn link_onchain_libraries(
+    artifact: &CompactContractBytecode,
+    onchain_code: &[u8],
+    is_runtime: bool,
+) -> Result<Bytes> {
+    let bytecode = artifact.bytecode.as_ref().ok_or_eyre("Contract artifact has no bytecode")?;
+    let link_references = if is_runtime {
+        artifact
+            .deployed_bytecode
+            .as_ref()
+            .and_then(|d| d.bytecode.as_ref())
+            .map(|b| &b.link_references)
+    } else {
+        Some(&bytecode.link_references)
+    };
+
+    let mut object = bytecode.object.clone();
+    for (file, libraries) in link_references.into_iter().flatten() {
+        for (name, offsets) in libraries {
+            let Some(offsets) = offsets.first() else { continue };
+            let start = offsets.start as usize;
+            let address =
+                onchain_code.get(start..start + 20).map(Address::from_slice).ok_or_else(|| {
+                    eyre::eyre!("On-chain bytecode doesn't contain the address of library {name}")
+                })?;
+            trace!(%file, %name, %address, "linking library");
+            object.link(file, name, address);
+        }
+    }
+
+    object.into_bytes().ok_or_eyre("Failed to link the libraries of the contract")
 }
 
 pub fn match_bytecodes(
@@ -61,7 +279,16 @@ pub fn match_bytecodes(
     constructor_args: &[u8],
     is_runtime: bool,
     bytecode_hash: BytecodeHash,
+    immutables: &[Offsets],
 ) -> Option<VerificationType> {
+    // Immutables are part of a full match, whatever their values.
+    let (local_bytecode, bytecode) = if is_runtime && !immutables.is_empty() {
+        (mask_immutables(local_bytecode, immutables), mask_immutables(bytecode, immutables))
+    } else {
+        (local_bytecode.to_vec(), bytecode.to_vec())
+    };
+    let (local_bytecode, bytecode) = (local_bytecode.as_slice(), bytecode.as_slice());
+
     // 1. Try full match
     if local_bytecode == bytecode {
         // If the bytecode_hash = 'none' in Config. Then it's always a partial match according to
@@ -77,20 +304,37 @@ pub fn match_bytecodes(
     }
 }
 
+/// Compiles the project and returns the artifact of the contract, along with the paths of the
+/// sources compiled with it by source ID.
 pub fn build_project(
     args: &VerifyBytecodeArgs,
     config: &Config,
-) -> Result<CompactContractBytecode> {
+) -> Result<(CompactContractBytecode, BTreeMap<u32, PathBuf>)> {
     let project = config.project()?;
     let compiler = ProjectCompiler::new();
 
     let mut output = compiler.compile(&project)?;
 
+    let version = output
+        .artifact_ids()
+        .find(|(id, _)| {
+            id.name == args.contract.name &&
+                args.contract.path.as_ref().is_none_or(|path| id.source.ends_with(path))
+        })
+        .map(|(id, _)| id.version);
+    let sources = output
+        .output()
+        .sources
+        .sources_with_version()
+        .filter(|(_, _, v)| version.as_ref() == Some(*v))
+        .map(|(path, source, _)| (source.id, path.clone()))
+        .collect();
+
     let artifact = output
         .remove_contract(&args.contract)
         .ok_or_eyre("Build Error: Contract artifact not found locally")?;
 
-    Ok(artifact.into_contract_bytecode())
+    Ok((artifact.into_contract_bytecode(), sources))
 }
 
 pub fn build_using_cache(
@@ -102,8 +346,17 @@ pub fn build_using_cache(
     let cache = project.read_cache_file()?;
     let cached_artifacts = cache.read_artifacts::<CompactContractBytecode>()?;
 
+    // The file of the contract, which is named after it unless its path is given.
+    let file_name = args
+        .contract
+        .path
+        .as_deref()
+        .and_then(|path| Path::new(path).file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| format!("{}.sol", args.contract.name));
+    let artifact_name = format!("{}.json", args.contract.name);
+
     for (key, value) in cached_artifacts {
-        let name = args.contract.name.to_owned() + ".sol";
         let version = etherscan_settings.compiler_version.to_owned();
         // Ignores vyper
         if version.starts_with("vyper:") {
@@ -112,12 +365,11 @@ pub fn build_using_cache(
         // Parse etherscan version string
         let version = version.split('+').next().unwrap_or("").trim_start_matches('v').to_string();
 
-        // Check if `out/directory` name matches the contract name
-        if key.ends_with(name.as_str()) {
-            let name = name.replace(".sol", ".json");
+        // Check if `out/directory` name matches the contract file
+        if key.ends_with(file_name.as_str()) {
             for artifact in value.into_values().flatten() {
                 // Check if ABI file matches the name
-                if !artifact.file.ends_with(&name) {
+                if !artifact.file.ends_with(&artifact_name) {
                     continue;
                 }
 
@@ -145,6 +397,7 @@ pub fn print_result(
     json_results: &mut Vec<JsonResult>,
     etherscan_config: &Metadata,
     config: &Config,
+    bytecode_mismatches: Vec<BytecodeMismatch>,
 ) {
     if let Some(res) = res {
         if !shell::is_json() {
@@ -154,7 +407,12 @@ pub fn print_result(
                 res.green().bold()
             );
         } else {
-            let json_res = JsonResult { bytecode_type, match_type: Some(res), message: None };
+            let json_res = JsonResult {
+                bytecode_type,
+                match_type: Some(res),
+                message: None,
+                mismatches: vec![],
+            };
             json_results.push(json_res);
         }
     } else if !shell::is_json() {
@@ -165,6 +423,9 @@ pub fn print_result(
         for mismatch in mismatches {
             let _ = sh_eprintln!("{}", mismatch.red().bold());
         }
+        for mismatch in &bytecode_mismatches {
+            let _ = sh_eprintln!("  {mismatch}");
+        }
     } else {
         let json_res = JsonResult {
             bytecode_type,
@@ -172,6 +433,7 @@ pub fn print_result(
             message: Some(format!(
                 "{bytecode_type:?} code did not match - this may be due to varying compiler settings"
             )),
+            mismatches: bytecode_mismatches,
         };
         json_results.push(json_res);
     }
@@ -300,6 +562,21 @@ pub fn check_and_encode_args(
     }
 }
 
+/// Formats the ABI-encoded constructor `args` of `artifact`, decoded if possible.
+pub fn decode_constructor_args(artifact: &CompactContractBytecode, args: &[u8]) -> String {
+    let decoded = artifact.abi.as_ref().and_then(|abi| abi.constructor()).and_then(|constructor| {
+        let types =
+            constructor.inputs.iter().map(|input| input.resolve().ok()).collect::<Option<_>>()?;
+        DynSolType::Tuple(types).abi_decode_params(args).ok()
+    });
+    match decoded {
+        Some(DynSolValue::Tuple(values)) => {
+            values.iter().map(format_token).collect::<Vec<_>>().join(", ")
+        }
+        _ => hex::encode_prefixed(args),
+    }
+}
+
 pub fn check_explorer_args(source_code: ContractMetadata) -> Result<Bytes, eyre::ErrReport> {
     if let Some(args) = source_code.items.first() {
         Ok(args.constructor_arguments.clone())
@@ -438,4 +715,36 @@ fn test_host_only() {
         assert!(is_host_only(&Url::parse("https://blockscout.net/").unwrap()));
         assert!(is_host_only(&Url::parse("https://blockscout.net").unwrap()));
     }
+
+    #[test]
+    fn test_match_bytecodes_with_immutables() {
+        // PUSH32 <immutable> STOP
+        let local = [[0x7f].as_slice(), &[0; 32], &[0x00]].concat();
+        let mut onchain = local.clone();
+        onchain[1..33].copy_from_slice(&[0xaa; 32]);
+
+        assert_eq!(match_bytecodes(&local, &onchain, &[], true, BytecodeHash::None, &[]), None);
+        let immutables = [Offsets { start: 1, length: 32 }];
+        assert_eq!(
+            match_bytecodes(&local, &onchain, &[], true, BytecodeHash::Ipfs, &immutables),
+            Some(VerificationType::Full)
+        );
+    }
+
+    #[test]
+    fn test_find_mismatches() {
+        // PUSH1 0x01 PUSH1 0x02 ADD
+        let local = [0x60, 0x01, 0x60, 0x02, 0x01];
+        let onchain = [0x60, 0x01, 0x60, 0x03, 0x01, 0x00];
+        assert_eq!(instruction_index(&local, 3), 1);
+        assert_eq!(instruction_index(&local, 4), 2);
+
+        let mismatches = find_mismatches(&local, &onchain, true, &SourceLocator::default());
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!((mismatches[0].offset, mismatches[0].length), (3, 1));
+        assert_eq!(mismatches[0].local, Bytes::from_static(&[0x02]));
+        assert_eq!(mismatches[0].onchain, Bytes::from_static(&[0x03]));
+        assert_eq!((mismatches[1].offset, mismatches[1].length), (5, 1));
+        assert!(mismatches[1].local.is_empty());
+    }
 }
@@ -211,6 +211,15 @@ pub async fn run(mut self) -> Result<()> {
         self.etherscan.chain = Some(chain);
         self.etherscan.key = config.get_etherscan_config_with_chain(Some(chain))?.map(|c| c.key);
 
+        // Use the verifier configured for the chain if no other verifier is set.
+        if self.verifier.verifier.is_sourcify() &&
+            self.verifier.verifier_url.is_none() &&
+            self.etherscan.key().is_none() &&
+            config.verifier(chain)?.is_some()
+        {
+            self.verifier.verifier = VerificationProviderType::StandardJson;
+        }
+
         if self.show_standard_json_input {
             let args = EtherscanVerificationProvider::default()
                 .create_verify_request(&self, &context)
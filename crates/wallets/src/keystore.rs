@@ -0,0 +1,201 @@
+//! Entries of the keystore directory besides encrypted keystores: watch-only accounts of hardware
+//! wallets, and labels of the accounts usable with `--account`.
+
+use crate::{utils, WalletSigner};
+use alloy_primitives::Address;
+use alloy_signer::Signer;
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The name of the file of the account labels in the keystore directory.
+pub const LABELS_FILE_NAME: &str = ".labels.json";
+
+/// A hardware wallet of a watch-only account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HardwareWallet {
+    Ledger,
+    Trezor,
+}
+
+impl fmt::Display for HardwareWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ledger => f.write_str("Ledger"),
+            Self::Trezor => f.write_str("Trezor"),
+        }
+    }
+}
+
+/// An account of a hardware wallet saved in the keystore directory.
+///
+/// It holds no key: the address is only used to list the account, and signing goes through the
+/// device with the saved derivation path.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchOnlyAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The hardware wallet holding the key of the account.
+    pub wallet: HardwareWallet,
+    /// The derivation path of the account, if not derived from the mnemonic index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hd_path: Option<String>,
+    /// The mnemonic index of the account.
+    #[serde(default)]
+    pub mnemonic_index: u32,
+}
+
+/// The content of a watch-only entry, the other keys of encrypted keystores are ignored.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    #[serde(default)]
+    watch_only: Option<WatchOnlyAccount>,
+}
+
+impl WatchOnlyAccount {
+    /// Reads the watch-only account at `path`, returns `None` if it's not a watch-only entry.
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read keystore file at {}", path.display()))?;
+        Ok(serde_json::from_str::<Entry>(&content).ok().and_then(|entry| entry.watch_only))
+    }
+
+    /// Writes the watch-only account to `path`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let entry = Entry { watch_only: Some(self.clone()) };
+        fs::write(path, serde_json::to_string_pretty(&entry)?)
+            .wrap_err_with(|| format!("Failed to write keystore file at {}", path.display()))
+    }
+
+    /// Connects to the hardware wallet, and checks that it derives the address of the account.
+    pub async fn signer(&self) -> Result<WalletSigner> {
+        let hd_path = self.hd_path.as_deref();
+        let signer = match self.wallet {
+            HardwareWallet::Ledger => utils::create_ledger_signer(hd_path, self.mnemonic_index),
+            HardwareWallet::Trezor => utils::create_trezor_signer(hd_path, self.mnemonic_index),
+        }
+        .await?;
+        if signer.address() != self.address {
+            eyre::bail!(
+                "The connected {} derives {} instead of the watch-only address {}",
+                self.wallet,
+                signer.address(),
+                self.address
+            )
+        }
+        Ok(signer)
+    }
+}
+
+/// Labels of the accounts of a keystore directory, which can be used in place of their file
+/// names.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AccountLabels(BTreeMap<String, String>);
+
+impl AccountLabels {
+    /// Reads the labels of the keystore directory `dir`.
+    pub fn read(dir: &Path) -> Result<Self> {
+        let path = dir.join(LABELS_FILE_NAME);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content)
+            .wrap_err_with(|| format!("Invalid account labels file {}", path.display()))
+    }
+
+    /// Writes the labels to the keystore directory `dir`.
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(LABELS_FILE_NAME);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("Failed to write account labels file {}", path.display()))
+    }
+
+    /// Returns the name of the account with the label.
+    pub fn get(&self, label: &str) -> Option<&str> {
+        self.0.get(label).map(String::as_str)
+    }
+
+    /// Labels an account, returns the account previously labeled with it.
+    pub fn insert(&mut self, label: String, account: String) -> Option<String> {
+        self.0.insert(label, account)
+    }
+
+    /// Returns the labels of an account.
+    pub fn labels_of<'a>(&'a self, account: &'a str) -> impl Iterator<Item = &'a str> {
+        self.0.iter().filter(move |(_, name)| *name == account).map(|(label, _)| label.as_str())
+    }
+
+    /// Removes the labels of an account, returns `true` if it had any.
+    pub fn remove_account(&mut self, account: &str) -> bool {
+        let len = self.0.len();
+        self.0.retain(|_, name| name != account);
+        self.0.len() != len
+    }
+}
+
+/// Returns the path of an account in the keystore directory `dir`, by its file name or label.
+pub fn account_path(dir: &Path, name: &str) -> Result<PathBuf> {
+    let path = dir.join(name);
+    if path.exists() {
+        return Ok(path);
+    }
+    Ok(AccountLabels::read(dir)?.get(name).map(|account| dir.join(account)).unwrap_or(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn watch_only_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let account = WatchOnlyAccount {
+            address: address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"),
+            wallet: HardwareWallet::Ledger,
+            hd_path: Some("m/44'/60'/1'/0/0".to_string()),
+            mnemonic_index: 0,
+        };
+        let path = dir.path().join("cold");
+        account.write(&path).unwrap();
+        assert_eq!(WatchOnlyAccount::read(&path).unwrap(), Some(account));
+
+        let keystore = Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../cast/tests/fixtures/keystore/UTC--2022-12-20T10-30-43.591916000Z--ec554aeafe75601aaab43bd4621a22284db566c2"
+        ));
+        assert_eq!(WatchOnlyAccount::read(keystore).unwrap(), None);
+        assert_eq!(WatchOnlyAccount::read(&dir.path().join("missing")).unwrap(), None);
+    }
+
+    #[test]
+    fn resolves_labels() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("deployer"), "{}").unwrap();
+
+        let mut labels = AccountLabels::read(dir.path()).unwrap();
+        assert_eq!(labels.insert("prod".to_string(), "deployer".to_string()), None);
+        labels.write(dir.path()).unwrap();
+
+        assert_eq!(account_path(dir.path(), "prod").unwrap(), dir.path().join("deployer"));
+        assert_eq!(account_path(dir.path(), "deployer").unwrap(), dir.path().join("deployer"));
+        assert_eq!(account_path(dir.path(), "other").unwrap(), dir.path().join("other"));
+
+        let mut labels = AccountLabels::read(dir.path()).unwrap();
+        assert_eq!(labels.labels_of("deployer").collect::<Vec<_>>(), ["prod"]);
+        assert!(labels.remove_account("deployer"));
+        assert_eq!(labels.get("prod"), None);
+    }
+}
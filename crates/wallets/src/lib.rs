@@ -9,6 +9,7 @@
 extern crate tracing;
 
 pub mod error;
+pub mod keystore;
 pub mod multi_wallet;
 pub mod raw_wallet;
 pub mod utils;
@@ -1,4 +1,5 @@
 use crate::{
+    keystore::{self, WatchOnlyAccount},
     utils,
     wallet_signer::{PendingSigner, WalletSigner},
 };
@@ -173,7 +174,8 @@ pub struct MultiWalletOpts {
     #[builder(default = "None")]
     pub keystore_paths: Option<Vec<String>>,
 
-    /// Use a keystore from the default keystores folder (~/.foundry/keystores) by its filename.
+    /// Use a keystore from the default keystores folder (~/.foundry/keystores) by its filename
+    /// or label.
     #[arg(
         long = "account",
         visible_alias = "accounts",
@@ -242,6 +244,9 @@ pub async fn get_multi_wallet(&self) -> Result<MultiWallet> {
             pending.extend(pending_keystores);
             signers.extend(unlocked);
         }
+        if let Some(watch_only) = self.watch_only_signers().await? {
+            signers.extend(watch_only);
+        }
         if let Some(pks) = self.private_keys()? {
             signers.extend(pks);
         }
@@ -289,8 +294,8 @@ fn keystore_paths(&self) -> Result<Option<Vec<PathBuf>>> {
             return Ok(Some(
                 keystore_account_names
                     .iter()
-                    .map(|keystore_name| default_keystore_dir.join(keystore_name))
-                    .collect(),
+                    .map(|name| keystore::account_path(&default_keystore_dir, name))
+                    .collect::<Result<_>>()?,
             ));
         }
         Ok(None)
@@ -311,10 +316,16 @@ pub fn keystores(&self) -> Result<Option<(Vec<PendingSigner>, Vec<WalletSigner>)
                 self.keystore_password_files.clone().unwrap_or_default().into_iter();
 
             for path in &keystore_paths {
+                let password = passwords_iter.next();
+                let password_file = password_files_iter.next();
+                // watch-only accounts sign with the device, see `watch_only_signers`
+                if WatchOnlyAccount::read(path)?.is_some() {
+                    continue;
+                }
                 let (maybe_signer, maybe_pending) = utils::create_keystore_signer(
                     path,
-                    passwords_iter.next().as_deref(),
-                    password_files_iter.next().as_deref(),
+                    password.as_deref(),
+                    password_file.as_deref(),
                 )?;
                 if let Some(pending_signer) = maybe_pending {
                     pending.push(pending_signer);
@@ -327,6 +338,23 @@ pub fn keystores(&self) -> Result<Option<(Vec<PendingSigner>, Vec<WalletSigner>)
         Ok(None)
     }
 
+    /// Returns the signers of the watch-only hardware wallet accounts of the provided keystores
+    /// arguments.
+    ///
+    /// Returns `Ok(None)` if no keystore provided.
+    pub async fn watch_only_signers(&self) -> Result<Option<Vec<WalletSigner>>> {
+        if let Some(keystore_paths) = self.keystore_paths()? {
+            let mut signers = Vec::new();
+            for path in &keystore_paths {
+                if let Some(account) = WatchOnlyAccount::read(path)? {
+                    signers.push(account.signer().await?);
+                }
+            }
+            return Ok(Some(signers));
+        }
+        Ok(None)
+    }
+
     pub fn mnemonics(&self) -> Result<Option<Vec<WalletSigner>>> {
         if let Some(ref mnemonics) = self.mnemonics {
             let mut wallets = vec![];
@@ -1,10 +1,10 @@
-use crate::{error::PrivateKeyError, PendingSigner, WalletSigner};
+use crate::{error::PrivateKeyError, keystore, PendingSigner, WalletSigner};
 use alloy_primitives::{hex::FromHex, B256};
 use alloy_signer_ledger::HDPath as LedgerHDPath;
 use alloy_signer_local::PrivateKeySigner;
 use alloy_signer_trezor::HDPath as TrezorHDPath;
 use eyre::{Context, Result};
-use foundry_config::Config;
+use foundry_config::{Config, WalletConfig};
 use std::{
     fs,
     path::{Path, PathBuf},
@@ -86,15 +86,18 @@ pub async fn create_trezor_signer(
     })
 }
 
+/// Returns the keystore path, or the path of the account in the default keystore directory by its
+/// file name or label.
 pub fn maybe_get_keystore_path(
     maybe_path: Option<&str>,
     maybe_name: Option<&str>,
 ) -> Result<Option<PathBuf>> {
     let default_keystore_dir = Config::foundry_keystores_dir()
         .ok_or_else(|| eyre::eyre!("Could not find the default keystore directory."))?;
-    Ok(maybe_path
-        .map(PathBuf::from)
-        .or_else(|| maybe_name.map(|name| default_keystore_dir.join(name))))
+    if let Some(path) = maybe_path {
+        return Ok(Some(PathBuf::from(path)));
+    }
+    maybe_name.map(|name| keystore::account_path(&default_keystore_dir, name)).transpose()
 }
 
 /// Creates keystore signer from given parameters.
@@ -119,6 +122,13 @@ pub fn create_keystore_signer(
         )
     }
 
+    if let Some(account) = keystore::WatchOnlyAccount::read(path)? {
+        eyre::bail!(
+            "Keystore file `{path:?}` is a watch-only {} account and can't be decrypted",
+            account.wallet
+        )
+    }
+
     let password = match (maybe_password, maybe_password_file) {
         (Some(password), _) => Ok(Some(password.to_string())),
         (_, Some(password_file)) => {
@@ -148,6 +158,32 @@ pub fn create_keystore_signer(
     }
 }
 
+/// Creates the [WalletSigner] configured for an address in the `[wallets]` table of the config.
+///
+/// The password of a keystore without a password file is prompted for.
+pub async fn create_config_signer(wallet: &WalletConfig) -> Result<WalletSigner> {
+    let mnemonic_index = wallet.mnemonic_index.unwrap_or(0);
+    if wallet.ledger {
+        return create_ledger_signer(wallet.hd_path.as_deref(), mnemonic_index).await;
+    }
+    if wallet.trezor {
+        return create_trezor_signer(wallet.hd_path.as_deref(), mnemonic_index).await;
+    }
+
+    let path = maybe_get_keystore_path(wallet.keystore.as_deref(), wallet.account.as_deref())?
+        .ok_or_else(|| {
+            eyre::eyre!("A wallet must set one of `ledger`, `trezor`, `keystore` or `account`")
+        })?;
+    if let Some(account) = keystore::WatchOnlyAccount::read(&path)? {
+        return account.signer().await;
+    }
+    match create_keystore_signer(&path, None, wallet.password_file.as_deref())? {
+        (Some(signer), _) => Ok(signer),
+        (_, Some(pending)) => Ok(pending.unlock()?),
+        (None, None) => unreachable!("keystore signer is either unlocked or pending"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,6 @@
-use crate::{raw_wallet::RawWalletOpts, utils, wallet_signer::WalletSigner};
+use crate::{
+    keystore::WatchOnlyAccount, raw_wallet::RawWalletOpts, utils, wallet_signer::WalletSigner,
+};
 use alloy_primitives::Address;
 use clap::Parser;
 use eyre::Result;
@@ -37,6 +39,9 @@ pub struct WalletOpts {
     pub keystore_path: Option<String>,
 
     /// Use a keystore from the default keystores folder (~/.foundry/keystores) by its filename
+    /// or label.
+    ///
+    /// Watch-only Ledger and Trezor accounts sign with the device.
     #[arg(
         long = "account",
         help_heading = "Wallet options - keystore",
@@ -112,6 +117,9 @@ pub async fn signer(&self) -> Result<WalletSigner> {
             self.keystore_path.as_deref(),
             self.keystore_account_name.as_deref(),
         )? {
+            if let Some(account) = WatchOnlyAccount::read(&path)? {
+                return account.signer().await;
+            }
             let (maybe_signer, maybe_pending) = utils::create_keystore_signer(
                 &path,
                 self.keystore_password.as_deref(),
@@ -26,6 +26,7 @@ interface Vm {
     struct SignedDelegation { uint8 v; bytes32 r; bytes32 s; uint64 nonce; address implementation; }
     struct PotentialRevert { address reverter; bool partialMatch; bytes revertData; }
     struct AccessListItem { address target; bytes32[] storageKeys; }
+    struct Simulation { bool success; uint64 gasUsed; bytes returnData; Log[] logs; AccessListItem[] accessList; }
     function _expectCheatcodeRevert() external;
     function _expectCheatcodeRevert(bytes4 revertData) external;
     function _expectCheatcodeRevert(bytes calldata revertData) external;
@@ -33,6 +34,8 @@ interface Vm {
     function accesses(address target) external returns (bytes32[] memory readSlots, bytes32[] memory writeSlots);
     function activeFork() external view returns (uint256 forkId);
     function addr(uint256 privateKey) external pure returns (address keyAddr);
+    function aggregateBlsPublicKeys(bytes[] calldata publicKeys) external pure returns (bytes memory publicKey);
+    function aggregateBlsSignatures(bytes[] calldata signatures) external pure returns (bytes memory signature);
     function allowCheatcodes(address account) external;
     function assertApproxEqAbsDecimal(uint256 left, uint256 right, uint256 maxDelta, uint256 decimals) external pure;
     function assertApproxEqAbsDecimal(uint256 left, uint256 right, uint256 maxDelta, uint256 decimals, string calldata error) external pure;
@@ -116,6 +119,8 @@ interface Vm {
     function assertLt(uint256 left, uint256 right, string calldata error) external pure;
     function assertLt(int256 left, int256 right) external pure;
     function assertLt(int256 left, int256 right, string calldata error) external pure;
+    function assertMatchesSnapshot(string calldata name, bytes calldata value) external;
+    function assertMatchesSnapshot(string calldata name, string calldata value) external;
     function assertNotEqDecimal(uint256 left, uint256 right, uint256 decimals) external pure;
     function assertNotEqDecimal(uint256 left, uint256 right, uint256 decimals, string calldata error) external pure;
     function assertNotEqDecimal(int256 left, int256 right, uint256 decimals) external pure;
@@ -238,6 +243,7 @@ interface Vm {
     function etch(address target, bytes calldata newRuntimeBytecode) external;
     function eth_getLogs(uint256 fromBlock, uint256 toBlock, address target, bytes32[] calldata topics) external returns (EthGetLogs[] memory logs);
     function exists(string calldata path) external view returns (bool result);
+    function expectCallCount(address callee, bytes4 selector, uint64 count) external;
     function expectCallMinGas(address callee, uint256 msgValue, uint64 minGas, bytes calldata data) external;
     function expectCallMinGas(address callee, uint256 msgValue, uint64 minGas, bytes calldata data, uint64 count) external;
     function expectCall(address callee, bytes calldata data) external;
@@ -252,6 +258,9 @@ interface Vm {
     function expectEmitAnonymous(bool checkTopic0, bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData, address emitter) external;
     function expectEmitAnonymous() external;
     function expectEmitAnonymous(address emitter) external;
+    function expectEmitCount(uint64 count) external;
+    function expectEmitMasked(bool checkTopic1, bool checkTopic2, bool checkTopic3, bytes calldata dataMask) external;
+    function expectEmitMasked(bool checkTopic1, bool checkTopic2, bool checkTopic3, bytes calldata dataMask, address emitter) external;
     function expectEmit(bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData) external;
     function expectEmit(bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData, address emitter) external;
     function expectEmit() external;
@@ -260,6 +269,7 @@ interface Vm {
     function expectEmit(bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData, address emitter, uint64 count) external;
     function expectEmit(uint64 count) external;
     function expectEmit(address emitter, uint64 count) external;
+    function expectGasLt(uint256 gas) external;
     function expectPartialRevert(bytes4 revertData) external;
     function expectPartialRevert(bytes4 revertData, address reverter) external;
     function expectRevert() external;
@@ -281,6 +291,7 @@ interface Vm {
     function foundryVersionAtLeast(string calldata version) external view returns (bool);
     function foundryVersionCmp(string calldata version) external view returns (int256);
     function fsMetadata(string calldata path) external view returns (FsMetadata memory metadata);
+    function gasUsedSince(string calldata label) external view returns (uint256 gasUsed);
     function getArtifactPathByCode(bytes calldata code) external view returns (string memory path);
     function getArtifactPathByDeployedCode(bytes calldata deployedCode) external view returns (string memory path);
     function getBlobBaseFee() external view returns (uint256 blobBaseFee);
@@ -303,6 +314,7 @@ interface Vm {
     function getNonce(address account) external view returns (uint64 nonce);
     function getNonce(Wallet calldata wallet) external returns (uint64 nonce);
     function getRecordedLogs() external returns (Log[] memory logs);
+    function getRecordedLogsFrom(address emitter) external returns (Log[] memory logs);
     function getStateDiff() external view returns (string memory diff);
     function getStateDiffJson() external view returns (string memory diff);
     function getWallets() external returns (address[] memory wallets);
@@ -322,6 +334,10 @@ interface Vm {
     function makePersistent(address account0, address account1) external;
     function makePersistent(address account0, address account1, address account2) external;
     function makePersistent(address[] calldata accounts) external;
+    function markGas(string calldata label) external;
+    function mockCallCount(address callee, bytes calldata data) external view returns (uint256 count);
+    function mockCallMasked(address callee, bytes calldata data, bytes calldata mask, bytes calldata returnData) external;
+    function mockCallRevertMasked(address callee, bytes calldata data, bytes calldata mask, bytes calldata revertData) external;
     function mockCallRevert(address callee, bytes calldata data, bytes calldata revertData) external;
     function mockCallRevert(address callee, uint256 msgValue, bytes calldata data, bytes calldata revertData) external;
     function mockCallRevert(address callee, bytes4 data, bytes calldata revertData) external;
@@ -333,11 +349,21 @@ interface Vm {
     function mockCalls(address callee, bytes calldata data, bytes[] calldata returnData) external;
     function mockCalls(address callee, uint256 msgValue, bytes calldata data, bytes[] calldata returnData) external;
     function mockFunction(address callee, address target, bytes calldata data) external;
+    function mockFunction(address callee, address target, bytes4 selector) external;
     function noAccessList() external;
     function parseAddress(string calldata stringifiedValue) external pure returns (address parsedValue);
     function parseBool(string calldata stringifiedValue) external pure returns (bool parsedValue);
     function parseBytes(string calldata stringifiedValue) external pure returns (bytes memory parsedValue);
     function parseBytes32(string calldata stringifiedValue) external pure returns (bytes32 parsedValue);
+    function parseCsv(string calldata csv) external pure returns (string[][] memory rows);
+    function parseCsvAddress(string calldata csv, string calldata column) external pure returns (address[] memory);
+    function parseCsvBool(string calldata csv, string calldata column) external pure returns (bool[] memory);
+    function parseCsvBytes(string calldata csv, string calldata column) external pure returns (bytes[] memory);
+    function parseCsvBytes32(string calldata csv, string calldata column) external pure returns (bytes32[] memory);
+    function parseCsvHeader(string calldata csv) external pure returns (string[] memory columns);
+    function parseCsvInt(string calldata csv, string calldata column) external pure returns (int256[] memory);
+    function parseCsvString(string calldata csv, string calldata column) external pure returns (string[] memory);
+    function parseCsvUint(string calldata csv, string calldata column) external pure returns (uint256[] memory);
     function parseInt(string calldata stringifiedValue) external pure returns (int256 parsedValue);
     function parseJsonAddress(string calldata json, string calldata key) external pure returns (address);
     function parseJsonAddressArray(string calldata json, string calldata key) external pure returns (address[] memory);
@@ -394,6 +420,7 @@ interface Vm {
     function promptSecret(string calldata promptText) external returns (string memory input);
     function promptSecretUint(string calldata promptText) external returns (uint256);
     function promptUint(string calldata promptText) external returns (uint256);
+    function publicKeyBls(uint256 privateKey) external pure returns (bytes memory publicKey);
     function publicKeyP256(uint256 privateKey) external pure returns (uint256 publicKeyX, uint256 publicKeyY);
     function randomAddress() external returns (address);
     function randomBool() external view returns (bool);
@@ -406,6 +433,7 @@ interface Vm {
     function randomUint(uint256 min, uint256 max) external returns (uint256);
     function randomUint(uint256 bits) external view returns (uint256);
     function readCallers() external returns (CallerMode callerMode, address msgSender, address txOrigin);
+    function readCsv(string calldata path) external view returns (string[][] memory rows);
     function readDir(string calldata path) external view returns (DirEntry[] memory entries);
     function readDir(string calldata path, uint64 maxDepth) external view returns (DirEntry[] memory entries);
     function readDir(string calldata path, uint64 maxDepth, bool followLinks) external view returns (DirEntry[] memory entries);
@@ -415,6 +443,7 @@ interface Vm {
     function readLink(string calldata linkPath) external view returns (string memory targetPath);
     function record() external;
     function recordLogs() external;
+    function relayLogs(uint256 forkId, address emitter, bytes32 topic0, address target, bytes4 selector) external returns (uint256 relayed);
     function rememberKey(uint256 privateKey) external returns (address keyAddr);
     function rememberKeys(string calldata mnemonic, string calldata derivationPath, uint32 count) external returns (address[] memory keyAddrs);
     function rememberKeys(string calldata mnemonic, string calldata derivationPath, string calldata language, uint32 count) external returns (address[] memory keyAddrs);
@@ -436,6 +465,8 @@ interface Vm {
     function rollFork(bytes32 txHash) external;
     function rollFork(uint256 forkId, uint256 blockNumber) external;
     function rollFork(uint256 forkId, bytes32 txHash) external;
+    function rpcBatch(string[] calldata methods, string[] calldata params) external returns (bytes[] memory data);
+    function rpcBatch(string calldata urlOrAlias, string[] calldata methods, string[] calldata params) external returns (bytes[] memory data);
     function rpcUrl(string calldata rpcAlias) external view returns (string memory json);
     function rpcUrlStructs() external view returns (Rpc[] memory urls);
     function rpcUrls() external view returns (string[2][] memory urls);
@@ -467,6 +498,7 @@ interface Vm {
     function setNonceUnsafe(address account, uint64 newNonce) external;
     function shuffle(uint256[] calldata array) external returns (uint256[] memory);
     function signAndAttachDelegation(address implementation, uint256 privateKey) external returns (SignedDelegation memory signedDelegation);
+    function signBls(uint256 privateKey, bytes calldata message) external pure returns (bytes memory signature);
     function signCompact(Wallet calldata wallet, bytes32 digest) external returns (bytes32 r, bytes32 vs);
     function signCompact(uint256 privateKey, bytes32 digest) external pure returns (bytes32 r, bytes32 vs);
     function signCompact(bytes32 digest) external pure returns (bytes32 r, bytes32 vs);
@@ -477,10 +509,12 @@ interface Vm {
     function sign(uint256 privateKey, bytes32 digest) external pure returns (uint8 v, bytes32 r, bytes32 s);
     function sign(bytes32 digest) external pure returns (uint8 v, bytes32 r, bytes32 s);
     function sign(address signer, bytes32 digest) external pure returns (uint8 v, bytes32 r, bytes32 s);
+    function simulate(address from, address to, uint256 value, bytes calldata data) external returns (Simulation memory simulation);
     function skip(bool skipTest) external;
     function skip(bool skipTest, string calldata reason) external;
     function sleep(uint256 duration) external;
     function snapshot() external returns (uint256 snapshotId);
+    function snapshotGas(string calldata name) external returns (uint256 gasUsed);
     function snapshotGasLastCall(string calldata name) external returns (uint256 gasUsed);
     function snapshotGasLastCall(string calldata group, string calldata name) external returns (uint256 gasUsed);
     function snapshotState() external returns (uint256 snapshotId);
@@ -510,6 +544,7 @@ interface Vm {
     function stopSnapshotGas(string calldata name) external returns (uint256 gasUsed);
     function stopSnapshotGas(string calldata group, string calldata name) external returns (uint256 gasUsed);
     function store(address target, bytes32 slot, bytes32 value) external;
+    function syncForkTimestamps() external;
     function toBase64URL(bytes calldata data) external pure returns (string memory);
     function toBase64URL(string calldata data) external pure returns (string memory);
     function toBase64(bytes calldata data) external pure returns (string memory);
@@ -528,8 +563,10 @@ interface Vm {
     function tryFfi(string[] calldata commandInput) external returns (FfiResult memory result);
     function txGasPrice(uint256 newGasPrice) external;
     function unixTime() external view returns (uint256 milliseconds);
+    function verifyBls(bytes calldata publicKey, bytes calldata message, bytes calldata signature) external pure returns (bool);
     function warmSlot(address target, bytes32 slot) external;
     function warp(uint256 newTimestamp) external;
+    function writeCsv(string calldata path, string[] calldata header, string[][] calldata rows) external;
     function writeFile(string calldata path, string calldata data) external;
     function writeFileBinary(string calldata path, bytes calldata data) external;
     function writeJson(string calldata json, string calldata path) external;
@@ -431,6 +431,28 @@ contract MultiChainBroadcastNoLink is DSTest {
         vm.broadcast(address(ACCOUNT_B));
         new NoLink();
     }
+
+    function deployAndRegister(string memory sforkA, string memory sforkB) public {
+        uint256 forkB = vm.createSelectFork(sforkB);
+        vm.broadcast(address(ACCOUNT_A));
+        MultiChainRegistry registry = new MultiChainRegistry();
+
+        vm.createSelectFork(sforkA);
+        vm.broadcast(address(ACCOUNT_A));
+        NoLink noLink = new NoLink();
+
+        vm.selectFork(forkB);
+        vm.broadcast(address(ACCOUNT_A));
+        registry.register(address(noLink));
+    }
+}
+
+contract MultiChainRegistry {
+    address[] public deployments;
+
+    function register(address deployment) public {
+        deployments.push(deployment);
+    }
 }
 
 contract MultiChainBroadcastLink is DSTest {
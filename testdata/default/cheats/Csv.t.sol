@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+import "cheats/Vm.sol";
+
+contract CsvTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    string csv;
+
+    function setUp() public {
+        csv = vm.readFile("fixtures/Csv/airdrop.csv");
+    }
+
+    function test_readCsv() public {
+        string[][] memory rows = vm.readCsv("fixtures/Csv/airdrop.csv");
+        assertEq(rows.length, 2);
+        assertEq(rows[0].length, 6);
+        assertEq(rows[0][1], "100");
+        assertEq(rows[0][4], "first, second");
+        assertEq(rows[1][4], 'say "hi"');
+    }
+
+    function test_parseCsvHeader() public {
+        string[] memory columns = vm.parseCsvHeader(csv);
+        assertEq(columns.length, 6);
+        assertEq(columns[0], "account");
+        assertEq(columns[5], "root");
+    }
+
+    function test_parseCsvColumns() public {
+        address[] memory accounts = vm.parseCsvAddress(csv, "account");
+        assertEq(accounts[0], address(0xdEaD));
+        assertEq(accounts[1], address(1));
+
+        uint256[] memory amounts = vm.parseCsvUint(csv, "amount");
+        assertEq(amounts[0], 100);
+        assertEq(amounts[1], 16);
+
+        int256[] memory deltas = vm.parseCsvInt(csv, "delta");
+        assertEq(deltas[0], -5);
+        assertEq(deltas[1], 7);
+
+        bool[] memory claimed = vm.parseCsvBool(csv, "claimed");
+        assertTrue(claimed[0]);
+        assertTrue(!claimed[1]);
+
+        string[] memory memos = vm.parseCsvString(csv, "memo");
+        assertEq(memos[0], "first, second");
+
+        bytes32[] memory roots = vm.parseCsvBytes32(csv, "root");
+        assertEq(roots[1], bytes32(uint256(2)));
+
+        bytes[] memory rootBytes = vm.parseCsvBytes(csv, "root");
+        assertEq(rootBytes[0].length, 32);
+    }
+
+    function test_parseCsvMissingColumn() public {
+        vm._expectCheatcodeRevert('column "missing" not found in CSV header');
+        vm.parseCsvUint(csv, "missing");
+    }
+
+    function test_parseCsvMismatchedRow() public {
+        vm._expectCheatcodeRevert("CSV row 1 has 1 fields, expected 2");
+        vm.parseCsv("a,b\n1\n");
+    }
+
+    function test_writeCsv() public {
+        string memory path = "fixtures/Csv/write_test.csv";
+        string[] memory header = new string[](2);
+        header[0] = "name";
+        header[1] = "value";
+        string[][] memory rows = new string[][](2);
+        rows[0] = new string[](2);
+        rows[0][0] = "alice";
+        rows[0][1] = "1";
+        rows[1] = new string[](2);
+        rows[1][0] = 'bob, "jr"';
+        rows[1][1] = "2";
+        vm.writeCsv(path, header, rows);
+
+        assertEq(vm.readFile(path), 'name,value\nalice,1\n"bob, ""jr""",2\n');
+        string[][] memory read = vm.readCsv(path);
+        assertEq(read[1][0], 'bob, "jr"');
+        assertEq(vm.parseCsvUint(vm.readFile(path), "value")[1], 2);
+    }
+}
@@ -330,6 +330,19 @@ contract ExpectCallCountTest is DSTest {
         vm.expectCallMinGas(address(inner), 0, 50_001, abi.encodeWithSelector(inner.add.selector, 1, 1), 0);
         this.exposed_addHardGasLimit(target, 1);
     }
+
+    function testExpectCallCountBySelector() public {
+        Contract target = new Contract();
+        vm.expectCallCount(address(target), Contract.add.selector, 3);
+        this.exposed_callTargetNTimes(target, 1, 2, 2);
+        target.add(3, 4);
+    }
+
+    function exposed_callTargetNTimes(Contract target, uint256 a, uint256 b, uint256 times) public {
+        for (uint256 i = 0; i < times; i++) {
+            target.add(a, b);
+        }
+    }
 }
 
 contract ExpectCallMixedTest is DSTest {
@@ -459,4 +459,48 @@ contract ExpectEmitCountTest is DSTest {
         emit Something(1, 2, 3, 4);
         emitter.emitNEvents(1, 2, 3, 4, count);
     }
+
+    function testExpectEmitCount() public {
+        vm.expectEmit(true, false, true, true);
+        vm.expectEmitCount(3);
+        emit Something(1, 0, 3, 4);
+        emitter.emitNEvents(1, 2, 3, 4, 3);
+    }
+
+    function testExpectEmitMaskedCount() public {
+        vm.expectEmitMasked(true, true, true, new bytes(32), address(emitter));
+        vm.expectEmitCount(2);
+        emit Something(1, 2, 3, 0);
+        emitter.emitNEvents(1, 2, 3, 4, 2);
+    }
+}
+
+contract ExpectEmitMaskedTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+    Emitter emitter;
+
+    event Something(uint256 indexed topic1, uint256 indexed topic2, uint256 indexed topic3, uint256 data);
+
+    function setUp() public {
+        emitter = new Emitter();
+    }
+
+    function testExpectEmitMaskedData() public {
+        // Only the high 16 bytes of the data are checked.
+        bytes memory mask = abi.encodePacked(bytes16(type(uint128).max), bytes16(0));
+        vm.expectEmitMasked(true, true, true, mask);
+        emit Something(1, 2, 3, 100);
+        emitter.emitEvent(1, 2, 3, 200);
+    }
+
+    function testExpectEmitMaskedEmitter() public {
+        vm.expectEmitMasked(true, false, true, new bytes(32), address(emitter));
+        emit Something(1, 0, 3, 0);
+        emitter.emitEvent(1, 2, 3, 4);
+    }
+
+    function testExpectEmitCountWithoutExpectEmit() public {
+        vm._expectCheatcodeRevert("no pending `expectEmit`");
+        vm.expectEmitCount(2);
+    }
 }
@@ -235,6 +235,75 @@ contract ForkTest is DSTest {
         assertGt(decodedResult, 20_000_000);
     }
 
+    function testRpcBatch() public {
+        vm.selectFork(mainnetFork);
+        string[] memory methods = new string[](2);
+        string[] memory params = new string[](2);
+        methods[0] = "eth_getBalance";
+        params[0] = vm.readFile("fixtures/Rpc/balance_params.json");
+        methods[1] = "eth_chainId";
+        params[1] = "[]";
+
+        bytes[] memory results = vm.rpcBatch(methods, params);
+        assertEq(results.length, 2);
+        assertEq(hex"10b7c11bcb51e6", results[0]);
+        assertEq(vm.parseUint(vm.toString(results[1])), 1);
+    }
+
+    function testRpcBatchWithUrl() public {
+        string[] memory methods = new string[](2);
+        string[] memory params = new string[](2);
+        methods[0] = "eth_blockNumber";
+        params[0] = "[]";
+        methods[1] = "eth_getStorageAt";
+        params[1] = '["0x6B175474E89094C44Da98b954EedeAC495271d0F","0x0","latest"]';
+
+        bytes[] memory results = vm.rpcBatch("mainnet", methods, params);
+        assertGt(vm.parseUint(vm.toString(results[0])), 20_000_000);
+        assertEq(results[1].length, 32);
+    }
+
+    function testRelayLogs() public {
+        vm.selectFork(optimismFork);
+        MessageReceiver receiver = new MessageReceiver();
+
+        vm.selectFork(mainnetFork);
+        MessageSender sender = new MessageSender();
+        vm.recordLogs();
+        sender.send(address(0xbeef), 1 ether);
+        sender.send(address(0xcafe), 2 ether);
+
+        uint256 relayed = vm.relayLogs(
+            optimismFork,
+            address(sender),
+            MessageSender.MessageSent.selector,
+            address(receiver),
+            MessageReceiver.receiveMessage.selector
+        );
+        assertEq(relayed, 2);
+        assertEq(vm.activeFork(), optimismFork);
+        assertEq(receiver.balances(address(0xbeef)), 1 ether);
+        assertEq(receiver.balances(address(0xcafe)), 2 ether);
+
+        // Relayed logs are consumed.
+        assertEq(vm.getRecordedLogs().length, 0);
+    }
+
+    function testRelayLogsRequiresRecording() public {
+        vm._expectCheatcodeRevert("logs are not being recorded; call `recordLogs` before emitting the logs to relay");
+        vm.relayLogs(optimismFork, address(this), bytes32(0), address(this), bytes4(0));
+    }
+
+    function testSyncForkTimestamps() public {
+        vm.selectFork(mainnetFork);
+        uint256 timestamp = block.timestamp + 1 days;
+        vm.warp(timestamp);
+        vm.syncForkTimestamps();
+
+        vm.selectFork(optimismFork);
+        assertEq(block.timestamp, timestamp);
+    }
+
     // <https://github.com/foundry-rs/foundry/issues/7858>
     function testRpcTransactionByHash() public {
         string memory param = string.concat('["0xe1a0fba63292976050b2fbf4379a1901691355ed138784b4e0d1854b4cf9193e"]');
@@ -253,3 +322,19 @@ contract DummyContract {
         val = _val;
     }
 }
+
+contract MessageSender {
+    event MessageSent(address to, uint256 amount);
+
+    function send(address to, uint256 amount) public {
+        emit MessageSent(to, amount);
+    }
+}
+
+contract MessageReceiver {
+    mapping(address => uint256) public balances;
+
+    function receiveMessage(address to, uint256 amount) public {
+        balances[to] += amount;
+    }
+}
@@ -181,6 +181,35 @@ contract MockCallTest is DSTest {
         assertEq(mock.add(1, 2), 10);
         mock.noReturnValue();
     }
+
+    function testMockCallMasked() public {
+        Mock mock = new Mock();
+
+        // matches `add(_, 2)`
+        bytes memory data = abi.encodeWithSelector(mock.add.selector, 0, 2);
+        bytes memory mask = abi.encodePacked(bytes4(0xffffffff), bytes32(0), bytes32(type(uint256).max));
+        vm.mockCallMasked(address(mock), data, mask, abi.encode(10));
+
+        assertEq(mock.add(1, 2), 10);
+        assertEq(mock.add(5, 2), 10);
+        assertEq(mock.add(1, 3), 4);
+    }
+
+    function testMockCallCount() public {
+        Mock mock = new Mock();
+
+        bytes memory data = abi.encodeWithSelector(mock.add.selector);
+        vm.mockCall(address(mock), data, abi.encode(10));
+        assertEq(vm.mockCallCount(address(mock), data), 0);
+
+        mock.add(1, 2);
+        mock.add(3, 4);
+        mock.numberA();
+        assertEq(vm.mockCallCount(address(mock), data), 2);
+
+        vm.clearMockedCalls();
+        assertEq(vm.mockCallCount(address(mock), data), 0);
+    }
 }
 
 contract MockCallRevertTest is DSTest {
@@ -344,4 +373,20 @@ contract MockCallRevertTest is DSTest {
             require(keccak256(err) == keccak256(ERROR_MESSAGE));
         }
     }
+
+    function testMockCallRevertMasked() public {
+        Mock mock = new Mock();
+
+        // reverts `add(1, _)`
+        bytes memory data = abi.encodeWithSelector(mock.add.selector, 1, 0);
+        bytes memory mask = abi.encodePacked(bytes4(0xffffffff), bytes32(type(uint256).max), bytes32(0));
+        vm.mockCallRevertMasked(address(mock), data, mask, ERROR_MESSAGE);
+
+        try mock.add(1, 7) {
+            revert();
+        } catch (bytes memory err) {
+            require(keccak256(err) == keccak256(ERROR_MESSAGE));
+        }
+        assertEq(mock.add(2, 7), 9);
+    }
 }
@@ -71,4 +71,14 @@ contract MockFunctionTest is DSTest {
         my_contract.mocked_args_function(789);
         assertEq(my_contract.a(), 123 + 789);
     }
+
+    function test_mock_function_selector() public {
+        vm.mockFunction(
+            address(my_contract), address(model_contract), MockFunctionContract.mocked_args_function.selector
+        );
+        my_contract.mocked_args_function(678);
+        assertEq(my_contract.a(), 123 + 678);
+        my_contract.mocked_function();
+        assertEq(my_contract.a(), 321);
+    }
 }
@@ -251,6 +251,45 @@ contract RecordAccountAccessesTest is DSTest {
         );
     }
 
+    /// @notice Test that nonce and code changes are included in the state diff
+    function testNonceAndCodeDiffs() public {
+        address target = address(0xdead);
+        cheats.startStateDiffRecording();
+
+        cheats.setNonce(target, 5);
+        cheats.etch(target, hex"00");
+
+        string memory diffs = cheats.getStateDiff();
+        assertEq(
+            "0x000000000000000000000000000000000000dEaD\n- nonce diff: 0 \xE2\x86\x92 5\n- code diff: 0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470 \xE2\x86\x92 0xbc36789e7a1e281436464229828f817d6612f7b477d66591ff96a9e064bcc98a\n\n",
+            diffs
+        );
+        string memory diffsJson = cheats.getStateDiffJson();
+        assertEq(
+            "{\"0x000000000000000000000000000000000000dead\":{\"label\":null,\"balanceDiff\":null,\"nonceDiff\":{\"previousValue\":0,\"newValue\":5},\"codeDiff\":{\"previousCodeHash\":\"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470\",\"newCodeHash\":\"0xbc36789e7a1e281436464229828f817d6612f7b477d66591ff96a9e064bcc98a\",\"newCode\":\"0x00\"},\"stateDiff\":{}}}",
+            diffsJson
+        );
+        cheats.stopAndReturnStateDiff();
+    }
+
+    /// @notice Test that nonce and code diffs are only reported for accounts changed with cheatcodes
+    function testNonceAndCodeDiffsOfChangedAccounts() public {
+        cheats.startStateDiffRecording();
+
+        // the creation changes the nonce of this contract and the code of the new one
+        new SelfCaller("");
+        // setting the current nonce is not a change
+        cheats.setNonceUnsafe(address(0xdead), 0);
+        cheats.setNonce(address(0xbeef), 1);
+
+        string memory diffsJson = cheats.getStateDiffJson();
+        assertEq(
+            "{\"0x000000000000000000000000000000000000beef\":{\"label\":null,\"balanceDiff\":null,\"nonceDiff\":{\"previousValue\":0,\"newValue\":1},\"stateDiff\":{}}}",
+            diffsJson
+        );
+        cheats.stopAndReturnStateDiff();
+    }
+
     /// @notice Test normal, non-nested storage accesses
     function testStorageAccesses() public {
         StorageAccessor one = test1;
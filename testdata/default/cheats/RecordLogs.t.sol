@@ -190,6 +190,26 @@ contract RecordLogsTest is DSTest {
         assertEq(entries[2].emitter, emitter2.getEmitterAddr());
     }
 
+    function testGetRecordedLogsFrom() public {
+        Emitter other = new Emitter();
+        vm.recordLogs();
+
+        emitter.emitEvent(1, generateTestData(16));
+        other.emitEvent(2, generateTestData(16));
+        emitter.emitEvent(3, generateTestData(16));
+
+        Vm.Log[] memory entries = vm.getRecordedLogsFrom(address(emitter));
+        assertEq(entries.length, 2);
+        assertEq(entries[0].topics[1], bytes32(uint256(1)));
+        assertEq(entries[1].topics[1], bytes32(uint256(3)));
+        assertEq(entries[1].emitter, address(emitter));
+
+        // The logs of the other emitters are still recorded.
+        entries = vm.getRecordedLogs();
+        assertEq(entries.length, 1);
+        assertEq(entries[0].emitter, address(other));
+    }
+
     function testRecordsConsumednAsRead() public {
         Vm.Log[] memory entries;
 
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+import "cheats/Vm.sol";
+
+contract SignBlsTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testSignBls() public {
+        bytes memory message = "validator deposit";
+        bytes memory publicKey = vm.publicKeyBls(1234);
+        bytes memory signature = vm.signBls(1234, message);
+
+        assertEq(publicKey.length, 48);
+        assertEq(signature.length, 96);
+        assertTrue(vm.verifyBls(publicKey, message, signature));
+        assertTrue(!vm.verifyBls(publicKey, "another message", signature));
+        assertTrue(!vm.verifyBls(vm.publicKeyBls(5678), message, signature));
+    }
+
+    function testAggregateBls() public {
+        bytes memory message = "attestation";
+        bytes[] memory publicKeys = new bytes[](3);
+        bytes[] memory signatures = new bytes[](3);
+        for (uint256 i; i < 3; i++) {
+            publicKeys[i] = vm.publicKeyBls(i + 1);
+            signatures[i] = vm.signBls(i + 1, message);
+        }
+
+        bytes memory publicKey = vm.aggregateBlsPublicKeys(publicKeys);
+        bytes memory signature = vm.aggregateBlsSignatures(signatures);
+        assertEq(publicKey.length, 48);
+        assertEq(signature.length, 96);
+        assertTrue(vm.verifyBls(publicKey, message, signature));
+        assertTrue(!vm.verifyBls(publicKeys[0], message, signature));
+    }
+
+    function testSignBlsZeroKey() public {
+        vm._expectCheatcodeRevert("private key cannot be 0");
+        vm.signBls(0, "message");
+    }
+}
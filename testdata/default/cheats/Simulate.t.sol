@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+import "cheats/Vm.sol";
+
+contract Counter {
+    uint256 public count;
+
+    function increment() public returns (uint256) {
+        return ++count;
+    }
+}
+
+contract SimulateTarget {
+    event Stored(uint256 value);
+
+    uint256 public value;
+
+    function store(uint256 newValue, Counter counter) public returns (uint256) {
+        value = newValue;
+        emit Stored(newValue);
+        return counter.increment();
+    }
+
+    function fail() public pure {
+        revert("simulation failed");
+    }
+}
+
+contract SimulateTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    SimulateTarget target;
+    Counter counter;
+
+    function setUp() public {
+        target = new SimulateTarget();
+        counter = new Counter();
+    }
+
+    function testSimulate() public {
+        Vm.Simulation memory simulation = vm.simulate(
+            address(this), address(target), 0, abi.encodeCall(SimulateTarget.store, (42, counter))
+        );
+
+        assertTrue(simulation.success);
+        assertEq(abi.decode(simulation.returnData, (uint256)), 1);
+        assertGt(simulation.gasUsed, 0);
+
+        assertEq(simulation.logs.length, 1);
+        assertEq(simulation.logs[0].emitter, address(target));
+        assertEq(simulation.logs[0].topics[0], keccak256("Stored(uint256)"));
+        assertEq(abi.decode(simulation.logs[0].data, (uint256)), 42);
+
+        // The sender and the target are not part of the access list.
+        assertEq(simulation.accessList.length, 1);
+        assertEq(simulation.accessList[0].target, address(counter));
+        assertEq(simulation.accessList[0].storageKeys.length, 1);
+        assertEq(simulation.accessList[0].storageKeys[0], bytes32(0));
+
+        // No state changes are committed.
+        assertEq(target.value(), 0);
+        assertEq(counter.count(), 0);
+    }
+
+    function testSimulateIsRepeatable() public {
+        bytes memory data = abi.encodeCall(SimulateTarget.store, (42, counter));
+        Vm.Simulation memory first = vm.simulate(address(this), address(target), 0, data);
+        Vm.Simulation memory second = vm.simulate(address(this), address(target), 0, data);
+
+        assertEq(first.gasUsed, second.gasUsed);
+        assertEq(first.returnData, second.returnData);
+    }
+
+    function testSimulateRevert() public {
+        Vm.Simulation memory simulation =
+            vm.simulate(address(this), address(target), 0, abi.encodeCall(SimulateTarget.fail, ()));
+
+        assertTrue(!simulation.success);
+        assertEq(simulation.returnData, abi.encodeWithSignature("Error(string)", "simulation failed"));
+        assertEq(simulation.logs.length, 0);
+    }
+}
@@ -147,6 +147,39 @@ contract GasSnapshotTest is DSTest {
         uint256 gasUsed = vm.snapshotGasLastCall("CustomGroup", "testSnapshotGasLastCallGroupName");
         assertGt(gasUsed, 0);
     }
+
+    function testGasUsedSince() public {
+        vm.markGas("flare");
+        flare.run(1);
+        uint256 gasUsedOnce = vm.gasUsedSince("flare");
+        flare.run(1);
+        uint256 gasUsedTwice = vm.gasUsedSince("flare");
+
+        assertGt(gasUsedOnce, 0);
+        assertGt(gasUsedTwice, gasUsedOnce);
+    }
+
+    // Writes to `GasSnapshotTest` group with `testSnapshotGasMark` name.
+    function testSnapshotGasMark() public {
+        vm.markGas("testSnapshotGasMark");
+        flare.run(1);
+        uint256 gasUsed = vm.snapshotGas("testSnapshotGasMark");
+        assertGt(gasUsed, 0);
+
+        vm._expectCheatcodeRevert("no gas mark was set with the label: testSnapshotGasMark");
+        vm.gasUsedSince("testSnapshotGasMark");
+    }
+
+    function testExpectGasLt() public {
+        vm.expectGasLt(1_000_000);
+        flare.run(1);
+    }
+
+    function testExpectGasLtExceeded() public {
+        vm.expectGasLt(1_000);
+        (bool success,) = address(flare).call(abi.encodeCall(Flare.run, (1)));
+        assertFalse(success);
+    }
 }
 
 contract GasComparisonTest is DSTest {